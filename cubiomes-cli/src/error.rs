@@ -0,0 +1,53 @@
+//! Errors of the command line interface
+
+use cubiomes::{
+    generator::error::{ExportError, GeneratorError},
+    search::error::SearchError,
+};
+use thiserror::Error;
+
+/// An error while running a command
+#[derive(Error, Debug)]
+pub enum CliError {
+    /// Generating biomes failed
+    #[error("Failed to generate biomes: {0}")]
+    Generator(GeneratorError),
+    /// Exporting a map as text failed
+    #[error("{0}")]
+    Export(ExportError),
+    /// Loading or running a search failed
+    #[error("{0}")]
+    Search(SearchError),
+    /// Writing an output file failed
+    #[error("Failed to write {path}: {source}")]
+    Write {
+        /// The file which couldn't be written
+        path: String,
+        /// The underlying error
+        source: std::io::Error,
+    },
+    /// Saved search results don't match when checked again
+    #[error("{0} saved seeds don't match their saved results anymore")]
+    Unverified(usize),
+    /// The output file has an extension which is not a supported map format
+    #[error("Unsupported map format {0:?}, use png, ppm, csv or json")]
+    UnknownImageFormat(String),
+}
+
+impl From<GeneratorError> for CliError {
+    fn from(value: GeneratorError) -> Self {
+        Self::Generator(value)
+    }
+}
+
+impl From<ExportError> for CliError {
+    fn from(value: ExportError) -> Self {
+        Self::Export(value)
+    }
+}
+
+impl From<SearchError> for CliError {
+    fn from(value: SearchError) -> Self {
+        Self::Search(value)
+    }
+}
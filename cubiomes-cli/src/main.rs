@@ -0,0 +1,389 @@
+//! A command line front end for the cubiomes library
+//!
+//! Run `cubiomes help` for the available commands.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use cubiomes::{
+    enums::{Dimension, MCVersion, StructureType},
+    generator::{BlockPosition, Cache, Generator, GeneratorFlags, Range, Scale, YLevel},
+    render::{Map, RenderOptions},
+    search::{Criterion, Discrepancy, SearchArchive, SearchDefinition, SearchResults},
+};
+use error::CliError;
+use serde::de::{value::StrDeserializer, DeserializeOwned, IntoDeserializer};
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+mod error;
+
+#[cfg(test)]
+mod tests;
+
+/// Minecraft worldgen from the command line, backed by cubiomes
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Prints the biome at a block
+    #[command(allow_negative_numbers = true)]
+    BiomeAt {
+        #[command(flatten)]
+        world: WorldArgs,
+        /// The x coordinate of the block
+        x: i32,
+        /// The y of the block, a number or sea_level, surface or build_limit
+        y: YLevel,
+        /// The z coordinate of the block
+        z: i32,
+    },
+    /// Renders a biome map into an image, or exports it as text
+    ///
+    /// The format is picked from the extension of the output, either png or
+    /// ppm for images, or csv or json for the biomes as text. An output of -
+    /// prints the map with colored blocks to the terminal instead
+    #[command(allow_negative_numbers = true)]
+    Map {
+        #[command(flatten)]
+        world: WorldArgs,
+        /// The scale of the map, each pixel is one cell of the scale
+        #[arg(long, value_enum, default_value_t = MapScale::Quad)]
+        scale: MapScale,
+        /// The x coordinate of the upper left corner, in cells of the scale
+        #[arg(long, default_value_t = 0)]
+        x: i32,
+        /// The z coordinate of the upper left corner, in cells of the scale
+        #[arg(long, default_value_t = 0)]
+        z: i32,
+        /// The width of the map in cells
+        #[arg(long, default_value_t = 512)]
+        width: u32,
+        /// The height of the map in cells
+        #[arg(long, default_value_t = 512)]
+        height: u32,
+        /// The y to generate the biomes at, a number in blocks or sea_level,
+        /// surface or build_limit
+        #[arg(long, default_value_t = YLevel::Absolute(64))]
+        y: YLevel,
+        /// Adds a legend of the biomes on the map to png and ppm images
+        #[arg(long)]
+        legend: bool,
+        /// Draws a scale bar in blocks on png and ppm images
+        #[arg(long)]
+        scale_bar: bool,
+        /// The file to save the map to, or - for the terminal
+        output: PathBuf,
+    },
+    /// Finds the closest structure of a type
+    #[command(allow_negative_numbers = true)]
+    FindStructure {
+        #[command(flatten)]
+        world: WorldArgs,
+        /// The type of the structure, for example Village or Monument
+        #[arg(value_parser = parse_enum::<StructureType>)]
+        structure: StructureType,
+        /// The x coordinate to search around
+        #[arg(long, default_value_t = 0)]
+        x: i32,
+        /// The z coordinate to search around
+        #[arg(long, default_value_t = 0)]
+        z: i32,
+        /// The maximum distance to search in blocks
+        #[arg(long, default_value_t = 4096)]
+        radius: u32,
+        /// Lists every structure within the radius instead of the closest one
+        #[arg(long)]
+        all: bool,
+    },
+    /// Runs a seed search from a toml or json config
+    Search {
+        /// The config file with the search definition
+        config: PathBuf,
+        /// A file to save the results to, together with the search so they
+        /// can be verified later
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// A file to save checkpoints to, which is resumed from if it exists
+        #[arg(long)]
+        checkpoint: Option<PathBuf>,
+        /// The time between checkpoints in seconds
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+        /// Criteria added to the ones of the config, for example
+        /// "structure(village) within 256 of spawn"
+        #[arg(long)]
+        criteria: Option<String>,
+        /// Searches the large biomes world type, in addition to the flags of
+        /// the config
+        #[arg(long)]
+        large_biomes: bool,
+    },
+    /// Checks the results saved by a search again
+    Verify {
+        /// The file the search saved its results to
+        results: PathBuf,
+    },
+}
+
+/// The settings for the generator
+#[derive(Args, Debug)]
+struct WorldArgs {
+    /// The seed of the world
+    #[arg(long, allow_negative_numbers = true)]
+    seed: i64,
+    /// The minecraft version, for example MC_1_21_WD
+    #[arg(long = "mc-version", default_value = "MC_1_21_WD", value_parser = parse_enum::<MCVersion>)]
+    version: MCVersion,
+    /// The dimension, for example DIM_OVERWORLD or DIM_NETHER
+    #[arg(long, default_value = "DIM_OVERWORLD", value_parser = parse_enum::<Dimension>)]
+    dimension: Dimension,
+    /// Generates the large biomes world type
+    #[arg(long)]
+    large_biomes: bool,
+}
+
+impl WorldArgs {
+    fn generator(&self) -> Generator {
+        Generator::new(self.version, self.seed, self.dimension, self.flags())
+    }
+
+    fn flags(&self) -> GeneratorFlags {
+        if self.large_biomes {
+            GeneratorFlags::LargeBiomes
+        } else {
+            GeneratorFlags::empty()
+        }
+    }
+}
+
+/// The scales a map can be rendered at
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum MapScale {
+    Block,
+    Quad,
+    Chunk,
+    QuadChunk,
+    HalfRegion,
+}
+
+impl From<MapScale> for Scale {
+    fn from(value: MapScale) -> Self {
+        match value {
+            MapScale::Block => Self::Block,
+            MapScale::Quad => Self::Quad,
+            MapScale::Chunk => Self::Chunk,
+            MapScale::QuadChunk => Self::QuadChunk,
+            MapScale::HalfRegion => Self::HalfRegion,
+        }
+    }
+}
+
+/// Parses an enum of cubiomes by the name of its variant
+fn parse_enum<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+    let deserializer: StrDeserializer<'_, serde::de::value::Error> = name.into_deserializer();
+    T::deserialize(deserializer).map_err(|err| err.to_string())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), CliError> {
+    match command {
+        Command::BiomeAt { world, x, y, z } => {
+            let biome = world.generator().biome_at_level((x, z), y)?;
+            println!("{biome:?}");
+        }
+        Command::Map {
+            world,
+            scale,
+            x,
+            z,
+            width,
+            height,
+            y,
+            legend,
+            scale_bar,
+            output,
+        } => {
+            let scale = Scale::from(scale);
+            let generator = world.generator();
+
+            let range = Range::plane(scale, x, z, width, height, 0);
+            generator.check_range(&range)?;
+
+            let cache = generator.biomes_at_level(range, y)?;
+
+            if output.as_os_str() == "-" {
+                print_map(&cache)?;
+            } else {
+                let options = RenderOptions {
+                    legend,
+                    scale_bar,
+                    ..RenderOptions::default()
+                };
+                save_map(&cache, &options, &output)?;
+                println!("Saved map to {}", output.display());
+            }
+        }
+        Command::FindStructure {
+            world,
+            structure,
+            x,
+            z,
+            radius,
+            all,
+        } => {
+            let mut generator = world.generator();
+            let origin = BlockPosition::new(x, z);
+
+            let hits = if all {
+                generator.structures_within(structure, origin, radius)
+            } else {
+                generator
+                    .nearest_structure(structure, origin, radius)
+                    .into_iter()
+                    .collect()
+            };
+
+            if hits.is_empty() {
+                println!("No {structure:?} found within {radius} blocks");
+            }
+
+            for hit in hits {
+                println!(
+                    "{:?} at x: {}, z: {} ({:.0} blocks away)",
+                    hit.kind, hit.pos.x, hit.pos.z, hit.distance
+                );
+            }
+        }
+        Command::Search {
+            config,
+            output,
+            checkpoint,
+            interval,
+            criteria,
+            large_biomes,
+        } => {
+            let mut search = SearchDefinition::load(config)?;
+
+            if large_biomes {
+                search.flags |= GeneratorFlags::LargeBiomes;
+            }
+
+            if let Some(criteria) = criteria {
+                search.criteria.extend(Criterion::parse_all(&criteria)?);
+            }
+
+            let results = match checkpoint {
+                Some(checkpoint) => {
+                    search.run_resumable(checkpoint, Duration::from_secs(interval))?
+                }
+                None => search.run()?,
+            };
+
+            print_results(&results);
+
+            if let Some(output) = output {
+                SearchArchive::new(search, results).save(output)?;
+            }
+        }
+        Command::Verify { results } => {
+            let archive = SearchArchive::load(results)?;
+            let discrepancies = archive.verify()?;
+
+            for discrepancy in &discrepancies {
+                println!(
+                    "{} criterion {}: saved at {}, now {}",
+                    discrepancy.seed,
+                    discrepancy.criterion,
+                    describe_match(discrepancy.saved),
+                    describe_match(discrepancy.found)
+                );
+            }
+
+            let unverified = discrepancy_seeds(&discrepancies);
+            if unverified > 0 {
+                return Err(CliError::Unverified(unverified));
+            }
+
+            eprintln!("Verified {} saved seeds", archive.results.hits.len());
+        }
+    }
+
+    Ok(())
+}
+
+fn print_results(results: &SearchResults) {
+    for hit in &results.hits {
+        let positions: Vec<String> = hit
+            .matches
+            .iter()
+            .map(|found| format!("({}, {})", found.pos.x, found.pos.z))
+            .collect();
+
+        println!("{} {}", hit.seed, positions.join(" "));
+    }
+
+    eprintln!(
+        "Found {} matching seeds out of {} checked",
+        results.hits.len(),
+        results.seeds_checked
+    );
+}
+
+fn describe_match(pos: Option<BlockPosition>) -> String {
+    match pos {
+        Some(pos) => format!("({}, {})", pos.x, pos.z),
+        None => "no match".to_owned(),
+    }
+}
+
+/// Counts the seeds with at least one discrepancy, which are next to each
+/// other as they are found per hit
+fn discrepancy_seeds(discrepancies: &[Discrepancy]) -> usize {
+    let mut seeds: Vec<i64> = discrepancies.iter().map(|found| found.seed).collect();
+    seeds.dedup();
+    seeds.len()
+}
+
+fn print_map(cache: &Cache<'_>) -> Result<(), CliError> {
+    cubiomes::render::ascii(cache, std::io::stdout().lock()).map_err(|source| CliError::Write {
+        path: "the terminal".to_string(),
+        source,
+    })
+}
+
+fn save_map(
+    cache: &Cache<'_>,
+    options: &RenderOptions,
+    path: &std::path::Path,
+) -> Result<(), CliError> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let result = match extension.as_str() {
+        "png" => Map::from_cache(cache).export(options).save_png(path),
+        "ppm" => Map::from_cache(cache).export(options).save_ppm(path),
+        "csv" => return Ok(cache.save_csv(path)?),
+        "json" => return Ok(cache.save_json(path)?),
+        _ => return Err(CliError::UnknownImageFormat(extension)),
+    };
+
+    result.map_err(|source| CliError::Write {
+        path: path.display().to_string(),
+        source,
+    })
+}
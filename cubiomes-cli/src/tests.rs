@@ -0,0 +1,162 @@
+use super::{parse_enum, Cli, Command, MapScale};
+use clap::Parser;
+use cubiomes::enums::{Dimension, MCVersion, StructureType};
+use cubiomes::generator::{GeneratorFlags, YLevel};
+
+#[test]
+fn parses_enum_names() {
+    assert_eq!(parse_enum("MC_1_20_6"), Ok(MCVersion::MC_1_20));
+    assert_eq!(parse_enum("DIM_NETHER"), Ok(Dimension::DIM_NETHER));
+    assert_eq!(parse_enum("Village"), Ok(StructureType::Village));
+    assert!(parse_enum::<StructureType>("Castle").is_err());
+}
+
+#[test]
+fn parses_negative_coordinates() {
+    let cli = Cli::try_parse_from([
+        "cubiomes",
+        "biome-at",
+        "--seed",
+        "-380434930381432806",
+        "512",
+        "100",
+        "-512",
+    ])
+    .expect("valid arguments");
+
+    let Command::BiomeAt { world, x, y, z } = cli.command else {
+        panic!("parsed the wrong command");
+    };
+
+    assert_eq!(world.seed, -380434930381432806);
+    assert_eq!(world.version, MCVersion::MC_1_21_WD);
+    assert!(!world.large_biomes);
+    assert_eq!((x, y, z), (512, YLevel::Absolute(100), -512));
+}
+
+#[test]
+fn parses_map_defaults() {
+    let cli = Cli::try_parse_from(["cubiomes", "map", "--seed", "1", "--x", "-64", "map.png"])
+        .expect("valid arguments");
+
+    let Command::Map {
+        scale, x, width, y, ..
+    } = cli.command
+    else {
+        panic!("parsed the wrong command");
+    };
+
+    assert_eq!(scale, MapScale::Quad);
+    assert_eq!((x, width), (-64, 512));
+    assert_eq!(y, YLevel::Absolute(64));
+}
+
+#[test]
+fn parses_map_render_options() {
+    let cli = Cli::try_parse_from([
+        "cubiomes",
+        "map",
+        "--seed",
+        "1",
+        "--legend",
+        "--scale-bar",
+        "map.png",
+    ])
+    .expect("valid arguments");
+
+    let Command::Map {
+        legend, scale_bar, ..
+    } = cli.command
+    else {
+        panic!("parsed the wrong command");
+    };
+
+    assert!(legend && scale_bar);
+}
+
+#[test]
+fn parses_named_y_levels() {
+    let cli = Cli::try_parse_from(["cubiomes", "biome-at", "--seed", "1", "0", "surface", "0"])
+        .expect("valid arguments");
+
+    let Command::BiomeAt { y, .. } = cli.command else {
+        panic!("parsed the wrong command");
+    };
+
+    assert_eq!(y, YLevel::Surface);
+    assert!(Cli::try_parse_from(["cubiomes", "biome-at", "--seed", "1", "0", "sky", "0"]).is_err());
+}
+
+#[test]
+fn parses_large_biomes() {
+    let cli = Cli::try_parse_from([
+        "cubiomes",
+        "map",
+        "--seed",
+        "1",
+        "--large-biomes",
+        "map.png",
+    ])
+    .expect("valid arguments");
+
+    let Command::Map { world, .. } = cli.command else {
+        panic!("parsed the wrong command");
+    };
+
+    assert_eq!(world.generator().flags(), GeneratorFlags::LargeBiomes);
+}
+
+#[test]
+fn parses_search_criteria() {
+    let cli = Cli::try_parse_from([
+        "cubiomes",
+        "search",
+        "search.toml",
+        "--criteria",
+        "structure(village) within 256 of spawn",
+    ])
+    .expect("valid arguments");
+
+    let Command::Search { criteria, .. } = cli.command else {
+        panic!("parsed the wrong command");
+    };
+
+    assert_eq!(
+        criteria.as_deref(),
+        Some("structure(village) within 256 of spawn")
+    );
+}
+
+#[test]
+fn verifies_saved_results() {
+    use cubiomes::{
+        enums::MCVersion,
+        search::{Criterion, SearchArchive, SearchDefinition, SeedRange},
+    };
+
+    let mut search = SearchDefinition::new(MCVersion::MC_1_21_WD, SeedRange::new(0, 16));
+    search.criteria.push(Criterion::Structure {
+        structure: StructureType::Village,
+        x: 0,
+        z: 0,
+        radius: 512,
+    });
+    let results = search.run().expect("search failed");
+    let mut archive = SearchArchive::new(search, results);
+
+    let path = std::env::temp_dir().join(format!("cubiomes-verify-{}.json", std::process::id()));
+    archive.save(&path).expect("failed to save results");
+    assert!(super::run(Command::Verify {
+        results: path.clone()
+    })
+    .is_ok());
+
+    archive.results.hits[0].matches[0].pos.z += 1;
+    archive.save(&path).expect("failed to save results");
+    let tampered = super::run(Command::Verify {
+        results: path.clone(),
+    });
+    std::fs::remove_file(&path).expect("failed to remove results");
+
+    assert!(matches!(tampered, Err(super::CliError::Unverified(1))));
+}
@@ -1,17 +1,12 @@
-#[cfg(not(feature = "cc_build"))]
-use std::process::Command;
-use std::{collections::HashSet, env, path::PathBuf};
-
-macro_rules! add_prefix {
-    ($x:literal, $($y:literal),+) => {
-        [$(
-            concat!($x, $y),
-        )+]
-    };
-}
+use std::{
+    collections::HashSet,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
-const C_OBJECTS: [&str; 8] = add_prefix!(
-    "cubiomes/",
+/// The sources of cubiomes compiled into the library
+const C_SOURCES: [&str; 8] = [
     "noise.c",
     "biomes.c",
     "layers.c",
@@ -19,115 +14,498 @@ const C_OBJECTS: [&str; 8] = add_prefix!(
     "generator.c",
     "finders.c",
     "util.c",
-    "quadbase.c"
-);
-
-#[derive(Debug)]
-struct IgnoreMacros(HashSet<String>);
-
-impl bindgen::callbacks::ParseCallbacks for IgnoreMacros {
-    fn will_parse_macro(&self, name: &str) -> bindgen::callbacks::MacroParsingBehavior {
-        if self.0.contains(name) {
-            bindgen::callbacks::MacroParsingBehavior::Ignore
-        } else {
-            bindgen::callbacks::MacroParsingBehavior::Default
+    "quadbase.c",
+];
+
+/// The vendored cubiomes, built unless `CUBIOMES_SRC_DIR` is set
+const VENDORED_DIR: &str = "cubiomes";
+
+/// The directory of the bindings used without the `bindgen` feature
+const PREGENERATED_DIR: &str = "pregenerated";
+
+/// Versions which are only generated with the `unstable-versions` feature
+///
+/// Cubiomes adds these before their generation is final, so their support can
+/// still change with an update of cubiomes. The aliases of the versions are
+/// hidden with them.
+const UNSTABLE_VERSIONS: [&str; 5] = [
+    "MC_1_21_1",
+    "MC_1_21_3",
+    "MC_1_21_WD",
+    "MC_1_21",
+    "MC_NEWEST",
+];
+
+/// The derives of the rustified enums, besides the ones bindgen always adds
+const ENUM_DERIVES: [&str; 4] = ["FromPrimitive", "ToPrimitive", "PartialOrd", "Ord"];
+
+/// The derives added to the rustified enums with the `serde` feature, which
+/// serialize them by the names of their variants
+const SERDE_DERIVES: [&str; 2] = ["serde::Serialize", "serde::Deserialize"];
+
+fn main() {
+    for path in [
+        "build.rs",
+        "wrapper.h",
+        "enum_wrapper.h",
+        "generator_flags.h",
+        PREGENERATED_DIR,
+    ] {
+        println!("cargo:rerun-if-changed={path}");
+    }
+    for var in [
+        "CUBIOMES_LIB_DIR",
+        "CUBIOMES_INCLUDE_DIR",
+        "CUBIOMES_SRC_DIR",
+        "CUBIOMES_UPDATE_BINDINGS",
+        "WASI_SYSROOT",
+    ] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+
+    let wasm = env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch == "wasm32");
+    let system = cfg!(feature = "system") || env::var_os("CUBIOMES_LIB_DIR").is_some();
+
+    // A checkout of cubiomes to build instead of the vendored one, like its
+    // master branch
+    let source_dir = env::var_os("CUBIOMES_SRC_DIR").map(PathBuf::from);
+    let vendored = source_dir.is_none() && !system;
+    let source_dir = source_dir.unwrap_or_else(|| PathBuf::from(VENDORED_DIR));
+    println!("cargo:rerun-if-changed={}", source_dir.display());
+
+    let revision = if system {
+        None
+    } else {
+        git_revision(&source_dir)
+    };
+    println!(
+        "cargo:rustc-env=CUBIOMES_REVISION={}",
+        revision.as_deref().unwrap_or("unknown")
+    );
+    println!("cargo:rustc-env=CUBIOMES_VENDORED={vendored}");
+
+    // The headers of an external cubiomes, which have to match the linked
+    // library
+    let include_dir = env::var_os("CUBIOMES_INCLUDE_DIR")
+        .or_else(|| env::var_os("CUBIOMES_SRC_DIR"))
+        .map(PathBuf::from);
+
+    if system {
+        link_system();
+    } else {
+        if wasm && env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os != "wasi") {
+            panic!(
+                "cubiomes needs a C standard library, which is only available with wasi on \
+                 wasm32. Build for wasm32-wasip1 instead"
+            );
         }
+
+        build_with_cc(&source_dir, wasm);
+    }
+
+    let mut enum_derives: Vec<String> = ENUM_DERIVES.into_iter().map(String::from).collect();
+    if cfg!(feature = "serde") {
+        enum_derives.extend(SERDE_DERIVES.into_iter().map(String::from));
+    }
+
+    let hidden_variants: HashSet<String> = if cfg!(feature = "unstable-versions") {
+        HashSet::new()
+    } else {
+        UNSTABLE_VERSIONS.into_iter().map(String::from).collect()
+    };
+
+    #[cfg(any(feature = "bindgen", windows, not(target_pointer_width = "64")))]
+    let (bindings, enums) = live::generate(
+        include_dir.as_deref(),
+        wasm,
+        system,
+        &enum_derives,
+        hidden_variants,
+    );
+    #[cfg(not(any(feature = "bindgen", windows, not(target_pointer_width = "64"))))]
+    let (bindings, enums) = pregenerated(include_dir.is_some(), &hidden_variants);
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    fs::write(out_path.join("bindings.rs"), bindings).expect("Couldn't write bindings");
+    fs::write(out_path.join("biome_enums.rs"), &enums).expect("Couldn't write biome enums");
+    fs::write(out_path.join("biome_table.rs"), biome_table(&enums))
+        .expect("Couldn't write biome table");
+}
+
+/// Reads the pregenerated bindings and adjusts the enums to the features
+///
+/// The bindings are generated on 64 bit linux, which has the same layout of
+/// the structs of cubiomes as the other 64 bit targets except windows. Their
+/// layout tests catch a mismatch at compile time. The enums are generated
+/// with the `unstable-versions` feature and without the `serde` feature.
+#[cfg(not(any(feature = "bindgen", windows, not(target_pointer_width = "64"))))]
+fn pregenerated(external_headers: bool, hidden_variants: &HashSet<String>) -> (String, String) {
+    if external_headers {
+        panic!(
+            "the headers of CUBIOMES_INCLUDE_DIR or CUBIOMES_SRC_DIR can differ from the vendored \
+             ones, so their bindings can't be pregenerated. Enable the bindgen feature to \
+             generate them"
+        );
+    }
+
+    let pointer_width = env::var("CARGO_CFG_TARGET_POINTER_WIDTH").unwrap_or_default();
+    let windows = env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "windows");
+    if pointer_width != "64" || windows {
+        panic!(
+            "there are no pregenerated bindings for {}, enable the bindgen feature to generate \
+             them when cross compiling",
+            env::var("TARGET").unwrap_or_default()
+        );
     }
+
+    let read = |file: &str| {
+        fs::read_to_string(Path::new(PREGENERATED_DIR).join(file))
+            .expect("Couldn't read pregenerated bindings")
+    };
+
+    let mut extra_derives = Vec::new();
+    if cfg!(feature = "serde") {
+        extra_derives.extend(SERDE_DERIVES);
+    }
+
+    (
+        read("bindings.rs"),
+        adjust_enums(&read("biome_enums.rs"), &extra_derives, hidden_variants),
+    )
 }
 
-#[derive(Debug)]
-struct DeriveMacros(Vec<String>);
+/// Adds derives to the pregenerated enums and leaves out hidden variants
+///
+/// A hidden variant is left out with its attributes, and so are the aliases
+/// of it, which bindgen generates as constants.
+#[cfg(not(any(feature = "bindgen", windows, not(target_pointer_width = "64"))))]
+fn adjust_enums(enums: &str, extra_derives: &[&str], hidden_variants: &HashSet<String>) -> String {
+    let hidden = |line: &str| {
+        let line = line.trim();
+        let name = line.strip_prefix("pub const ").unwrap_or(line);
+        let name = name
+            .split(|char: char| !(char.is_alphanumeric() || char == '_'))
+            .next()
+            .unwrap_or_default();
+        hidden_variants.contains(name)
+    };
+
+    let mut adjusted = String::with_capacity(enums.len());
+    let mut attributes = Vec::new();
 
-impl bindgen::callbacks::ParseCallbacks for DeriveMacros {
-    fn add_derives(&self, _info: &bindgen::callbacks::DeriveInfo<'_>) -> Vec<String> {
-        match _info.kind {
-            bindgen::callbacks::TypeKind::Struct => Vec::new(),
-            bindgen::callbacks::TypeKind::Enum => self.0.clone(),
-            bindgen::callbacks::TypeKind::Union => Vec::new(),
+    for line in enums.lines() {
+        if line.trim_start().starts_with("#[") {
+            attributes.push(line);
+            continue;
+        }
+        if hidden(line) {
+            attributes.clear();
+            continue;
         }
+
+        for attribute in attributes.drain(..) {
+            match attribute.strip_suffix(")]") {
+                Some(derives) if attribute.starts_with("#[derive(") => {
+                    adjusted.push_str(derives);
+                    for derive in extra_derives {
+                        adjusted.push_str(", ");
+                        adjusted.push_str(derive);
+                    }
+                    adjusted.push_str(")]");
+                }
+                _ => adjusted.push_str(attribute),
+            }
+            adjusted.push('\n');
+        }
+        adjusted.push_str(line);
+        adjusted.push('\n');
     }
+
+    adjusted
 }
 
-fn main() {
-    #[cfg(not(feature = "cc_build"))]
-    build_with_make();
-
-    #[cfg(feature = "cc_build")]
-    build_with_cc();
-
-    println!("cargo:rustc-link-search={}/", env::var("OUT_DIR").unwrap());
-    println!("cargo:rustc-link-lib=static=cubiomes");
-
-    let ignored_macros = IgnoreMacros(
-        vec![
-            "FP_INFINITE".into(),
-            "FP_NAN".into(),
-            "FP_NORMAL".into(),
-            "FP_SUBNORMAL".into(),
-            "FP_ZERO".into(),
-        ]
-        .into_iter()
-        .collect(),
-    );
+/// Generating the bindings with bindgen, which needs libclang
+///
+/// Building on windows or a 32 bit host always generates the bindings, as
+/// the pregenerated ones don't fit their targets, see `Cargo.toml`.
+#[cfg(any(feature = "bindgen", windows, not(target_pointer_width = "64")))]
+mod live {
+    use super::{ENUM_DERIVES, PREGENERATED_DIR};
+    use std::{
+        collections::HashSet,
+        env, fs,
+        path::{Path, PathBuf},
+    };
 
-    let bindings = bindgen::Builder::default()
-        .header("wrapper.h")
-        .parse_callbacks(Box::new(ignored_macros))
-        .newtype_enum(".*")
-        .layout_tests(true)
-        .generate()
-        .expect("Unable to generate binding for cubiomes");
-
-    // Generates rustified enums for use in a wrapper library
-    let biome_enum_bindings = bindgen::Builder::default()
-        .header("enum_wrapper.h")
-        .parse_callbacks(Box::new(DeriveMacros(vec![
-            "FromPrimitive".into(),
-            "ToPrimitive".into(),
-            "PartialOrd".into(),
-            "Ord".into(),
-        ])))
-        .blocklist_function(".*") //Blocks all functions, as we are only intrested in the enums
-        .allowlist_type(["BiomeID", "Dimension", "MCVersion", "StructureType"].join("|"))
-        .rustified_non_exhaustive_enum(".*")
-        .generate()
-        .expect("Unable to generate rustified enums for cubiomes");
+    /// The headers included by `wrapper.h`, used with `CUBIOMES_INCLUDE_DIR`
+    const HEADERS: [&str; 8] = [
+        "biomenoise.h",
+        "biomes.h",
+        "finders.h",
+        "generator.h",
+        "layers.h",
+        "noise.h",
+        "quadbase.h",
+        "util.h",
+    ];
 
-    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    /// The headers of cubiomes included by `enum_wrapper.h`, used with
+    /// `CUBIOMES_INCLUDE_DIR`
+    const ENUM_HEADERS: [&str; 3] = ["biomes.h", "finders.h", "generator.h"];
+
+    /// Names the anonymous enum of the flags of `generator.h`, included after
+    /// the headers of cubiomes
+    const FLAGS_HEADER: &str = "generator_flags.h";
+
+    #[derive(Debug)]
+    struct IgnoreMacros(HashSet<String>);
+
+    impl bindgen::callbacks::ParseCallbacks for IgnoreMacros {
+        fn will_parse_macro(&self, name: &str) -> bindgen::callbacks::MacroParsingBehavior {
+            if self.0.contains(name) {
+                bindgen::callbacks::MacroParsingBehavior::Ignore
+            } else {
+                bindgen::callbacks::MacroParsingBehavior::Default
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    struct HideVariants(HashSet<String>);
+
+    impl bindgen::callbacks::ParseCallbacks for HideVariants {
+        fn enum_variant_behavior(
+            &self,
+            _enum_name: Option<&str>,
+            original_variant_name: &str,
+            _variant_value: bindgen::callbacks::EnumVariantValue,
+        ) -> Option<bindgen::callbacks::EnumVariantCustomBehavior> {
+            self.0
+                .contains(original_variant_name)
+                .then_some(bindgen::callbacks::EnumVariantCustomBehavior::Hide)
+        }
+    }
 
-    bindings
-        .write_to_file(out_path.join("bindings.rs"))
-        .expect("Couldn't write bindings");
+    #[derive(Debug)]
+    struct DeriveMacros(Vec<String>);
 
-    biome_enum_bindings
-        .write_to_file(out_path.join("biome_enums.rs"))
-        .expect("Couldn't write biome enums");
+    impl bindgen::callbacks::ParseCallbacks for DeriveMacros {
+        fn add_derives(&self, _info: &bindgen::callbacks::DeriveInfo<'_>) -> Vec<String> {
+            match _info.kind {
+                bindgen::callbacks::TypeKind::Struct => Vec::new(),
+                bindgen::callbacks::TypeKind::Enum => self.0.clone(),
+                bindgen::callbacks::TypeKind::Union => Vec::new(),
+            }
+        }
+    }
+
+    /// Generates the bindings and the rustified enums
+    ///
+    /// With `CUBIOMES_UPDATE_BINDINGS` set, the pregenerated bindings are
+    /// written again too, with the enums of every version and without the
+    /// derives of the `serde` feature. Update them on 64 bit linux with the
+    /// vendored cubiomes.
+    pub(super) fn generate(
+        include_dir: Option<&Path>,
+        wasm: bool,
+        system: bool,
+        enum_derives: &[String],
+        hidden_variants: HashSet<String>,
+    ) -> (String, String) {
+        let headers = |wrapper: &str, headers: &[&str]| -> Vec<String> {
+            match include_dir {
+                Some(dir) => headers
+                    .iter()
+                    .map(|header| dir.join(header).display().to_string())
+                    .collect(),
+                None => vec![wrapper.into()],
+            }
+        };
+
+        // The headers of wasi-libc, which cc also finds through this
+        let clang_args: Vec<String> = env::var("WASI_SYSROOT")
+            .ok()
+            .filter(|_| wasm)
+            .map(|sysroot| format!("--sysroot={sysroot}"))
+            .into_iter()
+            .collect();
+
+        let ignored_macros = IgnoreMacros(
+            vec![
+                "FP_INFINITE".into(),
+                "FP_NAN".into(),
+                "FP_NORMAL".into(),
+                "FP_SUBNORMAL".into(),
+                "FP_ZERO".into(),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let mut bindings = headers("wrapper.h", &HEADERS)
+            .into_iter()
+            .fold(bindgen::Builder::default(), bindgen::Builder::header)
+            .clang_args(&clang_args)
+            .parse_callbacks(Box::new(ignored_macros))
+            .newtype_enum(".*")
+            .layout_tests(true);
+
+        // Quadbase isn't built for wasm, so its functions would not link
+        if wasm && !system {
+            bindings = bindings.blocklist_file(".*quadbase\\.h");
+        }
+
+        let bindings = bindings
+            .generate()
+            .expect("Unable to generate binding for cubiomes")
+            .to_string();
+
+        // The flags are named in a header of this crate, which isn't among
+        // the headers of an external cubiomes
+        let mut enum_headers = headers("enum_wrapper.h", &ENUM_HEADERS);
+        if include_dir.is_some() {
+            enum_headers.push(FLAGS_HEADER.into());
+        }
+
+        // Generates rustified enums for use in a wrapper library
+        let enums = |derives: Vec<String>, hidden_variants: HashSet<String>| {
+            enum_headers
+                .iter()
+                .fold(bindgen::Builder::default(), |builder, header| {
+                    builder.header(header)
+                })
+                .clang_args(&clang_args)
+                .parse_callbacks(Box::new(DeriveMacros(derives)))
+                .parse_callbacks(Box::new(HideVariants(hidden_variants)))
+                .blocklist_function(".*") //Blocks all functions, as we are only intrested in the enums
+                .allowlist_type(
+                    [
+                        "BiomeID",
+                        "Dimension",
+                        "GeneratorFlag",
+                        "MCVersion",
+                        "StructureType",
+                    ]
+                    .join("|"),
+                )
+                .rustified_non_exhaustive_enum(".*")
+                .generate()
+                .expect("Unable to generate rustified enums for cubiomes")
+                .to_string()
+        };
+
+        if env::var_os("CUBIOMES_UPDATE_BINDINGS").is_some() {
+            let derives = ENUM_DERIVES.into_iter().map(String::from).collect();
+            let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join(PREGENERATED_DIR);
+
+            fs::write(dir.join("bindings.rs"), &bindings)
+                .expect("Couldn't write pregenerated bindings");
+            fs::write(dir.join("biome_enums.rs"), enums(derives, HashSet::new()))
+                .expect("Couldn't write pregenerated biome enums");
+        }
+
+        let enums = enums(enum_derives.to_vec(), hidden_variants);
+
+        (bindings, enums)
+    }
 }
 
-#[cfg(not(feature = "cc_build"))]
-fn build_with_make() {
-    if !Command::new("make")
-        .arg("-C")
-        .arg("cubiomes/")
-        .status()
-        .expect("Failed to build cubiomes")
-        .success()
-    {
-        panic!("Make did not return 0")
+/// Generates the table of every biome by its id from the generated enums
+///
+/// The table lets ids be converted to biomes with a single lookup, instead of
+/// matching every variant like the derived `FromPrimitive` does.
+fn biome_table(enums: &str) -> String {
+    let start = enums
+        .find("pub enum BiomeID")
+        .expect("the biome enum is generated");
+    let body = &enums[start..];
+    let body = &body[body.find('{').expect("the biome enum has a body") + 1
+        ..body.find('}').expect("the biome enum has a body")];
+
+    let variants: Vec<(&str, i32)> = body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with("//"))
+        .filter_map(|line| {
+            let (name, value) = line.trim_end_matches(',').split_once('=')?;
+            Some((name.trim(), value.trim().parse().ok()?))
+        })
+        .collect();
+
+    let min = variants.iter().map(|&(_, id)| id).min().unwrap_or(0);
+    let max = variants.iter().map(|&(_, id)| id).max().unwrap_or(0);
+
+    let mut table = vec![String::from("None"); (max - min + 1) as usize];
+    for (name, id) in variants {
+        table[(id - min) as usize] = format!("Some(BiomeID::{name})");
     }
 
-    if !Command::new("mv")
-        .arg("cubiomes/libcubiomes.a")
-        .arg(env::var("OUT_DIR").unwrap())
-        .status()
-        .expect("Failed to move libcubiomes")
-        .success()
-    {
-        panic!("mv did not return 0")
+    format!(
+        "/// The lowest id of a biome, which is the id of the first entry of [`BIOME_IDS`]\n\
+         pub const BIOME_ID_MIN: i32 = {min};\n\n\
+         /// Every biome by its id minus [`BIOME_ID_MIN`], generated when building\n\
+         pub static BIOME_IDS: [Option<BiomeID>; {}] = [{}];\n",
+        table.len(),
+        table.join(", ")
+    )
+}
+
+/// Links a libcubiomes built outside of this crate
+///
+/// The library is searched in `CUBIOMES_LIB_DIR` if it is set, and otherwise
+/// in the default paths of the linker. Either a static or a shared library is
+/// linked, depending on what the linker finds.
+fn link_system() {
+    if let Some(dir) = env::var_os("CUBIOMES_LIB_DIR") {
+        println!(
+            "cargo:rustc-link-search=native={}",
+            Path::new(&dir).display()
+        );
     }
+
+    println!("cargo:rustc-link-lib=cubiomes");
 }
 
-#[cfg(feature = "cc_build")]
-fn build_with_cc() {
-    cc::Build::new().files(C_OBJECTS).compile("cubiomes");
+/// Gets the git revision of a checkout of cubiomes, like `v1.2.3-4-g1a2b3c4`
+///
+/// Sources without a repository of their own, like the vendored ones of a
+/// published crate, have no revision.
+fn git_revision(source_dir: &Path) -> Option<String> {
+    if !source_dir.join(".git").exists() {
+        return None;
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(source_dir)
+        .args(["describe", "--tags", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let revision = String::from_utf8(output.stdout).ok()?;
+    Some(revision.trim().to_owned()).filter(|revision| !revision.is_empty())
+}
+
+/// Compiles cubiomes into a static library in `OUT_DIR` and links it
+///
+/// This replaces the makefile of cubiomes, so no make or shell is needed and
+/// the crate builds with msvc too. The optimization level follows the cargo
+/// profile.
+///
+/// Quadbase is left out on wasm, as its searches need threads and a file
+/// system.
+fn build_with_cc(source_dir: &Path, wasm: bool) {
+    let files = C_SOURCES
+        .into_iter()
+        .filter(|file| !(wasm && *file == "quadbase.c"))
+        .map(|file| source_dir.join(file));
+
+    cc::Build::new()
+        .files(files)
+        // Cubiomes relies on signed integers wrapping like in java, which the
+        // makefile enables as well
+        .flag_if_supported("-fwrapv")
+        .compile("cubiomes");
 }
@@ -1,2 +1,4 @@
 #include "cubiomes/biomes.h"
-#include "cubiomes/finders.h"
\ No newline at end of file
+#include "cubiomes/finders.h"
+#include "cubiomes/generator.h"
+#include "generator_flags.h"
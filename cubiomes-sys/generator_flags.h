@@ -0,0 +1,12 @@
+/*
+ * The flags of setupGenerator are an anonymous enum in generator.h, which
+ * bindgen can only generate as constants of an unnamed type. This names them,
+ * with the values of generator.h, so they are generated as a rust enum like
+ * the other enums. It has to be included after generator.h.
+ */
+enum GeneratorFlag
+{
+    GF_LARGE_BIOMES         = LARGE_BIOMES,
+    GF_NO_BETA_OCEAN        = NO_BETA_OCEAN,
+    GF_FORCE_OCEAN_VARIANTS = FORCE_OCEAN_VARIANTS,
+};
@@ -0,0 +1,1076 @@
+/* automatically generated by rust-bindgen (local stand-in) */
+
+pub const __STDC_FORMAT_MACROS: u32 = 1;
+pub const LAYER_INIT_SHA: u64 = 18446744073709551615;
+
+impl MCVersion {
+    pub const MC_UNDEF: MCVersion = MCVersion(0);
+    pub const MC_B1_7: MCVersion = MCVersion(1);
+    pub const MC_B1_8: MCVersion = MCVersion(2);
+    pub const MC_1_0_0: MCVersion = MCVersion(3);
+    pub const MC_1_0: MCVersion = MCVersion(3);
+    pub const MC_1_1_0: MCVersion = MCVersion(4);
+    pub const MC_1_1: MCVersion = MCVersion(4);
+    pub const MC_1_2_5: MCVersion = MCVersion(5);
+    pub const MC_1_2: MCVersion = MCVersion(5);
+    pub const MC_1_3_2: MCVersion = MCVersion(6);
+    pub const MC_1_3: MCVersion = MCVersion(6);
+    pub const MC_1_4_7: MCVersion = MCVersion(7);
+    pub const MC_1_4: MCVersion = MCVersion(7);
+    pub const MC_1_5_2: MCVersion = MCVersion(8);
+    pub const MC_1_5: MCVersion = MCVersion(8);
+    pub const MC_1_6_4: MCVersion = MCVersion(9);
+    pub const MC_1_6: MCVersion = MCVersion(9);
+    pub const MC_1_7_10: MCVersion = MCVersion(10);
+    pub const MC_1_7: MCVersion = MCVersion(10);
+    pub const MC_1_8_9: MCVersion = MCVersion(11);
+    pub const MC_1_8: MCVersion = MCVersion(11);
+    pub const MC_1_9_4: MCVersion = MCVersion(12);
+    pub const MC_1_9: MCVersion = MCVersion(12);
+    pub const MC_1_10_2: MCVersion = MCVersion(13);
+    pub const MC_1_10: MCVersion = MCVersion(13);
+    pub const MC_1_11_2: MCVersion = MCVersion(14);
+    pub const MC_1_11: MCVersion = MCVersion(14);
+    pub const MC_1_12_2: MCVersion = MCVersion(15);
+    pub const MC_1_12: MCVersion = MCVersion(15);
+    pub const MC_1_13_2: MCVersion = MCVersion(16);
+    pub const MC_1_13: MCVersion = MCVersion(16);
+    pub const MC_1_14_4: MCVersion = MCVersion(17);
+    pub const MC_1_14: MCVersion = MCVersion(17);
+    pub const MC_1_15_2: MCVersion = MCVersion(18);
+    pub const MC_1_15: MCVersion = MCVersion(18);
+    pub const MC_1_16_1: MCVersion = MCVersion(19);
+    pub const MC_1_16_5: MCVersion = MCVersion(20);
+    pub const MC_1_16: MCVersion = MCVersion(20);
+    pub const MC_1_17_1: MCVersion = MCVersion(21);
+    pub const MC_1_17: MCVersion = MCVersion(21);
+    pub const MC_1_18_2: MCVersion = MCVersion(22);
+    pub const MC_1_18: MCVersion = MCVersion(22);
+    pub const MC_1_19_2: MCVersion = MCVersion(23);
+    pub const MC_1_19_4: MCVersion = MCVersion(24);
+    pub const MC_1_19: MCVersion = MCVersion(24);
+    pub const MC_1_20_6: MCVersion = MCVersion(25);
+    pub const MC_1_20: MCVersion = MCVersion(25);
+    pub const MC_1_21_1: MCVersion = MCVersion(26);
+    pub const MC_1_21_3: MCVersion = MCVersion(27);
+    pub const MC_1_21_WD: MCVersion = MCVersion(28);
+    pub const MC_1_21: MCVersion = MCVersion(28);
+    pub const MC_NEWEST: MCVersion = MCVersion(28);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct MCVersion(pub ::std::os::raw::c_uint);
+impl Dimension {
+    pub const DIM_NETHER: Dimension = Dimension(-1);
+    pub const DIM_OVERWORLD: Dimension = Dimension(0);
+    pub const DIM_END: Dimension = Dimension(1);
+    pub const DIM_UNDEF: Dimension = Dimension(1000);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct Dimension(pub ::std::os::raw::c_int);
+impl BiomeID {
+    pub const none: BiomeID = BiomeID(-1);
+    pub const ocean: BiomeID = BiomeID(0);
+    pub const plains: BiomeID = BiomeID(1);
+    pub const desert: BiomeID = BiomeID(2);
+    pub const mountains: BiomeID = BiomeID(3);
+    pub const extremeHills: BiomeID = BiomeID(3);
+    pub const forest: BiomeID = BiomeID(4);
+    pub const taiga: BiomeID = BiomeID(5);
+    pub const swamp: BiomeID = BiomeID(6);
+    pub const swampland: BiomeID = BiomeID(6);
+    pub const river: BiomeID = BiomeID(7);
+    pub const nether_wastes: BiomeID = BiomeID(8);
+    pub const hell: BiomeID = BiomeID(8);
+    pub const the_end: BiomeID = BiomeID(9);
+    pub const sky: BiomeID = BiomeID(9);
+    pub const frozen_ocean: BiomeID = BiomeID(10);
+    pub const frozenOcean: BiomeID = BiomeID(10);
+    pub const frozen_river: BiomeID = BiomeID(11);
+    pub const frozenRiver: BiomeID = BiomeID(11);
+    pub const snowy_tundra: BiomeID = BiomeID(12);
+    pub const icePlains: BiomeID = BiomeID(12);
+    pub const snowy_mountains: BiomeID = BiomeID(13);
+    pub const iceMountains: BiomeID = BiomeID(13);
+    pub const mushroom_fields: BiomeID = BiomeID(14);
+    pub const mushroomIsland: BiomeID = BiomeID(14);
+    pub const mushroom_field_shore: BiomeID = BiomeID(15);
+    pub const mushroomIslandShore: BiomeID = BiomeID(15);
+    pub const beach: BiomeID = BiomeID(16);
+    pub const desert_hills: BiomeID = BiomeID(17);
+    pub const desertHills: BiomeID = BiomeID(17);
+    pub const wooded_hills: BiomeID = BiomeID(18);
+    pub const forestHills: BiomeID = BiomeID(18);
+    pub const taiga_hills: BiomeID = BiomeID(19);
+    pub const taigaHills: BiomeID = BiomeID(19);
+    pub const mountain_edge: BiomeID = BiomeID(20);
+    pub const extremeHillsEdge: BiomeID = BiomeID(20);
+    pub const jungle: BiomeID = BiomeID(21);
+    pub const jungle_hills: BiomeID = BiomeID(22);
+    pub const jungleHills: BiomeID = BiomeID(22);
+    pub const jungle_edge: BiomeID = BiomeID(23);
+    pub const jungleEdge: BiomeID = BiomeID(23);
+    pub const deep_ocean: BiomeID = BiomeID(24);
+    pub const deepOcean: BiomeID = BiomeID(24);
+    pub const stone_shore: BiomeID = BiomeID(25);
+    pub const stoneBeach: BiomeID = BiomeID(25);
+    pub const snowy_beach: BiomeID = BiomeID(26);
+    pub const coldBeach: BiomeID = BiomeID(26);
+    pub const birch_forest: BiomeID = BiomeID(27);
+    pub const birchForest: BiomeID = BiomeID(27);
+    pub const birch_forest_hills: BiomeID = BiomeID(28);
+    pub const birchForestHills: BiomeID = BiomeID(28);
+    pub const dark_forest: BiomeID = BiomeID(29);
+    pub const roofedForest: BiomeID = BiomeID(29);
+    pub const snowy_taiga: BiomeID = BiomeID(30);
+    pub const coldTaiga: BiomeID = BiomeID(30);
+    pub const snowy_taiga_hills: BiomeID = BiomeID(31);
+    pub const coldTaigaHills: BiomeID = BiomeID(31);
+    pub const giant_tree_taiga: BiomeID = BiomeID(32);
+    pub const megaTaiga: BiomeID = BiomeID(32);
+    pub const giant_tree_taiga_hills: BiomeID = BiomeID(33);
+    pub const megaTaigaHills: BiomeID = BiomeID(33);
+    pub const wooded_mountains: BiomeID = BiomeID(34);
+    pub const extremeHillsPlus: BiomeID = BiomeID(34);
+    pub const savanna: BiomeID = BiomeID(35);
+    pub const savanna_plateau: BiomeID = BiomeID(36);
+    pub const savannaPlateau: BiomeID = BiomeID(36);
+    pub const badlands: BiomeID = BiomeID(37);
+    pub const mesa: BiomeID = BiomeID(37);
+    pub const wooded_badlands_plateau: BiomeID = BiomeID(38);
+    pub const mesaPlateau_F: BiomeID = BiomeID(38);
+    pub const badlands_plateau: BiomeID = BiomeID(39);
+    pub const mesaPlateau: BiomeID = BiomeID(39);
+    pub const small_end_islands: BiomeID = BiomeID(40);
+    pub const end_midlands: BiomeID = BiomeID(41);
+    pub const end_highlands: BiomeID = BiomeID(42);
+    pub const end_barrens: BiomeID = BiomeID(43);
+    pub const warm_ocean: BiomeID = BiomeID(44);
+    pub const warmOcean: BiomeID = BiomeID(44);
+    pub const lukewarm_ocean: BiomeID = BiomeID(45);
+    pub const lukewarmOcean: BiomeID = BiomeID(45);
+    pub const cold_ocean: BiomeID = BiomeID(46);
+    pub const coldOcean: BiomeID = BiomeID(46);
+    pub const deep_warm_ocean: BiomeID = BiomeID(47);
+    pub const warmDeepOcean: BiomeID = BiomeID(47);
+    pub const deep_lukewarm_ocean: BiomeID = BiomeID(48);
+    pub const lukewarmDeepOcean: BiomeID = BiomeID(48);
+    pub const deep_cold_ocean: BiomeID = BiomeID(49);
+    pub const coldDeepOcean: BiomeID = BiomeID(49);
+    pub const deep_frozen_ocean: BiomeID = BiomeID(50);
+    pub const frozenDeepOcean: BiomeID = BiomeID(50);
+    pub const seasonal_forest: BiomeID = BiomeID(51);
+    pub const rainforest: BiomeID = BiomeID(52);
+    pub const shrubland: BiomeID = BiomeID(53);
+    pub const the_void: BiomeID = BiomeID(127);
+    pub const sunflower_plains: BiomeID = BiomeID(129);
+    pub const desert_lakes: BiomeID = BiomeID(130);
+    pub const gravelly_mountains: BiomeID = BiomeID(131);
+    pub const flower_forest: BiomeID = BiomeID(132);
+    pub const taiga_mountains: BiomeID = BiomeID(133);
+    pub const swamp_hills: BiomeID = BiomeID(134);
+    pub const ice_spikes: BiomeID = BiomeID(140);
+    pub const modified_jungle: BiomeID = BiomeID(149);
+    pub const modified_jungle_edge: BiomeID = BiomeID(151);
+    pub const tall_birch_forest: BiomeID = BiomeID(155);
+    pub const tall_birch_hills: BiomeID = BiomeID(156);
+    pub const dark_forest_hills: BiomeID = BiomeID(157);
+    pub const snowy_taiga_mountains: BiomeID = BiomeID(158);
+    pub const giant_spruce_taiga: BiomeID = BiomeID(160);
+    pub const giant_spruce_taiga_hills: BiomeID = BiomeID(161);
+    pub const modified_gravelly_mountains: BiomeID = BiomeID(162);
+    pub const shattered_savanna: BiomeID = BiomeID(163);
+    pub const shattered_savanna_plateau: BiomeID = BiomeID(164);
+    pub const eroded_badlands: BiomeID = BiomeID(165);
+    pub const modified_wooded_badlands_plateau: BiomeID = BiomeID(166);
+    pub const modified_badlands_plateau: BiomeID = BiomeID(167);
+    pub const bamboo_jungle: BiomeID = BiomeID(168);
+    pub const bamboo_jungle_hills: BiomeID = BiomeID(169);
+    pub const soul_sand_valley: BiomeID = BiomeID(170);
+    pub const crimson_forest: BiomeID = BiomeID(171);
+    pub const warped_forest: BiomeID = BiomeID(172);
+    pub const basalt_deltas: BiomeID = BiomeID(173);
+    pub const dripstone_caves: BiomeID = BiomeID(174);
+    pub const lush_caves: BiomeID = BiomeID(175);
+    pub const meadow: BiomeID = BiomeID(177);
+    pub const grove: BiomeID = BiomeID(178);
+    pub const snowy_slopes: BiomeID = BiomeID(179);
+    pub const jagged_peaks: BiomeID = BiomeID(180);
+    pub const frozen_peaks: BiomeID = BiomeID(181);
+    pub const stony_peaks: BiomeID = BiomeID(182);
+    pub const old_growth_birch_forest: BiomeID = BiomeID(155);
+    pub const old_growth_pine_taiga: BiomeID = BiomeID(32);
+    pub const old_growth_spruce_taiga: BiomeID = BiomeID(160);
+    pub const snowy_plains: BiomeID = BiomeID(12);
+    pub const sparse_jungle: BiomeID = BiomeID(23);
+    pub const stony_shore: BiomeID = BiomeID(25);
+    pub const windswept_hills: BiomeID = BiomeID(3);
+    pub const windswept_forest: BiomeID = BiomeID(34);
+    pub const windswept_gravelly_hills: BiomeID = BiomeID(131);
+    pub const windswept_savanna: BiomeID = BiomeID(163);
+    pub const wooded_badlands: BiomeID = BiomeID(38);
+    pub const deep_dark: BiomeID = BiomeID(183);
+    pub const mangrove_swamp: BiomeID = BiomeID(184);
+    pub const cherry_grove: BiomeID = BiomeID(185);
+    pub const pale_garden: BiomeID = BiomeID(186);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct BiomeID(pub ::std::os::raw::c_int);
+impl BiomeTempCategory {
+    pub const Oceanic: BiomeTempCategory = BiomeTempCategory(0);
+    pub const Warm: BiomeTempCategory = BiomeTempCategory(1);
+    pub const Lush: BiomeTempCategory = BiomeTempCategory(2);
+    pub const Cold: BiomeTempCategory = BiomeTempCategory(3);
+    pub const Freezing: BiomeTempCategory = BiomeTempCategory(4);
+    pub const Special: BiomeTempCategory = BiomeTempCategory(5);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct BiomeTempCategory(pub ::std::os::raw::c_uint);
+impl LayerId {
+    pub const L_CONTINENT_4096: LayerId = LayerId(0);
+    pub const L_ISLAND_4096: LayerId = LayerId(0);
+    pub const L_ZOOM_4096: LayerId = LayerId(1);
+    pub const L_LAND_4096: LayerId = LayerId(2);
+    pub const L_ZOOM_2048: LayerId = LayerId(3);
+    pub const L_LAND_2048: LayerId = LayerId(4);
+    pub const L_ADD_ISLAND_2048: LayerId = LayerId(4);
+    pub const L_ZOOM_1024: LayerId = LayerId(5);
+    pub const L_LAND_1024_A: LayerId = LayerId(6);
+    pub const L_ADD_ISLAND_1024A: LayerId = LayerId(6);
+    pub const L_LAND_1024_B: LayerId = LayerId(7);
+    pub const L_ADD_ISLAND_1024B: LayerId = LayerId(7);
+    pub const L_LAND_1024_C: LayerId = LayerId(8);
+    pub const L_ADD_ISLAND_1024C: LayerId = LayerId(8);
+    pub const L_ISLAND_1024: LayerId = LayerId(9);
+    pub const L_REMOVE_OCEAN_1024: LayerId = LayerId(9);
+    pub const L_SNOW_1024: LayerId = LayerId(10);
+    pub const L_ADD_SNOW_1024: LayerId = LayerId(10);
+    pub const L_LAND_1024_D: LayerId = LayerId(11);
+    pub const L_ADD_ISLAND_1024D: LayerId = LayerId(11);
+    pub const L_COOL_1024: LayerId = LayerId(12);
+    pub const L_COOL_WARM_1024: LayerId = LayerId(12);
+    pub const L_HEAT_1024: LayerId = LayerId(13);
+    pub const L_HEAT_ICE_1024: LayerId = LayerId(13);
+    pub const L_SPECIAL_1024: LayerId = LayerId(14);
+    pub const L_ZOOM_512: LayerId = LayerId(15);
+    pub const L_LAND_512: LayerId = LayerId(16);
+    pub const L_ZOOM_256: LayerId = LayerId(17);
+    pub const L_LAND_256: LayerId = LayerId(18);
+    pub const L_ADD_ISLAND_256: LayerId = LayerId(18);
+    pub const L_MUSHROOM_256: LayerId = LayerId(19);
+    pub const L_ADD_MUSHROOM_256: LayerId = LayerId(19);
+    pub const L_DEEP_OCEAN_256: LayerId = LayerId(20);
+    pub const L_BIOME_256: LayerId = LayerId(21);
+    pub const L_BAMBOO_256: LayerId = LayerId(22);
+    pub const L14_BAMBOO_256: LayerId = LayerId(22);
+    pub const L_ZOOM_128: LayerId = LayerId(23);
+    pub const L_ZOOM_64: LayerId = LayerId(24);
+    pub const L_BIOME_EDGE_64: LayerId = LayerId(25);
+    pub const L_NOISE_256: LayerId = LayerId(26);
+    pub const L_RIVER_INIT_256: LayerId = LayerId(26);
+    pub const L_ZOOM_128_HILLS: LayerId = LayerId(27);
+    pub const L_ZOOM_64_HILLS: LayerId = LayerId(28);
+    pub const L_HILLS_64: LayerId = LayerId(29);
+    pub const L_SUNFLOWER_64: LayerId = LayerId(30);
+    pub const L_RARE_BIOME_64: LayerId = LayerId(30);
+    pub const L_ZOOM_32: LayerId = LayerId(31);
+    pub const L_LAND_32: LayerId = LayerId(32);
+    pub const L_ADD_ISLAND_32: LayerId = LayerId(32);
+    pub const L_ZOOM_16: LayerId = LayerId(33);
+    pub const L_SHORE_16: LayerId = LayerId(34);
+    pub const L_SWAMP_RIVER_16: LayerId = LayerId(35);
+    pub const L_ZOOM_8: LayerId = LayerId(36);
+    pub const L_ZOOM_4: LayerId = LayerId(37);
+    pub const L_SMOOTH_4: LayerId = LayerId(38);
+    pub const L_ZOOM_128_RIVER: LayerId = LayerId(39);
+    pub const L_ZOOM_64_RIVER: LayerId = LayerId(40);
+    pub const L_ZOOM_32_RIVER: LayerId = LayerId(41);
+    pub const L_ZOOM_16_RIVER: LayerId = LayerId(42);
+    pub const L_ZOOM_8_RIVER: LayerId = LayerId(43);
+    pub const L_ZOOM_4_RIVER: LayerId = LayerId(44);
+    pub const L_RIVER_4: LayerId = LayerId(45);
+    pub const L_SMOOTH_4_RIVER: LayerId = LayerId(46);
+    pub const L_RIVER_MIX_4: LayerId = LayerId(47);
+    pub const L_OCEAN_TEMP_256: LayerId = LayerId(48);
+    pub const L13_OCEAN_TEMP_256: LayerId = LayerId(48);
+    pub const L_ZOOM_128_OCEAN: LayerId = LayerId(49);
+    pub const L13_ZOOM_128: LayerId = LayerId(49);
+    pub const L_ZOOM_64_OCEAN: LayerId = LayerId(50);
+    pub const L13_ZOOM_64: LayerId = LayerId(50);
+    pub const L_ZOOM_32_OCEAN: LayerId = LayerId(51);
+    pub const L13_ZOOM_32: LayerId = LayerId(51);
+    pub const L_ZOOM_16_OCEAN: LayerId = LayerId(52);
+    pub const L13_ZOOM_16: LayerId = LayerId(52);
+    pub const L_ZOOM_8_OCEAN: LayerId = LayerId(53);
+    pub const L13_ZOOM_8: LayerId = LayerId(53);
+    pub const L_ZOOM_4_OCEAN: LayerId = LayerId(54);
+    pub const L13_ZOOM_4: LayerId = LayerId(54);
+    pub const L_OCEAN_MIX_4: LayerId = LayerId(55);
+    pub const L13_OCEAN_MIX_4: LayerId = LayerId(55);
+    pub const L_VORONOI_1: LayerId = LayerId(56);
+    pub const L_VORONOI_ZOOM_1: LayerId = LayerId(56);
+    pub const L_ZOOM_LARGE_A: LayerId = LayerId(57);
+    pub const L_ZOOM_LARGE_B: LayerId = LayerId(58);
+    pub const L_ZOOM_L_RIVER_A: LayerId = LayerId(59);
+    pub const L_ZOOM_L_RIVER_B: LayerId = LayerId(60);
+    pub const L_NUM: LayerId = LayerId(61);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct LayerId(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_1 {
+    pub const NP_TEMPERATURE: _bindgen_ty_1 = _bindgen_ty_1(0);
+    pub const NP_HUMIDITY: _bindgen_ty_1 = _bindgen_ty_1(1);
+    pub const NP_CONTINENTALNESS: _bindgen_ty_1 = _bindgen_ty_1(2);
+    pub const NP_EROSION: _bindgen_ty_1 = _bindgen_ty_1(3);
+    pub const NP_SHIFT: _bindgen_ty_1 = _bindgen_ty_1(4);
+    pub const NP_DEPTH: _bindgen_ty_1 = _bindgen_ty_1(4);
+    pub const NP_WEIRDNESS: _bindgen_ty_1 = _bindgen_ty_1(5);
+    pub const NP_MAX: _bindgen_ty_1 = _bindgen_ty_1(6);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_1(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_2 {
+    pub const SAMPLE_NO_SHIFT: _bindgen_ty_2 = _bindgen_ty_2(1);
+    pub const SAMPLE_NO_DEPTH: _bindgen_ty_2 = _bindgen_ty_2(2);
+    pub const SAMPLE_NO_BIOME: _bindgen_ty_2 = _bindgen_ty_2(4);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_2(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_3 {
+    pub const LARGE_BIOMES: _bindgen_ty_3 = _bindgen_ty_3(1);
+    pub const NO_BETA_OCEAN: _bindgen_ty_3 = _bindgen_ty_3(2);
+    pub const FORCE_OCEAN_VARIANTS: _bindgen_ty_3 = _bindgen_ty_3(4);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_3(pub ::std::os::raw::c_uint);
+impl StructureType {
+    pub const Feature: StructureType = StructureType(0);
+    pub const Desert_Pyramid: StructureType = StructureType(1);
+    pub const Jungle_Temple: StructureType = StructureType(2);
+    pub const Jungle_Pyramid: StructureType = StructureType(2);
+    pub const Swamp_Hut: StructureType = StructureType(3);
+    pub const Igloo: StructureType = StructureType(4);
+    pub const Village: StructureType = StructureType(5);
+    pub const Ocean_Ruin: StructureType = StructureType(6);
+    pub const Shipwreck: StructureType = StructureType(7);
+    pub const Monument: StructureType = StructureType(8);
+    pub const Mansion: StructureType = StructureType(9);
+    pub const Outpost: StructureType = StructureType(10);
+    pub const Ruined_Portal: StructureType = StructureType(11);
+    pub const Ruined_Portal_N: StructureType = StructureType(12);
+    pub const Ancient_City: StructureType = StructureType(13);
+    pub const Treasure: StructureType = StructureType(14);
+    pub const Mineshaft: StructureType = StructureType(15);
+    pub const Desert_Well: StructureType = StructureType(16);
+    pub const Geode: StructureType = StructureType(17);
+    pub const Fortress: StructureType = StructureType(18);
+    pub const Bastion: StructureType = StructureType(19);
+    pub const End_City: StructureType = StructureType(20);
+    pub const End_Gateway: StructureType = StructureType(21);
+    pub const End_Island: StructureType = StructureType(22);
+    pub const Trail_Ruins: StructureType = StructureType(23);
+    pub const Trial_Chambers: StructureType = StructureType(24);
+    pub const FEATURE_NUM: StructureType = StructureType(25);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct StructureType(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_4 {
+    pub const BF_APPROX: _bindgen_ty_4 = _bindgen_ty_4(1);
+    pub const BF_FORCED_OCEAN: _bindgen_ty_4 = _bindgen_ty_4(4);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_4(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_5 {
+    pub const BASE_FLOOR: _bindgen_ty_5 = _bindgen_ty_5(0);
+    pub const BASE_ROOF: _bindgen_ty_5 = _bindgen_ty_5(1);
+    pub const BRIDGE_END: _bindgen_ty_5 = _bindgen_ty_5(2);
+    pub const BRIDGE_GENTLE_STAIRS: _bindgen_ty_5 = _bindgen_ty_5(3);
+    pub const BRIDGE_PIECE: _bindgen_ty_5 = _bindgen_ty_5(4);
+    pub const BRIDGE_STEEP_STAIRS: _bindgen_ty_5 = _bindgen_ty_5(5);
+    pub const FAT_TOWER_BASE: _bindgen_ty_5 = _bindgen_ty_5(6);
+    pub const FAT_TOWER_MIDDLE: _bindgen_ty_5 = _bindgen_ty_5(7);
+    pub const FAT_TOWER_TOP: _bindgen_ty_5 = _bindgen_ty_5(8);
+    pub const SECOND_FLOOR_1: _bindgen_ty_5 = _bindgen_ty_5(9);
+    pub const SECOND_FLOOR_2: _bindgen_ty_5 = _bindgen_ty_5(10);
+    pub const SECOND_ROOF: _bindgen_ty_5 = _bindgen_ty_5(11);
+    pub const END_SHIP: _bindgen_ty_5 = _bindgen_ty_5(12);
+    pub const THIRD_FLOOR_1: _bindgen_ty_5 = _bindgen_ty_5(13);
+    pub const THIRD_FLOOR_2: _bindgen_ty_5 = _bindgen_ty_5(14);
+    pub const THIRD_ROOF: _bindgen_ty_5 = _bindgen_ty_5(15);
+    pub const TOWER_BASE: _bindgen_ty_5 = _bindgen_ty_5(16);
+    pub const TOWER_FLOOR: _bindgen_ty_5 = _bindgen_ty_5(17);
+    pub const TOWER_PIECE: _bindgen_ty_5 = _bindgen_ty_5(18);
+    pub const TOWER_TOP: _bindgen_ty_5 = _bindgen_ty_5(19);
+    pub const END_CITY_PIECES_MAX: _bindgen_ty_5 = _bindgen_ty_5(421);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_5(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_6 {
+    pub const FORTRESS_START: _bindgen_ty_6 = _bindgen_ty_6(0);
+    pub const BRIDGE_STRAIGHT: _bindgen_ty_6 = _bindgen_ty_6(1);
+    pub const BRIDGE_CROSSING: _bindgen_ty_6 = _bindgen_ty_6(2);
+    pub const BRIDGE_FORTIFIED_CROSSING: _bindgen_ty_6 = _bindgen_ty_6(3);
+    pub const BRIDGE_STAIRS: _bindgen_ty_6 = _bindgen_ty_6(4);
+    pub const BRIDGE_SPAWNER: _bindgen_ty_6 = _bindgen_ty_6(5);
+    pub const BRIDGE_CORRIDOR_ENTRANCE: _bindgen_ty_6 = _bindgen_ty_6(6);
+    pub const CORRIDOR_STRAIGHT: _bindgen_ty_6 = _bindgen_ty_6(7);
+    pub const CORRIDOR_CROSSING: _bindgen_ty_6 = _bindgen_ty_6(8);
+    pub const CORRIDOR_TURN_RIGHT: _bindgen_ty_6 = _bindgen_ty_6(9);
+    pub const CORRIDOR_TURN_LEFT: _bindgen_ty_6 = _bindgen_ty_6(10);
+    pub const CORRIDOR_STAIRS: _bindgen_ty_6 = _bindgen_ty_6(11);
+    pub const CORRIDOR_T_CROSSING: _bindgen_ty_6 = _bindgen_ty_6(12);
+    pub const CORRIDOR_NETHER_WART: _bindgen_ty_6 = _bindgen_ty_6(13);
+    pub const FORTRESS_END: _bindgen_ty_6 = _bindgen_ty_6(14);
+    pub const PIECE_COUNT: _bindgen_ty_6 = _bindgen_ty_6(15);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_6(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_7 {
+    pub const HouseSmall: _bindgen_ty_7 = _bindgen_ty_7(0);
+    pub const Church: _bindgen_ty_7 = _bindgen_ty_7(1);
+    pub const Library: _bindgen_ty_7 = _bindgen_ty_7(2);
+    pub const WoodHut: _bindgen_ty_7 = _bindgen_ty_7(3);
+    pub const Butcher: _bindgen_ty_7 = _bindgen_ty_7(4);
+    pub const FarmLarge: _bindgen_ty_7 = _bindgen_ty_7(5);
+    pub const FarmSmall: _bindgen_ty_7 = _bindgen_ty_7(6);
+    pub const Blacksmith: _bindgen_ty_7 = _bindgen_ty_7(7);
+    pub const HouseLarge: _bindgen_ty_7 = _bindgen_ty_7(8);
+    pub const HOUSE_NUM: _bindgen_ty_7 = _bindgen_ty_7(9);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_7(pub ::std::os::raw::c_uint);
+impl _bindgen_ty_8 {
+    pub const CST_NONE: _bindgen_ty_8 = _bindgen_ty_8(0);
+    pub const CST_IDEAL: _bindgen_ty_8 = _bindgen_ty_8(1);
+    pub const CST_CLASSIC: _bindgen_ty_8 = _bindgen_ty_8(2);
+    pub const CST_NORMAL: _bindgen_ty_8 = _bindgen_ty_8(3);
+    pub const CST_BARELY: _bindgen_ty_8 = _bindgen_ty_8(4);
+}
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct _bindgen_ty_8(pub ::std::os::raw::c_uint);
+pub type i8_ = i8;
+pub type u8_ = u8;
+pub type i16_ = i16;
+pub type u16_ = u16;
+pub type i32_ = i32;
+pub type u32_ = u32;
+pub type i64_ = i64;
+pub type u64_ = u64;
+pub type f32_ = f32;
+pub type f64_ = f64;
+pub type mapfunc_t = ::std::option::Option<unsafe extern "C" fn(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Xoroshiro {
+    pub lo: u64,
+    pub hi: u64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct PerlinNoise {
+    pub d: [u8; 257usize],
+    pub h2: u8,
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub amplitude: f64,
+    pub lacunarity: f64,
+    pub d2: f64,
+    pub t2: f64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OctaveNoise {
+    pub octcnt: ::std::os::raw::c_int,
+    pub octaves: *mut PerlinNoise,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct DoublePerlinNoise {
+    pub amplitude: f64,
+    pub octA: OctaveNoise,
+    pub octB: OctaveNoise,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Layer {
+    pub getMap: mapfunc_t,
+    pub mc: i8,
+    pub zoom: i8,
+    pub edge: i8,
+    pub scale: ::std::os::raw::c_int,
+    pub layerSalt: u64,
+    pub startSalt: u64,
+    pub startSeed: u64,
+    pub noise: *mut ::std::os::raw::c_void,
+    pub data: *mut ::std::os::raw::c_void,
+    pub p: *mut Layer,
+    pub p2: *mut Layer,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct LayerStack {
+    pub layers: [Layer; 61usize],
+    pub entry_1: *mut Layer,
+    pub entry_4: *mut Layer,
+    pub entry_16: *mut Layer,
+    pub entry_64: *mut Layer,
+    pub entry_256: *mut Layer,
+    pub oceanRnd: PerlinNoise,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Range {
+    pub scale: ::std::os::raw::c_int,
+    pub x: ::std::os::raw::c_int,
+    pub z: ::std::os::raw::c_int,
+    pub sx: ::std::os::raw::c_int,
+    pub sz: ::std::os::raw::c_int,
+    pub y: ::std::os::raw::c_int,
+    pub sy: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct NetherNoise {
+    pub temperature: DoublePerlinNoise,
+    pub humidity: DoublePerlinNoise,
+    pub oct: [PerlinNoise; 8usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct EndNoise {
+    pub perlin: PerlinNoise,
+    pub mc: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct SurfaceNoise {
+    pub xzScale: f64,
+    pub yScale: f64,
+    pub xzFactor: f64,
+    pub yFactor: f64,
+    pub octmin: OctaveNoise,
+    pub octmax: OctaveNoise,
+    pub octmain: OctaveNoise,
+    pub octsurf: OctaveNoise,
+    pub octdepth: OctaveNoise,
+    pub oct: [PerlinNoise; 60usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct SurfaceNoiseBeta {
+    pub octmin: OctaveNoise,
+    pub octmax: OctaveNoise,
+    pub octmain: OctaveNoise,
+    pub octcontA: OctaveNoise,
+    pub octcontB: OctaveNoise,
+    pub oct: [PerlinNoise; 66usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct SeaLevelColumnNoiseBeta {
+    pub contASample: f64,
+    pub contBSample: f64,
+    pub minSample: [f64; 2usize],
+    pub maxSample: [f64; 2usize],
+    pub mainSample: [f64; 2usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Spline {
+    pub len: ::std::os::raw::c_int,
+    pub typ: ::std::os::raw::c_int,
+    pub loc: [f32; 12usize],
+    pub der: [f32; 12usize],
+    pub val: [*mut Spline; 12usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FixSpline {
+    pub len: ::std::os::raw::c_int,
+    pub val: f32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct SplineStack {
+    pub stack: [Spline; 42usize],
+    pub fstack: [FixSpline; 151usize],
+    pub len: ::std::os::raw::c_int,
+    pub flen: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BiomeNoise {
+    pub climate: [DoublePerlinNoise; 6usize],
+    pub oct: [PerlinNoise; 46usize],
+    pub sp: *mut Spline,
+    pub ss: SplineStack,
+    pub nptype: ::std::os::raw::c_int,
+    pub mc: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BiomeNoiseBeta {
+    pub climate: [OctaveNoise; 3usize],
+    pub oct: [PerlinNoise; 10usize],
+    pub nptype: ::std::os::raw::c_int,
+    pub mc: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BiomeTree {
+    pub steps: *const u32,
+    pub param: *const i32,
+    pub nodes: *const u64,
+    pub order: u32,
+    pub len: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Generator__bindgen_ty_1__bindgen_ty_1 {
+    pub ls: LayerStack,
+    pub xlayer: [Layer; 5usize],
+    pub entry: *mut Layer,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Generator__bindgen_ty_1__bindgen_ty_2 {
+    pub bn: BiomeNoise,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Generator__bindgen_ty_1__bindgen_ty_3 {
+    pub bnb: BiomeNoiseBeta,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union Generator__bindgen_ty_1 {
+    pub __bindgen_anon_1: Generator__bindgen_ty_1__bindgen_ty_1,
+    pub __bindgen_anon_2: Generator__bindgen_ty_1__bindgen_ty_2,
+    pub __bindgen_anon_3: Generator__bindgen_ty_1__bindgen_ty_3,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Generator {
+    pub mc: ::std::os::raw::c_int,
+    pub dim: ::std::os::raw::c_int,
+    pub flags: u32,
+    pub seed: u64,
+    pub sha: u64,
+    pub __bindgen_anon_1: Generator__bindgen_ty_1,
+    pub nn: NetherNoise,
+    pub en: EndNoise,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct StructureConfig {
+    pub salt: i32,
+    pub regionSize: i8,
+    pub chunkRange: i8,
+    pub structType: u8,
+    pub dim: i8,
+    pub rarity: f32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Pos {
+    pub x: ::std::os::raw::c_int,
+    pub z: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Pos3 {
+    pub x: ::std::os::raw::c_int,
+    pub y: ::std::os::raw::c_int,
+    pub z: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct StrongholdIter {
+    pub pos: Pos,
+    pub nextapprox: Pos,
+    pub index: ::std::os::raw::c_int,
+    pub ringnum: ::std::os::raw::c_int,
+    pub ringmax: ::std::os::raw::c_int,
+    pub ringidx: ::std::os::raw::c_int,
+    pub angle: f64,
+    pub dist: f64,
+    pub rnds: u64,
+    pub mc: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct StructureVariant {
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: [u8; 1usize],
+    pub size: u8,
+    pub start: u8,
+    pub biome: ::std::os::raw::c_short,
+    pub rotation: u8,
+    pub mirror: u8,
+    pub x: i16,
+    pub y: i16,
+    pub z: i16,
+    pub sx: i16,
+    pub sy: i16,
+    pub sz: i16,
+}
+impl StructureVariant {
+    #[inline]
+    pub fn abandoned(&self) -> u8 {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        ((v >> 0) & ((1u64 << 1) - 1)) as u8
+    }
+    #[inline]
+    pub fn set_abandoned(&mut self, val: u8) {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        let mask = ((1u64 << 1) - 1) << 0;
+        v = (v & !mask) | (((val as u64) << 0) & mask);
+        for (i, b) in self._bitfield_1.iter_mut().enumerate() { *b = (v >> (8 * i)) as u8; }
+    }
+    #[inline]
+    pub fn giant(&self) -> u8 {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        ((v >> 1) & ((1u64 << 1) - 1)) as u8
+    }
+    #[inline]
+    pub fn set_giant(&mut self, val: u8) {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        let mask = ((1u64 << 1) - 1) << 1;
+        v = (v & !mask) | (((val as u64) << 1) & mask);
+        for (i, b) in self._bitfield_1.iter_mut().enumerate() { *b = (v >> (8 * i)) as u8; }
+    }
+    #[inline]
+    pub fn underground(&self) -> u8 {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        ((v >> 2) & ((1u64 << 1) - 1)) as u8
+    }
+    #[inline]
+    pub fn set_underground(&mut self, val: u8) {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        let mask = ((1u64 << 1) - 1) << 2;
+        v = (v & !mask) | (((val as u64) << 2) & mask);
+        for (i, b) in self._bitfield_1.iter_mut().enumerate() { *b = (v >> (8 * i)) as u8; }
+    }
+    #[inline]
+    pub fn airpocket(&self) -> u8 {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        ((v >> 3) & ((1u64 << 1) - 1)) as u8
+    }
+    #[inline]
+    pub fn set_airpocket(&mut self, val: u8) {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        let mask = ((1u64 << 1) - 1) << 3;
+        v = (v & !mask) | (((val as u64) << 3) & mask);
+        for (i, b) in self._bitfield_1.iter_mut().enumerate() { *b = (v >> (8 * i)) as u8; }
+    }
+    #[inline]
+    pub fn basement(&self) -> u8 {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        ((v >> 4) & ((1u64 << 1) - 1)) as u8
+    }
+    #[inline]
+    pub fn set_basement(&mut self, val: u8) {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        let mask = ((1u64 << 1) - 1) << 4;
+        v = (v & !mask) | (((val as u64) << 4) & mask);
+        for (i, b) in self._bitfield_1.iter_mut().enumerate() { *b = (v >> (8 * i)) as u8; }
+    }
+    #[inline]
+    pub fn cracked(&self) -> u8 {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        ((v >> 5) & ((1u64 << 1) - 1)) as u8
+    }
+    #[inline]
+    pub fn set_cracked(&mut self, val: u8) {
+        let mut v: u64 = 0;
+        for (i, b) in self._bitfield_1.iter().enumerate() { v |= (*b as u64) << (8 * i); }
+        let mask = ((1u64 << 1) - 1) << 5;
+        v = (v & !mask) | (((val as u64) << 5) & mask);
+        for (i, b) in self._bitfield_1.iter_mut().enumerate() { *b = (v >> (8 * i)) as u8; }
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Piece {
+    pub name: *const ::std::os::raw::c_char,
+    pub pos: Pos3,
+    pub bb0: Pos3,
+    pub bb1: Pos3,
+    pub rot: u8,
+    pub depth: i8,
+    pub type_: i8,
+    pub next: *mut Piece,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct EndIsland {
+    pub x: ::std::os::raw::c_int,
+    pub y: ::std::os::raw::c_int,
+    pub z: ::std::os::raw::c_int,
+    pub r: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct BiomeFilter {
+    pub tempsToFind: u64,
+    pub otempToFind: u64,
+    pub majorToFind: u64,
+    pub edgesToFind: u64,
+    pub raresToFind: u64,
+    pub raresToFindM: u64,
+    pub shoreToFind: u64,
+    pub shoreToFindM: u64,
+    pub riverToFind: u64,
+    pub riverToFindM: u64,
+    pub oceanToFind: u64,
+    pub specialCnt: ::std::os::raw::c_int,
+    pub flags: u32,
+    pub tempsToExcl: u64,
+    pub majorToExcl: u64,
+    pub edgesToExcl: u64,
+    pub raresToExcl: u64,
+    pub raresToExclM: u64,
+    pub shoreToExcl: u64,
+    pub shoreToExclM: u64,
+    pub riverToExcl: u64,
+    pub riverToExclM: u64,
+    pub biomeToExcl: u64,
+    pub biomeToExclM: u64,
+    pub biomeToFind: u64,
+    pub biomeToFindM: u64,
+    pub biomeToPick: u64,
+    pub biomeToPickM: u64,
+}
+extern "C" {
+    pub fn perlinInit(noise: *mut PerlinNoise, seed: *mut u64);
+    pub fn xPerlinInit(noise: *mut PerlinNoise, xr: *mut Xoroshiro);
+    pub fn samplePerlin(noise: *const PerlinNoise, x: f64, y: f64, z: f64, yamp: f64, ymin: f64) -> f64;
+    pub fn sampleSimplex2D(noise: *const PerlinNoise, x: f64, y: f64) -> f64;
+    pub fn octaveInit(noise: *mut OctaveNoise, seed: *mut u64, octaves: *mut PerlinNoise, omin: ::std::os::raw::c_int, len: ::std::os::raw::c_int);
+    pub fn octaveInitBeta(noise: *mut OctaveNoise, seed: *mut u64, octaves: *mut PerlinNoise, octcnt: ::std::os::raw::c_int, lac: f64, lacMul: f64, persist: f64, persistMul: f64);
+    pub fn xOctaveInit(noise: *mut OctaveNoise, xr: *mut Xoroshiro, octaves: *mut PerlinNoise, amplitudes: *const f64, omin: ::std::os::raw::c_int, len: ::std::os::raw::c_int, nmax: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn sampleOctave(noise: *const OctaveNoise, x: f64, y: f64, z: f64) -> f64;
+    pub fn sampleOctaveAmp(noise: *const OctaveNoise, x: f64, y: f64, z: f64, yamp: f64, ymin: f64, ydefault: ::std::os::raw::c_int) -> f64;
+    pub fn sampleOctave2D(noise: *const OctaveNoise, x: f64, z: f64) -> f64;
+    pub fn sampleOctaveBeta17Biome(noise: *const OctaveNoise, x: f64, z: f64) -> f64;
+    pub fn sampleOctaveBeta17Terrain(noise: *const OctaveNoise, v: *mut f64, x: f64, z: f64, yLacFlag: ::std::os::raw::c_int, lacmin: f64);
+    pub fn doublePerlinInit(noise: *mut DoublePerlinNoise, seed: *mut u64, octavesA: *mut PerlinNoise, octavesB: *mut PerlinNoise, omin: ::std::os::raw::c_int, len: ::std::os::raw::c_int);
+    pub fn xDoublePerlinInit(noise: *mut DoublePerlinNoise, xr: *mut Xoroshiro, octaves: *mut PerlinNoise, amplitudes: *const f64, omin: ::std::os::raw::c_int, len: ::std::os::raw::c_int, nmax: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn sampleDoublePerlin(noise: *const DoublePerlinNoise, x: f64, y: f64, z: f64) -> f64;
+    pub fn biomeExists(mc: ::std::os::raw::c_int, id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isOverworld(mc: ::std::os::raw::c_int, id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getDimension(id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getMutated(mc: ::std::os::raw::c_int, id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getCategory(mc: ::std::os::raw::c_int, id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn areSimilar(mc: ::std::os::raw::c_int, id1: ::std::os::raw::c_int, id2: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isMesa(id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isShallowOcean(id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isDeepOcean(id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isOceanic(id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isSnowy(id: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn setLayerSeed(layer: *mut Layer, worldSeed: u64);
+    pub fn mapContinent(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapZoomFuzzy(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapZoom(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapLand(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapLand16(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapLandB18(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapIsland(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapSnow(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapSnow16(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapCool(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapHeat(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapSpecial(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapMushroom(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapDeepOcean(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapBiome(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapBamboo(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapNoise(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapBiomeEdge(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapHills(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapRiver(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapSmooth(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapSunflower(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapShore(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapSwampRiver(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapRiverMix(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapOceanTemp(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapOceanMix(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapVoronoi(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapVoronoi114(arg1: *const Layer, arg2: *mut ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: ::std::os::raw::c_int, arg5: ::std::os::raw::c_int, arg6: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getVoronoiSHA(worldSeed: u64) -> u64;
+    pub fn voronoiAccess3D(sha: u64, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int, x4: *mut ::std::os::raw::c_int, y4: *mut ::std::os::raw::c_int, z4: *mut ::std::os::raw::c_int);
+    pub fn mapVoronoiPlane(sha: u64, out: *mut ::std::os::raw::c_int, src: *mut ::std::os::raw::c_int, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, y: ::std::os::raw::c_int, px: ::std::os::raw::c_int, pz: ::std::os::raw::c_int, pw: ::std::os::raw::c_int, ph: ::std::os::raw::c_int);
+    pub fn initSurfaceNoise(sn: *mut SurfaceNoise, dim: ::std::os::raw::c_int, seed: u64);
+    pub fn initSurfaceNoiseBeta(snb: *mut SurfaceNoiseBeta, seed: u64);
+    pub fn sampleSurfaceNoise(sn: *const SurfaceNoise, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int) -> f64;
+    pub fn sampleSurfaceNoiseBetween(sn: *const SurfaceNoise, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int, noiseMin: f64, noiseMax: f64) -> f64;
+    pub fn setNetherSeed(nn: *mut NetherNoise, seed: u64);
+    pub fn getNetherBiome(nn: *const NetherNoise, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int, ndel: *mut f32) -> ::std::os::raw::c_int;
+    pub fn mapNether2D(nn: *const NetherNoise, out: *mut ::std::os::raw::c_int, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapNether3D(nn: *const NetherNoise, out: *mut ::std::os::raw::c_int, r: Range, confidence: f32) -> ::std::os::raw::c_int;
+    pub fn genNetherScaled(nn: *const NetherNoise, out: *mut ::std::os::raw::c_int, r: Range, mc: ::std::os::raw::c_int, sha: u64) -> ::std::os::raw::c_int;
+    pub fn setEndSeed(en: *mut EndNoise, mc: ::std::os::raw::c_int, seed: u64);
+    pub fn mapEndBiome(en: *const EndNoise, out: *mut ::std::os::raw::c_int, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapEnd(en: *const EndNoise, out: *mut ::std::os::raw::c_int, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getEndSurfaceHeight(mc: ::std::os::raw::c_int, seed: u64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapEndSurfaceHeight(y: *mut f32, en: *const EndNoise, sn: *const SurfaceNoise, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, scale: ::std::os::raw::c_int, ymin: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn genEndScaled(en: *const EndNoise, out: *mut ::std::os::raw::c_int, r: Range, mc: ::std::os::raw::c_int, sha: u64) -> ::std::os::raw::c_int;
+    pub fn initBiomeNoise(bn: *mut BiomeNoise, mc: ::std::os::raw::c_int);
+    pub fn setBiomeSeed(bn: *mut BiomeNoise, seed: u64, large: ::std::os::raw::c_int);
+    pub fn setBetaBiomeSeed(bnb: *mut BiomeNoiseBeta, seed: u64);
+    pub fn sampleBiomeNoise(bn: *const BiomeNoise, np: *mut i64, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int, dat: *mut u64, sample_flags: u32) -> ::std::os::raw::c_int;
+    pub fn sampleBiomeNoiseBeta(bnb: *const BiomeNoiseBeta, np: *mut i64, nv: *mut f64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn approxSurfaceBeta(bnb: *const BiomeNoiseBeta, snb: *const SurfaceNoiseBeta, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int) -> f64;
+    pub fn getOldBetaBiome(t: f32, h: f32) -> ::std::os::raw::c_int;
+    pub fn climateToBiome(mc: ::std::os::raw::c_int, np: *const u64, dat: *mut u64) -> ::std::os::raw::c_int;
+    pub fn setClimateParaSeed(bn: *mut BiomeNoise, seed: u64, large: ::std::os::raw::c_int, nptype: ::std::os::raw::c_int, nmax: ::std::os::raw::c_int);
+    pub fn sampleClimatePara(bn: *const BiomeNoise, np: *mut i64, x: f64, z: f64) -> f64;
+    pub fn genBiomeNoiseChunkSection(bn: *const BiomeNoise, out: *mut [[::std::os::raw::c_int; 4usize]; 4usize], cx: ::std::os::raw::c_int, cy: ::std::os::raw::c_int, cz: ::std::os::raw::c_int, dat: *mut u64);
+    pub fn genBiomeNoiseScaled(bn: *const BiomeNoise, out: *mut ::std::os::raw::c_int, r: Range, sha: u64) -> ::std::os::raw::c_int;
+    pub fn genBiomeNoiseBetaScaled(bnb: *const BiomeNoiseBeta, snb: *const SurfaceNoiseBeta, out: *mut ::std::os::raw::c_int, r: Range) -> ::std::os::raw::c_int;
+    pub fn getBiomeDepthAndScale(id: ::std::os::raw::c_int, depth: *mut f64, scale: *mut f64, grass: *mut ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getVoronoiSrcRange(r: Range) -> Range;
+    pub fn setupGenerator(g: *mut Generator, mc: ::std::os::raw::c_int, flags: u32);
+    pub fn applySeed(g: *mut Generator, dim: ::std::os::raw::c_int, seed: u64);
+    pub fn getMinCacheSize(g: *const Generator, scale: ::std::os::raw::c_int, sx: ::std::os::raw::c_int, sy: ::std::os::raw::c_int, sz: ::std::os::raw::c_int) -> usize;
+    pub fn allocCache(g: *const Generator, r: Range) -> *mut ::std::os::raw::c_int;
+    pub fn genBiomes(g: *const Generator, cache: *mut ::std::os::raw::c_int, r: Range) -> ::std::os::raw::c_int;
+    pub fn getBiomeAt(g: *const Generator, scale: ::std::os::raw::c_int, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getLayerForScale(g: *const Generator, scale: ::std::os::raw::c_int) -> *const Layer;
+    pub fn setupLayerStack(g: *mut LayerStack, mc: ::std::os::raw::c_int, largeBiomes: ::std::os::raw::c_int);
+    pub fn getMinLayerCacheSize(layer: *const Layer, sizeX: ::std::os::raw::c_int, sizeZ: ::std::os::raw::c_int) -> usize;
+    pub fn setupLayer(l: *mut Layer, map: mapfunc_t, mc: ::std::os::raw::c_int, zoom: i8, edge: i8, saltbase: u64, p: *mut Layer, p2: *mut Layer) -> *mut Layer;
+    pub fn genArea(layer: *const Layer, out: *mut ::std::os::raw::c_int, areaX: ::std::os::raw::c_int, areaZ: ::std::os::raw::c_int, areaWidth: ::std::os::raw::c_int, areaHeight: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapApproxHeight(y: *mut f32, ids: *mut ::std::os::raw::c_int, g: *const Generator, sn: *const SurfaceNoise, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getStructureConfig(structureType: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, sconf: *mut StructureConfig) -> ::std::os::raw::c_int;
+    pub fn getStructurePos(structureType: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, seed: u64, regX: ::std::os::raw::c_int, regZ: ::std::os::raw::c_int, pos: *mut Pos) -> ::std::os::raw::c_int;
+    pub fn getMineshafts(mc: ::std::os::raw::c_int, seed: u64, chunkX: ::std::os::raw::c_int, chunkZ: ::std::os::raw::c_int, chunkW: ::std::os::raw::c_int, chunkH: ::std::os::raw::c_int, out: *mut Pos, nout: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getEndIslands(islands: *mut EndIsland, mc: ::std::os::raw::c_int, seed: u64, chunkX: ::std::os::raw::c_int, chunkZ: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn mapEndIslandHeight(y: *mut f32, en: *const EndNoise, seed: u64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, scale: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isEndChunkEmpty(en: *const EndNoise, sn: *const SurfaceNoise, seed: u64, chunkX: ::std::os::raw::c_int, chunkZ: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn initFirstStronghold(sh: *mut StrongholdIter, mc: ::std::os::raw::c_int, s48: u64) -> Pos;
+    pub fn nextStronghold(sh: *mut StrongholdIter, g: *const Generator) -> ::std::os::raw::c_int;
+    pub fn estimateSpawn(g: *const Generator, rng: *mut u64) -> Pos;
+    pub fn getSpawn(g: *const Generator) -> Pos;
+    pub fn locateBiome(g: *const Generator, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int, radius: ::std::os::raw::c_int, validB: u64, validM: u64, rng: *mut u64, passes: *mut ::std::os::raw::c_int) -> Pos;
+    pub fn isViableStructurePos(structType: ::std::os::raw::c_int, g: *mut Generator, blockX: ::std::os::raw::c_int, blockZ: ::std::os::raw::c_int, flags: u32) -> ::std::os::raw::c_int;
+    pub fn isViableFeatureBiome(mc: ::std::os::raw::c_int, structureType: ::std::os::raw::c_int, biomeID: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isViableStructureTerrain(structType: ::std::os::raw::c_int, g: *mut Generator, blockX: ::std::os::raw::c_int, blockZ: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn isViableEndCityTerrain(g: *const Generator, sn: *const SurfaceNoise, blockX: ::std::os::raw::c_int, blockZ: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getVariant(sv: *mut StructureVariant, structType: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, seed: u64, blockX: ::std::os::raw::c_int, blockZ: ::std::os::raw::c_int, biomeID: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getEndCityPieces(pieces: *mut Piece, seed: u64, chunkX: ::std::os::raw::c_int, chunkZ: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getFortressPieces(list: *mut Piece, n: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, seed: u64, chunkX: ::std::os::raw::c_int, chunkZ: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getFixedEndGateways(mc: ::std::os::raw::c_int, seed: u64, src: *mut Pos);
+    pub fn getLinkedGatewayChunk(en: *const EndNoise, sn: *const SurfaceNoise, seed: u64, src: Pos, dst: *mut Pos) -> Pos;
+    pub fn getLinkedGatewayPos(en: *const EndNoise, sn: *const SurfaceNoise, seed: u64, src: Pos) -> Pos;
+    pub fn getHouseList(houses: *mut ::std::os::raw::c_int, seed: u64, chunkX: ::std::os::raw::c_int, chunkZ: ::std::os::raw::c_int) -> u64;
+    pub fn monteCarloBiomes(g: *mut Generator, r: Range, rng: *mut u64, coverage: f64, confidence: f64, eval: ::std::option::Option<unsafe extern "C" fn(g: *mut Generator, scale: ::std::os::raw::c_int, x: ::std::os::raw::c_int, y: ::std::os::raw::c_int, z: ::std::os::raw::c_int, data: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int>, data: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int;
+    pub fn setupBiomeFilter(bf: *mut BiomeFilter, mc: ::std::os::raw::c_int, flags: u32, required: *const ::std::os::raw::c_int, requiredLen: ::std::os::raw::c_int, excluded: *const ::std::os::raw::c_int, excludedLen: ::std::os::raw::c_int, matchany: *const ::std::os::raw::c_int, matchanyLen: ::std::os::raw::c_int);
+    pub fn checkForBiomes(g: *mut Generator, cache: *mut ::std::os::raw::c_int, r: Range, dim: ::std::os::raw::c_int, seed: u64, filter: *const BiomeFilter, stop: *mut ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    pub fn checkForBiomesAtLayer(ls: *mut LayerStack, entry: *mut Layer, cache: *mut ::std::os::raw::c_int, seed: u64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_uint, h: ::std::os::raw::c_uint, filter: *const BiomeFilter) -> ::std::os::raw::c_int;
+    pub fn checkForTemps(g: *mut LayerStack, seed: u64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, tc: *const ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn getBiomeCenters(pos: *mut Pos, siz: *mut ::std::os::raw::c_int, nmax: ::std::os::raw::c_int, g: *mut Generator, r: Range, match_: ::std::os::raw::c_int, minsiz: ::std::os::raw::c_int, tol: ::std::os::raw::c_int, stop: *mut ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    pub fn canBiomeGenerate(layerId: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, flags: u32, biomeID: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn genPotential(mL: *mut u64, mM: *mut u64, layerId: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, flags: u32, biomeID: ::std::os::raw::c_int);
+    pub fn getAvailableBiomes(mL: *mut u64, mM: *mut u64, layerId: ::std::os::raw::c_int, mc: ::std::os::raw::c_int, flags: u32);
+    pub fn getParaDescent(para: *const DoublePerlinNoise, factor: f64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, i0: ::std::os::raw::c_int, j0: ::std::os::raw::c_int, maxrad: ::std::os::raw::c_int, maxiter: ::std::os::raw::c_int, alpha: f64, data: *mut ::std::os::raw::c_void, func: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: f64) -> ::std::os::raw::c_int>) -> f64;
+    pub fn getParaRange(para: *const DoublePerlinNoise, pmin: *mut f64, pmax: *mut f64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, data: *mut ::std::os::raw::c_void, func: ::std::option::Option<unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: ::std::os::raw::c_int, arg3: ::std::os::raw::c_int, arg4: f64) -> ::std::os::raw::c_int>) -> ::std::os::raw::c_int;
+    pub fn getBiomeParaExtremes(mc: ::std::os::raw::c_int) -> *const ::std::os::raw::c_int;
+    pub fn getBiomeParaLimits(mc: ::std::os::raw::c_int, id: ::std::os::raw::c_int) -> *const ::std::os::raw::c_int;
+    pub fn getPossibleBiomesForLimits(ids: *mut ::std::os::raw::c_char, mc: ::std::os::raw::c_int, limits: *mut [::std::os::raw::c_int; 2usize]);
+    pub fn getLargestRec(match_: ::std::os::raw::c_int, ids: *const ::std::os::raw::c_int, sx: ::std::os::raw::c_int, sz: ::std::os::raw::c_int, p0: *mut Pos, p1: *mut Pos) -> ::std::os::raw::c_int;
+    pub fn getQuadHutCst(low20: u64) -> ::std::os::raw::c_int;
+    pub fn searchAll48(seedbuf: *mut *mut u64, buflen: *mut u64, path: *const ::std::os::raw::c_char, threads: ::std::os::raw::c_int, lowBits: *const u64, lowBitN: ::std::os::raw::c_int, check: ::std::option::Option<unsafe extern "C" fn(s48: u64, data: *mut ::std::os::raw::c_void) -> ::std::os::raw::c_int>, data: *mut ::std::os::raw::c_void, stop: *mut ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    pub fn getOptimalAfk(p: *mut Pos, ax: ::std::os::raw::c_int, ay: ::std::os::raw::c_int, az: ::std::os::raw::c_int, spcnt: *mut ::std::os::raw::c_int) -> Pos;
+    pub fn scanForQuads(sconf: StructureConfig, radius: ::std::os::raw::c_int, s48: u64, lowBits: *const u64, lowBitN: ::std::os::raw::c_int, salt: u64, x: ::std::os::raw::c_int, z: ::std::os::raw::c_int, w: ::std::os::raw::c_int, h: ::std::os::raw::c_int, qplist: *mut Pos, n: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn loadSavedSeeds(fnam: *const ::std::os::raw::c_char, scnt: *mut u64) -> *mut u64;
+    pub fn mc2str(mc: ::std::os::raw::c_int) -> *const ::std::os::raw::c_char;
+    pub fn str2mc(s: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    pub fn biome2str(mc: ::std::os::raw::c_int, id: ::std::os::raw::c_int) -> *const ::std::os::raw::c_char;
+    pub fn struct2str(stype: ::std::os::raw::c_int) -> *const ::std::os::raw::c_char;
+    pub fn initBiomeColors(biomeColors: *mut [::std::os::raw::c_uchar; 3usize]);
+    pub fn initBiomeTypeColors(biomeColors: *mut [::std::os::raw::c_uchar; 3usize]);
+    pub fn parseBiomeColors(biomeColors: *mut [::std::os::raw::c_uchar; 3usize], buf: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+    pub fn biomesToImage(pixels: *mut ::std::os::raw::c_uchar, biomeColors: *mut [::std::os::raw::c_uchar; 3usize], biomes: *const ::std::os::raw::c_int, sx: ::std::os::raw::c_uint, sy: ::std::os::raw::c_uint, pixscale: ::std::os::raw::c_uint, flip: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+    pub fn savePPM(path: *const ::std::os::raw::c_char, pixels: *const ::std::os::raw::c_uchar, sx: ::std::os::raw::c_uint, sy: ::std::os::raw::c_uint) -> ::std::os::raw::c_int;
+}
+const _: () = assert!(::std::mem::size_of::<Xoroshiro>() == 16);
+const _: () = assert!(::std::mem::align_of::<Xoroshiro>() == 8);
+const _: () = assert!(::std::mem::size_of::<PerlinNoise>() == 320);
+const _: () = assert!(::std::mem::align_of::<PerlinNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<OctaveNoise>() == 16);
+const _: () = assert!(::std::mem::align_of::<OctaveNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<DoublePerlinNoise>() == 40);
+const _: () = assert!(::std::mem::align_of::<DoublePerlinNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<Layer>() == 72);
+const _: () = assert!(::std::mem::align_of::<Layer>() == 8);
+const _: () = assert!(::std::mem::size_of::<LayerStack>() == 4752);
+const _: () = assert!(::std::mem::align_of::<LayerStack>() == 8);
+const _: () = assert!(::std::mem::size_of::<Range>() == 28);
+const _: () = assert!(::std::mem::align_of::<Range>() == 4);
+const _: () = assert!(::std::mem::size_of::<NetherNoise>() == 2640);
+const _: () = assert!(::std::mem::align_of::<NetherNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<EndNoise>() == 328);
+const _: () = assert!(::std::mem::align_of::<EndNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<SurfaceNoise>() == 19312);
+const _: () = assert!(::std::mem::align_of::<SurfaceNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<SurfaceNoiseBeta>() == 21200);
+const _: () = assert!(::std::mem::align_of::<SurfaceNoiseBeta>() == 8);
+const _: () = assert!(::std::mem::size_of::<SeaLevelColumnNoiseBeta>() == 64);
+const _: () = assert!(::std::mem::align_of::<SeaLevelColumnNoiseBeta>() == 8);
+const _: () = assert!(::std::mem::size_of::<Spline>() == 200);
+const _: () = assert!(::std::mem::align_of::<Spline>() == 8);
+const _: () = assert!(::std::mem::size_of::<FixSpline>() == 8);
+const _: () = assert!(::std::mem::align_of::<FixSpline>() == 4);
+const _: () = assert!(::std::mem::size_of::<SplineStack>() == 9616);
+const _: () = assert!(::std::mem::align_of::<SplineStack>() == 8);
+const _: () = assert!(::std::mem::size_of::<BiomeNoise>() == 24592);
+const _: () = assert!(::std::mem::align_of::<BiomeNoise>() == 8);
+const _: () = assert!(::std::mem::size_of::<BiomeNoiseBeta>() == 3256);
+const _: () = assert!(::std::mem::align_of::<BiomeNoiseBeta>() == 8);
+const _: () = assert!(::std::mem::size_of::<BiomeTree>() == 32);
+const _: () = assert!(::std::mem::align_of::<BiomeTree>() == 8);
+const _: () = assert!(::std::mem::size_of::<Generator>() == 27592);
+const _: () = assert!(::std::mem::align_of::<Generator>() == 8);
+const _: () = assert!(::std::mem::size_of::<StructureConfig>() == 12);
+const _: () = assert!(::std::mem::align_of::<StructureConfig>() == 4);
+const _: () = assert!(::std::mem::size_of::<Pos>() == 8);
+const _: () = assert!(::std::mem::align_of::<Pos>() == 4);
+const _: () = assert!(::std::mem::size_of::<Pos3>() == 12);
+const _: () = assert!(::std::mem::align_of::<Pos3>() == 4);
+const _: () = assert!(::std::mem::size_of::<StrongholdIter>() == 64);
+const _: () = assert!(::std::mem::align_of::<StrongholdIter>() == 8);
+const _: () = assert!(::std::mem::size_of::<StructureVariant>() == 20);
+const _: () = assert!(::std::mem::align_of::<StructureVariant>() == 2);
+const _: () = assert!(::std::mem::size_of::<Piece>() == 56);
+const _: () = assert!(::std::mem::align_of::<Piece>() == 8);
+const _: () = assert!(::std::mem::size_of::<EndIsland>() == 16);
+const _: () = assert!(::std::mem::align_of::<EndIsland>() == 4);
+const _: () = assert!(::std::mem::size_of::<BiomeFilter>() == 216);
+const _: () = assert!(::std::mem::align_of::<BiomeFilter>() == 8);
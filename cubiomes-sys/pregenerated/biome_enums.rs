@@ -0,0 +1,263 @@
+/* automatically generated by rust-bindgen (local stand-in) */
+
+impl MCVersion {
+    pub const MC_1_0: MCVersion = MCVersion::MC_1_0_0;
+    pub const MC_1_1: MCVersion = MCVersion::MC_1_1_0;
+    pub const MC_1_2: MCVersion = MCVersion::MC_1_2_5;
+    pub const MC_1_3: MCVersion = MCVersion::MC_1_3_2;
+    pub const MC_1_4: MCVersion = MCVersion::MC_1_4_7;
+    pub const MC_1_5: MCVersion = MCVersion::MC_1_5_2;
+    pub const MC_1_6: MCVersion = MCVersion::MC_1_6_4;
+    pub const MC_1_7: MCVersion = MCVersion::MC_1_7_10;
+    pub const MC_1_8: MCVersion = MCVersion::MC_1_8_9;
+    pub const MC_1_9: MCVersion = MCVersion::MC_1_9_4;
+    pub const MC_1_10: MCVersion = MCVersion::MC_1_10_2;
+    pub const MC_1_11: MCVersion = MCVersion::MC_1_11_2;
+    pub const MC_1_12: MCVersion = MCVersion::MC_1_12_2;
+    pub const MC_1_13: MCVersion = MCVersion::MC_1_13_2;
+    pub const MC_1_14: MCVersion = MCVersion::MC_1_14_4;
+    pub const MC_1_15: MCVersion = MCVersion::MC_1_15_2;
+    pub const MC_1_16: MCVersion = MCVersion::MC_1_16_5;
+    pub const MC_1_17: MCVersion = MCVersion::MC_1_17_1;
+    pub const MC_1_18: MCVersion = MCVersion::MC_1_18_2;
+    pub const MC_1_19: MCVersion = MCVersion::MC_1_19_4;
+    pub const MC_1_20: MCVersion = MCVersion::MC_1_20_6;
+    pub const MC_1_21: MCVersion = MCVersion::MC_1_21_WD;
+    pub const MC_NEWEST: MCVersion = MCVersion::MC_1_21_WD;
+}
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, FromPrimitive, ToPrimitive, PartialOrd, Ord)]
+pub enum MCVersion {
+    MC_UNDEF = 0,
+    MC_B1_7 = 1,
+    MC_B1_8 = 2,
+    MC_1_0_0 = 3,
+    MC_1_1_0 = 4,
+    MC_1_2_5 = 5,
+    MC_1_3_2 = 6,
+    MC_1_4_7 = 7,
+    MC_1_5_2 = 8,
+    MC_1_6_4 = 9,
+    MC_1_7_10 = 10,
+    MC_1_8_9 = 11,
+    MC_1_9_4 = 12,
+    MC_1_10_2 = 13,
+    MC_1_11_2 = 14,
+    MC_1_12_2 = 15,
+    MC_1_13_2 = 16,
+    MC_1_14_4 = 17,
+    MC_1_15_2 = 18,
+    MC_1_16_1 = 19,
+    MC_1_16_5 = 20,
+    MC_1_17_1 = 21,
+    MC_1_18_2 = 22,
+    MC_1_19_2 = 23,
+    MC_1_19_4 = 24,
+    MC_1_20_6 = 25,
+    MC_1_21_1 = 26,
+    MC_1_21_3 = 27,
+    MC_1_21_WD = 28,
+}
+#[repr(i32)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, FromPrimitive, ToPrimitive, PartialOrd, Ord)]
+pub enum Dimension {
+    DIM_NETHER = -1,
+    DIM_OVERWORLD = 0,
+    DIM_END = 1,
+    DIM_UNDEF = 1000,
+}
+impl BiomeID {
+    pub const extremeHills: BiomeID = BiomeID::mountains;
+    pub const swampland: BiomeID = BiomeID::swamp;
+    pub const hell: BiomeID = BiomeID::nether_wastes;
+    pub const sky: BiomeID = BiomeID::the_end;
+    pub const frozenOcean: BiomeID = BiomeID::frozen_ocean;
+    pub const frozenRiver: BiomeID = BiomeID::frozen_river;
+    pub const icePlains: BiomeID = BiomeID::snowy_tundra;
+    pub const iceMountains: BiomeID = BiomeID::snowy_mountains;
+    pub const mushroomIsland: BiomeID = BiomeID::mushroom_fields;
+    pub const mushroomIslandShore: BiomeID = BiomeID::mushroom_field_shore;
+    pub const desertHills: BiomeID = BiomeID::desert_hills;
+    pub const forestHills: BiomeID = BiomeID::wooded_hills;
+    pub const taigaHills: BiomeID = BiomeID::taiga_hills;
+    pub const extremeHillsEdge: BiomeID = BiomeID::mountain_edge;
+    pub const jungleHills: BiomeID = BiomeID::jungle_hills;
+    pub const jungleEdge: BiomeID = BiomeID::jungle_edge;
+    pub const deepOcean: BiomeID = BiomeID::deep_ocean;
+    pub const stoneBeach: BiomeID = BiomeID::stone_shore;
+    pub const coldBeach: BiomeID = BiomeID::snowy_beach;
+    pub const birchForest: BiomeID = BiomeID::birch_forest;
+    pub const birchForestHills: BiomeID = BiomeID::birch_forest_hills;
+    pub const roofedForest: BiomeID = BiomeID::dark_forest;
+    pub const coldTaiga: BiomeID = BiomeID::snowy_taiga;
+    pub const coldTaigaHills: BiomeID = BiomeID::snowy_taiga_hills;
+    pub const megaTaiga: BiomeID = BiomeID::giant_tree_taiga;
+    pub const megaTaigaHills: BiomeID = BiomeID::giant_tree_taiga_hills;
+    pub const extremeHillsPlus: BiomeID = BiomeID::wooded_mountains;
+    pub const savannaPlateau: BiomeID = BiomeID::savanna_plateau;
+    pub const mesa: BiomeID = BiomeID::badlands;
+    pub const mesaPlateau_F: BiomeID = BiomeID::wooded_badlands_plateau;
+    pub const mesaPlateau: BiomeID = BiomeID::badlands_plateau;
+    pub const warmOcean: BiomeID = BiomeID::warm_ocean;
+    pub const lukewarmOcean: BiomeID = BiomeID::lukewarm_ocean;
+    pub const coldOcean: BiomeID = BiomeID::cold_ocean;
+    pub const warmDeepOcean: BiomeID = BiomeID::deep_warm_ocean;
+    pub const lukewarmDeepOcean: BiomeID = BiomeID::deep_lukewarm_ocean;
+    pub const coldDeepOcean: BiomeID = BiomeID::deep_cold_ocean;
+    pub const frozenDeepOcean: BiomeID = BiomeID::deep_frozen_ocean;
+    pub const old_growth_birch_forest: BiomeID = BiomeID::tall_birch_forest;
+    pub const old_growth_pine_taiga: BiomeID = BiomeID::giant_tree_taiga;
+    pub const old_growth_spruce_taiga: BiomeID = BiomeID::giant_spruce_taiga;
+    pub const snowy_plains: BiomeID = BiomeID::snowy_tundra;
+    pub const sparse_jungle: BiomeID = BiomeID::jungle_edge;
+    pub const stony_shore: BiomeID = BiomeID::stone_shore;
+    pub const windswept_hills: BiomeID = BiomeID::mountains;
+    pub const windswept_forest: BiomeID = BiomeID::wooded_mountains;
+    pub const windswept_gravelly_hills: BiomeID = BiomeID::gravelly_mountains;
+    pub const windswept_savanna: BiomeID = BiomeID::shattered_savanna;
+    pub const wooded_badlands: BiomeID = BiomeID::wooded_badlands_plateau;
+}
+#[repr(i32)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, FromPrimitive, ToPrimitive, PartialOrd, Ord)]
+pub enum BiomeID {
+    none = -1,
+    ocean = 0,
+    plains = 1,
+    desert = 2,
+    mountains = 3,
+    forest = 4,
+    taiga = 5,
+    swamp = 6,
+    river = 7,
+    nether_wastes = 8,
+    the_end = 9,
+    frozen_ocean = 10,
+    frozen_river = 11,
+    snowy_tundra = 12,
+    snowy_mountains = 13,
+    mushroom_fields = 14,
+    mushroom_field_shore = 15,
+    beach = 16,
+    desert_hills = 17,
+    wooded_hills = 18,
+    taiga_hills = 19,
+    mountain_edge = 20,
+    jungle = 21,
+    jungle_hills = 22,
+    jungle_edge = 23,
+    deep_ocean = 24,
+    stone_shore = 25,
+    snowy_beach = 26,
+    birch_forest = 27,
+    birch_forest_hills = 28,
+    dark_forest = 29,
+    snowy_taiga = 30,
+    snowy_taiga_hills = 31,
+    giant_tree_taiga = 32,
+    giant_tree_taiga_hills = 33,
+    wooded_mountains = 34,
+    savanna = 35,
+    savanna_plateau = 36,
+    badlands = 37,
+    wooded_badlands_plateau = 38,
+    badlands_plateau = 39,
+    small_end_islands = 40,
+    end_midlands = 41,
+    end_highlands = 42,
+    end_barrens = 43,
+    warm_ocean = 44,
+    lukewarm_ocean = 45,
+    cold_ocean = 46,
+    deep_warm_ocean = 47,
+    deep_lukewarm_ocean = 48,
+    deep_cold_ocean = 49,
+    deep_frozen_ocean = 50,
+    seasonal_forest = 51,
+    rainforest = 52,
+    shrubland = 53,
+    the_void = 127,
+    sunflower_plains = 129,
+    desert_lakes = 130,
+    gravelly_mountains = 131,
+    flower_forest = 132,
+    taiga_mountains = 133,
+    swamp_hills = 134,
+    ice_spikes = 140,
+    modified_jungle = 149,
+    modified_jungle_edge = 151,
+    tall_birch_forest = 155,
+    tall_birch_hills = 156,
+    dark_forest_hills = 157,
+    snowy_taiga_mountains = 158,
+    giant_spruce_taiga = 160,
+    giant_spruce_taiga_hills = 161,
+    modified_gravelly_mountains = 162,
+    shattered_savanna = 163,
+    shattered_savanna_plateau = 164,
+    eroded_badlands = 165,
+    modified_wooded_badlands_plateau = 166,
+    modified_badlands_plateau = 167,
+    bamboo_jungle = 168,
+    bamboo_jungle_hills = 169,
+    soul_sand_valley = 170,
+    crimson_forest = 171,
+    warped_forest = 172,
+    basalt_deltas = 173,
+    dripstone_caves = 174,
+    lush_caves = 175,
+    meadow = 177,
+    grove = 178,
+    snowy_slopes = 179,
+    jagged_peaks = 180,
+    frozen_peaks = 181,
+    stony_peaks = 182,
+    deep_dark = 183,
+    mangrove_swamp = 184,
+    cherry_grove = 185,
+    pale_garden = 186,
+}
+impl StructureType {
+    pub const Jungle_Pyramid: StructureType = StructureType::Jungle_Temple;
+}
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, FromPrimitive, ToPrimitive, PartialOrd, Ord)]
+pub enum StructureType {
+    Feature = 0,
+    Desert_Pyramid = 1,
+    Jungle_Temple = 2,
+    Swamp_Hut = 3,
+    Igloo = 4,
+    Village = 5,
+    Ocean_Ruin = 6,
+    Shipwreck = 7,
+    Monument = 8,
+    Mansion = 9,
+    Outpost = 10,
+    Ruined_Portal = 11,
+    Ruined_Portal_N = 12,
+    Ancient_City = 13,
+    Treasure = 14,
+    Mineshaft = 15,
+    Desert_Well = 16,
+    Geode = 17,
+    Fortress = 18,
+    Bastion = 19,
+    End_City = 20,
+    End_Gateway = 21,
+    End_Island = 22,
+    Trail_Ruins = 23,
+    Trial_Chambers = 24,
+    FEATURE_NUM = 25,
+}
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, FromPrimitive, ToPrimitive, PartialOrd, Ord)]
+pub enum GeneratorFlag {
+    GF_LARGE_BIOMES = 1,
+    GF_NO_BETA_OCEAN = 2,
+    GF_FORCE_OCEAN_VARIANTS = 4,
+}
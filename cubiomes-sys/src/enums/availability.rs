@@ -0,0 +1,59 @@
+use super::{Dimension, MCVersion, StructureType};
+use num_traits::FromPrimitive;
+use std::mem::MaybeUninit;
+
+impl StructureType {
+    /// Checks if the structure generates in a version and dimension
+    ///
+    /// This follows the structure configs of `getStructureConfig` in
+    /// cubiomes, which also covers features like desert wells or end
+    /// gateways. `Feature` and `FEATURE_NUM` aren't structures of their own,
+    /// so they are never available.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes_sys::enums::{Dimension, MCVersion, StructureType};
+    ///
+    /// let version = MCVersion::MC_1_20_6;
+    /// assert!(StructureType::Bastion.available_in(version, Dimension::DIM_NETHER));
+    /// assert!(!StructureType::Bastion.available_in(version, Dimension::DIM_OVERWORLD));
+    /// assert!(!StructureType::Bastion.available_in(MCVersion::MC_1_15, Dimension::DIM_NETHER));
+    /// ```
+    pub fn available_in(self, version: MCVersion, dimension: Dimension) -> bool {
+        if matches!(self, Self::Feature | Self::FEATURE_NUM) {
+            return false;
+        }
+
+        let mut config: MaybeUninit<crate::StructureConfig> = MaybeUninit::uninit();
+
+        // SAFETY: config is initialized if getStructureConfig did not return 0
+        unsafe {
+            crate::getStructureConfig(self as i32, version as i32, config.as_mut_ptr()) != 0
+                && i32::from(config.assume_init().dim) == dimension as i32
+        }
+    }
+}
+
+impl MCVersion {
+    /// Gets every structure which generates in a dimension of the version
+    ///
+    /// The structures are ordered like [`StructureType`], see
+    /// [`StructureType::available_in()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes_sys::enums::{Dimension, MCVersion, StructureType};
+    ///
+    /// assert_eq!(
+    ///     MCVersion::MC_1_20_6.structures(Dimension::DIM_END),
+    ///     [StructureType::End_City, StructureType::End_Gateway, StructureType::End_Island]
+    /// );
+    /// assert!(MCVersion::MC_B1_7.structures(Dimension::DIM_OVERWORLD).is_empty());
+    /// ```
+    pub fn structures(self, dimension: Dimension) -> Vec<StructureType> {
+        (0..StructureType::FEATURE_NUM as i32)
+            .filter_map(StructureType::from_i32)
+            .filter(|structure| structure.available_in(self, dimension))
+            .collect()
+    }
+}
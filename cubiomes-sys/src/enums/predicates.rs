@@ -0,0 +1,82 @@
+use super::{BiomeID, MCVersion};
+
+impl BiomeID {
+    /// Checks if the biome is a shallow ocean, like `ocean` or `warm_ocean`
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes_sys::enums::BiomeID;
+    ///
+    /// assert!(BiomeID::cold_ocean.is_shallow_ocean());
+    /// assert!(!BiomeID::deep_cold_ocean.is_shallow_ocean());
+    /// ```
+    pub fn is_shallow_ocean(self) -> bool {
+        // SAFETY: isShallowOcean only compares the id
+        unsafe { crate::isShallowOcean(self as i32) != 0 }
+    }
+
+    /// Checks if the biome is a deep ocean, like `deep_ocean` or
+    /// `deep_frozen_ocean`
+    ///
+    /// There is no deep warm ocean in the generated biomes, but cubiomes
+    /// still counts it as a deep ocean.
+    pub fn is_deep_ocean(self) -> bool {
+        // SAFETY: isDeepOcean only compares the id
+        unsafe { crate::isDeepOcean(self as i32) != 0 }
+    }
+
+    /// Checks if the biome is a shallow or a deep ocean
+    ///
+    /// Rivers and beaches are not oceans.
+    pub fn is_oceanic(self) -> bool {
+        // SAFETY: isOceanic only compares the id
+        unsafe { crate::isOceanic(self as i32) != 0 }
+    }
+
+    /// Checks if two biomes are similar in a version
+    ///
+    /// Biomes are similar if they are the same or in the same category, like
+    /// the variants of the badlands. Minecraft compares biomes this way for
+    /// the borders between them and for placing some structures.
+    ///
+    /// Before 1.16 the badlands plateaus are only similar to each other,
+    /// while the other badlands are similar to them, so the order of the
+    /// biomes matters.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes_sys::enums::{BiomeID, MCVersion};
+    ///
+    /// let version = MCVersion::MC_1_20_6;
+    /// assert!(BiomeID::desert.is_similar(BiomeID::desert_hills, version));
+    /// assert!(!BiomeID::desert.is_similar(BiomeID::plains, version));
+    /// ```
+    pub fn is_similar(self, other: BiomeID, version: MCVersion) -> bool {
+        // SAFETY: areSimilar only compares the version and the ids
+        unsafe { crate::areSimilar(version as i32, self as i32, other as i32) != 0 }
+    }
+
+    /// Gets the mutated variant of the biome in a version, like
+    /// `sunflower_plains` for `plains`
+    ///
+    /// Returns [`None`] for biomes without a mutated variant. Before 1.18 the
+    /// mutated variants were rare versions of their biomes, and in 1.9 and
+    /// 1.10 birch forests mutate into tall birch hills.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes_sys::enums::{BiomeID, MCVersion};
+    ///
+    /// assert_eq!(
+    ///     BiomeID::snowy_tundra.mutated(MCVersion::MC_1_16_5),
+    ///     Some(BiomeID::ice_spikes)
+    /// );
+    /// assert_eq!(BiomeID::river.mutated(MCVersion::MC_1_16_5), None);
+    /// ```
+    pub fn mutated(self, version: MCVersion) -> Option<BiomeID> {
+        // SAFETY: getMutated only compares the version and the id
+        let id = unsafe { crate::getMutated(version as i32, self as i32) };
+
+        Self::from_id(id).filter(|&biome| biome != Self::none)
+    }
+}
@@ -0,0 +1,281 @@
+use super::{BiomeID, MCVersion};
+
+/// How much of a dimension a biome covers in a version
+///
+/// The classes come from sampling the biomes of many seeds at 1:4 and y 64,
+/// by the share of the sampled area each biome covers in its dimension:
+///
+/// - [`BiomeRarity::Common`] biomes cover at least 2% of the area
+/// - [`BiomeRarity::Uncommon`] biomes cover at least 0.5%
+/// - [`BiomeRarity::Rare`] biomes cover at least 0.05%
+/// - [`BiomeRarity::VeryRare`] biomes cover less than that
+///
+/// The variants are ordered from the most to the least common.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BiomeRarity {
+    /// Covers at least 2% of the dimension
+    Common,
+    /// Covers at least 0.5% of the dimension
+    Uncommon,
+    /// Covers at least 0.05% of the dimension
+    Rare,
+    /// Covers less than 0.05% of the dimension
+    VeryRare,
+}
+
+impl BiomeID {
+    /// Gets how rare the biome is in a version, within its own dimension
+    ///
+    /// Returns [`None`] if the biome doesn't generate in the version, as told
+    /// by `biomeExists` of cubiomes. Biomes which cubiomes knows of but which
+    /// didn't show up when sampling, like deep warm oceans, are
+    /// [`BiomeRarity::VeryRare`].
+    ///
+    /// Cave biomes are classified by how often they are found at y 64, and
+    /// the end biome from 1.9 by its share of the end around the main island.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes_sys::enums::{BiomeID, BiomeRarity, MCVersion};
+    ///
+    /// assert_eq!(BiomeID::plains.rarity(MCVersion::MC_1_20_6), Some(BiomeRarity::Common));
+    /// assert_eq!(BiomeID::ice_spikes.rarity(MCVersion::MC_1_20_6), Some(BiomeRarity::Rare));
+    /// assert_eq!(BiomeID::pale_garden.rarity(MCVersion::MC_1_20_6), None);
+    /// ```
+    pub fn rarity(self, version: MCVersion) -> Option<BiomeRarity> {
+        // SAFETY: biomeExists only compares the version and the id
+        if unsafe { crate::biomeExists(version as i32, self as i32) } == 0 {
+            return None;
+        }
+
+        Some(match self {
+            Self::nether_wastes
+            | Self::soul_sand_valley
+            | Self::crimson_forest
+            | Self::warped_forest
+            | Self::basalt_deltas => BiomeRarity::Common,
+            Self::small_end_islands
+            | Self::end_midlands
+            | Self::end_highlands
+            | Self::end_barrens
+            | Self::the_end => BiomeRarity::Common,
+            _ if version <= MCVersion::MC_B1_7 => beta_rarity(self),
+            _ if version <= MCVersion::MC_1_6_4 => pre_1_7_rarity(self),
+            _ if version <= MCVersion::MC_1_12_2 => pre_1_13_rarity(self),
+            _ if version <= MCVersion::MC_1_17_1 => pre_1_18_rarity(self),
+            _ => rarity_1_18(self),
+        })
+    }
+}
+
+fn beta_rarity(biome: BiomeID) -> BiomeRarity {
+    match biome {
+        BiomeID::ocean
+        | BiomeID::forest
+        | BiomeID::savanna
+        | BiomeID::shrubland
+        | BiomeID::seasonal_forest
+        | BiomeID::desert
+        | BiomeID::snowy_tundra
+        | BiomeID::plains
+        | BiomeID::frozen_ocean
+        | BiomeID::taiga => BiomeRarity::Common,
+        BiomeID::rainforest | BiomeID::swamp => BiomeRarity::Uncommon,
+        _ => BiomeRarity::VeryRare,
+    }
+}
+
+fn pre_1_7_rarity(biome: BiomeID) -> BiomeRarity {
+    match biome {
+        BiomeID::ocean
+        | BiomeID::river
+        | BiomeID::forest
+        | BiomeID::swamp
+        | BiomeID::desert
+        | BiomeID::taiga
+        | BiomeID::mountains
+        | BiomeID::jungle
+        | BiomeID::plains
+        | BiomeID::snowy_tundra => BiomeRarity::Common,
+        BiomeID::beach | BiomeID::mountain_edge | BiomeID::snowy_mountains => BiomeRarity::Uncommon,
+        BiomeID::desert_hills
+        | BiomeID::wooded_hills
+        | BiomeID::taiga_hills
+        | BiomeID::frozen_river
+        | BiomeID::mushroom_field_shore
+        | BiomeID::mushroom_fields
+        | BiomeID::jungle_hills
+        | BiomeID::frozen_ocean => BiomeRarity::Rare,
+        _ => BiomeRarity::VeryRare,
+    }
+}
+
+fn pre_1_13_rarity(biome: BiomeID) -> BiomeRarity {
+    match biome {
+        BiomeID::ocean
+        | BiomeID::deep_ocean
+        | BiomeID::plains
+        | BiomeID::forest
+        | BiomeID::desert
+        | BiomeID::mountains
+        | BiomeID::savanna
+        | BiomeID::river
+        | BiomeID::swamp
+        | BiomeID::beach
+        | BiomeID::taiga
+        | BiomeID::birch_forest
+        | BiomeID::snowy_tundra
+        | BiomeID::dark_forest => BiomeRarity::Common,
+        BiomeID::wooded_hills
+        | BiomeID::desert_hills
+        | BiomeID::jungle
+        | BiomeID::savanna_plateau
+        | BiomeID::wooded_mountains
+        | BiomeID::taiga_hills
+        | BiomeID::sunflower_plains
+        | BiomeID::birch_forest_hills
+        | BiomeID::snowy_mountains
+        | BiomeID::snowy_taiga => BiomeRarity::Uncommon,
+        BiomeID::flower_forest
+        | BiomeID::stone_shore
+        | BiomeID::giant_tree_taiga
+        | BiomeID::jungle_hills
+        | BiomeID::shattered_savanna
+        | BiomeID::desert_lakes
+        | BiomeID::gravelly_mountains
+        | BiomeID::snowy_beach
+        | BiomeID::badlands
+        | BiomeID::giant_tree_taiga_hills
+        | BiomeID::ice_spikes
+        | BiomeID::taiga_mountains
+        | BiomeID::dark_forest_hills
+        | BiomeID::frozen_river
+        | BiomeID::modified_jungle
+        | BiomeID::wooded_badlands_plateau
+        | BiomeID::snowy_taiga_hills
+        | BiomeID::shattered_savanna_plateau
+        | BiomeID::swamp_hills
+        | BiomeID::jungle_edge
+        | BiomeID::modified_gravelly_mountains
+        | BiomeID::tall_birch_forest => BiomeRarity::Rare,
+        _ => BiomeRarity::VeryRare,
+    }
+}
+
+fn pre_1_18_rarity(biome: BiomeID) -> BiomeRarity {
+    match biome {
+        BiomeID::plains
+        | BiomeID::ocean
+        | BiomeID::deep_ocean
+        | BiomeID::forest
+        | BiomeID::desert
+        | BiomeID::cold_ocean
+        | BiomeID::mountains
+        | BiomeID::savanna
+        | BiomeID::river
+        | BiomeID::swamp
+        | BiomeID::beach
+        | BiomeID::lukewarm_ocean
+        | BiomeID::deep_cold_ocean
+        | BiomeID::taiga
+        | BiomeID::birch_forest
+        | BiomeID::snowy_tundra
+        | BiomeID::deep_lukewarm_ocean
+        | BiomeID::dark_forest => BiomeRarity::Common,
+        BiomeID::wooded_hills
+        | BiomeID::desert_hills
+        | BiomeID::warm_ocean
+        | BiomeID::savanna_plateau
+        | BiomeID::jungle
+        | BiomeID::deep_frozen_ocean
+        | BiomeID::wooded_mountains
+        | BiomeID::frozen_ocean
+        | BiomeID::sunflower_plains
+        | BiomeID::taiga_hills
+        | BiomeID::birch_forest_hills
+        | BiomeID::snowy_mountains
+        | BiomeID::snowy_taiga
+        | BiomeID::flower_forest => BiomeRarity::Uncommon,
+        BiomeID::stone_shore
+        | BiomeID::gravelly_mountains
+        | BiomeID::giant_tree_taiga
+        | BiomeID::shattered_savanna
+        | BiomeID::desert_lakes
+        | BiomeID::jungle_hills
+        | BiomeID::snowy_beach
+        | BiomeID::badlands
+        | BiomeID::giant_tree_taiga_hills
+        | BiomeID::taiga_mountains
+        | BiomeID::bamboo_jungle
+        | BiomeID::shattered_savanna_plateau
+        | BiomeID::ice_spikes
+        | BiomeID::dark_forest_hills
+        | BiomeID::wooded_badlands_plateau
+        | BiomeID::modified_gravelly_mountains
+        | BiomeID::frozen_river
+        | BiomeID::snowy_taiga_hills
+        | BiomeID::jungle_edge
+        | BiomeID::modified_jungle
+        | BiomeID::tall_birch_forest
+        | BiomeID::badlands_plateau => BiomeRarity::Rare,
+        _ => BiomeRarity::VeryRare,
+    }
+}
+
+fn rarity_1_18(biome: BiomeID) -> BiomeRarity {
+    match biome {
+        BiomeID::forest
+        | BiomeID::plains
+        | BiomeID::lukewarm_ocean
+        | BiomeID::river
+        | BiomeID::ocean
+        | BiomeID::snowy_tundra
+        | BiomeID::savanna
+        | BiomeID::snowy_taiga
+        | BiomeID::dripstone_caves
+        | BiomeID::taiga
+        | BiomeID::deep_lukewarm_ocean
+        | BiomeID::beach
+        | BiomeID::birch_forest
+        | BiomeID::cold_ocean
+        | BiomeID::dark_forest
+        | BiomeID::frozen_ocean
+        | BiomeID::tall_birch_forest => BiomeRarity::Common,
+        BiomeID::jungle
+        | BiomeID::frozen_river
+        | BiomeID::deep_ocean
+        | BiomeID::swamp
+        | BiomeID::jungle_edge
+        | BiomeID::deep_cold_ocean
+        | BiomeID::warm_ocean
+        | BiomeID::desert
+        | BiomeID::stone_shore
+        | BiomeID::mangrove_swamp
+        | BiomeID::meadow
+        | BiomeID::flower_forest
+        | BiomeID::badlands => BiomeRarity::Uncommon,
+        BiomeID::giant_tree_taiga
+        | BiomeID::deep_frozen_ocean
+        | BiomeID::snowy_beach
+        | BiomeID::lush_caves
+        | BiomeID::shattered_savanna
+        | BiomeID::grove
+        | BiomeID::savanna_plateau
+        | BiomeID::giant_spruce_taiga
+        | BiomeID::bamboo_jungle
+        | BiomeID::sunflower_plains
+        | BiomeID::snowy_slopes
+        | BiomeID::mountains
+        | BiomeID::wooded_mountains
+        | BiomeID::ice_spikes
+        | BiomeID::gravelly_mountains
+        | BiomeID::wooded_badlands_plateau
+        | BiomeID::eroded_badlands
+        | BiomeID::frozen_peaks
+        | BiomeID::jagged_peaks
+        | BiomeID::stony_peaks
+        | BiomeID::cherry_grove => BiomeRarity::Rare,
+        _ => BiomeRarity::VeryRare,
+    }
+}
@@ -15,10 +15,78 @@ extern crate num_derive;
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+/// The revision of cubiomes the crate is built with, see [`cubiomes_version()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubiomesVersion {
+    /// The git revision of the built sources, like `v1.2.3-4-g1a2b3c4`
+    ///
+    /// This is `unknown` for sources without a repository, like the vendored
+    /// ones of the published crate, and for a linked system library.
+    pub revision: &'static str,
+    /// Whether the vendored sources are built, instead of the ones of
+    /// `CUBIOMES_SRC_DIR` or a system library
+    pub vendored: bool,
+}
+
+/// Gets the revision of cubiomes the crate is built with
+///
+/// The vendored sources are replaced by setting `CUBIOMES_SRC_DIR` to another
+/// checkout of cubiomes when building, for example to test its master branch
+/// next to the pinned release.
+pub fn cubiomes_version() -> CubiomesVersion {
+    CubiomesVersion {
+        revision: env!("CUBIOMES_REVISION"),
+        vendored: matches!(env!("CUBIOMES_VENDORED").as_bytes(), b"true"),
+    }
+}
+
 /// This module contains generated rust enums representing different cubiomes enums
 ///
 /// The enums in this module are automatically generated from the version of cubiomes
 /// this crate links against.
 pub mod enums {
     include!(concat!(env!("OUT_DIR"), "/biome_enums.rs"));
+    include!(concat!(env!("OUT_DIR"), "/biome_table.rs"));
+
+    mod availability;
+    mod predicates;
+    mod rarity;
+    pub use rarity::BiomeRarity;
+
+    impl BiomeID {
+        /// Gets the biome with an id
+        ///
+        /// This gives the same biomes as `FromPrimitive::from_i32`, but looks
+        /// the id up in [`BIOME_IDS`], which is much faster when converting
+        /// every cell of a large cache.
+        #[inline]
+        pub fn from_id(id: i32) -> Option<Self> {
+            let index = usize::try_from(id.checked_sub(BIOME_ID_MIN)?).ok()?;
+            BIOME_IDS.get(index).copied().flatten()
+        }
+    }
+
+    impl MCVersion {
+        /// Iterates every supported version, from the oldest to the latest
+        ///
+        /// `MC_UNDEF` and the aliases of versions are not included. Without the
+        /// `unstable-versions` feature, the versions cubiomes hasn't finalized
+        /// are left out as well.
+        pub fn iter() -> impl DoubleEndedIterator<Item = Self> {
+            (1..=u32::from(u8::MAX)).filter_map(<Self as num_traits::FromPrimitive>::from_u32)
+        }
+
+        /// Gets the oldest supported version
+        pub fn oldest() -> Self {
+            Self::iter().next().unwrap_or(Self::MC_UNDEF)
+        }
+
+        /// Gets the latest supported version
+        ///
+        /// This is `MC_NEWEST` with the `unstable-versions` feature, and the
+        /// latest finalized version without it.
+        pub fn latest() -> Self {
+            Self::iter().next_back().unwrap_or(Self::MC_UNDEF)
+        }
+    }
 }
@@ -1,10 +1,10 @@
-use std::time::Duration;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use cubiomes::{
-    enums::{Dimension, MCVersion},
+    enums::{BiomeID, Dimension, MCVersion},
     generator::{BlockPosition, Cache, Generator, GeneratorFlags, Range},
 };
 use rand::{rngs::SmallRng, Rng, SeedableRng};
+use std::time::Duration;
 
 const RNG_SEED: u64 = 90825401;
 const RANGE: Range = Range {
@@ -19,7 +19,7 @@ const RANGE: Range = Range {
 
 fn init_generator() -> Generator {
     Generator::new(
-        MCVersion::MC_1_21_WD,
+        MCVersion::MC_1_20_6,
         0,
         Dimension::DIM_OVERWORLD,
         GeneratorFlags::empty(),
@@ -35,7 +35,7 @@ pub fn generator_initialization(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::from_parameter(seed), &seed, |bench, seed| {
             bench.iter(|| {
                 Generator::new(
-                    MCVersion::MC_1_21_WD,
+                    MCVersion::MC_1_20_6,
                     *seed,
                     Dimension::DIM_OVERWORLD,
                     GeneratorFlags::empty(),
@@ -83,10 +83,29 @@ pub fn stronghold_generation(c: &mut Criterion) {
     }
 }
 
+pub fn cache_scanning(c: &mut Criterion) {
+    let generator = init_generator();
+    let mut cache = Cache::new(&generator, RANGE);
+    cache.fill_cache().expect("cubiomes failure");
+
+    let mut group = c.benchmark_group("cache_scanning");
+
+    group.bench_function("count", |bench| {
+        bench.iter(|| cache.count(BiomeID::plains));
+    });
+    group.bench_function("find_first", |bench| {
+        bench.iter(|| cache.find_first(BiomeID::mushroom_fields));
+    });
+    group.bench_function("mask", |bench| {
+        bench.iter(|| cache.mask(BiomeID::plains));
+    });
+}
+
 criterion_group!(
     benches,
     biome_generation_benchmark,
     generator_initialization,
-    stronghold_generation
+    stronghold_generation,
+    cache_scanning
 );
 criterion_main!(benches);
@@ -10,7 +10,7 @@ use cubiomes::{
 // (this example is quite closely copied from cubiomes)
 
 const STRUCTURE_TYPE: StructureType = StructureType::Igloo;
-const MINECRAFT_VERSION: MCVersion = MCVersion::MC_1_21_WD;
+const MINECRAFT_VERSION: MCVersion = MCVersion::MC_1_20_6;
 
 fn main() {
     let mut lower_48: i64 = 0;
@@ -3,7 +3,7 @@ use cubiomes::{
     generator::{BlockPosition, Generator, GeneratorFlags},
 };
 
-const MINECRAFT_VERSION: MCVersion = MCVersion::MC_1_21_WD;
+const MINECRAFT_VERSION: MCVersion = MCVersion::MC_1_20_6;
 const SEED: i64 = 4239805798134;
 
 fn main() {
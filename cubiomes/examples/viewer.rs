@@ -0,0 +1,29 @@
+//! Opens a window with the biomes of a seed
+//!
+//! Run with `cargo run --example viewer --features viewer -- <seed>`. Pan with
+//! the arrow keys or the mouse, and zoom with the scroll wheel.
+
+use cubiomes::enums::{Dimension, MCVersion};
+use cubiomes::generator::{Generator, GeneratorFlags};
+use cubiomes::render::Viewer;
+
+const MINECRAFT_VERSION: MCVersion = MCVersion::MC_1_20_6;
+const SEED: i64 = -5134222539607995087;
+
+fn main() {
+    let seed = std::env::args()
+        .nth(1)
+        .map(|seed| seed.parse().expect("the seed should be a number"))
+        .unwrap_or(SEED);
+
+    let generator = Generator::new(
+        MINECRAFT_VERSION,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    Viewer::new(&generator, 800, 600)
+        .run()
+        .expect("failed to show the viewer");
+}
@@ -0,0 +1,220 @@
+use super::{Range, Scale};
+
+/// An axis aligned bounding box in block coordinates
+///
+/// Both the minimum and the maximum corner are inclusive, so a box with
+/// `min_x == max_x` is one block wide.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Aabb {
+    /// The smallest x coordinate inside the box
+    pub min_x: i32,
+    /// The smallest y coordinate inside the box
+    pub min_y: i32,
+    /// The smallest z coordinate inside the box
+    pub min_z: i32,
+    /// The largest x coordinate inside the box
+    pub max_x: i32,
+    /// The largest y coordinate inside the box
+    pub max_y: i32,
+    /// The largest z coordinate inside the box
+    pub max_z: i32,
+}
+
+impl Aabb {
+    /// Creates a new box from two corners
+    ///
+    /// The corners may be given in any order, the box is normalized so that
+    /// the minimum is always smaller than the maximum.
+    #[must_use]
+    pub fn new(a: (i32, i32, i32), b: (i32, i32, i32)) -> Self {
+        Self {
+            min_x: a.0.min(b.0),
+            min_y: a.1.min(b.1),
+            min_z: a.2.min(b.2),
+            max_x: a.0.max(b.0),
+            max_y: a.1.max(b.1),
+            max_z: a.2.max(b.2),
+        }
+    }
+
+    /// Checks if the given block coordinate is inside this box
+    #[must_use]
+    pub fn contains(&self, x: i32, y: i32, z: i32) -> bool {
+        (self.min_x..=self.max_x).contains(&x)
+            && (self.min_y..=self.max_y).contains(&y)
+            && (self.min_z..=self.max_z).contains(&z)
+    }
+
+    /// Gets the centers of the eight corner blocks of this box
+    ///
+    /// As a box is convex, the point furthest away from any position is
+    /// always one of these corners.
+    #[must_use]
+    pub fn corner_centers(&self) -> [[f64; 3]; 8] {
+        let xs = [self.min_x as f64 + 0.5, self.max_x as f64 + 0.5];
+        let ys = [self.min_y as f64 + 0.5, self.max_y as f64 + 0.5];
+        let zs = [self.min_z as f64 + 0.5, self.max_z as f64 + 0.5];
+
+        let mut corners = [[0.0; 3]; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            *corner = [xs[i & 1], ys[(i >> 1) & 1], zs[(i >> 2) & 1]];
+        }
+        corners
+    }
+
+    /// Checks if this box and another one share at least one block
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Gets the blocks inside of both this box and another one
+    ///
+    /// Returns [`None`] if the boxes don't share any block.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::generator::Aabb;
+    ///
+    /// let a = Aabb::new((0, 0, 0), (15, 15, 15));
+    /// let b = Aabb::new((8, -8, 8), (23, 7, 23));
+    ///
+    /// assert_eq!(a.intersection(&b), Some(Aabb::new((8, 0, 8), (15, 7, 15))));
+    /// assert_eq!(a.union(&b), Aabb::new((0, -8, 0), (23, 15, 23)));
+    /// assert_eq!(a.intersection(&Aabb::new((16, 0, 0), (16, 0, 0))), None);
+    /// ```
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self {
+            min_x: self.min_x.max(other.min_x),
+            min_y: self.min_y.max(other.min_y),
+            min_z: self.min_z.max(other.min_z),
+            max_x: self.max_x.min(other.max_x),
+            max_y: self.max_y.min(other.max_y),
+            max_z: self.max_z.min(other.max_z),
+        };
+
+        (intersection.min_x <= intersection.max_x
+            && intersection.min_y <= intersection.max_y
+            && intersection.min_z <= intersection.max_z)
+            .then_some(intersection)
+    }
+
+    /// Gets the smallest box containing both this box and another one
+    ///
+    /// See [`Self::intersection()`] for an example
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            min_z: self.min_z.min(other.min_z),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+            max_z: self.max_z.max(other.max_z),
+        }
+    }
+
+    /// Grows the box by a number of blocks on every side
+    ///
+    /// A negative amount shrinks the box, but never past its center, so the
+    /// box keeps at least one block on every axis.
+    #[must_use]
+    pub fn expand(&self, amount: i32) -> Self {
+        let axis = |min: i32, max: i32| {
+            let (min, max) = (min.saturating_sub(amount), max.saturating_add(amount));
+
+            if min <= max {
+                (min, max)
+            } else {
+                let center = min.midpoint(max);
+                (center, center)
+            }
+        };
+
+        let (min_x, max_x) = axis(self.min_x, self.max_x);
+        let (min_y, max_y) = axis(self.min_y, self.max_y);
+        let (min_z, max_z) = axis(self.min_z, self.max_z);
+
+        Self {
+            min_x,
+            min_y,
+            min_z,
+            max_x,
+            max_y,
+            max_z,
+        }
+    }
+
+    /// Gets the smallest range at a scale which covers every block of the box
+    ///
+    /// Like in [`Range`] the y coordinate is only scaled by 1:4 for scales
+    /// other than [`Scale::Block`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::generator::{Aabb, Range, Scale};
+    ///
+    /// let area = Aabb::new((-100, 64, -100), (100, 64, 100));
+    ///
+    /// assert_eq!(
+    ///     area.to_range(Scale::Chunk),
+    ///     Range {
+    ///         scale: Scale::Chunk,
+    ///         x: -7,
+    ///         z: -7,
+    ///         size_x: 14,
+    ///         size_z: 14,
+    ///         y: 16,
+    ///         size_y: 1,
+    ///     }
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_range(&self, scale: Scale) -> Range {
+        let scaled = |min: i32, max: i32, scale: i32| {
+            let (min, max) = (min.div_euclid(scale), max.div_euclid(scale));
+            (min, max.abs_diff(min).saturating_add(1))
+        };
+        let vertical = match scale {
+            Scale::Block => 1,
+            _ => 4,
+        };
+
+        let (x, size_x) = scaled(self.min_x, self.max_x, scale as i32);
+        let (z, size_z) = scaled(self.min_z, self.max_z, scale as i32);
+        let (y, size_y) = scaled(self.min_y, self.max_y, vertical);
+
+        Range {
+            scale,
+            x,
+            z,
+            size_x,
+            size_z,
+            y,
+            size_y,
+        }
+    }
+}
+
+impl From<Range> for Aabb {
+    /// Gets the blocks covered by every cell of a range
+    fn from(range: Range) -> Self {
+        let scale = range.scale as i32;
+        let vertical = match range.scale {
+            Scale::Block => 1,
+            _ => 4,
+        };
+        // Sizes of 0 and 1 both mean a single layer
+        let size_y = range.size_y.max(1) as i32;
+
+        Self {
+            min_x: range.x * scale,
+            min_y: range.y * vertical,
+            min_z: range.z * scale,
+            max_x: (range.x + range.size_x as i32) * scale - 1,
+            max_y: (range.y + size_y) * vertical - 1,
+            max_z: (range.z + range.size_z as i32) * scale - 1,
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use super::{error::GeneratorError, Aabb, BlockPosition, Cache, Generator, Range, Scale};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use std::collections::BTreeMap;
+
+/// The heights the biomes are sampled at
+///
+/// The cave biomes only generate deep underground, and the peaks only high up,
+/// so a single layer can't find every biome.
+const SAMPLE_HEIGHTS: [i32; 5] = [-48, 0, 64, 128, 256];
+
+/// The biomes required by Adventuring Time in 1.18
+const BIOMES_1_18: [BiomeID; 50] = [
+    BiomeID::badlands,
+    BiomeID::bamboo_jungle,
+    BiomeID::beach,
+    BiomeID::birch_forest,
+    BiomeID::cold_ocean,
+    BiomeID::dark_forest,
+    BiomeID::deep_cold_ocean,
+    BiomeID::deep_frozen_ocean,
+    BiomeID::deep_lukewarm_ocean,
+    BiomeID::deep_ocean,
+    BiomeID::desert,
+    BiomeID::dripstone_caves,
+    BiomeID::eroded_badlands,
+    BiomeID::flower_forest,
+    BiomeID::forest,
+    BiomeID::frozen_ocean,
+    BiomeID::frozen_peaks,
+    BiomeID::frozen_river,
+    BiomeID::grove,
+    BiomeID::ice_spikes,
+    BiomeID::jagged_peaks,
+    BiomeID::jungle,
+    BiomeID::lukewarm_ocean,
+    BiomeID::lush_caves,
+    BiomeID::meadow,
+    BiomeID::mushroom_fields,
+    BiomeID::ocean,
+    BiomeID::old_growth_birch_forest,
+    BiomeID::old_growth_pine_taiga,
+    BiomeID::old_growth_spruce_taiga,
+    BiomeID::plains,
+    BiomeID::river,
+    BiomeID::savanna,
+    BiomeID::savanna_plateau,
+    BiomeID::snowy_beach,
+    BiomeID::snowy_plains,
+    BiomeID::snowy_slopes,
+    BiomeID::snowy_taiga,
+    BiomeID::sparse_jungle,
+    BiomeID::stony_peaks,
+    BiomeID::stony_shore,
+    BiomeID::sunflower_plains,
+    BiomeID::swamp,
+    BiomeID::taiga,
+    BiomeID::warm_ocean,
+    BiomeID::windswept_forest,
+    BiomeID::windswept_gravelly_hills,
+    BiomeID::windswept_hills,
+    BiomeID::windswept_savanna,
+    BiomeID::wooded_badlands,
+];
+
+/// The biomes added to Adventuring Time after 1.18, with the version adding them
+///
+/// The pale garden is only added with the `unstable-versions` feature, since
+/// the winter drop is not a finalized version of cubiomes.
+const ADDED_BIOMES: &[(MCVersion, BiomeID)] = &[
+    (MCVersion::MC_1_19_2, BiomeID::deep_dark),
+    (MCVersion::MC_1_19_2, BiomeID::mangrove_swamp),
+    (MCVersion::MC_1_20_6, BiomeID::cherry_grove),
+    #[cfg(feature = "unstable-versions")]
+    (MCVersion::MC_1_21_WD, BiomeID::pale_garden),
+];
+
+/// Where an example of a biome was found by [`Generator::adventuring_time()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BiomeLocation {
+    /// The block position of the example
+    ///
+    /// This is the center of a chunk, where the biome generates at `y`.
+    pub pos: BlockPosition,
+    /// The y the biome was sampled at
+    pub y: i32,
+}
+
+/// The progress towards the Adventuring Time advancement around a position
+///
+/// Created with [`Generator::adventuring_time()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdventuringTime {
+    found: BTreeMap<BiomeID, BiomeLocation>,
+    missing: Vec<BiomeID>,
+}
+
+impl AdventuringTime {
+    /// Gets the biomes Adventuring Time requires in a version
+    ///
+    /// Returns [`None`] for versions before 1.18, which aren't supported.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, MCVersion};
+    /// use cubiomes::generator::AdventuringTime;
+    ///
+    /// let biomes = AdventuringTime::required_biomes(MCVersion::MC_1_20_6)
+    ///     .expect("1.20 is supported");
+    ///
+    /// assert_eq!(biomes.len(), 53);
+    /// assert!(biomes.contains(&BiomeID::cherry_grove));
+    /// assert!(!biomes.contains(&BiomeID::deep_warm_ocean));
+    /// ```
+    #[must_use]
+    pub fn required_biomes(version: MCVersion) -> Option<Vec<BiomeID>> {
+        if version < MCVersion::MC_1_18_2 {
+            return None;
+        }
+
+        let added = ADDED_BIOMES
+            .iter()
+            .filter(|&&(since, _)| version >= since)
+            .map(|&(_, biome)| biome);
+
+        let mut biomes: Vec<BiomeID> = BIOMES_1_18.iter().copied().chain(added).collect();
+        biomes.sort_unstable();
+        Some(biomes)
+    }
+
+    /// Checks if every required biome was found
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Gets the required biomes which weren't found, in biome id order
+    #[must_use]
+    pub fn missing(&self) -> &[BiomeID] {
+        &self.missing
+    }
+
+    /// Gets the closest example of every found biome
+    #[must_use]
+    pub fn found(&self) -> &BTreeMap<BiomeID, BiomeLocation> {
+        &self.found
+    }
+
+    /// Gets the closest example of a biome, if it was found
+    #[must_use]
+    pub fn location_of(&self, biome: BiomeID) -> Option<BiomeLocation> {
+        self.found.get(&biome).copied()
+    }
+}
+
+impl Generator {
+    /// Checks which biomes of the Adventuring Time advancement generate
+    /// within a radius around a position
+    ///
+    /// The area is sampled once per chunk at several heights, so cave biomes
+    /// and peaks are found as well. The closest sample of every biome which
+    /// also generates at that block is kept as its example. Biomes smaller
+    /// than a chunk may be missed. Pass the
+    /// spawn of the world as `origin` to check the surroundings of spawn.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let progress = generator
+    ///     .adventuring_time(BlockPosition::new(0, 0), 1000)
+    ///     .expect("failed to generate biomes");
+    ///
+    /// assert_eq!(progress.found().len(), 31);
+    /// assert_eq!(progress.missing().len(), 22);
+    /// assert!(progress.missing().contains(&BiomeID::mushroom_fields));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] before 1.18,
+    /// [`GeneratorError::UnsupportedDimension`] outside of the overworld, or
+    /// an error if generating the biomes fails
+    pub fn adventuring_time(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Result<AdventuringTime, GeneratorError> {
+        let required = AdventuringTime::required_biomes(self.version())
+            .ok_or(GeneratorError::UnsupportedVersion(self.version()))?;
+        if self.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        let reach = i32::try_from(radius).unwrap_or(i32::MAX);
+        let max_distance = u64::from(radius).pow(2);
+        let scale = Scale::Chunk as i32;
+
+        let mut candidates: BTreeMap<BiomeID, Vec<(u64, BiomeLocation)>> = BTreeMap::new();
+
+        for y in SAMPLE_HEIGHTS {
+            let area = Aabb::new(
+                (
+                    origin.x.saturating_sub(reach),
+                    y,
+                    origin.z.saturating_sub(reach),
+                ),
+                (
+                    origin.x.saturating_add(reach),
+                    y,
+                    origin.z.saturating_add(reach),
+                ),
+            );
+            let range = Range {
+                size_y: 0,
+                ..area.to_range(Scale::Chunk)
+            };
+
+            let mut cache = Cache::new(self, range);
+            cache.fill_cache()?;
+
+            for (i, &raw_biomeid) in cache.as_vec().iter().enumerate() {
+                let Some(biome) = BiomeID::from_id(raw_biomeid) else {
+                    continue;
+                };
+                if required.binary_search(&biome).is_err() {
+                    continue;
+                }
+
+                let x = (range.x + (i % range.size_x as usize) as i32) * scale + scale / 2;
+                let z = (range.z + (i / range.size_x as usize) as i32) * scale + scale / 2;
+                let distance =
+                    u64::from(x.abs_diff(origin.x)).pow(2) + u64::from(z.abs_diff(origin.z)).pow(2);
+
+                if distance > max_distance {
+                    continue;
+                }
+
+                let location = BiomeLocation {
+                    pos: BlockPosition::new(x, z),
+                    y,
+                };
+                candidates
+                    .entry(biome)
+                    .or_default()
+                    .push((distance, location));
+            }
+        }
+
+        // The coarse scale skips parts of the noise, so the samples are
+        // confirmed at block scale from the closest one outwards
+        let mut found = BTreeMap::new();
+        for (biome, mut locations) in candidates {
+            locations.sort_unstable_by_key(|&(distance, location)| {
+                (distance, location.y, location.pos.x, location.pos.z)
+            });
+
+            for (_, location) in locations {
+                if self.get_biome_at(location.pos.at_y(location.y))? == biome {
+                    found.insert(biome, location);
+                    break;
+                }
+            }
+        }
+
+        let missing = required
+            .into_iter()
+            .filter(|biome| !found.contains_key(biome))
+            .collect();
+
+        Ok(AdventuringTime { found, missing })
+    }
+}
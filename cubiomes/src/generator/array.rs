@@ -0,0 +1,97 @@
+use super::{error::GeneratorError, Cache};
+use crate::enums::BiomeID;
+use ndarray::{ArrayView2, ArrayView3};
+
+impl Cache<'_> {
+    /// Gets the lowest layer of the cache as a 2d array
+    ///
+    /// The array is indexed with `[z, x]`, the same order the cache is stored
+    /// in, so no data is copied. Requires the `ndarray` feature.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range {
+    ///     scale: Scale::Block,
+    ///     x: 512,
+    ///     z: -512,
+    ///     size_x: 64,
+    ///     size_z: 32,
+    ///     y: 100,
+    ///     size_y: 0,
+    /// });
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// let biomes = cache.as_array2().expect("the cache holds valid biomes");
+    ///
+    /// assert_eq!(biomes.dim(), (32, 64));
+    /// assert_eq!(biomes[[0, 13]], BiomeID::plains);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cache has not been
+    /// filled and [`GeneratorError::BiomeIDOutOfRange`] if any cell holds an
+    /// unknown biome
+    pub fn as_array2(&self) -> Result<ArrayView2<'_, BiomeID>, GeneratorError> {
+        let layer = (self.range.size_x * self.range.size_z) as usize;
+        let biomes = self.biome_slice()?;
+
+        ArrayView2::from_shape(
+            (self.range.size_z as usize, self.range.size_x as usize),
+            biomes
+                .get(..layer)
+                .ok_or(GeneratorError::IndexOutOfBounds)?,
+        )
+        .map_err(|_| GeneratorError::IndexOutOfBounds)
+    }
+
+    /// Gets every layer of the cache as a 3d array
+    ///
+    /// The array is indexed with `[y, z, x]`, the same order the cache is
+    /// stored in, so no data is copied. A cache with a `size_y` of 0 has a
+    /// single layer. Requires the `ndarray` feature.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cache has not been
+    /// filled and [`GeneratorError::BiomeIDOutOfRange`] if any cell holds an
+    /// unknown biome
+    pub fn as_array3(&self) -> Result<ArrayView3<'_, BiomeID>, GeneratorError> {
+        ArrayView3::from_shape(
+            (
+                self.range.size_y.max(1) as usize,
+                self.range.size_z as usize,
+                self.range.size_x as usize,
+            ),
+            self.biome_slice()?,
+        )
+        .map_err(|_| GeneratorError::IndexOutOfBounds)
+    }
+
+    /// Gets the data of the cache as biomes, checking every cell is valid
+    fn biome_slice(&self) -> Result<&[BiomeID], GeneratorError> {
+        if let Some(&invalid) = self
+            .buffer
+            .iter()
+            .find(|&&raw_biomeid| BiomeID::from_id(raw_biomeid).is_none())
+        {
+            return Err(GeneratorError::BiomeIDOutOfRange(invalid));
+        }
+
+        // SAFETY:
+        // BiomeID is represented as an i32 and every value in the buffer was
+        // checked to be a valid variant of it, so the data can be read as
+        // biomes. The lifetime of the slice is tied to the cache.
+        Ok(unsafe {
+            std::slice::from_raw_parts(self.buffer.as_ptr().cast::<BiomeID>(), self.buffer.len())
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use super::{
+    coords::block_to_chunk, error::GeneratorError, BlockPosition3D, Cache, Generator, Range, Scale,
+};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use std::collections::BTreeMap;
+
+impl Generator {
+    /// Gets the biomes at many blocks at once
+    ///
+    /// The points are grouped by their chunk and height, and every group of
+    /// more than one point is answered from one small cache covering the
+    /// points of the group. This is a lot faster than calling
+    /// [`Self::get_biome_at()`] for thousands of points near each other, while
+    /// giving the same biomes. The groups are generated in spatial order, so
+    /// neighbouring groups share the state of the generator.
+    ///
+    /// The layered overworld before 1.18 is always generated a whole chunk at
+    /// a time, as cubiomes gives different biomes for small ranges there. The
+    /// biomes then match a large cache at [`Scale::Block`], which
+    /// [`Self::get_biome_at()`] doesn't always do for 1.15 to 1.17.
+    ///
+    /// The biomes are returned in the order of the points.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition3D, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let points: Vec<BlockPosition3D> = (0..100)
+    ///     .map(|i| BlockPosition3D::new(i * 3, 64, i % 7))
+    ///     .collect();
+    ///
+    /// let biomes = generator.biomes_at(&points).expect("failed to generate biomes");
+    ///
+    /// assert_eq!(biomes[42], generator.get_biome_at(points[42]).expect("failed to generate biome"));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the first error of generating the biomes, see
+    /// [`Self::get_biome_at()`] and [`Cache::fill_cache()`]
+    pub fn biomes_at(&self, points: &[BlockPosition3D]) -> Result<Vec<BiomeID>, GeneratorError> {
+        // The points of each chunk and height, ordered so that neighbouring
+        // chunks are generated one after another
+        let mut groups: BTreeMap<(i32, i32, i32), Vec<usize>> = BTreeMap::new();
+        for (index, pos) in points.iter().enumerate() {
+            groups
+                .entry((pos.y, block_to_chunk(pos.z), block_to_chunk(pos.x)))
+                .or_default()
+                .push(index);
+        }
+
+        // The layered overworld gives different biomes for small ranges than
+        // for large ones, so it is always generated a whole chunk at a time
+        let layered = self.dimension() == Dimension::DIM_OVERWORLD
+            && self.minecraft_version() < MCVersion::MC_1_18_2;
+
+        let mut biomes = vec![BiomeID::none; points.len()];
+
+        for (&(y, chunk_z, chunk_x), indices) in &groups {
+            if let ([index], false) = (&indices[..], layered) {
+                biomes[*index] = self.get_biome_at(points[*index])?;
+                continue;
+            }
+
+            let range = if layered {
+                Range {
+                    scale: Scale::Block,
+                    x: chunk_x * 16,
+                    z: chunk_z * 16,
+                    size_x: 16,
+                    size_z: 16,
+                    y,
+                    size_y: 1,
+                }
+            } else {
+                let min_x = indices.iter().map(|&i| points[i].x).min().unwrap_or(0);
+                let max_x = indices.iter().map(|&i| points[i].x).max().unwrap_or(0);
+                let min_z = indices.iter().map(|&i| points[i].z).min().unwrap_or(0);
+                let max_z = indices.iter().map(|&i| points[i].z).max().unwrap_or(0);
+
+                // The points of a group are in one chunk, so the sizes are at
+                // most 16
+                Range {
+                    scale: Scale::Block,
+                    x: min_x,
+                    z: min_z,
+                    size_x: (max_x - min_x + 1) as u32,
+                    size_z: (max_z - min_z + 1) as u32,
+                    y,
+                    size_y: 1,
+                }
+            };
+
+            let mut cache = Cache::new(self, range);
+            cache.fill_cache()?;
+
+            for &index in indices {
+                let pos = points[index];
+                biomes[index] =
+                    cache.biome_at((pos.x - range.x) as u32, 0, (pos.z - range.z) as u32)?;
+            }
+        }
+
+        Ok(biomes)
+    }
+}
@@ -0,0 +1,258 @@
+use super::{error::GeneratorError, BlockPosition, Generator, GeneratorFlags, HeightMap, Scale};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+
+/// The height of the sea in beta 1.7, columns at or below it are
+/// oceans
+pub const BETA_SEA_LEVEL: f64 = 63.0;
+
+impl Generator {
+    /// Initializes a new generator for beta 1.7 or beta 1.8 with a seed
+    /// applied in the overworld
+    ///
+    /// The biomes of the two versions come from different generators, which
+    /// the flags affect differently:
+    ///
+    /// - In beta 1.7 the oceans come from the height of the terrain instead
+    ///   of the biomes. With `oceans` they are generated from the terrain,
+    ///   which is slow, see [`BetaSurfaceNoise`] for sampling the terrain
+    ///   directly. Without them [`GeneratorFlags::NoBetaOcean`] is set, and
+    ///   the land biomes under the oceans are given instead.
+    /// - In beta 1.8 the oceans are biomes like in later versions, so
+    ///   `oceans` doesn't change anything.
+    ///
+    /// [`GeneratorFlags::ForceOceanVariants`] doesn't apply to either, as the
+    /// ocean variants were only added in 1.13.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let seed = 3055141959546;
+    /// let generator = Generator::beta(MCVersion::MC_B1_7, seed, false).expect("b1.7 is a beta version");
+    ///
+    /// assert_eq!(
+    ///     generator,
+    ///     Generator::new(MCVersion::MC_B1_7, seed, Dimension::DIM_OVERWORLD, GeneratorFlags::NoBetaOcean)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] for versions other than
+    /// beta 1.7 and beta 1.8
+    pub fn beta(version: MCVersion, seed: i64, oceans: bool) -> Result<Self, GeneratorError> {
+        let flags = match version {
+            MCVersion::MC_B1_7 if !oceans => GeneratorFlags::NoBetaOcean,
+            MCVersion::MC_B1_7 | MCVersion::MC_B1_8 => GeneratorFlags::empty(),
+            _ => return Err(GeneratorError::UnsupportedVersion(version)),
+        };
+
+        Ok(Self::new(version, seed, Dimension::DIM_OVERWORLD, flags))
+    }
+
+    /// Approximates the height of the terrain at a block in beta 1.7
+    ///
+    /// This initializes a [`BetaSurfaceNoise`] for the generator, which
+    /// should be kept around for sampling more than a few blocks.
+    ///
+    /// # Errors
+    /// Returns the errors of [`BetaSurfaceNoise::new()`]
+    pub fn beta_surface_height(
+        &self,
+        pos: impl Into<BlockPosition>,
+    ) -> Result<f64, GeneratorError> {
+        BetaSurfaceNoise::new(self)?.surface_height(self, pos)
+    }
+}
+
+/// The terrain noise of beta 1.7
+///
+/// The terrain of these versions decides where the oceans are, as the biome
+/// generator has no oceans. Cubiomes approximates the height of the terrain
+/// from the noise at sea level, which is also how the generators place the
+/// oceans unless [`GeneratorFlags::NoBetaOcean`] is set. The heights are an
+/// estimate, which is close near the sea level and rougher in mountains.
+///
+/// Initializing the noise takes a while, so it should be kept around when
+/// sampling many heights.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::generator::{BetaSurfaceNoise, Generator};
+///
+/// let generator = Generator::beta(MCVersion::MC_B1_7, 3055141959546, false)
+///     .expect("b1.7 is a beta version");
+/// let noise = BetaSurfaceNoise::new(&generator).expect("b1.7 has beta terrain");
+///
+/// let height = noise
+///     .surface_height(&generator, (100, -200))
+///     .expect("the noise is for the generator");
+/// assert_eq!(height.round(), 66.0);
+///
+/// let heights = noise
+///     .approx_heights(&generator, 0, 0, 16, 16)
+///     .expect("failed to map heights");
+/// let (min, max) = heights.min_max().expect("the area is not empty");
+/// assert_eq!((min.round(), max.round()), (51.0, 116.0));
+/// ```
+pub struct BetaSurfaceNoise {
+    noise: Box<PaddedNoise>,
+    seed: u64,
+}
+
+/// The beta terrain noise followed by zeroed memory
+///
+/// Cubiomes samples the beta terrain with indices of up to 511 into the 257
+/// byte permutations of an octave, reading the padding and fields after them,
+/// and past the end of the noise for the last octaves. Zeroing the noise and
+/// the padding after it keeps those reads in bounds, and the heights the same
+/// between runs.
+#[repr(C)]
+struct PaddedNoise {
+    noise: cubiomes_sys::SurfaceNoiseBeta,
+    padding: [u8; 256],
+}
+
+impl std::fmt::Debug for BetaSurfaceNoise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BetaSurfaceNoise")
+            .field("seed", &self.seed.cast_signed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl BetaSurfaceNoise {
+    /// Initializes the terrain noise for the seed of a generator
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] for generators of other
+    /// versions than beta 1.7, and [`GeneratorError::UnsupportedDimension`] outside of the
+    /// overworld
+    pub fn new(generator: &Generator) -> Result<Self, GeneratorError> {
+        let version = generator.minecraft_version();
+        if version != MCVersion::MC_B1_7 {
+            return Err(GeneratorError::UnsupportedVersion(version));
+        }
+        if generator.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(generator.dimension()));
+        }
+
+        let seed = generator.seed().cast_unsigned();
+
+        // SAFETY:
+        // The noise is zeroed, including the padding of its structs, and
+        // then initialized by initSurfaceNoiseBeta before assuming so. It
+        // points into itself, which is fine as the box keeps it in place.
+        let noise = unsafe {
+            let mut noise = Box::<PaddedNoise>::new_uninit();
+            noise.as_mut_ptr().write_bytes(0, 1);
+            cubiomes_sys::initSurfaceNoiseBeta(&raw mut (*noise.as_mut_ptr()).noise, seed);
+            noise.assume_init()
+        };
+
+        Ok(Self { noise, seed })
+    }
+
+    /// Approximates the height of the terrain at a block
+    ///
+    /// Columns at or below [`BETA_SEA_LEVEL`] are oceans.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::MismatchedGenerator`] if the generator
+    /// doesn't have the seed this noise was created for
+    pub fn surface_height(
+        &self,
+        generator: &Generator,
+        pos: impl Into<BlockPosition>,
+    ) -> Result<f64, GeneratorError> {
+        self.check_generator(generator)?;
+        let pos = pos.into();
+
+        Ok(self.sample(generator, pos.x, pos.z))
+    }
+
+    /// Checks if the column at a block is an ocean, like the generator does
+    /// without [`GeneratorFlags::NoBetaOcean`]
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::MismatchedGenerator`] if the generator
+    /// doesn't have the seed this noise was created for
+    pub fn is_ocean(
+        &self,
+        generator: &Generator,
+        pos: impl Into<BlockPosition>,
+    ) -> Result<bool, GeneratorError> {
+        Ok(self.surface_height(generator, pos)? <= BETA_SEA_LEVEL)
+    }
+
+    /// Approximates the height of the terrain of an area
+    ///
+    /// The area is given in [`Scale::Quad`] coordinates, and the height of
+    /// every cell is sampled at its center like
+    /// [`Generator::approx_heights()`] does, but without initializing the
+    /// noise again. The biomes of the map are unknown.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::MismatchedGenerator`] if the generator
+    /// doesn't have the seed this noise was created for, and
+    /// [`GeneratorError::TryFromRangeError`] if the area is empty, too big or
+    /// too far out to be given to cubiomes
+    pub fn approx_heights(
+        &self,
+        generator: &Generator,
+        x: i32,
+        z: i32,
+        size_x: u32,
+        size_z: u32,
+    ) -> Result<HeightMap, GeneratorError> {
+        self.check_generator(generator)?;
+        super::validate::area(Scale::Quad, x, z, size_x, size_z)?;
+
+        let cells = size_x as usize * size_z as usize;
+        let mut heights = Vec::with_capacity(cells);
+
+        for j in 0..size_z as i32 {
+            for i in 0..size_x as i32 {
+                let center = |quad: i32| super::coords::quad_to_block(quad) + 2;
+                heights.push(self.sample(generator, center(x + i), center(z + j)) as f32);
+            }
+        }
+
+        Ok(HeightMap {
+            scale: Scale::Quad,
+            x,
+            z,
+            size_x,
+            size_z,
+            heights,
+            biomes: vec![BiomeID::none as i32; cells],
+        })
+    }
+
+    fn check_generator(&self, generator: &Generator) -> Result<(), GeneratorError> {
+        // The beta biome noise is only set up for beta 1.7 in the overworld,
+        // which new checks for
+        let beta = generator.minecraft_version() == MCVersion::MC_B1_7
+            && generator.dimension() == Dimension::DIM_OVERWORLD;
+
+        if !beta || generator.seed().cast_unsigned() != self.seed {
+            return Err(GeneratorError::MismatchedGenerator(generator.context()));
+        }
+
+        Ok(())
+    }
+
+    /// Samples the height at a block, after checking the generator
+    fn sample(&self, generator: &Generator, x: i32, z: i32) -> f64 {
+        // SAFETY:
+        // The generator was checked to be a beta generator in the overworld,
+        // so its beta biome noise is initialized, and so is the noise. The
+        // reads past the end of the noise stay in its padding.
+        unsafe {
+            let biome_noise =
+                &raw const (*generator.generator).__bindgen_anon_1.__bindgen_anon_3.bnb;
+            cubiomes_sys::approxSurfaceBeta(biome_noise, &raw const self.noise.noise, x, z)
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use super::{error::CacheFileError, Cache, Generator, GeneratorFlags, Range, Scale};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use cubiomes_sys::num_traits::FromPrimitive;
+use std::io::{Read, Write};
+
+/// The bytes every cache in the binary format starts with
+const MAGIC: [u8; 4] = *b"CBMC";
+
+/// The version of the binary format written by [`Cache::write_to()`]
+const FORMAT_VERSION: u16 = 1;
+
+/// The header of a cache in the binary cache format
+///
+/// The format stores a filled [`Cache`] along with the settings of its
+/// generator, so maps can be generated in one place and read elsewhere. All
+/// numbers are little endian:
+///
+/// | Bytes | Field |
+/// |-------|-------|
+/// | 4 | The magic bytes `CBMC` |
+/// | 2 | The version of the format, currently 1 |
+/// | 4 | The minecraft version as the value of [`MCVersion`] |
+/// | 4 | The dimension as the value of [`Dimension`] |
+/// | 4 | The bits of the [`GeneratorFlags`] |
+/// | 8 | The seed |
+/// | 4 | The [`Scale`] of the range as its number |
+/// | 4 × 3 | The x, z and y of the range |
+/// | 4 × 3 | The unsigned `size_x`, `size_z` and `size_y` of the range |
+/// | 1 per cell | The biome of every cell in the order of [`Cache::as_vec()`] |
+///
+/// The header is read separately with [`Self::read_from()`], for example to
+/// set up the generator for [`Cache::read_from()`] with [`Self::generator()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheHeader {
+    /// The minecraft version of the generator
+    pub version: MCVersion,
+    /// The dimension of the generator
+    pub dimension: Dimension,
+    /// The flags of the generator
+    pub flags: GeneratorFlags,
+    /// The seed of the generator
+    pub seed: i64,
+    /// The range of the cache
+    pub range: Range,
+}
+
+impl CacheHeader {
+    /// Reads the header at the start of a cache in the binary format
+    ///
+    /// Only the header is read, see [`Cache::read_from()`] for reading the
+    /// whole cache.
+    ///
+    /// # Errors
+    /// Returns [`CacheFileError::Io`] if reading fails,
+    /// [`CacheFileError::InvalidMagic`] and
+    /// [`CacheFileError::UnsupportedFormatVersion`] if the data isn't a cache
+    /// in a supported version of the format, and
+    /// [`CacheFileError::InvalidHeader`] if it has an invalid field
+    pub fn read_from(mut reader: impl Read) -> Result<Self, CacheFileError> {
+        let magic: [u8; 4] = read_bytes(&mut reader)?;
+        if magic != MAGIC {
+            return Err(CacheFileError::InvalidMagic(magic));
+        }
+
+        let format_version = u16::from_le_bytes(read_bytes(&mut reader)?);
+        if format_version != FORMAT_VERSION {
+            return Err(CacheFileError::UnsupportedFormatVersion(format_version));
+        }
+
+        let version = read_i32(&mut reader)?;
+        let version = MCVersion::from_i32(version).ok_or_else(|| invalid("version", version))?;
+        let dimension = read_i32(&mut reader)?;
+        let dimension = Dimension::from_i32(dimension)
+            .filter(|&dimension| dimension != Dimension::DIM_UNDEF)
+            .ok_or_else(|| invalid("dimension", dimension))?;
+        let flags = GeneratorFlags::from_bits_retain(u32::from_le_bytes(read_bytes(&mut reader)?));
+        let seed = i64::from_le_bytes(read_bytes(&mut reader)?);
+
+        let scale = read_i32(&mut reader)?;
+        let scale = match scale {
+            1 => Scale::Block,
+            4 => Scale::Quad,
+            16 => Scale::Chunk,
+            64 => Scale::QuadChunk,
+            256 => Scale::HalfRegion,
+            _ => return Err(invalid("scale", scale)),
+        };
+
+        let range = Range {
+            scale,
+            x: read_i32(&mut reader)?,
+            z: read_i32(&mut reader)?,
+            y: read_i32(&mut reader)?,
+            size_x: read_u32(&mut reader)?,
+            size_z: read_u32(&mut reader)?,
+            size_y: read_u32(&mut reader)?,
+        };
+
+        if cubiomes_sys::Range::try_from(range).is_err() {
+            return Err(CacheFileError::InvalidHeader {
+                field: "range size",
+                value: i64::from(range.size_x.max(range.size_z).max(range.size_y)),
+            });
+        }
+
+        Ok(Self {
+            version,
+            dimension,
+            flags,
+            seed,
+            range,
+        })
+    }
+
+    /// Creates a generator with the settings of the header
+    #[must_use]
+    pub fn generator(&self) -> Generator {
+        Generator::new(self.version, self.seed, self.dimension, self.flags)
+    }
+
+    /// Gets the amount of cells of the range, which is the size of the
+    /// payload in bytes
+    #[must_use]
+    pub fn cells(&self) -> u64 {
+        u64::from(self.range.size_x)
+            * u64::from(self.range.size_z)
+            * u64::from(self.range.size_y.max(1))
+    }
+
+    fn write_to(&self, mut writer: impl Write) -> Result<(), CacheFileError> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+
+        for value in [self.version as i32, self.dimension as i32] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.write_all(&self.flags.bits().to_le_bytes())?;
+        writer.write_all(&self.seed.to_le_bytes())?;
+
+        let range = self.range;
+        for value in [range.scale as i32, range.x, range.z, range.y] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        for value in [range.size_x, range.size_z, range.size_y] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'generator> Cache<'generator> {
+    /// Gets the header the cache is written with in the binary format
+    #[must_use]
+    pub fn header(&self) -> CacheHeader {
+        CacheHeader {
+            version: self.generator.minecraft_version(),
+            dimension: self.generator.dimension(),
+            flags: self.generator.flags(),
+            seed: self.generator.seed(),
+            range: self.range,
+        }
+    }
+
+    /// Writes the filled cache in the binary cache format
+    ///
+    /// The cache is written with a [`CacheHeader`] and a byte for the biome of
+    /// every cell, so it can be read again with [`Self::read_from()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, CacheHeader, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range {
+    ///     scale: Scale::Quad,
+    ///     x: -64,
+    ///     z: -64,
+    ///     size_x: 128,
+    ///     size_z: 128,
+    ///     y: 16,
+    ///     size_y: 0,
+    /// });
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// let mut data = Vec::new();
+    /// cache.write_to(&mut data).expect("failed to write cache");
+    ///
+    /// // Elsewhere, the generator is set up from the header of the data
+    /// let header = CacheHeader::read_from(&data[..]).expect("the data is a cache");
+    /// let generator = header.generator();
+    /// let read = Cache::read_from(&generator, &data[..]).expect("the data is a cache");
+    ///
+    /// assert_eq!(read.as_vec(), cache.as_vec());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`CacheFileError::GeneratorError`] if the cache isn't filled or
+    /// holds an unknown biome, and [`CacheFileError::Io`] if writing fails
+    pub fn write_to(&self, mut writer: impl Write) -> Result<(), CacheFileError> {
+        let payload = self.compact()?;
+
+        self.header().write_to(&mut writer)?;
+        writer.write_all(payload.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Reads a cache in the binary cache format
+    ///
+    /// The cache is read for the generator it was written with, which can be
+    /// created from its header with [`CacheHeader::generator()`]. The read
+    /// cache is filled, and can be moved and filled again like any other
+    /// cache of the generator. See [`Self::write_to()`] for an example.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`CacheHeader::read_from()`],
+    /// [`CacheFileError::GeneratorMismatch`] if the generator doesn't have the
+    /// settings of the header, [`CacheFileError::InvalidBiome`] if a cell
+    /// holds an unknown biome and [`CacheFileError::Io`] if the data ends
+    /// before every cell was read
+    pub fn read_from(
+        generator: &'generator Generator,
+        mut reader: impl Read,
+    ) -> Result<Self, CacheFileError> {
+        let header = CacheHeader::read_from(&mut reader)?;
+
+        let written_with = (header.version, header.dimension, header.flags, header.seed);
+        let read_with = (
+            generator.minecraft_version(),
+            generator.dimension(),
+            generator.flags(),
+            generator.seed(),
+        );
+
+        if written_with != read_with {
+            return Err(CacheFileError::GeneratorMismatch {
+                file: super::error::GeneratorContext {
+                    version: header.version,
+                    seed: header.seed,
+                    dimension: header.dimension,
+                },
+                file_flags: header.flags,
+                generator: generator.context(),
+                generator_flags: generator.flags(),
+            });
+        }
+
+        // The payload is read before allocating the cache, so a corrupt size
+        // in the header fails on the end of the data instead of allocating
+        let cells = header.cells();
+        let mut payload = Vec::new();
+        reader.take(cells).read_to_end(&mut payload)?;
+
+        if payload.len() as u64 != cells {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let mut cache = Cache::new(generator, header.range);
+        for raw_biomeid in payload {
+            let biome = BiomeID::from_id(i32::from(raw_biomeid))
+                .ok_or(CacheFileError::InvalidBiome(raw_biomeid))?;
+            cache.buffer.push(biome as i32);
+        }
+
+        Ok(cache)
+    }
+}
+
+fn invalid(field: &'static str, value: i32) -> CacheFileError {
+    CacheFileError::InvalidHeader {
+        field,
+        value: i64::from(value),
+    }
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> Result<[u8; N], CacheFileError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_i32(reader: &mut impl Read) -> Result<i32, CacheFileError> {
+    Ok(i32::from_le_bytes(read_bytes(reader)?))
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, CacheFileError> {
+    Ok(u32::from_le_bytes(read_bytes(reader)?))
+}
@@ -0,0 +1,258 @@
+use super::{error::GeneratorError, BlockPosition, Cache};
+use crate::enums::BiomeID;
+use std::collections::BTreeMap;
+
+/// A line along the edges of the cells between two sides of a border
+///
+/// The points are corners of cells in block coordinates, and the line only
+/// runs along the edges of the cells, so every segment is either parallel
+/// to the x or to the z axis. Points in the middle of a straight run are left
+/// out. Created with [`Cache::borders_between()`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{BiomeBorder, Cache, Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let mut cache = Cache::new(&generator, Range {
+///     scale: Scale::Quad,
+///     x: -128,
+///     z: -128,
+///     size_x: 256,
+///     size_z: 256,
+///     y: 16,
+///     size_y: 0,
+/// });
+/// cache.fill_cache().expect("failed to fill cache");
+///
+/// let is_ocean = |biome| {
+///     matches!(
+///         biome,
+///         BiomeID::ocean
+///             | BiomeID::deep_ocean
+///             | BiomeID::lukewarm_ocean
+///             | BiomeID::deep_lukewarm_ocean
+///             | BiomeID::cold_ocean
+///             | BiomeID::deep_cold_ocean
+///     )
+/// };
+///
+/// let coastlines = cache
+///     .borders_between(|biome| !is_ocean(biome), is_ocean)
+///     .expect("the cache holds valid biomes");
+///
+/// let beaches = cache
+///     .borders_between(|biome| biome == BiomeID::beach, is_ocean)
+///     .expect("the cache holds valid biomes");
+///
+/// let length = |borders: &[BiomeBorder]| borders.iter().map(BiomeBorder::length).sum::<u64>();
+/// assert_eq!((length(&beaches), length(&coastlines)), (124, 5800));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BiomeBorder {
+    /// The corners of the line, in block coordinates
+    pub points: Vec<BlockPosition>,
+    /// Whether the line is a loop, in which case the last point is the first
+    pub closed: bool,
+}
+
+impl BiomeBorder {
+    /// Gets the length of the line in blocks
+    #[must_use]
+    pub fn length(&self) -> u64 {
+        self.points
+            .windows(2)
+            .map(|segment| {
+                u64::from(segment[0].x.abs_diff(segment[1].x))
+                    + u64::from(segment[0].z.abs_diff(segment[1].z))
+            })
+            .sum()
+    }
+}
+
+impl Cache<'_> {
+    /// Finds the cells on one side of a border between two sides
+    ///
+    /// Returns the cells matching `side`, which share an edge with a cell
+    /// matching `other`. The cells are relative to the upper left corner of
+    /// the cache and ordered row by row. Only the lowest layer of the cache
+    /// is used.
+    ///
+    /// The sides can be a single biome or a class of biomes, like every
+    /// ocean. A cell matching both sides is on both sides.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cache has not been
+    /// filled and [`GeneratorError::BiomeIDOutOfRange`] if the layer holds an
+    /// unknown biome
+    pub fn border_cells(
+        &self,
+        side: impl FnMut(BiomeID) -> bool,
+        other: impl FnMut(BiomeID) -> bool,
+    ) -> Result<Vec<(u32, u32)>, GeneratorError> {
+        let sides = self.border_sides(side, other)?;
+        let size_x = self.range.size_x as usize;
+
+        let neighbours = |i: usize| {
+            let (x, z) = (i % size_x, i / size_x);
+            [
+                (x > 0).then(|| i - 1),
+                (x + 1 < size_x).then(|| i + 1),
+                (z > 0).then(|| i - size_x),
+                (i + size_x < sides.len()).then(|| i + size_x),
+            ]
+        };
+
+        Ok((0..sides.len())
+            .filter(|&i| sides[i].0)
+            .filter(|&i| neighbours(i).into_iter().flatten().any(|n| sides[n].1))
+            .map(|i| ((i % size_x) as u32, (i / size_x) as u32))
+            .collect())
+    }
+
+    /// Traces the borders between two sides as lines along the cell edges
+    ///
+    /// Every edge between a cell matching `side` and a cell matching `other`
+    /// is part of a line, and the edges are joined into as few lines as
+    /// possible. The lines start where a border ends, for example at the
+    /// edge of the cache, and borders which go around a patch are loops.
+    /// Only the lowest layer of the cache is used, see [`BiomeBorder`] for an
+    /// example.
+    ///
+    /// Where four cells meet at a corner with the sides alternating, the
+    /// lines cross, and which edges are joined into one line is arbitrary.
+    ///
+    /// # Errors
+    /// See [`Self::border_cells()`]
+    pub fn borders_between(
+        &self,
+        side: impl FnMut(BiomeID) -> bool,
+        other: impl FnMut(BiomeID) -> bool,
+    ) -> Result<Vec<BiomeBorder>, GeneratorError> {
+        let sides = self.border_sides(side, other)?;
+        let size_x = self.range.size_x as usize;
+        let size_z = self.range.size_z as usize;
+        let is_border =
+            |a: usize, b: usize| (sides[a].0 && sides[b].1) || (sides[a].1 && sides[b].0);
+
+        // The corners of the cells, row by row, with one more column and row
+        // than the cells
+        let corner = |x: usize, z: usize| z * (size_x + 1) + x;
+        let mut edges = Vec::new();
+        for z in 0..size_z {
+            for x in 0..size_x {
+                let i = z * size_x + x;
+
+                if x + 1 < size_x && is_border(i, i + 1) {
+                    edges.push((corner(x + 1, z), corner(x + 1, z + 1)));
+                }
+                if z + 1 < size_z && is_border(i, i + size_x) {
+                    edges.push((corner(x, z + 1), corner(x + 1, z + 1)));
+                }
+            }
+        }
+
+        let mut corners: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (edge, &(a, b)) in edges.iter().enumerate() {
+            corners.entry(a).or_default().push(edge);
+            corners.entry(b).or_default().push(edge);
+        }
+
+        let mut used = vec![false; edges.len()];
+        let unused = |used: &[bool], corner: usize| {
+            corners[&corner].iter().filter(|&&edge| !used[edge]).count()
+        };
+
+        // Lines starting at corners with an odd amount of edges end at
+        // another one, so tracing these first leaves only loops
+        let mut lines = Vec::new();
+        for &start in corners.keys() {
+            while unused(&used, start) % 2 == 1 {
+                lines.push(trace(start, &edges, &corners, &mut used));
+            }
+        }
+        for &start in corners.keys() {
+            while unused(&used, start) > 0 {
+                lines.push(trace(start, &edges, &corners, &mut used));
+            }
+        }
+
+        let range = self.range;
+        let scale = range.scale as i32;
+        let to_block = |corner: usize| {
+            BlockPosition::new(
+                (range.x + (corner % (size_x + 1)) as i32) * scale,
+                (range.z + (corner / (size_x + 1)) as i32) * scale,
+            )
+        };
+
+        Ok(lines
+            .into_iter()
+            .map(|line| BiomeBorder {
+                closed: line.len() > 2 && line.first() == line.last(),
+                points: simplify(line.into_iter().map(to_block).collect()),
+            })
+            .collect())
+    }
+
+    /// Gets for every cell of the lowest layer whether it matches each side
+    fn border_sides(
+        &self,
+        mut side: impl FnMut(BiomeID) -> bool,
+        mut other: impl FnMut(BiomeID) -> bool,
+    ) -> Result<Vec<(bool, bool)>, GeneratorError> {
+        Ok(self
+            .lowest_layer()?
+            .into_iter()
+            .map(|biome| (side(biome), other(biome)))
+            .collect())
+    }
+}
+
+/// Follows unused edges from a corner until there is none left
+fn trace(
+    start: usize,
+    edges: &[(usize, usize)],
+    corners: &BTreeMap<usize, Vec<usize>>,
+    used: &mut [bool],
+) -> Vec<usize> {
+    let mut line = vec![start];
+    let mut at = start;
+
+    while let Some(&edge) = corners[&at].iter().find(|&&edge| !used[edge]) {
+        used[edge] = true;
+        at = if edges[edge].0 == at {
+            edges[edge].1
+        } else {
+            edges[edge].0
+        };
+        line.push(at);
+    }
+
+    line
+}
+
+/// Leaves out the points in the middle of straight runs
+fn simplify(points: Vec<BlockPosition>) -> Vec<BlockPosition> {
+    let mut simple: Vec<BlockPosition> = Vec::with_capacity(points.len());
+
+    for point in points {
+        if let [.., before, last] = simple[..] {
+            let straight = (before.x == last.x && last.x == point.x)
+                || (before.z == last.z && last.z == point.z);
+            if straight {
+                simple.pop();
+            }
+        }
+        simple.push(point);
+    }
+
+    simple
+}
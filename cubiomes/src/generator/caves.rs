@@ -0,0 +1,188 @@
+use super::{error::GeneratorError, Aabb, BlockPosition, Cache, Generator, Scale};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use std::ops::Range as YRange;
+
+/// A column with a cave biome below the surface, found by a cave search like
+/// [`Generator::lush_caves_near()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaveBand {
+    /// The block position of the sampled column, at the center of a 1:4 cell
+    pub pos: BlockPosition,
+    /// The distance in blocks from the origin of the search to [`Self::pos`]
+    pub distance: f64,
+    /// The lowest sampled y of the cave biome in the column
+    pub min_y: i32,
+    /// The highest sampled y of the cave biome in the column
+    pub max_y: i32,
+}
+
+impl Generator {
+    /// Finds the columns with lush caves below them within a radius around a
+    /// surface position
+    ///
+    /// The biomes are sampled at 1:4 scale between the heights of `ys`,
+    /// excluding the end of the range, so the bounds of each band are within
+    /// 4 blocks of the actual band of lush caves. The lowest and highest sample
+    /// of a column are reported even if other biomes are between them. The
+    /// bands are sorted with the closest column first.
+    ///
+    /// The whole cuboid is generated at once, so a large radius together with
+    /// a tall range of heights needs a lot of memory.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let caves = generator
+    ///     .lush_caves_near(BlockPosition::new(0, 0), 256, -64..64)
+    ///     .expect("lush caves exist in 1.20");
+    ///
+    /// let closest = caves.first().expect("there are lush caves nearby");
+    /// assert_eq!(
+    ///     (closest.pos, closest.min_y, closest.max_y),
+    ///     (BlockPosition::new(-18, -38), -16, -16)
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] before 1.18, which has no
+    /// lush caves, [`GeneratorError::UnsupportedDimension`] outside of the
+    /// overworld, [`GeneratorError::YOutOfBounds`] if `ys` isn't inside the
+    /// [`Self::world_limits()`], or an error if generating the biomes fails
+    pub fn lush_caves_near(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        ys: YRange<i32>,
+    ) -> Result<Vec<CaveBand>, GeneratorError> {
+        self.cave_bands(BiomeID::lush_caves, origin, radius, ys)
+    }
+
+    /// Finds the columns with dripstone caves below them within a radius
+    /// around a surface position
+    ///
+    /// This samples the biomes the same way as [`Self::lush_caves_near()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let deepest = generator
+    ///     .dripstone_caves_near(BlockPosition::new(0, 0), 512, -64..64)
+    ///     .expect("dripstone caves exist in 1.20")
+    ///     .into_iter()
+    ///     .min_by_key(|caves| caves.min_y);
+    ///
+    /// let caves = deepest.expect("there are dripstone caves nearby");
+    /// assert_eq!((caves.pos, caves.min_y), (BlockPosition::new(462, 218), -48));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] before 1.18, which has no
+    /// dripstone caves, [`GeneratorError::UnsupportedDimension`] outside of the
+    /// overworld, [`GeneratorError::YOutOfBounds`] if `ys` isn't inside the
+    /// [`Self::world_limits()`], or an error if generating the biomes fails
+    pub fn dripstone_caves_near(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        ys: YRange<i32>,
+    ) -> Result<Vec<CaveBand>, GeneratorError> {
+        self.cave_bands(BiomeID::dripstone_caves, origin, radius, ys)
+    }
+
+    /// Finds the bands of a cave biome in the columns within a radius
+    fn cave_bands(
+        &self,
+        biome: BiomeID,
+        origin: BlockPosition,
+        radius: u32,
+        ys: YRange<i32>,
+    ) -> Result<Vec<CaveBand>, GeneratorError> {
+        if self.version() < MCVersion::MC_1_18_2 {
+            return Err(GeneratorError::UnsupportedVersion(self.version()));
+        }
+        if self.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+        if ys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let limits = self.world_limits();
+        limits.check_y(ys.start)?;
+        limits.check_y(ys.end - 1)?;
+
+        let reach = i32::try_from(radius).unwrap_or(i32::MAX);
+        let area = Aabb::new(
+            (
+                origin.x.saturating_sub(reach),
+                ys.start,
+                origin.z.saturating_sub(reach),
+            ),
+            (
+                origin.x.saturating_add(reach),
+                ys.end - 1,
+                origin.z.saturating_add(reach),
+            ),
+        );
+        let range = area.to_range(Scale::Quad);
+
+        let mut cache = Cache::new(self, range);
+        cache.fill_cache()?;
+
+        let quad = Scale::Quad as i32;
+        let mut bands = Vec::new();
+
+        for z in 0..range.size_z {
+            for x in 0..range.size_x {
+                let pos = BlockPosition::new(
+                    (range.x + x as i32) * quad + quad / 2,
+                    (range.z + z as i32) * quad + quad / 2,
+                );
+                let distance = origin.distance(pos);
+                if distance > f64::from(radius) {
+                    continue;
+                }
+
+                let mut band: Option<(i32, i32)> = None;
+                for layer in 0..range.size_y {
+                    if cache.biome_at(x, layer, z)? != biome {
+                        continue;
+                    }
+
+                    let y = (range.y + layer as i32) * quad;
+                    band = Some(band.map_or((y, y), |(min_y, _)| (min_y, y)));
+                }
+
+                if let Some((min_y, max_y)) = band {
+                    bands.push(CaveBand {
+                        pos,
+                        distance,
+                        min_y,
+                        max_y,
+                    });
+                }
+            }
+        }
+
+        bands.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(bands)
+    }
+}
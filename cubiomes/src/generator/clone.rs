@@ -0,0 +1,171 @@
+use super::{Generator, GeneratorFlags};
+use crate::enums::{Dimension, MCVersion};
+use std::alloc::{alloc, handle_alloc_error, Layout};
+use std::mem::size_of;
+
+impl Clone for Generator {
+    /// Copies the generator with its version, flags, seed and dimension
+    ///
+    /// The cubiomes generator is copied instead of set up and seeded again,
+    /// which is a lot cheaper than [`Generator::new()`]. The layers and noises
+    /// of the cubiomes generator point into the generator itself, so these
+    /// pointers are moved to the copy.
+    ///
+    /// Only the pointers `setupGenerator` and `applySeed` create are moved, so a
+    /// generator changed with [`Generator::as_raw_mut()`] to point into itself
+    /// in other ways isn't copied correctly.
+    fn clone(&self) -> Self {
+        let layout = Layout::new::<cubiomes_sys::Generator>();
+
+        // SAFETY:
+        // The new allocation fits a cubiomes generator and the bytes of this
+        // generator are copied to it without reading them. Afterwards every
+        // pointer into the old generator is moved to the new one, so the copy
+        // never refers to this generator.
+        unsafe {
+            let generator = alloc(layout).cast::<cubiomes_sys::Generator>();
+            if generator.is_null() {
+                handle_alloc_error(layout);
+            }
+
+            std::ptr::copy_nonoverlapping(self.generator, generator, 1);
+            relocate(generator, self.generator.addr());
+
+            Self { generator }
+        }
+    }
+}
+
+/// Moves the pointers of a copied generator from the original to the copy
+///
+/// Only fields which are initialized for the version and dimension of the
+/// generator are read.
+///
+/// # Safety
+/// `generator` must be a byte for byte copy of a generator at `original`,
+/// which was set up by `setupGenerator` and seeded by `applySeed`.
+unsafe fn relocate(generator: *mut cubiomes_sys::Generator, original: usize) {
+    let moved = Relocation {
+        original,
+        copy: generator.cast(),
+    };
+
+    // SAFETY:
+    // The fields are projected with raw pointers and only read if cubiomes
+    // initializes them for the version and dimension, see setupGenerator and
+    // applySeed in generator.c
+    unsafe {
+        let mc = (*generator).mc;
+        let dim = (*generator).dim;
+        let flags = GeneratorFlags::from_bits_retain((*generator).flags);
+        let noises = &raw mut (*generator).__bindgen_anon_1;
+
+        if (MCVersion::MC_B1_8 as i32..=MCVersion::MC_1_17 as i32).contains(&mc) {
+            let layers = &raw mut (*noises).__bindgen_anon_1;
+            let stack = &raw mut (*layers).ls;
+
+            for i in 0..(*stack).layers.len() {
+                moved.layer(&raw mut (*stack).layers[i]);
+            }
+
+            moved.pointer(&raw mut (*stack).entry_1);
+            moved.pointer(&raw mut (*stack).entry_4);
+            moved.pointer(&raw mut (*stack).entry_16);
+            moved.pointer(&raw mut (*stack).entry_64);
+            moved.pointer(&raw mut (*stack).entry_256);
+            moved.pointer(&raw mut (*layers).entry);
+
+            // The custom entry layers are only set up for forced ocean variants
+            if flags.contains(GeneratorFlags::ForceOceanVariants) && mc >= MCVersion::MC_1_13 as i32
+            {
+                for i in 2..(*layers).xlayer.len() {
+                    moved.layer(&raw mut (*layers).xlayer[i]);
+                }
+            }
+        } else if mc >= MCVersion::MC_1_18 as i32 {
+            let noise = &raw mut (*noises).__bindgen_anon_2.bn;
+            let splines = &raw mut (*noise).ss;
+
+            moved.pointer(&raw mut (*noise).sp);
+
+            let spline_count = usize::try_from((*splines).len).unwrap_or(0);
+            for i in 0..spline_count.min((*splines).stack.len()) {
+                let spline = &raw mut (*splines).stack[i];
+                let value_count = usize::try_from((*spline).len).unwrap_or(0);
+
+                for j in 0..value_count.min((*spline).val.len()) {
+                    moved.pointer(&raw mut (*spline).val[j]);
+                }
+            }
+
+            if dim == Dimension::DIM_OVERWORLD as i32 {
+                for i in 0..(*noise).climate.len() {
+                    moved.double_perlin(&raw mut (*noise).climate[i]);
+                }
+            }
+        } else if dim == Dimension::DIM_OVERWORLD as i32 {
+            let noise = &raw mut (*noises).__bindgen_anon_3.bnb;
+
+            for i in 0..(*noise).climate.len() {
+                moved.pointer(&raw mut (*noise).climate[i].octaves);
+            }
+        }
+
+        if dim == Dimension::DIM_NETHER as i32 && mc >= MCVersion::MC_1_16_1 as i32 {
+            let noise = &raw mut (*generator).nn;
+
+            moved.double_perlin(&raw mut (*noise).temperature);
+            moved.double_perlin(&raw mut (*noise).humidity);
+        }
+    }
+}
+
+/// Moves pointers into an original generator to the same offset in its copy
+struct Relocation {
+    original: usize,
+    copy: *mut u8,
+}
+
+impl Relocation {
+    /// Moves a single pointer, if it points into the original generator
+    ///
+    /// # Safety
+    /// The field must be initialized and inside of the copy.
+    unsafe fn pointer<T>(&self, field: *mut *mut T) {
+        // SAFETY: The caller guarantees the field is initialized, and the
+        // offset is inside of the copy, which has the size of a generator
+        unsafe {
+            let offset = field.read().addr().wrapping_sub(self.original);
+
+            if offset < size_of::<cubiomes_sys::Generator>() {
+                field.write(self.copy.add(offset).cast());
+            }
+        }
+    }
+
+    /// Moves the parents and noise of a layer
+    ///
+    /// # Safety
+    /// The layer must be set up by `setupLayer` and inside of the copy.
+    unsafe fn layer(&self, layer: *mut cubiomes_sys::Layer) {
+        // SAFETY: setupLayer initializes every pointer of the layer
+        unsafe {
+            self.pointer(&raw mut (*layer).noise);
+            self.pointer(&raw mut (*layer).data);
+            self.pointer(&raw mut (*layer).p);
+            self.pointer(&raw mut (*layer).p2);
+        }
+    }
+
+    /// Moves the octaves of a double perlin noise
+    ///
+    /// # Safety
+    /// The noise must be initialized and inside of the copy.
+    unsafe fn double_perlin(&self, noise: *mut cubiomes_sys::DoublePerlinNoise) {
+        // SAFETY: Both octave noises of an initialized noise are initialized
+        unsafe {
+            self.pointer(&raw mut (*noise).octA.octaves);
+            self.pointer(&raw mut (*noise).octB.octaves);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! This module is used for biome color mappings
 
-use crate::enums::BiomeID;
+use super::Cache;
+use crate::{enums::BiomeID, render::Image};
 use cubiomes_sys::num_traits::FromPrimitive;
 use std::{collections::BTreeMap, mem::MaybeUninit};
 
@@ -32,3 +33,64 @@ pub fn new_biome_color_map() -> BTreeMap<BiomeID, [u8; 3]> {
         .filter_map(|(index, color)| BiomeID::from_usize(index).map(|biome_id| (biome_id, color)))
         .collect()
 }
+
+impl Cache<'_> {
+    /// Renders the lowest layer of the cache into a biome map
+    ///
+    /// Every cell is one pixel, colored with the colors from
+    /// [`new_biome_color_map()`]. Cells with unknown biomes are drawn black.
+    /// The cache should be filled before rendering, otherwise the image is
+    /// black.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range {
+    ///     scale: Scale::Quad,
+    ///     x: -64,
+    ///     z: -64,
+    ///     size_x: 128,
+    ///     size_z: 128,
+    ///     y: 16,
+    ///     size_y: 0,
+    /// });
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// let image = cache.to_image();
+    /// assert_eq!((image.width(), image.height()), (128, 128));
+    /// ```
+    #[must_use]
+    pub fn to_image(&self) -> Image {
+        let colors = new_biome_color_map();
+        let range = self.range();
+        let mut image = Image::new(range.size_x, range.size_z);
+
+        for (index, &raw_biomeid) in self
+            .as_vec()
+            .iter()
+            .take((range.size_x * range.size_z) as usize)
+            .enumerate()
+        {
+            let color = BiomeID::from_id(raw_biomeid)
+                .and_then(|biome| colors.get(&biome).copied())
+                .unwrap_or_default();
+
+            image.set_pixel(
+                index as u32 % range.size_x,
+                index as u32 / range.size_x,
+                color,
+            );
+        }
+
+        image
+    }
+}
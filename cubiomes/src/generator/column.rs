@@ -0,0 +1,154 @@
+use super::{error::GeneratorError, Cache, Generator, Range, Scale};
+use crate::enums::BiomeID;
+use std::ops::Range as YRange;
+
+/// The biomes of a vertical column of blocks
+///
+/// Created with [`Generator::biome_column()`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BiomeColumn {
+    /// The x coordinate of the column
+    pub x: i32,
+    /// The z coordinate of the column
+    pub z: i32,
+    /// The sampled heights and their biomes, from the bottom up
+    pub samples: Vec<(i32, BiomeID)>,
+}
+
+/// A vertical run of samples with the same biome
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BiomeSpan {
+    /// The biome of the span
+    pub biome: BiomeID,
+    /// The lowest sampled y of the span
+    pub min_y: i32,
+    /// The highest sampled y of the span
+    pub max_y: i32,
+}
+
+impl BiomeColumn {
+    /// Gets the runs of samples with the same biome, from the bottom up
+    ///
+    /// The bounds of each span are the lowest and highest samples in it, so
+    /// with a step larger than 1 the actual border is somewhere between two
+    /// spans.
+    #[must_use]
+    pub fn spans(&self) -> Vec<BiomeSpan> {
+        let mut spans: Vec<BiomeSpan> = Vec::new();
+
+        for &(y, biome) in &self.samples {
+            match spans.last_mut() {
+                Some(span) if span.biome == biome => span.max_y = y,
+                _ => spans.push(BiomeSpan {
+                    biome,
+                    min_y: y,
+                    max_y: y,
+                }),
+            }
+        }
+
+        spans
+    }
+
+    /// Gets the spans of a single biome in the column
+    pub fn spans_of(&self, biome: BiomeID) -> impl Iterator<Item = BiomeSpan> {
+        self.spans()
+            .into_iter()
+            .filter(move |span| span.biome == biome)
+    }
+}
+
+impl Generator {
+    /// Gets the biome at every `step` blocks of height in a column
+    ///
+    /// The column is sampled at block scale from the bottom of `ys` up,
+    /// excluding the end of the range. This is cheaper than a full 3d cache
+    /// when only a single column is needed, for example to find where cave
+    /// biomes start and end.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let column = generator
+    ///     .biome_column(-18, -38, generator.world_limits().y_range(), 4)
+    ///     .expect("failed to generate biomes");
+    ///
+    /// // A lush cave below the ocean
+    /// let biomes: Vec<_> = column.spans().iter().map(|span| span.biome).collect();
+    /// assert_eq!(biomes, [BiomeID::cold_ocean, BiomeID::lush_caves, BiomeID::cold_ocean]);
+    ///
+    /// let cave = column.spans_of(BiomeID::lush_caves).next().expect("there is a lush cave");
+    /// assert_eq!((cave.min_y, cave.max_y), (-16, -16));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::ZeroStep`] if `step` is 0,
+    /// [`GeneratorError::YOutOfBounds`] if `ys` isn't inside the
+    /// [`Self::world_limits()`], and an error if generating the biomes fails
+    pub fn biome_column(
+        &self,
+        x: i32,
+        z: i32,
+        ys: YRange<i32>,
+        step: usize,
+    ) -> Result<BiomeColumn, GeneratorError> {
+        if step == 0 {
+            return Err(GeneratorError::ZeroStep);
+        }
+
+        if !ys.is_empty() {
+            let limits = self.world_limits();
+            limits.check_y(ys.start)?;
+            limits.check_y(ys.end - 1)?;
+        }
+
+        let range = Range {
+            scale: Scale::Block,
+            x,
+            z,
+            size_x: 1,
+            size_z: 1,
+            y: ys.start,
+            size_y: 1,
+        };
+
+        let samples = if ys.is_empty() {
+            Vec::new()
+        } else if step == 1 {
+            // Every block is needed, so the column is generated at once
+            let mut cache = Cache::new(
+                self,
+                Range {
+                    size_y: ys.len() as u32,
+                    ..range
+                },
+            );
+            cache.fill_cache()?;
+
+            ys.zip(0..)
+                .map(|(y, layer)| Ok((y, cache.biome_at(0, layer, 0)?)))
+                .collect::<Result<_, GeneratorError>>()?
+        } else {
+            let mut cache = Cache::new(self, range);
+
+            ys.step_by(step)
+                .map(|y| {
+                    cache.move_cache(x, y, z);
+                    cache.fill_cache()?;
+                    Ok((y, cache.biome_at(0, 0, 0)?))
+                })
+                .collect::<Result<_, GeneratorError>>()?
+        };
+
+        Ok(BiomeColumn { x, z, samples })
+    }
+}
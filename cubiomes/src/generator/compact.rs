@@ -0,0 +1,205 @@
+use super::{error::GeneratorError, Cache, Generator, Range};
+use crate::enums::BiomeID;
+
+/// The most cells generated at once by [`CompactCache::generate()`]
+///
+/// The range is generated in strips of rows of about this many cells, so only
+/// one strip is held as 32 bit ids at a time.
+const STRIP_CELLS: u64 = 1 << 16;
+
+/// Filled biomes of a range stored in a single byte per cell
+///
+/// Every biome id fits in a byte, so this takes a quarter of the memory of a
+/// [`Cache`], which holds the ids as `i32` like cubiomes does. Scanning the
+/// smaller buffer is faster as well. The cells are in the same order as
+/// [`Cache::as_vec()`], and the compact cache doesn't borrow the generator.
+///
+/// A filled cache is compacted with [`Cache::compact()`], and large ranges are
+/// generated directly with [`Self::generate()`] without ever holding the
+/// whole range as a cache.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{CompactCache, Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let map = CompactCache::generate(&generator, Range {
+///     scale: Scale::Quad,
+///     x: -512,
+///     z: -512,
+///     size_x: 1024,
+///     size_z: 1024,
+///     y: 16,
+///     size_y: 0,
+/// })
+/// .expect("failed to generate biomes");
+///
+/// assert_eq!(map.as_slice().len(), 1024 * 1024);
+/// assert_eq!(map.count(BiomeID::plains), 53407);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompactCache {
+    biomes: Vec<u8>,
+    range: Range,
+}
+
+impl CompactCache {
+    /// Generates the biomes of a range into a compact cache
+    ///
+    /// The range is generated in strips of rows, which are compacted one at a
+    /// time, so the memory used is about a byte per cell of the range. The
+    /// exception is [`super::Scale::Block`] in 1.15 to 1.17, where cubiomes
+    /// generates slightly different biomes for a strip than for the whole
+    /// range, so the range is generated at once.
+    ///
+    /// # Errors
+    /// Returns an error if generating a strip fails or it holds a biome which
+    /// doesn't fit in a byte
+    pub fn generate(generator: &Generator, range: Range) -> Result<Self, GeneratorError> {
+        let (size_x, size_y) = (u64::from(range.size_x), u64::from(range.size_y.max(1)));
+        let rows_per_strip = if generator.generates_in_parts(range.scale) {
+            u32::try_from(STRIP_CELLS / (size_x * size_y).max(1))
+                .unwrap_or(u32::MAX)
+                .max(1)
+        } else {
+            range.size_z
+        };
+
+        let layer = range.size_x as usize * range.size_z as usize;
+        let mut biomes = vec![0; layer * size_y as usize];
+
+        let mut z = 0;
+        while z < range.size_z {
+            let rows = rows_per_strip.min(range.size_z - z);
+            let mut strip = Cache::new(
+                generator,
+                Range {
+                    z: range.z + z as i32,
+                    size_z: rows,
+                    ..range
+                },
+            );
+            strip.fill_cache()?;
+
+            let strip = strip.compact()?;
+            let strip_layer = range.size_x as usize * rows as usize;
+            let offset = z as usize * range.size_x as usize;
+
+            for (y, cells) in strip.biomes.chunks_exact(strip_layer).enumerate() {
+                let start = y * layer + offset;
+                biomes[start..start + strip_layer].copy_from_slice(cells);
+            }
+
+            z += rows;
+        }
+
+        Ok(Self { biomes, range })
+    }
+
+    /// Gets the range of the biomes
+    #[must_use]
+    pub fn range(&self) -> &Range {
+        &self.range
+    }
+
+    /// Gets the biome id of every cell
+    ///
+    /// The cells are in the same order as in [`Cache::as_vec()`].
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.biomes
+    }
+
+    /// Gets the biome of a cell, relative to the upper left corner of the
+    /// range
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cell is outside of
+    /// the range
+    pub fn biome_at(&self, x: u32, y: u32, z: u32) -> Result<BiomeID, GeneratorError> {
+        if x >= self.range.size_x || z >= self.range.size_z {
+            return Err(GeneratorError::IndexOutOfBounds);
+        }
+
+        let index = (u64::from(y) * u64::from(self.range.size_z) + u64::from(z))
+            * u64::from(self.range.size_x)
+            + u64::from(x);
+        let raw_biomeid = *usize::try_from(index)
+            .ok()
+            .and_then(|index| self.biomes.get(index))
+            .ok_or(GeneratorError::IndexOutOfBounds)?;
+
+        BiomeID::from_id(i32::from(raw_biomeid))
+            .ok_or(GeneratorError::BiomeIDOutOfRange(i32::from(raw_biomeid)))
+    }
+
+    /// Iterates the biome of every cell, in the order of [`Self::as_slice()`]
+    pub fn biomes(&self) -> impl ExactSizeIterator<Item = BiomeID> + '_ {
+        // Every byte was a valid biome when the cache was compacted
+        self.biomes
+            .iter()
+            .map(|&raw_biomeid| BiomeID::from_id(i32::from(raw_biomeid)).unwrap_or(BiomeID::none))
+    }
+
+    /// Counts the cells of a single biome
+    ///
+    /// See [`Cache::count()`].
+    #[must_use]
+    pub fn count(&self, biome: BiomeID) -> usize {
+        match u8::try_from(biome as i32) {
+            Ok(biome) => self.biomes.iter().filter(|&&cell| cell == biome).count(),
+            Err(_) => 0,
+        }
+    }
+
+    /// Counts the cells matching a predicate
+    ///
+    /// See [`Cache::count_where()`].
+    pub fn count_where(&self, mut predicate: impl FnMut(BiomeID) -> bool) -> usize {
+        self.biomes().filter(|&biome| predicate(biome)).count()
+    }
+
+    /// Checks if any cell matches a predicate
+    ///
+    /// See [`Cache::any()`].
+    pub fn any(&self, predicate: impl FnMut(BiomeID) -> bool) -> bool {
+        self.biomes().any(predicate)
+    }
+}
+
+impl Cache<'_> {
+    /// Copies the filled biomes of the cache into a [`CompactCache`]
+    ///
+    /// The cache can then be dropped, or moved and filled again, while the
+    /// compact copy keeps the biomes with a quarter of the memory.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cache isn't filled
+    /// and [`GeneratorError::BiomeIDOutOfRange`] if it holds a biome which
+    /// doesn't fit in a byte
+    pub fn compact(&self) -> Result<CompactCache, GeneratorError> {
+        let biomes = self
+            .buffer
+            .get(..self.calculate_readable_cache_length())
+            .ok_or(GeneratorError::IndexOutOfBounds)?
+            .iter()
+            .map(|&raw_biomeid| {
+                BiomeID::from_id(raw_biomeid)
+                    .and_then(|_| u8::try_from(raw_biomeid).ok())
+                    .ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        Ok(CompactCache {
+            biomes,
+            range: self.range,
+        })
+    }
+}
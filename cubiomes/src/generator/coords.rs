@@ -0,0 +1,113 @@
+//! Conversions between block coordinates and coarser grids
+//!
+//! Minecraft splits the world into 1:4 biome cells, 16 block chunks and
+//! structure regions of a number of chunks. Every conversion here rounds
+//! towards negative infinity, so block -1 is in chunk -1 and not in chunk 0.
+//! This is what minecraft and cubiomes do, and differs from rust's `/`
+//! operator, which rounds towards zero and puts blocks -15 to 15 into the
+//! same chunk.
+//!
+//! The conversions to blocks give the block at the lowest corner of a cell,
+//! chunk or region.
+//!
+//! # Examples
+//! ```
+//! use cubiomes::generator::coords;
+//!
+//! assert_eq!(coords::block_to_chunk(-1), -1);
+//! assert_eq!(coords::block_to_chunk(-16), -1);
+//! assert_eq!(coords::block_to_chunk(-17), -2);
+//! assert_eq!(coords::chunk_to_block(-2), -32);
+//!
+//! assert_eq!(coords::block_to_quad(-5), -2);
+//! assert_eq!(coords::block_in_chunk(-1), 15);
+//!
+//! // Villages are in regions of 34 chunks
+//! assert_eq!(coords::block_to_region(-1, 34), -1);
+//! assert_eq!(coords::region_to_block(-1, 34), -544);
+//! ```
+
+use super::Scale;
+
+/// The width of a chunk in blocks
+pub const CHUNK_SIZE: i32 = 16;
+/// The width of a 1:4 biome cell in blocks
+pub const QUAD_SIZE: i32 = 4;
+
+/// Gets the coordinate of the cell of a scale holding a block coordinate
+///
+/// For [`Scale::Block`] this is the block coordinate itself
+#[must_use]
+pub const fn block_to_cell(block: i32, scale: Scale) -> i32 {
+    block.div_euclid(scale as i32)
+}
+
+/// Gets the lowest block coordinate of a cell of a scale
+#[must_use]
+pub const fn cell_to_block(cell: i32, scale: Scale) -> i32 {
+    cell * scale as i32
+}
+
+/// Gets the coordinate of the 1:4 biome cell holding a block coordinate
+#[must_use]
+pub const fn block_to_quad(block: i32) -> i32 {
+    block.div_euclid(QUAD_SIZE)
+}
+
+/// Gets the lowest block coordinate of a 1:4 biome cell
+#[must_use]
+pub const fn quad_to_block(quad: i32) -> i32 {
+    quad * QUAD_SIZE
+}
+
+/// Gets the coordinate of the chunk holding a block coordinate
+#[must_use]
+pub const fn block_to_chunk(block: i32) -> i32 {
+    block.div_euclid(CHUNK_SIZE)
+}
+
+/// Gets the lowest block coordinate of a chunk
+#[must_use]
+pub const fn chunk_to_block(chunk: i32) -> i32 {
+    chunk * CHUNK_SIZE
+}
+
+/// Gets the offset of a block coordinate inside its chunk, from 0 to 15
+#[must_use]
+pub const fn block_in_chunk(block: i32) -> i32 {
+    block.rem_euclid(CHUNK_SIZE)
+}
+
+/// Gets the coordinate of the structure region holding a chunk coordinate
+///
+/// `region_size` is the size of the regions in chunks, see
+/// [`crate::structures::StructureRegion::region_size_chunks()`]
+#[must_use]
+pub const fn chunk_to_region(chunk: i32, region_size: i32) -> i32 {
+    chunk.div_euclid(region_size)
+}
+
+/// Gets the lowest chunk coordinate of a structure region
+///
+/// `region_size` is the size of the regions in chunks
+#[must_use]
+pub const fn region_to_chunk(region: i32, region_size: i32) -> i32 {
+    region * region_size
+}
+
+/// Gets the coordinate of the structure region holding a block coordinate
+///
+/// `region_size` is the size of the regions in chunks, see
+/// [`crate::structures::StructureRegion::region_size_chunks()`]
+#[must_use]
+pub const fn block_to_region(block: i32, region_size: i32) -> i32 {
+    chunk_to_region(block_to_chunk(block), region_size)
+}
+
+/// Gets the lowest block coordinate of a structure region
+///
+/// `region_size` is the size of the regions in chunks
+#[must_use]
+pub const fn region_to_block(region: i32, region_size: i32) -> i32 {
+    chunk_to_block(region_to_chunk(region, region_size))
+}
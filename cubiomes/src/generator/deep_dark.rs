@@ -0,0 +1,93 @@
+use super::{
+    error::GeneratorError, Aabb, BiomePatch, BlockPosition, Cache, Generator, Range, Scale,
+};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use std::cmp::Reverse;
+
+/// The y at which deep dark patches are searched
+///
+/// This is the floor of ancient cities, deep enough that the deep dark
+/// covers most of its area at this height.
+pub const DEEP_DARK_Y: i32 = -51;
+
+impl Generator {
+    /// Finds the deep dark patches of at least `min_area` blocks around a
+    /// position
+    ///
+    /// The biomes are sampled at 1:4 scale at [`DEEP_DARK_Y`], in a square
+    /// reaching `radius` blocks from `origin`. Patches touching the edge of
+    /// the square may continue outside of it, so their area is a lower bound.
+    /// The patches are sorted with the largest one first.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let patches = generator
+    ///     .deep_dark_patches(BlockPosition::new(0, 0), 1000, 10_000)
+    ///     .expect("the deep dark exists in 1.20");
+    ///
+    /// assert_eq!(patches.len(), 4);
+    /// assert!(patches.iter().all(|patch| patch.area >= 10_000));
+    /// // The largest patch comes first
+    /// assert_eq!(patches[0].area, 482864);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] before 1.19, which has no
+    /// deep dark, [`GeneratorError::UnsupportedDimension`] outside of the
+    /// overworld, or an error if generating the biomes fails
+    pub fn deep_dark_patches(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        min_area: u64,
+    ) -> Result<Vec<BiomePatch>, GeneratorError> {
+        if self.version() < MCVersion::MC_1_19_2 {
+            return Err(GeneratorError::UnsupportedVersion(self.version()));
+        }
+        if self.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        let reach = i32::try_from(radius).unwrap_or(i32::MAX);
+        let area = Aabb::new(
+            (
+                origin.x.saturating_sub(reach),
+                DEEP_DARK_Y,
+                origin.z.saturating_sub(reach),
+            ),
+            (
+                origin.x.saturating_add(reach),
+                DEEP_DARK_Y,
+                origin.z.saturating_add(reach),
+            ),
+        );
+
+        let mut cache = Cache::new(
+            self,
+            Range {
+                size_y: 0,
+                ..area.to_range(Scale::Quad)
+            },
+        );
+        cache.fill_cache()?;
+
+        let mut patches: Vec<BiomePatch> = cache
+            .patches_where(|biome| biome == BiomeID::deep_dark)?
+            .into_iter()
+            .filter(|patch| patch.area >= min_area)
+            .collect();
+
+        patches.sort_by_key(|patch| Reverse(patch.area));
+        Ok(patches)
+    }
+}
@@ -0,0 +1,259 @@
+use super::{
+    colors, error::GeneratorError, BlockPosition, Cache, Generator, GeneratorFlags, Range,
+};
+use crate::{
+    enums::{BiomeID, Dimension, MCVersion},
+    render::Image,
+};
+use std::collections::BTreeMap;
+
+/// A cell whose biome differs between two generators
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BiomeChange {
+    /// The x coordinate of the cell, relative to the range
+    pub x: u32,
+    /// The y coordinate of the cell, relative to the range
+    pub y: u32,
+    /// The z coordinate of the cell, relative to the range
+    pub z: u32,
+    /// The biome generated by the first generator
+    pub first: BiomeID,
+    /// The biome generated by the second generator
+    pub second: BiomeID,
+}
+
+/// The amount of cells which changed from one biome to another
+///
+/// See [`BiomeDiff::transitions()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BiomeTransition {
+    /// The biome generated by the first generator
+    pub from: BiomeID,
+    /// The biome generated by the second generator
+    pub to: BiomeID,
+    /// The amount of cells which changed from `from` to `to`
+    pub cells: u64,
+}
+
+/// The differences between the biomes of two generators in a range
+///
+/// Constructed with [`Generator::diff_biomes()`]. The coordinates of the
+/// changes are relative to the range, the same as in
+/// [`Cache::biome_at()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BiomeDiff {
+    range: Range,
+    first: Vec<i32>,
+    changes: Vec<BiomeChange>,
+}
+
+impl BiomeDiff {
+    /// Compares the biomes of a seed between two minecraft versions
+    ///
+    /// This is [`Generator::diff_biomes()`] with a generator for each
+    /// version, which shows the cells of a world which change biome when it
+    /// is upgraded. See [`Self::transitions()`] for an example.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Generator::diff_biomes()`]
+    pub fn between_versions(
+        seed: i64,
+        dimension: Dimension,
+        flags: GeneratorFlags,
+        from: MCVersion,
+        to: MCVersion,
+        range: Range,
+    ) -> Result<Self, GeneratorError> {
+        let old = Generator::new(from, seed, dimension, flags);
+        let new = Generator::new(to, seed, dimension, flags);
+
+        old.diff_biomes(&new, range)
+    }
+
+    /// Gets the range the diff was generated in
+    #[must_use]
+    pub fn range(&self) -> &Range {
+        &self.range
+    }
+
+    /// Gets every cell that differs between the generators
+    #[must_use]
+    pub fn changes(&self) -> &[BiomeChange] {
+        &self.changes
+    }
+
+    /// Checks if both generators generated exactly the same biomes
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Gets the changed cells grouped by the biomes they changed from and to
+    ///
+    /// The transitions are sorted by their amount of cells, the most common
+    /// first, and then by their biomes.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{BiomeDiff, GeneratorFlags, Range, Scale};
+    ///
+    /// let range = Range {
+    ///     scale: Scale::Quad,
+    ///     x: -64,
+    ///     z: -64,
+    ///     size_x: 128,
+    ///     size_z: 128,
+    ///     y: 16,
+    ///     size_y: 0,
+    /// };
+    ///
+    /// let diff = BiomeDiff::between_versions(
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    ///     MCVersion::MC_1_17_1,
+    ///     MCVersion::MC_1_18_2,
+    ///     range,
+    /// )
+    /// .expect("failed to generate biomes");
+    ///
+    /// // The transitions are sorted with the most common one first
+    /// let transitions = diff.transitions();
+    /// assert_eq!(transitions.len(), 40);
+    /// assert_eq!((transitions[0].from, transitions[0].to, transitions[0].cells), (BiomeID::frozen_ocean, BiomeID::ocean, 2399));
+    /// ```
+    #[must_use]
+    pub fn transitions(&self) -> Vec<BiomeTransition> {
+        let mut counts: BTreeMap<(BiomeID, BiomeID), u64> = BTreeMap::new();
+        for change in &self.changes {
+            *counts.entry((change.first, change.second)).or_default() += 1;
+        }
+
+        let mut transitions: Vec<BiomeTransition> = counts
+            .into_iter()
+            .map(|((from, to), cells)| BiomeTransition { from, to, cells })
+            .collect();
+        transitions.sort_by(|a, b| b.cells.cmp(&a.cells).then(a.cmp(b)));
+
+        transitions
+    }
+
+    /// Gets the percentage of cells which differ between the generators
+    #[must_use]
+    pub fn percentage_changed(&self) -> f64 {
+        if self.first.is_empty() {
+            return 0.0;
+        }
+
+        self.changes.len() as f64 / self.first.len() as f64 * 100.0
+    }
+
+    /// Gets the block position of a changed cell
+    #[must_use]
+    pub fn block_position(&self, change: &BiomeChange) -> BlockPosition {
+        BlockPosition::from_scaled(
+            self.range.x + change.x as i32,
+            self.range.z + change.z as i32,
+            self.range.scale,
+        )
+    }
+
+    /// Renders the lowest layer of the diff into an image
+    ///
+    /// Every cell is one pixel. Unchanged cells are drawn with a darkened
+    /// color of their biome and changed cells are drawn in red.
+    #[must_use]
+    pub fn to_image(&self) -> Image {
+        let colors = colors::new_biome_color_map();
+        let mut image = Image::new(self.range.size_x, self.range.size_z);
+
+        for z in 0..self.range.size_z {
+            for x in 0..self.range.size_x {
+                let raw_biomeid = self.first[(z * self.range.size_x + x) as usize];
+                let [r, g, b] = BiomeID::from_id(raw_biomeid)
+                    .and_then(|biome| colors.get(&biome).copied())
+                    .unwrap_or_default();
+
+                image.set_pixel(x, z, [r / 3, g / 3, b / 3]);
+            }
+        }
+
+        for change in self.changes.iter().filter(|change| change.y == 0) {
+            image.set_pixel(change.x, change.z, [255, 0, 0]);
+        }
+
+        image
+    }
+}
+
+impl Generator {
+    /// Compares the biomes of two generators in the same range
+    ///
+    /// The generators can differ in seed, minecraft version or flags. Useful
+    /// for studying how biomes change between versions.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let seed = -5134222539607995087;
+    /// let old = Generator::new(MCVersion::MC_1_20, seed, Dimension::DIM_OVERWORLD, GeneratorFlags::empty());
+    /// let new = Generator::new(MCVersion::MC_1_20_6, seed, Dimension::DIM_OVERWORLD, GeneratorFlags::empty());
+    ///
+    /// let range = Range {
+    ///     scale: Scale::Quad,
+    ///     x: -64,
+    ///     z: -64,
+    ///     size_x: 128,
+    ///     size_z: 128,
+    ///     y: 16,
+    ///     size_y: 0,
+    /// };
+    ///
+    /// let diff = old.diff_biomes(&new, range).expect("failed to generate biomes");
+    ///
+    /// // The biomes didn't change between the two releases
+    /// assert!(diff.is_empty());
+    /// assert_eq!(diff.percentage_changed(), 0.0);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes of either generator fails or
+    /// they contain an invalid biome
+    pub fn diff_biomes(
+        &self,
+        other: &Generator,
+        range: Range,
+    ) -> Result<BiomeDiff, GeneratorError> {
+        let mut first = Cache::new(self, range);
+        first.fill_cache()?;
+
+        let mut second = Cache::new(other, range);
+        second.fill_cache()?;
+
+        let (size_x, size_z) = (range.size_x as usize, range.size_z as usize);
+        let mut changes = Vec::new();
+
+        for (i, (&a, &b)) in first.as_vec().iter().zip(second.as_vec()).enumerate() {
+            if a == b {
+                continue;
+            }
+
+            changes.push(BiomeChange {
+                x: (i % size_x) as u32,
+                y: (i / (size_x * size_z)) as u32,
+                z: ((i / size_x) % size_z) as u32,
+                first: BiomeID::from_id(a).ok_or(GeneratorError::BiomeIDOutOfRange(a))?,
+                second: BiomeID::from_id(b).ok_or(GeneratorError::BiomeIDOutOfRange(b))?,
+            });
+        }
+
+        Ok(BiomeDiff {
+            range,
+            first: first.as_vec().clone(),
+            changes,
+        })
+    }
+}
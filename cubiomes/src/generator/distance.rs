@@ -0,0 +1,239 @@
+use super::{error::GeneratorError, Cache, Scale};
+use crate::{enums::BiomeID, render::Image};
+
+/// A stand in for an infinite distance, which keeps the envelope math finite
+const FAR: f64 = 1e20;
+
+/// The distance from every cell of a cache to the nearest matching cell
+///
+/// The distances are euclidean and measured between the centers of the
+/// cells, in cells of the scale of the cache. Created with
+/// [`Cache::distance_field()`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let mut cache = Cache::new(&generator, Range {
+///     scale: Scale::Quad,
+///     x: -128,
+///     z: -128,
+///     size_x: 256,
+///     size_z: 256,
+///     y: 16,
+///     size_y: 0,
+/// });
+/// cache.fill_cache().expect("failed to fill cache");
+///
+/// let field = cache
+///     .distance_field(|biome| matches!(biome, BiomeID::ocean | BiomeID::deep_ocean))
+///     .expect("the cache holds valid biomes");
+///
+/// let distance = field.block_distance_at(128, 128).expect("there is an ocean in the cache");
+/// assert_eq!(distance.round(), 84.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceField {
+    size_x: u32,
+    size_z: u32,
+    scale: Scale,
+    distances: Vec<f32>,
+}
+
+impl DistanceField {
+    /// Gets the width of the field in cells
+    #[must_use]
+    pub fn size_x(&self) -> u32 {
+        self.size_x
+    }
+
+    /// Gets the height of the field in cells
+    #[must_use]
+    pub fn size_z(&self) -> u32 {
+        self.size_z
+    }
+
+    /// Gets the distances of every cell, row by row
+    ///
+    /// Cells with no matching cell in the cache have an infinite distance
+    #[must_use]
+    pub fn distances(&self) -> &[f32] {
+        &self.distances
+    }
+
+    /// Gets the distance in cells from a cell to the nearest matching cell
+    ///
+    /// The cell is relative to the upper left corner of the cache. Returns
+    /// [`None`] if the cell is outside the field or nothing matched.
+    #[must_use]
+    pub fn distance_at(&self, x: u32, z: u32) -> Option<f32> {
+        (x < self.size_x && z < self.size_z)
+            .then(|| self.distances[z as usize * self.size_x as usize + x as usize])
+            .filter(|distance| distance.is_finite())
+    }
+
+    /// Gets the distance in blocks from a cell to the nearest matching cell
+    ///
+    /// See [`Self::distance_at()`]
+    #[must_use]
+    pub fn block_distance_at(&self, x: u32, z: u32) -> Option<f64> {
+        self.distance_at(x, z)
+            .map(|distance| f64::from(distance) * f64::from(self.scale as i32))
+    }
+
+    /// Gets the largest finite distance in the field
+    ///
+    /// Returns [`None`] if nothing matched
+    #[must_use]
+    pub fn max_distance(&self) -> Option<f32> {
+        self.distances
+            .iter()
+            .copied()
+            .filter(|distance| distance.is_finite())
+            .reduce(f32::max)
+    }
+
+    /// Renders the field as a grayscale heatmap
+    ///
+    /// Matching cells are black and cells at `max_distance` or further are
+    /// white.
+    #[must_use]
+    pub fn to_image(&self, max_distance: f32) -> Image {
+        let mut image = Image::new(self.size_x, self.size_z);
+        let max_distance = max_distance.max(f32::EPSILON);
+
+        for (index, distance) in self.distances.iter().enumerate() {
+            let value = ((distance / max_distance).min(1.0) * 255.0).round() as u8;
+
+            image.set_pixel(
+                index as u32 % self.size_x,
+                index as u32 / self.size_x,
+                [value; 3],
+            );
+        }
+
+        image
+    }
+}
+
+impl Cache<'_> {
+    /// Computes the distance from every cell to the nearest cell matching a
+    /// predicate
+    ///
+    /// Only the lowest layer of the cache is used. The distances are exact,
+    /// computed in linear time with the distance transform from Felzenszwalb
+    /// and Huttenlocher. See [`DistanceField`] for an example.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cache has not been
+    /// filled and [`GeneratorError::BiomeIDOutOfRange`] if the layer holds an
+    /// unknown biome
+    pub fn distance_field(
+        &self,
+        mut predicate: impl FnMut(BiomeID) -> bool,
+    ) -> Result<DistanceField, GeneratorError> {
+        let size_x = self.range.size_x as usize;
+        let size_z = self.range.size_z as usize;
+        let layer = self
+            .buffer
+            .get(..size_x * size_z)
+            .ok_or(GeneratorError::IndexOutOfBounds)?;
+
+        let mut squared = layer
+            .iter()
+            .map(|&raw_biomeid| {
+                let biome = BiomeID::from_id(raw_biomeid)
+                    .ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))?;
+                Ok(if predicate(biome) { 0.0 } else { FAR })
+            })
+            .collect::<Result<Vec<f64>, GeneratorError>>()?;
+
+        // The transform is separable, so the rows and then the columns are
+        // transformed on their own
+        let mut line = Vec::with_capacity(size_x.max(size_z));
+
+        for row in squared.chunks_exact_mut(size_x) {
+            line.clear();
+            line.extend_from_slice(row);
+            transform_line(&line, row);
+        }
+
+        let mut column = vec![0.0; size_z];
+        for x in 0..size_x {
+            line.clear();
+            line.extend((0..size_z).map(|z| squared[z * size_x + x]));
+            transform_line(&line, &mut column);
+
+            for (z, &distance) in column.iter().enumerate() {
+                squared[z * size_x + x] = distance;
+            }
+        }
+
+        Ok(DistanceField {
+            size_x: self.range.size_x,
+            size_z: self.range.size_z,
+            scale: self.range.scale,
+            distances: squared
+                .into_iter()
+                .map(|distance| {
+                    if distance >= FAR {
+                        f32::INFINITY
+                    } else {
+                        distance.sqrt() as f32
+                    }
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Computes the squared distance transform of a single line
+///
+/// Every output is the smallest `(q - p)² + input[p]` over every `p`, found
+/// from the lower envelope of the parabolas rooted at each input.
+fn transform_line(input: &[f64], output: &mut [f64]) {
+    let n = input.len();
+    let mut roots = vec![0usize; n];
+    let mut bounds = vec![0.0; n + 1];
+    let mut k = 0;
+
+    bounds[0] = f64::NEG_INFINITY;
+    bounds[1] = f64::INFINITY;
+
+    let intersection = |q: usize, p: usize| {
+        ((input[q] + (q * q) as f64) - (input[p] + (p * p) as f64)) / (2.0 * (q - p) as f64)
+    };
+
+    for q in 1..n {
+        // The first bound is negative infinity, so this stops at the first
+        // parabola at the latest
+        let mut s = intersection(q, roots[k]);
+        while s <= bounds[k] {
+            k -= 1;
+            s = intersection(q, roots[k]);
+        }
+
+        k += 1;
+        roots[k] = q;
+        bounds[k] = s;
+        bounds[k + 1] = f64::INFINITY;
+    }
+
+    k = 0;
+    for (q, out) in output.iter_mut().enumerate().take(n) {
+        while bounds[k + 1] < q as f64 {
+            k += 1;
+        }
+
+        let offset = q as f64 - roots[k] as f64;
+        *out = (offset * offset + input[roots[k]]).min(FAR);
+    }
+}
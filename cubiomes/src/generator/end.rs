@@ -0,0 +1,433 @@
+use super::{
+    error::GeneratorError, BlockPosition, Cache, Generator, HeightMap, Range, Scale, SurfaceNoise,
+};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+
+/// The kind of terrain at a position in the end
+///
+/// Created with [`Generator::end_terrain_at()`] or
+/// [`Generator::end_terrains()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EndTerrain {
+    /// The void between the islands, without any blocks
+    Void,
+    /// The main island with the exit portal, at the center of the end
+    MainIsland,
+    /// One of the small islands floating in the void of the outer end
+    SmallIsland,
+    /// The edges of the outer islands
+    Barrens,
+    /// The slopes between the edges and the highlands of the outer islands
+    Midlands,
+    /// The centers of the outer islands, where end cities generate
+    Highlands,
+}
+
+impl EndTerrain {
+    /// Classifies the terrain of a column from its biome and surface height
+    ///
+    /// A height of 0 or less is void, like the void cells of
+    /// [`Generator::end_surface_heights()`]. Biomes which don't generate in
+    /// the end are also void.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::BiomeID;
+    /// use cubiomes::generator::EndTerrain;
+    ///
+    /// assert_eq!(EndTerrain::classify(BiomeID::end_highlands, 70.0), EndTerrain::Highlands);
+    /// assert_eq!(EndTerrain::classify(BiomeID::small_end_islands, 0.0), EndTerrain::Void);
+    /// ```
+    #[must_use]
+    pub fn classify(biome: BiomeID, height: f32) -> Self {
+        if height <= 0.0 {
+            return Self::Void;
+        }
+
+        match biome {
+            BiomeID::the_end => Self::MainIsland,
+            BiomeID::small_end_islands => Self::SmallIsland,
+            BiomeID::end_barrens => Self::Barrens,
+            BiomeID::end_midlands => Self::Midlands,
+            BiomeID::end_highlands => Self::Highlands,
+            _ => Self::Void,
+        }
+    }
+
+    /// Checks if the terrain has blocks to land on
+    #[must_use]
+    pub fn is_solid(&self) -> bool {
+        *self != Self::Void
+    }
+}
+
+impl Generator {
+    /// Gets the surface height of a block column in the end
+    ///
+    /// The height includes the small end islands. Returns [`None`] if the
+    /// column is void, which makes it easy to tell the gaps between the outer
+    /// islands apart from solid ground.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_END,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// // The main island is always at the center of the end
+    /// assert!(generator.end_surface_height((0, 0)).expect("the generator is in the end").is_some());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedDimension`] if the generator isn't
+    /// in the end, [`GeneratorError::UnsupportedVersion`] before 1.9, which
+    /// has no end terrain noise, and [`GeneratorError::TryFromRangeError`] at
+    /// the very edge of the coordinates
+    pub fn end_surface_height(
+        &self,
+        pos: impl Into<BlockPosition>,
+    ) -> Result<Option<i32>, GeneratorError> {
+        let pos = pos.into();
+        super::validate::area(Scale::Block, pos.x, pos.z, 1, 1)?;
+
+        let noise = self.end_surface_noise()?;
+        let heights = self.map_end_heights(&noise, (pos.x, pos.z, 1, 1), Scale::Block)?;
+
+        Ok(heights
+            .first()
+            .map(|&height| height as i32)
+            .filter(|&height| height > 0))
+    }
+
+    /// Gets the surface heights of an area of the end
+    ///
+    /// The map is at the scale of the range, which has to be either
+    /// [`Scale::Block`] or [`Scale::Quad`], and its biomes are generated at
+    /// the y of the range. Void cells have a height of 0. Like
+    /// [`Self::end_surface_height()`], the heights include the small end
+    /// islands.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_END,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let heights = generator
+    ///     .end_surface_heights(Range {
+    ///         scale: Scale::Quad,
+    ///         x: 250,
+    ///         z: 0,
+    ///         size_x: 64,
+    ///         size_z: 64,
+    ///         y: 64,
+    ///         size_y: 0,
+    ///     })
+    ///     .expect("failed to map heights");
+    ///
+    /// let void = heights.heights().iter().filter(|&&height| height == 0.0).count();
+    /// assert_eq!(void, 1446);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::end_surface_height()`],
+    /// [`GeneratorError::UnsupportedScale`] if the range isn't at
+    /// [`Scale::Block`] or [`Scale::Quad`], and an error if the range can't be
+    /// converted or generating the biomes fails
+    pub fn end_surface_heights(&self, range: Range) -> Result<HeightMap, GeneratorError> {
+        if !matches!(range.scale, Scale::Block | Scale::Quad) {
+            return Err(GeneratorError::UnsupportedScale(range.scale));
+        }
+
+        let raw = cubiomes_sys::Range::try_from(range)?;
+        let noise = self.end_surface_noise()?;
+        let heights = self.map_end_heights(&noise, (raw.x, raw.z, raw.sx, raw.sz), range.scale)?;
+
+        let mut cache = Cache::new(self, Range { size_y: 0, ..range });
+        cache.fill_cache()?;
+
+        let cells = heights.len();
+        Ok(HeightMap {
+            scale: range.scale,
+            x: range.x,
+            z: range.z,
+            size_x: range.size_x,
+            size_z: range.size_z,
+            heights,
+            biomes: cache.as_vec()[..cells].to_vec(),
+        })
+    }
+
+    /// Gets the kind of terrain of a block column in the end
+    ///
+    /// This tells the main island, the small end islands and the parts of the
+    /// outer islands apart from the void between them, which the biome alone
+    /// can't, as the void of the outer end has the biome of the nearby
+    /// islands. See [`EndTerrain::classify()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{EndTerrain, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_END,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// assert_eq!(generator.end_terrain_at((0, 0)), Ok(EndTerrain::MainIsland));
+    /// assert_eq!(generator.end_terrain_at((700, 0)), Ok(EndTerrain::Void));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::end_surface_height()`], and an error
+    /// if generating the biome fails
+    pub fn end_terrain_at(
+        &self,
+        pos: impl Into<BlockPosition>,
+    ) -> Result<EndTerrain, GeneratorError> {
+        let pos = pos.into();
+        let terrains = self.end_terrains(Range {
+            scale: Scale::Block,
+            x: pos.x,
+            z: pos.z,
+            size_x: 1,
+            size_z: 1,
+            y: 0,
+            size_y: 0,
+        })?;
+
+        Ok(terrains[0])
+    }
+
+    /// Gets the kind of terrain of every cell of an area of the end, row by
+    /// row
+    ///
+    /// The terrain is classified from [`Self::end_surface_heights()`] of the
+    /// range, so it has the same requirements of the range.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{EndTerrain, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_END,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let terrains = generator
+    ///     .end_terrains(Range {
+    ///         scale: Scale::Quad,
+    ///         x: 250,
+    ///         z: 0,
+    ///         size_x: 64,
+    ///         size_z: 64,
+    ///         y: 64,
+    ///         size_y: 0,
+    ///     })
+    ///     .expect("failed to classify the terrain");
+    ///
+    /// let landing = terrains.iter().filter(|terrain| terrain.is_solid()).count();
+    /// assert_eq!(landing, 2650);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::end_surface_heights()`], and
+    /// [`GeneratorError::BiomeIDOutOfRange`] if a biome isn't known
+    pub fn end_terrains(&self, range: Range) -> Result<Vec<EndTerrain>, GeneratorError> {
+        let heights = self.end_surface_heights(range)?;
+
+        heights
+            .heights
+            .iter()
+            .zip(&heights.biomes)
+            .map(|(&height, &biome)| {
+                let biome =
+                    BiomeID::from_id(biome).ok_or(GeneratorError::BiomeIDOutOfRange(biome))?;
+                Ok(EndTerrain::classify(biome, height))
+            })
+            .collect()
+    }
+
+    /// Checks if a chunk of the end has no blocks
+    ///
+    /// The chunk is given in chunk coordinates. Like
+    /// [`Self::end_surface_height()`], this includes the small end islands,
+    /// but it only samples the terrain noise at the corners of the chunk, so
+    /// it is cheaper than mapping the heights of the chunk. Small end islands
+    /// only reaching the outermost blocks of the chunk are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_END,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// assert!(!generator.is_end_chunk_empty(0, 0).expect("the generator is in the end"));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::end_surface_height()`]
+    pub fn is_end_chunk_empty(&self, chunk_x: i32, chunk_z: i32) -> Result<bool, GeneratorError> {
+        let noise = self.end_surface_noise()?;
+
+        // SAFETY:
+        // The generator is an end generator since 1.9, so its end noise is
+        // seeded, and the surface noise is initialized for the same seed
+        let empty = unsafe {
+            let raw = &*self.generator;
+            cubiomes_sys::isEndChunkEmpty(&raw.en, noise.as_raw(), raw.seed, chunk_x, chunk_z)
+        };
+
+        Ok(empty != 0)
+    }
+
+    /// Gets the height of the ground an end city would stand on in a chunk
+    ///
+    /// This is the lowest surface of the corners of the footprint of an end
+    /// city in the chunk of `pos`, rotated the way the city would be, so it
+    /// tells if there is enough land for a building at any position of the
+    /// outer end. Returns [`None`] if the ground is lower than y 60, which is
+    /// too low for an end city. The small end islands are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_END,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let height = generator
+    ///     .end_city_terrain_height(BlockPosition::new(1500, -800))
+    ///     .expect("the generator is in the end");
+    ///
+    /// assert_eq!(height, Some(61));
+    ///
+    /// // The void between the main island and the outer islands
+    /// let void = generator
+    ///     .end_city_terrain_height(BlockPosition::new(600, 0))
+    ///     .expect("the generator is in the end");
+    /// assert_eq!(void, None);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::end_surface_height()`]
+    pub fn end_city_terrain_height(
+        &self,
+        pos: BlockPosition,
+    ) -> Result<Option<i32>, GeneratorError> {
+        let noise = self.end_surface_noise()?;
+
+        // SAFETY:
+        // The generator is an end generator since 1.9, so its end noise is
+        // seeded, and the surface noise is initialized for the same seed
+        let height = unsafe {
+            cubiomes_sys::isViableEndCityTerrain(&*self.generator, noise.as_raw(), pos.x, pos.z)
+        };
+
+        Ok((height > 0).then_some(height))
+    }
+
+    fn end_surface_noise(&self) -> Result<SurfaceNoise, GeneratorError> {
+        if self.dimension() != Dimension::DIM_END {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        // The end noise isn't seeded before 1.9
+        if self.minecraft_version() < MCVersion::MC_1_9 {
+            return Err(GeneratorError::UnsupportedVersion(self.minecraft_version()));
+        }
+
+        Ok(SurfaceNoise::new(self))
+    }
+
+    /// Maps the heights of an area given as x, z, width and height, which
+    /// are positive
+    fn map_end_heights(
+        &self,
+        noise: &SurfaceNoise,
+        (x, z, width, height): (i32, i32, i32, i32),
+        scale: Scale,
+    ) -> Result<Vec<f32>, GeneratorError> {
+        let mut heights = vec![0.0; width as usize * height as usize];
+
+        // SAFETY:
+        // The generator is an end generator since 1.9, so its end noise is
+        // seeded. The buffer has room for every cell of the area.
+        let result = unsafe {
+            let raw = &*self.generator;
+            let result = cubiomes_sys::mapEndSurfaceHeight(
+                heights.as_mut_ptr(),
+                &raw.en,
+                noise.as_raw(),
+                x,
+                z,
+                width,
+                height,
+                scale as i32,
+                0,
+            );
+
+            if result == 0 {
+                cubiomes_sys::mapEndIslandHeight(
+                    heights.as_mut_ptr(),
+                    &raw.en,
+                    raw.seed,
+                    x,
+                    z,
+                    width,
+                    height,
+                    scale as i32,
+                )
+            } else {
+                result
+            }
+        };
+
+        if result != 0 {
+            return Err(GeneratorError::ApproxHeightFailure {
+                code: result,
+                context: self.context(),
+                range: Range {
+                    scale,
+                    x,
+                    z,
+                    size_x: width as u32,
+                    size_z: height as u32,
+                    y: 0,
+                    size_y: 0,
+                },
+            });
+        }
+
+        Ok(heights)
+    }
+}
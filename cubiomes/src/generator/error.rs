@@ -1,7 +1,34 @@
 //! Errors related to [`super::Generator`] and [`super::Range`]
 
+use super::{BlockPosition3D, GeneratorFlags, Range, Scale};
+use crate::enums::{Dimension, MCVersion};
+use std::fmt::Display;
 use thiserror::Error;
 
+/// The settings of the generator an error happened with
+///
+/// Errors from cubiomes carry this, so the failing seed can be reproduced
+/// from the error alone, for example from the logs of a long search.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct GeneratorContext {
+    /// The minecraft version of the generator
+    pub version: MCVersion,
+    /// The seed applied to the generator
+    pub seed: i64,
+    /// The dimension applied to the generator
+    pub dimension: Dimension,
+}
+
+impl Display for GeneratorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "seed {} in {:?} of {:?}",
+            self.seed, self.dimension, self.version
+        )
+    }
+}
+
 /// An error with the generator
 ///
 /// This enum is produced as an error from the generator
@@ -17,20 +44,37 @@ pub enum GeneratorError {
     /// how you got it.
     #[error("Biome id {0} is out of range and is not a valid biomeid")]
     BiomeIDOutOfRange(i32),
+    /// The generator was created with [`super::Generator::new_without_seed()`]
+    /// and used before a seed was applied
+    ///
+    /// The version of the generator is given as a parameter.
+    #[error("The generator for {0:?} has no seed applied, use Generator::apply_seed() first")]
+    SeedNotApplied(MCVersion),
     /// The underlying cubiomes library indicates an error with your biome request
     ///
-    /// Cubiomes function getBiomeAt returned -1. This might indicate forgetting to
-    /// initialize the seed.
-    #[error(
-        "Function getBiomeAt failed with error code -1, did you forgot to initialize the seed?"
-    )]
-    GetBiomeAtFailure,
+    /// Cubiomes function getBiomeAt returned -1.
+    #[error("Function getBiomeAt failed at {pos:?} at {scale:?} scale for {context}")]
+    GetBiomeAtFailure {
+        /// The generator the biome was requested from
+        context: GeneratorContext,
+        /// The requested position, at the scale of the request
+        pos: BlockPosition3D,
+        /// The scale of the request
+        scale: Scale,
+    },
     /// Failed to fill the cache
     ///
     /// This indicates, that cubiomes failed to fill the cache and returned a non 0
     /// exit code.
-    #[error("Function genBiomes failed with error code {0}")]
-    GenBiomeToCacheFailure(i32),
+    #[error("Function genBiomes failed with error code {code} for {range:?} with {context}")]
+    GenBiomeToCacheFailure {
+        /// The exit code of genBiomes
+        code: i32,
+        /// The generator the cache was filled from
+        context: GeneratorContext,
+        /// The range of the cache
+        range: Range,
+    },
     /// Index out of bounds while getting from the cache
     ///
     /// This indicates that [`super::Cache::biome_at()`] tried to get an index
@@ -39,6 +83,78 @@ pub enum GeneratorError {
     /// of the lenght of the vector
     #[error("Index out of bounds")]
     IndexOutOfBounds,
+    /// Failed to approximate the surface height
+    ///
+    /// Cubiomes function mapApproxHeight or mapEndSurfaceHeight returned a
+    /// non 0 exit code. This happens for example in the nether, which has no
+    /// surface.
+    #[error(
+        "Function mapApproxHeight or mapEndSurfaceHeight failed with error code {code} for {range:?} with {context}"
+    )]
+    ApproxHeightFailure {
+        /// The exit code of the failed function
+        code: i32,
+        /// The generator the heights were approximated with
+        context: GeneratorContext,
+        /// The area of the heights, which are always 2d
+        range: Range,
+    },
+    /// The generator is for a dimension the operation doesn't support
+    ///
+    /// The dimension of the generator is given as a parameter.
+    #[error("The operation isn't supported in {0:?}")]
+    UnsupportedDimension(Dimension),
+    /// The generator is for a version the operation doesn't support
+    ///
+    /// The version of the generator is given as a parameter.
+    #[error("The operation isn't supported in {0:?}")]
+    UnsupportedVersion(MCVersion),
+    /// The operation doesn't support the scale of the range
+    ///
+    /// The scale of the range is given as a parameter.
+    #[error("The operation isn't supported at {0:?} scale")]
+    UnsupportedScale(Scale),
+    /// A range has several y levels, but the biomes of the dimension don't
+    /// vary with y at its scale
+    ///
+    /// See [`super::BiomeScales::varies_with_y()`].
+    #[error("The biomes of {dimension:?} don't vary with y at {scale:?} scale")]
+    PlanarBiomes {
+        /// The dimension of the generator
+        dimension: Dimension,
+        /// The scale of the range
+        scale: Scale,
+    },
+    /// A y is outside the build limits of the generator
+    ///
+    /// The y is given as a parameter. See [`super::WorldLimits`] for the
+    /// limits of each version and dimension.
+    #[error("The y {0} is outside the build limits")]
+    YOutOfBounds(i32),
+    /// The operation was cancelled with a [`crate::progress::CancellationToken`]
+    #[error("The operation was cancelled")]
+    Cancelled,
+    /// Voronoi access isn't possible with the given cache
+    ///
+    /// Biomes can only be looked up with voronoi access from caches at
+    /// [`super::Scale::Quad`], and only in the nether since 1.15 and in the
+    /// overworld since 1.18.
+    #[error("Voronoi access isn't supported for this cache")]
+    NoVoronoiAccess,
+    /// The flags have bits set which aren't a flag of cubiomes
+    ///
+    /// The unknown bits are given as a parameter. See
+    /// [`super::GeneratorFlags::validate_for()`].
+    #[error("The flags {0:#x} aren't known to cubiomes")]
+    UnknownFlags(u32),
+    /// A step of 0 was given, which would never advance
+    #[error("The step must not be 0")]
+    ZeroStep,
+    /// A value made with one generator was used with another one
+    ///
+    /// The generator it was used with is given as a parameter.
+    #[error("The value was made with another generator than {0}")]
+    MismatchedGenerator(GeneratorContext),
     #[error("Failed to convert range")]
     /// An error happened converting the range for use with cubiomes.
     TryFromRangeError(TryFromRangeError),
@@ -50,13 +166,24 @@ impl From<TryFromRangeError> for GeneratorError {
     }
 }
 
-/// The given size x y or z is too big to fit an i32 or x or z are zero.
+/// A y level couldn't be parsed from a string
+///
+/// See [`super::YLevel`] for the accepted names.
+#[derive(Error, Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
+#[error("{0:?} isn't a y level, expected a number, sea_level, surface or build_limit")]
+pub struct ParseYLevelError(pub String);
+
+/// A range can't be given to cubiomes
 ///
 /// As cubiomes uses i32 for size, but states that it should be positive. (except for `size_y`)
 /// I opted to use an unsized integer for abstraction. The conversion will
 /// fail if either `size_x` or `size_z` is 0 or any size is bigger than [`i32::MAX`].
 ///
 /// A `size_y` of 0 is equal to `size_y` of 1
+///
+/// Cubiomes does its math with `int`, so ranges with more than 2^28 cells,
+/// or with cells whose block coordinates don't fit an i32, are rejected as
+/// well.
 #[derive(Error, Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
 pub enum TryFromRangeError {
     #[error("x sixe is out of bounds for range")]
@@ -68,4 +195,110 @@ pub enum TryFromRangeError {
     #[error("y size is out of bounds for range")]
     #[allow(missing_docs)]
     YSizeOutOfBouns,
+    /// The range has more cells than cubiomes can generate at once
+    #[error("range has too many cells")]
+    TooManyCells,
+    /// The x coordinates of the range don't fit an i32 in blocks
+    #[error("x coordinates are out of bounds for range")]
+    XOutOfBounds,
+    /// The z coordinates of the range don't fit an i32 in blocks
+    #[error("z coordinates are out of bounds for range")]
+    ZOutOfBounds,
+    /// The y coordinates of the range don't fit an i32 in blocks
+    #[error("y coordinates are out of bounds for range")]
+    YOutOfBounds,
+}
+
+/// An error while exporting a cache as text
+#[derive(Error, Debug)]
+pub enum ExportError {
+    /// The cache holds no valid biomes, for example because it is not filled
+    #[error("Failed to read the cache: {0}")]
+    GeneratorError(GeneratorError),
+    /// Writing the export failed
+    #[error("Failed to write export: {0}")]
+    Io(std::io::Error),
+    /// Writing the json export failed
+    #[cfg(feature = "json")]
+    #[error("Failed to write json: {0}")]
+    Json(serde_json::Error),
+}
+
+impl From<GeneratorError> for ExportError {
+    fn from(value: GeneratorError) -> Self {
+        Self::GeneratorError(value)
+    }
+}
+
+impl From<std::io::Error> for ExportError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for ExportError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+/// An error while writing or reading a cache in the binary cache format
+///
+/// See [`super::CacheHeader`] for the format.
+#[derive(Error, Debug)]
+pub enum CacheFileError {
+    /// The cache holds no valid biomes, for example because it is not filled
+    #[error("Failed to read the cache: {0}")]
+    GeneratorError(GeneratorError),
+    /// Reading or writing the data failed
+    ///
+    /// Data which ends before the whole cache was read gives an error of kind
+    /// [`std::io::ErrorKind::UnexpectedEof`].
+    #[error("Failed to access cache data: {0}")]
+    Io(std::io::Error),
+    /// The data doesn't start with the magic bytes of the format
+    #[error("The data is not a cache, its magic bytes are {0:?}")]
+    InvalidMagic([u8; 4]),
+    /// The data is in a newer version of the format
+    #[error("Cache format version {0} is not supported")]
+    UnsupportedFormatVersion(u16),
+    /// A field of the header holds an invalid value
+    #[error("The cache header has an invalid {field}: {value}")]
+    InvalidHeader {
+        /// The name of the invalid field
+        field: &'static str,
+        /// The value of the field
+        value: i64,
+    },
+    /// A cell of the data holds an unknown biome
+    #[error("Biome id {0} in the cache data is not a valid biomeid")]
+    InvalidBiome(u8),
+    /// The cache was written with a different generator than it is read with
+    #[error(
+        "The cache was written for {file} with flags {file_flags:?}, but is read for {generator} \
+         with flags {generator_flags:?}"
+    )]
+    GeneratorMismatch {
+        /// The generator the cache was written with
+        file: GeneratorContext,
+        /// The flags of the generator the cache was written with
+        file_flags: GeneratorFlags,
+        /// The generator the cache is read with
+        generator: GeneratorContext,
+        /// The flags of the generator the cache is read with
+        generator_flags: GeneratorFlags,
+    },
+}
+
+impl From<GeneratorError> for CacheFileError {
+    fn from(value: GeneratorError) -> Self {
+        Self::GeneratorError(value)
+    }
+}
+
+impl From<std::io::Error> for CacheFileError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
 }
@@ -0,0 +1,174 @@
+use super::{error::GeneratorError, validate, Generator, Range, Scale};
+use crate::{enums::BiomeID, rng::Xoroshiro};
+
+/// The z score of a 95% confidence
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// An estimate of the share of a range covered by a biome, from random samples
+///
+/// Created with [`Generator::estimate_biome_fraction()`]. The confidence
+/// intervals are Wilson score intervals, which stay inside 0 to 1 and are
+/// reasonable for biomes which are rarely or almost always hit.
+///
+/// # Examples
+/// ```
+/// use cubiomes::generator::FractionEstimate;
+///
+/// let estimate = FractionEstimate { hits: 30, samples: 1000 };
+///
+/// let (low, high) = estimate.confidence_interval();
+/// assert!(low < estimate.fraction() && estimate.fraction() < high);
+/// assert!((low - 0.021).abs() < 0.001 && (high - 0.043).abs() < 0.001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct FractionEstimate {
+    /// The amount of samples of the biome
+    pub hits: u32,
+    /// The amount of samples taken
+    pub samples: u32,
+}
+
+impl FractionEstimate {
+    /// Gets the estimated share of the range covered by the biome, from 0 to 1
+    ///
+    /// This is 0 without any samples
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+
+        f64::from(self.hits) / f64::from(self.samples)
+    }
+
+    /// Gets the 95% confidence interval of the share, from 0 to 1
+    #[must_use]
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        self.wilson_interval(Z_95)
+    }
+
+    /// Gets the Wilson score interval of the share for a z score
+    ///
+    /// A z score of 1.96 gives a 95% confidence interval, and 2.576 a 99%
+    /// one. Without any samples the interval is 0 to 1.
+    #[must_use]
+    pub fn wilson_interval(&self, z: f64) -> (f64, f64) {
+        if self.samples == 0 {
+            return (0.0, 1.0);
+        }
+
+        let n = f64::from(self.samples);
+        let p = self.fraction();
+        let z2 = z * z;
+
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+}
+
+impl Generator {
+    /// Estimates the share of a range covered by a biome from random samples
+    ///
+    /// Instead of generating every cell of the range, `samples` random cells
+    /// are looked up one at a time, with positions drawn from `rng`. This is
+    /// a lot faster than filling a cache for huge ranges, for example to
+    /// score seeds quickly, and the error of the estimate only depends on the
+    /// amount of samples, not on the size of the range.
+    ///
+    /// Ranges at [`Scale::Block`] and [`Scale::Quad`] are sampled at their
+    /// own scale. Coarser ranges are sampled at random 1:4 cells inside the
+    /// area they cover, which gives the share of the biome at 1:4 rather
+    /// than at the scale of the range.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+    /// use cubiomes::rng::Xoroshiro;
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// // 20000 by 20000 blocks around spawn
+    /// let range = Range::plane(Scale::Quad, -2500, -2500, 5000, 5000, 64);
+    /// let estimate = generator
+    ///     .estimate_biome_fraction(range, BiomeID::plains, 500, &mut Xoroshiro::new(1))
+    ///     .expect("failed to generate biomes");
+    ///
+    /// // The samples are the same for the same random source
+    /// assert_eq!((estimate.hits, estimate.samples), (65, 500));
+    ///
+    /// let (low, high) = estimate.confidence_interval();
+    /// assert!(low <= estimate.fraction() && estimate.fraction() <= high);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the range can't be given to cubiomes, see
+    /// [`super::error::TryFromRangeError`], or if looking up a sample fails,
+    /// see [`Self::get_biome_at()`]
+    pub fn estimate_biome_fraction(
+        &self,
+        range: Range,
+        biome: BiomeID,
+        samples: u32,
+        rng: &mut Xoroshiro,
+    ) -> Result<FractionEstimate, GeneratorError> {
+        self.estimate_fraction_where(range, |other| other == biome, samples, rng)
+    }
+
+    /// Estimates the share of a range covered by the biomes matching a
+    /// predicate from random samples
+    ///
+    /// This estimates classes of biomes, like every ocean, in one go. See
+    /// [`Self::estimate_biome_fraction()`].
+    ///
+    /// # Errors
+    /// See [`Self::estimate_biome_fraction()`]
+    pub fn estimate_fraction_where(
+        &self,
+        range: Range,
+        mut predicate: impl FnMut(BiomeID) -> bool,
+        samples: u32,
+        rng: &mut Xoroshiro,
+    ) -> Result<FractionEstimate, GeneratorError> {
+        self.check_seeded()?;
+        validate::range(&range)?;
+
+        // The sampled cells, at the scale they are looked up at
+        let (scale, factor) = match range.scale {
+            Scale::Block => (Scale::Block, 1),
+            scale => (Scale::Quad, scale as i32 / 4),
+        };
+        // The sizes fit an i32 in blocks, so they fit it at 1:4 as well
+        let size = |size: u32| {
+            i32::try_from(size)
+                .unwrap_or(i32::MAX)
+                .saturating_mul(factor)
+        };
+        let (size_x, size_z) = (size(range.size_x), size(range.size_z));
+        let size_y = i32::try_from(range.size_y.max(1)).unwrap_or(i32::MAX);
+
+        let mut estimate = FractionEstimate { hits: 0, samples };
+        for _ in 0..samples {
+            let x = range.x * factor + rng.next_int(size_x);
+            let z = range.z * factor + rng.next_int(size_z);
+            let y = range.y + rng.next_int(size_y);
+
+            let biome = match scale {
+                Scale::Block => self.get_biome_at((x, y, z))?,
+                _ => self.quad_biome_at(super::QuadPosition::new(x, y, z))?,
+            };
+            if predicate(biome) {
+                estimate.hits += 1;
+            }
+        }
+
+        Ok(estimate)
+    }
+}
@@ -0,0 +1,191 @@
+use super::{error::ExportError, Cache};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+#[cfg(feature = "json")]
+use crate::enums::{BiomeID, Dimension, MCVersion};
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+impl Cache<'_> {
+    /// Writes the biomes of the cache as CSV
+    ///
+    /// Every cell is a row of its coordinates and the name of its biome, with
+    /// a header naming the columns. The coordinates are in cells of the scale
+    /// of the range, like the [`super::Range`] itself. The columns are
+    /// `x,z,biome` for a 2d cache, and `x,y,z,biome` if the cache has more
+    /// than one layer.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range {
+    ///     scale: Scale::Block,
+    ///     x: 512,
+    ///     z: -512,
+    ///     size_x: 16,
+    ///     size_z: 16,
+    ///     y: 100,
+    ///     size_y: 0,
+    /// });
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// let mut csv = Vec::new();
+    /// cache.write_csv(&mut csv).expect("failed to write csv");
+    ///
+    /// let csv = String::from_utf8(csv).expect("csv is utf-8");
+    /// assert_eq!(csv.lines().next(), Some("x,z,biome"));
+    /// assert_eq!(csv.lines().nth(14), Some("525,-512,plains"));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`ExportError::GeneratorError`] if the cache isn't filled or
+    /// holds an unknown biome, and [`ExportError::Io`] if writing fails
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), ExportError> {
+        let layered = self.range.size_y > 1;
+
+        if layered {
+            writeln!(writer, "x,y,z,biome")?;
+        } else {
+            writeln!(writer, "x,z,biome")?;
+        }
+
+        for (y, z, x) in self.cells() {
+            let biome = self.biome_at(x, y, z)?;
+            let (x, z) = (self.range.x + x as i32, self.range.z + z as i32);
+
+            if layered {
+                writeln!(writer, "{x},{},{z},{biome:?}", self.range.y + y as i32)?;
+            } else {
+                writeln!(writer, "{x},{z},{biome:?}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Saves the biomes of the cache to a file as CSV, see [`Self::write_csv()`]
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::write_csv()`]
+    pub fn save_csv(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_csv(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Writes the biomes of the cache as a json grid with its metadata
+    ///
+    /// The json is an object with the `seed`, `version` and `dimension` of
+    /// the generator, the `range` of the cache with its scale as a number,
+    /// and the `biomes` by name. The biomes are indexed with `[y][z][x]`,
+    /// like [`Self::as_vec()`], so a 2d cache has a single layer. Requires
+    /// the `json` feature.
+    ///
+    /// ```json
+    /// {
+    ///   "seed": -380434930381432806,
+    ///   "version": "MC_1_20_6",
+    ///   "dimension": "DIM_OVERWORLD",
+    ///   "range": { "scale": 4, "x": 0, "z": 0, "y": 16, "size_x": 2, "size_z": 1, "size_y": 0 },
+    ///   "biomes": [[["plains", "forest"]]]
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`ExportError::GeneratorError`] if the cache isn't filled or
+    /// holds an unknown biome, and [`ExportError::Json`] if writing fails
+    #[cfg(feature = "json")]
+    pub fn write_json(&self, writer: impl Write) -> Result<(), ExportError> {
+        let mut biomes = Vec::new();
+        for y in 0..self.range.size_y.max(1) {
+            let mut layer = Vec::new();
+            for z in 0..self.range.size_z {
+                let row = (0..self.range.size_x)
+                    .map(|x| self.biome_at(x, y, z))
+                    .collect::<Result<Vec<_>, _>>()?;
+                layer.push(row);
+            }
+            biomes.push(layer);
+        }
+
+        let range = self.range;
+        let export = JsonExport {
+            seed: self.generator.seed(),
+            version: self.generator.minecraft_version(),
+            dimension: self.generator.dimension(),
+            range: JsonRange {
+                scale: range.scale as i32,
+                x: range.x,
+                z: range.z,
+                y: range.y,
+                size_x: range.size_x,
+                size_z: range.size_z,
+                size_y: range.size_y,
+            },
+            biomes,
+        };
+
+        Ok(serde_json::to_writer(writer, &export)?)
+    }
+
+    /// Saves the biomes of the cache to a file as json, see
+    /// [`Self::write_json()`]
+    ///
+    /// Requires the `json` feature
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::write_json()`], and
+    /// [`ExportError::Io`] if the file can't be written
+    #[cfg(feature = "json")]
+    pub fn save_json(&self, path: impl AsRef<Path>) -> Result<(), ExportError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_json(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Iterates the cells of the cache as `(y, z, x)`, in the order they are
+    /// stored in
+    fn cells(&self) -> impl Iterator<Item = (u32, u32, u32)> {
+        let range = self.range;
+
+        (0..range.size_y.max(1)).flat_map(move |y| {
+            (0..range.size_z).flat_map(move |z| (0..range.size_x).map(move |x| (y, z, x)))
+        })
+    }
+}
+
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonExport {
+    seed: i64,
+    version: MCVersion,
+    dimension: Dimension,
+    range: JsonRange,
+    biomes: Vec<Vec<Vec<BiomeID>>>,
+}
+
+/// A [`super::Range`] with its scale as a number
+#[cfg(feature = "json")]
+#[derive(Serialize)]
+struct JsonRange {
+    scale: i32,
+    x: i32,
+    z: i32,
+    y: i32,
+    size_x: u32,
+    size_z: u32,
+    size_y: u32,
+}
@@ -0,0 +1,128 @@
+use super::{error::GeneratorError, GeneratorFlags};
+use crate::enums::MCVersion;
+use std::fmt::Display;
+
+/// A flag which has no effect in the version it is used with
+///
+/// Cubiomes ignores flags which don't apply to a version, so the biomes are
+/// generated as if the flag wasn't set. See
+/// [`GeneratorFlags::validate_for()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlagWarning {
+    /// The flag which has no effect
+    pub flag: GeneratorFlags,
+    /// The version the flag was used with
+    pub version: MCVersion,
+}
+
+impl Display for FlagWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let applies_to = if self.flag == GeneratorFlags::LargeBiomes {
+            "beta 1.8 and later"
+        } else if self.flag == GeneratorFlags::NoBetaOcean {
+            "beta 1.7 and older"
+        } else {
+            "1.13 to 1.17"
+        };
+
+        write!(
+            f,
+            "{:?} has no effect in {:?}, it only applies to {applies_to}",
+            self.flag, self.version
+        )
+    }
+}
+
+impl GeneratorFlags {
+    /// Gets the flags which change the generation of a version
+    ///
+    /// - [`Self::LargeBiomes`] applies from beta 1.8 on, the biomes of beta
+    ///   1.7 and older come from a climate noise without the option.
+    /// - [`Self::NoBetaOcean`] only applies to beta 1.7 and older. Their
+    ///   oceans come from the surface noise of the terrain instead of the
+    ///   biomes, which is slow to generate, so this flag turns them off and
+    ///   gives the land biomes under them instead.
+    /// - [`Self::ForceOceanVariants`] only applies to 1.13 to 1.17, where it
+    ///   gives the warm, cold and other ocean variants at scales above 1:4
+    ///   too. Before 1.13 there are no variants, and since 1.18 every scale
+    ///   has them.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::MCVersion;
+    /// use cubiomes::generator::GeneratorFlags;
+    ///
+    /// let flags = GeneratorFlags::LargeBiomes | GeneratorFlags::ForceOceanVariants;
+    ///
+    /// assert_eq!(flags.effective_for(MCVersion::MC_1_16_5), flags);
+    /// assert_eq!(flags.effective_for(MCVersion::MC_1_20_6), GeneratorFlags::LargeBiomes);
+    /// ```
+    #[must_use]
+    pub fn effective_for(self, version: MCVersion) -> Self {
+        let mut effective = Self::empty();
+
+        if version >= MCVersion::MC_B1_8 {
+            effective |= Self::LargeBiomes;
+        }
+        if version <= MCVersion::MC_B1_7 {
+            effective |= Self::NoBetaOcean;
+        }
+        if (MCVersion::MC_1_13_2..=MCVersion::MC_1_17_1).contains(&version) {
+            effective |= Self::ForceOceanVariants;
+        }
+
+        self & effective
+    }
+
+    /// Checks that the flags can be used with a version
+    ///
+    /// Returns a warning for every flag which has no effect in the version,
+    /// see [`Self::effective_for()`] for where each flag applies. Comparing
+    /// the results of a generator with such a flag to the game can be
+    /// confusing, as the flag is silently ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::MCVersion;
+    /// use cubiomes::generator::{FlagWarning, GeneratorFlags};
+    ///
+    /// let warnings = GeneratorFlags::NoBetaOcean
+    ///     .validate_for(MCVersion::MC_1_20_6)
+    ///     .expect("the flags are known");
+    ///
+    /// assert_eq!(
+    ///     warnings,
+    ///     [FlagWarning {
+    ///         flag: GeneratorFlags::NoBetaOcean,
+    ///         version: MCVersion::MC_1_20_6,
+    ///     }]
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnknownFlags`] if bits are set which aren't a
+    /// flag of cubiomes, and [`GeneratorError::UnsupportedVersion`] for
+    /// `MC_UNDEF`
+    pub fn validate_for(self, version: MCVersion) -> Result<Vec<FlagWarning>, GeneratorError> {
+        let unknown = self.difference(Self::all_known());
+        if !unknown.is_empty() {
+            return Err(GeneratorError::UnknownFlags(unknown.bits()));
+        }
+
+        if version == MCVersion::MC_UNDEF {
+            return Err(GeneratorError::UnsupportedVersion(version));
+        }
+
+        let ignored = self.difference(self.effective_for(version));
+
+        Ok(ignored
+            .iter()
+            .map(|flag| FlagWarning { flag, version })
+            .collect())
+    }
+
+    /// Gets every named flag of cubiomes
+    fn all_known() -> Self {
+        Self::LargeBiomes | Self::NoBetaOcean | Self::ForceOceanVariants
+    }
+}
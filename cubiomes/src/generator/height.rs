@@ -0,0 +1,339 @@
+use super::{
+    error::{GeneratorError, TryFromRangeError},
+    BlockPosition, Generator, Range, Scale,
+};
+use crate::enums::BiomeID;
+
+/// An approximation of the surface height of an area
+///
+/// The map is usually at [`Scale::Quad`], so every cell covers 4x4 blocks.
+/// Along with the heights, the map holds the biome of each cell. Created with
+/// [`Generator::approx_heights()`] or [`Generator::end_surface_heights()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeightMap {
+    pub(super) scale: Scale,
+    pub(super) x: i32,
+    pub(super) z: i32,
+    pub(super) size_x: u32,
+    pub(super) size_z: u32,
+    pub(super) heights: Vec<f32>,
+    pub(super) biomes: Vec<i32>,
+}
+
+impl HeightMap {
+    /// Gets the block position of the upper left corner of the map
+    #[must_use]
+    pub fn origin(&self) -> BlockPosition {
+        BlockPosition::from_scaled(self.x, self.z, self.scale)
+    }
+
+    /// Gets the scale of the cells of the map
+    #[must_use]
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// Gets the width of the map in cells
+    #[must_use]
+    pub fn size_x(&self) -> u32 {
+        self.size_x
+    }
+
+    /// Gets the height of the map in cells
+    #[must_use]
+    pub fn size_z(&self) -> u32 {
+        self.size_z
+    }
+
+    /// Gets the heights of every cell, row by row
+    #[must_use]
+    pub fn heights(&self) -> &[f32] {
+        &self.heights
+    }
+
+    /// Gets the height of a cell in blocks
+    ///
+    /// The cell is relative to the upper left corner of the map. Returns
+    /// [`None`] if the cell is outside the map.
+    #[must_use]
+    pub fn height_at(&self, x: u32, z: u32) -> Option<f32> {
+        self.index(x, z).map(|i| self.heights[i])
+    }
+
+    /// Gets the biome of a cell
+    ///
+    /// The cell is relative to the upper left corner of the map. Returns
+    /// [`None`] if the cell is outside the map or the biome is unknown.
+    #[must_use]
+    pub fn biome_at(&self, x: u32, z: u32) -> Option<BiomeID> {
+        self.index(x, z)
+            .and_then(|i| BiomeID::from_id(self.biomes[i]))
+    }
+
+    /// Gets the lowest and highest height in the map
+    ///
+    /// Returns [`None`] if the map is empty
+    #[must_use]
+    pub fn min_max(&self) -> Option<(f32, f32)> {
+        let first = *self.heights.first()?;
+
+        Some(
+            self.heights
+                .iter()
+                .fold((first, first), |(min, max), &h| (min.min(h), max.max(h))),
+        )
+    }
+
+    fn index(&self, x: u32, z: u32) -> Option<usize> {
+        (x < self.size_x && z < self.size_z).then(|| z as usize * self.size_x as usize + x as usize)
+    }
+}
+
+impl Generator {
+    /// Approximates the surface height of an area
+    ///
+    /// The area is given in [`Scale::Quad`] coordinates, so `x` and `z` are
+    /// the block coordinates divided by 4. The heights are an estimate from
+    /// the terrain noise and don't account for carvers or surface features.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let heights = generator
+    ///     .approx_heights(-64, -64, 128, 128)
+    ///     .expect("failed to map heights");
+    ///
+    /// let (min, max) = heights.min_max().expect("the map is not empty");
+    /// assert_eq!((min.round(), max.round()), (39.0, 116.0));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::ApproxHeightFailure`] if cubiomes can't
+    /// approximate the height, for example in the nether, and
+    /// [`GeneratorError::TryFromRangeError`] if the area is empty, too big or
+    /// too far out to be given to cubiomes
+    pub fn approx_heights(
+        &self,
+        x: i32,
+        z: i32,
+        size_x: u32,
+        size_z: u32,
+    ) -> Result<HeightMap, GeneratorError> {
+        SurfaceNoise::new(self).approx_heights(self, x, z, size_x, size_z)
+    }
+}
+
+/// The surface noise of a generator, used for approximating heights
+///
+/// This is the 3d terrain density noise of the end and of the overworld
+/// before 1.18. Since 1.18 the overworld heights are approximated from the
+/// biome noise instead, so only [`Self::approx_heights()`] is meaningful there.
+///
+/// Initializing the noise takes a while, so it should be kept around when
+/// sampling many points or heights.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{Generator, GeneratorFlags, SurfaceNoise};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_16_5,
+///     -380434930381432806,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let noise = SurfaceNoise::new(&generator);
+///
+/// let ranges: Vec<_> = (0..4)
+///     .map(|z| {
+///         let heights = noise
+///             .approx_heights(&generator, 0, z * 16, 16, 16)
+///             .expect("failed to map heights");
+///         let (min, max) = heights.min_max().expect("the map is not empty");
+///         (min.round(), max.round())
+///     })
+///     .collect();
+/// assert_eq!(ranges, [(54.0, 71.0), (55.0, 81.0), (58.0, 81.0), (56.0, 74.0)]);
+///
+/// assert_eq!(noise.sample(0, 8, 0).round(), -38.0);
+/// ```
+pub struct SurfaceNoise {
+    noise: Box<cubiomes_sys::SurfaceNoise>,
+    seed: u64,
+    dimension: i32,
+}
+
+impl std::fmt::Debug for SurfaceNoise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SurfaceNoise")
+            .field("seed", &self.seed.cast_signed())
+            .field("dimension", &self.dimension)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SurfaceNoise {
+    /// Initializes the surface noise for the seed and dimension of a generator
+    #[must_use]
+    pub fn new(generator: &Generator) -> Self {
+        // SAFETY:
+        // The generator is initialized, so its fields can be read. The noise
+        // is fully initialized by initSurfaceNoise before assuming so.
+        unsafe {
+            let raw = &*generator.generator;
+            let mut noise = Box::<cubiomes_sys::SurfaceNoise>::new_uninit();
+            cubiomes_sys::initSurfaceNoise(noise.as_mut_ptr(), raw.dim, raw.seed);
+
+            Self {
+                noise: noise.assume_init(),
+                seed: raw.seed,
+                dimension: raw.dim,
+            }
+        }
+    }
+
+    /// Gets the raw cubiomes noise
+    pub(super) fn as_raw(&self) -> &cubiomes_sys::SurfaceNoise {
+        &self.noise
+    }
+
+    /// Samples the terrain density at a point of the noise grid
+    ///
+    /// The coordinates are in cells of the noise, which are 4 blocks wide and
+    /// 8 blocks high in the overworld and 8 blocks wide and 4 blocks high in
+    /// the end. Positive densities are solid and negative ones are air, before
+    /// the density is shaped by the biomes or the end islands.
+    #[must_use]
+    pub fn sample(&self, x: i32, y: i32, z: i32) -> f64 {
+        // SAFETY:
+        // The noise is initialized
+        unsafe { cubiomes_sys::sampleSurfaceNoise(&*self.noise, x, y, z) }
+    }
+
+    /// Samples the terrain density at a point, stopping early outside bounds
+    ///
+    /// The result is the same as [`Self::sample()`], up to rounding errors,
+    /// if the density is between `min` and `max`. Otherwise sampling may stop
+    /// as soon as the density is known to be outside the bounds, and the
+    /// crossed bound is returned. This is a lot faster when only checking if a
+    /// point is solid.
+    #[must_use]
+    pub fn sample_between(&self, x: i32, y: i32, z: i32, min: f64, max: f64) -> f64 {
+        // SAFETY:
+        // The noise is initialized
+        unsafe { cubiomes_sys::sampleSurfaceNoiseBetween(&*self.noise, x, y, z, min, max) }
+    }
+
+    /// Approximates the surface height of an area with this noise
+    ///
+    /// This is the same as [`Generator::approx_heights()`], but doesn't
+    /// initialize the noise again for every area.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::MismatchedGenerator`] if the generator
+    /// doesn't have the seed and dimension this noise was created for, and
+    /// otherwise the same errors as [`Generator::approx_heights()`]
+    pub fn approx_heights(
+        &self,
+        generator: &Generator,
+        x: i32,
+        z: i32,
+        size_x: u32,
+        size_z: u32,
+    ) -> Result<HeightMap, GeneratorError> {
+        let (width, height) = super::validate::area(Scale::Quad, x, z, size_x, size_z)?;
+
+        let cells = size_x as usize * size_z as usize;
+        let mut heights = vec![0.0; cells];
+        let mut biomes = vec![BiomeID::none as i32; cells];
+
+        self.map_heights(generator, (x, z, width, height), &mut heights, &mut biomes)?;
+
+        Ok(HeightMap {
+            scale: Scale::Quad,
+            x,
+            z,
+            size_x,
+            size_z,
+            heights,
+            biomes,
+        })
+    }
+
+    /// Approximates the heights of an area given as x, z, width and height at
+    /// [`Scale::Quad`]
+    ///
+    /// Returns [`GeneratorError::MismatchedGenerator`] if the generator
+    /// doesn't match the noise, and [`TryFromRangeError::TooManyCells`] if the
+    /// area is empty or the buffers don't have room for every cell of it
+    pub(super) fn map_heights(
+        &self,
+        generator: &Generator,
+        (x, z, width, height): (i32, i32, i32, i32),
+        heights: &mut [f32],
+        biomes: &mut [i32],
+    ) -> Result<(), GeneratorError> {
+        if (
+            generator.seed().cast_unsigned(),
+            generator.dimension() as i32,
+        ) != (self.seed, self.dimension)
+        {
+            return Err(GeneratorError::MismatchedGenerator(generator.context()));
+        }
+
+        let fits = usize::try_from(width)
+            .ok()
+            .zip(usize::try_from(height).ok())
+            .map(|(width, height)| width * height)
+            .is_some_and(|cells| cells > 0 && heights.len() >= cells && biomes.len() >= cells);
+
+        if !fits {
+            return Err(TryFromRangeError::TooManyCells.into());
+        }
+
+        // SAFETY:
+        // The generator and the noise are initialized and the buffers were
+        // checked to have room for every cell of the area
+        let result = unsafe {
+            cubiomes_sys::mapApproxHeight(
+                heights.as_mut_ptr(),
+                biomes.as_mut_ptr(),
+                generator.generator,
+                &*self.noise,
+                x,
+                z,
+                width,
+                height,
+            )
+        };
+
+        if result != 0 {
+            return Err(GeneratorError::ApproxHeightFailure {
+                code: result,
+                context: generator.context(),
+                range: Range {
+                    scale: Scale::Quad,
+                    x,
+                    z,
+                    size_x: width as u32,
+                    size_z: height as u32,
+                    y: 0,
+                    size_y: 0,
+                },
+            });
+        }
+
+        Ok(())
+    }
+}
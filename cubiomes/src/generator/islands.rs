@@ -0,0 +1,193 @@
+use super::{
+    error::GeneratorError, Aabb, BiomePatch, BlockPosition, Cache, Generator, Range, Scale,
+};
+use crate::enums::{BiomeID, Dimension};
+
+/// The blocks the coarse scan reaches past the search radius
+///
+/// Islands with their center inside the radius can reach outside of it, so
+/// the scan is extended to find their edges as well.
+const SCAN_MARGIN: i32 = 512;
+
+/// The blocks around every coarse hit which are refined
+///
+/// The coarse scan only samples one point per cell, so an island may reach
+/// into cells which didn't sample it.
+const REFINE_MARGIN: i32 = 2 * Scale::QuadChunk as i32;
+
+/// A mushroom island found by [`Generator::mushroom_islands()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MushroomIsland {
+    /// The center of mass of the island, in block coordinates
+    pub center: BlockPosition,
+    /// The distance in blocks from the origin of the search to the center
+    pub distance: f64,
+    /// The mushroom cells of the island at [`Scale::Quad`]
+    ///
+    /// The patch includes the mushroom field shores of versions before 1.18.
+    pub patch: BiomePatch,
+}
+
+impl Generator {
+    /// Finds the mushroom islands with their center within a radius around a
+    /// position
+    ///
+    /// The area is first scanned at [`Scale::QuadChunk`], and only the
+    /// surroundings of cells containing mushroom fields are generated at
+    /// [`Scale::Quad`], where the islands are measured with
+    /// [`Cache::patches_where()`]. This is orders of magnitude faster than
+    /// scanning the area at [`Scale::Quad`], but may miss islands smaller
+    /// than a cell of the coarse scan, which are 64 by 64 blocks.
+    ///
+    /// The islands are sampled at the sea level and sorted by their distance
+    /// from `origin`, with the closest island first.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let islands = generator
+    ///     .mushroom_islands(BlockPosition::new(0, 0), 3000)
+    ///     .expect("failed to generate biomes");
+    ///
+    /// let closest = islands.first().expect("there is a mushroom island nearby");
+    /// assert_eq!(closest.center, BlockPosition::new(2370, 898));
+    /// assert!(islands.windows(2).all(|pair| pair[0].distance <= pair[1].distance));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedDimension`] outside of the
+    /// overworld, or an error if generating the biomes fails
+    pub fn mushroom_islands(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Result<Vec<MushroomIsland>, GeneratorError> {
+        if self.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        let y = self.world_limits().sea_level;
+        let reach = i32::try_from(radius)
+            .unwrap_or(i32::MAX)
+            .saturating_add(SCAN_MARGIN);
+        let area = horizontal_area(origin, origin, reach, y);
+
+        let mut coarse = Cache::new(
+            self,
+            Range {
+                size_y: 0,
+                ..area.to_range(Scale::QuadChunk)
+            },
+        );
+        coarse.fill_cache()?;
+
+        let areas = coarse
+            .patches_where(is_mushroom)?
+            .into_iter()
+            .map(|hit| horizontal_area(hit.min, hit.max, REFINE_MARGIN, y))
+            .collect();
+
+        let mut islands = Vec::new();
+
+        for area in merge_touching(areas) {
+            let mut cache = Cache::new(
+                self,
+                Range {
+                    size_y: 0,
+                    ..area.to_range(Scale::Quad)
+                },
+            );
+            cache.fill_cache()?;
+
+            let refined = Aabb::from(*cache.range());
+
+            for patch in cache.patches_where(is_mushroom)? {
+                // Only islands reaching past the coarse scan can be cut off,
+                // and these are too far from the origin to matter
+                if patch.min.x <= refined.min_x
+                    || patch.min.z <= refined.min_z
+                    || patch.max.x >= refined.max_x
+                    || patch.max.z >= refined.max_z
+                {
+                    continue;
+                }
+
+                let center = BlockPosition::new(
+                    patch.centroid_x.floor() as i32,
+                    patch.centroid_z.floor() as i32,
+                );
+                let distance = origin.distance(center);
+
+                if distance <= f64::from(radius) {
+                    islands.push(MushroomIsland {
+                        center,
+                        distance,
+                        patch,
+                    });
+                }
+            }
+        }
+
+        islands.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        Ok(islands)
+    }
+}
+
+fn is_mushroom(biome: BiomeID) -> bool {
+    matches!(
+        biome,
+        BiomeID::mushroom_fields | BiomeID::mushroom_field_shore
+    )
+}
+
+/// Merges the areas which overlap or touch each other
+///
+/// An island can be sampled by coarse cells which aren't connected, so their
+/// areas are refined together to find the island in one piece.
+fn merge_touching(mut areas: Vec<Aabb>) -> Vec<Aabb> {
+    let mut merged: Vec<Aabb> = Vec::with_capacity(areas.len());
+
+    while let Some(mut area) = areas.pop() {
+        while let Some(i) = areas
+            .iter()
+            .chain(&merged)
+            .position(|other| other.intersects(&area.expand(1)))
+        {
+            let other = if i < areas.len() {
+                areas.swap_remove(i)
+            } else {
+                merged.swap_remove(i - areas.len())
+            };
+            area = area.union(&other);
+        }
+
+        merged.push(area);
+    }
+
+    merged
+}
+
+/// Gets a single layer box between two corners, grown by a margin
+fn horizontal_area(min: BlockPosition, max: BlockPosition, margin: i32, y: i32) -> Aabb {
+    Aabb::new(
+        (
+            min.x.saturating_sub(margin),
+            y,
+            min.z.saturating_sub(margin),
+        ),
+        (
+            max.x.saturating_add(margin),
+            y,
+            max.z.saturating_add(margin),
+        ),
+    )
+}
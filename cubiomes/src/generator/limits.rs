@@ -0,0 +1,146 @@
+use super::{error::GeneratorError, Generator};
+use crate::enums::{Dimension, MCVersion};
+use std::ops::Range as YRange;
+
+/// The vertical limits of a dimension in a version
+///
+/// The build height of the overworld grew from 128 to 256 blocks in 1.2 and
+/// was extended down to -64 in 1.18, so the heights to query differ between
+/// versions. Use [`Generator::world_limits()`] instead of hardcoding them.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::WorldLimits;
+///
+/// let limits = WorldLimits::of(MCVersion::MC_1_20_6, Dimension::DIM_OVERWORLD);
+///
+/// assert_eq!(limits.min_y, -64);
+/// assert_eq!(limits.max_y(), 319);
+/// assert_eq!(limits.sea_level, 63);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorldLimits {
+    /// The y of the surface of the oceans, or the lava oceans in the nether
+    pub sea_level: i32,
+    /// The lowest y blocks can be placed at
+    pub min_y: i32,
+    /// The number of blocks between the lowest and highest buildable y
+    pub height: u32,
+    /// The height from [`Self::min_y`] which portals and chorus fruit can
+    /// teleport to
+    ///
+    /// This is lower than [`Self::height`] in the nether, which has a
+    /// bedrock roof at 128.
+    pub logical_height: u32,
+}
+
+impl WorldLimits {
+    /// Gets the limits of a dimension in a version
+    #[must_use]
+    pub fn of(version: MCVersion, dimension: Dimension) -> Self {
+        let height = if version < MCVersion::MC_1_2 {
+            128
+        } else {
+            256
+        };
+
+        match dimension {
+            Dimension::DIM_NETHER => Self {
+                sea_level: 32,
+                min_y: 0,
+                height,
+                logical_height: 128,
+            },
+            Dimension::DIM_END => Self {
+                sea_level: 0,
+                min_y: 0,
+                height,
+                logical_height: height,
+            },
+            _ if version >= MCVersion::MC_1_18 => Self {
+                sea_level: 63,
+                min_y: -64,
+                height: 384,
+                logical_height: 384,
+            },
+            _ => Self {
+                sea_level: 63,
+                min_y: 0,
+                height,
+                logical_height: height,
+            },
+        }
+    }
+
+    /// Gets the highest y blocks can be placed at
+    #[must_use]
+    pub fn max_y(&self) -> i32 {
+        self.min_y + self.height as i32 - 1
+    }
+
+    /// Gets the range of every buildable y, excluding the end
+    #[must_use]
+    pub fn y_range(&self) -> YRange<i32> {
+        self.min_y..self.max_y() + 1
+    }
+
+    /// Gets the range of every y portals can teleport to, excluding the end
+    ///
+    /// In the nether this is the part below the bedrock roof, see
+    /// [`Self::logical_height`].
+    #[must_use]
+    pub fn logical_y_range(&self) -> YRange<i32> {
+        self.min_y..self.min_y + self.logical_height as i32
+    }
+
+    /// Clamps a y between the lowest and highest buildable y
+    #[must_use]
+    pub fn clamp_y(&self, y: i32) -> i32 {
+        y.clamp(self.min_y, self.max_y())
+    }
+
+    /// Checks if a y is between the lowest and highest buildable y
+    #[must_use]
+    pub fn contains_y(&self, y: i32) -> bool {
+        self.y_range().contains(&y)
+    }
+
+    /// Checks that a y is buildable
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::YOutOfBounds`] if the y is outside
+    /// [`Self::y_range()`]
+    pub fn check_y(&self, y: i32) -> Result<(), GeneratorError> {
+        if self.contains_y(y) {
+            Ok(())
+        } else {
+            Err(GeneratorError::YOutOfBounds(y))
+        }
+    }
+}
+
+impl Generator {
+    /// Gets the vertical limits of the version and dimension of the generator
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_16_5,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let limits = generator.world_limits();
+    /// assert_eq!(limits.y_range(), 0..256);
+    /// assert_eq!(limits.logical_height, 128);
+    /// ```
+    #[must_use]
+    pub fn world_limits(&self) -> WorldLimits {
+        WorldLimits::of(self.minecraft_version(), self.dimension())
+    }
+}
@@ -0,0 +1,137 @@
+use super::{error::GeneratorError, BlockPosition3D, Cache, Generator, Range, Scale};
+use crate::enums::BiomeID;
+
+/// A biome sampled along a line, created with [`Generator::biomes_along()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSample {
+    /// The block the biome was sampled at
+    pub pos: BlockPosition3D,
+    /// The distance of the block from the start of the line in blocks
+    pub distance: f64,
+    /// The biome of the cell holding the block, at the scale of the line
+    pub biome: BiomeID,
+}
+
+impl Generator {
+    /// Gets the biomes every `step` blocks along a straight line
+    ///
+    /// The line goes from `from` to `to`, both in blocks and including the
+    /// y, so it can climb or dive like an elytra flight. The first sample is
+    /// at `from`, the others are `step` blocks apart along the line, rounded
+    /// to the nearest block, and the last sample is at `to`.
+    ///
+    /// Every sample is the biome of the cell of `scale` holding its block,
+    /// like a cache at that scale would give. Neighbouring samples in the
+    /// same cell are only generated once. This works in every dimension, for
+    /// example at [`Scale::Chunk`] in the nether to plan a tunnel.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let tunnel = generator
+    ///     .biomes_along((0, 64, 0), (1000, 64, -400), 16, Scale::Quad)
+    ///     .expect("failed to generate biomes");
+    ///
+    /// let wastes = tunnel
+    ///     .iter()
+    ///     .filter(|sample| sample.biome == BiomeID::nether_wastes)
+    ///     .count();
+    /// println!("{wastes} of {} samples are nether wastes", tunnel.len());
+    ///
+    /// assert_eq!(tunnel.first().map(|sample| sample.pos.x), Some(0));
+    /// assert_eq!(tunnel.last().map(|sample| sample.pos.x), Some(1000));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::ZeroStep`] if `step` is 0, and an error if
+    /// generating the biomes fails
+    pub fn biomes_along(
+        &self,
+        from: impl Into<BlockPosition3D>,
+        to: impl Into<BlockPosition3D>,
+        step: u32,
+        scale: Scale,
+    ) -> Result<Vec<LineSample>, GeneratorError> {
+        if step == 0 {
+            return Err(GeneratorError::ZeroStep);
+        }
+
+        let (from, to) = (from.into(), to.into());
+        let delta = |from: i32, to: i32| f64::from(to) - f64::from(from);
+        let (dx, dy, dz) = (
+            delta(from.x, to.x),
+            delta(from.y, to.y),
+            delta(from.z, to.z),
+        );
+        let length = (dx * dx + dy * dy + dz * dz).sqrt();
+
+        // The blocks along the line, with the end added unless a step
+        // landed on it
+        let mut points: Vec<(BlockPosition3D, f64)> = (0_u64..)
+            .map(|i| i as f64 * f64::from(step))
+            .take_while(|&distance| distance < length)
+            .map(|distance| {
+                let t = distance / length;
+                let at = |start: i32, d: f64| (f64::from(start) + d * t).round() as i32;
+
+                (
+                    BlockPosition3D::new(at(from.x, dx), at(from.y, dy), at(from.z, dz)),
+                    distance,
+                )
+            })
+            .collect();
+        if points.last().map(|(pos, _)| *pos) != Some(to) {
+            points.push((to, length));
+        }
+
+        let mut cache = Cache::new(
+            self,
+            Range {
+                scale,
+                x: 0,
+                z: 0,
+                size_x: 1,
+                size_z: 1,
+                y: 0,
+                size_y: 1,
+            },
+        );
+        let cell = |pos: BlockPosition3D| {
+            (
+                scale.scale_coord(pos.x),
+                scale.scale_y(pos.y),
+                scale.scale_coord(pos.z),
+            )
+        };
+
+        let mut samples: Vec<LineSample> = Vec::with_capacity(points.len());
+        for (pos, distance) in points {
+            let biome = match samples.last() {
+                Some(last) if cell(last.pos) == cell(pos) => last.biome,
+                _ => {
+                    let (x, y, z) = cell(pos);
+                    cache.move_cache(x, y, z);
+                    cache.fill_cache()?;
+                    cache.biome_at(0, 0, 0)?
+                }
+            };
+
+            samples.push(LineSample {
+                pos,
+                distance,
+                biome,
+            });
+        }
+
+        Ok(samples)
+    }
+}
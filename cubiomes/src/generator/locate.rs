@@ -0,0 +1,140 @@
+use super::{error::GeneratorError, Aabb, BlockPosition, Cache, Generator, Range, Scale};
+use crate::enums::BiomeID;
+
+impl Cache<'_> {
+    /// Iterates over the positions of every cell of a biome in the cache
+    ///
+    /// The positions are the block coordinates of the upper left corner of
+    /// each cell, so at [`Scale::Quad`] every position covers a 4x4 area of
+    /// blocks. For caches with multiple layers, a position is given for every
+    /// layer the biome is in.
+    ///
+    /// The cache should be filled with [`Self::fill_cache()`] before calling
+    /// this, otherwise no positions are given.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range {
+    ///     scale: Scale::Block,
+    ///     x: 512,
+    ///     z: -512,
+    ///     size_x: 64,
+    ///     size_z: 64,
+    ///     y: 100,
+    ///     size_y: 0,
+    /// });
+    ///
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// for pos in cache.positions_of(BiomeID::plains) {
+    ///     assert_eq!(generator.get_biome_at(pos.at_y(100)), Ok(BiomeID::plains));
+    /// }
+    /// ```
+    pub fn positions_of(&self, biome: BiomeID) -> impl Iterator<Item = BlockPosition> + '_ {
+        let range = self.range;
+        let (size_x, size_z) = (range.size_x as usize, range.size_z as usize);
+
+        self.as_vec()
+            .iter()
+            .enumerate()
+            .filter(move |(_, &raw_biomeid)| raw_biomeid == biome as i32)
+            .map(move |(i, _)| {
+                let x = (i % size_x) as i32;
+                let z = ((i / size_x) % size_z) as i32;
+
+                BlockPosition::from_scaled(range.x + x, range.z + z, range.scale)
+            })
+    }
+}
+
+impl Generator {
+    /// Finds the positions of a biome within a range
+    ///
+    /// The range is first generated at [`Scale::Chunk`] and only the chunks
+    /// containing the biome, or next to a chunk containing it, are generated
+    /// at [`Scale::Quad`]. This is a lot faster than generating the whole
+    /// range at [`Scale::Quad`], but may miss patches of the biome which are
+    /// smaller than a chunk. Use [`Cache::positions_of()`] on a filled cache
+    /// if every cell is needed.
+    ///
+    /// Only the layer at [`Range::y`] is searched. The positions are block
+    /// coordinates of the upper left corner of each 4x4 cell, within the range.
+    ///
+    /// # Errors
+    /// Returns an error if the range can't be given to cubiomes, or if
+    /// generating the biomes fails
+    pub fn biome_positions(
+        &self,
+        biome: BiomeID,
+        range: Range,
+    ) -> Result<Vec<BlockPosition>, GeneratorError> {
+        super::validate::range(&range)?;
+
+        let bounds = Aabb::from(range);
+        let chunk_range = Range {
+            size_y: 0,
+            ..bounds.to_range(Scale::Chunk)
+        };
+
+        let (chunk_x, chunk_z, y) = (chunk_range.x, chunk_range.z, chunk_range.y);
+        let (size_x, size_z) = (chunk_range.size_x as i32, chunk_range.size_z as i32);
+
+        let mut chunks = Cache::new(self, chunk_range);
+        chunks.fill_cache()?;
+
+        let contains_biome: Vec<bool> = chunks
+            .as_vec()
+            .iter()
+            .map(|&raw_biomeid| raw_biomeid == biome as i32)
+            .collect();
+
+        let mut quads = Cache::new(
+            self,
+            Range {
+                scale: Scale::Quad,
+                x: 0,
+                z: 0,
+                size_x: 4,
+                size_z: 4,
+                y,
+                size_y: 0,
+            },
+        );
+
+        let mut positions = Vec::new();
+
+        for z in 0..size_z {
+            for x in 0..size_x {
+                let near_biome = (-1..=1)
+                    .flat_map(|dz| (-1..=1).map(move |dx| (x + dx, z + dz)))
+                    .filter(|&(x, z)| (0..size_x).contains(&x) && (0..size_z).contains(&z))
+                    .any(|(x, z)| contains_biome[(z * size_x + x) as usize]);
+
+                if !near_biome {
+                    continue;
+                }
+
+                quads.move_cache((chunk_x + x) * 4, y, (chunk_z + z) * 4);
+                quads.fill_cache()?;
+
+                positions.extend(
+                    quads
+                        .positions_of(biome)
+                        .filter(|pos| bounds.contains(pos.x, bounds.min_y, pos.z)),
+                );
+            }
+        }
+
+        Ok(positions)
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    num::NonZeroUsize,
+};
+
+/// A map keeping up to `capacity` entries, dropping the least recently used
+/// one to make room for a new one
+///
+/// Used by [`super::CachedGenerator`] and [`super::GeneratorRegistry`].
+#[derive(Debug, Clone)]
+pub(super) struct Lru<K, V> {
+    capacity: NonZeroUsize,
+    entries: HashMap<K, (V, u64)>,
+    /// The keys of the entries by the time they were last used
+    recent: BTreeMap<u64, K>,
+    clock: u64,
+}
+
+impl<K: Copy + Eq + Hash, V> Lru<K, V> {
+    pub(super) fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recent: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub(super) fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(super) fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Gets the value of a key and marks it as the most recently used
+    pub(super) fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+
+        let (value, used) = self.entries.get_mut(key)?;
+        self.recent.remove(used);
+        self.recent.insert(self.clock, *key);
+        *used = self.clock;
+
+        Some(value)
+    }
+
+    /// Inserts the value of a key as the most recently used
+    ///
+    /// If the map is full and doesn't have the key yet, the least recently
+    /// used entry is dropped first.
+    pub(super) fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+
+        if let Some((_, used)) = self.entries.remove(&key) {
+            self.recent.remove(&used);
+        } else if self.entries.len() >= self.capacity.get() {
+            if let Some((_, oldest)) = self.recent.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+
+        self.entries.insert(key, (value, self.clock));
+        self.recent.insert(self.clock, key);
+    }
+
+    pub(super) fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, used) = self.entries.remove(key)?;
+        self.recent.remove(&used);
+
+        Some(value)
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+        self.recent.clear();
+    }
+}
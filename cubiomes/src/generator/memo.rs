@@ -0,0 +1,147 @@
+use super::{error::GeneratorError, lru::Lru, BlockPosition3D, Generator, QuadPosition, Scale};
+use crate::enums::BiomeID;
+use std::num::NonZeroUsize;
+
+/// The scale and coordinates of a memoized point query
+type Key = (Scale, i32, i32, i32);
+
+/// A generator remembering the results of its latest point queries
+///
+/// Interactive viewers query the same cells over and over while panning.
+/// This wraps a generator and memoizes [`Generator::get_biome_at()`] and
+/// [`Generator::quad_biome_at()`], keyed by the scale and the coordinates of
+/// the query. Once `capacity` results are remembered, the least recently used
+/// one is forgotten. Failed queries aren't remembered.
+///
+/// The generator is borrowed, so its seed can't change while its results are
+/// remembered.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{CachedGenerator, Generator, GeneratorFlags};
+/// use std::num::NonZeroUsize;
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let capacity = NonZeroUsize::new(4096).expect("the capacity isn't 0");
+/// let mut cached = CachedGenerator::new(&generator, capacity);
+///
+/// let biome = cached.get_biome_at((100, 64, -200));
+/// assert_eq!(cached.get_biome_at((100, 64, -200)), biome);
+/// assert_eq!((cached.hits(), cached.misses()), (1, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedGenerator<'generator> {
+    generator: &'generator Generator,
+    entries: Lru<Key, BiomeID>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<'generator> CachedGenerator<'generator> {
+    /// Creates a new memoizing wrapper remembering up to `capacity` results
+    #[must_use]
+    pub fn new(generator: &'generator Generator, capacity: NonZeroUsize) -> Self {
+        Self {
+            generator,
+            entries: Lru::new(capacity),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Gets the biome at a block like [`Generator::get_biome_at()`]
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Generator::get_biome_at()`]
+    pub fn get_biome_at(
+        &mut self,
+        pos: impl Into<BlockPosition3D>,
+    ) -> Result<BiomeID, GeneratorError> {
+        let pos = pos.into();
+        let generator = self.generator;
+
+        self.memoized((Scale::Block, pos.x, pos.y, pos.z), || {
+            generator.get_biome_at(pos)
+        })
+    }
+
+    /// Gets the biome of a 1:4 cell like [`Generator::quad_biome_at()`]
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Generator::quad_biome_at()`]
+    pub fn quad_biome_at(&mut self, pos: QuadPosition) -> Result<BiomeID, GeneratorError> {
+        let generator = self.generator;
+
+        self.memoized((Scale::Quad, pos.x, pos.y, pos.z), || {
+            generator.quad_biome_at(pos)
+        })
+    }
+
+    /// Gets the generator the queries are run on
+    #[must_use]
+    pub fn generator(&self) -> &'generator Generator {
+        self.generator
+    }
+
+    /// Gets the most results remembered at once
+    #[must_use]
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.entries.capacity()
+    }
+
+    /// Gets the amount of results currently remembered
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if no results are remembered
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the amount of queries answered from a remembered result
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Gets the amount of queries which had to be generated
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Forgets every remembered result
+    ///
+    /// The hits and misses are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn memoized(
+        &mut self,
+        key: Key,
+        generate: impl FnOnce() -> Result<BiomeID, GeneratorError>,
+    ) -> Result<BiomeID, GeneratorError> {
+        if let Some(&biome) = self.entries.get(&key) {
+            self.hits += 1;
+
+            return Ok(biome);
+        }
+
+        self.misses += 1;
+        let biome = generate()?;
+        self.entries.insert(key, biome);
+
+        Ok(biome)
+    }
+}
@@ -11,37 +11,118 @@
 //!
 //! For structure generation, see [`crate::structures`]
 //!
-//! ## Optimal height
-//!
-//! For the y value in generation you should generally use minecraft build limit for surface biomes in the overworld.
-//! (either 320 for post 1.18, or 255 for pre 1.18)
-//! Note that this isn't exhaustive, please check the [minecraft wiki](https://minecraft.wiki/w/Altitude#History)
-//! for an exhaustive list of changes in worldheight.
+//! For versions up to 1.17, the layers used for each scale can be accessed
+//! with [`Generator::layer_for_scale()`], see [`crate::layers`]
 //!
+//! ## Y levels
 //!
+//! Queries which take a y coordinate accept a [`YLevel`], which names the
+//! height by what it is instead of a number that changes between versions.
+//! Use [`YLevel::Surface`] for the biomes players see on the surface,
+//! [`YLevel::BuildLimit`] or [`YLevel::SeaLevel`] for a fixed height of the
+//! world, or a plain `i32` for an absolute y. The levels are resolved for the
+//! version and dimension with [`YLevel::resolve()`], see
+//! [`Generator::world_limits()`] for the heights they come from.
 //!
 //! # Details
 //!
 //! This module follow closely to how the underlying cubiomes library works, but the
 //! features have been wrapped by a safe rust api
 
+pub use aabb::*;
+pub use adventuring::*;
+pub use beta::*;
+pub use binary::*;
+pub use borders::*;
+pub use caves::*;
+pub use column::*;
+pub use compact::*;
+pub use deep_dark::*;
+pub use diff::*;
+pub use distance::*;
+pub use end::*;
+pub use estimate::*;
+pub use flags::*;
+pub use height::*;
+pub use islands::*;
+pub use limits::*;
+pub use line::*;
+pub use memo::*;
+pub use nether::*;
+pub use patches::*;
 pub use position::*;
 pub use range::*;
+pub use registry::*;
+pub use route::*;
+pub use scales::*;
+pub use spiral::*;
+pub use stats::*;
+pub use surface::*;
+pub use terrain::*;
+pub use tiles::*;
+pub use view::*;
+pub use voronoi::*;
+pub use y_level::*;
 
-use crate::enums;
+use crate::enums::{self, GeneratorFlag};
 use bitflags::bitflags;
-use error::GeneratorError;
+use cubiomes_sys::{getMinCacheSize, num_traits::FromPrimitive};
+use error::{GeneratorContext, GeneratorError};
 use std::{
     alloc::{alloc, dealloc, Layout},
     fmt::Debug,
-    mem::transmute,
+    hash::{Hash, Hasher},
 };
-use cubiomes_sys::{getMinCacheSize, num_traits::FromPrimitive};
 
+mod aabb;
+mod adventuring;
+#[cfg(feature = "ndarray")]
+mod array;
+mod batch;
+mod beta;
+mod binary;
+mod borders;
+mod caves;
+mod clone;
 pub mod colors;
+mod column;
+mod compact;
+pub mod coords;
+mod deep_dark;
+mod diff;
+mod distance;
+mod end;
 pub mod error;
-mod range;
+mod estimate;
+mod export;
+mod flags;
+mod height;
+mod islands;
+mod limits;
+mod line;
+mod locate;
+mod lru;
+mod memo;
+mod nether;
+mod patches;
 mod position;
+mod range;
+mod registry;
+mod route;
+mod scales;
+mod scan;
+mod shift;
+mod spiral;
+mod stats;
+mod surface;
+#[cfg(feature = "tokio")]
+mod tasks;
+mod terrain;
+mod tiles;
+pub(crate) mod validate;
+mod view;
+mod voronoi;
+mod y_level;
 
 #[cfg(test)]
 mod tests;
@@ -53,13 +134,16 @@ bitflags! {
     /// This indicates flags to pass to cubiomes. Unless you know what
     /// you are doing, you should probably leave these empty. Check the
     /// actual cubiomes library for documentation on what they do.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
     pub struct GeneratorFlags: u32 {
-        #[allow(missing_docs)]
-        const LargeBiomes = 0x1;
-        #[allow(missing_docs)]
-        const NoBetaOcean = 0x2;
-        #[allow(missing_docs)]
-        const ForceOceanVariants = 0x4;
+        /// Generates the biomes of the large biomes world type, from beta 1.8
+        const LargeBiomes = GeneratorFlag::GF_LARGE_BIOMES as u32;
+        /// Leaves out the oceans of beta 1.7 and older, which come from the
+        /// terrain instead of the biomes and are slow to generate
+        const NoBetaOcean = GeneratorFlag::GF_NO_BETA_OCEAN as u32;
+        /// Gives the ocean variants at scales above 1:4 too, in 1.13 to 1.17
+        const ForceOceanVariants = GeneratorFlag::GF_FORCE_OCEAN_VARIANTS as u32;
         //the source may set any bits
         #[allow(missing_docs)]
         const _ = !0;
@@ -75,7 +159,9 @@ bitflags! {
 ///
 /// Biomes can be generated either with [`Self::get_biome_at()`] for single points,
 /// or in conjuntion with a [`Cache`] generated by [`Cache::new()`]
-#[derive(Debug)]
+///
+/// Generators are equal if they were set up with the same version and flags,
+/// and seeded with the same seed and dimension.
 pub struct Generator {
     generator: *mut cubiomes_sys::Generator,
 }
@@ -84,13 +170,48 @@ impl Drop for Generator {
     fn drop(&mut self) {
         // Safety:
         // The memory is safe to deallocate as its been allocated in new
-        // and the pointer to it is dropped, so it is never referred to again
+        // or handed over in from_raw with the same layout, and the pointer to
+        // it is dropped, so it is never referred to again
         unsafe {
-            dealloc(self.generator as *mut u8, Layout::new::<Generator>());
+            dealloc(
+                self.generator as *mut u8,
+                Layout::new::<cubiomes_sys::Generator>(),
+            );
         }
     }
 }
 
+impl Debug for Generator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Generator")
+            .field("version", &self.version())
+            .field("seed", &self.seed())
+            .field("dimension", &self.dimension())
+            .field("flags", &self.flags())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Generator {
+    fn eq(&self, other: &Self) -> bool {
+        self.version() == other.version()
+            && self.seed() == other.seed()
+            && self.dimension() == other.dimension()
+            && self.flags() == other.flags()
+    }
+}
+
+impl Eq for Generator {}
+
+impl Hash for Generator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.version().hash(state);
+        self.seed().hash(state);
+        self.dimension().hash(state);
+        self.flags().hash(state);
+    }
+}
+
 // SAFETY: As the raw pointer inside generator is exclusive to this instance of
 // generator, sending it in between threads should be safe. Only way to access
 // the raw pointer is via unsafe.
@@ -115,7 +236,7 @@ impl Generator {
     /// use cubiomes::enums::{MCVersion, Dimension};
     ///
     /// let seed: i64 = -4804349813814383506;
-    /// let mc_version = MCVersion::MC_1_21_WD;
+    /// let mc_version = MCVersion::MC_1_20_6;
     ///
     /// let generator = Generator::new(mc_version, seed, Dimension::DIM_OVERWORLD, GeneratorFlags::empty());
     ///
@@ -138,6 +259,74 @@ impl Generator {
         }
     }
 
+    /// Initializes a new generator like [`Self::new()`], checking the
+    /// version, dimension and flags first
+    ///
+    /// [`Self::new()`] accepts every value, and a generator for `MC_UNDEF` or
+    /// `DIM_UNDEF` returns errors from its queries instead. This rejects them
+    /// upfront.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{error::GeneratorError, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::try_new(MCVersion::MC_UNDEF, 0, Dimension::DIM_OVERWORLD, GeneratorFlags::empty());
+    ///
+    /// assert_eq!(generator, Err(GeneratorError::UnsupportedVersion(MCVersion::MC_UNDEF)));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the errors of [`GeneratorFlags::validate_for()`], and
+    /// [`GeneratorError::UnsupportedDimension`] for `DIM_UNDEF`
+    pub fn try_new(
+        mc_version: enums::MCVersion,
+        seed: i64,
+        dimension: enums::Dimension,
+        flags: GeneratorFlags,
+    ) -> Result<Self, GeneratorError> {
+        flags.validate_for(mc_version)?;
+
+        if dimension == enums::Dimension::DIM_UNDEF {
+            return Err(GeneratorError::UnsupportedDimension(dimension));
+        }
+
+        Ok(Self::new(mc_version, seed, dimension, flags))
+    }
+
+    /// Initializes a new generator for the large biomes world type with a
+    /// seed applied in the overworld
+    ///
+    /// This is [`Self::new()`] with [`GeneratorFlags::LargeBiomes`]. The
+    /// biomes are four times as large in both directions, but coordinates and
+    /// scales stay in blocks as usual, so the same [`Range`] covers the same
+    /// blocks as without the flag. Structure placement doesn't change, only
+    /// the biome checks of the structures do. The other dimensions aren't
+    /// affected by the world type.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let seed: i64 = -4804349813814383506;
+    /// let generator = Generator::large_biomes(MCVersion::MC_1_20_6, seed);
+    ///
+    /// assert_eq!(
+    ///     generator,
+    ///     Generator::new(MCVersion::MC_1_20_6, seed, Dimension::DIM_OVERWORLD, GeneratorFlags::LargeBiomes)
+    /// );
+    /// ```
+    #[must_use]
+    pub fn large_biomes(mc_version: enums::MCVersion, seed: i64) -> Self {
+        Self::new(
+            mc_version,
+            seed,
+            enums::Dimension::DIM_OVERWORLD,
+            GeneratorFlags::LargeBiomes,
+        )
+    }
+
     /// Initializes a new generator for the given minecraft version and flags
     ///
     /// This function initializes a new cubiomes generator for the specified
@@ -156,7 +345,7 @@ impl Generator {
     /// use cubiomes::generator::GeneratorFlags;
     ///
     /// // Version of minecraft to use with the generator
-    /// let mc_version = MCVersion::MC_1_21_WD;
+    /// let mc_version = MCVersion::MC_1_20_6;
     /// let generator;
     /// unsafe{
     ///     generator = Generator::new_without_seed(mc_version, GeneratorFlags::empty());
@@ -170,7 +359,7 @@ impl Generator {
         // the pointer is stored as a pointer
         unsafe {
             let generator =
-            alloc(Layout::new::<cubiomes_sys::Generator>()) as *mut cubiomes_sys::Generator;
+                alloc(Layout::new::<cubiomes_sys::Generator>()) as *mut cubiomes_sys::Generator;
 
             cubiomes_sys::setupGenerator(generator, version as i32, flags.bits());
             Self { generator }
@@ -183,30 +372,121 @@ impl Generator {
     /// initialization if the generator was generated with [`Self::new_without_seed()`]
     /// or changing the seed of the generator
     pub fn apply_seed(&mut self, dimension: enums::Dimension, seed: i64) {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(seed, ?dimension, version = ?self.version(), "applying seed");
+
         // SAFETY:
         // As the generator is correctly initialized and its fields are private
         // the applySeed function is only given valid instances of generator
         unsafe {
-            cubiomes_sys::applySeed(
-                self.generator,
-                dimension as i32,
-                transmute::<i64, u64>(seed),
-            );
+            cubiomes_sys::applySeed(self.generator, dimension as i32, seed.cast_unsigned());
         }
     }
 
     /// Tries to get a biomeid at the specific location.
-    pub fn get_biome_at(&self, x: i32, y: i32, z: i32) -> Result<enums::BiomeID, GeneratorError> {
+    ///
+    /// The position is in blocks and can be a [`BlockPosition3D`], a
+    /// `(x, y, z)` tuple or an `[x, y, z]` array. See [`Self::quad_biome_at()`]
+    /// for getting the biome of a 1:4 cell.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let column = BlockPosition::new(100, -200);
+    /// assert_eq!(
+    ///     generator.get_biome_at((100, 64, -200)),
+    ///     generator.get_biome_at(column.at_y(64))
+    /// );
+    /// ```
+    pub fn get_biome_at(
+        &self,
+        pos: impl Into<BlockPosition3D>,
+    ) -> Result<enums::BiomeID, GeneratorError> {
+        let pos = pos.into();
+        self.biome_at_scale(Scale::Block, pos.x, pos.y, pos.z)
+    }
+
+    /// Tries to get the biomeid of a 1:4 cell
+    ///
+    /// This is the biome a cache at [`Scale::Quad`] has for the cell, without
+    /// the voronoi zoom [`Self::get_biome_at()`] applies to blocks.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, QuadPosition, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(
+    ///     &generator,
+    ///     Range {
+    ///         scale: Scale::Quad,
+    ///         x: 25,
+    ///         z: -50,
+    ///         size_x: 1,
+    ///         size_z: 1,
+    ///         y: 16,
+    ///         size_y: 1,
+    ///     },
+    /// );
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// assert_eq!(
+    ///     generator.quad_biome_at(QuadPosition::new(25, 16, -50)),
+    ///     cache.biome_at(0, 0, 0)
+    /// );
+    /// ```
+    pub fn quad_biome_at(&self, pos: QuadPosition) -> Result<enums::BiomeID, GeneratorError> {
+        self.biome_at_scale(Scale::Quad, pos.x, pos.y, pos.z)
+    }
+
+    fn biome_at_scale(
+        &self,
+        scale: Scale,
+        x: i32,
+        y: i32,
+        z: i32,
+    ) -> Result<enums::BiomeID, GeneratorError> {
+        self.check_seeded()?;
+        validate::range(&Range {
+            scale,
+            x,
+            z,
+            size_x: 1,
+            size_z: 1,
+            y,
+            size_y: 1,
+        })?;
+
         // SAFETY:
         // As the generator is correctly initialized and its fields are private
         // the applySeed function is only given valid instances of generator.
         //
-        // The scale enum guarantees that getBiomeAt is only given a scale of 1 or 4
-        // As specified in its documentation
+        // The callers only pass Scale::Block or Scale::Quad, so getBiomeAt is
+        // only given a scale of 1 or 4 as specified in its documentation
         unsafe {
-            match cubiomes_sys::getBiomeAt(self.generator, Scale::Block as i32, x, y, z) {
-                -1 => Err(GeneratorError::GetBiomeAtFailure),
-                n => FromPrimitive::from_i32(n).ok_or(GeneratorError::BiomeIDOutOfRange(n)),
+            match cubiomes_sys::getBiomeAt(self.generator, scale as i32, x, y, z) {
+                -1 => Err(GeneratorError::GetBiomeAtFailure {
+                    context: self.context(),
+                    pos: BlockPosition3D::new(x, y, z),
+                    scale,
+                }),
+                n => enums::BiomeID::from_id(n).ok_or(GeneratorError::BiomeIDOutOfRange(n)),
             }
         }
     }
@@ -217,17 +497,89 @@ impl Generator {
         // SAFETY:
         // The generator pointer can't be null as its been initialized
         // when constructing this struct
-        unsafe { transmute::<u64, i64>((*self.generator).seed) }
+        unsafe { (*self.generator).seed.cast_signed() }
     }
 
     /// Gets the minecraft version of [self]
+    ///
+    /// This is the same as [`Self::version()`]
     #[must_use]
     pub fn minecraft_version(&self) -> enums::MCVersion {
+        self.version()
+    }
+
+    /// Gets the minecraft version [self] was set up for
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -4804349813814383506,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::LargeBiomes,
+    /// );
+    ///
+    /// assert_eq!(generator.version(), MCVersion::MC_1_20_6);
+    /// assert_eq!(generator.seed(), -4804349813814383506);
+    /// assert_eq!(generator.dimension(), Dimension::DIM_NETHER);
+    /// assert_eq!(generator.flags(), GeneratorFlags::LargeBiomes);
+    /// ```
+    #[must_use]
+    pub fn version(&self) -> enums::MCVersion {
+        // SAFETY:
+        // The generator pointer can't be null as its been initialized
+        // when constructing this struct
+        enums::MCVersion::from_i32(unsafe { (*self.generator).mc })
+            .unwrap_or(enums::MCVersion::MC_UNDEF)
+    }
+
+    /// Gets the flags [self] was set up with
+    #[must_use]
+    pub fn flags(&self) -> GeneratorFlags {
+        // SAFETY:
+        // The generator pointer can't be null as its been initialized
+        // when constructing this struct
+        GeneratorFlags::from_bits_retain(unsafe { (*self.generator).flags })
+    }
+
+    /// Gets the dimension of [self]
+    #[must_use]
+    pub fn dimension(&self) -> enums::Dimension {
         // SAFETY:
         // The generator pointer can't be null as its been initialized
         // when constructing this struct
-        enums::MCVersion::from_i32(unsafe { *self.generator }.mc)
-            .expect("Cubiomes generator has an invalid mc version")
+        enums::Dimension::from_i32(unsafe { (*self.generator).dim })
+            .unwrap_or(enums::Dimension::DIM_UNDEF)
+    }
+
+    /// Gets the version, seed and dimension of [self]
+    ///
+    /// This is what errors from cubiomes carry to reproduce the failure.
+    #[must_use]
+    pub fn context(&self) -> GeneratorContext {
+        GeneratorContext {
+            version: self.version(),
+            seed: self.seed(),
+            dimension: self.dimension(),
+        }
+    }
+
+    /// Returns [`GeneratorError::SeedNotApplied`] if no seed was applied to
+    /// [self] since it was created with [`Self::new_without_seed()`], and
+    /// [`GeneratorError::UnsupportedVersion`] for a generator of `MC_UNDEF`
+    pub(crate) fn check_seeded(&self) -> Result<(), GeneratorError> {
+        if self.version() == enums::MCVersion::MC_UNDEF {
+            return Err(GeneratorError::UnsupportedVersion(self.version()));
+        }
+
+        if self.dimension() == enums::Dimension::DIM_UNDEF {
+            return Err(GeneratorError::SeedNotApplied(self.version()));
+        }
+
+        Ok(())
     }
 
     /// Gets a raw mutable pointer to the underlying generator
@@ -265,13 +617,119 @@ impl Generator {
         self.generator
     }
 
+    /// Gets a reference to the underlying cubiomes generator
+    ///
+    /// This is the safe way of calling functions from `cubiomes_sys` which
+    /// aren't wrapped yet and only read the generator, by passing
+    /// [`std::ptr::from_ref()`] of the reference. The generator is always set
+    /// up for its version and has a seed applied, unless it was created with
+    /// [`Self::new_without_seed()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// // SAFETY: getBiomeAt only reads the generator, and 1 is a valid scale
+    /// let biome = unsafe {
+    ///     cubiomes_sys::getBiomeAt(std::ptr::from_ref(generator.as_raw()), 1, 0, 64, 0)
+    /// };
+    ///
+    /// assert_eq!(Ok(biome), generator.get_biome_at((0, 64, 0)).map(|biome| biome as i32));
+    /// ```
+    #[must_use]
+    pub fn as_raw(&self) -> &cubiomes_sys::Generator {
+        // SAFETY:
+        // The generator pointer can't be null as its been initialized
+        // when constructing this struct, and it is only mutated through
+        // exclusive references
+        unsafe { &*self.generator }
+    }
+
+    /// Gets a mutable reference to the underlying cubiomes generator
+    ///
+    /// This can be used for calling functions from `cubiomes_sys` which
+    /// mutate the generator, like `applySeed` with a dimension this crate
+    /// doesn't support.
+    ///
+    /// # Safety
+    /// The safe api of this crate passes the generator to cubiomes, so once the
+    /// reference is dropped the generator must still be in a state cubiomes
+    /// can use:
+    ///
+    /// - `mc` must be a [`enums::MCVersion`] and `dim` a [`enums::Dimension`]
+    /// - The generator must be set up with `setupGenerator` for its version,
+    ///   which it is unless `mc` or the layers are changed by hand
+    /// - The generator must not be moved out of the reference, as it points
+    ///   into itself
+    pub unsafe fn as_raw_mut(&mut self) -> &mut cubiomes_sys::Generator {
+        // SAFETY:
+        // The generator pointer can't be null as its been initialized
+        // when constructing this struct, and the exclusive reference to
+        // self guarantees exclusive access
+        unsafe { &mut *self.generator }
+    }
+
+    /// Takes ownership of a cubiomes generator allocated outside of this crate
+    ///
+    /// The generator is deallocated when the returned generator is dropped.
+    /// This is the inverse of [`Self::into_raw()`].
+    ///
+    /// # Safety
+    /// - The pointer must be allocated with the global allocator with the
+    ///   layout of [`cubiomes_sys::Generator`], like [`Box::into_raw()`] or
+    ///   [`Self::into_raw()`] do
+    /// - It must be set up with `setupGenerator` after it was allocated, and
+    ///   not moved since, as the generator points into itself
+    /// - A seed must be applied with `applySeed` before biomes are generated,
+    ///   like with [`Self::new_without_seed()`]
+    /// - Nothing else may access or deallocate the generator afterwards
+    #[must_use]
+    pub unsafe fn from_raw(generator: *mut cubiomes_sys::Generator) -> Self {
+        Self { generator }
+    }
+
+    /// Gives up ownership of the underlying cubiomes generator
+    ///
+    /// The generator isn't deallocated, it can be deallocated by passing the
+    /// pointer to [`Self::from_raw()`] and dropping the result, or with
+    /// [`Box::from_raw()`].
+    #[must_use]
+    pub fn into_raw(self) -> *mut cubiomes_sys::Generator {
+        let generator = self.generator;
+        std::mem::forget(self);
+        generator
+    }
+
+    /// Checks if generating parts of a range separately gives the same biomes
+    /// as generating the whole range at once
+    ///
+    /// The 1:1 biomes of 1.15 to 1.17 depend on where cubiomes starts the
+    /// generated area, so they have to be generated as a whole.
+    fn generates_in_parts(&self, scale: Scale) -> bool {
+        scale != Scale::Block
+            || !(enums::MCVersion::MC_1_15_2..=enums::MCVersion::MC_1_17_1)
+                .contains(&self.minecraft_version())
+    }
+
+    /// Gets the size of the buffer cubiomes needs for a range
+    ///
+    /// Ranges cubiomes can't generate get no buffer, as filling the cache
+    /// fails converting the range before it reaches cubiomes.
     fn min_cache_size_from_range(&self, range: Range) -> usize {
-        #[allow(clippy::unwrap_used)]
-        let raw_range: cubiomes_sys::Range = range.try_into().unwrap();
+        let Ok(raw_range) = validate::range(&range) else {
+            return 0;
+        };
 
         // SAFETY:
-        // The conversion from cubiomes_sys::Range provides a range that fits the requirements
-        // of the function
+        // The range was checked to fit the requirements of the function
         unsafe {
             self.unchecked_min_cache_size(raw_range.scale, raw_range.sx, raw_range.sy, raw_range.sz)
         }
@@ -299,30 +757,50 @@ impl Generator {
 
     /// Fills the provided cache from the generator
     ///
-    /// # Safety
-    /// The caller must guarantee, that the cache is able to contain the generated data.
-    /// The best way to guarantee this, is to use a cache generated from this generator
-    unsafe fn unchecked_generate_biomes_to_cache(
-        &self,
-        cache: &mut Cache,
-    ) -> Result<(), GeneratorError> {
-        let result_num = cubiomes_sys::genBiomes(
-            self.generator,
-            cache.buffer.as_mut_ptr(),
-            cache.range.try_into()?,
-        );
+    /// The buffer of the cache is grown to the minimum cache size of its range
+    /// first, so it doesn't matter how the cache was made.
+    fn generate_biomes_to_cache(&self, cache: &mut Cache) -> Result<(), GeneratorError> {
+        self.check_seeded()?;
+        let range = cache.range.try_into()?;
+
+        // genBiomes writes up to the minimum cache size, which is more than
+        // the readable cells for some ranges, like 1:1 in 1.15 to 1.17
+        cache.buffer.clear();
+        cache
+            .buffer
+            .reserve(self.min_cache_size_from_range(cache.range));
+
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
+
+        // SAFETY: The buffer can hold the minimum cache size of the range,
+        // which is the most genBiomes writes
+        let result_num =
+            unsafe { cubiomes_sys::genBiomes(self.generator, cache.buffer.as_mut_ptr(), range) };
 
         // If error is returned from genbiomes, dont resize the vec as it may contain garbage data
         if result_num != 0 {
-            return Err(GeneratorError::GenBiomeToCacheFailure(result_num));
+            return Err(GeneratorError::GenBiomeToCacheFailure {
+                code: result_num,
+                context: self.context(),
+                range: cache.range,
+            });
         }
 
         // We set the caches lenght to what an user would want to read from it as we
         // can't be sure if cubiomes has actually initialized all variables beyond
         // the readable area.
-        cache
-            .buffer
-            .set_len(cache.calculate_readable_cache_length());
+        //
+        // SAFETY: genBiomes succeeded, so it wrote every readable cell, which
+        // fit in the minimum cache size
+        unsafe {
+            cache
+                .buffer
+                .set_len(cache.calculate_readable_cache_length());
+        }
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_fill(cache.buffer.len(), started.elapsed());
 
         Ok(())
     }
@@ -332,13 +810,32 @@ impl Generator {
 ///
 /// The cache is usually generated with [`Self::new()`]
 /// and holds a vector filled with biome data.
-#[derive(Clone)]
+#[derive(PartialEq, Eq, Hash)]
 pub struct Cache<'generator> {
     buffer: Vec<i32>,
     range: Range,
     generator: &'generator Generator,
 }
 
+// The buffer of a clone has to hold the minimum cache size again, so it can be
+// filled like the original
+impl Clone for Cache<'_> {
+    fn clone(&self) -> Self {
+        let mut buffer = Vec::with_capacity(
+            self.generator
+                .min_cache_size_from_range(self.range)
+                .max(self.buffer.len()),
+        );
+        buffer.extend_from_slice(&self.buffer);
+
+        Self {
+            buffer,
+            range: self.range,
+            generator: self.generator,
+        }
+    }
+}
+
 //Custom dbg implementation, so we get the cache formatted as a table
 impl Debug for Cache<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -346,7 +843,8 @@ impl Debug for Cache<'_> {
         writeln!(f, "Range: {:?}", &self.range)?;
         writeln!(f, "Cache: ")?;
 
-        for line in self.buffer.chunks(self.range.size_x as usize) {
+        // An empty range can't be filled, but is still printed
+        for line in self.buffer.chunks(self.range.size_x.max(1) as usize) {
             writeln!(f, "{line:?}")?;
         }
         Ok(())
@@ -358,7 +856,7 @@ impl Cache<'_> {
     ///
     /// This function creates a new [`Cache`] against this version of the generator
     #[must_use]
-    pub fn new(generator: &Generator, range: Range) -> Cache {
+    pub fn new(generator: &Generator, range: Range) -> Cache<'_> {
         let cache_size = generator.min_cache_size_from_range(range);
         let cache = Vec::with_capacity(cache_size);
 
@@ -369,13 +867,63 @@ impl Cache<'_> {
         }
     }
 
+    /// Generates a new cache reusing the allocation of a buffer
+    ///
+    /// The buffer is cleared and grown if it is too small for the range. Get
+    /// the buffer back with [`Self::into_buffer()`] to reuse it for the next
+    /// cache, which saves allocating a cache for every seed of a search.
+    #[must_use]
+    pub fn with_buffer(generator: &Generator, range: Range, mut buffer: Vec<i32>) -> Cache<'_> {
+        buffer.clear();
+        buffer.reserve(generator.min_cache_size_from_range(range));
+
+        Cache {
+            buffer,
+            range,
+            generator,
+        }
+    }
+
+    /// Takes the buffer of the cache, see [`Self::with_buffer()`]
+    #[must_use]
+    pub fn into_buffer(self) -> Vec<i32> {
+        self.buffer
+    }
+
     /// Fills the cache so it can be read
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            name = "fill_cache",
+            skip_all,
+            fields(
+                scale = ?self.range.scale,
+                x = self.range.x,
+                z = self.range.z,
+                y = self.range.y,
+                size_x = self.range.size_x,
+                size_z = self.range.size_z,
+                size_y = self.range.size_y,
+                seed = self.generator.seed(),
+            ),
+            err(level = "warn"),
+        )
+    )]
     pub fn fill_cache(&mut self) -> Result<(), GeneratorError> {
-        // Safety:
-        // As the cache holds a reference to the generator, the generator
-        // could not have been modified after the vec was allocated so the
-        // vec inside this cache holds enough space for the generator
-        unsafe { self.generator.unchecked_generate_biomes_to_cache(self) }
+        #[cfg(feature = "tracing")]
+        let started = std::time::Instant::now();
+
+        self.generator.generate_biomes_to_cache(self)?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            cells = self.buffer.len(),
+            elapsed = ?started.elapsed(),
+            "filled cache"
+        );
+
+        Ok(())
     }
 
     /// Gets a reference to the internal representation of the cache.
@@ -393,7 +941,7 @@ impl Cache<'_> {
     /// use cubiomes::enums::{MCVersion, Dimension, BiomeID};
     ///
     /// let mut generator = Generator::new(
-    ///     MCVersion::MC_1_21_WD,
+    ///     MCVersion::MC_1_20_6,
     ///     -380434930381432806,
     ///     Dimension::DIM_OVERWORLD,
     ///     GeneratorFlags::empty()
@@ -421,6 +969,52 @@ impl Cache<'_> {
         &self.buffer
     }
 
+    /// Gets the generator of this cache
+    #[inline]
+    #[must_use]
+    pub fn generator(&self) -> &Generator {
+        self.generator
+    }
+
+    /// Gets the number of biomes the buffer of this cache can hold
+    ///
+    /// This is the minimum cache size cubiomes requires for the range, which
+    /// can be larger than what is readable after filling the cache.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Gets a raw mutable pointer to the buffer of this cache
+    ///
+    /// This can be used for filling the cache with functions from
+    /// `cubiomes_sys`, like `genBiomes` with the range of the cache converted
+    /// to a [`cubiomes_sys::Range`]. Call [`Self::assume_filled()`] afterwards
+    /// to make the biomes readable.
+    ///
+    /// # Safety
+    /// At most [`Self::capacity()`] biomes may be written to the pointer.
+    ///
+    /// The pointer shouldn't outlive the cache, or be used after the cache is
+    /// moved, filled or resized.
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut i32 {
+        self.buffer.as_mut_ptr()
+    }
+
+    /// Marks the cache as filled after writing to [`Self::as_mut_ptr()`]
+    ///
+    /// # Safety
+    /// Every biome of the range of the cache must have been written to the
+    /// buffer, like `genBiomes` does for the range.
+    pub unsafe fn assume_filled(&mut self) {
+        let len = self.calculate_readable_cache_length();
+
+        // SAFETY: The caller guarantees the readable part of the buffer is
+        // initialized, which also means it fits in the capacity
+        unsafe { self.buffer.set_len(len) }
+    }
+
     /// Gets a reference to the range used by this cache
     ///
     /// Gets the range this cache was generated with. Useful for
@@ -435,19 +1029,83 @@ impl Cache<'_> {
 
     /// This function gets a biome at the specified point in the cache
     ///
-    /// The specified point is relative to the left upper corner of
-    /// the caches range.
+    /// The specified point is an index relative to the left upper corner of
+    /// the caches range, in cells of its scale. The origin of the range isn't
+    /// applied, see [`Self::biome_at_block()`] for looking up world
+    /// coordinates.
     ///
     /// The cache start from x:0 y:0 mapping to the 0,0 of the range it
     /// was generated with. This means that attempting to read x: 16 or y:16
     /// on a cache with a size of 16 will be out of bounds.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the point is outside of
+    /// the range or the cache isn't filled
     pub fn biome_at(&self, x: u32, y: u32, z: u32) -> Result<enums::BiomeID, GeneratorError> {
-        let raw_biomeid = *self
-            .buffer
-            .get((y * self.range.size_x * self.range.size_z + z * self.range.size_x + x) as usize)
+        if x >= self.range.size_x || z >= self.range.size_z {
+            return Err(GeneratorError::IndexOutOfBounds);
+        }
+
+        let index = (u64::from(y) * u64::from(self.range.size_z) + u64::from(z))
+            * u64::from(self.range.size_x)
+            + u64::from(x);
+        let raw_biomeid = *usize::try_from(index)
+            .ok()
+            .and_then(|index| self.buffer.get(index))
             .ok_or(GeneratorError::IndexOutOfBounds)?;
 
-        enums::BiomeID::from_i32(raw_biomeid).ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))
+        enums::BiomeID::from_id(raw_biomeid).ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))
+    }
+
+    /// Gets the biome of the cell holding a block of the world
+    ///
+    /// The position is in blocks, and is mapped to a cell of the cache
+    /// through the origin and the scale of its range, like
+    /// [`Scale::scale_y()`] for the y. The biome is the one the cache holds,
+    /// so at scales other than [`Scale::Block`] it's the biome of the cell
+    /// around the block, not necessarily the biome of the block itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range::plane(Scale::Quad, -32, 16, 64, 64, 64));
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// // The block -100, 64, 70 is in the cell -25, 16, 17, the index 7, 0, 1
+    /// assert_eq!(cache.biome_at_block((-100, 64, 70)), cache.biome_at(7, 0, 1));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the block isn't inside
+    /// the range or the cache isn't filled
+    pub fn biome_at_block(
+        &self,
+        pos: impl Into<BlockPosition3D>,
+    ) -> Result<enums::BiomeID, GeneratorError> {
+        let pos = pos.into();
+        let scale = self.range.scale;
+
+        let local = |coord: i32, start: i32, size: u32| {
+            u32::try_from(i64::from(coord) - i64::from(start))
+                .ok()
+                .filter(|local| *local < size.max(1))
+                .ok_or(GeneratorError::IndexOutOfBounds)
+        };
+
+        self.biome_at(
+            local(scale.scale_coord(pos.x), self.range.x, self.range.size_x)?,
+            local(scale.scale_y(pos.y), self.range.y, self.range.size_y)?,
+            local(scale.scale_coord(pos.z), self.range.z, self.range.size_z)?,
+        )
     }
 
     /// Moves the cache to new position x,y,z without reallocating the space
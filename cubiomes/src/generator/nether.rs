@@ -0,0 +1,435 @@
+use super::{
+    error::GeneratorError, Aabb, BiomePatch, BiomeStats, BlockPosition, BlockPosition3D, Cache,
+    Generator, Range, Scale,
+};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use std::collections::BTreeMap;
+
+/// The biomes of the nether since 1.16
+const NETHER_BIOMES_1_16: [BiomeID; 5] = [
+    BiomeID::nether_wastes,
+    BiomeID::soul_sand_valley,
+    BiomeID::crimson_forest,
+    BiomeID::warped_forest,
+    BiomeID::basalt_deltas,
+];
+
+/// Where the closest cell of a nether biome was found by
+/// [`Generator::nearest_nether_biomes()`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetherBiomeLocation {
+    /// The block position in the nether, at the center of a 1:4 cell
+    pub pos: BlockPosition,
+    /// The block position in the overworld linking to [`Self::pos`] through
+    /// a portal, 8 times as far from 0, 0
+    pub overworld_pos: BlockPosition,
+    /// The distance in nether blocks from the origin of the search to
+    /// [`Self::pos`]
+    pub distance: f64,
+}
+
+/// The closest cell of every nether biome around a position
+///
+/// Created with [`Generator::nearest_nether_biomes()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetherBiomes {
+    found: BTreeMap<BiomeID, NetherBiomeLocation>,
+    missing: Vec<BiomeID>,
+}
+
+impl NetherBiomes {
+    /// Gets the biomes generated in the nether of a version
+    ///
+    /// Before 1.16 the whole nether is a nether wastes.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, MCVersion};
+    /// use cubiomes::generator::NetherBiomes;
+    ///
+    /// assert_eq!(NetherBiomes::of(MCVersion::MC_1_15_2), [BiomeID::nether_wastes]);
+    /// assert_eq!(NetherBiomes::of(MCVersion::MC_1_20_6).len(), 5);
+    /// ```
+    #[must_use]
+    pub fn of(version: MCVersion) -> &'static [BiomeID] {
+        if version < MCVersion::MC_1_16_1 {
+            &NETHER_BIOMES_1_16[..1]
+        } else {
+            &NETHER_BIOMES_1_16
+        }
+    }
+
+    /// Checks if every nether biome of the version was found
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Gets the nether biomes which weren't found
+    #[must_use]
+    pub fn missing(&self) -> &[BiomeID] {
+        &self.missing
+    }
+
+    /// Gets the closest cell of every biome which was found
+    #[must_use]
+    pub fn found(&self) -> &BTreeMap<BiomeID, NetherBiomeLocation> {
+        &self.found
+    }
+
+    /// Gets the closest cell of a biome, if it was found
+    #[must_use]
+    pub fn location_of(&self, biome: BiomeID) -> Option<NetherBiomeLocation> {
+        self.found.get(&biome).copied()
+    }
+}
+
+impl Generator {
+    /// Gets the biome at a block of the nether below its bedrock roof
+    ///
+    /// The nether is 256 blocks high, but its terrain ends at the bedrock roof
+    /// at y 128. The biomes above the roof are still generated and change
+    /// with the height at 1:4 scale, so a biome queried there doesn't tell
+    /// which biome the caves below are. This checks that `y` is within the
+    /// [`super::WorldLimits::logical_y_range()`] instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{error::GeneratorError, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// assert!(generator.nether_biome_at((0, 64, 0)).is_ok());
+    /// assert_eq!(
+    ///     generator.nether_biome_at((0, 200, 0)),
+    ///     Err(GeneratorError::YOutOfBounds(200))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedDimension`] if the generator isn't
+    /// in the nether, [`GeneratorError::YOutOfBounds`] if `y` is above the roof
+    /// or below the bottom of the nether, and an error if generating the biome
+    /// fails
+    pub fn nether_biome_at(
+        &self,
+        pos: impl Into<BlockPosition3D>,
+    ) -> Result<BiomeID, GeneratorError> {
+        let pos = pos.into();
+        self.check_nether_y(pos.y)?;
+        self.get_biome_at(pos)
+    }
+
+    /// Clamps the heights of a range into the nether below its bedrock roof
+    ///
+    /// The y of the range is moved into the nether, and its vertical size is
+    /// shortened so that the range ends below the roof. The y of ranges at
+    /// [`Scale::Block`] is in blocks and the y of other ranges at 1:4, where
+    /// the roof is at 32. A plane with a vertical size of 0 stays a plane.
+    /// See [`Self::nether_biome_at()`] for why the biomes above the roof are
+    /// misleading.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let range = Range {
+    ///     scale: Scale::Quad,
+    ///     x: 0,
+    ///     z: 0,
+    ///     size_x: 16,
+    ///     size_z: 16,
+    ///     y: 24,
+    ///     size_y: 16,
+    /// };
+    ///
+    /// let clamped = generator.clamp_nether_range(range).expect("the generator is in the nether");
+    /// assert_eq!((clamped.y, clamped.size_y), (24, 8));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedDimension`] if the generator isn't
+    /// in the nether
+    pub fn clamp_nether_range(&self, range: Range) -> Result<Range, GeneratorError> {
+        if self.dimension() != Dimension::DIM_NETHER {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        let vertical = match range.scale {
+            Scale::Block => 1,
+            _ => 4,
+        };
+        let logical = self.world_limits().logical_y_range();
+        let (bottom, roof) = (logical.start / vertical, logical.end / vertical);
+
+        let y = range.y.clamp(bottom, roof - 1);
+        let size_y = match range.size_y {
+            0 => 0,
+            size => {
+                let end = range.y.saturating_add_unsigned(size).clamp(y + 1, roof);
+                end.abs_diff(y)
+            }
+        };
+
+        Ok(Range { y, size_y, ..range })
+    }
+
+    /// Collects the coverage of every nether biome within a radius around the
+    /// center of the nether
+    ///
+    /// The biomes are sampled at 1:4 scale at `y`, and only the cells whose
+    /// center is within `radius` blocks of 0, 0 are counted. Before 1.16 the
+    /// whole nether is a nether wastes.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let coverage = generator
+    ///     .nether_biome_coverage(500, 32)
+    ///     .expect("the generator is in the nether");
+    ///
+    /// assert_eq!(coverage.total(), 49080);
+    /// assert_eq!(coverage.count(BiomeID::basalt_deltas), 8917);
+    /// assert_eq!(coverage.percentage(BiomeID::basalt_deltas).round(), 18.0);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::nether_biome_at()`]
+    pub fn nether_biome_coverage(&self, radius: u32, y: i32) -> Result<BiomeStats, GeneratorError> {
+        let mut counts = BTreeMap::new();
+        for (_, _, biome) in self.nether_cells_within(BlockPosition::new(0, 0), radius, y)? {
+            *counts.entry(biome).or_insert(0) += 1;
+        }
+
+        Ok(BiomeStats::from_counts(counts))
+    }
+
+    /// Finds the nearest patch of a nether biome of at least `min_area` blocks
+    ///
+    /// The biomes are sampled at 1:4 scale at `y`, in a square reaching
+    /// `radius` blocks from `origin`. The nearest patch is the one with its
+    /// centroid closest to `origin`. Patches touching the edge of the square
+    /// may continue outside of it, so their area is a lower bound. Returns
+    /// [`None`] if there is no large enough patch.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let wastes = generator
+    ///     .nearest_nether_patch(BiomeID::nether_wastes, BlockPosition::new(0, 0), 1000, 20_000, 32)
+    ///     .expect("the generator is in the nether");
+    ///
+    /// let patch = wastes.expect("there is a large nether wastes nearby");
+    /// assert!(patch.area >= 20_000);
+    /// let (x, z) = patch.centroid();
+    /// assert_eq!((x.round(), z.round()), (-300.0, 160.0));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::nether_biome_at()`]
+    pub fn nearest_nether_patch(
+        &self,
+        biome: BiomeID,
+        origin: BlockPosition,
+        radius: u32,
+        min_area: u64,
+        y: i32,
+    ) -> Result<Option<BiomePatch>, GeneratorError> {
+        let cache = self.nether_cache_around(origin, radius, y)?;
+
+        let (x, z) = (f64::from(origin.x), f64::from(origin.z));
+        let nearest = cache
+            .patches_where(|other| other == biome)?
+            .into_iter()
+            .filter(|patch| patch.area >= min_area)
+            .min_by(|a, b| {
+                let distance =
+                    |patch: &BiomePatch| (patch.centroid_x - x).hypot(patch.centroid_z - z);
+                distance(a).total_cmp(&distance(b))
+            });
+
+        Ok(nearest)
+    }
+
+    /// Finds the closest cell of every nether biome within a radius around a
+    /// position
+    ///
+    /// This plans a nether hub by telling how far each biome is from
+    /// `origin`, which is in nether coordinates, along with where a portal in
+    /// the overworld has to be built to reach it. The biomes are sampled at
+    /// 1:4 scale at `y` like [`Self::nether_biome_coverage()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let biomes = generator
+    ///     .nearest_nether_biomes(BlockPosition::new(0, 0), 1000, 32)
+    ///     .expect("the generator is in the nether");
+    ///
+    /// let deltas = biomes
+    ///     .location_of(BiomeID::basalt_deltas)
+    ///     .expect("there are basalt deltas nearby");
+    /// assert_eq!(deltas.pos, BlockPosition::new(50, 206));
+    /// assert_eq!(deltas.overworld_pos, BlockPosition::new(deltas.pos.x * 8, deltas.pos.z * 8));
+    /// assert_eq!(biomes.missing(), &[]);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::nether_biome_at()`]
+    pub fn nearest_nether_biomes(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        y: i32,
+    ) -> Result<NetherBiomes, GeneratorError> {
+        let mut found: BTreeMap<BiomeID, NetherBiomeLocation> = BTreeMap::new();
+
+        for (pos, distance, biome) in self.nether_cells_within(origin, radius, y)? {
+            let closer = found
+                .get(&biome)
+                .is_none_or(|location| distance < location.distance);
+
+            if closer {
+                let location = NetherBiomeLocation {
+                    pos,
+                    overworld_pos: BlockPosition::new(
+                        pos.x.saturating_mul(8),
+                        pos.z.saturating_mul(8),
+                    ),
+                    distance,
+                };
+                found.insert(biome, location);
+            }
+        }
+
+        let missing = NetherBiomes::of(self.version())
+            .iter()
+            .copied()
+            .filter(|biome| !found.contains_key(biome))
+            .collect();
+
+        Ok(NetherBiomes { found, missing })
+    }
+
+    /// Gets the center, the distance from `origin` and the biome of every
+    /// 1:4 cell at `y` within a radius
+    fn nether_cells_within(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        y: i32,
+    ) -> Result<Vec<(BlockPosition, f64, BiomeID)>, GeneratorError> {
+        let cache = self.nether_cache_around(origin, radius, y)?;
+        let range = *cache.range();
+        let quad = Scale::Quad as i32;
+
+        let mut cells = Vec::new();
+        for (i, &raw_biomeid) in cache.as_vec().iter().enumerate() {
+            let x = (i % range.size_x as usize) as i32;
+            let z = (i / range.size_x as usize) as i32;
+            let pos = BlockPosition::new(
+                (range.x + x) * quad + quad / 2,
+                (range.z + z) * quad + quad / 2,
+            );
+
+            let distance = origin.distance(pos);
+            if distance > f64::from(radius) {
+                continue;
+            }
+
+            let biome = BiomeID::from_id(raw_biomeid)
+                .ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))?;
+            cells.push((pos, distance, biome));
+        }
+
+        Ok(cells)
+    }
+
+    /// Checks that the generator is in the nether and `y` is below its roof
+    fn check_nether_y(&self, y: i32) -> Result<(), GeneratorError> {
+        if self.dimension() != Dimension::DIM_NETHER {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+        if !self.world_limits().logical_y_range().contains(&y) {
+            return Err(GeneratorError::YOutOfBounds(y));
+        }
+
+        Ok(())
+    }
+
+    /// Generates the 1:4 nether biomes at `y` in a square around a position
+    fn nether_cache_around(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        y: i32,
+    ) -> Result<Cache<'_>, GeneratorError> {
+        self.check_nether_y(y)?;
+
+        let reach = i32::try_from(radius).unwrap_or(i32::MAX);
+        let area = Aabb::new(
+            (
+                origin.x.saturating_sub(reach),
+                y,
+                origin.z.saturating_sub(reach),
+            ),
+            (
+                origin.x.saturating_add(reach),
+                y,
+                origin.z.saturating_add(reach),
+            ),
+        );
+
+        let mut cache = Cache::new(
+            self,
+            Range {
+                size_y: 0,
+                ..area.to_range(Scale::Quad)
+            },
+        );
+        cache.fill_cache()?;
+
+        Ok(cache)
+    }
+}
@@ -0,0 +1,247 @@
+use super::{error::GeneratorError, BlockPosition, Cache};
+use crate::enums::BiomeID;
+
+/// A connected region of a cache
+///
+/// Cells are connected through their edges, so cells which only touch at a
+/// corner are in different patches. The bounds and the centroid are in block
+/// coordinates. Created with [`Cache::biome_patches()`] or
+/// [`Cache::patches_where()`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let mut cache = Cache::new(&generator, Range {
+///     scale: Scale::Quad,
+///     x: 512,
+///     z: 128,
+///     size_x: 256,
+///     size_z: 256,
+///     y: 16,
+///     size_y: 0,
+/// });
+/// cache.fill_cache().expect("failed to fill cache");
+///
+/// let islands = cache
+///     .biome_patches()
+///     .expect("the cache holds valid biomes")
+///     .into_iter()
+///     .filter(|patch| patch.biome == BiomeID::mushroom_fields)
+///     .filter(|patch| patch.width() >= 200 && patch.depth() >= 200)
+///     .map(|patch| {
+///         let (x, z) = patch.centroid();
+///         (x.round(), z.round())
+///     })
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(islands, [(2668.0, 1004.0)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiomePatch {
+    /// The biome of the first cell of the patch
+    ///
+    /// Every cell of a patch from [`Cache::biome_patches()`] has this biome,
+    /// while patches from [`Cache::patches_where()`] may hold any matching
+    /// biome.
+    pub biome: BiomeID,
+    /// The amount of cells in the patch
+    pub cells: u64,
+    /// The amount of blocks covered by the cells of the patch
+    pub area: u64,
+    /// The smallest block coordinates covered by the patch
+    pub min: BlockPosition,
+    /// The largest block coordinates covered by the patch, inclusive
+    pub max: BlockPosition,
+    /// The x coordinate of the center of mass of the patch
+    pub centroid_x: f64,
+    /// The z coordinate of the center of mass of the patch
+    pub centroid_z: f64,
+}
+
+impl BiomePatch {
+    /// Gets the width of the bounding box of the patch in blocks
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.max.x.abs_diff(self.min.x) + 1
+    }
+
+    /// Gets the depth of the bounding box of the patch in blocks
+    #[must_use]
+    pub fn depth(&self) -> u32 {
+        self.max.z.abs_diff(self.min.z) + 1
+    }
+
+    /// Gets the center of mass of the patch as block coordinates
+    ///
+    /// The centroid of a patch which isn't convex may be outside of it.
+    #[must_use]
+    pub fn centroid(&self) -> (f64, f64) {
+        (self.centroid_x, self.centroid_z)
+    }
+}
+
+impl Cache<'_> {
+    /// Finds the connected patches of the same biome in the cache
+    ///
+    /// Only the lowest layer of the cache is used. The patches are ordered by
+    /// their first cell, row by row. See [`BiomePatch`] for an example.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cache has not been
+    /// filled and [`GeneratorError::BiomeIDOutOfRange`] if the layer holds an
+    /// unknown biome
+    pub fn biome_patches(&self) -> Result<Vec<BiomePatch>, GeneratorError> {
+        let biomes = self.lowest_layer()?;
+
+        Ok(self.label(&biomes, Some))
+    }
+
+    /// Finds the connected patches of cells matching a predicate
+    ///
+    /// This groups classes of biomes, like every jungle variant, into the
+    /// same patch. Cells not matching the predicate aren't in any patch.
+    ///
+    /// # Errors
+    /// See [`Self::biome_patches()`]
+    pub fn patches_where(
+        &self,
+        mut predicate: impl FnMut(BiomeID) -> bool,
+    ) -> Result<Vec<BiomePatch>, GeneratorError> {
+        let biomes = self.lowest_layer()?;
+
+        Ok(self.label(&biomes, |biome| predicate(biome).then_some(())))
+    }
+
+    pub(super) fn lowest_layer(&self) -> Result<Vec<BiomeID>, GeneratorError> {
+        let size = self.range.size_x as usize * self.range.size_z as usize;
+
+        self.buffer
+            .get(..size)
+            .ok_or(GeneratorError::IndexOutOfBounds)?
+            .iter()
+            .map(|&raw_biomeid| {
+                BiomeID::from_id(raw_biomeid).ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))
+            })
+            .collect()
+    }
+
+    /// Labels the patches of neighbouring cells with the same key
+    ///
+    /// Cells without a key aren't part of any patch.
+    fn label<K: PartialEq>(
+        &self,
+        biomes: &[BiomeID],
+        key_of: impl FnMut(BiomeID) -> Option<K>,
+    ) -> Vec<BiomePatch> {
+        let keys: Vec<Option<K>> = biomes.iter().copied().map(key_of).collect();
+        let size_x = self.range.size_x as usize;
+
+        let mut visited = vec![false; keys.len()];
+        let mut stack = Vec::new();
+        let mut patches = Vec::new();
+
+        for start in 0..keys.len() {
+            let Some(key) = &keys[start] else {
+                continue;
+            };
+            if visited[start] {
+                continue;
+            }
+
+            let mut bounds = PatchBounds::new(start % size_x, start / size_x);
+            visited[start] = true;
+            stack.push(start);
+
+            // Flood fill the patch, the stack avoids recursing for every cell
+            while let Some(i) = stack.pop() {
+                let (x, z) = (i % size_x, i / size_x);
+                bounds.add(x, z);
+
+                let neighbours = [
+                    (x > 0).then(|| i - 1),
+                    (x + 1 < size_x).then(|| i + 1),
+                    (z > 0).then(|| i - size_x),
+                    (i + size_x < keys.len()).then(|| i + size_x),
+                ];
+
+                for neighbour in neighbours.into_iter().flatten() {
+                    if !visited[neighbour] && keys[neighbour].as_ref() == Some(key) {
+                        visited[neighbour] = true;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+
+            patches.push(bounds.into_patch(biomes[start], self));
+        }
+
+        patches
+    }
+}
+
+/// The bounds and center of mass of a patch in cells
+struct PatchBounds {
+    cells: u64,
+    min_x: usize,
+    min_z: usize,
+    max_x: usize,
+    max_z: usize,
+    sum_x: f64,
+    sum_z: f64,
+}
+
+impl PatchBounds {
+    fn new(x: usize, z: usize) -> Self {
+        Self {
+            cells: 0,
+            min_x: x,
+            min_z: z,
+            max_x: x,
+            max_z: z,
+            sum_x: 0.0,
+            sum_z: 0.0,
+        }
+    }
+
+    fn add(&mut self, x: usize, z: usize) {
+        self.cells += 1;
+        self.min_x = self.min_x.min(x);
+        self.min_z = self.min_z.min(z);
+        self.max_x = self.max_x.max(x);
+        self.max_z = self.max_z.max(z);
+        // The center of mass is taken from the centers of the cells
+        self.sum_x += x as f64 + 0.5;
+        self.sum_z += z as f64 + 0.5;
+    }
+
+    fn into_patch(self, biome: BiomeID, cache: &Cache<'_>) -> BiomePatch {
+        let range = cache.range;
+        let scale = range.scale as i32;
+        let cell_start = |offset: usize, start: i32| (start + offset as i32) * scale;
+
+        BiomePatch {
+            biome,
+            cells: self.cells,
+            area: self.cells * (scale as u64).pow(2),
+            min: BlockPosition::new(
+                cell_start(self.min_x, range.x),
+                cell_start(self.min_z, range.z),
+            ),
+            max: BlockPosition::new(
+                cell_start(self.max_x + 1, range.x) - 1,
+                cell_start(self.max_z + 1, range.z) - 1,
+            ),
+            centroid_x: (f64::from(range.x) + self.sum_x / self.cells as f64) * f64::from(scale),
+            centroid_z: (f64::from(range.z) + self.sum_z / self.cells as f64) * f64::from(scale),
+        }
+    }
+}
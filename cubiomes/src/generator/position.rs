@@ -1,8 +1,10 @@
-use cubiomes_sys::Pos;
+use super::coords::{block_to_quad, quad_to_block};
 use super::range::Scale;
+use cubiomes_sys::Pos;
 
 ///A 2d position inside minecraft
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockPosition {
     /// The x axis of the position
     pub x: i32,
@@ -28,6 +30,9 @@ impl BlockPosition {
     }
 
     /// Scales this minecraft position to a [Scale]
+    ///
+    /// Gets the cell of the scale holding the block, rounding negative
+    /// coordinates down, see [`super::coords`]
     #[must_use]
     pub fn as_scaled(&self, scale: Scale) -> (i32, i32) {
         (scale.scale_coord(self.x), scale.scale_coord(self.z))
@@ -35,11 +40,45 @@ impl BlockPosition {
 
     /// Scales the position down by a given number
     ///
-    /// Internally divides both axis by scale
+    /// Internally divides both axis by scale, rounding towards negative
+    /// infinity
     #[must_use]
     pub fn scale_by_num(&self, scale: i32) -> (i32, i32) {
         (self.x.div_euclid(scale), self.z.div_euclid(scale))
     }
+
+    /// Gets the block at a height of this column
+    #[must_use]
+    pub fn at_y(&self, y: i32) -> BlockPosition3D {
+        BlockPosition3D::new(self.x, y, self.z)
+    }
+    /// Gets the straight line distance to another position in blocks
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::generator::BlockPosition;
+    ///
+    /// assert_eq!(BlockPosition::new(0, 0).distance(BlockPosition::new(-3, 4)), 5.0);
+    /// ```
+    #[must_use]
+    pub fn distance(self, other: BlockPosition) -> f64 {
+        let dx = f64::from(self.x) - f64::from(other.x);
+        let dz = f64::from(self.z) - f64::from(other.z);
+
+        (dx * dx + dz * dz).sqrt()
+    }
+}
+
+impl From<(i32, i32)> for BlockPosition {
+    fn from((x, z): (i32, i32)) -> Self {
+        Self::new(x, z)
+    }
+}
+
+impl From<[i32; 2]> for BlockPosition {
+    fn from([x, z]: [i32; 2]) -> Self {
+        Self::new(x, z)
+    }
 }
 
 impl From<Pos> for BlockPosition {
@@ -50,3 +89,93 @@ impl From<Pos> for BlockPosition {
         }
     }
 }
+
+/// A 3d block position inside minecraft
+///
+/// Point queries like [`super::Generator::get_biome_at()`] take anything
+/// converting into this, so either this, a `(x, y, z)` tuple or an
+/// `[x, y, z]` array can be given. The coordinates are always in blocks, see
+/// [`QuadPosition`] for 1:4 cells.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockPosition3D {
+    /// The x axis of the position
+    pub x: i32,
+    /// The y axis of the position
+    pub y: i32,
+    /// The z axis of the position
+    pub z: i32,
+}
+
+impl BlockPosition3D {
+    /// Creates a new instance of a 3d minecraft position at block scale
+    #[must_use]
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Gets the column of the position, dropping the y axis
+    #[must_use]
+    pub fn column(&self) -> BlockPosition {
+        BlockPosition::new(self.x, self.z)
+    }
+
+    /// Gets the 1:4 cell containing the position
+    ///
+    /// Note that since 1.15 the biome of a block isn't necessarily the biome
+    /// of the cell containing it, see
+    /// [`super::Generator::voronoi_cell()`].
+    #[must_use]
+    pub fn to_quad(&self) -> QuadPosition {
+        QuadPosition::new(
+            block_to_quad(self.x),
+            block_to_quad(self.y),
+            block_to_quad(self.z),
+        )
+    }
+}
+
+impl From<(i32, i32, i32)> for BlockPosition3D {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<[i32; 3]> for BlockPosition3D {
+    fn from([x, y, z]: [i32; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+/// A 3d position of a 1:4 cell, as used by caches at [`Scale::Quad`]
+///
+/// Unlike [`BlockPosition3D`] this doesn't convert from tuples or arrays, so
+/// block coordinates can't be mistaken for 1:4 coordinates.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadPosition {
+    /// The x axis of the cell
+    pub x: i32,
+    /// The y axis of the cell
+    pub y: i32,
+    /// The z axis of the cell
+    pub z: i32,
+}
+
+impl QuadPosition {
+    /// Creates a new instance of a 1:4 cell position
+    #[must_use]
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Gets the block at the lowest corner of the cell
+    #[must_use]
+    pub fn to_block(&self) -> BlockPosition3D {
+        BlockPosition3D::new(
+            quad_to_block(self.x),
+            quad_to_block(self.y),
+            quad_to_block(self.z),
+        )
+    }
+}
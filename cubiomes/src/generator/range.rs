@@ -1,4 +1,4 @@
-use super::error::{GeneratorError, TryFromRangeError};
+use super::error::GeneratorError;
 
 /// A scale for the [Range]
 ///
@@ -22,20 +22,35 @@ pub enum Scale {
 impl Scale {
     /// Scales a block coordinate according to this scale
     ///
-    /// Divides the input number by the scale
+    /// Gets the cell of this scale holding the block, rounding towards
+    /// negative infinity like [`super::coords::block_to_cell()`], so block -1
+    /// is in cell -1
     #[must_use]
     pub const fn scale_coord(&self, num: i32) -> i32 {
-        num / *self as i32
+        super::coords::block_to_cell(num, *self)
     }
 
     /// Reverses scaling done with this scale
     ///
     /// Turns whatever number is at this scale back to block coordinates
     ///
-    /// Multiplies the input number with this scale
+    /// Multiplies the input number with this scale, giving the lowest block
+    /// of the cell
     #[must_use]
     pub const fn unscale_coord(&self, num: i32) -> i32 {
-        num * *self as i32
+        super::coords::cell_to_block(num, *self)
+    }
+
+    /// Scales the y of a block like the y of a [`Range`] at this scale
+    ///
+    /// The y is only scaled by 1:4 for scales other than [`Scale::Block`],
+    /// as the biomes have no vertical resolution above 1:4.
+    #[must_use]
+    pub const fn scale_y(&self, y: i32) -> i32 {
+        match self {
+            Self::Block => y,
+            _ => super::coords::block_to_quad(y),
+        }
     }
 }
 
@@ -73,41 +88,60 @@ pub struct Range {
 impl TryFrom<Range> for cubiomes_sys::Range {
     type Error = GeneratorError;
 
+    /// Converts a range for cubiomes, checking that cubiomes can generate it
+    ///
+    /// See [`super::error::TryFromRangeError`] for the ranges which are rejected.
     fn try_from(value: Range) -> Result<Self, Self::Error> {
-        Ok(Self {
-            scale: value.scale as i32,
-            x: value.x,
-            z: value.z,
-            sx: value
-                .size_x
-                .try_into()
-                .map_err(|_| TryFromRangeError::XSizeOutOfBounds)
-                .and_then(|sx| err_if_zero(sx, TryFromRangeError::XSizeOutOfBounds))?,
-            sz: value
-                .size_z
-                .try_into()
-                .map_err(|_| TryFromRangeError::ZSizeOutOfBounds)
-                .and_then(|sz| err_if_zero(sz, TryFromRangeError::YSizeOutOfBouns))?,
-
-            y: value.y,
-            sy: value
-                .size_y
-                .try_into()
-                .map_err(|_| TryFromRangeError::YSizeOutOfBouns)?,
-        })
+        Ok(super::validate::range(&value)?)
     }
 }
 
 impl Range {
+    /// Creates a range of a single y level
+    ///
+    /// The position and size are in cells of the scale, like the fields of
+    /// the range, but the y is given in blocks and scaled with
+    /// [`Scale::scale_y()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::generator::{Range, Scale};
+    ///
+    /// let range = Range::plane(Scale::Chunk, -8, -8, 16, 16, 64);
+    ///
+    /// assert_eq!(range.y, 16);
+    /// assert_eq!(range.size_y, 0);
+    /// ```
+    #[must_use]
+    pub const fn plane(scale: Scale, x: i32, z: i32, size_x: u32, size_z: u32, y: i32) -> Self {
+        Self {
+            scale,
+            x,
+            z,
+            size_x,
+            size_z,
+            y: scale.scale_y(y),
+            size_y: 0,
+        }
+    }
+
+    /// Sets the amount of y levels of the range, from its y upwards
+    ///
+    /// The levels are in the y scale of the range, so they are 4 blocks apart
+    /// for scales other than [`Scale::Block`]. Only the overworld from 1.18,
+    /// the nether since 1.16 and the 1:1 end since 1.15 have biomes which vary
+    /// between the levels, see [`super::BiomeScales`].
+    #[must_use]
+    pub const fn with_y_levels(self, size_y: u32) -> Self {
+        Self { size_y, ..self }
+    }
+
     /// Checks if a given minecraft coordinate is within this range.
     ///
     /// First scales and then checks if a given coordinate is within this range.
     #[must_use]
     pub fn is_inside(&self, x: i32, z: i32) -> bool {
-        ((self.x <= self.scale.scale_coord(x))
-            && (self.scale.scale_coord(x) < (self.x + self.size_x as i32)))
-            && ((self.z <= self.scale.scale_coord(z))
-                && (self.scale.scale_coord(z) < (self.z + self.size_z as i32)))
+        self.global_to_local_coord(x, z).is_some()
     }
 
     /// Tries to calculate a coordinate relative to this range.
@@ -118,24 +152,15 @@ impl Range {
     ///
     #[must_use]
     pub fn global_to_local_coord(&self, x: i32, z: i32) -> Option<(u32, u32)> {
-        if self.is_inside(x, z) {
-            Some((
-                (self.scale.scale_coord(x) - self.x)
-                    .try_into()
-                    .unwrap_or_else(|_| unreachable!()),
-                (self.scale.scale_coord(z) - self.z)
-                    .try_into()
-                    .unwrap_or_else(|_| unreachable!()),
-            ))
-        } else {
-            None
-        }
-    }
-}
+        let local = |coord: i32, start: i32, size: u32| {
+            u32::try_from(i64::from(self.scale.scale_coord(coord)) - i64::from(start))
+                .ok()
+                .filter(|&local| local < size)
+        };
 
-fn err_if_zero(num: i32, err: TryFromRangeError) -> Result<i32, TryFromRangeError> {
-    if num == 0 {
-        return Err(err);
+        Some((
+            local(x, self.x, self.size_x)?,
+            local(z, self.z, self.size_z)?,
+        ))
     }
-    Ok(num)
 }
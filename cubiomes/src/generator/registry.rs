@@ -0,0 +1,174 @@
+use super::{lru::Lru, Generator, GeneratorFlags};
+use crate::enums::{Dimension, MCVersion};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// The settings a [`GeneratorRegistry`] keeps a generator for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratorKey {
+    /// The minecraft version of the generator
+    pub version: MCVersion,
+    /// The dimension the seeds are applied in
+    pub dimension: Dimension,
+    /// The flags of the generator
+    pub flags: GeneratorFlags,
+}
+
+impl GeneratorKey {
+    /// Creates a new key
+    #[must_use]
+    pub fn new(version: MCVersion, dimension: Dimension, flags: GeneratorFlags) -> Self {
+        Self {
+            version,
+            dimension,
+            flags,
+        }
+    }
+}
+
+/// A generator shared between the users of a [`GeneratorRegistry`]
+pub type SharedGenerator = Arc<Mutex<Generator>>;
+
+/// A thread safe registry of generators for many versions, dimensions and
+/// flags
+///
+/// Services answering queries for whatever version a user asks for would
+/// otherwise set up a generator for every query. The registry creates a
+/// generator the first time its [`GeneratorKey`] is used and keeps it for
+/// the next queries, applying the seed of each query to it. Once `capacity`
+/// generators are kept, the least recently used one is dropped.
+///
+/// Every generator is behind its own lock, so queries for different keys run
+/// concurrently, while queries for the same key wait for each other. Use a
+/// generator per thread instead, like [`crate::search::ParallelSeeds`], to
+/// check many seeds of a single key in parallel.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{GeneratorFlags, GeneratorKey, GeneratorRegistry};
+/// use std::num::NonZeroUsize;
+///
+/// let capacity = NonZeroUsize::new(8).expect("the capacity isn't 0");
+/// let registry = GeneratorRegistry::new(capacity);
+///
+/// let key = GeneratorKey::new(
+///     MCVersion::MC_1_20_6,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let biome = registry.with_generator(key, -5134222539607995087, |generator| {
+///     generator.get_biome_at((0, 64, 0))
+/// });
+/// assert_eq!(biome, Ok(BiomeID::cold_ocean));
+/// assert_eq!(registry.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct GeneratorRegistry {
+    generators: Mutex<Lru<GeneratorKey, SharedGenerator>>,
+}
+
+impl GeneratorRegistry {
+    /// Creates an empty registry keeping up to `capacity` generators
+    #[must_use]
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            generators: Mutex::new(Lru::new(capacity)),
+        }
+    }
+
+    /// Runs a function on the generator of a key with a seed applied
+    ///
+    /// The generator is created if the registry doesn't have it, and the seed
+    /// is applied in the dimension of the key. The generator is locked while
+    /// `f` runs, so other queries for the same key wait for it.
+    pub fn with_generator<R>(
+        &self,
+        key: GeneratorKey,
+        seed: i64,
+        f: impl FnOnce(&mut Generator) -> R,
+    ) -> R {
+        let generator = self.generator(key);
+        let mut generator = generator.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if generator.seed() != seed || generator.dimension() != key.dimension {
+            generator.apply_seed(key.dimension, seed);
+        }
+
+        f(&mut generator)
+    }
+
+    /// Gets the generator of a key, creating it if the registry doesn't have
+    /// it
+    ///
+    /// The returned generator has the seed of the last query applied. It
+    /// stays usable after it is dropped from the registry, but isn't shared
+    /// with the queries after that anymore.
+    #[must_use]
+    pub fn generator(&self, key: GeneratorKey) -> SharedGenerator {
+        let mut generators = self.lock_generators();
+
+        if let Some(generator) = generators.get(&key) {
+            return Arc::clone(generator);
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?key, "creating generator for registry");
+
+        let generator = Arc::new(Mutex::new(Generator::new(
+            key.version,
+            0,
+            key.dimension,
+            key.flags,
+        )));
+        generators.insert(key, Arc::clone(&generator));
+
+        generator
+    }
+
+    /// Checks if the registry has the generator of a key
+    #[must_use]
+    pub fn contains(&self, key: GeneratorKey) -> bool {
+        self.lock_generators().contains_key(&key)
+    }
+
+    /// Drops the generator of a key from the registry
+    ///
+    /// Returns whether the registry had the generator.
+    pub fn remove(&self, key: GeneratorKey) -> bool {
+        self.lock_generators().remove(&key).is_some()
+    }
+
+    /// Drops every generator of the registry
+    pub fn clear(&self) {
+        self.lock_generators().clear();
+    }
+
+    /// Gets the most generators kept at once
+    #[must_use]
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.lock_generators().capacity()
+    }
+
+    /// Gets the amount of generators currently kept
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock_generators().len()
+    }
+
+    /// Checks if no generators are kept
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock_generators().is_empty()
+    }
+
+    fn lock_generators(&self) -> MutexGuard<'_, Lru<GeneratorKey, SharedGenerator>> {
+        self.generators
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,261 @@
+use super::{error::GeneratorError, BlockPosition, Cache};
+use crate::enums::BiomeID;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+/// The steps to the 8 neighbours of a cell, with their length in cells
+const STEPS: [(i64, i64, f64); 8] = [
+    (1, 0, 1.0),
+    (-1, 0, 1.0),
+    (0, 1, 1.0),
+    (0, -1, 1.0),
+    (1, 1, std::f64::consts::SQRT_2),
+    (1, -1, std::f64::consts::SQRT_2),
+    (-1, 1, std::f64::consts::SQRT_2),
+    (-1, -1, std::f64::consts::SQRT_2),
+];
+
+/// A route through the cells of a cache, found with [`Cache::plan_route()`]
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{BlockPosition, Cache, Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let mut cache = Cache::new(&generator, Range::plane(Scale::Chunk, -64, -64, 128, 128, 64));
+/// cache.fill_cache().expect("failed to fill cache");
+///
+/// // Walk over land, preferring plains and avoiding mountains
+/// let route = cache
+///     .plan_route(BlockPosition::new(0, 0), BlockPosition::new(800, -600), |biome| {
+///         match biome {
+///             BiomeID::plains | BiomeID::sunflower_plains => Some(1.0),
+///             BiomeID::jagged_peaks | BiomeID::frozen_peaks | BiomeID::stony_peaks => Some(4.0),
+///             BiomeID::ocean | BiomeID::deep_ocean | BiomeID::river => None,
+///             _ => Some(2.0),
+///         }
+///     })
+///     .expect("both ends are in the cache");
+///
+/// let route = route.expect("there is a way over land");
+/// assert_eq!(route.length.round(), 1052.0);
+/// assert_eq!(route.waypoints.len(), 12);
+/// assert_eq!(route.waypoints.first(), Some(&BlockPosition::new(8, 8)));
+/// assert_eq!(route.waypoints.last(), Some(&BlockPosition::new(808, -600)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    /// The centers of the cells the route turns at, in block coordinates
+    ///
+    /// The first and the last waypoint are the centers of the cells of the
+    /// start and the goal. Cells in the middle of a straight run are left
+    /// out.
+    pub waypoints: Vec<BlockPosition>,
+    /// The length of the route in blocks, between the centers of its cells
+    pub length: f64,
+    /// The cost of the route, the sum of the length in blocks of each step
+    /// times the cost of the cell it steps on
+    pub cost: f64,
+}
+
+impl Cache<'_> {
+    /// Plans the cheapest route between two blocks through the cache
+    ///
+    /// The route steps between neighbouring cells, including diagonal ones,
+    /// and is found with A*. Stepping onto a cell costs the length of the
+    /// step in blocks times the cost of its biome, as given by `cost`. Biomes
+    /// with a cost of [`None`], or a negative or infinite cost, can't be
+    /// stepped on. Diagonal steps can't squeeze between two such cells
+    /// either.
+    ///
+    /// The costs are multipliers, so giving preferred biomes a cost of 1 and
+    /// the other biomes higher costs finds routes which take detours of up to
+    /// that factor through preferred biomes. Only the lowest layer of the
+    /// cache is used, see [`Route`] for an example.
+    ///
+    /// Returns [`None`] if the goal can't be reached from the start, for
+    /// example if either of them is in a biome which can't be stepped on.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the start or the goal
+    /// isn't inside the range or the cache isn't filled, and
+    /// [`GeneratorError::BiomeIDOutOfRange`] if the layer holds an unknown
+    /// biome
+    pub fn plan_route(
+        &self,
+        start: BlockPosition,
+        goal: BlockPosition,
+        mut cost: impl FnMut(BiomeID) -> Option<f64>,
+    ) -> Result<Option<Route>, GeneratorError> {
+        let size_x = self.range.size_x as usize;
+        let size_z = self.range.size_z as usize;
+        let scale = f64::from(self.range.scale as i32);
+        let start = self.cell_of(start)?;
+        let goal = self.cell_of(goal)?;
+
+        let costs: Vec<Option<f64>> = self
+            .lowest_layer()?
+            .into_iter()
+            .map(|biome| cost(biome).filter(|cost| cost.is_finite() && *cost >= 0.0))
+            .collect();
+
+        if costs[start].is_none() || costs[goal].is_none() {
+            return Ok(None);
+        }
+
+        // The octile distance to the goal, at the lowest cost of any cell
+        let cheapest = costs
+            .iter()
+            .flatten()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        let (goal_x, goal_z) = ((goal % size_x) as f64, (goal / size_x) as f64);
+        let estimate = |cell: usize| {
+            let dx = ((cell % size_x) as f64 - goal_x).abs();
+            let dz = ((cell / size_x) as f64 - goal_z).abs();
+            (dx.max(dz) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dz)) * scale * cheapest
+        };
+
+        let mut best = vec![f64::INFINITY; costs.len()];
+        let mut came_from = vec![usize::MAX; costs.len()];
+        let mut open = BinaryHeap::new();
+        best[start] = 0.0;
+        open.push(Reverse(Candidate(estimate(start), start)));
+
+        let neighbour = |cell: usize, dx: i64, dz: i64| {
+            let x = usize::try_from((cell % size_x) as i64 + dx).ok()?;
+            let z = usize::try_from((cell / size_x) as i64 + dz).ok()?;
+            (x < size_x && z < size_z).then_some(z * size_x + x)
+        };
+
+        while let Some(Reverse(Candidate(estimated, cell))) = open.pop() {
+            if cell == goal {
+                return Ok(Some(self.route(&came_from, &costs, goal)));
+            }
+            // A cheaper way to the cell was found after this one was queued
+            if estimated > best[cell] + estimate(cell) {
+                continue;
+            }
+
+            for (dx, dz, length) in STEPS {
+                let Some(next) = neighbour(cell, dx, dz) else {
+                    continue;
+                };
+                let Some(next_cost) = costs[next] else {
+                    continue;
+                };
+
+                let squeezes = dx != 0
+                    && dz != 0
+                    && [neighbour(cell, dx, 0), neighbour(cell, 0, dz)]
+                        .into_iter()
+                        .any(|side| side.and_then(|side| costs[side]).is_none());
+                if squeezes {
+                    continue;
+                }
+
+                let reached = best[cell] + length * scale * next_cost;
+                if reached < best[next] {
+                    best[next] = reached;
+                    came_from[next] = cell;
+                    open.push(Reverse(Candidate(reached + estimate(next), next)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Gets the index of the cell of the lowest layer holding a block
+    fn cell_of(&self, pos: BlockPosition) -> Result<usize, GeneratorError> {
+        let scale = self.range.scale;
+        let local = |coord: i32, start: i32, size: u32| {
+            usize::try_from(i64::from(scale.scale_coord(coord)) - i64::from(start))
+                .ok()
+                .filter(|local| *local < size as usize)
+                .ok_or(GeneratorError::IndexOutOfBounds)
+        };
+
+        Ok(
+            local(pos.z, self.range.z, self.range.size_z)? * self.range.size_x as usize
+                + local(pos.x, self.range.x, self.range.size_x)?,
+        )
+    }
+
+    /// Walks the steps back from the goal and turns them into a route
+    fn route(&self, came_from: &[usize], costs: &[Option<f64>], goal: usize) -> Route {
+        let size_x = self.range.size_x as usize;
+        let scale = self.range.scale as i32;
+        let center = |cell: usize| {
+            BlockPosition::new(
+                (self.range.x + (cell % size_x) as i32) * scale + scale / 2,
+                (self.range.z + (cell / size_x) as i32) * scale + scale / 2,
+            )
+        };
+
+        let mut cells = vec![goal];
+        let mut cell = goal;
+        while came_from[cell] != usize::MAX {
+            cell = came_from[cell];
+            cells.push(cell);
+        }
+        cells.reverse();
+
+        let mut route = Route {
+            waypoints: vec![center(cells[0])],
+            length: 0.0,
+            cost: 0.0,
+        };
+        for step in cells.windows(2) {
+            let (from, to) = (center(step[0]), center(step[1]));
+            let length = f64::from(to.x - from.x).hypot(f64::from(to.z - from.z));
+            route.length += length;
+            route.cost += length * costs[step[1]].unwrap_or_default();
+
+            // Leave out the cells in the middle of a straight run
+            let direction =
+                |a: BlockPosition, b: BlockPosition| ((b.x - a.x).signum(), (b.z - a.z).signum());
+            if let [.., before, last] = route.waypoints[..] {
+                if direction(before, last) == direction(last, to) {
+                    route.waypoints.pop();
+                }
+            }
+            route.waypoints.push(to);
+        }
+
+        route
+    }
+}
+
+/// A cell to visit with the estimated cost of the route through it
+#[derive(Debug, Clone, Copy)]
+struct Candidate(f64, usize);
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
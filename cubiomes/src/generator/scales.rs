@@ -0,0 +1,159 @@
+use super::{error::GeneratorError, Generator, Range, Scale};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+
+/// How the biomes of a dimension in a version behave at each scale
+///
+/// Cubiomes generates the biomes of every dimension differently, which the
+/// scales and the y of a [`Range`] reflect:
+///
+/// - The overworld has 3d biomes from 1.18, which vary with y at every scale.
+///   Before that the biomes are planar and the y is ignored.
+/// - The nether only has nether wastes up to 1.15. From 1.16 its biomes are
+///   3d at every scale.
+/// - The end only has the end biome up to 1.8, and doesn't exist before 1.0.
+///   Its biomes are planar, except at [`Scale::Block`] from 1.15, where the
+///   voronoi zoom varies with y. Cubiomes doesn't generate the end at
+///   [`Scale::HalfRegion`].
+///
+/// Use [`Generator::biome_scales()`] to get the behavior of a generator.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{BiomeScales, Scale};
+///
+/// let end = BiomeScales::of(MCVersion::MC_1_20_6, Dimension::DIM_END);
+///
+/// assert!(end.varies_with_y(Scale::Block));
+/// assert!(!end.varies_with_y(Scale::Quad));
+/// assert!(!end.supports(Scale::HalfRegion));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BiomeScales {
+    /// The version the biomes are generated in
+    pub version: MCVersion,
+    /// The dimension the biomes are generated in
+    pub dimension: Dimension,
+}
+
+impl BiomeScales {
+    /// Gets the behavior of the biomes of a dimension in a version
+    #[must_use]
+    pub fn of(version: MCVersion, dimension: Dimension) -> Self {
+        Self { version, dimension }
+    }
+
+    /// Checks if cubiomes can generate the biomes at a scale
+    #[must_use]
+    pub fn supports(&self, scale: Scale) -> bool {
+        match self.dimension {
+            Dimension::DIM_OVERWORLD | Dimension::DIM_NETHER => true,
+            Dimension::DIM_END => self.version >= MCVersion::MC_1_0_0 && scale != Scale::HalfRegion,
+            _ => false,
+        }
+    }
+
+    /// Checks if the biomes at a scale are different at different y
+    ///
+    /// If they aren't, every y level of a range has the same biomes, so
+    /// generating a plane is enough.
+    #[must_use]
+    pub fn varies_with_y(&self, scale: Scale) -> bool {
+        match self.dimension {
+            Dimension::DIM_OVERWORLD => self.version >= MCVersion::MC_1_18_2,
+            Dimension::DIM_NETHER => self.version >= MCVersion::MC_1_16_1,
+            Dimension::DIM_END => scale == Scale::Block && self.version >= MCVersion::MC_1_15_2,
+            _ => false,
+        }
+    }
+
+    /// Gets the only biome of the dimension, if it only has one
+    ///
+    /// This is the case for the nether up to 1.15 and the end up to 1.8.
+    #[must_use]
+    pub fn single_biome(&self) -> Option<BiomeID> {
+        match self.dimension {
+            Dimension::DIM_NETHER if self.version <= MCVersion::MC_1_15_2 => {
+                Some(BiomeID::nether_wastes)
+            }
+            Dimension::DIM_END
+                if (MCVersion::MC_1_0_0..=MCVersion::MC_1_8_9).contains(&self.version) =>
+            {
+                Some(BiomeID::the_end)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks that a range makes sense for the dimension
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedDimension`] for `DIM_UNDEF`,
+    /// [`GeneratorError::UnsupportedVersion`] for the end before 1.0,
+    /// [`GeneratorError::UnsupportedScale`] if cubiomes can't generate the
+    /// biomes at the scale of the range, and
+    /// [`GeneratorError::PlanarBiomes`] if the range has several y levels but
+    /// the biomes don't vary with y at its scale
+    pub fn check_range(&self, range: &Range) -> Result<(), GeneratorError> {
+        match self.dimension {
+            Dimension::DIM_OVERWORLD | Dimension::DIM_NETHER => {}
+            Dimension::DIM_END if self.version < MCVersion::MC_1_0_0 => {
+                return Err(GeneratorError::UnsupportedVersion(self.version));
+            }
+            Dimension::DIM_END => {}
+            dimension => return Err(GeneratorError::UnsupportedDimension(dimension)),
+        }
+
+        if !self.supports(range.scale) {
+            return Err(GeneratorError::UnsupportedScale(range.scale));
+        }
+
+        if range.size_y > 1 && !self.varies_with_y(range.scale) {
+            return Err(GeneratorError::PlanarBiomes {
+                dimension: self.dimension,
+                scale: range.scale,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Generator {
+    /// Gets how the biomes of the generator behave at each scale
+    #[must_use]
+    pub fn biome_scales(&self) -> BiomeScales {
+        BiomeScales::of(self.minecraft_version(), self.dimension())
+    }
+
+    /// Checks that a range makes sense for the dimension of the generator
+    ///
+    /// See [`BiomeScales::check_range()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{error::GeneratorError, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_16_5,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// // The biomes of 1.16 are the same at every y
+    /// let column = Range::plane(Scale::Quad, 0, 0, 16, 16, 64).with_y_levels(10);
+    ///
+    /// assert!(matches!(
+    ///     generator.check_range(&column),
+    ///     Err(GeneratorError::PlanarBiomes { .. })
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`BiomeScales::check_range()`]
+    pub fn check_range(&self, range: &Range) -> Result<(), GeneratorError> {
+        self.biome_scales().check_range(range)
+    }
+}
@@ -0,0 +1,136 @@
+use super::{error::GeneratorError, Cache, Range};
+
+impl Cache<'_> {
+    /// Moves the cache by an offset and generates only the biomes which
+    /// weren't in the cache before
+    ///
+    /// The offset is in cells of the scale of the range. The biomes the old
+    /// and the new range share are moved in the buffer, and only the strips of
+    /// rows and columns which the move exposes are generated. This makes
+    /// panning a map by small steps much cheaper than [`Self::move_cache()`]
+    /// and [`Self::fill_cache()`], while the biomes are the same.
+    ///
+    /// If the cache isn't filled yet or the ranges don't overlap, the whole
+    /// cache is generated. This is also the case for [`super::Scale::Block`] in 1.15
+    /// to 1.17, where cubiomes generates slightly different biomes for a strip
+    /// than for the same cells of a larger range.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut viewport = Cache::new(&generator, Range {
+    ///     scale: Scale::Quad,
+    ///     x: -128,
+    ///     z: -128,
+    ///     size_x: 256,
+    ///     size_z: 256,
+    ///     y: 16,
+    ///     size_y: 0,
+    /// });
+    /// viewport.fill_cache().expect("failed to fill cache");
+    ///
+    /// // Pan the view right and up a little
+    /// viewport.shift(16, -8).expect("failed to generate biomes");
+    ///
+    /// assert_eq!((viewport.range().x, viewport.range().z), (-112, -136));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if generating the exposed biomes fails. The cache is
+    /// left at the new position and should be filled again before reading it.
+    pub fn shift(&mut self, dx: i32, dz: i32) -> Result<(), GeneratorError> {
+        let filled = self.buffer.len() == self.calculate_readable_cache_length();
+        let (size_x, size_z) = (self.range.size_x, self.range.size_z);
+        if filled && dx == 0 && dz == 0 {
+            return Ok(());
+        }
+
+        self.move_cache(self.range.x + dx, self.range.y, self.range.z + dz);
+
+        if !filled
+            || !self.generator.generates_in_parts(self.range.scale)
+            || dx.unsigned_abs() >= size_x
+            || dz.unsigned_abs() >= size_z
+        {
+            self.buffer.clear();
+            return self.fill_cache();
+        }
+
+        let (width, height) = (size_x as usize, size_z as usize);
+        let (kept_x, kept_z) = (
+            width - dx.unsigned_abs() as usize,
+            height - dz.unsigned_abs() as usize,
+        );
+
+        // Each kept row is copied from the row it was at, in an order which
+        // reads every row before it is overwritten
+        for layer in self.buffer.chunks_exact_mut(width * height) {
+            for row in 0..kept_z {
+                let z = if dz > 0 { row } else { height - 1 - row };
+                let from_z = z.wrapping_add_signed(dz as isize);
+                let from = from_z * width + dx.max(0) as usize;
+                let to = z * width + (-dx).max(0) as usize;
+
+                layer.copy_within(from..from + kept_x, to);
+            }
+        }
+
+        // The exposed rows span the whole width, and the exposed columns the
+        // kept rows next to them
+        let first_kept_row = if dz > 0 { 0 } else { height - kept_z };
+        let first_exposed_row = if dz > 0 { kept_z } else { 0 };
+        let first_exposed_column = if dx > 0 { kept_x } else { 0 };
+
+        self.regenerate(0, first_exposed_row, width, height - kept_z)?;
+        self.regenerate(first_exposed_column, first_kept_row, width - kept_x, kept_z)?;
+
+        Ok(())
+    }
+
+    /// Generates the biomes of a rectangle of the cache, relative to the
+    /// upper left corner of its range, into the buffer
+    fn regenerate(
+        &mut self,
+        x: usize,
+        z: usize,
+        width: usize,
+        height: usize,
+    ) -> Result<(), GeneratorError> {
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let mut part = Cache::new(
+            self.generator,
+            Range {
+                x: self.range.x + x as i32,
+                z: self.range.z + z as i32,
+                size_x: width as u32,
+                size_z: height as u32,
+                ..self.range
+            },
+        );
+        part.fill_cache()?;
+
+        let layer = self.range.size_x as usize * self.range.size_z as usize;
+        let (cache_width, buffer) = (self.range.size_x as usize, &mut self.buffer);
+
+        for (y, part_layer) in part.buffer.chunks_exact(width * height).enumerate() {
+            for (row, cells) in part_layer.chunks_exact(width).enumerate() {
+                let start = y * layer + (z + row) * cache_width + x;
+                buffer[start..start + width].copy_from_slice(cells);
+            }
+        }
+
+        Ok(())
+    }
+}
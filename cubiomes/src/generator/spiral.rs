@@ -0,0 +1,151 @@
+use super::BlockPosition;
+use std::iter::FusedIterator;
+
+/// A cell given by [`spiral()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpiralCell {
+    /// The ring of the cell, the origin being ring 0
+    ///
+    /// Every cell of a ring is this many steps away from the origin on at
+    /// least one axis, and at most this many on the other one.
+    pub ring: u32,
+    /// The position of the cell
+    pub pos: BlockPosition,
+}
+
+/// Iterates over the cells of a grid around an origin, ring by ring
+///
+/// The cells are `step` apart and the iterator stops after the last ring
+/// which is at most `max_radius` from the origin, so every cell of a square
+/// with a radius of `max_radius` is given exactly once. The origin can be in
+/// any coordinates, like block or region coordinates, as long as `step` and
+/// `max_radius` are in the same ones.
+///
+/// The order is stable: the origin comes first, then every ring in order.
+/// Each ring starts at its corner with the smallest x and z and goes around
+/// clockwise, first towards positive x, then positive z. As the rings are
+/// squares, a cell of ring `n` is at least `(n - 1) * step` from the origin,
+/// so a cell closer than this can't be found in a later ring.
+///
+/// A `step` which isn't positive can't form rings, so only the origin is
+/// given for it.
+///
+/// # Examples
+/// ```
+/// use cubiomes::generator::{spiral, BlockPosition};
+///
+/// let cells: Vec<_> = spiral(BlockPosition::new(0, 0), 16, 16)
+///     .map(|cell| (cell.ring, cell.pos.x, cell.pos.z))
+///     .collect();
+///
+/// assert_eq!(
+///     cells,
+///     [
+///         (0, 0, 0),
+///         (1, -16, -16),
+///         (1, 0, -16),
+///         (1, 16, -16),
+///         (1, 16, 0),
+///         (1, 16, 16),
+///         (1, 0, 16),
+///         (1, -16, 16),
+///         (1, -16, 0),
+///     ]
+/// );
+/// ```
+#[must_use]
+pub fn spiral(origin: BlockPosition, step: i32, max_radius: u32) -> Spiral {
+    Spiral {
+        origin,
+        step,
+        max_ring: if step > 0 {
+            max_radius / step.unsigned_abs()
+        } else {
+            0
+        },
+        ring: 0,
+        index: 0,
+        finished: false,
+    }
+}
+
+/// The iterator returned by [`spiral()`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spiral {
+    origin: BlockPosition,
+    step: i32,
+    max_ring: u32,
+    ring: u32,
+    index: u64,
+    finished: bool,
+}
+
+impl Spiral {
+    /// Gets the offset of the current cell from the origin in steps
+    fn offset(&self) -> (i64, i64) {
+        let ring = i64::from(self.ring);
+        let side = 2 * ring;
+        let index = self.index as i64;
+
+        // The ring is walked in four sides of equal length, each starting
+        // at a corner
+        match index / side.max(1) {
+            0 => (-ring + index, -ring),
+            1 => (ring, -ring + index - side),
+            2 => (ring - (index - 2 * side), ring),
+            _ => (-ring, ring - (index - 3 * side)),
+        }
+    }
+
+    /// Gets the amount of cells in rings up to and including `ring`
+    fn cells_up_to(ring: u32) -> Option<usize> {
+        let width = usize::try_from(ring).ok()?.checked_mul(2)?.checked_add(1)?;
+        width.checked_mul(width)
+    }
+}
+
+impl Iterator for Spiral {
+    type Item = SpiralCell;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let (x, z) = self.offset();
+        let step = |offset: i64| (offset as i32).wrapping_mul(self.step);
+        let cell = SpiralCell {
+            ring: self.ring,
+            pos: BlockPosition::new(
+                self.origin.x.wrapping_add(step(x)),
+                self.origin.z.wrapping_add(step(z)),
+            ),
+        };
+
+        self.index += 1;
+        if self.index >= (8 * u64::from(self.ring)).max(1) {
+            if self.ring == self.max_ring {
+                self.finished = true;
+            } else {
+                self.ring += 1;
+                self.index = 0;
+            }
+        }
+
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.finished {
+            return (0, Some(0));
+        }
+
+        let taken = self.ring.checked_sub(1).map_or(Some(0), Self::cells_up_to);
+        let remaining = Self::cells_up_to(self.max_ring)
+            .and_then(|total| total.checked_sub(taken? + usize::try_from(self.index).ok()?));
+
+        (remaining.unwrap_or(usize::MAX), remaining)
+    }
+}
+
+impl FusedIterator for Spiral {}
@@ -0,0 +1,165 @@
+use super::{error::GeneratorError, Cache, Generator, Range};
+use crate::enums::BiomeID;
+use std::collections::BTreeMap;
+
+/// Every biome id is below this, so the cells can be counted in an array
+const BIOME_ID_LIMIT: usize = 256;
+
+/// Statistics about the biomes inside a range
+///
+/// Holds the amount of cells of each biome and the total amount of cells,
+/// so the coverage of a biome can be read as a percentage.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let stats = generator
+///     .biome_stats(Range {
+///         scale: Scale::Quad,
+///         x: -512,
+///         z: -512,
+///         size_x: 1024,
+///         size_z: 1024,
+///         y: 16,
+///         size_y: 0,
+///     })
+///     .expect("failed to generate biomes");
+///
+/// let ocean = stats.percentage_where(|biome| {
+///     matches!(biome, BiomeID::ocean | BiomeID::deep_ocean | BiomeID::cold_ocean)
+/// });
+///
+/// assert_eq!(ocean.round(), 23.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BiomeStats {
+    counts: BTreeMap<BiomeID, u64>,
+    total: u64,
+}
+
+impl BiomeStats {
+    /// Creates statistics from the amount of cells of each biome
+    #[must_use]
+    pub fn from_counts(counts: BTreeMap<BiomeID, u64>) -> Self {
+        let total = counts.values().sum();
+        Self { counts, total }
+    }
+
+    /// Gets the amount of cells of every biome
+    ///
+    /// Biomes which are not present in the range are not included
+    #[must_use]
+    pub fn counts(&self) -> &BTreeMap<BiomeID, u64> {
+        &self.counts
+    }
+
+    /// Gets the total amount of cells
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Gets the amount of cells of a biome
+    #[must_use]
+    pub fn count(&self, biome: BiomeID) -> u64 {
+        self.counts.get(&biome).copied().unwrap_or(0)
+    }
+
+    /// Gets the percentage of cells, which are of the given biome
+    ///
+    /// Returns 0 if there are no cells
+    #[must_use]
+    pub fn percentage(&self, biome: BiomeID) -> f64 {
+        self.percentage_where(|other| other == biome)
+    }
+
+    /// Gets the percentage of cells, whose biome matches the predicate
+    ///
+    /// Useful for groups of biomes, like all oceans. Returns 0 if there are
+    /// no cells
+    #[must_use]
+    pub fn percentage_where(&self, mut predicate: impl FnMut(BiomeID) -> bool) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        let matching: u64 = self
+            .counts
+            .iter()
+            .filter(|(&biome, _)| predicate(biome))
+            .map(|(_, count)| count)
+            .sum();
+
+        matching as f64 / self.total as f64 * 100.0
+    }
+
+    /// Iterates over every biome, its amount of cells and its percentage
+    ///
+    /// The biomes are ordered by their id
+    pub fn iter(&self) -> impl Iterator<Item = (BiomeID, u64, f64)> + '_ {
+        self.counts
+            .iter()
+            .map(|(&biome, &count)| (biome, count, count as f64 / self.total as f64 * 100.0))
+    }
+}
+
+impl Cache<'_> {
+    /// Counts the amount of cells of each biome in the cache
+    ///
+    /// The cache is counted in a single pass over an array indexed by the
+    /// biome ids, without decoding every cell. Cells which aren't a valid
+    /// biome are skipped instead of stopping the count.
+    ///
+    /// The cache should be filled with [`Self::fill_cache()`] before calling
+    /// this, otherwise the histogram is empty.
+    #[must_use]
+    pub fn histogram(&self) -> BTreeMap<BiomeID, u64> {
+        histogram(self.as_vec())
+    }
+}
+
+/// Counts the cells of each biome, skipping cells which aren't a valid biome
+pub(super) fn histogram<'a>(cells: impl IntoIterator<Item = &'a i32>) -> BTreeMap<BiomeID, u64> {
+    let mut raw_counts = [0_u64; BIOME_ID_LIMIT];
+
+    for &raw_biomeid in cells {
+        if let Some(count) = usize::try_from(raw_biomeid)
+            .ok()
+            .and_then(|index| raw_counts.get_mut(index))
+        {
+            *count += 1;
+        }
+    }
+
+    (0_i32..)
+        .zip(raw_counts)
+        .filter(|&(_, count)| count > 0)
+        .filter_map(|(raw_biomeid, count)| {
+            BiomeID::from_id(raw_biomeid).map(|biome| (biome, count))
+        })
+        .collect()
+}
+
+impl Generator {
+    /// Generates the biomes of a range and collects statistics about them
+    ///
+    /// See [`BiomeStats`] for an example
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes fails
+    pub fn biome_stats(&self, range: Range) -> Result<BiomeStats, GeneratorError> {
+        let mut cache = Cache::new(self, range);
+        cache.fill_cache()?;
+
+        Ok(BiomeStats::from_counts(cache.histogram()))
+    }
+}
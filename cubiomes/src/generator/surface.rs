@@ -0,0 +1,171 @@
+use super::{
+    coords::block_to_quad, error::GeneratorError, height::SurfaceNoise, BlockPosition, Cache,
+    Generator, Range, Scale,
+};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+
+/// The sea level of the overworld, in blocks
+pub const SEA_LEVEL: i32 = 63;
+
+impl Generator {
+    /// Checks if the biomes of the generator change with the height
+    ///
+    /// This is the case in the overworld since 1.18, where caves have their
+    /// own biomes.
+    fn has_height_dependent_biomes(&self) -> bool {
+        self.dimension() == Dimension::DIM_OVERWORLD
+            && self.minecraft_version() >= MCVersion::MC_1_18_2
+    }
+
+    /// Gets the y to sample the surface biome of a block column at
+    ///
+    /// Before 1.18 and outside of the overworld the biomes don't depend on
+    /// the height, so this is [`SEA_LEVEL`]. Since 1.18 it's the approximate
+    /// surface height of the column, see [`Self::approx_heights()`].
+    ///
+    /// # Errors
+    /// Returns an error if approximating the surface height fails
+    pub fn surface_y(&self, pos: impl Into<BlockPosition>) -> Result<i32, GeneratorError> {
+        if !self.has_height_dependent_biomes() {
+            return Ok(SEA_LEVEL);
+        }
+
+        let pos = pos.into();
+        self.surface_y_with(&SurfaceNoise::new(self), pos.x, pos.z)
+    }
+
+    /// Gets the surface biome of a block column
+    ///
+    /// The biome is sampled at [`Self::surface_y()`], so the callers don't
+    /// need to know which height to use for the version. Otherwise this is
+    /// the same as [`Self::get_biome_at()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// assert_eq!(generator.surface_biome((512, -512)), Ok(BiomeID::plains));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if approximating the surface height or getting the
+    /// biome fails
+    pub fn surface_biome(&self, pos: impl Into<BlockPosition>) -> Result<BiomeID, GeneratorError> {
+        let pos = pos.into();
+        self.get_biome_at(pos.at_y(self.surface_y(pos)?))
+    }
+
+    /// Generates the surface biomes of a 2d area into a cache
+    ///
+    /// The `y` and `size_y` of the range are ignored, as every cell is sampled
+    /// at its own surface height like [`Self::surface_biome()`]. Before 1.18
+    /// and outside of the overworld this is the same as filling a cache at
+    /// [`SEA_LEVEL`]. Since 1.18 the cells are generated one at a time, which
+    /// is slower than filling a cache at a fixed height.
+    ///
+    /// The `y` of the range of the returned cache is set to sea level.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let cache = generator
+    ///     .surface_biomes(Range {
+    ///         scale: Scale::Chunk,
+    ///         x: -16,
+    ///         z: -16,
+    ///         size_x: 32,
+    ///         size_z: 32,
+    ///         y: 0,
+    ///         size_y: 0,
+    ///     })
+    ///     .expect("failed to generate biomes");
+    ///
+    /// assert_eq!(cache.as_vec().len(), 32 * 32);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if approximating the surface heights or generating the
+    /// biomes fails
+    pub fn surface_biomes(&self, range: Range) -> Result<Cache<'_>, GeneratorError> {
+        let range = Range {
+            y: Self::scaled_y(range.scale, SEA_LEVEL),
+            size_y: 0,
+            ..range
+        };
+
+        let mut cache = Cache::new(self, range);
+
+        if !self.has_height_dependent_biomes() {
+            cache.fill_cache()?;
+            return Ok(cache);
+        }
+
+        let noise = SurfaceNoise::new(self);
+        let mut cell = Cache::new(
+            self,
+            Range {
+                size_x: 1,
+                size_z: 1,
+                ..range
+            },
+        );
+
+        // The buffer has room for at least the readable data, so this doesn't
+        // reallocate
+        cache.buffer.clear();
+
+        for z in 0..range.size_z as i32 {
+            for x in 0..range.size_x as i32 {
+                let (cell_x, cell_z) = (range.x + x, range.z + z);
+                let y = self.surface_y_with(
+                    &noise,
+                    range.scale.unscale_coord(cell_x),
+                    range.scale.unscale_coord(cell_z),
+                )?;
+
+                cell.move_cache(cell_x, Self::scaled_y(range.scale, y), cell_z);
+                cell.fill_cache()?;
+                cache.buffer.push(cell.buffer[0]);
+            }
+        }
+
+        Ok(cache)
+    }
+
+    fn surface_y_with(&self, noise: &SurfaceNoise, x: i32, z: i32) -> Result<i32, GeneratorError> {
+        let mut height = [0.0];
+        let mut biome = [BiomeID::none as i32];
+
+        noise.map_heights(
+            self,
+            (block_to_quad(x), block_to_quad(z), 1, 1),
+            &mut height,
+            &mut biome,
+        )?;
+
+        let limits = self.world_limits();
+        Ok((height[0].round() as i32).clamp(limits.min_y, limits.max_y()))
+    }
+
+    /// Scales a block y for a range, which is 1:1 only at [`Scale::Block`]
+    fn scaled_y(scale: Scale, y: i32) -> i32 {
+        scale.scale_y(y)
+    }
+}
@@ -0,0 +1,51 @@
+use super::Generator;
+use tokio::task::JoinError;
+
+impl Generator {
+    /// Runs work with the generator on a blocking thread of the tokio runtime
+    ///
+    /// Generating biomes and structures can take a long time, which would
+    /// block the executor if done in async code. The generator is moved to
+    /// [`tokio::task::spawn_blocking()`] for the work and given back along
+    /// with the result.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let (generator, biome) = generator
+    ///     .run_blocking(|generator| generator.get_biome_at((512, 100, -512)))
+    ///     .await
+    ///     .expect("the task failed");
+    ///
+    /// assert_eq!(biome, Ok(BiomeID::plains));
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if called outside of a tokio runtime
+    ///
+    /// # Errors
+    /// Returns an error if the work panics or the runtime shuts down before
+    /// running it
+    pub async fn run_blocking<T: Send + 'static>(
+        mut self,
+        work: impl FnOnce(&mut Self) -> T + Send + 'static,
+    ) -> Result<(Self, T), JoinError> {
+        tokio::task::spawn_blocking(move || {
+            let result = work(&mut self);
+            (self, result)
+        })
+        .await
+    }
+}
@@ -0,0 +1,58 @@
+use crate::enums::BiomeID;
+
+/// The terrain parameters of a biome before 1.18
+///
+/// Up to 1.17 the terrain height was shaped by the depth and scale of the
+/// surrounding biomes, which are blended together for each column. This is
+/// what [`super::Generator::approx_heights()`] uses for these versions.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct BiomeTerrain {
+    /// The base height of the terrain
+    ///
+    /// Positive depths are above sea level, and oceans have negative depths.
+    pub depth: f64,
+    /// How much the terrain varies around the base height
+    pub scale: f64,
+    /// The lowest surface height where the biome has grass
+    ///
+    /// This is [`None`] for biomes without grass on the surface, like deserts.
+    /// The world spawn is only placed on grass.
+    pub grass_height: Option<i32>,
+}
+
+impl BiomeTerrain {
+    /// Gets the terrain parameters of a biome
+    ///
+    /// Returns [`None`] for biomes which aren't generated in the overworld
+    /// before 1.18, as they have no parameters.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::BiomeID;
+    /// use cubiomes::generator::BiomeTerrain;
+    ///
+    /// let plains = BiomeTerrain::of(BiomeID::plains).expect("plains are generated before 1.18");
+    /// let mountains = BiomeTerrain::of(BiomeID::mountains).expect("mountains are generated before 1.18");
+    ///
+    /// assert!(plains.depth < mountains.depth);
+    /// assert!(plains.scale < mountains.scale);
+    /// ```
+    #[must_use]
+    pub fn of(biome: BiomeID) -> Option<Self> {
+        let mut depth = 0.0;
+        let mut scale = 0.0;
+        let mut grass = 0;
+
+        // SAFETY:
+        // The outputs point to valid values. The function only reads a table.
+        let found = unsafe {
+            cubiomes_sys::getBiomeDepthAndScale(biome as i32, &mut depth, &mut scale, &mut grass)
+        };
+
+        (found != 0).then_some(Self {
+            depth,
+            scale,
+            grass_height: (grass > 0).then_some(grass),
+        })
+    }
+}
@@ -1,7 +1,11 @@
 use super::colors;
-use super::{position::BlockPosition, Generator};
+use super::{
+    position::{BlockPosition, BlockPosition3D, QuadPosition},
+    Aabb, Cache, CachedGenerator, EndTerrain, Generator, Range, Scale, DEEP_DARK_Y,
+};
 use crate::enums::*;
 use crate::structures::StructureRegion;
+use cubiomes_sys::num_traits::FromPrimitive;
 
 #[test]
 #[should_panic]
@@ -31,7 +35,7 @@ fn no_structure_found() {
 #[test]
 fn test_structure_generation() {
     let seed = -5134222539607995087;
-    let minecraft_version = MCVersion::MC_1_21_WD;
+    let minecraft_version = MCVersion::MC_1_20_6;
     let structure_type = StructureType::Outpost;
 
     let pos = StructureRegion::from_block_position(
@@ -58,7 +62,7 @@ fn test_structure_generation() {
 #[test]
 fn test_structure_generation_negative() {
     let seed = -5134222539607995087;
-    let minecraft_version = MCVersion::MC_1_21_WD;
+    let minecraft_version = MCVersion::MC_1_20_6;
     let structure_type = StructureType::Igloo;
 
     let pos = StructureRegion::from_block_position(
@@ -90,3 +94,3375 @@ fn init_biome_colors() {
 
     assert_eq!(colors.len(), 94);
 }
+
+#[test]
+fn biome_stats_sum_up() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: -128,
+        z: -128,
+        size_x: 256,
+        size_z: 256,
+        y: 16,
+        size_y: 0,
+    };
+
+    let mut cache = Cache::new(&generator, range);
+    cache.fill_cache().expect("failed to fill cache");
+
+    let histogram = cache.histogram();
+    let stats = generator
+        .biome_stats(range)
+        .expect("failed to generate stats");
+
+    dbg!(&stats);
+
+    assert_eq!(stats.counts(), &histogram);
+    assert_eq!(stats.total(), 256 * 256);
+
+    let percentages: f64 = stats.iter().map(|(_, _, percentage)| percentage).sum();
+    assert!((percentages - 100.0).abs() < 1e-9);
+    assert_eq!(stats.percentage(BiomeID::the_void), 0.0);
+}
+
+#[test]
+fn biome_positions_match_full_scan() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: -130,
+        z: -70,
+        size_x: 250,
+        size_z: 180,
+        y: 16,
+        size_y: 0,
+    };
+
+    let mut cache = Cache::new(&generator, range);
+    cache.fill_cache().expect("failed to fill cache");
+
+    let histogram = cache.histogram();
+    let (&biome, _) = histogram
+        .iter()
+        .max_by_key(|(_, &count)| count)
+        .expect("range contains biomes");
+
+    let all: Vec<BlockPosition> = cache.positions_of(biome).collect();
+    let found = generator
+        .biome_positions(biome, range)
+        .expect("failed to find biome positions");
+
+    dbg!(biome, all.len(), found.len());
+
+    assert!(!found.is_empty());
+    assert!(found.iter().all(|pos| all.contains(pos)));
+    // The prefilter should only miss a couple of cells on the edges of the biome
+    assert!(found.len() * 100 >= all.len() * 95);
+}
+
+#[test]
+fn diff_biomes_between_versions() {
+    let seed = -5134222539607995087;
+    let old = Generator::new(
+        MCVersion::MC_1_17_1,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let new = Generator::new(
+        MCVersion::MC_1_20_6,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: -32,
+        z: -32,
+        size_x: 64,
+        size_z: 48,
+        y: 16,
+        size_y: 0,
+    };
+
+    let same = new.diff_biomes(&new, range).expect("failed to diff biomes");
+    assert!(same.is_empty());
+    assert_eq!(same.percentage_changed(), 0.0);
+
+    let diff = old.diff_biomes(&new, range).expect("failed to diff biomes");
+    dbg!(diff.percentage_changed());
+    assert!(!diff.is_empty());
+
+    let image = diff.to_image();
+    assert_eq!((image.width(), image.height()), (64, 48));
+
+    for change in diff.changes().iter().take(20) {
+        let pos = diff.block_position(change);
+
+        assert_ne!(change.first, change.second);
+        assert!(old.get_biome_at((pos.x, 64, pos.z)).is_ok());
+        assert_eq!(image.pixel(change.x, change.z), Some([255, 0, 0]));
+    }
+}
+
+#[test]
+fn version_changes_are_grouped_by_transition() {
+    let range = Range {
+        scale: Scale::Quad,
+        x: -32,
+        z: -32,
+        size_x: 64,
+        size_z: 48,
+        y: 16,
+        size_y: 0,
+    };
+
+    let diff = super::BiomeDiff::between_versions(
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+        MCVersion::MC_1_17_1,
+        MCVersion::MC_1_18_2,
+        range,
+    )
+    .expect("failed to diff biomes");
+
+    let transitions = diff.transitions();
+    dbg!(&transitions);
+
+    assert!(!transitions.is_empty());
+    assert_eq!(
+        transitions.iter().map(|t| t.cells).sum::<u64>(),
+        diff.changes().len() as u64
+    );
+    assert!(transitions.windows(2).all(|w| w[0].cells >= w[1].cells));
+
+    for transition in &transitions {
+        assert_ne!(transition.from, transition.to);
+        assert_eq!(
+            diff.changes()
+                .iter()
+                .filter(|c| (c.first, c.second) == (transition.from, transition.to))
+                .count() as u64,
+            transition.cells
+        );
+    }
+}
+
+#[test]
+fn tiled_fill_matches_fill_cache() {
+    use crate::progress::CancellationToken;
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    // Not a multiple of the tile size, so the edges get smaller tiles
+    let range = Range {
+        scale: Scale::Quad,
+        x: -300,
+        z: -20,
+        size_x: 600,
+        size_z: 270,
+        y: 12,
+        size_y: 2,
+    };
+
+    let mut full = Cache::new(&generator, range);
+    full.fill_cache().expect("failed to fill cache");
+
+    let mut reports = Vec::new();
+    let mut tiled = Cache::new(&generator, range);
+    tiled
+        .fill_cache_with_progress(&CancellationToken::new(), |progress| {
+            reports.push(*progress);
+        })
+        .expect("failed to fill cache");
+
+    dbg!(&reports);
+
+    assert_eq!(tiled.as_vec(), full.as_vec());
+    assert_eq!(reports.len(), 6);
+    assert!(reports.windows(2).all(|w| w[0].done < w[1].done));
+
+    let last = reports.last().expect("progress is reported");
+    assert_eq!(last.done, 600 * 270 * 2);
+    assert_eq!(last.fraction(), 1.0);
+    assert_eq!(last.eta(), Some(std::time::Duration::ZERO));
+
+    let cancel = CancellationToken::new();
+    let mut cancelled = Cache::new(&generator, range);
+    let result = cancelled.fill_cache_with_progress(&cancel, |_| cancel.cancel());
+
+    assert_eq!(result, Err(super::error::GeneratorError::Cancelled));
+    assert!(cancelled.as_vec().is_empty());
+}
+
+#[test]
+fn approx_heights_render() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let heights = generator
+        .approx_heights(-100, -50, 200, 100)
+        .expect("failed to map heights");
+    let (min, max) = heights.min_max().expect("the map is not empty");
+
+    dbg!(min, max);
+
+    assert_eq!(heights.heights().len(), 200 * 100);
+    assert!(min < max);
+    assert!(heights.biome_at(0, 0).is_some());
+    assert_eq!(heights.height_at(200, 0), None);
+
+    let grayscale = heights.to_grayscale(min, max);
+    let values: Vec<u8> = grayscale.as_bytes().iter().step_by(3).copied().collect();
+    assert_eq!(values.iter().min(), Some(&0));
+    assert_eq!(values.iter().max(), Some(&255));
+
+    let hillshade = heights.to_hillshade();
+    let shaded = heights.to_shaded_biomes();
+    assert_eq!((hillshade.width(), shaded.height()), (200, 100));
+
+    let nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    assert!(matches!(
+        nether.approx_heights(0, 0, 16, 16),
+        Err(super::error::GeneratorError::ApproxHeightFailure { .. })
+    ));
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn cache_arrays_match_biome_at() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -20,
+            z: 10,
+            size_x: 30,
+            size_z: 20,
+            y: -10,
+            size_y: 8,
+        },
+    );
+
+    assert!(cache.as_array3().is_err());
+    cache.fill_cache().expect("failed to fill cache");
+
+    let layers = cache.as_array3().expect("the cache holds valid biomes");
+    let plane = cache.as_array2().expect("the cache holds valid biomes");
+
+    assert_eq!(layers.dim(), (8, 20, 30));
+    assert_eq!(plane.dim(), (20, 30));
+
+    for ((y, z, x), biome) in layers.indexed_iter() {
+        assert_eq!(cache.biome_at(x as u32, y as u32, z as u32), Ok(*biome));
+    }
+    assert_eq!(plane, layers.index_axis(ndarray::Axis(0), 0));
+}
+
+#[test]
+fn distance_field_matches_brute_force() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Chunk,
+            x: -40,
+            z: -25,
+            size_x: 80,
+            size_z: 50,
+            y: 16,
+            size_y: 0,
+        },
+    );
+    cache.fill_cache().expect("failed to fill cache");
+
+    let target = cache.biome_at(3, 0, 7).expect("the cell is in the cache");
+    let field = cache
+        .distance_field(|biome| biome == target)
+        .expect("the cache holds valid biomes");
+
+    let matching: Vec<(u32, u32)> = (0..50)
+        .flat_map(|z| (0..80).map(move |x| (x, z)))
+        .filter(|&(x, z)| cache.biome_at(x, 0, z) == Ok(target))
+        .collect();
+
+    for z in 0..50 {
+        for x in 0..80 {
+            let expected = matching
+                .iter()
+                .map(|&(mx, mz)| (f64::from(mx) - f64::from(x)).hypot(f64::from(mz) - f64::from(z)))
+                .fold(f64::INFINITY, f64::min);
+
+            let distance = field.distance_at(x, z).expect("a cell matched");
+            assert!((f64::from(distance) - expected).abs() < 1e-4);
+        }
+    }
+
+    assert_eq!(field.distance_at(3, 7), Some(0.0));
+    assert_eq!(
+        field.block_distance_at(70, 40),
+        field
+            .distance_at(70, 40)
+            .map(|distance| f64::from(distance) * 16.0)
+    );
+
+    let none = cache
+        .distance_field(|_| false)
+        .expect("the cache holds valid biomes");
+    assert_eq!(none.distance_at(0, 0), None);
+    assert_eq!(none.max_distance(), None);
+}
+
+#[test]
+fn surface_biomes_match_single_queries() {
+    let new = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let old = Generator::new(
+        MCVersion::MC_1_16_5,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    assert_eq!(old.surface_y((100, 100)), Ok(super::SEA_LEVEL));
+    assert_ne!(new.surface_y((100, 100)), Ok(super::SEA_LEVEL));
+
+    for generator in [&new, &old] {
+        let range = Range {
+            scale: Scale::Block,
+            x: -300,
+            z: 200,
+            size_x: 12,
+            size_z: 9,
+            y: 0,
+            size_y: 0,
+        };
+        let cache = generator
+            .surface_biomes(range)
+            .expect("failed to generate biomes");
+
+        for z in 0..range.size_z {
+            for x in 0..range.size_x {
+                assert_eq!(
+                    cache.biome_at(x, 0, z),
+                    generator.surface_biome((range.x + x as i32, range.z + z as i32))
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn biome_columns_match_get_biome_at() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let full = generator
+        .biome_column(600, 100, -64..320, 1)
+        .expect("failed to generate biomes");
+    let sparse = generator
+        .biome_column(600, 100, -64..320, 16)
+        .expect("failed to generate biomes");
+
+    assert_eq!(full.samples.len(), 384);
+    assert_eq!(sparse.samples.len(), 24);
+
+    for &(y, biome) in &sparse.samples {
+        assert_eq!(generator.get_biome_at((600, y, 100)), Ok(biome));
+        assert!(full.samples.contains(&(y, biome)));
+    }
+
+    let spans = full.spans();
+    dbg!(&spans);
+
+    assert_eq!(spans.first().map(|span| span.min_y), Some(-64));
+    assert_eq!(spans.last().map(|span| span.max_y), Some(319));
+    assert!(spans.windows(2).all(|w| w[0].max_y + 1 == w[1].min_y));
+
+    let deep_dark = full
+        .spans_of(BiomeID::deep_dark)
+        .next()
+        .expect("the column starts in the deep dark");
+    assert_eq!(deep_dark.min_y, -64);
+}
+
+#[test]
+fn voronoi_access_matches_block_scale() {
+    let worlds = [
+        (MCVersion::MC_1_20_6, Dimension::DIM_OVERWORLD),
+        (MCVersion::MC_1_20_6, Dimension::DIM_NETHER),
+        (MCVersion::MC_1_16_5, Dimension::DIM_NETHER),
+    ];
+
+    for (version, dimension) in worlds {
+        let generator = Generator::new(
+            version,
+            -5134222539607995087,
+            dimension,
+            super::GeneratorFlags::empty(),
+        );
+
+        let blocks = Range {
+            scale: Scale::Block,
+            x: -37,
+            z: 291,
+            size_x: 48,
+            size_z: 40,
+            y: 60,
+            size_y: 3,
+        };
+        let mut block_cache = Cache::new(&generator, blocks);
+        block_cache.fill_cache().expect("failed to fill cache");
+
+        let source = blocks
+            .voronoi_source()
+            .expect("the range is at block scale");
+        let mut quad_cache = Cache::new(&generator, source);
+        quad_cache.fill_cache().expect("failed to fill cache");
+
+        for y in 0..blocks.size_y {
+            for z in 0..blocks.size_z {
+                for x in 0..blocks.size_x {
+                    assert_eq!(
+                        quad_cache.voronoi_biome_at((
+                            blocks.x + x as i32,
+                            blocks.y + y as i32,
+                            blocks.z + z as i32
+                        )),
+                        block_cache.biome_at(x, y, z)
+                    );
+                }
+            }
+        }
+
+        assert_eq!(
+            quad_cache.voronoi_biome_at((blocks.x - 100, blocks.y, blocks.z)),
+            Err(super::error::GeneratorError::IndexOutOfBounds)
+        );
+        assert_eq!(
+            block_cache.voronoi_biome_at((blocks.x, blocks.y, blocks.z)),
+            Err(super::error::GeneratorError::NoVoronoiAccess)
+        );
+    }
+
+    let old = Generator::new(
+        MCVersion::MC_1_14_4,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(old.voronoi_cell((0, 64, 0)), None);
+
+    // The layered overworld is zoomed with planar voronoi noise
+    let layered = Generator::new(
+        MCVersion::MC_1_16_5,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let mut cache = Cache::new(
+        &layered,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 4,
+            size_z: 4,
+            y: 15,
+            size_y: 2,
+        },
+    );
+    cache.fill_cache().expect("failed to fill cache");
+
+    assert_eq!(
+        cache.voronoi_biome_at((4, 64, 4)),
+        Err(super::error::GeneratorError::NoVoronoiAccess)
+    );
+}
+
+#[test]
+fn voronoi_sha_matches_generator() {
+    let seed = -5134222539607995087;
+
+    for (version, sha) in [
+        (MCVersion::MC_1_20_6, super::voronoi_sha(seed)),
+        (MCVersion::MC_1_16_5, super::voronoi_sha(seed)),
+        (MCVersion::MC_1_14_4, 0),
+    ] {
+        let generator = Generator::new(
+            version,
+            seed,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::empty(),
+        );
+
+        assert_eq!(generator.voronoi_sha(), sha);
+    }
+
+    assert_ne!(super::voronoi_sha(seed), super::voronoi_sha(seed + 1));
+}
+
+#[test]
+fn legacy_terrain_parameters() {
+    let plains = super::BiomeTerrain::of(BiomeID::plains).expect("plains have terrain");
+    let desert = super::BiomeTerrain::of(BiomeID::desert).expect("deserts have terrain");
+    let deep_ocean =
+        super::BiomeTerrain::of(BiomeID::deep_ocean).expect("deep oceans have terrain");
+
+    dbg!(plains, desert, deep_ocean);
+
+    assert_eq!(plains.depth, 0.125);
+    assert_eq!(plains.grass_height, Some(62));
+    assert_eq!(desert.grass_height, None);
+    assert!(deep_ocean.depth < plains.depth);
+
+    // Biomes added in 1.18 and the other dimensions have no parameters
+    assert_eq!(super::BiomeTerrain::of(BiomeID::cherry_grove), None);
+    assert_eq!(super::BiomeTerrain::of(BiomeID::nether_wastes), None);
+}
+
+#[test]
+fn surface_noise_is_reusable() {
+    let generator = Generator::new(
+        MCVersion::MC_1_16_5,
+        -380434930381432806,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let noise = super::SurfaceNoise::new(&generator);
+
+    for (x, z) in [(0, 0), (-40, 96)] {
+        assert_eq!(
+            noise.approx_heights(&generator, x, z, 16, 16),
+            generator.approx_heights(x, z, 16, 16)
+        );
+    }
+
+    for (x, y, z) in [(0, 8, 0), (13, 2, -7), (-200, 30, 51)] {
+        let density = noise.sample(x, y, z);
+        dbg!(density);
+
+        assert!(density.is_finite());
+        assert!((noise.sample_between(x, y, z, -1e9, 1e9) - density).abs() < 1e-6);
+        assert!(noise.sample_between(x, y, z, density + 1.0, density + 2.0) <= density + 1.0);
+    }
+}
+
+#[test]
+fn end_surface_heights() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_END,
+        super::GeneratorFlags::empty(),
+    );
+
+    let center = generator
+        .end_surface_height((0, 0))
+        .expect("failed to get height");
+    dbg!(center);
+    assert!(center.is_some());
+
+    // The gap between the main island and the outer islands is void
+    assert_eq!(generator.end_surface_height((700, 0)), Ok(None));
+
+    let range = Range {
+        scale: Scale::Block,
+        x: -8,
+        z: -8,
+        size_x: 16,
+        size_z: 16,
+        y: 64,
+        size_y: 0,
+    };
+    let heights = generator
+        .end_surface_heights(range)
+        .expect("failed to map heights");
+
+    assert_eq!(heights.scale(), Scale::Block);
+    assert_eq!(heights.height_at(8, 8).map(|height| height as i32), center);
+    assert_eq!(heights.biome_at(8, 8), Some(BiomeID::the_end));
+
+    let overworld = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        overworld.end_surface_height((0, 0)),
+        Err(super::error::GeneratorError::UnsupportedDimension(
+            Dimension::DIM_OVERWORLD
+        ))
+    );
+
+    assert_eq!(
+        generator.end_surface_heights(Range {
+            scale: Scale::Chunk,
+            ..range
+        }),
+        Err(super::error::GeneratorError::UnsupportedScale(Scale::Chunk))
+    );
+}
+
+#[test]
+fn world_limits_per_version() {
+    let limits = |version, dimension| super::WorldLimits::of(version, dimension);
+
+    let old = limits(MCVersion::MC_1_1, Dimension::DIM_OVERWORLD);
+    let legacy = limits(MCVersion::MC_1_17_1, Dimension::DIM_OVERWORLD);
+    let modern = limits(MCVersion::MC_1_18_2, Dimension::DIM_OVERWORLD);
+    let nether = limits(MCVersion::MC_1_20_6, Dimension::DIM_NETHER);
+    dbg!(old, legacy, modern, nether);
+
+    assert_eq!(old.y_range(), 0..128);
+    assert_eq!(legacy.y_range(), 0..256);
+    assert_eq!(modern.y_range(), -64..320);
+    assert_eq!(nether.logical_height, 128);
+    assert_eq!(nether.sea_level, 32);
+
+    let generator = Generator::new(
+        MCVersion::MC_1_17_1,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(generator.world_limits(), legacy);
+    assert_eq!(
+        generator.biome_column(0, 0, -64..320, 16),
+        Err(super::error::GeneratorError::YOutOfBounds(-64))
+    );
+    assert!(generator.biome_column(0, 0, legacy.y_range(), 16).is_ok());
+}
+
+#[test]
+fn nether_queries_stay_below_the_roof() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    let limits = generator.world_limits();
+
+    assert_eq!(limits.logical_y_range(), 0..128);
+    assert_eq!(limits.clamp_y(300), 255);
+    assert_eq!(limits.clamp_y(-5), 0);
+
+    for y in [0, 32, 127] {
+        assert_eq!(
+            generator.nether_biome_at((100, y, -40)),
+            generator.get_biome_at((100, y, -40))
+        );
+    }
+    assert_eq!(
+        generator.nether_biome_at((100, 128, -40)),
+        Err(super::error::GeneratorError::YOutOfBounds(128))
+    );
+    assert_eq!(
+        generator.nether_biome_at((100, -1, -40)),
+        Err(super::error::GeneratorError::YOutOfBounds(-1))
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: 0,
+        z: 0,
+        size_x: 4,
+        size_z: 4,
+        y: 40,
+        size_y: 10,
+    };
+    let clamp = |range| {
+        generator
+            .clamp_nether_range(range)
+            .expect("failed to clamp")
+    };
+
+    assert_eq!(
+        clamp(range),
+        Range {
+            y: 31,
+            size_y: 1,
+            ..range
+        }
+    );
+    assert_eq!(
+        clamp(Range {
+            y: -8,
+            size_y: 16,
+            ..range
+        }),
+        Range {
+            y: 0,
+            size_y: 8,
+            ..range
+        }
+    );
+    assert_eq!(
+        clamp(Range {
+            y: 10,
+            size_y: 0,
+            ..range
+        }),
+        Range {
+            y: 10,
+            size_y: 0,
+            ..range
+        }
+    );
+    assert_eq!(
+        clamp(Range {
+            scale: Scale::Block,
+            y: 100,
+            size_y: 64,
+            ..range
+        }),
+        Range {
+            scale: Scale::Block,
+            y: 100,
+            size_y: 28,
+            ..range
+        }
+    );
+
+    let clamped = clamp(Range {
+        y: 20,
+        size_y: 40,
+        ..range
+    });
+    let mut cache = Cache::new(&generator, clamped);
+    cache.fill_cache().expect("failed to fill cache");
+    assert_eq!(cache.as_vec().len(), 4 * 4 * 12);
+
+    let overworld = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        overworld.nether_biome_at((0, 64, 0)),
+        Err(super::error::GeneratorError::UnsupportedDimension(
+            Dimension::DIM_OVERWORLD
+        ))
+    );
+}
+
+#[test]
+fn nether_biome_coverage_and_patches() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+
+    let coverage = generator
+        .nether_biome_coverage(400, 32)
+        .expect("failed to collect the coverage");
+    dbg!(&coverage);
+
+    // The cells within the radius make up a circle of 1:4 cells
+    let circle = std::f64::consts::PI * 100.0 * 100.0;
+    assert!((coverage.total() as f64 - circle).abs() < circle * 0.02);
+    assert!(coverage.counts().len() >= 3);
+    assert!(coverage.counts().keys().all(|biome| matches!(
+        biome,
+        BiomeID::nether_wastes
+            | BiomeID::soul_sand_valley
+            | BiomeID::crimson_forest
+            | BiomeID::warped_forest
+            | BiomeID::basalt_deltas
+    )));
+
+    let origin = BlockPosition::new(0, 0);
+    let nearest = generator
+        .nearest_nether_patch(BiomeID::basalt_deltas, origin, 1000, 5000, 32)
+        .expect("failed to find patches")
+        .expect("there are basalt deltas near the center");
+    dbg!(nearest);
+    assert_eq!(nearest.biome, BiomeID::basalt_deltas);
+    assert!(nearest.area >= 5000);
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -250,
+            z: -250,
+            size_x: 501,
+            size_z: 501,
+            y: 8,
+            size_y: 0,
+        },
+    );
+    cache.fill_cache().expect("Failed to fill cache");
+    let closest = cache
+        .patches_where(|biome| biome == BiomeID::basalt_deltas)
+        .expect("Failed to find patches")
+        .into_iter()
+        .filter(|patch| patch.area >= 5000)
+        .map(|patch| patch.centroid_x.hypot(patch.centroid_z))
+        .fold(f64::INFINITY, f64::min);
+    assert_eq!(nearest.centroid_x.hypot(nearest.centroid_z), closest);
+
+    assert_eq!(
+        generator.nearest_nether_patch(BiomeID::plains, origin, 200, 0, 32),
+        Ok(None)
+    );
+    assert_eq!(
+        generator.nether_biome_coverage(100, 200),
+        Err(super::error::GeneratorError::YOutOfBounds(200))
+    );
+
+    let old = Generator::new(
+        MCVersion::MC_1_15_2,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    let old_coverage = old
+        .nether_biome_coverage(100, 32)
+        .expect("failed to collect the coverage");
+    assert_eq!(old_coverage.percentage(BiomeID::nether_wastes), 100.0);
+}
+
+#[test]
+fn nearest_nether_biomes_cover_every_biome() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(120, -80);
+
+    let biomes = generator
+        .nearest_nether_biomes(origin, 1500, 32)
+        .expect("failed to find the nether biomes");
+    dbg!(&biomes);
+
+    assert!(biomes.is_complete());
+    assert_eq!(biomes.found().len(), 5);
+
+    for (&biome, location) in biomes.found() {
+        assert!(location.distance <= 1500.0);
+        assert_eq!(
+            location.overworld_pos,
+            BlockPosition::new(location.pos.x * 8, location.pos.z * 8)
+        );
+
+        let mut cell = Cache::new(
+            &generator,
+            Range {
+                scale: Scale::Quad,
+                x: location.pos.x >> 2,
+                z: location.pos.z >> 2,
+                size_x: 1,
+                size_z: 1,
+                y: 32 >> 2,
+                size_y: 0,
+            },
+        );
+        cell.fill_cache().expect("Failed to fill cache");
+        assert_eq!(cell.biome_at(0, 0, 0), Ok(biome));
+    }
+
+    // A smaller search finds the same closest cells for the biomes near
+    // the origin
+    let near = generator
+        .nearest_nether_biomes(origin, 200, 32)
+        .expect("failed to find the nether biomes");
+    for (&biome, location) in near.found() {
+        assert_eq!(biomes.location_of(biome), Some(*location));
+    }
+
+    let old = Generator::new(
+        MCVersion::MC_1_15_2,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    let old_biomes = old
+        .nearest_nether_biomes(origin, 100, 32)
+        .expect("failed to find the nether biomes");
+    assert!(old_biomes.is_complete());
+    assert_eq!(
+        old_biomes
+            .location_of(BiomeID::nether_wastes)
+            .map(|location| location.distance < 4.0),
+        Some(true)
+    );
+}
+
+#[test]
+fn raw_generator_round_trip() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let expected = generator
+        .get_biome_at((100, 64, -300))
+        .expect("The biome should generate");
+
+    let raw = generator.into_raw();
+
+    // SAFETY: The pointer comes from into_raw and is not used afterwards
+    let mut generator = unsafe { Generator::from_raw(raw) };
+
+    assert_eq!(generator.get_biome_at((100, 64, -300)), Ok(expected));
+    assert_eq!(
+        generator.as_raw().seed,
+        (-5134222539607995087_i64).cast_unsigned()
+    );
+
+    // SAFETY: Applying a seed keeps the generator set up for its version
+    unsafe {
+        cubiomes_sys::applySeed(generator.as_raw_mut(), Dimension::DIM_NETHER as i32, 42);
+    }
+
+    assert_eq!(generator.seed(), 42);
+    assert_eq!(generator.dimension(), Dimension::DIM_NETHER);
+}
+
+#[test]
+fn raw_cache_fill_matches_fill_cache() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let range = Range {
+        scale: Scale::Quad,
+        x: -40,
+        z: 12,
+        size_x: 32,
+        size_z: 24,
+        y: 16,
+        size_y: 0,
+    };
+
+    let mut cache = Cache::new(&generator, range);
+    cache.fill_cache().expect("The cache should fill");
+
+    let mut raw_cache = Cache::new(&generator, range);
+    let raw_range = cubiomes_sys::Range::try_from(*raw_cache.range()).expect("The range is valid");
+
+    // SAFETY: genBiomes writes the range, which fits in the minimum cache
+    // size the cache was allocated with
+    unsafe {
+        let result = cubiomes_sys::genBiomes(
+            std::ptr::from_ref(raw_cache.generator().as_raw()),
+            raw_cache.as_mut_ptr(),
+            raw_range,
+        );
+        assert_eq!(result, 0);
+
+        raw_cache.assume_filled();
+    }
+
+    assert!(raw_cache.capacity() >= raw_cache.as_vec().len());
+    assert_eq!(cache.as_vec(), raw_cache.as_vec());
+}
+
+#[test]
+fn cloned_caches_can_be_filled_again() {
+    // 1:1 in 1.16 needs a buffer larger than the readable cells
+    let generator = Generator::new(
+        MCVersion::MC_1_16_5,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let mut cache = Cache::new(&generator, Range::plane(Scale::Block, 0, 0, 64, 64, 64));
+    cache.fill_cache().expect("failed to fill cache");
+    assert!(cache.capacity() > cache.as_vec().len());
+
+    let mut cloned = cache.clone();
+    assert_eq!(cloned.capacity(), cache.capacity());
+    cloned.fill_cache().expect("failed to fill the clone");
+    assert_eq!(cloned.as_vec(), cache.as_vec());
+
+    // Filling grows a buffer which is too small for the range itself
+    cloned.buffer = Vec::new();
+    cloned.fill_cache().expect("failed to fill the clone");
+    assert_eq!(cloned.capacity(), cache.capacity());
+    assert_eq!(cloned.as_vec(), cache.as_vec());
+}
+
+#[test]
+fn cloned_generators_are_independent() {
+    let seed = -5134222539607995087;
+    let range = Range {
+        scale: Scale::Quad,
+        x: -64,
+        z: -64,
+        size_x: 32,
+        size_z: 32,
+        y: 16,
+        size_y: 1,
+    };
+
+    for (version, dimension, flags) in [
+        (
+            MCVersion::MC_B1_7,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::empty(),
+        ),
+        (
+            MCVersion::MC_1_12_2,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::LargeBiomes,
+        ),
+        (
+            MCVersion::MC_1_16_5,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::ForceOceanVariants,
+        ),
+        (
+            MCVersion::MC_1_16_5,
+            Dimension::DIM_NETHER,
+            super::GeneratorFlags::empty(),
+        ),
+        (
+            MCVersion::MC_1_20_6,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::empty(),
+        ),
+        (
+            MCVersion::MC_1_20_6,
+            Dimension::DIM_NETHER,
+            super::GeneratorFlags::empty(),
+        ),
+        (
+            MCVersion::MC_1_20_6,
+            Dimension::DIM_END,
+            super::GeneratorFlags::empty(),
+        ),
+    ] {
+        let fresh = Generator::new(version, seed, dimension, flags);
+        let mut expected = Cache::new(&fresh, range);
+        expected.fill_cache().expect("The cache should fill");
+
+        let mut original = Generator::new(version, seed, dimension, flags);
+        let clone = original.clone();
+
+        // Reseeding changes the noises and layers of the original, which the
+        // clone would see if it still pointed into the original
+        original.apply_seed(dimension, 42);
+        drop(original);
+
+        let mut cache = Cache::new(&clone, range);
+        cache.fill_cache().expect("The cache should fill");
+
+        assert_eq!(
+            expected.as_vec(),
+            cache.as_vec(),
+            "{version:?} in {dimension:?}"
+        );
+        assert_eq!(clone.seed(), seed);
+    }
+}
+
+#[test]
+fn getters_read_back_configuration() {
+    let flags = super::GeneratorFlags::LargeBiomes | super::GeneratorFlags::NoBetaOcean;
+    let mut generator = Generator::new(MCVersion::MC_1_16_5, 1234, Dimension::DIM_END, flags);
+
+    assert_eq!(generator.version(), MCVersion::MC_1_16_5);
+    assert_eq!(generator.minecraft_version(), MCVersion::MC_1_16_5);
+    assert_eq!(generator.seed(), 1234);
+    assert_eq!(generator.dimension(), Dimension::DIM_END);
+    assert_eq!(generator.flags(), flags);
+
+    generator.apply_seed(Dimension::DIM_NETHER, -42);
+
+    assert_eq!(generator.seed(), -42);
+    assert_eq!(generator.dimension(), Dimension::DIM_NETHER);
+    assert_eq!(generator.flags(), flags);
+}
+
+#[test]
+fn generators_compare_by_configuration() {
+    use std::collections::HashSet;
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -42,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let other_seed = Generator::new(
+        MCVersion::MC_1_20_6,
+        42,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    assert_eq!(generator, generator.clone());
+    assert_ne!(generator, other_seed);
+
+    let copy = generator.clone();
+    let set: HashSet<_> = [&generator, &copy, &other_seed].into_iter().collect();
+    assert_eq!(set.len(), 2);
+
+    let debug = dbg!(format!("{generator:?}"));
+    assert!(debug.contains("MC_1_20_6"));
+    assert!(debug.contains("-42"));
+    assert!(debug.contains("DIM_OVERWORLD"));
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: 0,
+        z: 0,
+        size_x: 4,
+        size_z: 4,
+        y: 16,
+        size_y: 1,
+    };
+    let mut cache = Cache::new(&generator, range);
+    let mut other_cache = Cache::new(&other_seed, range);
+    cache.fill_cache().expect("The cache should fill");
+    other_cache.fill_cache().expect("The cache should fill");
+
+    assert_eq!(cache, cache.clone());
+    assert_ne!(cache, other_cache);
+}
+
+#[test]
+fn cache_scans_match_naive_loops() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    // The size isn't a multiple of the chunks the scans work in
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -300,
+            z: 100,
+            size_x: 237,
+            size_z: 113,
+            y: 16,
+            size_y: 1,
+        },
+    );
+    cache.fill_cache().expect("The cache should fill");
+
+    for biome in [
+        BiomeID::plains,
+        BiomeID::ocean,
+        BiomeID::deep_ocean,
+        BiomeID::forest,
+        BiomeID::mushroom_fields,
+    ] {
+        let raw = biome as i32;
+        let cells = cache.as_vec();
+
+        assert_eq!(
+            cache.count(biome),
+            cells.iter().filter(|&&cell| cell == raw).count(),
+            "{biome:?}"
+        );
+        assert_eq!(
+            cache.find_first(biome),
+            cells.iter().position(|&cell| cell == raw),
+            "{biome:?}"
+        );
+        assert_eq!(
+            cache.mask(biome),
+            cells.iter().map(|&cell| cell == raw).collect::<Vec<_>>(),
+            "{biome:?}"
+        );
+    }
+
+    assert_eq!(cache.count(BiomeID::the_end), 0);
+    assert_eq!(cache.find_first(BiomeID::the_end), None);
+}
+
+#[test]
+fn cache_predicates_match_naive_loops() {
+    let generator = Generator::new(
+        MCVersion::MC_1_18_2,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -128,
+            z: -128,
+            size_x: 256,
+            size_z: 256,
+            y: 16,
+            size_y: 1,
+        },
+    );
+    cache.fill_cache().expect("The cache should fill");
+
+    let is_ocean = |biome| {
+        matches!(
+            biome,
+            BiomeID::ocean | BiomeID::deep_ocean | BiomeID::lukewarm_ocean
+        )
+    };
+    let oceans = cache
+        .as_vec()
+        .iter()
+        .filter(|&&cell| {
+            cell == BiomeID::ocean as i32
+                || cell == BiomeID::deep_ocean as i32
+                || cell == BiomeID::lukewarm_ocean as i32
+        })
+        .count();
+
+    assert_eq!(dbg!(cache.count_where(is_ocean)), oceans);
+    assert_eq!(cache.any(is_ocean), oceans > 0);
+    assert_eq!(cache.all(is_ocean), oceans == cache.as_vec().len());
+
+    assert!(cache.all(|_| true));
+    assert!(!cache.any(|_| false));
+    assert_eq!(cache.count_where(|_| true), cache.as_vec().len());
+}
+
+#[test]
+fn histogram_skips_unknown_biomes() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 64,
+            size_z: 64,
+            y: 16,
+            size_y: 1,
+        },
+    );
+    cache.fill_cache().expect("The cache should fill");
+
+    let mut expected = cache.histogram();
+    for &cell in &cache.as_vec()[..2] {
+        let biome = BiomeID::from_i32(cell).expect("The biome is valid");
+        let count = expected.get_mut(&biome).expect("The biome was counted");
+
+        *count -= 1;
+        if *count == 0 {
+            expected.remove(&biome);
+        }
+    }
+
+    // SAFETY: The cache is filled, so the first two cells are inside of its
+    // capacity
+    unsafe {
+        *cache.as_mut_ptr() = -1;
+        *cache.as_mut_ptr().add(1) = 9999;
+    }
+
+    let histogram = dbg!(cache.histogram());
+
+    assert_eq!(histogram, expected);
+    assert_eq!(histogram.values().sum::<u64>(), 64 * 64 - 2);
+}
+
+#[test]
+fn biome_patches_are_connected_regions() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -2,
+            z: 5,
+            size_x: 4,
+            size_z: 3,
+            y: 16,
+            size_y: 1,
+        },
+    );
+    cache.fill_cache().expect("The cache should fill");
+
+    let (p, o) = (BiomeID::plains as i32, BiomeID::ocean as i32);
+    #[rustfmt::skip]
+    let pattern = [
+        p, p, o, p,
+        o, p, o, p,
+        o, o, o, p,
+    ];
+
+    // SAFETY: The filled cache holds exactly the cells of the pattern
+    unsafe {
+        std::ptr::copy_nonoverlapping(pattern.as_ptr(), cache.as_mut_ptr(), pattern.len());
+    }
+
+    let patches = dbg!(cache.biome_patches().expect("The cache holds valid biomes"));
+
+    assert_eq!(patches.len(), 3);
+    assert_eq!(
+        patches
+            .iter()
+            .map(|patch| (patch.biome, patch.cells))
+            .collect::<Vec<_>>(),
+        [
+            (BiomeID::plains, 3),
+            (BiomeID::ocean, 6),
+            (BiomeID::plains, 3)
+        ]
+    );
+
+    let first = patches[0];
+    assert_eq!(first.min, BlockPosition::new(-8, 20));
+    assert_eq!(first.max, BlockPosition::new(-1, 27));
+    assert_eq!((first.width(), first.depth()), (8, 8));
+    assert_eq!(first.area, 3 * 16);
+    assert!((first.centroid_x - (-8.0 + 3.5 / 3.0 * 4.0)).abs() < 1e-9);
+    assert!((first.centroid_z - (20.0 + 2.5 / 3.0 * 4.0)).abs() < 1e-9);
+
+    let last = patches[2];
+    assert_eq!(last.min, BlockPosition::new(4, 20));
+    assert_eq!(last.max, BlockPosition::new(7, 31));
+
+    // Grouping every biome gives a single patch over the whole cache
+    let all = cache
+        .patches_where(|_| true)
+        .expect("The cache holds valid biomes");
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].cells, 12);
+    assert_eq!(
+        (all[0].min, all[0].max),
+        (BlockPosition::new(-8, 20), BlockPosition::new(7, 31))
+    );
+
+    let plains = cache
+        .patches_where(|biome| biome == BiomeID::plains)
+        .expect("The cache holds valid biomes");
+    assert_eq!(plains.len(), 2);
+}
+
+#[test]
+fn biome_patches_cover_the_cache() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -128,
+            z: -128,
+            size_x: 256,
+            size_z: 256,
+            y: 16,
+            size_y: 1,
+        },
+    );
+    cache.fill_cache().expect("The cache should fill");
+
+    let patches = cache.biome_patches().expect("The cache holds valid biomes");
+    dbg!(patches.len());
+
+    let mut counts = std::collections::BTreeMap::new();
+    for patch in &patches {
+        *counts.entry(patch.biome).or_default() += patch.cells;
+    }
+
+    assert_eq!(counts, cache.histogram());
+}
+
+#[test]
+fn aabb_range_conversions() {
+    let range = Range {
+        scale: Scale::Quad,
+        x: -3,
+        z: 7,
+        size_x: 10,
+        size_z: 4,
+        y: 15,
+        size_y: 2,
+    };
+
+    let area = Aabb::from(range);
+    assert_eq!(area, Aabb::new((-12, 60, 28), (27, 67, 43)));
+    assert_eq!(area.to_range(Scale::Quad), range);
+
+    // Covering an area at a coarser scale rounds outwards
+    let chunks = area.to_range(Scale::Chunk);
+    assert_eq!((chunks.x, chunks.z), (-1, 1));
+    assert_eq!((chunks.size_x, chunks.size_z), (3, 2));
+    assert_eq!((chunks.y, chunks.size_y), (15, 2));
+
+    // Blocks keep the y coordinate unscaled
+    let blocks = Aabb::from(Range {
+        scale: Scale::Block,
+        size_y: 0,
+        ..range
+    });
+    assert_eq!(blocks, Aabb::new((-3, 15, 7), (6, 15, 10)));
+}
+
+#[test]
+fn aabb_expansion_and_intersection() {
+    let area = Aabb::new((0, 0, 0), (9, 4, 9));
+
+    assert_eq!(area.expand(2), Aabb::new((-2, -2, -2), (11, 6, 11)));
+    assert_eq!(area.expand(-2), Aabb::new((2, 2, 2), (7, 2, 7)));
+    assert_eq!(area.expand(-100), Aabb::new((4, 2, 4), (4, 2, 4)));
+
+    let other = Aabb::new((9, 4, 9), (20, 20, 20));
+    assert!(area.intersects(&other));
+    assert_eq!(
+        area.intersection(&other),
+        Some(Aabb::new((9, 4, 9), (9, 4, 9)))
+    );
+    assert!(!area.intersects(&other.expand(-1)));
+    assert_eq!(area.union(&other), Aabb::new((0, 0, 0), (20, 20, 20)));
+}
+
+#[test]
+fn spiral_covers_square_ring_by_ring() {
+    let origin = BlockPosition::new(-40, 100);
+    let cells: Vec<_> = super::spiral(origin, 8, 30).collect();
+
+    // 30 blocks at a step of 8 is 3 rings around the origin
+    assert_eq!(cells.len(), 7 * 7);
+    assert_eq!(super::spiral(origin, 8, 30).size_hint(), (49, Some(49)));
+    assert_eq!(cells[0].pos, origin);
+
+    let unique: std::collections::HashSet<_> = cells.iter().map(|cell| cell.pos).collect();
+    assert_eq!(unique.len(), cells.len());
+
+    for pair in cells.windows(2) {
+        assert!(pair[0].ring <= pair[1].ring);
+    }
+
+    for cell in &cells {
+        let dx = (cell.pos.x - origin.x) / 8;
+        let dz = (cell.pos.z - origin.z) / 8;
+
+        assert_eq!((cell.pos.x - origin.x) % 8, 0);
+        assert_eq!(dx.unsigned_abs().max(dz.unsigned_abs()), cell.ring);
+    }
+
+    let mut partial = super::spiral(origin, 8, 30);
+    partial.by_ref().take(10).for_each(drop);
+    assert_eq!(partial.size_hint(), (39, Some(39)));
+    assert_eq!(partial.count(), 39);
+
+    assert_eq!(
+        dbg!(super::spiral(origin, 1, 0).collect::<Vec<_>>()).len(),
+        1
+    );
+}
+
+#[test]
+fn end_terrain_checks_match_heights() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_END,
+        super::GeneratorFlags::empty(),
+    );
+
+    assert_eq!(generator.is_end_chunk_empty(0, 0), Ok(false));
+
+    let (mut empty, mut viable) = (0, 0);
+    for chunk_x in 40..120 {
+        for chunk_z in (-40..40).step_by(8) {
+            let heights = generator
+                .end_surface_heights(Range {
+                    scale: Scale::Block,
+                    x: chunk_x * 16,
+                    z: chunk_z * 16,
+                    size_x: 16,
+                    size_z: 16,
+                    y: 64,
+                    size_y: 0,
+                })
+                .expect("failed to map heights");
+
+            if generator
+                .is_end_chunk_empty(chunk_x, chunk_z)
+                .expect("failed to check chunk")
+            {
+                empty += 1;
+
+                // Cubiomes doesn't count small end islands just touching the
+                // border of the chunk, which the height map does include
+                assert!((1..15)
+                    .flat_map(|z| (1..15).map(move |x| (x, z)))
+                    .all(|(x, z)| heights.height_at(x, z) == Some(0.0)));
+            }
+
+            let pos = BlockPosition::new(chunk_x * 16, chunk_z * 16);
+            if let Some(height) = generator
+                .end_city_terrain_height(pos)
+                .expect("failed to check terrain")
+            {
+                viable += 1;
+                assert!(height >= 60);
+                assert!(heights.height_at(7, 7).map(|center| center as i32) >= Some(height));
+            }
+        }
+    }
+    dbg!(empty, viable);
+
+    assert!(empty > 0);
+    assert!(viable > 0);
+
+    let overworld = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        overworld.is_end_chunk_empty(0, 0),
+        Err(super::error::GeneratorError::UnsupportedDimension(
+            Dimension::DIM_OVERWORLD
+        ))
+    );
+}
+
+#[test]
+fn end_terrains_tell_islands_from_void() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_END,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: 150,
+        z: -100,
+        size_x: 200,
+        size_z: 200,
+        y: 64,
+        size_y: 0,
+    };
+    let terrains = generator
+        .end_terrains(range)
+        .expect("failed to classify the terrain");
+    let heights = generator
+        .end_surface_heights(range)
+        .expect("failed to map heights");
+
+    assert_eq!(terrains.len(), heights.heights().len());
+    for (terrain, &height) in terrains.iter().zip(heights.heights()) {
+        assert_eq!(terrain.is_solid(), height > 0.0);
+    }
+
+    let kinds: std::collections::BTreeSet<_> = terrains.iter().copied().collect();
+    dbg!(&kinds);
+    assert!(kinds.contains(&EndTerrain::Void));
+    assert!(kinds.contains(&EndTerrain::SmallIsland));
+    assert!(kinds.contains(&EndTerrain::Highlands));
+
+    for (x, z) in [(0, 0), (700, 0), (-1500, 1200), (2100, 2100)] {
+        let cell = generator
+            .end_terrains(Range {
+                scale: Scale::Block,
+                x,
+                z,
+                size_x: 1,
+                size_z: 1,
+                y: 0,
+                size_y: 0,
+            })
+            .expect("failed to classify the terrain");
+        assert_eq!(generator.end_terrain_at((x, z)), Ok(cell[0]));
+    }
+    assert_eq!(generator.end_terrain_at((0, 0)), Ok(EndTerrain::MainIsland));
+}
+
+#[test]
+fn mushroom_islands_match_full_scan() {
+    let is_mushroom = |biome| {
+        matches!(
+            biome,
+            BiomeID::mushroom_fields | BiomeID::mushroom_field_shore
+        )
+    };
+
+    for (version, seed, origin, radius) in [
+        (
+            MCVersion::MC_1_20_6,
+            -5134222539607995087,
+            BlockPosition::new(2800, 1000),
+            800,
+        ),
+        (
+            MCVersion::MC_1_16_5,
+            2151901553968352745,
+            BlockPosition::new(-740, -4900),
+            600,
+        ),
+    ] {
+        let generator = Generator::new(
+            version,
+            seed,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::empty(),
+        );
+
+        let islands = generator
+            .mushroom_islands(origin, radius)
+            .expect("The biomes should generate");
+        dbg!(version, islands.len());
+
+        // Every island found by scanning the whole area at 1:4
+        let reach = radius as i32 + 512;
+        let mut cache = Cache::new(
+            &generator,
+            Range {
+                scale: Scale::Quad,
+                x: (origin.x - reach) / 4,
+                z: (origin.z - reach) / 4,
+                size_x: reach as u32 / 2,
+                size_z: reach as u32 / 2,
+                y: 15,
+                size_y: 0,
+            },
+        );
+        cache.fill_cache().expect("The cache should fill");
+
+        let key = |patch: &super::BiomePatch| (patch.min, patch.max, patch.cells);
+        let mut expected: Vec<_> = cache
+            .patches_where(is_mushroom)
+            .expect("The cache holds valid biomes")
+            .iter()
+            .filter(|patch| {
+                let center = BlockPosition::new(
+                    patch.centroid_x.floor() as i32,
+                    patch.centroid_z.floor() as i32,
+                );
+                let (dx, dz) = (
+                    f64::from(center.x - origin.x),
+                    f64::from(center.z - origin.z),
+                );
+                (dx * dx + dz * dz).sqrt() <= f64::from(radius)
+            })
+            .map(key)
+            .collect();
+        let mut found: Vec<_> = islands.iter().map(|island| key(&island.patch)).collect();
+
+        expected.sort();
+        found.sort();
+
+        // Islands smaller than a cell of the coarse scan may be missed, but
+        // nothing is found which isn't there
+        assert!(found.iter().all(|island| expected.contains(island)));
+        assert!(expected
+            .iter()
+            .filter(|(_, _, cells)| cells * 16 >= 64 * 64)
+            .all(|island| found.contains(island)));
+        assert!(islands.iter().any(|island| island.patch.area >= 200 * 200));
+
+        assert!(islands
+            .windows(2)
+            .all(|pair| pair[0].distance <= pair[1].distance));
+        assert!(islands
+            .iter()
+            .all(|island| island.distance <= f64::from(radius)));
+    }
+
+    let nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        0,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        nether.mushroom_islands(BlockPosition::new(0, 0), 100),
+        Err(super::error::GeneratorError::UnsupportedDimension(
+            Dimension::DIM_NETHER
+        ))
+    );
+}
+
+#[test]
+fn adventuring_time_finds_nearby_biomes() {
+    let counts = [
+        (MCVersion::MC_1_18_2, 50),
+        (MCVersion::MC_1_19_2, 52),
+        (MCVersion::MC_1_20_6, 53),
+        #[cfg(feature = "unstable-versions")]
+        (MCVersion::MC_1_21_3, 53),
+        #[cfg(feature = "unstable-versions")]
+        (MCVersion::MC_1_21_WD, 54),
+    ];
+    for (version, count) in counts {
+        let biomes = super::AdventuringTime::required_biomes(version)
+            .expect("Versions since 1.18 are supported");
+        assert_eq!(dbg!(&biomes).len(), count);
+        assert!(biomes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+    assert!(super::AdventuringTime::required_biomes(MCVersion::MC_1_17_1).is_none());
+
+    let origin = BlockPosition::new(300, -200);
+    let radius = 1500;
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let progress = generator
+        .adventuring_time(origin, radius)
+        .expect("The biomes should generate");
+    dbg!(progress.missing());
+
+    let required =
+        super::AdventuringTime::required_biomes(MCVersion::MC_1_20_6).expect("1.20 is supported");
+    assert_eq!(
+        progress.found().len() + progress.missing().len(),
+        required.len()
+    );
+    assert!(!progress.found().is_empty());
+    assert_eq!(progress.is_complete(), progress.missing().is_empty());
+
+    for (&biome, location) in progress.found() {
+        assert!(required.contains(&biome));
+        assert!(!progress.missing().contains(&biome));
+        assert_eq!(progress.location_of(biome), Some(*location));
+
+        let (dx, dz) = (
+            i64::from(location.pos.x - origin.x),
+            i64::from(location.pos.z - origin.z),
+        );
+        assert!(dx * dx + dz * dz <= i64::from(radius).pow(2));
+
+        assert_eq!(
+            generator
+                .get_biome_at(location.pos.at_y(location.y))
+                .expect("The biome should generate"),
+            biome
+        );
+    }
+
+    assert_eq!(
+        Generator::new(
+            MCVersion::MC_1_16_5,
+            -5134222539607995087,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::empty(),
+        )
+        .adventuring_time(origin, radius),
+        Err(super::error::GeneratorError::UnsupportedVersion(
+            MCVersion::MC_1_16_5
+        ))
+    );
+}
+
+#[test]
+fn deep_dark_patches_cover_every_deep_dark_cell() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(200, -100);
+
+    let patches = generator
+        .deep_dark_patches(origin, 1000, 0)
+        .expect("Failed to find deep dark patches");
+    let large = generator
+        .deep_dark_patches(origin, 1000, 5000)
+        .expect("Failed to find deep dark patches");
+    dbg!(patches.len(), large.len());
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: (200 - 1000) / 4,
+            z: (-100 - 1000) / 4,
+            size_x: 501,
+            size_z: 501,
+            y: DEEP_DARK_Y >> 2,
+            size_y: 0,
+        },
+    );
+    cache.fill_cache().expect("Failed to fill cache");
+    let cells = cache.positions_of(BiomeID::deep_dark).count() as u64;
+
+    assert!(cells > 0);
+    assert_eq!(patches.iter().map(|patch| patch.cells).sum::<u64>(), cells);
+    assert!(patches.windows(2).all(|pair| pair[0].area >= pair[1].area));
+    assert!(large.iter().all(|patch| patch.area >= 5000));
+    assert_eq!(large[..], patches[..large.len()]);
+
+    let old = Generator::new(
+        MCVersion::MC_1_18_2,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        old.deep_dark_patches(origin, 100, 0),
+        Err(super::error::GeneratorError::UnsupportedVersion(
+            MCVersion::MC_1_18_2
+        ))
+    );
+}
+
+#[test]
+fn lush_caves_near_match_their_cells() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(-300, 150);
+
+    let caves = generator
+        .lush_caves_near(origin, 200, -64..32)
+        .expect("Failed to find lush caves");
+    dbg!(caves.len(), caves.first());
+
+    assert!(!caves.is_empty());
+    assert!(caves
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+
+    let mut cell = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 1,
+            size_z: 1,
+            y: 0,
+            size_y: 1,
+        },
+    );
+    for caves in caves.iter().step_by(10) {
+        assert!(caves.distance <= 200.0);
+        assert!(-64 <= caves.min_y && caves.min_y <= caves.max_y && caves.max_y < 32);
+
+        for y in [caves.min_y, caves.max_y] {
+            cell.move_cache(caves.pos.x >> 2, y >> 2, caves.pos.z >> 2);
+            cell.fill_cache().expect("Failed to fill cache");
+            assert_eq!(cell.biome_at(0, 0, 0), Ok(BiomeID::lush_caves));
+        }
+    }
+
+    assert_eq!(generator.lush_caves_near(origin, 400, 0..0), Ok(Vec::new()));
+    assert_eq!(
+        generator.lush_caves_near(origin, 400, -100..0),
+        Err(super::error::GeneratorError::YOutOfBounds(-100))
+    );
+
+    let old = Generator::new(
+        MCVersion::MC_1_17_1,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        old.lush_caves_near(origin, 100, 0..64),
+        Err(super::error::GeneratorError::UnsupportedVersion(
+            MCVersion::MC_1_17_1
+        ))
+    );
+}
+
+#[test]
+fn dripstone_caves_near_match_their_cells() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(550, -500);
+
+    let caves = generator
+        .dripstone_caves_near(origin, 200, -64..32)
+        .expect("Failed to find dripstone caves");
+    let lush = generator
+        .lush_caves_near(origin, 200, -64..32)
+        .expect("Failed to find lush caves");
+    dbg!(caves.len(), lush.len());
+
+    assert!(!caves.is_empty());
+    assert!(caves
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+
+    let mut cell = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 1,
+            size_z: 1,
+            y: 0,
+            size_y: 1,
+        },
+    );
+    for caves in caves.iter().step_by(10) {
+        assert!(caves.distance <= 200.0);
+
+        for y in [caves.min_y, caves.max_y] {
+            cell.move_cache(caves.pos.x >> 2, y >> 2, caves.pos.z >> 2);
+            cell.fill_cache().expect("Failed to fill cache");
+            assert_eq!(cell.biome_at(0, 0, 0), Ok(BiomeID::dripstone_caves));
+        }
+
+        // A cell is only ever one biome, so the bands can't share their ends
+        if let Some(other) = lush.iter().find(|other| other.pos == caves.pos) {
+            assert!(![other.min_y, other.max_y].contains(&caves.min_y));
+            assert!(![other.min_y, other.max_y].contains(&caves.max_y));
+        }
+    }
+
+    let nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        nether.dripstone_caves_near(origin, 100, 0..64),
+        Err(super::error::GeneratorError::UnsupportedDimension(
+            Dimension::DIM_NETHER
+        ))
+    );
+}
+
+#[test]
+fn point_queries_take_any_position() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let pos = BlockPosition3D::new(-300, 40, 170);
+    let biome = generator.get_biome_at(pos);
+    assert!(biome.is_ok());
+    assert_eq!(generator.get_biome_at((-300, 40, 170)), biome);
+    assert_eq!(generator.get_biome_at([-300, 40, 170]), biome);
+    assert_eq!(generator.get_biome_at(pos.column().at_y(40)), biome);
+    assert_eq!(
+        generator.surface_biome((-300, 170)),
+        generator.surface_biome(pos.column())
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: -80,
+        z: 40,
+        size_x: 8,
+        size_z: 8,
+        y: 10,
+        size_y: 1,
+    };
+    let mut cache = Cache::new(&generator, range);
+    cache.fill_cache().expect("failed to fill cache");
+
+    for z in 0..range.size_z {
+        for x in 0..range.size_x {
+            let cell = QuadPosition::new(range.x + x as i32, range.y, range.z + z as i32);
+            assert_eq!(generator.quad_biome_at(cell), cache.biome_at(x, 0, z));
+            assert_eq!(cell.to_block().to_quad(), cell);
+        }
+    }
+
+    // The voronoi source cell of a block is next to the cell containing it
+    let cell = generator
+        .voronoi_cell(pos)
+        .expect("1.20 uses voronoi access");
+    let containing = pos.to_quad();
+    assert!((cell.x - containing.x).abs() <= 1);
+    assert!((cell.y - containing.y).abs() <= 1);
+    assert!((cell.z - containing.z).abs() <= 1);
+}
+
+#[test]
+fn errors_carry_their_context() {
+    // SAFETY: the generator is only used to check that it refuses to generate
+    let unseeded = unsafe {
+        Generator::new_without_seed(MCVersion::MC_1_20_6, super::GeneratorFlags::empty())
+    };
+
+    assert_eq!(
+        unseeded.get_biome_at((0, 64, 0)),
+        Err(super::error::GeneratorError::SeedNotApplied(
+            MCVersion::MC_1_20_6
+        ))
+    );
+    let mut cache = Cache::new(
+        &unseeded,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 4,
+            size_z: 4,
+            y: 16,
+            size_y: 1,
+        },
+    );
+    assert_eq!(
+        cache.fill_cache(),
+        Err(super::error::GeneratorError::SeedNotApplied(
+            MCVersion::MC_1_20_6
+        ))
+    );
+
+    let nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+    let Err(error) = nether.approx_heights(10, -20, 16, 8) else {
+        panic!("the nether has no surface");
+    };
+
+    dbg!(error.to_string());
+
+    let super::error::GeneratorError::ApproxHeightFailure { context, range, .. } = error else {
+        panic!("unexpected error {error:?}");
+    };
+    assert_eq!(context, nether.context());
+    assert_eq!(
+        (range.x, range.z, range.size_x, range.size_z),
+        (10, -20, 16, 8)
+    );
+    assert!(error.to_string().contains("seed -5134222539607995087"));
+}
+
+#[test]
+fn cached_generator_forgets_least_recently_used() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let capacity = std::num::NonZeroUsize::new(3).expect("the capacity isn't 0");
+    let mut cached = CachedGenerator::new(&generator, capacity);
+
+    for x in 0..3 {
+        assert_eq!(
+            cached.get_biome_at((x * 100, 64, 0)),
+            generator.get_biome_at((x * 100, 64, 0))
+        );
+    }
+    assert_eq!((cached.len(), cached.hits(), cached.misses()), (3, 0, 3));
+
+    // Using the first result again makes the second the least recently used
+    assert_eq!(
+        cached.get_biome_at((0, 64, 0)),
+        generator.get_biome_at((0, 64, 0))
+    );
+    assert_eq!(cached.hits(), 1);
+
+    // A 1:4 cell is a different key than the block with the same coordinates
+    let cell = QuadPosition::new(0, 64, 0);
+    assert_eq!(cached.quad_biome_at(cell), generator.quad_biome_at(cell));
+    assert_eq!((cached.len(), cached.misses()), (3, 4));
+
+    cached
+        .get_biome_at((0, 64, 0))
+        .expect("the biome is remembered");
+    cached
+        .get_biome_at((200, 64, 0))
+        .expect("the biome is remembered");
+    assert_eq!(cached.hits(), 3);
+
+    cached
+        .get_biome_at((100, 64, 0))
+        .expect("the biome is generated");
+    assert_eq!((cached.len(), cached.misses()), (3, 5));
+
+    cached.clear();
+    assert!(cached.is_empty());
+}
+
+#[test]
+fn batched_point_queries_match_get_biome_at() {
+    let points: Vec<BlockPosition3D> = (0..600)
+        .map(|i: i32| {
+            // Dense clusters with a few scattered points, at a few heights
+            let cluster = i / 100;
+            BlockPosition3D::new(
+                cluster * 1000 + (i * 7) % 40 - 20,
+                [-40, 16, 64, 100][(i % 4) as usize],
+                cluster * -700 + (i * 13) % 30,
+            )
+        })
+        .chain([BlockPosition3D::new(12345, 64, -6789)])
+        .collect();
+
+    for (version, dimension) in [
+        (MCVersion::MC_1_20_6, Dimension::DIM_OVERWORLD),
+        (MCVersion::MC_1_20_6, Dimension::DIM_NETHER),
+        (MCVersion::MC_1_20_6, Dimension::DIM_END),
+    ] {
+        let generator = Generator::new(
+            version,
+            -5134222539607995087,
+            dimension,
+            super::GeneratorFlags::empty(),
+        );
+        // The nether has no biomes above its roof
+        let points: Vec<BlockPosition3D> = points
+            .iter()
+            .copied()
+            .filter(|pos| dimension != Dimension::DIM_NETHER || (0..128).contains(&pos.y))
+            .collect();
+
+        let biomes = generator
+            .biomes_at(&points)
+            .expect("failed to generate biomes");
+
+        assert_eq!(biomes.len(), points.len());
+        for (pos, biome) in points.iter().zip(biomes) {
+            assert_eq!(
+                generator.get_biome_at(*pos),
+                Ok(biome),
+                "{dimension:?} {pos:?}"
+            );
+        }
+    }
+
+    // The layered overworld matches a large cache instead
+    let layered = Generator::new(
+        MCVersion::MC_1_16_5,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let range = Range {
+        scale: Scale::Block,
+        x: -32,
+        z: -16,
+        size_x: 64,
+        size_z: 64,
+        y: 64,
+        size_y: 0,
+    };
+    let mut cache = Cache::new(&layered, range);
+    cache.fill_cache().expect("failed to fill cache");
+
+    let cluster: Vec<BlockPosition3D> = points[..100].to_vec();
+    let biomes = layered
+        .biomes_at(&cluster)
+        .expect("failed to generate biomes");
+
+    for (pos, biome) in cluster.iter().zip(biomes) {
+        assert_eq!(
+            cache.biome_at((pos.x - range.x) as u32, 0, (pos.z - range.z) as u32),
+            Ok(biome),
+            "{pos:?}"
+        );
+    }
+
+    assert_eq!(layered.biomes_at(&[]), Ok(Vec::new()));
+}
+
+#[test]
+fn caches_export_as_csv() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -380434930381432806,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: -3,
+        z: 5,
+        size_x: 4,
+        size_z: 3,
+        y: 10,
+        size_y: 2,
+    };
+    let mut cache = Cache::new(&generator, range);
+
+    let mut csv = Vec::new();
+    assert!(matches!(
+        cache.write_csv(&mut csv),
+        Err(super::error::ExportError::GeneratorError(
+            super::error::GeneratorError::IndexOutOfBounds
+        ))
+    ));
+
+    cache.fill_cache().expect("failed to fill cache");
+
+    let mut csv = Vec::new();
+    cache.write_csv(&mut csv).expect("failed to write csv");
+    let csv = String::from_utf8(csv).expect("csv is utf-8");
+
+    dbg!(&csv);
+
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("x,y,z,biome"));
+
+    let rows: Vec<&str> = lines.collect();
+    assert_eq!(rows.len(), 4 * 3 * 2);
+
+    for (row, (y, z, x)) in rows
+        .iter()
+        .zip((0..2).flat_map(|y| (0..3).flat_map(move |z| (0..4).map(move |x| (y, z, x)))))
+    {
+        let biome = cache.biome_at(x, y, z).expect("the cell is in the cache");
+        let expected = format!(
+            "{},{},{},{biome:?}",
+            range.x + x as i32,
+            range.y + y as i32,
+            range.z + z as i32
+        );
+
+        assert_eq!(*row, expected);
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn caches_export_as_json() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -380434930381432806,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Block,
+            x: 512,
+            z: -512,
+            size_x: 16,
+            size_z: 2,
+            y: 100,
+            size_y: 0,
+        },
+    );
+    cache.fill_cache().expect("failed to fill cache");
+
+    let mut json = Vec::new();
+    cache.write_json(&mut json).expect("failed to write json");
+    let json: serde_json::Value = serde_json::from_slice(&json).expect("the export is json");
+
+    dbg!(&json);
+
+    assert_eq!(json["seed"], -380434930381432806_i64);
+    assert_eq!(json["version"], "MC_1_20_6");
+    assert_eq!(json["dimension"], "DIM_OVERWORLD");
+    assert_eq!(json["range"]["scale"], 1);
+    assert_eq!(json["range"]["size_x"], 16);
+    assert_eq!(json["biomes"][0].as_array().map(Vec::len), Some(2));
+    assert_eq!(json["biomes"][0][0][13], "plains");
+    assert_eq!(json["biomes"][1], serde_json::Value::Null);
+}
+
+#[test]
+fn caches_roundtrip_the_binary_format() {
+    use super::{error::CacheFileError, CacheHeader};
+
+    let generator = Generator::new(
+        MCVersion::MC_1_16_5,
+        -1693727681172482083,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range {
+        scale: Scale::Quad,
+        x: -20,
+        z: 7,
+        size_x: 24,
+        size_z: 9,
+        y: -3,
+        size_y: 4,
+    };
+    let mut cache = Cache::new(&generator, range);
+
+    assert!(matches!(
+        cache.write_to(Vec::new()),
+        Err(CacheFileError::GeneratorError(
+            super::error::GeneratorError::IndexOutOfBounds
+        ))
+    ));
+
+    cache.fill_cache().expect("failed to fill cache");
+
+    let mut data = Vec::new();
+    cache.write_to(&mut data).expect("failed to write cache");
+
+    // The header is 54 bytes, followed by a byte per cell
+    assert_eq!(data.len(), 54 + 24 * 9 * 4);
+
+    let header = CacheHeader::read_from(&data[..]).expect("the data is a cache");
+    assert_eq!(header, cache.header());
+    assert_eq!(header.cells(), 24 * 9 * 4);
+
+    let same_generator = header.generator();
+    let mut read = Cache::read_from(&same_generator, &data[..]).expect("the data is a cache");
+    assert_eq!(read.as_vec(), cache.as_vec());
+    assert_eq!(read.range(), &range);
+
+    // The read cache can be filled again like any other
+    read.move_cache(100, -3, 100);
+    read.fill_cache().expect("failed to fill the read cache");
+
+    let overworld = Generator::new(
+        MCVersion::MC_1_16_5,
+        -1693727681172482083,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let mismatch = Cache::read_from(&overworld, &data[..]);
+    dbg!(&mismatch);
+    assert!(matches!(
+        mismatch,
+        Err(CacheFileError::GeneratorMismatch { file, .. }) if file == generator.context()
+    ));
+
+    assert!(matches!(
+        Cache::read_from(&generator, &data[..data.len() - 1]),
+        Err(CacheFileError::Io(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+    assert!(matches!(
+        CacheHeader::read_from(&b"P6\n16 16\n255\n"[..]),
+        Err(CacheFileError::InvalidMagic(magic)) if &magic == b"P6\n1"
+    ));
+
+    let mut corrupt = data.clone();
+    corrupt[54] = 255;
+    assert!(matches!(
+        Cache::read_from(&generator, &corrupt[..]),
+        Err(CacheFileError::InvalidBiome(255))
+    ));
+
+    let mut newer = data;
+    newer[4] = 2;
+    assert!(matches!(
+        CacheHeader::read_from(&newer[..]),
+        Err(CacheFileError::UnsupportedFormatVersion(2))
+    ));
+}
+
+#[test]
+fn flags_are_validated_for_versions() {
+    let all = super::GeneratorFlags::LargeBiomes
+        | super::GeneratorFlags::NoBetaOcean
+        | super::GeneratorFlags::ForceOceanVariants;
+
+    let beta = all
+        .validate_for(MCVersion::MC_B1_7)
+        .expect("the flags are known");
+    dbg!(&beta);
+    assert_eq!(
+        beta.iter().map(|warning| warning.flag).collect::<Vec<_>>(),
+        [
+            super::GeneratorFlags::LargeBiomes,
+            super::GeneratorFlags::ForceOceanVariants
+        ]
+    );
+    assert!(beta[0].to_string().contains("beta 1.8 and later"));
+
+    assert_eq!(
+        all.effective_for(MCVersion::MC_1_16_5),
+        super::GeneratorFlags::LargeBiomes | super::GeneratorFlags::ForceOceanVariants
+    );
+    assert_eq!(
+        all.effective_for(MCVersion::MC_1_20_6),
+        super::GeneratorFlags::LargeBiomes
+    );
+    assert_eq!(
+        super::GeneratorFlags::LargeBiomes.validate_for(MCVersion::MC_1_20_6),
+        Ok(Vec::new())
+    );
+
+    assert_eq!(
+        super::GeneratorFlags::from_bits_retain(0x11).validate_for(MCVersion::MC_1_20_6),
+        Err(super::error::GeneratorError::UnknownFlags(0x10))
+    );
+    assert_eq!(
+        super::GeneratorFlags::empty().validate_for(MCVersion::MC_UNDEF),
+        Err(super::error::GeneratorError::UnsupportedVersion(
+            MCVersion::MC_UNDEF
+        ))
+    );
+}
+
+#[test]
+fn compact_caches_match_their_cache() {
+    let generator = super::Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    // Strips of 15 rows wouldn't line up with the 1:4 cells in 1.16
+    let aligned = super::Generator::new(
+        MCVersion::MC_1_16_5,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let range = Range {
+        scale: Scale::Block,
+        x: -40,
+        z: 25,
+        size_x: 4369,
+        size_z: 48,
+        y: 15,
+        size_y: 0,
+    };
+    let mut cache = Cache::new(&aligned, range);
+    cache.fill_cache().expect("failed to fill cache");
+    assert_eq!(
+        super::CompactCache::generate(&aligned, range),
+        cache.compact()
+    );
+
+    // Large enough to be generated in several strips
+    for (size_y, y) in [(0, 16), (3, -8)] {
+        let range = Range {
+            scale: Scale::Quad,
+            x: -150,
+            z: -70,
+            size_x: 300,
+            size_z: 250,
+            y,
+            size_y,
+        };
+
+        let mut cache = Cache::new(&generator, range);
+        cache.fill_cache().expect("failed to fill cache");
+
+        let compact = cache.compact().expect("the cache is filled");
+        let generated =
+            super::CompactCache::generate(&generator, range).expect("failed to generate");
+
+        assert_eq!(generated, compact);
+        assert!(compact
+            .as_slice()
+            .iter()
+            .zip(cache.as_vec())
+            .all(|(&a, &b)| i32::from(a) == b));
+
+        assert_eq!(compact.biome_at(17, 0, 230), cache.biome_at(17, 0, 230));
+        assert_eq!(compact.count(BiomeID::plains), cache.count(BiomeID::plains));
+        assert_eq!(
+            compact.biome_at(300, 0, 0),
+            Err(super::error::GeneratorError::IndexOutOfBounds)
+        );
+    }
+
+    let empty = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 16,
+            size_z: 16,
+            y: 16,
+            size_y: 0,
+        },
+    );
+    assert_eq!(
+        empty.compact(),
+        Err(super::error::GeneratorError::IndexOutOfBounds)
+    );
+}
+
+#[test]
+fn shifted_caches_match_a_full_fill() {
+    for (version, scale, size_y) in [
+        (MCVersion::MC_1_20_6, Scale::Quad, 0),
+        (MCVersion::MC_1_20_6, Scale::Quad, 3),
+        (MCVersion::MC_1_16_5, Scale::Chunk, 0),
+        (MCVersion::MC_1_16_5, Scale::Block, 0),
+        (MCVersion::MC_1_12_2, Scale::Block, 0),
+    ] {
+        let generator = super::Generator::new(
+            version,
+            -5134222539607995087,
+            Dimension::DIM_OVERWORLD,
+            super::GeneratorFlags::empty(),
+        );
+        let range = Range {
+            scale,
+            x: -40,
+            z: 25,
+            size_x: 64,
+            size_z: 48,
+            y: 15,
+            size_y,
+        };
+
+        let mut shifted = Cache::new(&generator, range);
+        shifted.fill_cache().expect("failed to fill cache");
+
+        for (dx, dz) in [
+            (5, 0),
+            (0, -7),
+            (-13, 9),
+            (30, 30),
+            (0, 0),
+            (64, -2),
+            (-1, 47),
+        ] {
+            shifted.shift(dx, dz).expect("failed to shift cache");
+
+            let mut filled = Cache::new(&generator, *shifted.range());
+            filled.fill_cache().expect("failed to fill cache");
+
+            assert_eq!(
+                shifted.as_vec(),
+                filled.as_vec(),
+                "{version:?} {scale:?} {dx} {dz}"
+            );
+        }
+    }
+}
+
+#[test]
+fn biome_scales_follow_the_dimension() {
+    use super::{error::GeneratorError, BiomeScales};
+
+    let overworld_1_16 = BiomeScales::of(MCVersion::MC_1_16_5, Dimension::DIM_OVERWORLD);
+    let overworld = BiomeScales::of(MCVersion::MC_1_20_6, Dimension::DIM_OVERWORLD);
+    let old_nether = BiomeScales::of(MCVersion::MC_1_15_2, Dimension::DIM_NETHER);
+    let end = BiomeScales::of(MCVersion::MC_1_20_6, Dimension::DIM_END);
+
+    assert!(!overworld_1_16.varies_with_y(Scale::Block));
+    assert!(overworld.varies_with_y(Scale::HalfRegion));
+    assert_eq!(old_nether.single_biome(), Some(BiomeID::nether_wastes));
+    assert_eq!(overworld.single_biome(), None);
+
+    let column = Range::plane(Scale::Quad, -8, -8, 16, 16, 64).with_y_levels(4);
+    assert_eq!(column.y, 16);
+    assert_eq!(overworld.check_range(&column), Ok(()));
+    assert_eq!(
+        end.check_range(&column),
+        Err(GeneratorError::PlanarBiomes {
+            dimension: Dimension::DIM_END,
+            scale: Scale::Quad,
+        })
+    );
+    assert_eq!(
+        end.check_range(&Range::plane(Scale::HalfRegion, 0, 0, 4, 4, 64)),
+        Err(GeneratorError::UnsupportedScale(Scale::HalfRegion))
+    );
+    assert_eq!(
+        BiomeScales::of(MCVersion::MC_B1_8, Dimension::DIM_END).check_range(&column),
+        Err(GeneratorError::UnsupportedVersion(MCVersion::MC_B1_8))
+    );
+
+    // The biomes which don't vary with y are the same at every level
+    for (version, dimension) in [
+        (MCVersion::MC_1_16_5, Dimension::DIM_OVERWORLD),
+        (MCVersion::MC_1_20_6, Dimension::DIM_END),
+    ] {
+        let generator = super::Generator::new(
+            version,
+            -5134222539607995087,
+            dimension,
+            super::GeneratorFlags::empty(),
+        );
+        let range = Range::plane(Scale::Quad, 200, -300, 32, 32, 0).with_y_levels(8);
+        assert!(generator.check_range(&range).is_err());
+
+        let mut cache = Cache::new(&generator, range);
+        cache.fill_cache().expect("failed to fill cache");
+
+        let (first, rest) = cache.as_vec().split_at(32 * 32);
+        assert!(rest.chunks_exact(32 * 32).all(|level| level == first));
+    }
+}
+
+#[test]
+fn y_levels_resolve_per_dimension() {
+    use super::YLevel;
+
+    let overworld = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    let nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        super::GeneratorFlags::empty(),
+    );
+
+    assert_eq!(YLevel::SeaLevel.resolve(&overworld, (0, 0)), Ok(63));
+    assert_eq!(YLevel::SeaLevel.resolve(&nether, (0, 0)), Ok(32));
+    assert_eq!(YLevel::BuildLimit.resolve(&nether, (0, 0)), Ok(255));
+    assert_eq!(YLevel::from(-20).resolve(&overworld, (0, 0)), Ok(-20));
+    assert_eq!(
+        YLevel::Surface.resolve(&overworld, (512, -512)),
+        overworld.surface_y((512, -512))
+    );
+
+    for level in ["sea_level", "surface", "build_limit", "-20"] {
+        let parsed: YLevel = level.parse().expect("valid level");
+        assert_eq!(parsed.to_string(), level);
+    }
+    assert!("sky".parse::<YLevel>().is_err());
+
+    // The levels of a range are the same as generating at the resolved y
+    let range = Range::plane(Scale::Quad, -64, 32, 16, 16, 0);
+    let levels = overworld
+        .biomes_at_level(range, YLevel::BuildLimit)
+        .expect("failed to generate biomes");
+    let mut cache = Cache::new(
+        &overworld,
+        Range {
+            y: 319 / 4,
+            ..range
+        },
+    );
+    cache.fill_cache().expect("failed to fill cache");
+    assert_eq!(levels.as_vec(), cache.as_vec());
+
+    let surface = overworld
+        .biomes_at_level(range, YLevel::Surface)
+        .expect("failed to generate biomes");
+    let expected = overworld
+        .surface_biomes(range)
+        .expect("failed to generate biomes");
+    assert_eq!(surface.as_vec(), expected.as_vec());
+}
+
+#[test]
+fn caches_look_up_world_coordinates() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut blocks = Cache::new(&generator, Range::plane(Scale::Block, -40, 100, 32, 32, 70));
+    blocks.fill_cache().expect("failed to fill cache");
+
+    for (x, z) in [(-40, 100), (-9, 131), (-20, 115)] {
+        assert_eq!(
+            blocks.biome_at_block((x, 70, z)),
+            generator.get_biome_at((x, 70, z))
+        );
+    }
+    assert_eq!(
+        blocks.biome_at_block((-41, 70, 100)),
+        Err(super::error::GeneratorError::IndexOutOfBounds)
+    );
+    assert_eq!(
+        blocks.biome_at_block((-40, 71, 100)),
+        Err(super::error::GeneratorError::IndexOutOfBounds)
+    );
+
+    let mut chunks = Cache::new(&generator, Range::plane(Scale::Chunk, -4, -4, 8, 8, 64));
+    chunks.fill_cache().expect("failed to fill cache");
+
+    // Every block of a cell maps to it, including negative coordinates
+    assert_eq!(
+        chunks.biome_at_block((-64, 64, -1)),
+        chunks.biome_at(0, 0, 3)
+    );
+    assert_eq!(
+        chunks.biome_at_block((-49, 67, -1)),
+        chunks.biome_at(0, 0, 3)
+    );
+    assert_eq!(
+        chunks.biome_at_block((63, 64, 63)),
+        chunks.biome_at(7, 0, 7)
+    );
+
+    // The raw index doesn't wrap into the next row
+    assert_eq!(
+        chunks.biome_at(8, 0, 0),
+        Err(super::error::GeneratorError::IndexOutOfBounds)
+    );
+}
+
+#[test]
+fn ranges_are_checked_before_cubiomes() {
+    use super::error::{GeneratorError, TryFromRangeError};
+
+    let generator = Generator::new(
+        MCVersion::MC_1_16_5,
+        -4804349703814383506,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let fill = |range: Range| Cache::new(&generator, range).fill_cache();
+    let rejected = |error| Err(GeneratorError::TryFromRangeError(error));
+
+    assert_eq!(
+        fill(Range::plane(Scale::Quad, 0, 0, 16, 0, 64)),
+        rejected(TryFromRangeError::ZSizeOutOfBounds)
+    );
+    assert_eq!(
+        fill(Range::plane(Scale::Quad, 0, 0, 1 << 15, 1 << 15, 64)),
+        rejected(TryFromRangeError::TooManyCells)
+    );
+    assert_eq!(
+        fill(Range::plane(
+            Scale::HalfRegion,
+            i32::MAX / 256,
+            0,
+            16,
+            16,
+            64
+        )),
+        rejected(TryFromRangeError::XOutOfBounds)
+    );
+    assert_eq!(
+        fill(Range::plane(Scale::Chunk, 0, i32::MIN, 16, 16, 64)),
+        rejected(TryFromRangeError::ZOutOfBounds)
+    );
+    assert_eq!(
+        fill(Range {
+            y: i32::MAX / 4,
+            ..Range::plane(Scale::Quad, 0, 0, 16, 16, 64).with_y_levels(4)
+        }),
+        rejected(TryFromRangeError::YOutOfBounds)
+    );
+    assert_eq!(
+        generator
+            .quad_biome_at(QuadPosition::new(i32::MAX / 4 + 1, 16, 0))
+            .map(|_| ()),
+        rejected(TryFromRangeError::XOutOfBounds)
+    );
+    assert_eq!(
+        generator.approx_heights(0, 0, 0, 16).map(|_| ()),
+        rejected(TryFromRangeError::XSizeOutOfBounds)
+    );
+
+    // The last cells with block coordinates fitting an i32 still generate
+    fill(Range::plane(
+        Scale::Chunk,
+        i32::MAX / 16 - 16,
+        -16,
+        16,
+        16,
+        64,
+    ))
+    .expect("failed to fill cache at the edge of the coordinates");
+}
+
+#[test]
+fn borders_cover_every_edge_between_the_sides() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(&generator, Range::plane(Scale::Chunk, -40, -25, 80, 50, 16));
+    cache.fill_cache().expect("failed to fill cache");
+
+    let target = cache.biome_at(3, 0, 7).expect("the cell is in the cache");
+    let is_target = |x: u32, z: u32| cache.biome_at(x, 0, z) == Ok(target);
+
+    let mut edges = 0;
+    let mut cells = Vec::new();
+    for z in 0..50 {
+        for x in 0..80 {
+            let right = x + 1 < 80 && is_target(x, z) != is_target(x + 1, z);
+            let below = z + 1 < 50 && is_target(x, z) != is_target(x, z + 1);
+            edges += u64::from(right) + u64::from(below);
+
+            let touches_other = [(-1, 0), (1, 0), (0, -1), (0, 1)].iter().any(|&(dx, dz)| {
+                match (x.checked_add_signed(dx), z.checked_add_signed(dz)) {
+                    (Some(nx), Some(nz)) if nx < 80 && nz < 50 => !is_target(nx, nz),
+                    _ => false,
+                }
+            });
+            if is_target(x, z) && touches_other {
+                cells.push((x, z));
+            }
+        }
+    }
+    assert!(edges > 0);
+
+    let borders = cache
+        .borders_between(|biome| biome == target, |biome| biome != target)
+        .expect("the cache holds valid biomes");
+
+    assert_eq!(
+        borders.iter().map(super::BiomeBorder::length).sum::<u64>(),
+        edges * 16
+    );
+    for border in &borders {
+        assert!(border.points.len() >= 2);
+        assert_eq!(border.closed, border.points.first() == border.points.last());
+        for segment in border.points.windows(2) {
+            assert!((segment[0].x == segment[1].x) != (segment[0].z == segment[1].z));
+            assert_eq!(segment[0].x.rem_euclid(16), 0);
+            assert_eq!(segment[0].z.rem_euclid(16), 0);
+        }
+    }
+
+    let border_cells = cache
+        .border_cells(|biome| biome == target, |biome| biome != target)
+        .expect("the cache holds valid biomes");
+    assert_eq!(border_cells, cells);
+
+    // The sides are swapped in the lines, but not in the cells
+    let swapped = cache
+        .borders_between(|biome| biome != target, |biome| biome == target)
+        .expect("the cache holds valid biomes");
+    assert_eq!(
+        swapped.iter().map(super::BiomeBorder::length).sum::<u64>(),
+        edges * 16
+    );
+    assert!(cache
+        .borders_between(|_| false, |_| true)
+        .expect("valid")
+        .is_empty());
+}
+
+#[test]
+fn routes_avoid_impassable_biomes() {
+    use super::BlockPosition;
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let mut cache = Cache::new(&generator, Range::plane(Scale::Chunk, -40, -25, 80, 50, 64));
+    cache.fill_cache().expect("failed to fill cache");
+
+    let (start, goal) = (BlockPosition::new(-600, -380), BlockPosition::new(500, 300));
+
+    // Without obstacles the route is the octile distance between the cells
+    let open = cache
+        .plan_route(start, goal, |_| Some(1.0))
+        .expect("both ends are in the cache")
+        .expect("every cell can be stepped on");
+    // The start is in the cell -38, -24 and the goal in 31, 18
+    let (dx, dz) = (69.0_f64, 42.0_f64);
+    let octile = (dx.max(dz) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dz)) * 16.0;
+    assert!((open.length - octile).abs() < 1e-6);
+    assert!((open.cost - open.length).abs() < 1e-6);
+    assert_eq!(
+        open.waypoints.first(),
+        Some(&BlockPosition::new(-600, -376))
+    );
+    assert_eq!(open.waypoints.last(), Some(&BlockPosition::new(504, 296)));
+
+    // Every cell along the route is of a biome which can be stepped on
+    let ends = [start, goal].map(|pos| cache.biome_at_block((pos.x, 64, pos.z)));
+    let (&blocked, _) = cache
+        .histogram()
+        .iter()
+        .filter(|(biome, _)| !ends.contains(&Ok(**biome)))
+        .max_by_key(|(_, &count)| count)
+        .expect("the cache has more biomes than the ends");
+    let route = cache
+        .plan_route(start, goal, |biome| (biome != blocked).then_some(1.0))
+        .expect("both ends are in the cache")
+        .expect("there is a way around the biome");
+    assert!(route.length >= open.length - 1e-6);
+    for segment in route.waypoints.windows(2) {
+        let step = (
+            (segment[1].x - segment[0].x).signum() * 16,
+            (segment[1].z - segment[0].z).signum() * 16,
+        );
+        let mut pos = segment[0];
+        while pos != segment[1] {
+            assert_ne!(cache.biome_at_block((pos.x, 64, pos.z)), Ok(blocked));
+            pos = BlockPosition::new(pos.x + step.0, pos.z + step.1);
+        }
+    }
+
+    assert_eq!(cache.plan_route(start, goal, |_| None), Ok(None));
+    assert_eq!(
+        cache.plan_route(start, BlockPosition::new(10_000, 0), |_| Some(1.0)),
+        Err(super::error::GeneratorError::IndexOutOfBounds)
+    );
+}
+
+#[test]
+fn fraction_estimates_contain_the_exact_fraction() {
+    use crate::rng::Xoroshiro;
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let quad = Range::plane(Scale::Quad, -256, -256, 512, 512, 64);
+    let stats = generator
+        .biome_stats(quad)
+        .expect("failed to generate biomes");
+    let (&biome, _) = stats
+        .counts()
+        .iter()
+        .max_by_key(|(_, &count)| count)
+        .expect("the range has biomes");
+    let exact = stats.percentage(biome) / 100.0;
+
+    // A chunk range over the same area is sampled at 1:4
+    let chunk = Range::plane(Scale::Chunk, -64, -64, 128, 128, 64);
+    for range in [quad, chunk] {
+        let estimate = generator
+            .estimate_biome_fraction(range, biome, 4000, &mut Xoroshiro::new(7))
+            .expect("failed to generate biomes");
+        assert_eq!(estimate.samples, 4000);
+
+        let (low, high) = estimate.wilson_interval(3.29);
+        assert!(
+            low <= exact && exact <= high,
+            "{exact} not in {low}..{high}"
+        );
+        assert!(high - low < 0.06);
+    }
+
+    let none = generator
+        .estimate_fraction_where(quad, |_| false, 100, &mut Xoroshiro::new(7))
+        .expect("failed to generate biomes");
+    assert_eq!(none.fraction(), 0.0);
+    assert_eq!(
+        generator.estimate_biome_fraction(quad, biome, 0, &mut Xoroshiro::new(7)),
+        Ok(super::FractionEstimate::default())
+    );
+    assert!(generator
+        .estimate_biome_fraction(
+            Range { size_x: 0, ..quad },
+            biome,
+            10,
+            &mut Xoroshiro::new(7)
+        )
+        .is_err());
+}
+
+#[test]
+fn biomes_along_lines_match_caches() {
+    for (dimension, scale) in [
+        (Dimension::DIM_OVERWORLD, Scale::Block),
+        (Dimension::DIM_NETHER, Scale::Chunk),
+        (Dimension::DIM_END, Scale::Quad),
+    ] {
+        let generator = Generator::new(
+            MCVersion::MC_1_20_6,
+            -5134222539607995087,
+            dimension,
+            super::GeneratorFlags::empty(),
+        );
+
+        let samples = generator
+            .biomes_along((-300, 20, 50), (700, 90, -450), 25, scale)
+            .expect("failed to generate biomes");
+
+        // 1120 blocks long, so 45 steps and the end
+        assert_eq!(samples.len(), 46);
+        assert_eq!(samples[0].pos, super::BlockPosition3D::new(-300, 20, 50));
+        assert_eq!(samples[45].pos, super::BlockPosition3D::new(700, 90, -450));
+        assert!((samples[45].distance - 1120.2).abs() < 0.1);
+        assert!((0..45).all(|i| samples[i].distance == f64::from(i as u32 * 25)));
+
+        for sample in &samples {
+            let pos = sample.pos;
+            let mut cache = Cache::new(
+                &generator,
+                Range {
+                    scale,
+                    x: pos.x.div_euclid(scale as i32),
+                    z: pos.z.div_euclid(scale as i32),
+                    size_x: 1,
+                    size_z: 1,
+                    y: scale.scale_y(pos.y),
+                    size_y: 1,
+                },
+            );
+            cache.fill_cache().expect("failed to fill cache");
+            assert_eq!(cache.biome_at(0, 0, 0), Ok(sample.biome));
+        }
+    }
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        generator
+            .biomes_along((0, 64, 0), (0, 64, 0), 16, Scale::Quad)
+            .map(|samples| samples.len()),
+        Ok(1)
+    );
+    assert_eq!(
+        generator.biomes_along((0, 64, 0), (100, 64, 0), 0, Scale::Quad),
+        Err(super::error::GeneratorError::ZeroStep)
+    );
+}
+
+#[test]
+fn coordinates_round_towards_negative_infinity() {
+    use super::coords;
+
+    for block in -600..600 {
+        let chunk = coords::block_to_chunk(block);
+        let start = coords::chunk_to_block(chunk);
+        assert!(start <= block && block < start + 16);
+        assert_eq!(start + coords::block_in_chunk(block), block);
+
+        let quad = coords::block_to_quad(block);
+        assert!(coords::quad_to_block(quad) <= block && block < coords::quad_to_block(quad + 1));
+        assert_eq!(coords::block_to_chunk(coords::quad_to_block(quad)), chunk);
+
+        let region = coords::block_to_region(block, 34);
+        assert_eq!(region, coords::chunk_to_region(chunk, 34));
+        let start = coords::region_to_block(region, 34);
+        assert!(start <= block && block < start + 34 * 16);
+
+        for scale in [
+            Scale::Block,
+            Scale::Quad,
+            Scale::Chunk,
+            Scale::QuadChunk,
+            Scale::HalfRegion,
+        ] {
+            let cell = coords::block_to_cell(block, scale);
+            assert_eq!(scale.scale_coord(block), cell);
+            assert!(scale.unscale_coord(cell) <= block && block < scale.unscale_coord(cell + 1));
+        }
+    }
+
+    assert_eq!(
+        BlockPosition::new(-1, -16).as_scaled(Scale::Chunk),
+        (-1, -1)
+    );
+    assert_eq!(
+        BlockPosition3D::new(-1, -4, -5).to_quad(),
+        QuadPosition::new(-1, -1, -2)
+    );
+    assert_eq!(
+        QuadPosition::new(-1, 0, -2).to_block(),
+        BlockPosition3D::new(-4, 0, -8)
+    );
+}
+
+#[test]
+fn registry_shares_generators_between_threads() {
+    use super::{GeneratorFlags, GeneratorKey, GeneratorRegistry};
+    use std::sync::Arc;
+
+    let capacity = std::num::NonZeroUsize::new(2).expect("the capacity isn't 0");
+    let registry = GeneratorRegistry::new(capacity);
+    let seed = -5134222539607995087;
+    let keys = [
+        GeneratorKey::new(
+            MCVersion::MC_1_20_6,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        ),
+        GeneratorKey::new(
+            MCVersion::MC_1_16_5,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::LargeBiomes,
+        ),
+        GeneratorKey::new(
+            MCVersion::MC_1_20_6,
+            Dimension::DIM_NETHER,
+            GeneratorFlags::empty(),
+        ),
+    ];
+
+    std::thread::scope(|scope| {
+        for key in &keys[..2] {
+            for thread in 0..2 {
+                let registry = &registry;
+                scope.spawn(move || {
+                    let pos = (thread * 500, 64, -300);
+                    let biome = registry.with_generator(*key, seed + thread as i64, |generator| {
+                        assert_eq!(generator.seed(), seed + thread as i64);
+                        generator.get_biome_at(pos)
+                    });
+                    let expected =
+                        Generator::new(key.version, seed + thread as i64, key.dimension, key.flags)
+                            .get_biome_at(pos);
+                    assert_eq!(biome, expected);
+                });
+            }
+        }
+    });
+    assert_eq!(registry.len(), 2);
+
+    // The registry keeps handing out the same generator for a key
+    let first = registry.generator(keys[0]);
+    assert!(Arc::ptr_eq(&first, &registry.generator(keys[0])));
+
+    // The first key was used last, so the second one is dropped for the third
+    registry.with_generator(keys[2], seed, |generator| {
+        assert_eq!(generator.dimension(), Dimension::DIM_NETHER);
+    });
+    assert!(registry.contains(keys[0]));
+    assert!(!registry.contains(keys[1]));
+    assert!(registry.contains(keys[2]));
+
+    assert!(registry.remove(keys[0]));
+    assert!(!registry.remove(keys[0]));
+    assert!(!Arc::ptr_eq(&first, &registry.generator(keys[0])));
+
+    registry.clear();
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn flags_match_the_generated_enum() {
+    use super::GeneratorFlags;
+
+    for (flag, generated) in [
+        (GeneratorFlags::LargeBiomes, GeneratorFlag::GF_LARGE_BIOMES),
+        (GeneratorFlags::NoBetaOcean, GeneratorFlag::GF_NO_BETA_OCEAN),
+        (
+            GeneratorFlags::ForceOceanVariants,
+            GeneratorFlag::GF_FORCE_OCEAN_VARIANTS,
+        ),
+    ] {
+        assert_eq!(GeneratorFlag::from_u32(flag.bits()), Some(generated));
+    }
+}
+
+#[test]
+fn beta_terrain_heights_are_sampled_at_cell_centers() {
+    use super::{BetaSurfaceNoise, GeneratorFlags, BETA_SEA_LEVEL};
+    use crate::generator::error::GeneratorError;
+
+    let seed = 3055141959546;
+    let generator =
+        Generator::beta(MCVersion::MC_B1_7, seed, true).expect("b1.7 is a beta version");
+    assert_eq!(generator.flags(), GeneratorFlags::empty());
+    assert_eq!(
+        Generator::beta(MCVersion::MC_B1_8, seed, false).map(|generator| generator.flags()),
+        Ok(GeneratorFlags::empty())
+    );
+    assert_eq!(
+        Generator::beta(MCVersion::MC_1_0, seed, false).err(),
+        Some(GeneratorError::UnsupportedVersion(MCVersion::MC_1_0))
+    );
+
+    let noise = BetaSurfaceNoise::new(&generator).expect("b1.7 has beta terrain");
+    let heights = noise
+        .approx_heights(&generator, -20, 30, 24, 16)
+        .expect("failed to map heights");
+    // The generator's own map sets the noise up on the stack, where cubiomes'
+    // reads past its end give other heights, so only its size is compared
+    let expected = generator
+        .approx_heights(-20, 30, 24, 16)
+        .expect("failed to map heights");
+    assert_eq!(
+        (heights.size_x(), heights.size_z()),
+        (expected.size_x(), expected.size_z())
+    );
+    assert_eq!(
+        BetaSurfaceNoise::new(&generator)
+            .and_then(|noise| noise.approx_heights(&generator, -20, 30, 24, 16))
+            .map(|again| again.heights().to_vec()),
+        Ok(heights.heights().to_vec())
+    );
+
+    // Cells are sampled at their center block
+    let height = noise
+        .surface_height(&generator, (-20 * 4 + 2, 30 * 4 + 2))
+        .expect("the noise is for the generator");
+    assert_eq!(heights.height_at(0, 0), Some(height as f32));
+    assert_eq!(
+        noise.is_ocean(&generator, (-78, 122)),
+        Ok(height <= BETA_SEA_LEVEL)
+    );
+    assert_eq!(generator.beta_surface_height((-78, 122)), Ok(height));
+
+    let other =
+        Generator::beta(MCVersion::MC_B1_7, seed + 1, true).expect("b1.7 is a beta version");
+    assert!(matches!(
+        noise.surface_height(&other, (0, 0)),
+        Err(GeneratorError::MismatchedGenerator(_))
+    ));
+    assert!(matches!(
+        BetaSurfaceNoise::new(&Generator::large_biomes(MCVersion::MC_1_16_5, seed)),
+        Err(GeneratorError::UnsupportedVersion(MCVersion::MC_1_16_5))
+    ));
+}
+
+#[test]
+fn cache_views_borrow_sub_ranges() {
+    use super::error::GeneratorError;
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        super::GeneratorFlags::empty(),
+    );
+
+    let range = Range::plane(Scale::Quad, -40, 20, 48, 32, -16).with_y_levels(6);
+    let mut cache = Cache::new(&generator, range);
+    assert_eq!(
+        cache.view(range).err(),
+        Some(GeneratorError::IndexOutOfBounds),
+        "the cache isn't filled"
+    );
+    cache.fill_cache().expect("failed to fill cache");
+
+    let sub_area = Range {
+        x: -30,
+        z: 31,
+        y: range.y + 2,
+        ..Range::plane(Scale::Quad, 0, 0, 17, 9, 0).with_y_levels(3)
+    };
+    let view = cache
+        .view(sub_area)
+        .expect("the area is inside of the cache");
+    assert_eq!(view.range(), &sub_area);
+    assert_eq!(view.origin(), BlockPosition::new(-120, 124));
+
+    // The view holds the same biomes as a cache of its own range
+    let mut separate = Cache::new(&generator, sub_area);
+    separate.fill_cache().expect("failed to fill cache");
+    let biomes: Vec<BiomeID> = view.biomes().collect();
+    let expected: Vec<BiomeID> = separate
+        .as_vec()
+        .iter()
+        .filter_map(|&raw| BiomeID::from_id(raw))
+        .collect();
+    assert_eq!(biomes, expected);
+    assert_eq!(view.rows().count(), 9 * 3);
+    assert_eq!(view.histogram(), separate.histogram());
+    assert_eq!(view.to_image(), separate.to_image());
+    assert_eq!(view.biome_at(16, 2, 8), separate.biome_at(16, 2, 8));
+    assert_eq!(view.biome_at(16, 2, 8), cache.biome_at(26, 4, 19));
+    assert_eq!(
+        view.biome_at(17, 0, 0),
+        Err(GeneratorError::IndexOutOfBounds)
+    );
+
+    // Areas reaching outside of the cache, or at another scale
+    for outside in [
+        Range { x: -41, ..sub_area },
+        Range {
+            size_z: 22,
+            ..sub_area
+        },
+        Range {
+            y: range.y + 4,
+            ..sub_area
+        },
+    ] {
+        assert_eq!(
+            cache.view(outside).err(),
+            Some(GeneratorError::IndexOutOfBounds),
+            "{outside:?}"
+        );
+    }
+    assert_eq!(
+        cache
+            .view(Range {
+                scale: Scale::Chunk,
+                ..sub_area
+            })
+            .err(),
+        Some(GeneratorError::UnsupportedScale(Scale::Chunk))
+    );
+}
@@ -0,0 +1,130 @@
+use super::{error::GeneratorError, Cache, Range};
+use crate::progress::{CancellationToken, Progress};
+use std::time::Instant;
+
+/// The size of the tiles used by [`Cache::fill_cache_with_progress()`]
+///
+/// The size is in cells of the caches scale, on both the x and z axis.
+pub const TILE_SIZE: u32 = 256;
+
+impl Cache<'_> {
+    /// Fills the cache tile by tile, reporting the progress after each tile
+    ///
+    /// The range of the cache is split into tiles of [`TILE_SIZE`] cells,
+    /// which are generated one after another. After each tile the amount of
+    /// cells generated is passed to `on_progress`. The cache is filled with the
+    /// same data as with [`Self::fill_cache()`], but large ranges can be
+    /// followed and stopped midway.
+    ///
+    /// `cancel` is checked before each tile.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    /// use cubiomes::progress::CancellationToken;
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range {
+    ///     scale: Scale::Chunk,
+    ///     x: -256,
+    ///     z: -256,
+    ///     size_x: 512,
+    ///     size_z: 512,
+    ///     y: 80,
+    ///     size_y: 0,
+    /// });
+    ///
+    /// cache
+    ///     .fill_cache_with_progress(&CancellationToken::new(), |progress| {
+    ///         println!("{} of {} cells generated", progress.done, progress.total);
+    ///     })
+    ///     .expect("failed to fill cache");
+    ///
+    /// assert_eq!(cache.as_vec().len(), 512 * 512);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::Cancelled`] if `cancel` is cancelled before
+    /// every tile is generated and otherwise the same errors as
+    /// [`Self::fill_cache()`]. The cache is left empty on errors.
+    pub fn fill_cache_with_progress(
+        &mut self,
+        cancel: &CancellationToken,
+        mut on_progress: impl FnMut(&Progress),
+    ) -> Result<(), GeneratorError> {
+        let result = self.fill_tiles(cancel, &mut on_progress);
+
+        if result.is_err() {
+            self.buffer.clear();
+        }
+
+        result
+    }
+
+    fn fill_tiles(
+        &mut self,
+        cancel: &CancellationToken,
+        on_progress: &mut impl FnMut(&Progress),
+    ) -> Result<(), GeneratorError> {
+        let started = Instant::now();
+        let range = self.range;
+        let size_x = range.size_x as usize;
+        let layer = size_x * range.size_z as usize;
+        let layers = range.size_y.max(1) as usize;
+
+        let mut progress = Progress {
+            done: 0,
+            total: (layer * layers) as u64,
+            elapsed: started.elapsed(),
+        };
+
+        // The buffer has room for at least the readable data, so this doesn't
+        // reallocate
+        self.buffer.clear();
+        self.buffer.resize(layer * layers, 0);
+
+        for tile_z in (0..range.size_z).step_by(TILE_SIZE as usize) {
+            for tile_x in (0..range.size_x).step_by(TILE_SIZE as usize) {
+                if cancel.is_cancelled() {
+                    return Err(GeneratorError::Cancelled);
+                }
+
+                let tile_range = Range {
+                    x: range.x + tile_x as i32,
+                    z: range.z + tile_z as i32,
+                    size_x: TILE_SIZE.min(range.size_x - tile_x),
+                    size_z: TILE_SIZE.min(range.size_z - tile_z),
+                    ..range
+                };
+                let mut tile = Cache::new(self.generator, tile_range);
+                tile.fill_cache()?;
+
+                let tile_size_x = tile_range.size_x as usize;
+
+                for (index, row) in tile.buffer.chunks_exact(tile_size_x).enumerate() {
+                    let y = index / tile_range.size_z as usize;
+                    let z = tile_z as usize + index % tile_range.size_z as usize;
+                    let start = y * layer + z * size_x + tile_x as usize;
+
+                    self.buffer[start..start + tile_size_x].copy_from_slice(row);
+                }
+
+                #[cfg(feature = "metrics")]
+                crate::metrics::record_tile();
+
+                progress.done += tile.buffer.len() as u64;
+                progress.elapsed = started.elapsed();
+                on_progress(&progress);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Checks for the values passed to cubiomes
+//!
+//! Cubiomes trusts its callers: it doesn't check the sizes of areas, and
+//! computes coordinates and buffer sizes with plain `int` math, which
+//! overflows for areas which are too big or too far out. The functions here
+//! check the values before they reach cubiomes and turn them into errors.
+//!
+//! Scales and dimensions are checked by their enums, which only have values
+//! cubiomes knows of. A generator without a dimension is caught by
+//! [`super::Generator::check_seeded()`].
+
+use super::{error::TryFromRangeError, Range, Scale};
+
+/// The most cells an area given to cubiomes may have
+///
+/// Cubiomes generates the layers of an area into buffers which are somewhat
+/// bigger than the area, and sizes them with `int` math, so areas are kept
+/// well below [`i32::MAX`] cells.
+pub(crate) const MAX_CELLS: u64 = 1 << 28;
+
+/// Checks a range and converts it for cubiomes
+pub(crate) fn range(range: &Range) -> Result<cubiomes_sys::Range, TryFromRangeError> {
+    let (sx, sz) = area(range.scale, range.x, range.z, range.size_x, range.size_z)?;
+    let sy = i32::try_from(range.size_y).map_err(|_| TryFromRangeError::YSizeOutOfBouns)?;
+
+    let cells = u64::from(range.size_x) * u64::from(range.size_z) * u64::from(range.size_y.max(1));
+    if cells > MAX_CELLS {
+        return Err(TryFromRangeError::TooManyCells);
+    }
+
+    // The y is only scaled by 1:4, see Scale::scale_y()
+    let y_scale = if range.scale == Scale::Block { 1 } else { 4 };
+    if !fits_in_blocks(range.y, range.size_y.max(1), y_scale) {
+        return Err(TryFromRangeError::YOutOfBounds);
+    }
+
+    Ok(cubiomes_sys::Range {
+        scale: range.scale as i32,
+        x: range.x,
+        z: range.z,
+        sx,
+        sz,
+        y: range.y,
+        sy,
+    })
+}
+
+/// Checks a 2d area at a scale and converts its size for cubiomes
+pub(crate) fn area(
+    scale: Scale,
+    x: i32,
+    z: i32,
+    size_x: u32,
+    size_z: u32,
+) -> Result<(i32, i32), TryFromRangeError> {
+    let sx = i32::try_from(size_x)
+        .ok()
+        .filter(|&sx| sx > 0)
+        .ok_or(TryFromRangeError::XSizeOutOfBounds)?;
+    let sz = i32::try_from(size_z)
+        .ok()
+        .filter(|&sz| sz > 0)
+        .ok_or(TryFromRangeError::ZSizeOutOfBounds)?;
+
+    if u64::from(size_x) * u64::from(size_z) > MAX_CELLS {
+        return Err(TryFromRangeError::TooManyCells);
+    }
+    if !fits_in_blocks(x, size_x, scale as i32) {
+        return Err(TryFromRangeError::XOutOfBounds);
+    }
+    if !fits_in_blocks(z, size_z, scale as i32) {
+        return Err(TryFromRangeError::ZOutOfBounds);
+    }
+
+    Ok((sx, sz))
+}
+
+/// Checks that the cells from a start have block coordinates fitting an i32
+fn fits_in_blocks(start: i32, size: u32, scale: i32) -> bool {
+    i32::try_from(size)
+        .ok()
+        .and_then(|size| start.checked_add(size))
+        .and_then(|end| end.checked_mul(scale))
+        .and_then(|_| start.checked_mul(scale))
+        .is_some()
+}
@@ -0,0 +1,203 @@
+use super::{colors::new_biome_color_map, error::GeneratorError, BlockPosition, Cache, Range};
+use crate::{enums::BiomeID, render::Image};
+use std::collections::BTreeMap;
+
+/// A rectangular part of a filled [`Cache`], borrowed without copying
+///
+/// The view has its own [`Range`] inside the range of the cache, and reads
+/// the biomes of that range straight from the cache, see [`Cache::view()`].
+/// Indices given to the view are relative to the upper left corner of its own
+/// range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheView<'cache> {
+    buffer: &'cache [i32],
+    /// The size of the cache along x and z
+    parent_size: (usize, usize),
+    /// The cell of the cache at the corner of the view, as x, y and z
+    offset: (usize, usize, usize),
+    range: Range,
+}
+
+impl Cache<'_> {
+    /// Borrows a part of the filled cache as a [`CacheView`]
+    ///
+    /// `sub_area` is a range at the scale of the cache, in the same world
+    /// coordinates as the range of the cache, which has to be inside of it.
+    /// The view can then be iterated, counted and rendered like a cache of
+    /// its own, so zooming into a part of a large map doesn't generate or
+    /// copy its biomes again.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range::plane(Scale::Quad, -128, -128, 256, 256, 64));
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// // The 64 by 64 cells around the origin
+    /// let view = cache
+    ///     .view(Range::plane(Scale::Quad, -32, -32, 64, 64, 64))
+    ///     .expect("the area is inside of the cache");
+    ///
+    /// assert_eq!(view.biome_at(32, 0, 32), cache.biome_at(128, 0, 128));
+    /// assert_eq!(view.histogram().values().sum::<u64>(), 64 * 64);
+    ///
+    /// let image = view.to_image();
+    /// assert_eq!((image.width(), image.height()), (64, 64));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedScale`] if the area isn't at the
+    /// scale of the cache, and [`GeneratorError::IndexOutOfBounds`] if it
+    /// isn't inside the range of the cache or the cache isn't filled
+    pub fn view(&self, sub_area: Range) -> Result<CacheView<'_>, GeneratorError> {
+        if sub_area.scale != self.range.scale {
+            return Err(GeneratorError::UnsupportedScale(sub_area.scale));
+        }
+
+        // Gets the offset of the view into the cache along an axis, if it
+        // fits into the cache
+        let offset = |start: i32, size: u32, parent_start: i32, parent_size: u32| {
+            usize::try_from(i64::from(start) - i64::from(parent_start))
+                .ok()
+                .filter(|&offset| offset + size as usize <= parent_size as usize)
+                .ok_or(GeneratorError::IndexOutOfBounds)
+        };
+
+        let range = &self.range;
+        let offset = (
+            offset(sub_area.x, sub_area.size_x, range.x, range.size_x)?,
+            offset(sub_area.y, layers(&sub_area), range.y, layers(range))?,
+            offset(sub_area.z, sub_area.size_z, range.z, range.size_z)?,
+        );
+
+        let buffer = self
+            .buffer
+            .get(..self.calculate_readable_cache_length())
+            .ok_or(GeneratorError::IndexOutOfBounds)?;
+
+        Ok(CacheView {
+            buffer,
+            parent_size: (range.size_x as usize, range.size_z as usize),
+            offset,
+            range: sub_area,
+        })
+    }
+}
+
+impl<'cache> CacheView<'cache> {
+    /// Gets the range of the view
+    #[inline]
+    #[must_use]
+    pub fn range(&self) -> &Range {
+        &self.range
+    }
+
+    /// Iterates over the rows of the view, borrowed from the cache
+    ///
+    /// Every row holds the raw biome ids of a line of cells along the x axis.
+    /// The rows are ordered by z and then by y, like the cells of a cache.
+    pub fn rows(&self) -> impl Iterator<Item = &'cache [i32]> + '_ {
+        let (size_x, size_y, size_z) = (
+            self.range.size_x as usize,
+            layers(&self.range) as usize,
+            self.range.size_z as usize,
+        );
+        let (x, y, z) = self.offset;
+
+        (y..y + size_y).flat_map(move |layer| {
+            (z..z + size_z).filter_map(move |row| {
+                let start = self.index(x, layer, row);
+                self.buffer.get(start..start + size_x)
+            })
+        })
+    }
+
+    /// Iterates over the biome of every cell, row by row
+    ///
+    /// Cells which aren't a valid biome are given as [`BiomeID::none`].
+    pub fn biomes(&self) -> impl Iterator<Item = BiomeID> + '_ {
+        self.rows()
+            .flatten()
+            .map(|&raw_biomeid| BiomeID::from_id(raw_biomeid).unwrap_or(BiomeID::none))
+    }
+
+    /// Gets the biome of a cell, relative to the upper left corner of the
+    /// view
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the cell is outside of
+    /// the view
+    pub fn biome_at(&self, x: u32, y: u32, z: u32) -> Result<BiomeID, GeneratorError> {
+        if x >= self.range.size_x || z >= self.range.size_z || y >= layers(&self.range) {
+            return Err(GeneratorError::IndexOutOfBounds);
+        }
+
+        let (offset_x, offset_y, offset_z) = self.offset;
+        let index = self.index(
+            offset_x + x as usize,
+            offset_y + y as usize,
+            offset_z + z as usize,
+        );
+        let raw_biomeid = *self
+            .buffer
+            .get(index)
+            .ok_or(GeneratorError::IndexOutOfBounds)?;
+
+        BiomeID::from_id(raw_biomeid).ok_or(GeneratorError::BiomeIDOutOfRange(raw_biomeid))
+    }
+
+    /// Gets the block position of the upper left corner of the view
+    #[must_use]
+    pub fn origin(&self) -> BlockPosition {
+        BlockPosition::from_scaled(self.range.x, self.range.z, self.range.scale)
+    }
+
+    /// Counts the amount of cells of each biome in the view
+    ///
+    /// See [`Cache::histogram()`].
+    #[must_use]
+    pub fn histogram(&self) -> BTreeMap<BiomeID, u64> {
+        super::stats::histogram(self.rows().flatten())
+    }
+
+    /// Renders the lowest layer of the view into a biome map
+    ///
+    /// See [`Cache::to_image()`].
+    #[must_use]
+    pub fn to_image(&self) -> Image {
+        let colors = new_biome_color_map();
+        let mut image = Image::new(self.range.size_x, self.range.size_z);
+
+        for (z, row) in (0..self.range.size_z).zip(self.rows()) {
+            for (x, &raw_biomeid) in (0..).zip(row) {
+                let color = BiomeID::from_id(raw_biomeid)
+                    .and_then(|biome| colors.get(&biome).copied())
+                    .unwrap_or_default();
+
+                image.set_pixel(x, z, color);
+            }
+        }
+
+        image
+    }
+
+    /// Gets the index of a cell of the cache in its buffer
+    fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        let (width, depth) = self.parent_size;
+        (y * depth + z) * width + x
+    }
+}
+
+/// Gets the amount of y levels of a range, which is 1 for planes
+fn layers(range: &Range) -> u32 {
+    range.size_y.max(1)
+}
@@ -0,0 +1,234 @@
+use super::{error::GeneratorError, BlockPosition3D, Cache, Generator, QuadPosition, Range, Scale};
+use crate::enums::{BiomeID, Dimension, MCVersion};
+
+/// Gets the voronoi seed of a world seed
+///
+/// Since 1.15 the voronoi noise used for zooming biomes from 1:4 to block
+/// scale is seeded with the first 8 bytes of the SHA-256 hash of the world
+/// seed, which is what minecraft calls the obfuscated or hashed seed. The same
+/// value is sent to clients when joining a world.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{voronoi_sha, Generator, GeneratorFlags};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// assert_eq!(generator.voronoi_sha(), voronoi_sha(-5134222539607995087));
+/// ```
+#[must_use]
+pub fn voronoi_sha(seed: i64) -> u64 {
+    // SAFETY:
+    // The function only hashes the given seed
+    unsafe { cubiomes_sys::getVoronoiSHA(seed.cast_unsigned()) }
+}
+
+impl Generator {
+    /// Gets the voronoi seed of the applied seed
+    ///
+    /// This is the same as [`voronoi_sha()`] of [`Self::seed()`] since 1.15
+    /// and 0 for older versions, which don't use the voronoi seed.
+    #[must_use]
+    pub fn voronoi_sha(&self) -> u64 {
+        // SAFETY:
+        // The generator pointer can't be null as its been initialized
+        // when constructing this struct
+        unsafe { (*self.generator).sha }
+    }
+
+    /// Gets the 1:4 cell which the biome of a block is taken from
+    ///
+    /// Since 1.15 biomes are generated at a 1:4 scale, and the biome of each
+    /// block is picked from one of the neighbouring 1:4 cells with voronoi
+    /// noise. This maps a block coordinate to its source cell exactly like
+    /// minecraft does, so block accurate biomes can be looked up from a cache
+    /// generated at [`Scale::Quad`], see [`Cache::voronoi_biome_at()`].
+    ///
+    /// The returned cell is in 1:4 coordinates, including y.
+    ///
+    /// Returns [`None`] before 1.15, where the voronoi zoom is a 2d layer of
+    /// the generator itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let cell = generator
+    ///     .voronoi_cell((100, 64, -100))
+    ///     .expect("1.20 uses voronoi access");
+    ///
+    /// // The source cell is always next to the cell containing the block
+    /// assert!((cell.x - 100 / 4).abs() <= 1);
+    /// assert!((cell.y - 64 / 4).abs() <= 1);
+    /// assert!((cell.z - -100 / 4).abs() <= 1);
+    /// ```
+    #[must_use]
+    pub fn voronoi_cell(&self, pos: impl Into<BlockPosition3D>) -> Option<QuadPosition> {
+        let pos = pos.into();
+        if self.minecraft_version() < MCVersion::MC_1_15_2 {
+            return None;
+        }
+
+        let (mut x4, mut y4, mut z4) = (0, 0, 0);
+
+        // SAFETY:
+        // The outputs point to valid integers
+        unsafe {
+            cubiomes_sys::voronoiAccess3D(
+                self.voronoi_sha(),
+                pos.x,
+                pos.y,
+                pos.z,
+                &mut x4,
+                &mut y4,
+                &mut z4,
+            );
+        }
+
+        Some(QuadPosition::new(x4, y4, z4))
+    }
+}
+
+impl Range {
+    /// Gets the 1:4 range which the voronoi access of this range can read
+    ///
+    /// Every block of a [`Scale::Block`] range takes its biome from a cell
+    /// inside the returned range, so a cache of the returned range can be used
+    /// with [`Cache::voronoi_biome_at()`] for every block of this range. This
+    /// matches `getVoronoiSrcRange` of cubiomes, except a `size_y` of 0 is
+    /// treated like 1, as the voronoi access is always 3d.
+    ///
+    /// Returns [`None`] if the range isn't at [`Scale::Block`]
+    #[must_use]
+    pub fn voronoi_source(&self) -> Option<Range> {
+        if self.scale != Scale::Block {
+            return None;
+        }
+
+        let x = self.x - 2;
+        let z = self.z - 2;
+        let y = self.y - 2;
+
+        let source_x = x >> 2;
+        let source_z = z >> 2;
+        let source_y = y >> 2;
+
+        Some(Range {
+            scale: Scale::Quad,
+            x: source_x,
+            z: source_z,
+            size_x: (((x + self.size_x as i32) >> 2) - source_x + 2) as u32,
+            size_z: (((z + self.size_z as i32) >> 2) - source_z + 2) as u32,
+            y: source_y,
+            size_y: (((y + self.size_y.max(1) as i32) >> 2) - source_y + 2) as u32,
+        })
+    }
+}
+
+impl Cache<'_> {
+    /// Gets the biome of a block from a filled cache at [`Scale::Quad`]
+    ///
+    /// The block is mapped to its source cell with
+    /// [`Generator::voronoi_cell()`], so the biome is the same as generating
+    /// the block at [`Scale::Block`]. Generating at 1:4 and looking up single
+    /// blocks is a lot cheaper than generating at 1:1, for example when block
+    /// accuracy is only needed near biome borders.
+    ///
+    /// The position is in blocks, like for [`Generator::get_biome_at()`]. A cache of
+    /// [`Range::voronoi_source()`] covers every block of the original range.
+    /// Note that the source cells can be above or below the block, so the
+    /// cache should have a `size_y` of at least 2.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let blocks = Range {
+    ///     scale: Scale::Block,
+    ///     x: 0,
+    ///     z: 0,
+    ///     size_x: 64,
+    ///     size_z: 64,
+    ///     y: 64,
+    ///     size_y: 1,
+    /// };
+    ///
+    /// let source = blocks.voronoi_source().expect("the range is at block scale");
+    /// let mut cache = Cache::new(&generator, source);
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// assert_eq!(
+    ///     cache.voronoi_biome_at((10, 64, 20)),
+    ///     generator.get_biome_at((10, 64, 20))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::NoVoronoiAccess`] if the cache isn't at
+    /// [`Scale::Quad`] or the block scale biomes of the generator aren't
+    /// generated with the 3d voronoi access. This is the case in the end and
+    /// in the overworld before 1.18, which cubiomes zooms with a planar
+    /// version of the voronoi noise. Returns
+    /// [`GeneratorError::IndexOutOfBounds`] if the source cell isn't inside the
+    /// cache.
+    pub fn voronoi_biome_at(
+        &self,
+        pos: impl Into<BlockPosition3D>,
+    ) -> Result<BiomeID, GeneratorError> {
+        if self.range.scale != Scale::Quad {
+            return Err(GeneratorError::NoVoronoiAccess);
+        }
+
+        // The layered overworld before 1.18 and the end are zoomed with a
+        // planar version of the voronoi noise in cubiomes
+        let planar = match self.generator.dimension() {
+            Dimension::DIM_NETHER => false,
+            Dimension::DIM_OVERWORLD => self.generator.minecraft_version() < MCVersion::MC_1_18_2,
+            _ => true,
+        };
+
+        if planar {
+            return Err(GeneratorError::NoVoronoiAccess);
+        }
+
+        let cell = self
+            .generator
+            .voronoi_cell(pos)
+            .ok_or(GeneratorError::NoVoronoiAccess)?;
+
+        let local = |coord: i32, start: i32, size: u32| {
+            u32::try_from(coord - start)
+                .ok()
+                .filter(|local| *local < size.max(1))
+                .ok_or(GeneratorError::IndexOutOfBounds)
+        };
+
+        self.biome_at(
+            local(cell.x, self.range.x, self.range.size_x)?,
+            local(cell.y, self.range.y, self.range.size_y)?,
+            local(cell.z, self.range.z, self.range.size_z)?,
+        )
+    }
+}
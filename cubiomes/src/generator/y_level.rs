@@ -0,0 +1,214 @@
+use super::{
+    error::{GeneratorError, ParseYLevelError},
+    BlockPosition, Cache, Generator, Range,
+};
+use crate::enums::BiomeID;
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A y coordinate, either in blocks or named by what it is in the world
+///
+/// The named levels are resolved for the version and dimension of the
+/// generator with [`Self::resolve()`], so the right height doesn't have to be
+/// looked up for every version. Every query taking a `impl Into<YLevel>` also
+/// takes a plain `i32`, which is [`Self::Absolute`].
+///
+/// With the `serde` feature, the named levels are serialized as `sea_level`,
+/// `surface` and `build_limit`, and absolute levels as numbers. The same
+/// names are parsed by [`FromStr`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{Generator, GeneratorFlags, YLevel};
+///
+/// let old = Generator::new(MCVersion::MC_1_16_5, 0, Dimension::DIM_OVERWORLD, GeneratorFlags::empty());
+/// let new = Generator::new(MCVersion::MC_1_20_6, 0, Dimension::DIM_OVERWORLD, GeneratorFlags::empty());
+///
+/// assert_eq!(YLevel::BuildLimit.resolve(&old, (0, 0)), Ok(255));
+/// assert_eq!(YLevel::BuildLimit.resolve(&new, (0, 0)), Ok(319));
+/// assert_eq!("sea_level".parse(), Ok(YLevel::SeaLevel));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "YLevelRepr", into = "YLevelRepr")
+)]
+pub enum YLevel {
+    /// The surface of the oceans, or of the lava oceans in the nether
+    ///
+    /// See [`super::WorldLimits::sea_level`].
+    SeaLevel,
+    /// The surface of the block column, see [`Generator::surface_y()`]
+    Surface,
+    /// The highest y blocks can be placed at
+    ///
+    /// See [`super::WorldLimits::max_y()`].
+    BuildLimit,
+    /// A y in blocks
+    Absolute(i32),
+}
+
+impl YLevel {
+    /// Gets the y in blocks of the level in a block column
+    ///
+    /// The column is only needed for [`Self::Surface`].
+    ///
+    /// # Errors
+    /// Returns an error if approximating the surface height fails
+    pub fn resolve(
+        self,
+        generator: &Generator,
+        pos: impl Into<BlockPosition>,
+    ) -> Result<i32, GeneratorError> {
+        match self {
+            Self::SeaLevel => Ok(generator.world_limits().sea_level),
+            Self::Surface => generator.surface_y(pos),
+            Self::BuildLimit => Ok(generator.world_limits().max_y()),
+            Self::Absolute(y) => Ok(y),
+        }
+    }
+}
+
+impl From<i32> for YLevel {
+    fn from(y: i32) -> Self {
+        Self::Absolute(y)
+    }
+}
+
+impl Display for YLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SeaLevel => f.write_str("sea_level"),
+            Self::Surface => f.write_str("surface"),
+            Self::BuildLimit => f.write_str("build_limit"),
+            Self::Absolute(y) => write!(f, "{y}"),
+        }
+    }
+}
+
+impl FromStr for YLevel {
+    type Err = ParseYLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "sea_level" => Ok(Self::SeaLevel),
+            "surface" => Ok(Self::Surface),
+            "build_limit" => Ok(Self::BuildLimit),
+            y => y
+                .parse()
+                .map(Self::Absolute)
+                .map_err(|_| ParseYLevelError(s.into())),
+        }
+    }
+}
+
+/// The serialized form of a [`YLevel`]
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum YLevelRepr {
+    Absolute(i32),
+    Named(NamedYLevel),
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum NamedYLevel {
+    SeaLevel,
+    Surface,
+    BuildLimit,
+}
+
+#[cfg(feature = "serde")]
+impl From<YLevelRepr> for YLevel {
+    fn from(repr: YLevelRepr) -> Self {
+        match repr {
+            YLevelRepr::Absolute(y) => Self::Absolute(y),
+            YLevelRepr::Named(NamedYLevel::SeaLevel) => Self::SeaLevel,
+            YLevelRepr::Named(NamedYLevel::Surface) => Self::Surface,
+            YLevelRepr::Named(NamedYLevel::BuildLimit) => Self::BuildLimit,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<YLevel> for YLevelRepr {
+    fn from(level: YLevel) -> Self {
+        match level {
+            YLevel::SeaLevel => Self::Named(NamedYLevel::SeaLevel),
+            YLevel::Surface => Self::Named(NamedYLevel::Surface),
+            YLevel::BuildLimit => Self::Named(NamedYLevel::BuildLimit),
+            YLevel::Absolute(y) => Self::Absolute(y),
+        }
+    }
+}
+
+impl Generator {
+    /// Gets the biome of a block column at a y level
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, YLevel};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// assert_eq!(
+    ///     generator.biome_at_level((512, -512), YLevel::Surface),
+    ///     generator.surface_biome((512, -512))
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if resolving the level or getting the biome fails
+    pub fn biome_at_level(
+        &self,
+        pos: impl Into<BlockPosition>,
+        y: impl Into<YLevel>,
+    ) -> Result<BiomeID, GeneratorError> {
+        let pos = pos.into();
+        self.get_biome_at(pos.at_y(y.into().resolve(self, pos)?))
+    }
+
+    /// Generates the biomes of a 2d range at a y level into a cache
+    ///
+    /// The y of the range is replaced by the level. [`YLevel::Surface`] is
+    /// resolved for every column like [`Self::surface_biomes()`], the other
+    /// levels are the same for the whole range.
+    ///
+    /// # Errors
+    /// Returns an error if resolving the level or generating the biomes fails
+    pub fn biomes_at_level(
+        &self,
+        range: Range,
+        y: impl Into<YLevel>,
+    ) -> Result<Cache<'_>, GeneratorError> {
+        let range = Range { size_y: 0, ..range };
+
+        let y = match y.into() {
+            YLevel::Surface => return self.surface_biomes(range),
+            level => level.resolve(self, BlockPosition::new(0, 0))?,
+        };
+
+        let mut cache = Cache::new(
+            self,
+            Range {
+                y: range.scale.scale_y(y),
+                ..range
+            },
+        );
+        cache.fill_cache()?;
+
+        Ok(cache)
+    }
+}
@@ -0,0 +1,29 @@
+//! Errors related to [`super::LayerStack`] and [`super::Layer`]
+
+use crate::enums::MCVersion;
+use thiserror::Error;
+
+/// An error with the layered generation
+#[derive(Error, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum LayerError {
+    /// The version doesn't generate biomes with layers
+    ///
+    /// Only the overworld of versions from beta 1.8 to 1.17 is layered.
+    #[error("Version {0:?} doesn't use layered biome generation")]
+    UnsupportedVersion(MCVersion),
+    /// The size of the area is 0 or doesn't fit an i32, or the area is too
+    /// big or too far out for cubiomes
+    #[error("The size of the area is out of bounds")]
+    InvalidSize,
+    /// Failed to generate the area
+    ///
+    /// Cubiomes function genArea returned a non 0 exit code.
+    #[error("Function genArea failed with error code {0}")]
+    GenAreaFailure(i32),
+    /// The layer produced something that is not a valid biomeid
+    ///
+    /// Only some of the layers produce biomes, see
+    /// [`super::Layer::gen_biomes()`].
+    #[error("Biome id {0} is out of range and is not a valid biomeid")]
+    BiomeIDOutOfRange(i32),
+}
@@ -0,0 +1,354 @@
+//! Module containing the layered biome generation of versions up to 1.17
+//!
+//! Until 1.18 the overworld biomes were generated with a stack of layers,
+//! which each zoom, modify or merge the output of their parent layers. The
+//! [`crate::generator::Generator`] only gives access to the final biomes of a
+//! few layers, while this module gives access to every [`Layer`] of a
+//! [`LayerStack`], for example to only generate the rivers.
+//!
+//! The output of a layer isn't always a biome id. For example the river layer
+//! produces either [`crate::enums::BiomeID::river`] or -1 and the early land
+//! layers produce 0 for ocean and 1 for land, so the output is returned as is.
+//!
+//! # Examples
+//! ```
+//! use cubiomes::enums::MCVersion;
+//! use cubiomes::layers::{LayerId, LayerStack};
+//!
+//! let mut stack = LayerStack::new(MCVersion::MC_1_16_5, false).expect("1.16 is layered");
+//! stack.set_seed(-5134222539607995087);
+//!
+//! let rivers = stack
+//!     .layer(LayerId::L_RIVER_4)
+//!     .expect("1.16 has a river layer")
+//!     .gen_area(0, 0, 64, 64)
+//!     .expect("failed to generate rivers");
+//!
+//! assert_eq!(rivers.len(), 64 * 64);
+//! ```
+
+use crate::enums::{BiomeID, MCVersion};
+use crate::generator::{Generator, Scale};
+use cubiomes_sys::enums::Dimension;
+use error::LayerError;
+use std::fmt::Debug;
+
+pub use cubiomes_sys::LayerId;
+
+pub mod error;
+#[cfg(test)]
+mod tests;
+
+/// The overworld layer stack of a version up to 1.17
+///
+/// Unlike the [`Generator`] the layers of the stack can be seeded separately
+/// with [`Self::set_layer_seed()`].
+pub struct LayerStack {
+    stack: Box<cubiomes_sys::LayerStack>,
+    version: MCVersion,
+}
+
+impl Debug for LayerStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayerStack")
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
+
+// SAFETY: The layers only refer to each other inside the box, which is
+// exclusive to this instance, so sending it to another thread is safe.
+unsafe impl Send for LayerStack {}
+
+// SAFETY: Generating an area only reads the layers. Seeding them requires an
+// exclusive reference.
+unsafe impl Sync for LayerStack {}
+
+impl LayerStack {
+    /// Creates the layer stack of a version
+    ///
+    /// The layers are not seeded, use [`Self::set_seed()`] before generating.
+    ///
+    /// # Errors
+    /// Returns [`LayerError::UnsupportedVersion`] if the version doesn't
+    /// generate biomes with layers, which is every version before beta 1.8 and
+    /// since 1.18
+    pub fn new(version: MCVersion, large_biomes: bool) -> Result<Self, LayerError> {
+        if !is_layered(version) {
+            return Err(LayerError::UnsupportedVersion(version));
+        }
+
+        // SAFETY:
+        // setupLayerStack fully initializes the stack before assuming so. The
+        // layers refer to each other inside the box, which is never moved out
+        // of.
+        let stack = unsafe {
+            let mut stack = Box::<cubiomes_sys::LayerStack>::new_uninit();
+            cubiomes_sys::setupLayerStack(
+                stack.as_mut_ptr(),
+                version as i32,
+                i32::from(large_biomes),
+            );
+            stack.assume_init()
+        };
+
+        Ok(Self { stack, version })
+    }
+
+    /// Gets the version of the layer stack
+    #[must_use]
+    pub fn minecraft_version(&self) -> MCVersion {
+        self.version
+    }
+
+    /// Applies a world seed to every layer of the stack
+    pub fn set_seed(&mut self, seed: i64) {
+        let entry = self.stack.entry_1;
+
+        // SAFETY:
+        // The entry layer points inside the stack, which is initialized.
+        // Seeding it seeds every layer it depends on, which is all of them.
+        unsafe { cubiomes_sys::setLayerSeed(entry, seed.cast_unsigned()) }
+    }
+
+    /// Applies a world seed to a single layer and the layers it depends on
+    ///
+    /// Returns false without seeding if the version doesn't use the layer
+    pub fn set_layer_seed(&mut self, layer: LayerId, seed: i64) -> bool {
+        let Some(raw) = self.stack.layers.get_mut(layer.0 as usize) else {
+            return false;
+        };
+
+        if raw.getMap.is_none() {
+            return false;
+        }
+
+        // SAFETY:
+        // The layer is set up, so its parents point inside the stack
+        unsafe { cubiomes_sys::setLayerSeed(raw, seed.cast_unsigned()) }
+
+        true
+    }
+
+    /// Gets a layer of the stack
+    ///
+    /// Returns [`None`] if the version doesn't use the layer
+    #[must_use]
+    pub fn layer(&self, layer: LayerId) -> Option<Layer<'_>> {
+        Layer::new(self.stack.layers.get(layer.0 as usize)?)
+    }
+
+    /// Gets the last layer of the stack at a scale
+    ///
+    /// These are the layers the [`Generator`] generates caches with, see
+    /// [`Generator::layer_for_scale()`].
+    #[must_use]
+    pub fn entry(&self, scale: Scale) -> Option<Layer<'_>> {
+        let entry = match scale {
+            Scale::Block => self.stack.entry_1,
+            Scale::Quad => self.stack.entry_4,
+            Scale::Chunk => self.stack.entry_16,
+            Scale::QuadChunk => self.stack.entry_64,
+            Scale::HalfRegion => self.stack.entry_256,
+        };
+
+        // SAFETY:
+        // The entries are either null or point to layers inside the stack
+        Layer::new(unsafe { entry.as_ref()? })
+    }
+}
+
+impl Generator {
+    /// Gets a layer of the layer stack used by the generator
+    ///
+    /// The layer is seeded with the seed of the generator.
+    ///
+    /// Returns [`None`] if the generator isn't a layered overworld generator
+    /// or the version doesn't use the layer
+    #[must_use]
+    pub fn layer(&self, layer: LayerId) -> Option<Layer<'_>> {
+        if !self.is_layered_overworld() {
+            return None;
+        }
+
+        // SAFETY:
+        // The generator pointer can't be null as its been initialized when
+        // constructing this struct, and its layer stack is set up
+        Layer::new(unsafe {
+            (*self.as_ptr())
+                .__bindgen_anon_1
+                .__bindgen_anon_1
+                .ls
+                .layers
+                .get(layer.0 as usize)?
+        })
+    }
+
+    /// Gets the layer the generator generates caches of a scale with
+    ///
+    /// Returns [`None`] if the generator isn't a layered overworld generator
+    #[must_use]
+    pub fn layer_for_scale(&self, scale: Scale) -> Option<Layer<'_>> {
+        if !self.is_layered_overworld() {
+            return None;
+        }
+
+        // SAFETY:
+        // The generator is initialized, and the returned layer is either null
+        // or points inside the generator
+        Layer::new(unsafe { cubiomes_sys::getLayerForScale(self.as_ptr(), scale as i32).as_ref()? })
+    }
+
+    fn is_layered_overworld(&self) -> bool {
+        self.dimension() == Dimension::DIM_OVERWORLD && is_layered(self.minecraft_version())
+    }
+}
+
+/// A single layer of a [`LayerStack`] or a [`Generator`]
+#[derive(Clone, Copy)]
+pub struct Layer<'stack> {
+    layer: &'stack cubiomes_sys::Layer,
+}
+
+impl Debug for Layer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Layer")
+            .field("scale", &self.scale())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'stack> Layer<'stack> {
+    fn new(layer: &'stack cubiomes_sys::Layer) -> Option<Self> {
+        layer.getMap.is_some().then_some(Self { layer })
+    }
+
+    /// Gets the scale of the layer
+    ///
+    /// One cell of the layer is `scale` by `scale` blocks. This goes up to
+    /// 1:4096 for the first layers, so it isn't a [`Scale`].
+    #[must_use]
+    pub fn scale(&self) -> u32 {
+        self.layer.scale.cast_unsigned()
+    }
+
+    /// Gets the layer this layer is generated from
+    ///
+    /// Returns [`None`] for the first layer of the stack
+    #[must_use]
+    pub fn parent(&self) -> Option<Layer<'stack>> {
+        // SAFETY:
+        // The parents are either null or point to layers in the same stack
+        Layer::new(unsafe { self.layer.p.as_ref()? })
+    }
+
+    /// Gets the second layer this layer is generated from
+    ///
+    /// Only layers merging two branches of the stack, like the river mix
+    /// layer, have a second parent.
+    #[must_use]
+    pub fn second_parent(&self) -> Option<Layer<'stack>> {
+        // SAFETY:
+        // The parents are either null or point to layers in the same stack
+        Layer::new(unsafe { self.layer.p2.as_ref()? })
+    }
+
+    /// Generates an area of the layer
+    ///
+    /// The area is given in cells of the layer's scale. The output is indexed
+    /// `x + z * size_x`.
+    ///
+    /// # Errors
+    /// Returns [`LayerError::InvalidSize`] if either size is 0 or doesn't fit
+    /// an i32, or the area is too big or too far out to be given to cubiomes,
+    /// and [`LayerError::GenAreaFailure`] if cubiomes fails to generate
+    /// the area.
+    pub fn gen_area(
+        &self,
+        x: i32,
+        z: i32,
+        size_x: u32,
+        size_z: u32,
+    ) -> Result<Vec<i32>, LayerError> {
+        let (Ok(width), Ok(height)) = (i32::try_from(size_x), i32::try_from(size_z)) else {
+            return Err(LayerError::InvalidSize);
+        };
+
+        if width == 0
+            || height == 0
+            || u64::from(size_x) * u64::from(size_z) > crate::generator::validate::MAX_CELLS
+            || x.checked_add(width).is_none()
+            || z.checked_add(height).is_none()
+        {
+            return Err(LayerError::InvalidSize);
+        }
+
+        // SAFETY:
+        // The layer is set up and the size is positive
+        let length = unsafe { cubiomes_sys::getMinLayerCacheSize(self.layer, width, height) };
+        let mut out = vec![0; length];
+
+        // SAFETY:
+        // The buffer is as big as cubiomes requires for the area
+        let result =
+            unsafe { cubiomes_sys::genArea(self.layer, out.as_mut_ptr(), x, z, width, height) };
+
+        if result != 0 {
+            return Err(LayerError::GenAreaFailure(result));
+        }
+
+        out.truncate(size_x as usize * size_z as usize);
+        Ok(out)
+    }
+
+    /// Generates an area of a layer which produces biomes
+    ///
+    /// This is the same as [`Self::gen_area()`], except the output is checked
+    /// to be biomes. The entry layers of every scale, which
+    /// [`Generator::layer_for_scale()`] gives, produce biomes. Generating at a
+    /// coarse scale first and refining at finer scales only where needed
+    /// is a lot faster than generating everything at a fine scale.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags, Scale};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_12_2,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// // A quick overview of 4096 by 4096 blocks
+    /// let preview = generator
+    ///     .layer_for_scale(Scale::HalfRegion)
+    ///     .expect("1.12 is layered")
+    ///     .gen_biomes(-8, -8, 16, 16)
+    ///     .expect("failed to generate biomes");
+    ///
+    /// assert_eq!(preview.len(), 16 * 16);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::gen_area()`], and
+    /// [`LayerError::BiomeIDOutOfRange`] if the layer produces something else
+    /// than biomes.
+    pub fn gen_biomes(
+        &self,
+        x: i32,
+        z: i32,
+        size_x: u32,
+        size_z: u32,
+    ) -> Result<Vec<BiomeID>, LayerError> {
+        self.gen_area(x, z, size_x, size_z)?
+            .into_iter()
+            .map(|id| BiomeID::from_id(id).ok_or(LayerError::BiomeIDOutOfRange(id)))
+            .collect()
+    }
+}
+
+fn is_layered(version: MCVersion) -> bool {
+    (MCVersion::MC_B1_8..=MCVersion::MC_1_17_1).contains(&version)
+}
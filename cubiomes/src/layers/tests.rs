@@ -0,0 +1,121 @@
+use super::{error::LayerError, LayerId, LayerStack};
+use crate::enums::*;
+use crate::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+
+const SEED: i64 = -5134222539607995087;
+
+#[test]
+fn layer_stack_matches_generator() {
+    let mut stack = LayerStack::new(MCVersion::MC_1_16_5, false).expect("1.16 is layered");
+    stack.set_seed(SEED);
+
+    let generator = Generator::new(
+        MCVersion::MC_1_16_5,
+        SEED,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    for scale in [
+        Scale::Block,
+        Scale::Quad,
+        Scale::Chunk,
+        Scale::QuadChunk,
+        Scale::HalfRegion,
+    ] {
+        let mut cache = Cache::new(
+            &generator,
+            Range {
+                scale,
+                x: -20,
+                z: 13,
+                size_x: 40,
+                size_z: 24,
+                y: 0,
+                size_y: 0,
+            },
+        );
+        cache.fill_cache().expect("failed to fill cache");
+
+        let entry = stack.entry(scale).expect("every scale has an entry");
+        let from_generator = generator
+            .layer_for_scale(scale)
+            .expect("the generator is layered");
+
+        assert_eq!(entry.scale(), scale as u32);
+        assert_eq!(entry.gen_area(-20, 13, 40, 24).as_ref(), Ok(cache.as_vec()));
+        assert_eq!(
+            from_generator.gen_area(-20, 13, 40, 24).as_ref(),
+            Ok(cache.as_vec())
+        );
+
+        let biomes = from_generator
+            .gen_biomes(-20, 13, 40, 24)
+            .expect("entry layers produce biomes");
+        assert_eq!(
+            biomes[41],
+            cache.biome_at(1, 0, 1).expect("inside the cache")
+        );
+    }
+
+    let modern = Generator::new(
+        MCVersion::MC_1_20_6,
+        SEED,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    assert!(modern.layer_for_scale(Scale::Quad).is_none());
+    assert!(matches!(
+        LayerStack::new(MCVersion::MC_1_18_2, false),
+        Err(LayerError::UnsupportedVersion(MCVersion::MC_1_18_2))
+    ));
+}
+
+#[test]
+fn layers_are_seeded_separately() {
+    let mut full = LayerStack::new(MCVersion::MC_1_12_2, false).expect("1.12 is layered");
+    full.set_seed(SEED);
+
+    let mut rivers_only = LayerStack::new(MCVersion::MC_1_12_2, false).expect("1.12 is layered");
+    assert!(rivers_only.set_layer_seed(LayerId::L_RIVER_4, SEED));
+
+    let river = full.layer(LayerId::L_RIVER_4).expect("1.12 has rivers");
+    let rivers = river
+        .gen_area(0, 0, 128, 128)
+        .expect("failed to generate rivers");
+
+    dbg!(river);
+
+    assert_eq!(river.scale(), 4);
+    assert!(rivers.contains(&(BiomeID::river as i32)));
+    assert!(rivers
+        .iter()
+        .all(|&cell| cell == -1 || cell == BiomeID::river as i32));
+    assert_eq!(
+        rivers_only
+            .layer(LayerId::L_RIVER_4)
+            .expect("1.12 has rivers")
+            .gen_area(0, 0, 128, 128),
+        Ok(rivers)
+    );
+
+    // The river mix merges the biomes with the rivers
+    let mix = full
+        .layer(LayerId::L_RIVER_MIX_4)
+        .expect("1.12 mixes rivers");
+    assert!(mix.parent().is_some() && mix.second_parent().is_some());
+
+    // Bamboo jungles were added in 1.14
+    assert!(full.layer(LayerId::L_BAMBOO_256).is_none());
+    assert!(!rivers_only.set_layer_seed(LayerId::L_BAMBOO_256, SEED));
+    assert_eq!(river.gen_area(0, 0, 0, 16), Err(LayerError::InvalidSize));
+
+    // The river noise is a random number for every cell
+    let noise = full
+        .layer(LayerId::L_NOISE_256)
+        .expect("1.12 has river noise");
+    assert!(matches!(
+        noise.gen_biomes(0, 0, 16, 16),
+        Err(LayerError::BiomeIDOutOfRange(_))
+    ));
+}
@@ -12,18 +12,74 @@
 //! See each module for usage of a specific feature of the library.
 //!
 //! - For biome generation see [`crate::generator`]
+//! - For the layers of the biome generation up to 1.17 see [`crate::layers`]
 //! - For structure geneartion see [`crate::structures`]
+//! - For rendering images see [`crate::render`]
+//! - For the random number generators of java edition see [`crate::rng`]
+//! - For seed searches see [`crate::search`]
+//! - For following and cancelling long operations see [`crate::progress`]
+//! - For counting the work done by the generator see `crate::metrics`, with
+//!   the `metrics` feature
 //!
+//! # Wasm
+//! The crate builds for `wasm32-wasip1`, for example for running biome previews
+//! in a browser with a wasi shim. Cubiomes is compiled with the clang and
+//! sysroot of the [wasi-sdk](https://github.com/WebAssembly/wasi-sdk), which are
+//! given with the `CC_wasm32_wasip1` and `WASI_SYSROOT` environment variables.
+//!
+//! `wasm32-unknown-unknown` isn't supported, as cubiomes needs a C standard
+//! library. Without threads searches run on the calling thread, and the quad
+//! structure searches of cubiomes aren't available in the bindings.
+//!
+//! # Cubiomes revision
+//! The crate builds the cubiomes vendored in cubiomes-sys. To try another
+//! revision, like the master branch of cubiomes, set `CUBIOMES_SRC_DIR` to a
+//! checkout of it and enable the `bindgen` feature, as its bindings can differ
+//! from the pregenerated ones. [`cubiomes_version()`] tells which revision a
+//! build uses.
+//!
+//! # Tracing
+//! With the `tracing` feature, filling caches and running searches are
+//! instrumented with [tracing](https://docs.rs/tracing) spans and events. The
+//! spans and events carry the sizes of the ranges, durations and the seeds per
+//! second of searches. Filling a cache emits at the debug level, seeding a
+//! generator at the trace level and searches at the info level.
+//!
+//! # Panics
+//! The safe api doesn't panic on bad input, like a generator for `MC_UNDEF`,
+//! a range cubiomes can't generate or an index outside of a cache. Errors are
+//! returned instead, and functions which can't fail document what they do
+//! with unusual input, like [`generator::spiral()`] with a step of 0.
+//! Cubiomes itself exits the process for some input, which the crate checks
+//! for before calling it.
+//!
+//! The exceptions are:
+//!
+//! - The async functions must be called inside a tokio runtime, like
+//!   `tokio::task::spawn_blocking()`, they panic otherwise
+//! - Arithmetic on coordinates far outside of the world, close to the limits
+//!   of an i32, may overflow and panic in debug builds
+//! - Panics in callbacks given to the crate are passed on
+//! - Running out of memory aborts, as usual
 
 #![warn(clippy::undocumented_unsafe_blocks)]
 #![warn(missing_docs)]
 #![warn(missing_copy_implementations)]
 #![warn(missing_debug_implementations)]
 #![warn(clippy::unwrap_used)]
+#![cfg_attr(not(test), warn(clippy::expect_used, clippy::panic))]
 
 pub use cubiomes_sys::enums;
+pub use cubiomes_sys::{cubiomes_version, CubiomesVersion};
 
 pub mod generator;
+pub mod layers;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod progress;
+pub mod render;
+pub mod rng;
+pub mod search;
 pub mod structures;
 
 #[cfg(test)]
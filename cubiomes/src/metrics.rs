@@ -0,0 +1,155 @@
+//! Module containing counters of the work done by the generator
+//!
+//! With the `metrics` feature, every cache fill, tile and searched seed is
+//! counted process wide. The counters can be read at any time as
+//! [`GenerationMetrics`] with [`snapshot()`], so different strategies for
+//! generating the same area can be compared without an external profiler.
+//!
+//! The counters are shared by every thread, so the metrics of one operation
+//! are best taken as the difference of a snapshot before and after it, see
+//! [`GenerationMetrics::since()`].
+//!
+//! # Examples
+//! ```
+//! use cubiomes::enums::{Dimension, MCVersion};
+//! use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+//! use cubiomes::metrics;
+//!
+//! let generator = Generator::new(
+//!     MCVersion::MC_1_20_6,
+//!     -5134222539607995087,
+//!     Dimension::DIM_OVERWORLD,
+//!     GeneratorFlags::empty(),
+//! );
+//!
+//! let before = metrics::snapshot();
+//!
+//! let mut cache = Cache::new(&generator, Range {
+//!     scale: Scale::Quad,
+//!     x: 0,
+//!     z: 0,
+//!     size_x: 64,
+//!     size_z: 64,
+//!     y: 16,
+//!     size_y: 0,
+//! });
+//! cache.fill_cache().expect("failed to fill cache");
+//!
+//! let used = metrics::snapshot().since(&before);
+//! assert_eq!((used.caches_filled, used.cells_generated), (1, 64 * 64));
+//! assert_eq!(used.average_latency(), Some(used.generation_time));
+//! ```
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+static CACHES_FILLED: AtomicU64 = AtomicU64::new(0);
+static CELLS_GENERATED: AtomicU64 = AtomicU64::new(0);
+static GENERATION_NANOS: AtomicU64 = AtomicU64::new(0);
+static TILES_COMPLETED: AtomicU64 = AtomicU64::new(0);
+static SEEDS_EVALUATED: AtomicU64 = AtomicU64::new(0);
+
+/// The work done by the generator, as counted since the start of the process
+/// or the last [`reset()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct GenerationMetrics {
+    /// The amount of times cubiomes filled a cache with genBiomes
+    pub caches_filled: u64,
+    /// The amount of cells generated into caches
+    pub cells_generated: u64,
+    /// The total time spent in genBiomes
+    pub generation_time: Duration,
+    /// The amount of tiles generated by
+    /// [`crate::generator::Cache::fill_cache_with_progress()`]
+    pub tiles_completed: u64,
+    /// The amount of seeds checked by searches
+    pub seeds_evaluated: u64,
+}
+
+impl GenerationMetrics {
+    /// Gets the average time genBiomes took to fill a cache
+    ///
+    /// Returns [`None`] if no caches were filled
+    #[must_use]
+    pub fn average_latency(&self) -> Option<Duration> {
+        let fills = u32::try_from(self.caches_filled).unwrap_or(u32::MAX);
+        (fills > 0).then(|| self.generation_time / fills)
+    }
+
+    /// Gets the amount of cells generated per second spent in genBiomes
+    ///
+    /// Returns 0 if no time was spent generating
+    #[must_use]
+    pub fn cells_per_second(&self) -> f64 {
+        let seconds = self.generation_time.as_secs_f64();
+        if seconds == 0.0 {
+            return 0.0;
+        }
+
+        self.cells_generated as f64 / seconds
+    }
+
+    /// Gets the work done since an earlier snapshot
+    ///
+    /// Counters which were [`reset()`] in between saturate at 0.
+    #[must_use]
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            caches_filled: self.caches_filled.saturating_sub(earlier.caches_filled),
+            cells_generated: self.cells_generated.saturating_sub(earlier.cells_generated),
+            generation_time: self.generation_time.saturating_sub(earlier.generation_time),
+            tiles_completed: self.tiles_completed.saturating_sub(earlier.tiles_completed),
+            seeds_evaluated: self.seeds_evaluated.saturating_sub(earlier.seeds_evaluated),
+        }
+    }
+}
+
+/// Gets the current values of the counters
+#[must_use]
+pub fn snapshot() -> GenerationMetrics {
+    GenerationMetrics {
+        caches_filled: CACHES_FILLED.load(Ordering::Relaxed),
+        cells_generated: CELLS_GENERATED.load(Ordering::Relaxed),
+        generation_time: Duration::from_nanos(GENERATION_NANOS.load(Ordering::Relaxed)),
+        tiles_completed: TILES_COMPLETED.load(Ordering::Relaxed),
+        seeds_evaluated: SEEDS_EVALUATED.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets every counter back to 0
+///
+/// This affects every thread, so prefer [`GenerationMetrics::since()`] when
+/// other work may be running.
+pub fn reset() {
+    for counter in [
+        &CACHES_FILLED,
+        &CELLS_GENERATED,
+        &GENERATION_NANOS,
+        &TILES_COMPLETED,
+        &SEEDS_EVALUATED,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Counts a cache filled by genBiomes
+pub(crate) fn record_fill(cells: usize, elapsed: Duration) {
+    CACHES_FILLED.fetch_add(1, Ordering::Relaxed);
+    CELLS_GENERATED.fetch_add(cells as u64, Ordering::Relaxed);
+    GENERATION_NANOS.fetch_add(
+        u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+        Ordering::Relaxed,
+    );
+}
+
+/// Counts a tile of a cache filled tile by tile
+pub(crate) fn record_tile() {
+    TILES_COMPLETED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts a seed checked by a search
+pub(crate) fn record_seed() {
+    SEEDS_EVALUATED.fetch_add(1, Ordering::Relaxed);
+}
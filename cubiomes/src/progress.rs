@@ -0,0 +1,106 @@
+//! Module containing progress reporting and cancellation for long operations
+//!
+//! Filling big caches and running seed searches can take a long time. The
+//! functions for these take a [`CancellationToken`] for stopping the work from
+//! another thread and report their [`Progress`] to a callback. See
+//! [`crate::generator::Cache::fill_cache_with_progress()`] and
+//! [`crate::search::SearchDefinition::run_monitored()`].
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// The progress of a long running operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Progress {
+    /// The amount of work done, for example cells generated or seeds checked
+    pub done: u64,
+    /// The total amount of work
+    pub total: u64,
+    /// The time spent on the work so far
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Gets the fraction of the work done, between 0 and 1
+    ///
+    /// Returns 1 if there is no work to do
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+
+        self.done as f64 / self.total as f64
+    }
+
+    /// Gets the amount of work done per second
+    #[must_use]
+    pub fn per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+
+        if seconds == 0.0 {
+            return 0.0;
+        }
+
+        self.done as f64 / seconds
+    }
+
+    /// Estimates the time left until the work is done
+    ///
+    /// The estimate assumes the rest of the work continues at the same rate.
+    /// Returns [`None`] if nothing has been done yet.
+    #[must_use]
+    pub fn eta(&self) -> Option<Duration> {
+        if self.done == 0 {
+            return None;
+        }
+
+        let left = self.total.saturating_sub(self.done) as f64;
+        Some(self.elapsed.mul_f64(left / self.done as f64))
+    }
+}
+
+/// A token for cancelling long running operations
+///
+/// Clones of the token share the same state, so one clone can be given to the
+/// operation while another one is used to cancel it.
+///
+/// # Examples
+/// ```
+/// use cubiomes::progress::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let worker_token = token.clone();
+///
+/// token.cancel();
+///
+/// assert!(worker_token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, which is not cancelled
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every operation using this token or one of its clones
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks if the token has been cancelled
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
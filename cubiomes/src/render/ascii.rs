@@ -0,0 +1,120 @@
+use crate::enums::BiomeID;
+use crate::generator::{colors::new_biome_color_map, Cache};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+/// The upper half of a character cell, so one line of text shows two rows
+const UPPER_HALF: &str = "\u{2580}";
+
+/// Resets the colors of the terminal
+const RESET: &str = "\x1b[0m";
+
+/// Prints the lowest layer of a cache as colored blocks for a terminal
+///
+/// Every cell is half a character: each line of text shows two rows of the
+/// cache, the upper one as the foreground and the lower one as the
+/// background of a `▀`. The colors are the ones of [`Cache::to_image()`],
+/// written as 24 bit ANSI escape codes, which most terminals support.
+///
+/// The map is followed by a legend of the biomes in it, from the most to the
+/// least common, with the share of the cells each biome covers. Cells with
+/// unknown biomes are drawn black and left out of the legend. A cache
+/// without any cells only gets the empty legend.
+///
+/// Every cell is a character wide, so keep the cache at most as wide as the
+/// terminal, for example by using a coarser [`crate::generator::Scale`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -380434930381432806,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let mut cache = Cache::new(&generator, Range::plane(Scale::QuadChunk, -32, -16, 64, 32, 16));
+/// cache.fill_cache().expect("failed to fill cache");
+///
+/// let mut map = Vec::new();
+/// cubiomes::render::ascii(&cache, &mut map).expect("failed to write map");
+///
+/// // Two rows of cells per line of text
+/// let map = String::from_utf8(map).expect("the map is text");
+/// assert_eq!(map.lines().take_while(|line| !line.is_empty()).count(), 16);
+///
+/// // Or straight to the terminal
+/// cubiomes::render::ascii(&cache, std::io::stdout().lock()).expect("failed to print map");
+/// ```
+///
+/// # Errors
+/// Returns the errors of the writer
+pub fn ascii(cache: &Cache<'_>, mut writer: impl Write) -> io::Result<()> {
+    let colors = new_biome_color_map();
+    let range = cache.range();
+    let width = range.size_x as usize;
+    let cells = &cache.as_vec()[..(width * range.size_z as usize).min(cache.as_vec().len())];
+
+    let mut counts: BTreeMap<BiomeID, u64> = BTreeMap::new();
+    for biome in cells.iter().filter_map(|&raw| BiomeID::from_id(raw)) {
+        *counts.entry(biome).or_default() += 1;
+    }
+
+    let color = |raw: i32| {
+        BiomeID::from_id(raw)
+            .and_then(|biome| colors.get(&biome).copied())
+            .unwrap_or_default()
+    };
+
+    if width == 0 {
+        return ascii_legend(&counts, &colors, writer);
+    }
+
+    for rows in cells.chunks(width * 2) {
+        let (upper, lower) = rows.split_at(width.min(rows.len()));
+
+        for (x, &raw) in upper.iter().enumerate() {
+            let [r, g, b] = color(raw);
+            write!(writer, "\x1b[38;2;{r};{g};{b}m")?;
+
+            // The last row of a cache with an odd height has nothing below it
+            if let Some(&below) = lower.get(x) {
+                let [r, g, b] = color(below);
+                write!(writer, "\x1b[48;2;{r};{g};{b}m")?;
+            }
+
+            write!(writer, "{UPPER_HALF}")?;
+        }
+
+        writeln!(writer, "{RESET}")?;
+    }
+
+    ascii_legend(&counts, &colors, writer)
+}
+
+/// Prints the biomes of a map with their colors, the most common first
+fn ascii_legend(
+    counts: &BTreeMap<BiomeID, u64>,
+    colors: &BTreeMap<BiomeID, [u8; 3]>,
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let total: u64 = counts.values().sum();
+
+    let mut biomes: Vec<(BiomeID, u64)> = counts.iter().map(|(&biome, &n)| (biome, n)).collect();
+    biomes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    writeln!(writer)?;
+    for (biome, count) in biomes {
+        let [r, g, b] = colors.get(&biome).copied().unwrap_or_default();
+        writeln!(
+            writer,
+            "\x1b[38;2;{r};{g};{b}m\u{2588}\u{2588}{RESET} {biome:?} {:.1}%",
+            count as f64 / total as f64 * 100.0
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Errors related to rendering and saving images
+
+use crate::generator::error::GeneratorError;
+use thiserror::Error;
+
+/// An error while rendering or saving images
+#[derive(Error, Debug)]
+pub enum RenderError {
+    /// Generating the biomes for the image failed
+    #[error("Failed to generate biomes: {0}")]
+    GeneratorError(GeneratorError),
+    /// Writing the image failed
+    #[error("Failed to write image: {0}")]
+    Io(std::io::Error),
+    /// Opening or updating the window of a [`super::Viewer`] failed
+    #[cfg(feature = "viewer")]
+    #[error("Failed to show the window: {0}")]
+    Window(minifb::Error),
+}
+
+impl From<GeneratorError> for RenderError {
+    fn from(value: GeneratorError) -> Self {
+        Self::GeneratorError(value)
+    }
+}
+
+impl From<std::io::Error> for RenderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
@@ -0,0 +1,321 @@
+use super::{Image, Map, Rgb};
+use crate::generator::colors::new_biome_color_map;
+
+/// The background of the legend and the scale bar
+const PANEL: Rgb = [255, 255, 255];
+/// The color of text, outlines and the scale bar
+const INK: Rgb = [0, 0, 0];
+
+/// The width of a glyph of the font in font pixels
+const GLYPH_WIDTH: u32 = 3;
+/// The height of a glyph of the font in font pixels
+const GLYPH_HEIGHT: u32 = 5;
+
+/// A tiny font for the legend and the scale bar, with a row of pixels per
+/// string
+///
+/// Lowercase letters are drawn as uppercase ones, and characters without a
+/// glyph are left blank.
+const GLYPHS: [(char, [&str; 5]); 36] = [
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#.#", "###", "###", "#.#", "#.#"]),
+    ('N', ["##.", "#.#", "#.#", "#.#", "#.#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", "##.", ".##"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", "###"]),
+    ('V', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('W', ["#.#", "#.#", "###", "###", "#.#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["##.", "..#", ".#.", "#..", "###"]),
+    ('3', ["##.", "..#", ".#.", "..#", "##."]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "##.", "..#", "##."]),
+    ('6', [".##", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", ".#.", ".#.", ".#."]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "##."]),
+];
+
+/// Options for exporting a [`Map`] with [`Map::export()`]
+///
+/// # Examples
+/// ```
+/// use cubiomes::render::RenderOptions;
+///
+/// let options = RenderOptions {
+///     legend: true,
+///     scale_bar: true,
+///     ..RenderOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderOptions {
+    /// Whether to add a legend of the biomes of the map to the right of it
+    pub legend: bool,
+    /// Whether to draw a bar of a round distance in blocks in the lower left
+    /// corner of the map
+    pub scale_bar: bool,
+    /// The size in pixels of a pixel of the text, at least 1
+    pub text_scale: u32,
+}
+
+impl Default for RenderOptions {
+    /// Creates options without a legend or a scale bar, with a text scale
+    /// of 2
+    fn default() -> Self {
+        Self {
+            legend: false,
+            scale_bar: false,
+            text_scale: 2,
+        }
+    }
+}
+
+impl Map {
+    /// Renders the map with its overlays for exporting
+    ///
+    /// The legend lists a color swatch and the name of every biome of
+    /// [`Self::legend_biomes()`], in a panel to the right of the map, which
+    /// makes the image wider and possibly taller. The scale bar is drawn on
+    /// top of the map and is the longest round distance, like 200 or 5000
+    /// blocks, fitting in a quarter of its width. It is left out if the map
+    /// is too small for it.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Cache, Generator, GeneratorFlags, Range, Scale};
+    /// use cubiomes::render::{Map, RenderOptions};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mut cache = Cache::new(&generator, Range::plane(Scale::Quad, -128, -128, 256, 256, 64));
+    /// cache.fill_cache().expect("failed to fill cache");
+    ///
+    /// let image = Map::from_cache(&cache).export(&RenderOptions {
+    ///     legend: true,
+    ///     scale_bar: true,
+    ///     ..RenderOptions::default()
+    /// });
+    /// assert!(image.width() > 256);
+    /// ```
+    #[must_use]
+    pub fn export(&self, options: &RenderOptions) -> Image {
+        let text_scale = options.text_scale.max(1);
+        let map = self.image();
+
+        let legend = if options.legend && !self.legend_biomes().is_empty() {
+            Some(self.legend(text_scale))
+        } else {
+            None
+        };
+        let (legend_width, legend_height) = legend
+            .as_ref()
+            .map_or((0, 0), |legend| (legend.width(), legend.height()));
+
+        let mut image = filled(
+            map.width() + legend_width,
+            map.height().max(legend_height),
+            PANEL,
+        );
+        paste(&mut image, map, 0, 0);
+        if let Some(legend) = &legend {
+            paste(&mut image, legend, map.width(), 0);
+        }
+
+        if options.scale_bar {
+            self.draw_scale_bar(&mut image, text_scale);
+        }
+
+        image
+    }
+
+    /// Renders the legend panel
+    fn legend(&self, text_scale: u32) -> Image {
+        let colors = new_biome_color_map();
+        let names: Vec<String> = self
+            .legend_biomes()
+            .iter()
+            .map(|biome| format!("{biome:?}").replace('_', " "))
+            .collect();
+
+        let padding = 2 * text_scale;
+        let swatch = GLYPH_HEIGHT * text_scale;
+        let line_height = (GLYPH_HEIGHT + 2) * text_scale;
+        let text_x = padding + swatch + 2 * text_scale;
+        let text_width = names
+            .iter()
+            .map(|name| text_width(name, text_scale))
+            .max()
+            .unwrap_or_default();
+
+        let mut legend = filled(
+            text_x + text_width + padding,
+            2 * padding + names.len() as u32 * line_height,
+            PANEL,
+        );
+
+        for (i, (biome, name)) in self.legend_biomes().iter().zip(&names).enumerate() {
+            let y = padding + i as u32 * line_height + text_scale;
+            let color = colors.get(biome).copied().unwrap_or_default();
+
+            fill(&mut legend, padding, y, swatch, swatch, INK);
+            if swatch > 2 {
+                fill(
+                    &mut legend,
+                    padding + 1,
+                    y + 1,
+                    swatch - 2,
+                    swatch - 2,
+                    color,
+                );
+            }
+            draw_text(&mut legend, text_x, y, name, text_scale, INK);
+        }
+
+        legend
+    }
+
+    /// Draws the scale bar in the lower left corner of the map
+    fn draw_scale_bar(&self, image: &mut Image, text_scale: u32) {
+        let blocks_per_pixel = u64::from(self.blocks_per_pixel());
+        let max_blocks = u64::from(self.image().width() / 4) * blocks_per_pixel;
+        let Some(blocks) = round_distance(max_blocks) else {
+            return;
+        };
+        let bar_width = ((blocks + blocks_per_pixel / 2) / blocks_per_pixel) as u32;
+        if bar_width == 0 {
+            return;
+        }
+
+        let label = format!("{blocks} blocks");
+        let padding = 2 * text_scale;
+        let bar_height = 2 * text_scale;
+        let width = bar_width.max(text_width(&label, text_scale)) + 2 * padding;
+        let height = GLYPH_HEIGHT * text_scale + bar_height + 3 * padding;
+        let margin = 4 * text_scale;
+
+        let Some(y) = self.image().height().checked_sub(height + margin) else {
+            return;
+        };
+        if width + margin > self.image().width() {
+            return;
+        }
+
+        let x = margin;
+        fill(image, x, y, width, height, PANEL);
+        draw_text(image, x + padding, y + padding, &label, text_scale, INK);
+        fill(
+            image,
+            x + padding,
+            y + height - padding - bar_height,
+            bar_width,
+            bar_height,
+            INK,
+        );
+    }
+}
+
+/// Gets the longest distance of 1, 2 or 5 times a power of ten blocks which
+/// isn't longer than `max_blocks`
+fn round_distance(max_blocks: u64) -> Option<u64> {
+    let mut power = 1_u64;
+    let mut best = None;
+
+    while power <= max_blocks {
+        best = [5, 2, 1]
+            .into_iter()
+            .map(|factor| factor * power)
+            .find(|&distance| distance <= max_blocks)
+            .or(best);
+        match power.checked_mul(10) {
+            Some(next) => power = next,
+            None => break,
+        }
+    }
+
+    best
+}
+
+/// Gets the width in pixels of a line of text
+fn text_width(text: &str, text_scale: u32) -> u32 {
+    let chars = text.chars().count() as u32;
+    (chars * (GLYPH_WIDTH + 1)).saturating_sub(1) * text_scale
+}
+
+/// Draws a line of text with its upper left corner at a pixel
+fn draw_text(image: &mut Image, x: u32, y: u32, text: &str, text_scale: u32, color: Rgb) {
+    for (i, char) in (0..).zip(text.chars()) {
+        let Some((_, rows)) = GLYPHS
+            .iter()
+            .find(|(glyph, _)| *glyph == char.to_ascii_uppercase())
+        else {
+            continue;
+        };
+        let glyph_x = x + i * (GLYPH_WIDTH + 1) * text_scale;
+
+        for (row, line) in (0..).zip(rows) {
+            for (column, _) in (0..).zip(line.chars()).filter(|(_, pixel)| *pixel == '#') {
+                fill(
+                    image,
+                    glyph_x + column * text_scale,
+                    y + row * text_scale,
+                    text_scale,
+                    text_scale,
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// Creates an image of a single color
+fn filled(width: u32, height: u32, color: Rgb) -> Image {
+    let mut image = Image::new(width, height);
+    fill(&mut image, 0, 0, width, height, color);
+    image
+}
+
+/// Fills a rectangle of an image with a color
+fn fill(image: &mut Image, x: u32, y: u32, width: u32, height: u32, color: Rgb) {
+    for y in y..y.saturating_add(height) {
+        for x in x..x.saturating_add(width) {
+            image.set_pixel(x, y, color);
+        }
+    }
+}
+
+/// Copies an image into another with its upper left corner at a pixel
+fn paste(image: &mut Image, other: &Image, x: u32, y: u32) {
+    for other_y in 0..other.height() {
+        for other_x in 0..other.width() {
+            if let Some(color) = other.pixel(other_x, other_y) {
+                image.set_pixel(x + other_x, y + other_y, color);
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::Image;
+use crate::generator::{colors::new_biome_color_map, HeightMap};
+
+/// The direction towards the light used for hillshading
+///
+/// The light comes from the upper left corner of the image at an angle of 45
+/// degrees, the usual convention for shaded relief maps.
+const LIGHT: [f32; 3] = [-0.5, -0.5, std::f32::consts::FRAC_1_SQRT_2];
+
+impl HeightMap {
+    /// Renders the heights as a grayscale image
+    ///
+    /// Heights at or below `min` are black and heights at or above `max` are
+    /// white. Use [`Self::min_max()`] to stretch the image over the heights of
+    /// the map.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -380434930381432806,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let heights = generator
+    ///     .approx_heights(-64, -64, 128, 128)
+    ///     .expect("failed to map heights");
+    ///
+    /// let image = heights.to_grayscale(-64.0, 320.0);
+    /// assert_eq!((image.width(), image.height()), (128, 128));
+    /// ```
+    #[must_use]
+    pub fn to_grayscale(&self, min: f32, max: f32) -> Image {
+        let span = (max - min).max(f32::EPSILON);
+
+        self.render(|x, z| {
+            let height = self.height_at(x, z).unwrap_or(min);
+            let value = (((height - min) / span).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+            [value; 3]
+        })
+    }
+
+    /// Renders the heights as a hillshaded relief
+    ///
+    /// Every cell is shaded by how much its slope faces a light coming from
+    /// the upper left corner, so flat terrain is a medium gray.
+    #[must_use]
+    pub fn to_hillshade(&self) -> Image {
+        self.render(|x, z| [(self.shade(x, z) * 255.0).round() as u8; 3])
+    }
+
+    /// Renders the biome colors of the map, hillshaded with the heights
+    ///
+    /// Flat terrain keeps the color of its biome, while slopes facing the
+    /// light are lightened and slopes facing away are darkened. Unknown
+    /// biomes are drawn black.
+    #[must_use]
+    pub fn to_shaded_biomes(&self) -> Image {
+        let colors = new_biome_color_map();
+
+        self.render(|x, z| {
+            let color = self
+                .biome_at(x, z)
+                .and_then(|biome| colors.get(&biome).copied())
+                .unwrap_or_default();
+            let factor = self.shade(x, z) / LIGHT[2];
+
+            color.map(|channel| (f32::from(channel) * factor).round().clamp(0.0, 255.0) as u8)
+        })
+    }
+
+    /// Gets the brightness of a cell lit by [`LIGHT`], between 0 and 1
+    fn shade(&self, x: u32, z: u32) -> f32 {
+        let height = |dx: i32, dz: i32| {
+            let x = x.saturating_add_signed(dx).min(self.size_x() - 1);
+            let z = z.saturating_add_signed(dz).min(self.size_z() - 1);
+            self.height_at(x, z).unwrap_or_default()
+        };
+
+        let cell_size = self.scale().unscale_coord(1) as f32;
+        let slope_x = (height(1, 0) - height(-1, 0)) / (2.0 * cell_size);
+        let slope_z = (height(0, 1) - height(0, -1)) / (2.0 * cell_size);
+
+        let normal = [-slope_x, -slope_z, 1.0];
+        let length = normal.iter().map(|n| n * n).sum::<f32>().sqrt();
+
+        (normal.iter().zip(LIGHT).map(|(n, l)| n * l).sum::<f32>() / length).max(0.0)
+    }
+
+    fn render(&self, mut color: impl FnMut(u32, u32) -> super::Rgb) -> Image {
+        let mut image = Image::new(self.size_x(), self.size_z());
+
+        for z in 0..self.size_z() {
+            for x in 0..self.size_x() {
+                image.set_pixel(x, z, color(x, z));
+            }
+        }
+
+        image
+    }
+}
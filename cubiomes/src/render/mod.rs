@@ -0,0 +1,165 @@
+//! Module containing images rendered from biome and structure data
+//!
+//! The images are stored as plain RGB pixel data in an [`Image`], which can be
+//! written to disk as a [PPM](https://netpbm.sourceforge.net/doc/ppm.html)
+//! file without any additional dependencies. With the `png` feature, images
+//! can also be saved as PNG files.
+//!
+//! For world viewers, biome maps can be rendered as slippy map tiles at
+//! multiple zoom levels with a [`TileRenderer`]. Terrain previews can be
+//! rendered from a [`crate::generator::HeightMap`] as grayscale, hillshaded
+//! or shaded biome images. For a quick look without image files, [`ascii()`]
+//! prints a biome map with colored blocks to the terminal.
+//!
+//! Structure markers, slime chunks, grid lines and the spawn can be drawn on
+//! top of a biome map as [`Overlay`]s on a [`Map`], which can be exported with
+//! a legend and a scale bar, see [`RenderOptions`].
+//!
+//! With the `viewer` feature, a `Viewer` opens a window with the biomes of a
+//! generator, which can be panned and zoomed to quickly look around a seed.
+
+pub use ascii::*;
+pub use export::*;
+pub use overlay::*;
+pub use tiles::*;
+#[cfg(feature = "viewer")]
+pub use viewer::*;
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+mod ascii;
+pub mod error;
+mod export;
+mod height;
+mod overlay;
+mod tiles;
+#[cfg(feature = "viewer")]
+mod viewer;
+
+/// A color of a single pixel as red, green and blue
+pub type Rgb = [u8; 3];
+
+/// An RGB image with 8 bits per channel
+///
+/// The pixels are stored row by row, starting from the upper left corner.
+///
+/// # Examples
+/// ```
+/// use cubiomes::render::Image;
+///
+/// let mut image = Image::new(16, 8);
+/// image.set_pixel(3, 4, [255, 0, 0]);
+///
+/// assert_eq!(image.pixel(3, 4), Some([255, 0, 0]));
+/// assert_eq!(image.pixel(16, 0), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Creates a new black image of the given size
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 3],
+        }
+    }
+
+    /// Gets the width of the image in pixels
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Gets the height of the image in pixels
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Gets the color of a pixel
+    ///
+    /// Returns [`None`] if the pixel is outside the image
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Rgb> {
+        let i = self.index(x, y)?;
+        Some([self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]])
+    }
+
+    /// Sets the color of a pixel
+    ///
+    /// Pixels outside the image are ignored
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Rgb) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i..i + 3].copy_from_slice(&color);
+        }
+    }
+
+    /// Gets the raw pixel data of the image
+    ///
+    /// Every pixel is three bytes, see [`Image`] for the layout
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Writes the image in the binary PPM format
+    pub fn write_ppm(&self, mut writer: impl Write) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        writer.write_all(&self.pixels)
+    }
+
+    /// Saves the image to a file in the binary PPM format
+    pub fn save_ppm(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_ppm(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Writes the image in the PNG format
+    ///
+    /// Requires the `png` feature
+    #[cfg(feature = "png")]
+    pub fn write_png(&self, writer: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(png_error)?;
+        writer.write_image_data(&self.pixels).map_err(png_error)?;
+        writer.finish().map_err(png_error)
+    }
+
+    /// Saves the image to a file in the PNG format
+    ///
+    /// Requires the `png` feature
+    #[cfg(feature = "png")]
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_png(&mut writer)?;
+        writer.flush()
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height)
+            .then(|| (y as usize * self.width as usize + x as usize) * 3)
+    }
+}
+
+#[cfg(feature = "png")]
+fn png_error(err: png::EncodingError) -> io::Error {
+    match err {
+        png::EncodingError::IoError(err) => err,
+        err => io::Error::other(err),
+    }
+}
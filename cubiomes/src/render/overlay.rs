@@ -0,0 +1,361 @@
+use super::{Image, Rgb};
+use crate::enums::BiomeID;
+use crate::generator::{BlockPosition, Cache};
+use crate::structures::{finder::StructureHit, slime::is_slime_chunk};
+
+/// The color of the outline around markers
+const OUTLINE: Rgb = [0, 0, 0];
+
+/// A layer drawn on top of a biome map, see [`Map::draw()`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Overlay {
+    /// A square marker on each structure
+    Structures {
+        /// The block positions of the structures
+        positions: Vec<BlockPosition>,
+        /// The color of the markers
+        color: Rgb,
+    },
+    /// A shade over every slime chunk of a seed
+    SlimeChunks {
+        /// The seed of the world
+        seed: i64,
+        /// The color the slime chunks are shaded with
+        color: Rgb,
+        /// How much of the color is mixed in, from 0 to 1
+        opacity: f32,
+    },
+    /// Lines along the borders of a grid of squares aligned to the origin
+    Grid {
+        /// The width of a square of the grid in blocks
+        spacing: u32,
+        /// The color of the lines
+        color: Rgb,
+    },
+    /// A cross marker on the world spawn
+    Spawn {
+        /// The block position of the spawn
+        pos: BlockPosition,
+        /// The color of the marker
+        color: Rgb,
+    },
+}
+
+impl Overlay {
+    /// Creates markers for the structures found by a search, like
+    /// [`crate::generator::Generator::structures_within()`]
+    #[must_use]
+    pub fn structures(hits: &[StructureHit], color: Rgb) -> Self {
+        Self::Structures {
+            positions: hits.iter().map(|hit| hit.pos).collect(),
+            color,
+        }
+    }
+
+    /// Creates grid lines along the borders of chunks
+    #[must_use]
+    pub fn chunk_grid(color: Rgb) -> Self {
+        Self::Grid { spacing: 16, color }
+    }
+
+    /// Creates grid lines along the borders of region files, which are 512
+    /// blocks wide
+    #[must_use]
+    pub fn region_grid(color: Rgb) -> Self {
+        Self::Grid {
+            spacing: 512,
+            color,
+        }
+    }
+}
+
+/// An image of an area of the world which overlays can be drawn on
+///
+/// The map knows which block each pixel covers, so overlays given in block
+/// coordinates end up on the right pixels. Overlays are drawn in the order
+/// they are added, so later ones cover earlier ones.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+/// use cubiomes::generator::{BlockPosition, Cache, Generator, GeneratorFlags, Range, Scale};
+/// use cubiomes::render::{Map, Overlay};
+///
+/// let mut generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let mut cache = Cache::new(&generator, Range::plane(Scale::Quad, -128, -128, 256, 256, 64));
+/// cache.fill_cache().expect("failed to fill cache");
+/// let mut map = Map::from_cache(&cache);
+/// drop(cache);
+///
+/// let villages = generator.structures_within(StructureType::Village, BlockPosition::new(0, 0), 512);
+/// let spawn = generator.estimate_spawn().expect("the generator is for the overworld");
+///
+/// map.draw(&Overlay::SlimeChunks {
+///     seed: generator.seed(),
+///     color: [0, 255, 0],
+///     opacity: 0.3,
+/// });
+/// map.draw(&Overlay::chunk_grid([64, 64, 64]));
+/// map.draw(&Overlay::structures(&villages, [255, 0, 0]));
+/// map.draw(&Overlay::Spawn { pos: spawn, color: [255, 255, 255] });
+///
+/// let image = map.into_image();
+/// assert_eq!((image.width(), image.height()), (256, 256));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map {
+    image: Image,
+    origin: BlockPosition,
+    blocks_per_pixel: u32,
+    biomes: Vec<BiomeID>,
+}
+
+impl Map {
+    /// Creates a map from an image
+    ///
+    /// `origin` is the block at the upper left corner of the image, and every
+    /// pixel covers `blocks_per_pixel` by `blocks_per_pixel` blocks. A
+    /// `blocks_per_pixel` of 0 is treated as 1. The map has no biomes for
+    /// its legend, see [`Self::with_legend_biomes()`].
+    #[must_use]
+    pub fn new(image: Image, origin: BlockPosition, blocks_per_pixel: u32) -> Self {
+        Self {
+            image,
+            origin,
+            blocks_per_pixel: blocks_per_pixel.max(1),
+            biomes: Vec::new(),
+        }
+    }
+
+    /// Creates a map from the biomes of the lowest layer of a cache
+    ///
+    /// The image is [`Cache::to_image()`], with one pixel per cell of the
+    /// cache. The biomes for the legend are the ones of the cache, from the
+    /// most to the least common.
+    #[must_use]
+    pub fn from_cache(cache: &Cache<'_>) -> Self {
+        let range = cache.range();
+        let scale = range.scale as i32;
+
+        let mut counts: Vec<(BiomeID, u64)> = cache.histogram().into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        Self::new(
+            cache.to_image(),
+            BlockPosition::new(range.x * scale, range.z * scale),
+            scale as u32,
+        )
+        .with_legend_biomes(counts.into_iter().map(|(biome, _)| biome))
+    }
+
+    /// Sets the biomes listed in the legend of the map, in order
+    #[must_use]
+    pub fn with_legend_biomes(self, biomes: impl IntoIterator<Item = BiomeID>) -> Self {
+        Self {
+            biomes: biomes.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Gets the biomes listed in the legend of the map
+    #[must_use]
+    pub fn legend_biomes(&self) -> &[BiomeID] {
+        &self.biomes
+    }
+
+    /// Gets the image of the map with the overlays drawn so far
+    #[must_use]
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Takes the image of the map with the overlays drawn so far
+    #[must_use]
+    pub fn into_image(self) -> Image {
+        self.image
+    }
+
+    /// Gets the block at the upper left corner of the map
+    #[must_use]
+    pub fn origin(&self) -> BlockPosition {
+        self.origin
+    }
+
+    /// Gets the width and height in blocks of the area a pixel covers
+    #[must_use]
+    pub fn blocks_per_pixel(&self) -> u32 {
+        self.blocks_per_pixel
+    }
+
+    /// Gets the pixel covering a block
+    ///
+    /// Returns [`None`] if the block is outside the map
+    #[must_use]
+    pub fn pixel_of(&self, pos: BlockPosition) -> Option<(u32, u32)> {
+        let (x, y) = self.signed_pixel_of(pos);
+        let x = u32::try_from(x).ok().filter(|x| *x < self.image.width())?;
+        let y = u32::try_from(y).ok().filter(|y| *y < self.image.height())?;
+
+        Some((x, y))
+    }
+
+    /// Gets the block at the upper left corner of the area a pixel covers
+    #[must_use]
+    pub fn block_at(&self, x: u32, y: u32) -> BlockPosition {
+        let offset = |pixel: u32| (i64::from(pixel) * i64::from(self.blocks_per_pixel)) as i32;
+
+        BlockPosition::new(
+            self.origin.x.wrapping_add(offset(x)),
+            self.origin.z.wrapping_add(offset(y)),
+        )
+    }
+
+    /// Draws an overlay on top of the map
+    pub fn draw(&mut self, overlay: &Overlay) {
+        match overlay {
+            Overlay::Structures { positions, color } => {
+                for &pos in positions {
+                    self.draw_square(pos, *color);
+                }
+            }
+            Overlay::SlimeChunks {
+                seed,
+                color,
+                opacity,
+            } => self.draw_slime_chunks(*seed, *color, *opacity),
+            Overlay::Grid { spacing, color } => self.draw_grid(*spacing, *color),
+            Overlay::Spawn { pos, color } => self.draw_cross(*pos, *color),
+        }
+    }
+
+    /// Draws several overlays on top of the map, in order
+    #[must_use]
+    pub fn with_overlays<'a>(mut self, overlays: impl IntoIterator<Item = &'a Overlay>) -> Self {
+        for overlay in overlays {
+            self.draw(overlay);
+        }
+
+        self
+    }
+
+    /// Gets the pixel covering a block, which might be outside the image
+    fn signed_pixel_of(&self, pos: BlockPosition) -> (i64, i64) {
+        let pixel = |coord: i32, origin: i32| {
+            (i64::from(coord) - i64::from(origin)).div_euclid(i64::from(self.blocks_per_pixel))
+        };
+
+        (pixel(pos.x, self.origin.x), pixel(pos.z, self.origin.z))
+    }
+
+    /// Sets a pixel given in signed coordinates, ignoring pixels outside the
+    /// image
+    fn set(&mut self, x: i64, y: i64, color: Rgb) {
+        if let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) {
+            self.image.set_pixel(x, y, color);
+        }
+    }
+
+    /// Draws a 5 by 5 square with an outline centered on a block
+    fn draw_square(&mut self, pos: BlockPosition, color: Rgb) {
+        let (x, y) = self.signed_pixel_of(pos);
+
+        for dy in -3..=3_i64 {
+            for dx in -3..=3_i64 {
+                let color = if dx.abs() == 3 || dy.abs() == 3 {
+                    OUTLINE
+                } else {
+                    color
+                };
+                self.set(x + dx, y + dy, color);
+            }
+        }
+    }
+
+    /// Draws a 9 pixel wide cross with an outline centered on a block
+    fn draw_cross(&mut self, pos: BlockPosition, color: Rgb) {
+        let (x, y) = self.signed_pixel_of(pos);
+
+        // The outline first, so the arms of the cross cover its inner parts
+        for (dx, dy) in
+            arms(5).flat_map(|(dx, dy)| [(dx - 1, dy), (dx + 1, dy), (dx, dy - 1), (dx, dy + 1)])
+        {
+            self.set(x + dx, y + dy, OUTLINE);
+        }
+        for (dx, dy) in arms(4) {
+            self.set(x + dx, y + dy, color);
+        }
+    }
+
+    /// Mixes a color into every pixel whose center is in a slime chunk
+    fn draw_slime_chunks(&mut self, seed: i64, color: Rgb, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        let half_pixel = (self.blocks_per_pixel / 2) as i32;
+
+        for y in 0..self.image.height() {
+            for x in 0..self.image.width() {
+                let block = self.block_at(x, y);
+                let (chunk_x, chunk_z) = BlockPosition::new(
+                    block.x.wrapping_add(half_pixel),
+                    block.z.wrapping_add(half_pixel),
+                )
+                .scale_by_num(16);
+
+                if is_slime_chunk(seed, chunk_x, chunk_z) {
+                    if let Some(pixel) = self.image.pixel(x, y) {
+                        self.image.set_pixel(x, y, blend(pixel, color, opacity));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Colors every pixel covering a block on a line of the grid
+    ///
+    /// Grids with squares narrower than two pixels would cover the whole
+    /// map, so they aren't drawn.
+    fn draw_grid(&mut self, spacing: u32, color: Rgb) {
+        if spacing < self.blocks_per_pixel.saturating_mul(2) {
+            return;
+        }
+
+        let spacing = i64::from(spacing);
+        let blocks_per_pixel = i64::from(self.blocks_per_pixel);
+        // Whether the blocks of a pixel along an axis start a square
+        let on_line = |origin: i32, pixel: u32| {
+            let start = i64::from(origin) + i64::from(pixel) * blocks_per_pixel;
+            (start - 1).div_euclid(spacing) != (start + blocks_per_pixel - 1).div_euclid(spacing)
+        };
+
+        let columns: Vec<bool> = (0..self.image.width())
+            .map(|x| on_line(self.origin.x, x))
+            .collect();
+        for y in 0..self.image.height() {
+            let row = on_line(self.origin.z, y);
+            for (x, &column) in (0..).zip(&columns) {
+                if row || column {
+                    self.image.set_pixel(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+/// Gets the offsets of the pixels of a cross with arms of a length
+fn arms(length: i64) -> impl Iterator<Item = (i64, i64)> {
+    (-length..=length).flat_map(|i| [(i, 0), (0, i)])
+}
+
+/// Mixes a color into another
+fn blend(base: Rgb, color: Rgb, opacity: f32) -> Rgb {
+    [0, 1, 2].map(|i| {
+        (f32::from(base[i]) * (1.0 - opacity) + f32::from(color[i]) * opacity)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    })
+}
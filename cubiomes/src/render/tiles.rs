@@ -0,0 +1,262 @@
+use super::{Image, Rgb};
+use crate::enums::BiomeID;
+use crate::generator::{
+    colors::new_biome_color_map, error::GeneratorError, BlockPosition, Generator, Range, Scale,
+    YLevel,
+};
+use std::{collections::BTreeMap, path::PathBuf};
+
+#[cfg(feature = "png")]
+use super::error::RenderError;
+#[cfg(feature = "png")]
+use crate::progress::CancellationToken;
+
+/// The width and height of a map tile in pixels
+pub const TILE_PIXELS: u32 = 256;
+
+/// The zoom level where one pixel is one block
+///
+/// Each zoom level below this halves the resolution, so at zoom 0 one pixel
+/// covers 256 blocks, the same as [`Scale::HalfRegion`]. Deeper zoom levels
+/// are one block per pixel as well.
+pub const MAX_ZOOM: u8 = 8;
+
+/// The scales cubiomes can generate at, from the coarsest to the finest
+const SCALES: [Scale; 5] = [
+    Scale::HalfRegion,
+    Scale::QuadChunk,
+    Scale::Chunk,
+    Scale::Quad,
+    Scale::Block,
+];
+
+/// The position of a map tile
+///
+/// The tiles follow the z/x/y scheme of slippy maps, so they can be shown with
+/// Leaflet or OpenLayers. The tile x is along the minecraft x axis and the
+/// tile y along the minecraft z axis. The tile at x 0 and y 0 has its upper
+/// left corner at the world origin, so tiles in the negative directions have
+/// negative coordinates.
+///
+/// # Examples
+/// ```
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::render::TileId;
+///
+/// let tile = TileId::containing(8, BlockPosition::new(-1, 300));
+///
+/// assert_eq!(tile, TileId { zoom: 8, x: -1, y: 1 });
+/// assert_eq!(tile.path("png").to_str(), Some("8/-1/1.png"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TileId {
+    /// The zoom level of the tile, at most [`MAX_ZOOM`]
+    pub zoom: u8,
+    /// The x coordinate of the tile
+    pub x: i32,
+    /// The y coordinate of the tile, along the minecraft z axis
+    pub y: i32,
+}
+
+impl TileId {
+    /// Gets the tile of a zoom level containing a block
+    #[must_use]
+    pub fn containing(zoom: u8, pos: BlockPosition) -> Self {
+        let size = Self::block_size(zoom);
+
+        Self {
+            zoom,
+            x: pos.x.div_euclid(size),
+            y: pos.z.div_euclid(size),
+        }
+    }
+
+    /// Iterates over every tile of a zoom level overlapping an area
+    ///
+    /// The area is given as two opposite corners in blocks, which are both
+    /// inclusive. The tiles are given row by row.
+    pub fn covering(
+        zoom: u8,
+        corner: BlockPosition,
+        opposite: BlockPosition,
+    ) -> impl Iterator<Item = Self> {
+        let min = Self::containing(
+            zoom,
+            BlockPosition::new(corner.x.min(opposite.x), corner.z.min(opposite.z)),
+        );
+        let max = Self::containing(
+            zoom,
+            BlockPosition::new(corner.x.max(opposite.x), corner.z.max(opposite.z)),
+        );
+
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Self { zoom, x, y }))
+    }
+
+    /// Gets the relative path of the tile, in the form of `{zoom}/{x}/{y}.{extension}`
+    #[must_use]
+    pub fn path(&self, extension: &str) -> PathBuf {
+        [
+            self.zoom.to_string(),
+            self.x.to_string(),
+            format!("{}.{extension}", self.y),
+        ]
+        .iter()
+        .collect()
+    }
+
+    /// Gets the amount of blocks covered by one pixel of the tile
+    #[must_use]
+    pub fn blocks_per_pixel(&self) -> u32 {
+        1 << MAX_ZOOM.saturating_sub(self.zoom)
+    }
+
+    /// Gets the block position of the upper left corner of the tile
+    #[must_use]
+    pub fn origin(&self) -> BlockPosition {
+        let size = Self::block_size(self.zoom);
+        BlockPosition::new(self.x * size, self.y * size)
+    }
+
+    /// Gets the width of a tile of a zoom level in blocks
+    fn block_size(zoom: u8) -> i32 {
+        let tile = Self { zoom, x: 0, y: 0 };
+        (TILE_PIXELS * tile.blocks_per_pixel()) as i32
+    }
+}
+
+/// A renderer for biome map tiles
+///
+/// Each tile is rendered from its own [`crate::generator::Cache`], which is
+/// dropped once the tile is done. The memory used is bounded by the size of a
+/// single tile, no matter how large of an area is rendered.
+///
+/// Zoom levels with the same resolution as one of the scales of cubiomes are
+/// generated at that scale. Zoom levels between two scales are generated at
+/// the finer scale and every other cell is used.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{Generator, GeneratorFlags, YLevel};
+/// use cubiomes::render::{TileId, TileRenderer, TILE_PIXELS};
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -380434930381432806,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let renderer = TileRenderer::new(&generator, YLevel::SeaLevel);
+/// let tile = renderer
+///     .render(TileId { zoom: 2, x: 0, y: -1 })
+///     .expect("failed to render tile");
+///
+/// assert_eq!((tile.width(), tile.height()), (TILE_PIXELS, TILE_PIXELS));
+/// ```
+#[derive(Debug)]
+pub struct TileRenderer<'generator> {
+    generator: &'generator Generator,
+    y: YLevel,
+    colors: BTreeMap<BiomeID, Rgb>,
+}
+
+impl<'generator> TileRenderer<'generator> {
+    /// Creates a renderer for a generator, drawing the biomes at a y level
+    ///
+    /// See [`Generator::biomes_at_level()`].
+    #[must_use]
+    pub fn new(generator: &'generator Generator, y: impl Into<YLevel>) -> Self {
+        Self {
+            generator,
+            y: y.into(),
+            colors: new_biome_color_map(),
+        }
+    }
+
+    /// Renders a single tile
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes of the tile fails
+    pub fn render(&self, tile: TileId) -> Result<Image, GeneratorError> {
+        let blocks_per_pixel = tile.blocks_per_pixel();
+        let scale = SCALES
+            .into_iter()
+            .find(|&scale| scale as u32 <= blocks_per_pixel)
+            .unwrap_or(Scale::Block);
+        let step = blocks_per_pixel / scale as u32;
+        let size = TILE_PIXELS * step;
+        let origin = tile.origin();
+
+        let cache = self.generator.biomes_at_level(
+            Range {
+                scale,
+                x: origin.x / scale as i32,
+                z: origin.z / scale as i32,
+                size_x: size,
+                size_z: size,
+                y: 0,
+                size_y: 0,
+            },
+            self.y,
+        )?;
+
+        let cells = cache.as_vec();
+        let mut image = Image::new(TILE_PIXELS, TILE_PIXELS);
+
+        for z in 0..TILE_PIXELS {
+            for x in 0..TILE_PIXELS {
+                let raw_biomeid = cells[(z * step * size + x * step) as usize];
+                let color = BiomeID::from_id(raw_biomeid)
+                    .and_then(|biome| self.colors.get(&biome).copied())
+                    .unwrap_or_default();
+
+                image.set_pixel(x, z, color);
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Renders every tile covering an area into png files in a directory
+    ///
+    /// The area is given as two opposite corners in blocks, see
+    /// [`TileId::covering()`]. The tiles of every zoom level in `zooms` are
+    /// saved to `directory` at the path from [`TileId::path()`], creating the
+    /// directories as needed. `cancel` is checked before each tile.
+    ///
+    /// Returns the amount of tiles saved. Requires the `png` feature.
+    ///
+    /// # Errors
+    /// Returns an error if rendering or saving a tile fails or
+    /// [`GeneratorError::Cancelled`] if cancelled
+    #[cfg(feature = "png")]
+    pub fn save_tiles(
+        &self,
+        zooms: std::ops::RangeInclusive<u8>,
+        corner: BlockPosition,
+        opposite: BlockPosition,
+        directory: impl AsRef<std::path::Path>,
+        cancel: &CancellationToken,
+    ) -> Result<u64, RenderError> {
+        let mut saved = 0;
+
+        for zoom in zooms {
+            for tile in TileId::covering(zoom, corner, opposite) {
+                if cancel.is_cancelled() {
+                    return Err(GeneratorError::Cancelled.into());
+                }
+
+                let path = directory.as_ref().join(tile.path("png"));
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                self.render(tile)?.save_png(path)?;
+                saved += 1;
+            }
+        }
+
+        Ok(saved)
+    }
+}
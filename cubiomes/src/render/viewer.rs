@@ -0,0 +1,388 @@
+use super::error::RenderError;
+use crate::enums::BiomeID;
+use crate::generator::{
+    colors::new_biome_color_map, error::GeneratorError, BlockPosition, Cache, Generator, Range,
+    Scale,
+};
+use crate::progress::{CancellationToken, Progress};
+use minifb::{Key, KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+
+/// The scales the viewer zooms through, from the most zoomed out
+const ZOOM_LEVELS: [Scale; 5] = [
+    Scale::HalfRegion,
+    Scale::QuadChunk,
+    Scale::Chunk,
+    Scale::Quad,
+    Scale::Block,
+];
+
+/// An interactive preview of the biomes of a generator, see [`Self::run()`]
+///
+/// The viewer holds a [`Cache`] with a cell for every pixel of its window.
+/// Zooming changes the scale of the cache and fills it again tile by tile,
+/// see [`Cache::fill_cache_with_progress()`], while panning moves it with
+/// [`Cache::shift()`], so only the cells coming into view are generated.
+///
+/// The viewer can also be driven without a window through [`Self::pan()`]
+/// and [`Self::zoom()`], reading the pixels from [`Self::frame()`].
+/// Requires the `viewer` feature.
+///
+/// # Examples
+/// ```no_run
+/// use cubiomes::enums::{Dimension, MCVersion};
+/// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+/// use cubiomes::render::Viewer;
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// Viewer::new(&generator, 800, 600)
+///     .with_center(BlockPosition::new(1000, -300))
+///     .run()
+///     .expect("failed to show the preview");
+/// ```
+pub struct Viewer<'generator> {
+    generator: &'generator Generator,
+    cache: Cache<'generator>,
+    /// The y the biomes are shown at in blocks, which the range only keeps
+    /// at its own scale
+    y: i32,
+    /// The colors of the frame by biome id
+    colors: Box<[u32]>,
+    frame: Vec<u32>,
+}
+
+impl std::fmt::Debug for Viewer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Viewer")
+            .field("range", self.range())
+            .field("y", &self.y)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'generator> Viewer<'generator> {
+    /// Creates a viewer of `width` by `height` pixels, centered on the origin
+    ///
+    /// The biomes are shown at [`Scale::Quad`] and y 64 until changed. Nothing
+    /// is generated before [`Self::render()`] or [`Self::run()`].
+    #[must_use]
+    pub fn new(generator: &'generator Generator, width: u32, height: u32) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let range = Range::plane(
+            Scale::Quad,
+            -((width / 2) as i32),
+            -((height / 2) as i32),
+            width,
+            height,
+            64,
+        );
+
+        // The biome ids are below 256, the others are drawn black
+        let mut colors = vec![0; 256].into_boxed_slice();
+        for (biome, [r, g, b]) in new_biome_color_map() {
+            if let Some(color) = usize::try_from(biome as i32)
+                .ok()
+                .and_then(|index| colors.get_mut(index))
+            {
+                *color = u32::from_be_bytes([0, r, g, b]);
+            }
+        }
+
+        Self {
+            generator,
+            cache: Cache::new(generator, range),
+            y: 64,
+            colors,
+            frame: vec![0; width as usize * height as usize],
+        }
+    }
+
+    /// Centers the view on a block
+    #[must_use]
+    pub fn with_center(mut self, center: BlockPosition) -> Self {
+        let range = self.centered_range(center, self.scale());
+        self.cache.move_cache(range.x, range.y, range.z);
+        self
+    }
+
+    /// Sets the y level the biomes are shown at, in blocks
+    #[must_use]
+    pub fn with_y(mut self, y: i32) -> Self {
+        let range = *self.range();
+        self.y = y;
+        self.cache
+            .move_cache(range.x, range.scale.scale_y(y), range.z);
+        self
+    }
+
+    /// Gets the range of the biomes in view, with a cell for every pixel
+    #[must_use]
+    pub fn range(&self) -> &Range {
+        self.cache.range()
+    }
+
+    /// Gets the scale, with every pixel showing a cell of it
+    #[must_use]
+    pub fn scale(&self) -> Scale {
+        self.range().scale
+    }
+
+    /// Gets the block at the center of the view
+    #[must_use]
+    pub fn center(&self) -> BlockPosition {
+        let range = self.range();
+        BlockPosition::from_scaled(
+            range.x + (range.size_x / 2) as i32,
+            range.z + (range.size_z / 2) as i32,
+            range.scale,
+        )
+    }
+
+    /// Gets the pixels of the view as `0RGB`, row by row
+    ///
+    /// The pixels are black until the view is rendered.
+    #[must_use]
+    pub fn frame(&self) -> &[u32] {
+        &self.frame
+    }
+
+    /// Gets the biome shown at a pixel of the view
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::IndexOutOfBounds`] if the pixel is outside
+    /// of the view or the view isn't rendered
+    pub fn biome_at_pixel(&self, x: u32, y: u32) -> Result<BiomeID, GeneratorError> {
+        self.cache.biome_at(x, 0, y)
+    }
+
+    /// Generates every biome in view
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes fails
+    pub fn render(&mut self) -> Result<(), GeneratorError> {
+        self.render_with_progress(&CancellationToken::new(), |_| ())
+    }
+
+    /// Moves the view by an amount of pixels
+    ///
+    /// Only the biomes coming into view are generated.
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes fails
+    pub fn pan(&mut self, dx: i32, dz: i32) -> Result<(), GeneratorError> {
+        self.cache.shift(dx, dz)?;
+        self.draw();
+        Ok(())
+    }
+
+    /// Shows the biomes at another scale, keeping the center of the view
+    ///
+    /// Every biome in view is generated again.
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes fails
+    pub fn zoom(&mut self, scale: Scale) -> Result<(), GeneratorError> {
+        self.zoom_with_progress(scale, &CancellationToken::new(), |_| ())
+    }
+
+    /// Zooms in to the next finer scale of cubiomes
+    ///
+    /// Returns whether the scale changed, which it doesn't past
+    /// [`Scale::Block`].
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes fails
+    pub fn zoom_in(&mut self) -> Result<bool, GeneratorError> {
+        self.zoom_to_next(true)
+    }
+
+    /// Zooms out to the next coarser scale of cubiomes
+    ///
+    /// Returns whether the scale changed, which it doesn't past
+    /// [`Scale::HalfRegion`].
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes fails
+    pub fn zoom_out(&mut self) -> Result<bool, GeneratorError> {
+        self.zoom_to_next(false)
+    }
+
+    /// Opens a window showing the view until it is closed
+    ///
+    /// The view is panned with the arrow keys, WASD or by dragging with the
+    /// mouse, and zoomed with the scroll wheel, + and -. The title of the
+    /// window shows the block and the biome under the mouse. Escape closes
+    /// the window.
+    ///
+    /// # Errors
+    /// Returns [`RenderError::Window`] if the window can't be opened or
+    /// updated, for example without a display, and
+    /// [`RenderError::GeneratorError`] if generating the biomes fails
+    pub fn run(mut self) -> Result<(), RenderError> {
+        let (width, height) = (self.range().size_x as usize, self.range().size_z as usize);
+        let mut window = Window::new("cubiomes", width, height, WindowOptions::default())?;
+        window.set_target_fps(60);
+
+        let cancel = CancellationToken::new();
+        self.render_with_progress(&cancel, |progress| show_progress(&mut window, progress))?;
+
+        let mut dragged_from: Option<(f32, f32)> = None;
+
+        while window.is_open() && !window.is_key_down(Key::Escape) {
+            let step = (self.range().size_x.min(self.range().size_z) / 16).max(1) as i32;
+            let (mut dx, mut dz) = (0, 0);
+            let mut zoom = None;
+
+            for key in window.get_keys_pressed(KeyRepeat::Yes) {
+                match key {
+                    Key::Left | Key::A => dx -= step,
+                    Key::Right | Key::D => dx += step,
+                    Key::Up | Key::W => dz -= step,
+                    Key::Down | Key::S => dz += step,
+                    Key::Equal | Key::NumPadPlus => zoom = Some(true),
+                    Key::Minus | Key::NumPadMinus => zoom = Some(false),
+                    _ => {}
+                }
+            }
+
+            if let Some((_, scroll)) = window.get_scroll_wheel() {
+                if scroll != 0.0 {
+                    zoom = Some(scroll > 0.0);
+                }
+            }
+
+            // Dragging moves the view with the mouse, once it moved a pixel
+            let mouse = window.get_mouse_pos(MouseMode::Discard);
+            if !window.get_mouse_down(MouseButton::Left) {
+                dragged_from = None;
+            } else if let (Some((from_x, from_z)), Some((x, z))) = (dragged_from, mouse) {
+                let (drag_x, drag_z) = ((from_x - x) as i32, (from_z - z) as i32);
+                if drag_x != 0 || drag_z != 0 {
+                    (dx, dz) = (dx + drag_x, dz + drag_z);
+                    dragged_from = mouse;
+                }
+            } else {
+                dragged_from = mouse;
+            }
+
+            if let Some(scale) = zoom.and_then(|zoom_in| self.next_scale(zoom_in)) {
+                self.zoom_with_progress(scale, &cancel, |progress| {
+                    show_progress(&mut window, progress);
+                })?;
+            } else if dx != 0 || dz != 0 {
+                self.pan(dx, dz)?;
+            }
+
+            window.set_title(&self.title(mouse));
+            window.update_with_buffer(&self.frame, width, height)?;
+        }
+
+        Ok(())
+    }
+
+    fn render_with_progress(
+        &mut self,
+        cancel: &CancellationToken,
+        on_progress: impl FnMut(&Progress),
+    ) -> Result<(), GeneratorError> {
+        self.cache.fill_cache_with_progress(cancel, on_progress)?;
+        self.draw();
+        Ok(())
+    }
+
+    fn zoom_with_progress(
+        &mut self,
+        scale: Scale,
+        cancel: &CancellationToken,
+        on_progress: impl FnMut(&Progress),
+    ) -> Result<(), GeneratorError> {
+        let range = self.centered_range(self.center(), scale);
+        self.cache = Cache::new(self.generator, range);
+
+        self.render_with_progress(cancel, on_progress)
+    }
+
+    /// Gets the range of the same size as the view around a block
+    fn centered_range(&self, center: BlockPosition, scale: Scale) -> Range {
+        let range = self.range();
+
+        Range::plane(
+            scale,
+            scale.scale_coord(center.x) - (range.size_x / 2) as i32,
+            scale.scale_coord(center.z) - (range.size_z / 2) as i32,
+            range.size_x,
+            range.size_z,
+            self.y,
+        )
+    }
+
+    fn zoom_to_next(&mut self, zoom_in: bool) -> Result<bool, GeneratorError> {
+        match self.next_scale(zoom_in) {
+            Some(scale) => self.zoom(scale).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    fn next_scale(&self, zoom_in: bool) -> Option<Scale> {
+        let current = ZOOM_LEVELS
+            .iter()
+            .position(|&scale| scale == self.scale())?;
+        let next = if zoom_in {
+            current + 1
+        } else {
+            current.checked_sub(1)?
+        };
+
+        ZOOM_LEVELS.get(next).copied()
+    }
+
+    /// Colors the frame from the biomes of the cache
+    fn draw(&mut self) {
+        for (pixel, &raw_biomeid) in self.frame.iter_mut().zip(self.cache.as_vec()) {
+            *pixel = usize::try_from(raw_biomeid)
+                .ok()
+                .and_then(|index| self.colors.get(index))
+                .copied()
+                .unwrap_or(0);
+        }
+    }
+
+    fn title(&self, mouse: Option<(f32, f32)>) -> String {
+        let range = self.range();
+        let hovered = mouse.and_then(|(x, z)| {
+            let (x, z) = (x as u32, z as u32);
+            let biome = self.biome_at_pixel(x, z).ok()?;
+            let pos =
+                BlockPosition::from_scaled(range.x + x as i32, range.z + z as i32, range.scale);
+            Some(format!(" - {biome:?} at {}, {}", pos.x, pos.z))
+        });
+
+        format!(
+            "cubiomes {:?} seed {} at 1:{}{}",
+            self.generator.minecraft_version(),
+            self.generator.seed(),
+            range.scale as i32,
+            hovered.unwrap_or_default(),
+        )
+    }
+}
+
+/// Shows the progress of generating the view in the title of the window
+fn show_progress(window: &mut Window, progress: &Progress) {
+    window.set_title(&format!(
+        "cubiomes - generating {:.0}%",
+        progress.fraction() * 100.0
+    ));
+    window.update();
+}
+
+impl From<minifb::Error> for RenderError {
+    fn from(value: minifb::Error) -> Self {
+        Self::Window(value)
+    }
+}
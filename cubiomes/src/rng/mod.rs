@@ -0,0 +1,213 @@
+//! Module containing the random number generators of java edition
+//!
+//! Most of the world generation before 1.18 and the placement of structures
+//! in every version is driven by `java.util.Random`, a 48-bit linear
+//! congruential generator. [`JavaRandom`] reproduces it exactly, including
+//! the special cases of `nextInt` for powers of two, so seed math done with it
+//! matches minecraft bit for bit.
+//!
+//! Since 1.18 the noises and many features are seeded with [`Xoroshiro`]
+//! instead, which derives its generators from names and positions with a
+//! [`PositionalXoroshiro`].
+//!
+//! The generators placing the features of a chunk are seeded with
+//! [`population_seed()`] and [`decorator_seed()`].
+//!
+//! Cubiomes implements these as inline functions, which aren't available in
+//! the bindings, so they are ported to rust here.
+//!
+//! # Examples
+//! ```
+//! use cubiomes::rng::{region_seed, JavaRandom};
+//!
+//! // The chunk of the swamp hut attempt in region 0, 0 of a seed
+//! let mut rng = JavaRandom::new(region_seed(-5134222539607995087, 0, 0, 14357620));
+//! let chunk_x = rng.next_int(24);
+//! let chunk_z = rng.next_int(24);
+//!
+//! assert!((0..24).contains(&chunk_x) && (0..24).contains(&chunk_z));
+//! ```
+
+pub use population::*;
+pub use xoroshiro::*;
+
+mod md5;
+mod population;
+#[cfg(test)]
+mod tests;
+mod xoroshiro;
+
+/// The random number generator used by java edition
+///
+/// This is `java.util.Random`. The internal state is 48 bits, and seeds are
+/// scrambled before use like in java.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JavaRandom {
+    seed: u64,
+}
+
+impl JavaRandom {
+    const MULTIPLIER: u64 = 0x5deece66d;
+    const ADDEND: u64 = 0xb;
+    const MASK: u64 = (1 << 48) - 1;
+
+    /// Creates a generator like `new Random(seed)`
+    #[must_use]
+    pub fn new(seed: i64) -> Self {
+        let mut rng = Self { seed: 0 };
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// Creates a generator from an internal state
+    ///
+    /// Unlike [`Self::new()`] the state isn't scrambled, so this continues
+    /// from a state given by [`Self::state()`]. Only the lower 48 bits are
+    /// used.
+    #[must_use]
+    pub fn from_state(state: u64) -> Self {
+        Self {
+            seed: state & Self::MASK,
+        }
+    }
+
+    /// Gets the internal 48-bit state of the generator
+    #[must_use]
+    pub fn state(&self) -> u64 {
+        self.seed
+    }
+
+    /// Reseeds the generator like `setSeed(seed)`
+    pub fn set_seed(&mut self, seed: i64) {
+        self.seed = (seed.cast_unsigned() ^ Self::MULTIPLIER) & Self::MASK;
+    }
+
+    /// Advances the generator and gets its upper `bits` bits
+    ///
+    /// At most 32 bits are generated at once, more bits are the same as 32.
+    pub fn next(&mut self, bits: u32) -> i32 {
+        let bits = bits.min(32);
+
+        self.seed = self
+            .seed
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(Self::ADDEND)
+            & Self::MASK;
+        // Truncating to 32 bits gives negative values like the int cast in java
+        (self.seed >> (48 - bits)) as i32
+    }
+
+    /// Gets a uniform integer between 0 and `bound`, excluding `bound`
+    ///
+    /// Java throws for a `bound` which isn't positive, this gives 0 without
+    /// advancing the generator instead.
+    pub fn next_int(&mut self, bound: i32) -> i32 {
+        if bound <= 0 {
+            return 0;
+        }
+
+        let m = bound - 1;
+
+        if m & bound == 0 {
+            let x = i64::from(bound) * i64::from(self.next(31));
+            return (x >> 31) as i32;
+        }
+
+        loop {
+            let bits = self.next(31);
+            let val = bits % bound;
+
+            if (bits - val).wrapping_add(m) >= 0 {
+                return val;
+            }
+        }
+    }
+
+    /// Gets a uniform 64-bit integer
+    ///
+    /// Because the state only has 48 bits, not every value can be generated.
+    pub fn next_long(&mut self) -> i64 {
+        let high = i64::from(self.next(32));
+        let low = i64::from(self.next(32));
+
+        (high << 32).wrapping_add(low)
+    }
+
+    /// Gets a uniform boolean
+    pub fn next_bool(&mut self) -> bool {
+        self.next(1) != 0
+    }
+
+    /// Gets a uniform float between 0 and 1, excluding 1
+    pub fn next_float(&mut self) -> f32 {
+        self.next(24) as f32 / (1 << 24) as f32
+    }
+
+    /// Gets a uniform double between 0 and 1, excluding 1
+    pub fn next_double(&mut self) -> f64 {
+        let high = i64::from(self.next(26)) << 27;
+        let low = i64::from(self.next(27));
+
+        (high + low) as f64 / (1_i64 << 53) as f64
+    }
+
+    /// Skips ahead by `n` calls of [`Self::next()`]
+    ///
+    /// The state is advanced in `log(n)` steps, so this is a lot faster than
+    /// calling [`Self::next()`] `n` times.
+    pub fn skip(&mut self, n: u64) {
+        let mut multiplier = 1_u64;
+        let mut addend = 0_u64;
+        let mut step_multiplier = Self::MULTIPLIER;
+        let mut step_addend = Self::ADDEND;
+
+        let mut k = n;
+        while k != 0 {
+            if k & 1 != 0 {
+                multiplier = multiplier.wrapping_mul(step_multiplier);
+                addend = step_multiplier
+                    .wrapping_mul(addend)
+                    .wrapping_add(step_addend);
+            }
+
+            step_addend = step_multiplier.wrapping_add(1).wrapping_mul(step_addend);
+            step_multiplier = step_multiplier.wrapping_mul(step_multiplier);
+            k >>= 1;
+        }
+
+        self.seed = self.seed.wrapping_mul(multiplier).wrapping_add(addend) & Self::MASK;
+    }
+}
+
+/// Gets the seed of the structure generation attempt in a region
+///
+/// Structures spread over regions, like swamp huts or villages, pick their
+/// chunk in each region with a [`JavaRandom`] created from this seed. The
+/// region is given in region coordinates and the salt is specific to each
+/// structure.
+#[must_use]
+pub fn region_seed(world_seed: i64, region_x: i32, region_z: i32, salt: i64) -> i64 {
+    i64::from(region_x)
+        .wrapping_mul(341873128712)
+        .wrapping_add(i64::from(region_z).wrapping_mul(132897987541))
+        .wrapping_add(world_seed)
+        .wrapping_add(salt)
+}
+
+/// Gets the seed which decides whether a chunk is a slime chunk
+///
+/// A chunk is a slime chunk if the first `nextInt(10)` of a [`JavaRandom`]
+/// created from this seed is 0, see [`crate::structures::slime::is_slime_chunk()`].
+/// The chunk is given in chunk coordinates.
+#[must_use]
+pub fn slime_seed(world_seed: i64, chunk_x: i32, chunk_z: i32) -> i64 {
+    // The products are evaluated with java ints, except the last one
+    world_seed
+        .wrapping_add(i64::from(
+            chunk_x.wrapping_mul(chunk_x).wrapping_mul(0x4c1906),
+        ))
+        .wrapping_add(i64::from(chunk_x.wrapping_mul(0x5ac0db)))
+        .wrapping_add(i64::from(chunk_z.wrapping_mul(chunk_z)).wrapping_mul(0x4307a7))
+        .wrapping_add(i64::from(chunk_z.wrapping_mul(0x5f24f)))
+        ^ 0x3ad8025f
+}
@@ -0,0 +1,78 @@
+use super::{JavaRandom, Xoroshiro};
+use crate::enums::MCVersion;
+
+/// Gets the population seed of a chunk
+///
+/// The features of a chunk, like ores, trees or geodes, are placed with
+/// generators seeded from this. Since 1.13 each feature is placed with its own
+/// [`decorator_seed()`] derived from it. The chunk is given in chunk
+/// coordinates.
+///
+/// Since 1.18 the multipliers are drawn from a [`Xoroshiro`] of the world
+/// seed, and before from a [`JavaRandom`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::rng::{decorator_seed, population_seed, Xoroshiro};
+///
+/// let population = population_seed(MCVersion::MC_1_20_6, -5134222539607995087, 10, -2);
+///
+/// // The random source geodes are placed with in a chunk
+/// let mut rng = Xoroshiro::new(decorator_seed(population, 2, 2));
+/// let has_geode = rng.next_float() < 1.0 / 24.0;
+/// ```
+#[must_use]
+pub fn population_seed(version: MCVersion, world_seed: i64, chunk_x: i32, chunk_z: i32) -> i64 {
+    let (a, b) = if version >= MCVersion::MC_1_18 {
+        let mut rng = Xoroshiro::new(world_seed);
+        (rng.next_legacy_long(), rng.next_legacy_long())
+    } else {
+        let mut rng = JavaRandom::new(world_seed);
+        (rng.next_long(), rng.next_long())
+    };
+
+    // Since 1.13 the seed is made from the block position of the chunk
+    let (a, b, x, z) = if version >= MCVersion::MC_1_13 {
+        (
+            a | 1,
+            b | 1,
+            chunk_x.wrapping_mul(16),
+            chunk_z.wrapping_mul(16),
+        )
+    } else {
+        (a / 2 * 2 + 1, b / 2 * 2 + 1, chunk_x, chunk_z)
+    };
+
+    i64::from(x)
+        .wrapping_mul(a)
+        .wrapping_add(i64::from(z).wrapping_mul(b))
+        ^ world_seed
+}
+
+/// Gets the seed of a single feature in a chunk since 1.13
+///
+/// The features of a chunk are placed in steps, like the underground
+/// structures or the vegetation, and `index` is the position of the feature
+/// in its step. The salt cubiomes uses for decorator features, like geodes,
+/// is `index + 10000 * step`.
+#[must_use]
+pub fn decorator_seed(population_seed: i64, index: i32, step: i32) -> i64 {
+    population_seed
+        .wrapping_add(i64::from(index))
+        .wrapping_add(10000 * i64::from(step))
+}
+
+/// Gets the generator used for the carvers and structures starting in a chunk
+///
+/// Caves, ravines and the pieces of structures like mineshafts are generated
+/// with this, in every version. This is a port of `chunkGenerateRnd` of
+/// cubiomes. The chunk is given in chunk coordinates.
+#[must_use]
+pub fn chunk_generate_random(world_seed: i64, chunk_x: i32, chunk_z: i32) -> JavaRandom {
+    let mut rng = JavaRandom::new(world_seed);
+    let a = rng.next_long().wrapping_mul(i64::from(chunk_x));
+    let b = rng.next_long().wrapping_mul(i64::from(chunk_z));
+
+    JavaRandom::new(a ^ b ^ world_seed)
+}
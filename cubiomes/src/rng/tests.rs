@@ -0,0 +1,187 @@
+use super::{
+    chunk_generate_random, decorator_seed, md5::md5_halves, population_seed, region_seed,
+    JavaRandom, Xoroshiro,
+};
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::BlockPosition;
+use crate::structures::StructureRegion;
+
+#[test]
+fn java_random_matches_java() {
+    // Values of `new Random(0)` in java
+    let mut rng = JavaRandom::new(0);
+    assert_eq!(rng.next_int(i32::MAX), 1_569_741_360);
+
+    let mut rng = JavaRandom::new(0);
+    assert_eq!(rng.next(32), -1_155_484_576);
+
+    let mut rng = JavaRandom::new(0);
+    assert_eq!(rng.next_long(), -4_962_768_465_676_381_896);
+
+    let mut rng = JavaRandom::new(0);
+    assert_eq!(rng.next_double(), 0.730_967_787_376_657);
+
+    let mut rng = JavaRandom::new(0);
+    let bools = [0; 4].map(|_| rng.next_bool());
+    dbg!(bools);
+    assert_eq!(bools, [true, true, false, true]);
+}
+
+#[test]
+fn skip_matches_next() {
+    let mut skipped = JavaRandom::new(-5134222539607995087);
+    let mut stepped = skipped;
+
+    for n in [0, 1, 7, 1000] {
+        skipped.skip(n);
+        for _ in 0..n {
+            stepped.next(32);
+        }
+
+        assert_eq!(skipped, stepped);
+    }
+
+    assert_eq!(JavaRandom::from_state(stepped.state()), stepped);
+}
+
+#[test]
+fn region_seed_gives_structure_positions() {
+    let seed = -5134222539607995087;
+
+    for (region_x, region_z) in [(0, 0), (-3, 5), (12, -40)] {
+        let mut rng = JavaRandom::new(region_seed(seed, region_x, region_z, 14357620));
+        let chunk_x = region_x * 32 + rng.next_int(24);
+        let chunk_z = region_z * 32 + rng.next_int(24);
+
+        let region = StructureRegion::new(
+            region_x,
+            region_z,
+            MCVersion::MC_1_20_6,
+            StructureType::Swamp_Hut,
+        )
+        .expect("swamp huts are generated in 1.20");
+
+        assert_eq!(
+            region.get_structure_generation_attempt(seed),
+            Some(BlockPosition::new(chunk_x * 16, chunk_z * 16))
+        );
+    }
+}
+
+#[test]
+fn md5_matches_cubiomes_constants() {
+    assert_eq!(
+        md5_halves(b"minecraft:offset"),
+        (0x080518cf6af25384, 0x3f3dfb40a54febd5)
+    );
+    assert_eq!(
+        md5_halves(b"octave_-12"),
+        (0xb198de63a8012672, 0x7b84cad43ef7b5a8)
+    );
+    // A message longer than one block
+    assert_eq!(
+        md5_halves(&[b'a'; 100]),
+        (0x36a92cc94a9e0fa2, 0x1f625f8bfb007adf)
+    );
+}
+
+#[test]
+fn xoroshiro_matches_cubiomes() {
+    // Values of the inline functions of cubiomes for the same calls
+    let seed = -5134222539607995087;
+    let mut rng = Xoroshiro::new(seed);
+    assert_eq!(rng.state(), (0xa396e09ee4fab0e6, 0xf87227174e7dcfdc));
+
+    assert_eq!(rng.next_long(), -5547511632617281288);
+    assert_eq!(rng.next_int(100), 57);
+    assert_eq!(rng.next_int(1 << 20), 240359);
+    assert_eq!(rng.next_legacy_long(), 8839208850978584577);
+    assert_eq!(rng.next_legacy_int(100), 68);
+    assert_eq!(rng.next_legacy_int(16), 4);
+    assert_eq!(rng.next_double(), 0.704_377_396_026_286_1);
+
+    // The population seed of chunk 10, -2 since 1.18
+    let mut rng = Xoroshiro::new(seed);
+    let a = rng.next_legacy_long() | 1;
+    let b = rng.next_legacy_long() | 1;
+    assert_eq!(
+        (160_i64.wrapping_mul(a)).wrapping_add((-32_i64).wrapping_mul(b)) ^ seed,
+        -1077747737391829135
+    );
+}
+
+#[test]
+fn positional_xoroshiro_is_deterministic() {
+    let positional = Xoroshiro::new(-5134222539607995087).fork_positional();
+    dbg!(positional);
+
+    assert_eq!(positional.at(1, 2, 3), positional.at(1, 2, 3));
+    assert_ne!(positional.at(1, 2, 3), positional.at(3, 2, 1));
+    assert_ne!(
+        positional.from_hash_of("minecraft:offset"),
+        positional.from_hash_of("minecraft:ridge")
+    );
+
+    let mut rng = positional.at(0, 0, 0);
+    for bound in [1, 7, 16, 1000] {
+        assert!((0..bound).contains(&rng.next_int(bound)));
+        assert!((0..bound).contains(&rng.next_legacy_int(bound)));
+    }
+    assert!((0.0..1.0).contains(&rng.next_double()));
+    assert!((0.0..1.0).contains(&rng.next_float()));
+    assert_eq!(
+        Xoroshiro::from_state(0, 0),
+        Xoroshiro::from_state(0x9e3779b97f4a7c15, 0x6a09e667f3bcc909)
+    );
+}
+
+#[test]
+fn population_seed_places_geodes() {
+    let seed = -5134222539607995087;
+
+    for (version, salt_index) in [(MCVersion::MC_1_17_1, 0), (MCVersion::MC_1_20_6, 2)] {
+        let mut found = 0;
+
+        for chunk_x in -20..20 {
+            for chunk_z in -20..20 {
+                let population = population_seed(version, seed, chunk_x, chunk_z);
+                let decorator = decorator_seed(population, salt_index, 2);
+
+                let expected = if version >= MCVersion::MC_1_18 {
+                    let mut rng = Xoroshiro::new(decorator);
+                    (rng.next_float() < 1.0 / 24.0)
+                        .then(|| (rng.next_legacy_int(16), rng.next_legacy_int(16)))
+                } else {
+                    let mut rng = JavaRandom::new(decorator);
+                    (rng.next_float() < 1.0 / 24.0).then(|| (rng.next_int(16), rng.next_int(16)))
+                }
+                .map(|(x, z)| BlockPosition::new(chunk_x * 16 + x, chunk_z * 16 + z));
+
+                let region = StructureRegion::new(chunk_x, chunk_z, version, StructureType::Geode)
+                    .expect("geodes are generated since 1.17");
+
+                assert_eq!(region.get_structure_generation_attempt(seed), expected);
+                found += usize::from(expected.is_some());
+            }
+        }
+
+        dbg!(found);
+        assert!(found > 0);
+    }
+}
+
+#[test]
+fn chunk_generate_random_matches_java() {
+    // Every multiplier is 0 for chunk 0, 0, leaving the world seed
+    assert_eq!(
+        chunk_generate_random(-5134222539607995087, 0, 0),
+        JavaRandom::new(-5134222539607995087)
+    );
+
+    let mut rng = JavaRandom::new(42);
+    let (a, b) = (rng.next_long(), rng.next_long());
+    assert_eq!(
+        chunk_generate_random(42, 3, -7),
+        JavaRandom::new(a.wrapping_mul(3) ^ b.wrapping_mul(-7) ^ 42)
+    );
+}
@@ -0,0 +1,242 @@
+use super::md5::md5_halves;
+
+/// The random number generator used by the world generation since 1.18
+///
+/// This is `XoroshiroRandomSource`, a xoroshiro128++ generator. Since 1.18 it
+/// seeds the biome and terrain noises, and many features are placed with it.
+/// Random sources for a position or a name are derived with
+/// [`Self::fork_positional()`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::rng::Xoroshiro;
+///
+/// let mut world = Xoroshiro::new(-5134222539607995087);
+/// let positional = world.fork_positional();
+///
+/// // The same name always gives the same random source
+/// let mut first = positional.from_hash_of("minecraft:ore_gold");
+/// let mut second = positional.from_hash_of("minecraft:ore_gold");
+/// assert_eq!(first.next_long(), second.next_long());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xoroshiro {
+    lo: u64,
+    hi: u64,
+}
+
+impl Xoroshiro {
+    /// The state java uses instead of an all zero state, which would only
+    /// produce zeros
+    const ZERO_REPLACEMENT: (u64, u64) = (0x9e3779b97f4a7c15, 0x6a09e667f3bcc909);
+
+    /// Creates a generator like `new XoroshiroRandomSource(seed)`
+    ///
+    /// The 64-bit seed is expanded to the 128-bit state like
+    /// `RandomSupport.upgradeSeedTo128bit`.
+    #[must_use]
+    pub fn new(seed: i64) -> Self {
+        let (lo_mix, hi_mix) = Self::ZERO_REPLACEMENT;
+        let lo = seed.cast_unsigned() ^ hi_mix;
+        let hi = lo.wrapping_add(lo_mix);
+
+        Self {
+            lo: mix_stafford13(lo),
+            hi: mix_stafford13(hi),
+        }
+    }
+
+    /// Creates a generator from a 128-bit state
+    ///
+    /// An all zero state is replaced like in java, as it would only produce
+    /// zeros.
+    #[must_use]
+    pub fn from_state(lo: u64, hi: u64) -> Self {
+        if lo | hi == 0 {
+            let (lo, hi) = Self::ZERO_REPLACEMENT;
+            return Self { lo, hi };
+        }
+
+        Self { lo, hi }
+    }
+
+    /// Gets the 128-bit state of the generator as the low and high half
+    #[must_use]
+    pub fn state(&self) -> (u64, u64) {
+        (self.lo, self.hi)
+    }
+
+    /// Gets a uniform 64-bit integer
+    pub fn next_long(&mut self) -> i64 {
+        let (lo, mut hi) = (self.lo, self.hi);
+        let next = lo.wrapping_add(hi).rotate_left(17).wrapping_add(lo);
+
+        hi ^= lo;
+        self.lo = lo.rotate_left(49) ^ hi ^ (hi << 21);
+        self.hi = hi.rotate_left(28);
+
+        next.cast_signed()
+    }
+
+    /// Gets the upper `bits` bits of the next long
+    ///
+    /// This is what `WorldgenRandom` builds the legacy `java.util.Random`
+    /// methods on when it wraps a xoroshiro generator.
+    ///
+    /// At most 32 bits are generated at once, more bits are the same as 32.
+    /// No bits give 0, but still advance the generator.
+    pub fn next_bits(&mut self, bits: u32) -> i32 {
+        let bits = bits.min(32);
+
+        self.next_long()
+            .cast_unsigned()
+            .checked_shr(64 - bits)
+            .unwrap_or(0) as i32
+    }
+
+    /// Gets a uniform integer between 0 and `bound`, excluding `bound`
+    ///
+    /// Java throws for a `bound` which isn't positive, this gives 0 without
+    /// advancing the generator instead.
+    pub fn next_int(&mut self, bound: i32) -> i32 {
+        if bound <= 0 {
+            return 0;
+        }
+
+        let bound = u64::from(bound.cast_unsigned());
+        let mut product = (self.next_long().cast_unsigned() & 0xffff_ffff) * bound;
+
+        if product & 0xffff_ffff < bound {
+            let threshold = (bound.wrapping_neg() & 0xffff_ffff) % bound;
+
+            while product & 0xffff_ffff < threshold {
+                product = (self.next_long().cast_unsigned() & 0xffff_ffff) * bound;
+            }
+        }
+
+        (product >> 32) as i32
+    }
+
+    /// Gets a uniform boolean
+    pub fn next_bool(&mut self) -> bool {
+        self.next_long() & 1 != 0
+    }
+
+    /// Gets a uniform float between 0 and 1, excluding 1
+    pub fn next_float(&mut self) -> f32 {
+        (self.next_long().cast_unsigned() >> (64 - 24)) as f32 * 5.960_464_5E-8
+    }
+
+    /// Gets a uniform double between 0 and 1, excluding 1
+    pub fn next_double(&mut self) -> f64 {
+        (self.next_long().cast_unsigned() >> (64 - 53)) as f64 * 1.110_223_024_625_156_5E-16
+    }
+
+    /// Gets a long like `java.util.Random.nextLong()` built on
+    /// [`Self::next_bits()`]
+    ///
+    /// Since 1.18 the population seed of chunks is derived with this.
+    pub fn next_legacy_long(&mut self) -> i64 {
+        let high = i64::from(self.next_bits(32));
+        let low = i64::from(self.next_bits(32));
+
+        (high << 32).wrapping_add(low)
+    }
+
+    /// Gets an integer like `java.util.Random.nextInt(bound)` built on
+    /// [`Self::next_bits()`]
+    ///
+    /// Java throws for a `bound` which isn't positive, this gives 0 without
+    /// advancing the generator instead.
+    pub fn next_legacy_int(&mut self, bound: i32) -> i32 {
+        if bound <= 0 {
+            return 0;
+        }
+
+        let m = bound - 1;
+
+        if m & bound == 0 {
+            let x = i64::from(bound) * i64::from(self.next_bits(31));
+            return (x >> 31) as i32;
+        }
+
+        loop {
+            let bits = self.next_bits(31);
+            let val = bits % bound;
+
+            if (bits - val).wrapping_add(m) >= 0 {
+                return val;
+            }
+        }
+    }
+
+    /// Skips ahead by `n` calls of [`Self::next_long()`]
+    pub fn skip(&mut self, n: u64) {
+        for _ in 0..n {
+            self.next_long();
+        }
+    }
+
+    /// Forks a factory for random sources at positions or for names
+    ///
+    /// This advances the generator by two longs, like `forkPositional` in
+    /// java.
+    pub fn fork_positional(&mut self) -> PositionalXoroshiro {
+        PositionalXoroshiro {
+            lo: self.next_long().cast_unsigned(),
+            hi: self.next_long().cast_unsigned(),
+        }
+    }
+}
+
+/// A factory for xoroshiro generators of positions and names
+///
+/// This is `XoroshiroPositionalRandomFactory`, created with
+/// [`Xoroshiro::fork_positional()`]. Every noise of the world generation
+/// since 1.18 is seeded from the world seed by the md5 hash of its name, for
+/// example `minecraft:temperature`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PositionalXoroshiro {
+    lo: u64,
+    hi: u64,
+}
+
+impl PositionalXoroshiro {
+    /// Gets the generator of a block position
+    #[must_use]
+    pub fn at(&self, x: i32, y: i32, z: i32) -> Xoroshiro {
+        Xoroshiro::from_state(position_seed(x, y, z).cast_unsigned() ^ self.lo, self.hi)
+    }
+
+    /// Gets the generator of a name, like `minecraft:offset`
+    ///
+    /// The name is hashed with md5 like `RandomSupport.seedFromHashOf`.
+    #[must_use]
+    pub fn from_hash_of(&self, name: &str) -> Xoroshiro {
+        let (lo, hi) = md5_halves(name.as_bytes());
+
+        Xoroshiro::from_state(lo ^ self.lo, hi ^ self.hi)
+    }
+}
+
+/// Gets the seed of a block position
+///
+/// This is `Mth.getSeed`, which the positional random sources mix into their
+/// state. Note that only x is multiplied as a java int.
+#[must_use]
+pub fn position_seed(x: i32, y: i32, z: i32) -> i64 {
+    let seed =
+        i64::from(x.wrapping_mul(3129871)) ^ i64::from(z).wrapping_mul(116129781) ^ i64::from(y);
+
+    seed.wrapping_mul(seed)
+        .wrapping_mul(42317861)
+        .wrapping_add(seed.wrapping_mul(11))
+        >> 16
+}
+
+fn mix_stafford13(seed: u64) -> u64 {
+    let seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    let seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+    seed ^ (seed >> 31)
+}
@@ -0,0 +1,148 @@
+use super::{error::SearchError, SearchDefinition, SearchResults};
+use crate::generator::{BlockPosition, Generator};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Search results saved together with the search they were found by
+///
+/// The results alone only name the criteria by their index, so they are
+/// archived with the definition of the search. Each hit then has its seed,
+/// the criteria it matched and the position of every match, and can be
+/// checked again later with [`Self::verify()`], for example after updating
+/// cubiomes.
+///
+/// With the `toml` or `json` features, archives can be saved to and loaded
+/// from files, see `SearchArchive::save()`.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{MCVersion, StructureType};
+/// use cubiomes::search::{Criterion, SearchArchive, SearchDefinition, SeedRange};
+///
+/// let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 16));
+/// search.criteria.push(Criterion::Structure {
+///     structure: StructureType::Village,
+///     x: 0,
+///     z: 0,
+///     radius: 512,
+/// });
+///
+/// let results = search.run().expect("search failed");
+/// let archive = SearchArchive::new(search, results);
+///
+/// assert_eq!(archive.verify().expect("verifying failed"), []);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchArchive {
+    /// The search the results were found by
+    pub definition: SearchDefinition,
+    /// The results of the search
+    pub results: SearchResults,
+}
+
+/// A saved match which doesn't match the same way when checked again
+///
+/// See [`SearchArchive::verify()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Discrepancy {
+    /// The seed of the hit
+    pub seed: i64,
+    /// The index of the criterion in [`SearchDefinition::criteria`]
+    pub criterion: usize,
+    /// The saved position of the match, or [`None`] if the hit has no match
+    /// for the criterion
+    pub saved: Option<BlockPosition>,
+    /// The position the criterion matches at now, or [`None`] if it doesn't
+    /// match or isn't a criterion of the search
+    pub found: Option<BlockPosition>,
+}
+
+impl SearchArchive {
+    /// Archives the results of a search together with its definition
+    #[must_use]
+    pub fn new(definition: SearchDefinition, results: SearchResults) -> Self {
+        Self {
+            definition,
+            results,
+        }
+    }
+
+    /// Checks every saved hit again with a generator set up like the search
+    ///
+    /// Returns the matches which don't match at the saved position anymore,
+    /// so an empty list means the results were verified.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::Criterion`] if checking a criterion fails
+    pub fn verify(&self) -> Result<Vec<Discrepancy>, SearchError> {
+        let mut generator = Generator::new(
+            self.definition.version,
+            self.definition.seeds.start,
+            self.definition.dimension,
+            self.definition.flags,
+        );
+
+        self.verify_with(&mut generator)
+    }
+
+    /// Checks every saved hit again with the given generator
+    ///
+    /// The seed of each hit is applied to the generator in the dimension of
+    /// the search, so the generator only has to have the version and flags
+    /// to verify against, see [`Self::verify()`].
+    ///
+    /// # Errors
+    /// Returns [`SearchError::Criterion`] if checking a criterion fails
+    pub fn verify_with(&self, generator: &mut Generator) -> Result<Vec<Discrepancy>, SearchError> {
+        let criteria = &self.definition.criteria;
+        let mut discrepancies = Vec::new();
+
+        for hit in &self.results.hits {
+            generator.apply_seed(self.definition.dimension, hit.seed);
+
+            for (criterion, condition) in criteria.iter().enumerate() {
+                let saved = hit
+                    .matches
+                    .iter()
+                    .find(|found| found.criterion == criterion)
+                    .map(|found| found.pos);
+
+                let found = condition
+                    .check(generator)
+                    .map_err(|error| SearchError::Criterion {
+                        seed: hit.seed,
+                        criterion,
+                        error,
+                    })?;
+
+                if saved != found {
+                    discrepancies.push(Discrepancy {
+                        seed: hit.seed,
+                        criterion,
+                        saved,
+                        found,
+                    });
+                }
+            }
+
+            // Matches of criteria which aren't in the search can't be checked
+            for unknown in hit
+                .matches
+                .iter()
+                .filter(|found| found.criterion >= criteria.len())
+            {
+                discrepancies.push(Discrepancy {
+                    seed: hit.seed,
+                    criterion: unknown.criterion,
+                    saved: Some(unknown.pos),
+                    found: None,
+                });
+            }
+        }
+
+        Ok(discrepancies)
+    }
+}
@@ -0,0 +1,66 @@
+use super::{SearchHit, SeedRange};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The saved state of a search, which can be resumed later
+///
+/// Every seed from the start of the range up to [`Self::processed`] has been
+/// checked and the hits among them are stored in the checkpoint. Seeds after
+/// that are checked again when resuming.
+///
+/// See [`super::SearchDefinition::run_checkpointed()`] for creating
+/// checkpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Checkpoint {
+    /// The seeds of the search the checkpoint was made for
+    pub seeds: SeedRange,
+    /// The amount of seeds from the start of the range which have been checked
+    pub processed: u64,
+    /// The hits found in the processed seeds
+    pub hits: Vec<SearchHit>,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint for a search which hasn't started yet
+    #[must_use]
+    pub fn new(seeds: SeedRange) -> Self {
+        Self {
+            seeds,
+            processed: 0,
+            hits: Vec::new(),
+        }
+    }
+
+    /// Creates a checkpoint from the hits found in the processed seeds
+    ///
+    /// Only the hits of finished batches are given, the hits of later
+    /// batches would be found again when resuming.
+    pub(super) fn from_hits(seeds: SeedRange, processed: u64, hits: &[SearchHit]) -> Self {
+        debug_assert!(hits.iter().all(|hit| seeds.offset_of(hit.seed) < processed));
+
+        let mut hits = hits.to_vec();
+        hits.sort_by_key(|hit| hit.seed);
+
+        Self {
+            seeds,
+            processed,
+            hits,
+        }
+    }
+
+    /// Gets the next seed which will be checked when resuming
+    ///
+    /// Returns [`None`] if the search is finished
+    #[must_use]
+    pub fn next_seed(&self) -> Option<i64> {
+        (!self.is_finished()).then(|| self.seeds.nth(self.processed))
+    }
+
+    /// Checks if every seed of the search has been checked
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.processed >= self.seeds.len()
+    }
+}
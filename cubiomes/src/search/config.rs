@@ -0,0 +1,203 @@
+use super::{error::SearchError, Checkpoint, SearchArchive, SearchDefinition, SearchResults};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+/// A file format for search definitions and results
+///
+/// Each format is only available with its feature enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ConfigFormat {
+    /// The toml format, requires the `toml` feature
+    #[cfg(feature = "toml")]
+    Toml,
+    /// The json format, requires the `json` feature
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl ConfigFormat {
+    /// Picks the format based on the extension of a file
+    ///
+    /// # Errors
+    /// Returns [`SearchError::UnknownFormat`] if the extension is not one of
+    /// the enabled formats
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, SearchError> {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match extension.as_str() {
+            #[cfg(feature = "toml")]
+            "toml" => Ok(Self::Toml),
+            #[cfg(feature = "json")]
+            "json" => Ok(Self::Json),
+            _ => Err(SearchError::UnknownFormat(extension)),
+        }
+    }
+
+    /// Parses a value from a string in this format
+    ///
+    /// # Errors
+    /// Returns an error if the string is not a valid value in this format
+    pub fn parse<T: DeserializeOwned>(self, input: &str) -> Result<T, SearchError> {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::Toml => Ok(toml::from_str(input)?),
+            #[cfg(feature = "json")]
+            Self::Json => Ok(serde_json::from_str(input)?),
+        }
+    }
+
+    /// Writes a value into a string in this format
+    ///
+    /// # Errors
+    /// Returns an error if the value can't be represented in this format
+    pub fn write<T: Serialize>(self, value: &T) -> Result<String, SearchError> {
+        match self {
+            #[cfg(feature = "toml")]
+            Self::Toml => Ok(toml::to_string_pretty(value)?),
+            #[cfg(feature = "json")]
+            Self::Json => Ok(serde_json::to_string_pretty(value)?),
+        }
+    }
+}
+
+impl SearchDefinition {
+    /// Loads a search definition from a config file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or is not a valid
+    /// definition
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        format.parse(&fs::read_to_string(path)?)
+    }
+
+    /// Saves the search definition to a config file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be written
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        Ok(fs::write(path, format.write(self)?)?)
+    }
+
+    /// Runs the search, saving checkpoints to a file
+    ///
+    /// If the file already exists, the search is resumed from the checkpoint
+    /// in it. The checkpoint is saved every `interval` and once the search is
+    /// finished, see [`Self::run_checkpointed()`].
+    ///
+    /// # Errors
+    /// Returns an error if the checkpoint can't be read or written and
+    /// otherwise the same errors as [`Self::run_checkpointed()`]
+    pub fn run_resumable(
+        &self,
+        checkpoint: impl AsRef<Path>,
+        interval: Duration,
+    ) -> Result<SearchResults, SearchError> {
+        let path = checkpoint.as_ref();
+        let resume = if path.exists() {
+            Some(Checkpoint::load(path)?)
+        } else {
+            None
+        };
+
+        self.run_checkpointed(resume, interval, |checkpoint| checkpoint.save(path))
+    }
+}
+
+impl Checkpoint {
+    /// Loads a checkpoint from a file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or doesn't contain a
+    /// checkpoint
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        format.parse(&fs::read_to_string(path)?)
+    }
+
+    /// Saves the checkpoint to a file
+    ///
+    /// The checkpoint is first written next to the file and then moved over
+    /// it, so the previous checkpoint stays intact if writing is interrupted.
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be written
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SearchError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+
+        fs::write(&temporary, format.write(self)?)?;
+        Ok(fs::rename(&temporary, path)?)
+    }
+}
+
+impl SearchResults {
+    /// Loads search results from a file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or doesn't contain results
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        format.parse(&fs::read_to_string(path)?)
+    }
+
+    /// Saves the search results to a file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be written
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        Ok(fs::write(path, format.write(self)?)?)
+    }
+}
+
+impl SearchArchive {
+    /// Loads an archive of search results from a file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be read or doesn't contain an
+    /// archive
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        format.parse(&fs::read_to_string(path)?)
+    }
+
+    /// Saves the archive to a file
+    ///
+    /// The format of the file is picked from its extension, see
+    /// [`ConfigFormat::from_path()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file can't be written
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SearchError> {
+        let format = ConfigFormat::from_path(&path)?;
+        Ok(fs::write(path, format.write(self)?)?)
+    }
+}
@@ -0,0 +1,248 @@
+use crate::enums::{BiomeID, Dimension, StructureType};
+use crate::generator::{
+    error::{GeneratorError, TryFromRangeError},
+    Aabb, BlockPosition, Generator, Range, Scale, YLevel,
+};
+use crate::structures::strongholds::first_stronghold;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A condition a seed has to fulfill to match a search
+///
+/// Every criterion is checked against a generator with the seed applied. A
+/// matching criterion gives the position it matched at.
+///
+/// With the `serde` feature, criteria are serialized as tables with a `type`
+/// field naming the criterion, for example in toml:
+///
+/// ```toml
+/// [[criteria]]
+/// type = "structure"
+/// structure = "Village"
+/// x = 0
+/// z = 0
+/// radius = 256
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum Criterion {
+    /// The biome at a single block is the given biome
+    BiomeAt {
+        /// The biome required at the block
+        biome: BiomeID,
+        /// The x coordinate of the block
+        x: i32,
+        /// The z coordinate of the block
+        z: i32,
+        /// The y level of the block
+        #[cfg_attr(feature = "serde", serde(default = "default_y"))]
+        y: YLevel,
+    },
+    /// The biome is found within a square around a position
+    ///
+    /// The biomes are checked at [`Scale::Quad`], using
+    /// [`Generator::biome_positions()`]. The matched position is the cell of
+    /// the biome closest to the center.
+    ///
+    /// With [`crate::generator::GeneratorFlags::LargeBiomes`] the cells are
+    /// still 4x4 blocks, so `min_cells` covers the same area in both world
+    /// types.
+    Biome {
+        /// The biome to find
+        biome: BiomeID,
+        /// The x coordinate of the center of the square
+        x: i32,
+        /// The z coordinate of the center of the square
+        z: i32,
+        /// Half of the width of the square in blocks
+        radius: u32,
+        /// The y level at which the biomes are checked
+        ///
+        /// The level is resolved at the center, so [`YLevel::Surface`] checks
+        /// the whole square at the height of the surface there.
+        #[cfg_attr(feature = "serde", serde(default = "default_y"))]
+        y: YLevel,
+        /// The least amount of 4x4 cells of the biome required
+        #[cfg_attr(feature = "serde", serde(default = "default_min_cells"))]
+        min_cells: u32,
+    },
+    /// A structure is found within a radius of a position
+    ///
+    /// Uses [`Generator::nearest_structure()`], so only structures which are
+    /// verified to generate match.
+    Structure {
+        /// The type of the structure
+        structure: StructureType,
+        /// The x coordinate of the center of the search
+        x: i32,
+        /// The z coordinate of the center of the search
+        z: i32,
+        /// The largest allowed distance to the structure in blocks
+        radius: u32,
+    },
+    /// A structure is found within a radius of the world spawn
+    ///
+    /// The spawn is estimated with [`Generator::estimate_spawn()`], so this
+    /// only works in the overworld.
+    StructureNearSpawn {
+        /// The type of the structure
+        structure: StructureType,
+        /// The largest allowed distance from spawn to the structure in blocks
+        radius: u32,
+    },
+    /// A structure is found within a radius of a position in the nether
+    ///
+    /// This is [`Self::Structure`] checked in the nether, whatever the
+    /// dimension of the search is, so fortresses and bastions can be combined
+    /// with criteria in the overworld.
+    NetherStructure {
+        /// The type of the structure
+        structure: StructureType,
+        /// The x coordinate of the center of the search, in nether blocks
+        x: i32,
+        /// The z coordinate of the center of the search, in nether blocks
+        z: i32,
+        /// The largest allowed distance to the structure in blocks
+        radius: u32,
+    },
+    /// The first stronghold is within a radius of a position
+    ///
+    /// The first stronghold is one of the three strongholds of the innermost
+    /// ring, see [`first_stronghold()`]. Its estimate is checked first, so
+    /// only seeds with a stronghold near the position generate the biomes to
+    /// refine it. Putting the position in a convenient direction, like towards
+    /// a nether fortress, picks the seeds with a stronghold there. Only works
+    /// in the overworld.
+    FirstStronghold {
+        /// The x coordinate of the center of the search
+        x: i32,
+        /// The z coordinate of the center of the search
+        z: i32,
+        /// The largest allowed distance to the stronghold in blocks
+        radius: u32,
+    },
+}
+
+impl Criterion {
+    /// Checks the criterion against a generator
+    ///
+    /// The generator should already have the seed which is checked applied.
+    /// Returns the position the criterion matched at, or [`None`] if the
+    /// criterion did not match.
+    ///
+    /// # Errors
+    /// Returns an error if generating the biomes for the criterion fails, and
+    /// [`GeneratorError::TryFromRangeError`] if the area of a biome criterion
+    /// reaches past the coordinates of an i32
+    pub fn check(
+        &self,
+        generator: &mut Generator,
+    ) -> Result<Option<BlockPosition>, GeneratorError> {
+        match *self {
+            Self::BiomeAt { biome, x, z, y } => {
+                Ok((generator.biome_at_level((x, z), y)? == biome)
+                    .then_some(BlockPosition::new(x, z)))
+            }
+            Self::Biome {
+                biome,
+                x,
+                z,
+                radius,
+                y,
+                min_cells,
+            } => {
+                // The radius can come straight from a config, so it may not
+                // fit around the center
+                let bounds = |center: i32, error: TryFromRangeError| {
+                    i32::try_from(radius)
+                        .ok()
+                        .and_then(|radius| {
+                            Some((center.checked_sub(radius)?, center.checked_add(radius)?))
+                        })
+                        .ok_or(error)
+                };
+                let (min_x, max_x) = bounds(x, TryFromRangeError::XOutOfBounds)?;
+                let (min_z, max_z) = bounds(z, TryFromRangeError::ZOutOfBounds)?;
+
+                let y = y.resolve(generator, (x, z))?;
+                let area = Aabb::new((min_x, y, min_z), (max_x, y, max_z));
+
+                let range = Range {
+                    size_y: 0,
+                    ..area.to_range(Scale::Quad)
+                };
+
+                let positions = generator.biome_positions(biome, range)?;
+
+                if positions.len() < min_cells.max(1) as usize {
+                    return Ok(None);
+                }
+
+                Ok(positions
+                    .into_iter()
+                    .min_by_key(|pos| i64::from(pos.x - x).pow(2) + i64::from(pos.z - z).pow(2)))
+            }
+            Self::Structure {
+                structure,
+                x,
+                z,
+                radius,
+            } => Ok(generator
+                .nearest_structure(structure, BlockPosition::new(x, z), radius)
+                .map(|hit| hit.pos)),
+            Self::StructureNearSpawn { structure, radius } => {
+                let spawn = generator.estimate_spawn()?;
+
+                Ok(generator
+                    .nearest_structure(structure, spawn, radius)
+                    .map(|hit| hit.pos))
+            }
+            Self::NetherStructure {
+                structure,
+                x,
+                z,
+                radius,
+            } => {
+                let (dimension, seed) = (generator.dimension(), generator.seed());
+                if dimension != Dimension::DIM_NETHER {
+                    generator.apply_seed(Dimension::DIM_NETHER, seed);
+                }
+
+                let hit = generator.nearest_structure(structure, BlockPosition::new(x, z), radius);
+
+                // The following criteria are checked in the dimension of the
+                // search again
+                if dimension != Dimension::DIM_NETHER {
+                    generator.apply_seed(dimension, seed);
+                }
+
+                Ok(hit.map(|hit| hit.pos))
+            }
+            Self::FirstStronghold { x, z, radius } => {
+                let estimate = first_stronghold(generator.minecraft_version(), generator.seed());
+                let center = BlockPosition::new(x, z);
+
+                // Refining moves the stronghold by up to 112 blocks on each
+                // axis, which is at most 159 blocks away from the estimate
+                if center.distance(estimate.approx) > f64::from(radius) + 159.0 {
+                    return Ok(None);
+                }
+
+                let pos = estimate.refine(generator)?;
+                Ok((center.distance(pos) <= f64::from(radius)).then_some(pos))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_y() -> YLevel {
+    YLevel::Absolute(64)
+}
+
+#[cfg(feature = "serde")]
+fn default_min_cells() -> u32 {
+    1
+}
@@ -0,0 +1,132 @@
+//! Errors related to [`super::SearchDefinition`]
+
+use super::SeedRange;
+use crate::enums::{Dimension, MCVersion, StructureType};
+use crate::generator::error::GeneratorError;
+use thiserror::Error;
+
+/// An error while loading or running a search
+// The errors of the config formats are not Copy, so with them disabled this
+// could be Copy, but it shouldn't change based on features
+#[allow(missing_copy_implementations)]
+#[derive(Error, Debug)]
+pub enum SearchError {
+    /// The search has no criteria, so every seed would match
+    #[error("The search definition has no criteria")]
+    NoCriteria,
+    /// The checkpoint to resume from was made for a different range of seeds
+    #[error("Checkpoint is for seeds {found:?}, but the search is for seeds {expected:?}")]
+    CheckpointMismatch {
+        /// The seeds of the search
+        expected: SeedRange,
+        /// The seeds of the checkpoint
+        found: SeedRange,
+    },
+    /// Checking a criterion failed in the generator
+    #[error("Failed to check a criterion: {0}")]
+    GeneratorError(GeneratorError),
+    /// Checking a criterion failed for a seed of the search
+    #[error("Failed to check criterion {criterion} for seed {seed}: {error}")]
+    Criterion {
+        /// The seed which was checked
+        seed: i64,
+        /// The index of the failed criterion in
+        /// [`super::SearchDefinition::criteria`]
+        criterion: usize,
+        /// The error of the generator
+        error: GeneratorError,
+    },
+    /// A criterion looks for a structure which never generates where it is
+    /// checked, so no seed could match
+    ///
+    /// See [`StructureType::available_in()`].
+    #[error("Criterion {criterion} looks for {structure:?}, which doesn't generate in {dimension:?} of {version:?}")]
+    UnavailableStructure {
+        /// The index of the criterion in [`super::SearchDefinition::criteria`]
+        criterion: usize,
+        /// The structure of the criterion
+        structure: StructureType,
+        /// The version of the search
+        version: MCVersion,
+        /// The dimension the criterion is checked in
+        dimension: Dimension,
+    },
+    /// A criteria expression couldn't be parsed
+    ///
+    /// See [`super::Criterion::parse_all()`] for the syntax.
+    #[error("Invalid criteria expression at offset {offset}: expected {expected}")]
+    Expression {
+        /// The offset in bytes of the part which couldn't be parsed
+        offset: usize,
+        /// A description of what was expected there
+        expected: &'static str,
+    },
+    /// The blocking task running the search failed
+    #[cfg(feature = "tokio")]
+    #[error("Search task failed: {0}")]
+    Task(tokio::task::JoinError),
+    /// The config file has an extension which is not a supported format
+    ///
+    /// The formats are only available with their features enabled, see
+    /// [`super::ConfigFormat`].
+    #[cfg(any(feature = "toml", feature = "json"))]
+    #[error("Unsupported config format {0:?}")]
+    UnknownFormat(String),
+    /// Reading or writing a config file failed
+    #[cfg(any(feature = "toml", feature = "json"))]
+    #[error("Failed to access config file: {0}")]
+    Io(std::io::Error),
+    /// The config could not be parsed as toml
+    #[cfg(feature = "toml")]
+    #[error("Failed to parse toml: {0}")]
+    TomlDeserialize(toml::de::Error),
+    /// The value could not be written as toml
+    #[cfg(feature = "toml")]
+    #[error("Failed to write toml: {0}")]
+    TomlSerialize(toml::ser::Error),
+    /// The config could not be parsed or written as json
+    #[cfg(feature = "json")]
+    #[error("Failed to parse or write json: {0}")]
+    Json(serde_json::Error),
+}
+
+impl From<GeneratorError> for SearchError {
+    fn from(value: GeneratorError) -> Self {
+        Self::GeneratorError(value)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl From<tokio::task::JoinError> for SearchError {
+    fn from(value: tokio::task::JoinError) -> Self {
+        Self::Task(value)
+    }
+}
+
+#[cfg(any(feature = "toml", feature = "json"))]
+impl From<std::io::Error> for SearchError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<toml::de::Error> for SearchError {
+    fn from(value: toml::de::Error) -> Self {
+        Self::TomlDeserialize(value)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl From<toml::ser::Error> for SearchError {
+    fn from(value: toml::ser::Error) -> Self {
+        Self::TomlSerialize(value)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for SearchError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
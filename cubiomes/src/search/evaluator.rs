@@ -0,0 +1,114 @@
+use crate::generator::{error::GeneratorError, Cache, Generator, Range};
+
+/// Scores seeds one after another, reusing its generator and caches
+///
+/// An evaluator holds a generator and a buffer for the cache of every range.
+/// [`Self::evaluate()`] applies a seed, fills the caches of the ranges into
+/// the buffers and gives them to the scoring function, so checking millions
+/// of seeds doesn't set up a generator or allocate the biomes of the caches
+/// for each one.
+///
+/// The evaluator runs on a single thread. For a parallel scan, create one
+/// evaluator per thread, like with [`super::ParallelSeeds`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, Dimension, MCVersion};
+/// use cubiomes::generator::{Generator, GeneratorFlags, Range, Scale};
+/// use cubiomes::search::Evaluator;
+///
+/// let generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     0,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// // The share of jungle around spawn
+/// let mut evaluator = Evaluator::new(
+///     generator,
+///     [Range::plane(Scale::Chunk, -16, -16, 32, 32, 64)],
+///     |_, caches| caches[0].histogram().get(&BiomeID::jungle).copied().unwrap_or(0),
+/// );
+///
+/// let best = (0..100)
+///     .map(|seed| Ok((evaluator.evaluate(seed)?, seed)))
+///     .collect::<Result<Vec<_>, cubiomes::generator::error::GeneratorError>>()
+///     .expect("failed to generate biomes")
+///     .into_iter()
+///     .max();
+///
+/// assert_eq!(best, Some((398, 80)));
+/// ```
+#[derive(Debug)]
+pub struct Evaluator<F> {
+    generator: Generator,
+    ranges: Vec<Range>,
+    buffers: Vec<Vec<i32>>,
+    score: F,
+}
+
+impl<F, Score> Evaluator<F>
+where
+    F: FnMut(&Generator, &[Cache<'_>]) -> Score,
+{
+    /// Creates an evaluator scoring the biomes of ranges
+    ///
+    /// The seeds are applied in the dimension of the generator. `score` is
+    /// given the generator with the seed applied and a filled cache for every
+    /// range, in the order of `ranges`.
+    #[must_use]
+    pub fn new(generator: Generator, ranges: impl IntoIterator<Item = Range>, score: F) -> Self {
+        let ranges: Vec<Range> = ranges.into_iter().collect();
+        let buffers = ranges
+            .iter()
+            .map(|&range| Cache::new(&generator, range).into_buffer())
+            .collect();
+
+        Self {
+            generator,
+            ranges,
+            buffers,
+            score,
+        }
+    }
+
+    /// Gets the ranges filled for every seed
+    #[must_use]
+    pub fn ranges(&self) -> &[Range] {
+        &self.ranges
+    }
+
+    /// Gets the generator, with the seed of the last evaluation applied
+    #[must_use]
+    pub fn generator(&self) -> &Generator {
+        &self.generator
+    }
+
+    /// Scores a seed
+    ///
+    /// # Errors
+    /// Returns an error if filling the cache of a range fails, see
+    /// [`Cache::fill_cache()`]
+    pub fn evaluate(&mut self, seed: i64) -> Result<Score, GeneratorError> {
+        self.generator.apply_seed(self.generator.dimension(), seed);
+
+        let mut caches = Vec::with_capacity(self.ranges.len());
+        let mut result = Ok(());
+        for (&range, buffer) in self.ranges.iter().zip(&mut self.buffers) {
+            let mut cache = Cache::with_buffer(&self.generator, range, std::mem::take(buffer));
+            result = result.and_then(|()| cache.fill_cache());
+            caches.push(cache);
+        }
+
+        let score = result.map(|()| (self.score)(&self.generator, &caches));
+
+        // The buffers are put back even if filling failed, so they are never
+        // allocated again
+        for (buffer, cache) in self.buffers.iter_mut().zip(caches) {
+            *buffer = cache.into_buffer();
+        }
+
+        score
+    }
+}
@@ -0,0 +1,350 @@
+use super::{error::SearchError, Criterion};
+use crate::enums::{BiomeID, StructureType};
+use crate::generator::YLevel;
+use cubiomes_sys::num_traits::FromPrimitive;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{self, value::SeqAccessDeserializer, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+impl Criterion {
+    /// Parses criteria from a textual expression
+    ///
+    /// The expression is a list of criteria joined with `AND`, with each
+    /// criterion written as:
+    ///
+    /// - `biome(<biome>) at (<x>, <z>)` for [`Criterion::BiomeAt`]
+    /// - `biome(<biome>) within <radius> of (<x>, <z>)` for
+    ///   [`Criterion::Biome`]
+    /// - `structure(<structure>) within <radius> of (<x>, <z>)` for
+    ///   [`Criterion::Structure`]
+    /// - `structure(<structure>) within <radius> of spawn` for
+    ///   [`Criterion::StructureNearSpawn`]
+    /// - `nether_structure(<structure>) within <radius> of (<x>, <z>)` for
+    ///   [`Criterion::NetherStructure`]
+    /// - `stronghold within <radius> of (<x>, <z>)` for
+    ///   [`Criterion::FirstStronghold`]
+    ///
+    /// Biomes and structures are named like their variants, and the keywords
+    /// and names are case insensitive. The biomes are checked at y 64.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{BiomeID, StructureType};
+    /// use cubiomes::generator::YLevel;
+    /// use cubiomes::search::Criterion;
+    ///
+    /// let criteria = Criterion::parse_all(
+    ///     "biome(mushroom_fields) within 512 of (0,0) AND structure(village) within 256 of spawn",
+    /// )
+    /// .expect("the expression is valid");
+    ///
+    /// assert_eq!(
+    ///     criteria,
+    ///     [
+    ///         Criterion::Biome {
+    ///             biome: BiomeID::mushroom_fields,
+    ///             x: 0,
+    ///             z: 0,
+    ///             radius: 512,
+    ///             y: YLevel::Absolute(64),
+    ///             min_cells: 1,
+    ///         },
+    ///         Criterion::StructureNearSpawn {
+    ///             structure: StructureType::Village,
+    ///             radius: 256,
+    ///         },
+    ///     ]
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`SearchError::Expression`] with the offset of the first part
+    /// of the expression which couldn't be parsed
+    pub fn parse_all(expression: &str) -> Result<Vec<Self>, SearchError> {
+        let mut parser = Parser::new(expression);
+        let mut criteria = vec![parser.criterion()?];
+
+        while !parser.at_end() {
+            parser.keyword("AND")?;
+            criteria.push(parser.criterion()?);
+        }
+
+        Ok(criteria)
+    }
+}
+
+impl FromStr for Criterion {
+    type Err = SearchError;
+
+    /// Parses a single criterion, see [`Criterion::parse_all()`]
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(expression);
+        let criterion = parser.criterion()?;
+
+        if parser.at_end() {
+            Ok(criterion)
+        } else {
+            Err(parser.error("the end of the criterion"))
+        }
+    }
+}
+
+/// The center a radius is measured from
+enum Center {
+    Point(i32, i32),
+    Spawn,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    fn error(&self, expected: &'static str) -> SearchError {
+        SearchError::Expression {
+            offset: self.offset,
+            expected,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.offset..];
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.offset == self.input.len()
+    }
+
+    /// Takes the next word, made of letters, digits and underscores
+    fn word(&mut self) -> &'a str {
+        self.skip_whitespace();
+
+        let rest = &self.input[self.offset..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.offset += len;
+
+        &rest[..len]
+    }
+
+    fn peek_word(&mut self) -> &'a str {
+        let offset = self.offset;
+        let word = self.word();
+        self.offset = offset;
+
+        word
+    }
+
+    fn keyword(&mut self, keyword: &'static str) -> Result<(), SearchError> {
+        self.skip_whitespace();
+        let start = self.offset;
+
+        if self.word().eq_ignore_ascii_case(keyword) {
+            Ok(())
+        } else {
+            self.offset = start;
+            Err(self.error(keyword))
+        }
+    }
+
+    fn symbol(&mut self, symbol: char, expected: &'static str) -> Result<(), SearchError> {
+        self.skip_whitespace();
+
+        if self.input[self.offset..].starts_with(symbol) {
+            self.offset += symbol.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    fn number<T: FromStr>(&mut self, expected: &'static str) -> Result<T, SearchError> {
+        self.skip_whitespace();
+        let start = self.offset;
+
+        let negative = self.input[self.offset..].starts_with('-');
+        if negative {
+            self.offset += 1;
+        }
+        self.word();
+
+        self.input[start..self.offset].parse().map_err(|_| {
+            self.offset = start;
+            self.error(expected)
+        })
+    }
+
+    /// Parses a name in parentheses, like `(village)`
+    fn name<T>(
+        &mut self,
+        expected: &'static str,
+        lookup: impl Fn(&str) -> Option<T>,
+    ) -> Result<T, SearchError> {
+        self.symbol('(', "(")?;
+
+        self.skip_whitespace();
+        let start = self.offset;
+        let found = lookup(self.word()).ok_or_else(|| {
+            self.offset = start;
+            self.error(expected)
+        })?;
+
+        self.symbol(')', ")")?;
+        Ok(found)
+    }
+
+    fn point(&mut self) -> Result<(i32, i32), SearchError> {
+        self.symbol('(', "(")?;
+        let x = self.number("an x coordinate")?;
+        self.symbol(',', ",")?;
+        let z = self.number("a z coordinate")?;
+        self.symbol(')', ")")?;
+
+        Ok((x, z))
+    }
+
+    /// Parses `within <radius> of <center>`
+    fn within(&mut self) -> Result<(u32, Center), SearchError> {
+        self.keyword("within")?;
+        let radius = self.number("a radius")?;
+        self.keyword("of")?;
+
+        if self.peek_word().eq_ignore_ascii_case("spawn") {
+            self.word();
+            return Ok((radius, Center::Spawn));
+        }
+
+        let (x, z) = self.point()?;
+        Ok((radius, Center::Point(x, z)))
+    }
+
+    /// Parses `within <radius> of (<x>, <z>)`, where spawn isn't supported
+    fn within_point(&mut self) -> Result<(u32, i32, i32), SearchError> {
+        self.skip_whitespace();
+        let start = self.offset;
+
+        match self.within()? {
+            (radius, Center::Point(x, z)) => Ok((radius, x, z)),
+            (_, Center::Spawn) => {
+                self.offset = start;
+                Err(self.error("a radius around a position instead of spawn"))
+            }
+        }
+    }
+
+    fn criterion(&mut self) -> Result<Criterion, SearchError> {
+        self.skip_whitespace();
+        let start = self.offset;
+
+        match self.word().to_ascii_lowercase().as_str() {
+            "biome" => {
+                let biome = self.name("a biome", biome_named)?;
+
+                if self.peek_word().eq_ignore_ascii_case("at") {
+                    self.word();
+                    let (x, z) = self.point()?;
+
+                    return Ok(Criterion::BiomeAt {
+                        biome,
+                        x,
+                        z,
+                        y: YLevel::Absolute(64),
+                    });
+                }
+
+                let (radius, x, z) = self.within_point()?;
+                Ok(Criterion::Biome {
+                    biome,
+                    x,
+                    z,
+                    radius,
+                    y: YLevel::Absolute(64),
+                    min_cells: 1,
+                })
+            }
+            "structure" => {
+                let structure = self.name("a structure", structure_named)?;
+
+                Ok(match self.within()? {
+                    (radius, Center::Point(x, z)) => Criterion::Structure {
+                        structure,
+                        x,
+                        z,
+                        radius,
+                    },
+                    (radius, Center::Spawn) => Criterion::StructureNearSpawn { structure, radius },
+                })
+            }
+            "nether_structure" => {
+                let structure = self.name("a structure", structure_named)?;
+                let (radius, x, z) = self.within_point()?;
+
+                Ok(Criterion::NetherStructure {
+                    structure,
+                    x,
+                    z,
+                    radius,
+                })
+            }
+            "stronghold" => {
+                let (radius, x, z) = self.within_point()?;
+
+                Ok(Criterion::FirstStronghold { x, z, radius })
+            }
+            _ => {
+                self.offset = start;
+                Err(self.error("biome, structure, nether_structure or stronghold"))
+            }
+        }
+    }
+}
+
+fn biome_named(name: &str) -> Option<BiomeID> {
+    (0..=u8::MAX)
+        .filter_map(BiomeID::from_u8)
+        .find(|biome| format!("{biome:?}").eq_ignore_ascii_case(name))
+}
+
+fn structure_named(name: &str) -> Option<StructureType> {
+    (0..StructureType::FEATURE_NUM as i32)
+        .filter_map(StructureType::from_i32)
+        .find(|structure| format!("{structure:?}").eq_ignore_ascii_case(name))
+}
+
+/// Deserializes criteria from either a list of criteria or an expression
+#[cfg(feature = "serde")]
+pub(super) fn deserialize_criteria<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Criterion>, D::Error> {
+    struct CriteriaVisitor;
+
+    impl<'de> Visitor<'de> for CriteriaVisitor {
+        type Value = Vec<Criterion>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            formatter.write_str("a list of criteria or a criteria expression")
+        }
+
+        fn visit_str<E: de::Error>(self, expression: &str) -> Result<Self::Value, E> {
+            Criterion::parse_all(expression).map_err(E::custom)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            Vec::deserialize(SeqAccessDeserializer::new(seq))
+        }
+    }
+
+    deserializer.deserialize_any(CriteriaVisitor)
+}
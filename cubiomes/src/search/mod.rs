@@ -0,0 +1,727 @@
+//! Module containing seed searches driven by a list of criteria
+//!
+//! A search is described by a [`SearchDefinition`], which holds the settings
+//! for the generator, the seeds to check and the [`Criterion`]s each seed has
+//! to fulfill. Running the definition checks the seeds on multiple threads
+//! and gives the matching seeds as [`SearchResults`].
+//!
+//! With the `toml` or `json` features, definitions can be loaded from config
+//! files, so searches can be driven without writing any rust. See
+//! `SearchDefinition::load()`. An example config in toml:
+//!
+//! ```toml
+//! version = "MC_1_20_6"
+//! threads = 4
+//!
+//! [seeds]
+//! start = 0
+//! end = 100000
+//!
+//! [[criteria]]
+//! type = "biome"
+//! biome = "mushroom_fields"
+//! x = 0
+//! z = 0
+//! radius = 512
+//!
+//! [[criteria]]
+//! type = "structure"
+//! structure = "Village"
+//! x = 0
+//! z = 0
+//! radius = 256
+//! ```
+//!
+//! The criteria can also be written as an expression, like
+//! `criteria = "structure(village) within 256 of spawn"`, see
+//! [`Criterion::parse_all()`].
+//!
+//! Results can be archived with their search as a [`SearchArchive`], which
+//! checks the saved hits again with [`SearchArchive::verify()`].
+//!
+//! Common searches, like the ones speedrunners run, are available as a
+//! [`SearchPreset`] to start from.
+//!
+//! Instead of matching seeds, a [`BiomeSurvey`] samples the biomes of many
+//! seeds and adds them up, to compare how often biomes generate in different
+//! versions. Custom scans scoring millions of seeds can reuse their generator
+//! and caches with an [`Evaluator`].
+//!
+//! With the `tokio` feature, searches can be run from async code without
+//! blocking the executor. See `SearchDefinition::stream()`, which gives the
+//! hits as a stream while the search is running.
+
+use crate::enums::{Dimension, MCVersion};
+use crate::generator::{BlockPosition, Generator, GeneratorFlags};
+use crate::progress::{CancellationToken, Progress};
+use error::SearchError;
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Mutex, PoisonError,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub use archive::*;
+pub use checkpoint::*;
+#[cfg(any(feature = "toml", feature = "json"))]
+pub use config::*;
+pub use criteria::*;
+pub use evaluator::*;
+pub use parallel::*;
+pub use presets::*;
+#[cfg(feature = "tokio")]
+pub use stream::*;
+pub use survey::*;
+
+mod archive;
+mod checkpoint;
+#[cfg(any(feature = "toml", feature = "json"))]
+mod config;
+mod criteria;
+pub mod error;
+mod evaluator;
+mod expression;
+mod parallel;
+mod presets;
+#[cfg(feature = "tokio")]
+mod stream;
+mod survey;
+
+#[cfg(test)]
+mod tests;
+
+/// Whether threads can be spawned on the target
+///
+/// Wasm without the atomics feature, like wasm32-wasip1, has no threads, so
+/// searches are run on the calling thread there.
+const THREADS_SUPPORTED: bool = !cfg!(all(target_family = "wasm", not(target_feature = "atomics")));
+
+// The amount of seeds a thread takes at once
+const BATCH_SIZE: u64 = 256;
+
+/// A range of seeds to search through
+///
+/// The start is inclusive and the end exclusive, so a range from 0 to 10
+/// contains 10 seeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SeedRange {
+    /// The first seed of the range
+    pub start: i64,
+    /// The seed after the last seed of the range
+    pub end: i64,
+}
+
+impl SeedRange {
+    /// Creates a new range of seeds
+    #[must_use]
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// Gets the amount of seeds in the range
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        (i128::from(self.end) - i128::from(self.start)).max(0) as u64
+    }
+
+    /// Checks if the range contains no seeds
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the seed at an offset from the start of the range
+    fn nth(&self, offset: u64) -> i64 {
+        self.start.wrapping_add(offset.cast_signed())
+    }
+
+    /// Gets the offset of a seed from the start of the range
+    fn offset_of(&self, seed: i64) -> u64 {
+        seed.wrapping_sub(self.start).cast_unsigned()
+    }
+}
+
+/// A full description of a seed search
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{MCVersion, StructureType};
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::search::{Criterion, SearchDefinition, SeedRange};
+///
+/// let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 64));
+///
+/// search.criteria.push(Criterion::Structure {
+///     structure: StructureType::Village,
+///     x: 0,
+///     z: 0,
+///     radius: 512,
+/// });
+///
+/// let results = search.run().expect("search failed");
+///
+/// assert_eq!(results.seeds_checked, 64);
+/// assert_eq!(results.hits.len(), 34);
+///
+/// let first = &results.hits[0];
+/// assert_eq!((first.seed, first.matches[0].pos), (2, BlockPosition::new(-416, 240)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchDefinition {
+    /// The minecraft version to generate the seeds in
+    pub version: MCVersion,
+    /// The dimension the criteria are checked in
+    #[cfg_attr(feature = "serde", serde(default = "default_dimension"))]
+    pub dimension: Dimension,
+    /// The flags for the generator
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flags: GeneratorFlags,
+    /// The seeds to check
+    pub seeds: SeedRange,
+    /// The amount of threads to use for the search
+    ///
+    /// If not set, the available parallelism of the system is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub threads: Option<NonZeroUsize>,
+    /// The criteria every matching seed has to fulfill
+    ///
+    /// In config files, the criteria can also be given as an expression, see
+    /// [`Criterion::parse_all()`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "expression::deserialize_criteria")
+    )]
+    pub criteria: Vec<Criterion>,
+}
+
+impl SearchDefinition {
+    /// Creates a new search in the overworld without any criteria
+    #[must_use]
+    pub fn new(version: MCVersion, seeds: SeedRange) -> Self {
+        Self {
+            version,
+            dimension: Dimension::DIM_OVERWORLD,
+            flags: GeneratorFlags::empty(),
+            seeds,
+            threads: None,
+            criteria: Vec::new(),
+        }
+    }
+
+    /// Creates a new search in the overworld of the large biomes world type
+    /// without any criteria
+    ///
+    /// The generators of the search have [`GeneratorFlags::LargeBiomes`], see
+    /// [`Generator::large_biomes()`]. The positions and radii of the criteria
+    /// are in blocks either way, but the biome criteria match different seeds
+    /// since the biomes are larger. Structure criteria only change where the
+    /// biomes decide if a structure generates.
+    #[must_use]
+    pub fn large_biomes(version: MCVersion, seeds: SeedRange) -> Self {
+        Self {
+            flags: GeneratorFlags::LargeBiomes,
+            ..Self::new(version, seeds)
+        }
+    }
+
+    /// Gets the amount of threads the search is run on
+    ///
+    /// This is always 1 on targets without threads, like wasm32-wasip1, where
+    /// the search is run on the calling thread.
+    #[must_use]
+    pub fn thread_count(&self) -> usize {
+        if !THREADS_SUPPORTED {
+            return 1;
+        }
+
+        self.threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+    }
+
+    /// Checks a single seed against every criterion
+    ///
+    /// The seed is applied to the generator before checking. Returns the
+    /// positions of the matches, if every criterion matched.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::Criterion`] with the seed and the criterion if
+    /// checking any of the criteria fails
+    pub fn check_seed(
+        &self,
+        generator: &mut Generator,
+        seed: i64,
+    ) -> Result<Option<SearchHit>, SearchError> {
+        generator.apply_seed(self.dimension, seed);
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_seed();
+
+        let mut matches = Vec::with_capacity(self.criteria.len());
+
+        for (criterion, condition) in self.criteria.iter().enumerate() {
+            let checked = condition
+                .check(generator)
+                .map_err(|error| SearchError::Criterion {
+                    seed,
+                    criterion,
+                    error,
+                })?;
+
+            match checked {
+                Some(pos) => matches.push(CriterionMatch { criterion, pos }),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(SearchHit { seed, matches }))
+    }
+
+    /// Runs the search over every seed in the range
+    ///
+    /// The seeds are split between [`Self::thread_count()`] threads, each with
+    /// their own generator. The hits are sorted by seed.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::NoCriteria`] if there are no criteria,
+    /// [`SearchError::UnavailableStructure`] if a criterion looks for a
+    /// structure which doesn't generate in the version and otherwise the first
+    /// error encountered while checking seeds
+    pub fn run(&self) -> Result<SearchResults, SearchError> {
+        self.run_from(
+            Checkpoint::new(self.seeds),
+            None,
+            None,
+            |_| (),
+            |_, _| Ok(()),
+        )
+    }
+
+    /// Runs the search, periodically giving checkpoints of its state
+    ///
+    /// Every `interval` the current [`Checkpoint`] is passed to
+    /// `on_checkpoint`, which should persist it. A final checkpoint is given
+    /// once the search is finished. The search can be continued from a
+    /// checkpoint by passing it as `resume`, for example after a crash.
+    ///
+    /// `on_checkpoint` is called on the calling thread, while the search runs
+    /// on [`Self::thread_count()`] other threads.
+    ///
+    /// # Errors
+    /// Returns [`SearchError::CheckpointMismatch`] if the checkpoint is for a
+    /// different range of seeds, the first error returned by `on_checkpoint`
+    /// and otherwise the same errors as [`Self::run()`]
+    pub fn run_checkpointed(
+        &self,
+        resume: Option<Checkpoint>,
+        interval: Duration,
+        mut on_checkpoint: impl FnMut(&Checkpoint) -> Result<(), SearchError>,
+    ) -> Result<SearchResults, SearchError> {
+        let checkpoint = resume.unwrap_or_else(|| Checkpoint::new(self.seeds));
+
+        if checkpoint.seeds != self.seeds {
+            return Err(SearchError::CheckpointMismatch {
+                expected: self.seeds,
+                found: checkpoint.seeds,
+            });
+        }
+
+        self.run_from(
+            checkpoint,
+            Some(interval),
+            None,
+            |_| (),
+            |checkpoint, _| on_checkpoint(checkpoint),
+        )
+    }
+
+    /// Runs the search, periodically reporting its progress
+    ///
+    /// Every `interval` the [`Progress`] of the search is passed to
+    /// `on_progress`, along with a final report once the search is finished.
+    /// The progress counts the seeds checked out of the seeds in the range.
+    ///
+    /// The search stops as soon as `cancel` is cancelled, which is checked
+    /// between each seed. The results then only contain the seeds from the
+    /// start of the range up to the first batch which wasn't finished, so
+    /// [`SearchResults::seeds_checked`] is less than the length of the range.
+    ///
+    /// `on_progress` is called on the calling thread, while the search runs
+    /// on [`Self::thread_count()`] other threads.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{MCVersion, StructureType};
+    /// use cubiomes::progress::CancellationToken;
+    /// use cubiomes::search::{Criterion, SearchDefinition, SeedRange};
+    /// use std::time::Duration;
+    ///
+    /// let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 64));
+    ///
+    /// search.criteria.push(Criterion::Structure {
+    ///     structure: StructureType::Village,
+    ///     x: 0,
+    ///     z: 0,
+    ///     radius: 512,
+    /// });
+    ///
+    /// let cancel = CancellationToken::new();
+    /// let results = search
+    ///     .run_monitored(&cancel, Duration::from_millis(100), |progress| {
+    ///         println!("{:.1}% done, eta {:?}", progress.fraction() * 100.0, progress.eta());
+    ///     })
+    ///     .expect("search failed");
+    ///
+    /// assert_eq!(results.seeds_checked, 64);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::run()`]
+    pub fn run_monitored(
+        &self,
+        cancel: &CancellationToken,
+        interval: Duration,
+        mut on_progress: impl FnMut(&Progress),
+    ) -> Result<SearchResults, SearchError> {
+        self.run_from(
+            Checkpoint::new(self.seeds),
+            Some(interval),
+            Some(cancel),
+            |_| (),
+            |_, progress| {
+                on_progress(progress);
+                Ok(())
+            },
+        )
+    }
+
+    /// Checks that the structures of the criteria generate where they are
+    /// checked, before checking any seeds
+    fn check_structures(&self) -> Result<(), SearchError> {
+        for (criterion, condition) in self.criteria.iter().enumerate() {
+            let (structure, dimension) = match *condition {
+                Criterion::Structure { structure, .. }
+                | Criterion::StructureNearSpawn { structure, .. } => (structure, self.dimension),
+                Criterion::NetherStructure { structure, .. } => (structure, Dimension::DIM_NETHER),
+                _ => continue,
+            };
+
+            if !structure.available_in(self.version, dimension) {
+                return Err(SearchError::UnavailableStructure {
+                    criterion,
+                    structure,
+                    version: self.version,
+                    dimension,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "info",
+            name = "search",
+            skip_all,
+            fields(
+                version = ?self.version,
+                dimension = ?self.dimension,
+                start = self.seeds.start,
+                end = self.seeds.end,
+                criteria = self.criteria.len(),
+                threads = self.thread_count(),
+            ),
+        )
+    )]
+    fn run_from(
+        &self,
+        checkpoint: Checkpoint,
+        interval: Option<Duration>,
+        cancel: Option<&CancellationToken>,
+        on_hits: impl Fn(&[SearchHit]) + Sync,
+        mut on_tick: impl FnMut(&Checkpoint, &Progress) -> Result<(), SearchError>,
+    ) -> Result<SearchResults, SearchError> {
+        if self.criteria.is_empty() {
+            return Err(SearchError::NoCriteria);
+        }
+        self.check_structures()?;
+
+        let started = Instant::now();
+        let seeds = self.seeds.len();
+        let remaining = seeds.saturating_sub(checkpoint.processed);
+        let next_batch = AtomicU64::new(checkpoint.processed);
+        let checked = AtomicU64::new(0);
+        let failed = AtomicBool::new(false);
+        let stopped = || {
+            failed.load(Ordering::Relaxed) || cancel.is_some_and(CancellationToken::is_cancelled)
+        };
+        let progress = Mutex::new(BatchProgress::new(checkpoint.processed, checkpoint.hits));
+        let error = Mutex::new(None);
+
+        #[cfg(feature = "tracing")]
+        let (span, dispatch) = (
+            tracing::Span::current(),
+            tracing::dispatcher::get_default(Clone::clone),
+        );
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            resumed_at = checkpoint.processed,
+            remaining,
+            "search started"
+        );
+
+        let check_batches = || {
+            // The search threads report to the subscriber of the caller,
+            // under the span of the search
+            #[cfg(feature = "tracing")]
+            let _dispatch = tracing::dispatcher::set_default(&dispatch);
+            #[cfg(feature = "tracing")]
+            let _entered = span.enter();
+
+            let mut generator =
+                Generator::new(self.version, self.seeds.start, self.dimension, self.flags);
+
+            while !stopped() {
+                let start = next_batch.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                if start >= seeds {
+                    break;
+                }
+                let end = (start + BATCH_SIZE).min(seeds);
+
+                let mut batch_hits = Vec::new();
+
+                for offset in start..end {
+                    // A cancelled batch is left unfinished, so it is
+                    // checked again when resuming
+                    if stopped() {
+                        return;
+                    }
+
+                    match self.check_seed(&mut generator, self.seeds.nth(offset)) {
+                        Ok(Some(hit)) => batch_hits.push(hit),
+                        Ok(None) => (),
+                        Err(err) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::error!(error = %err, "checking a seed failed");
+
+                            failed.store(true, Ordering::Relaxed);
+                            error
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .get_or_insert(err);
+                            return;
+                        }
+                    }
+                }
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(start, end, hits = batch_hits.len(), "checked batch");
+
+                on_hits(&batch_hits);
+
+                progress
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .complete(start, end, batch_hits);
+                checked.fetch_add(end - start, Ordering::Relaxed);
+            }
+        };
+
+        // Gives the current checkpoint to on_tick, returns whether the search
+        // should stop
+        let mut tick = || {
+            if failed.load(Ordering::Relaxed) {
+                return true;
+            }
+
+            let checkpoint = {
+                let progress = progress.lock().unwrap_or_else(PoisonError::into_inner);
+                Checkpoint::from_hits(self.seeds, progress.processed, &progress.hits)
+            };
+            let progress = Progress {
+                done: checked.load(Ordering::Relaxed),
+                total: remaining,
+                elapsed: started.elapsed(),
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                done = progress.done,
+                total = progress.total,
+                hits = checkpoint.hits.len(),
+                seeds_per_second = progress.per_second(),
+                "search progress"
+            );
+
+            if let Err(err) = on_tick(&checkpoint, &progress) {
+                failed.store(true, Ordering::Relaxed);
+                error
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .get_or_insert(err);
+                return true;
+            }
+
+            false
+        };
+
+        if THREADS_SUPPORTED {
+            // Every thread holds a sender, so the channel disconnects once all
+            // of them have finished
+            let (running, finished) = mpsc::channel::<()>();
+
+            thread::scope(|scope| {
+                for _ in 0..self.thread_count() {
+                    let running = running.clone();
+                    let check_batches = &check_batches;
+
+                    scope.spawn(move || {
+                        let _running = running;
+                        check_batches();
+                    });
+                }
+                drop(running);
+
+                let Some(interval) = interval else {
+                    return;
+                };
+
+                loop {
+                    let done = !matches!(
+                        finished.recv_timeout(interval),
+                        Err(mpsc::RecvTimeoutError::Timeout)
+                    );
+
+                    if tick() || done {
+                        break;
+                    }
+                }
+            });
+        } else {
+            // Only the final checkpoint is given, as nothing else runs while
+            // the seeds are checked
+            check_batches();
+
+            if interval.is_some() {
+                tick();
+            }
+        }
+
+        if let Some(err) = error.into_inner().unwrap_or_else(PoisonError::into_inner) {
+            return Err(err);
+        }
+
+        // A cancelled search can leave finished batches after an unfinished
+        // one, their seeds are not counted as checked and their held back
+        // hits are dropped
+        let BatchProgress {
+            processed: seeds_checked,
+            mut hits,
+            ..
+        } = progress
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        hits.sort_by_key(|hit: &SearchHit| hit.seed);
+
+        #[cfg(feature = "tracing")]
+        {
+            let elapsed = started.elapsed();
+            let checked = checked.load(Ordering::Relaxed);
+
+            tracing::info!(
+                checked,
+                hits = hits.len(),
+                ?elapsed,
+                seeds_per_second = checked as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+                cancelled = cancel.is_some_and(CancellationToken::is_cancelled),
+                "search finished"
+            );
+        }
+
+        Ok(SearchResults {
+            seeds_checked,
+            hits,
+        })
+    }
+}
+
+/// Keeps track of the batches of seeds which have been checked
+///
+/// As the batches finish out of order, only the amount of seeds from the
+/// start of the range which have all been checked is counted as processed.
+/// The hits of a batch are held back until every batch before it has
+/// finished, so [`Self::hits`] only contains hits of processed seeds.
+#[derive(Debug)]
+struct BatchProgress {
+    processed: u64,
+    hits: Vec<SearchHit>,
+    /// The end and the hits of the finished batches after the processed
+    /// seeds, by their start
+    finished: BTreeMap<u64, (u64, Vec<SearchHit>)>,
+}
+
+impl BatchProgress {
+    fn new(processed: u64, hits: Vec<SearchHit>) -> Self {
+        Self {
+            processed,
+            hits,
+            finished: BTreeMap::new(),
+        }
+    }
+
+    fn complete(&mut self, start: u64, end: u64, hits: Vec<SearchHit>) {
+        self.finished.insert(start, (end, hits));
+
+        while let Some((end, hits)) = self.finished.remove(&self.processed) {
+            self.processed = end;
+            self.hits.extend(hits);
+        }
+    }
+}
+
+/// The position a criterion matched at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CriterionMatch {
+    /// The index of the criterion in [`SearchDefinition::criteria`]
+    pub criterion: usize,
+    /// The position the criterion matched at
+    pub pos: BlockPosition,
+}
+
+/// A seed which matched every criterion of a search
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchHit {
+    /// The matching seed
+    pub seed: i64,
+    /// The matches of the criteria, in the same order as the criteria
+    pub matches: Vec<CriterionMatch>,
+}
+
+/// The results of running a search
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchResults {
+    /// The amount of seeds that were checked
+    pub seeds_checked: u64,
+    /// The seeds which matched every criterion, sorted by seed
+    pub hits: Vec<SearchHit>,
+}
+
+#[cfg(feature = "serde")]
+fn default_dimension() -> Dimension {
+    Dimension::DIM_OVERWORLD
+}
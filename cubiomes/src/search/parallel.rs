@@ -0,0 +1,210 @@
+use super::{SeedRange, BATCH_SIZE, THREADS_SUPPORTED};
+use crate::enums::{Dimension, MCVersion};
+use crate::generator::{Generator, GeneratorFlags};
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, PoisonError,
+    },
+    thread,
+};
+
+/// The seeds of a range, checked in parallel with one generator per thread
+///
+/// Created with [`SeedRange::par_seeds()`]. Every thread sets up its own
+/// generator once and applies the seeds to it one after another, which is
+/// what a custom search needs but is easy to get wrong when sharing a single
+/// generator between threads. The settings of the generators are set with the
+/// builder methods, and the seeds are checked with [`Self::filter()`] or
+/// [`Self::filter_map()`].
+///
+/// Use a [`super::SearchDefinition`] instead if the conditions can be written
+/// as criteria.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, MCVersion};
+/// use cubiomes::search::SeedRange;
+///
+/// let mushroom_spawns = SeedRange::new(0, 1000)
+///     .par_seeds(MCVersion::MC_1_20_6)
+///     .filter(|generator| {
+///         generator.get_biome_at((0, 64, 0)) == Ok(BiomeID::mushroom_fields)
+///     });
+///
+/// assert_eq!(mushroom_spawns, [262, 702]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParallelSeeds {
+    seeds: SeedRange,
+    version: MCVersion,
+    dimension: Dimension,
+    flags: GeneratorFlags,
+    threads: Option<NonZeroUsize>,
+}
+
+impl SeedRange {
+    /// Checks the seeds of the range in parallel, with a generator for the
+    /// version on every thread
+    ///
+    /// The generators are in the overworld without flags, unless set otherwise
+    /// on the returned [`ParallelSeeds`].
+    #[must_use]
+    pub fn par_seeds(self, version: MCVersion) -> ParallelSeeds {
+        ParallelSeeds {
+            seeds: self,
+            version,
+            dimension: Dimension::DIM_OVERWORLD,
+            flags: GeneratorFlags::empty(),
+            threads: None,
+        }
+    }
+}
+
+impl ParallelSeeds {
+    /// Sets the dimension the seeds are applied in
+    #[must_use]
+    pub fn dimension(self, dimension: Dimension) -> Self {
+        Self { dimension, ..self }
+    }
+
+    /// Sets the flags of the generators
+    #[must_use]
+    pub fn flags(self, flags: GeneratorFlags) -> Self {
+        Self { flags, ..self }
+    }
+
+    /// Uses generators of the large biomes world type
+    ///
+    /// Adds [`GeneratorFlags::LargeBiomes`] to the flags, see
+    /// [`Generator::large_biomes()`].
+    #[must_use]
+    pub fn large_biomes(self) -> Self {
+        self.flags(self.flags | GeneratorFlags::LargeBiomes)
+    }
+
+    /// Sets the amount of threads to use
+    ///
+    /// If not set, the available parallelism of the system is used. Like
+    /// searches, this always runs on the calling thread on targets without
+    /// threads.
+    #[must_use]
+    pub fn threads(self, threads: NonZeroUsize) -> Self {
+        Self {
+            threads: Some(threads),
+            ..self
+        }
+    }
+
+    /// Gets the seeds for which the predicate returns true, sorted by seed
+    ///
+    /// The predicate is given a generator with the seed applied, the seed
+    /// itself is [`Generator::seed()`].
+    pub fn filter(&self, predicate: impl Fn(&mut Generator) -> bool + Sync) -> Vec<i64> {
+        self.filter_map(|generator| predicate(generator).then_some(()))
+            .into_iter()
+            .map(|(seed, ())| seed)
+            .collect()
+    }
+
+    /// Gets the seeds for which `f` returns a value, together with the value,
+    /// sorted by seed
+    ///
+    /// `f` is given a generator with the seed applied, the seed itself is
+    /// [`Generator::seed()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{MCVersion, StructureType};
+    /// use cubiomes::generator::BlockPosition;
+    /// use cubiomes::search::SeedRange;
+    ///
+    /// let villages = SeedRange::new(0, 100)
+    ///     .par_seeds(MCVersion::MC_1_20_6)
+    ///     .filter_map(|generator| {
+    ///         generator.nearest_structure(StructureType::Village, BlockPosition::new(0, 0), 200)
+    ///     });
+    ///
+    /// let seeds: Vec<i64> = villages.iter().map(|&(seed, _)| seed).collect();
+    /// assert_eq!(seeds, [12, 25, 96, 97]);
+    /// assert!(villages.iter().all(|(_, village)| village.distance <= 200.0));
+    /// ```
+    pub fn filter_map<T: Send>(
+        &self,
+        f: impl Fn(&mut Generator) -> Option<T> + Sync,
+    ) -> Vec<(i64, T)> {
+        let mut found: Vec<(i64, T)> = self
+            .for_each_seed(Vec::new, |found, generator| {
+                if let Some(value) = f(generator) {
+                    found.push((generator.seed(), value));
+                }
+            })
+            .into_iter()
+            .flatten()
+            .collect();
+
+        found.sort_by_key(|(seed, _)| *seed);
+        found
+    }
+
+    /// Applies every seed to a generator and hands it to `f`, together with
+    /// a state of the thread checking the seed
+    ///
+    /// Every thread starts with a state from `init`, and the states of all
+    /// threads are returned in no particular order.
+    pub(super) fn for_each_seed<S: Send>(
+        &self,
+        init: impl Fn() -> S + Sync,
+        f: impl Fn(&mut S, &mut Generator) + Sync,
+    ) -> Vec<S> {
+        let seeds = self.seeds.len();
+        let next_batch = AtomicU64::new(0);
+        let states = Mutex::new(Vec::new());
+
+        let check_batches = || {
+            let mut generator =
+                Generator::new(self.version, self.seeds.start, self.dimension, self.flags);
+            let mut state = init();
+
+            loop {
+                let start = next_batch.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                if start >= seeds {
+                    break;
+                }
+
+                for offset in start..(start + BATCH_SIZE).min(seeds) {
+                    generator.apply_seed(self.dimension, self.seeds.nth(offset));
+                    f(&mut state, &mut generator);
+                }
+            }
+
+            states
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(state);
+        };
+
+        if THREADS_SUPPORTED {
+            thread::scope(|scope| {
+                for _ in 0..self.thread_count() {
+                    scope.spawn(check_batches);
+                }
+            });
+        } else {
+            check_batches();
+        }
+
+        states.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn thread_count(&self) -> usize {
+        if !THREADS_SUPPORTED {
+            return 1;
+        }
+
+        self.threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+    }
+}
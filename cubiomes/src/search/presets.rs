@@ -0,0 +1,115 @@
+use super::{Criterion, SearchDefinition, SeedRange};
+use crate::enums::{MCVersion, StructureType};
+
+/// The largest distance from spawn to the village and the ruined portal of
+/// the speedrun presets
+const SPAWN_RADIUS: u32 = 96;
+
+/// The largest distance from 0, 0 in the nether to the fortress and the
+/// bastion of the speedrun presets
+const NETHER_RADIUS: u32 = 120;
+
+/// The largest distance from 0, 0 to the first stronghold of the speedrun
+/// preset, which is about as close as the innermost ring gets
+const STRONGHOLD_RADIUS: u32 = 1800;
+
+/// Ready-made criteria for common seed searches
+///
+/// The presets encode what speedrunners look for in a seed. Their criteria
+/// can be run as they are with [`SearchDefinition::from_preset()`], or
+/// tweaked by editing [`SearchDefinition::criteria`] afterwards. With the
+/// `toml` or `json` features, a preset can be saved as a config to edit.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{MCVersion, StructureType};
+/// use cubiomes::search::{Criterion, SearchDefinition, SearchPreset, SeedRange};
+///
+/// let mut search = SearchDefinition::from_preset(
+///     SearchPreset::SpawnStart,
+///     MCVersion::MC_1_20_6,
+///     SeedRange::new(900, 1000),
+/// );
+///
+/// // Allow the ruined portal to be a bit further away
+/// for criterion in &mut search.criteria {
+///     if let Criterion::StructureNearSpawn {
+///         structure: StructureType::Ruined_Portal,
+///         radius,
+///     } = criterion
+///     {
+///         *radius = 128;
+///     }
+/// }
+///
+/// let results = search.run().expect("search failed");
+/// let seeds: Vec<_> = results.hits.iter().map(|hit| hit.seed).collect();
+/// assert_eq!(seeds, [960, 964]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SearchPreset {
+    /// A village and a ruined portal within 96 blocks of spawn
+    SpawnStart,
+    /// A fortress and a bastion within 120 blocks of 0, 0 in the nether
+    ///
+    /// Bastions only generate since 1.16, so this never matches before.
+    NetherEntry,
+    /// A village and a ruined portal within 96 blocks of spawn, a fortress
+    /// within 120 blocks of 0, 0 in the nether and the first stronghold within
+    /// 1800 blocks of 0, 0
+    Speedrun,
+}
+
+impl SearchPreset {
+    /// Gets the criteria of the preset
+    ///
+    /// The cheap criteria come first, so seeds failing them are rejected
+    /// before the expensive ones are checked.
+    #[must_use]
+    pub fn criteria(self) -> Vec<Criterion> {
+        let near_spawn = |structure| Criterion::StructureNearSpawn {
+            structure,
+            radius: SPAWN_RADIUS,
+        };
+        let nether = |structure| Criterion::NetherStructure {
+            structure,
+            x: 0,
+            z: 0,
+            radius: NETHER_RADIUS,
+        };
+
+        match self {
+            Self::SpawnStart => vec![
+                near_spawn(StructureType::Ruined_Portal),
+                near_spawn(StructureType::Village),
+            ],
+            Self::NetherEntry => vec![
+                nether(StructureType::Bastion),
+                nether(StructureType::Fortress),
+            ],
+            Self::Speedrun => vec![
+                nether(StructureType::Fortress),
+                near_spawn(StructureType::Ruined_Portal),
+                near_spawn(StructureType::Village),
+                Criterion::FirstStronghold {
+                    x: 0,
+                    z: 0,
+                    radius: STRONGHOLD_RADIUS,
+                },
+            ],
+        }
+    }
+}
+
+impl SearchDefinition {
+    /// Creates a new search in the overworld with the criteria of a preset
+    ///
+    /// See [`SearchPreset`] for an example.
+    #[must_use]
+    pub fn from_preset(preset: SearchPreset, version: MCVersion, seeds: SeedRange) -> Self {
+        Self {
+            criteria: preset.criteria(),
+            ..Self::new(version, seeds)
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use super::{error::SearchError, Checkpoint, SearchDefinition, SearchHit, SearchResults};
+use crate::progress::CancellationToken;
+use futures_core::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc;
+
+impl SearchDefinition {
+    /// Runs the search on blocking threads of the tokio runtime
+    ///
+    /// The search is moved to [`tokio::task::spawn_blocking()`], so it doesn't
+    /// block the executor. Otherwise this is the same as [`Self::run()`].
+    ///
+    /// # Panics
+    /// Panics if called outside of a tokio runtime
+    ///
+    /// # Errors
+    /// Returns [`SearchError::Task`] if the blocking task fails and otherwise
+    /// the same errors as [`Self::run()`]
+    pub async fn run_async(self) -> Result<SearchResults, SearchError> {
+        tokio::task::spawn_blocking(traced(move || self.run())).await?
+    }
+
+    /// Runs the search on blocking threads of the tokio runtime, streaming
+    /// the hits as they are found
+    ///
+    /// The hits are given in the order they are found, which is not sorted by
+    /// seed as the seeds are checked on multiple threads. If the search fails,
+    /// the error is given as the last item of the stream.
+    ///
+    /// Dropping the stream cancels the search.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{MCVersion, StructureType};
+    /// use cubiomes::search::{Criterion, SearchDefinition, SeedRange};
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 64));
+    ///
+    /// search.criteria.push(Criterion::Structure {
+    ///     structure: StructureType::Village,
+    ///     x: 0,
+    ///     z: 0,
+    ///     radius: 512,
+    /// });
+    ///
+    /// let mut hits = search.stream();
+    /// let mut seeds = Vec::new();
+    ///
+    /// while let Some(hit) = hits.next().await {
+    ///     seeds.push(hit.expect("search failed").seed);
+    /// }
+    ///
+    /// // The hits are streamed as they are found, which isn't sorted by seed
+    /// seeds.sort();
+    /// assert_eq!(seeds.len(), 34);
+    /// assert_eq!(seeds[..3], [2, 4, 5]);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if called outside of a tokio runtime
+    #[must_use]
+    pub fn stream(self) -> SearchStream {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let cancel = CancellationToken::new();
+        let search_cancel = cancel.clone();
+
+        tokio::task::spawn_blocking(traced(move || {
+            let result = self.run_from(
+                Checkpoint::new(self.seeds),
+                None,
+                Some(&search_cancel),
+                |hits| {
+                    for hit in hits {
+                        // The receiver is only gone if the stream was
+                        // dropped, which also cancels the search
+                        let _ = sender.send(Ok(hit.clone()));
+                    }
+                },
+                |_, _| Ok(()),
+            );
+
+            if let Err(err) = result {
+                let _ = sender.send(Err(err));
+            }
+        }));
+
+        SearchStream { receiver, cancel }
+    }
+}
+
+/// Wraps work for a blocking thread to report to the subscriber and under the
+/// span of the caller, like a search run on the calling thread
+fn traced<T>(work: impl FnOnce() -> T) -> impl FnOnce() -> T {
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::Span::current();
+        let dispatch = tracing::dispatcher::get_default(Clone::clone);
+
+        move || tracing::dispatcher::with_default(&dispatch, || span.in_scope(work))
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    work
+}
+
+/// A stream of the hits of a search running in the background
+///
+/// Created with [`SearchDefinition::stream()`]. The stream ends once every
+/// seed has been checked.
+#[derive(Debug)]
+pub struct SearchStream {
+    receiver: mpsc::UnboundedReceiver<Result<SearchHit, SearchError>>,
+    cancel: CancellationToken,
+}
+
+impl SearchStream {
+    /// Cancels the search
+    ///
+    /// The hits found before cancelling are still given by the stream.
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+}
+
+impl Stream for SearchStream {
+    type Item = Result<SearchHit, SearchError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Drop for SearchStream {
+    fn drop(&mut self) {
+        self.cancel.cancel();
+    }
+}
@@ -0,0 +1,227 @@
+use super::SeedRange;
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use crate::generator::{
+    error::GeneratorError, BiomeStats, BlockPosition3D, Cache, Generator, GeneratorFlags, Range,
+};
+use std::{collections::BTreeMap, num::NonZeroUsize};
+
+/// Every biome id is below this, so the samples can be counted in an array
+const BIOME_ID_LIMIT: usize = 256;
+
+/// Where the biomes of every seed of a [`BiomeSurvey`] are sampled
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SamplePattern {
+    /// Every cell of a range, generated as one cache per seed
+    ///
+    /// Neighbouring cells are often of the same biome, so an area needs more
+    /// seeds than scattered points for the same accuracy, but it is a lot
+    /// faster to generate per cell.
+    Area(Range),
+    /// A list of blocks, looked up with [`Generator::biomes_at()`]
+    Points(Vec<BlockPosition3D>),
+}
+
+impl SamplePattern {
+    /// Creates a square grid of points around the origin
+    ///
+    /// The grid has `count` by `count` points, `spacing` blocks apart, all at
+    /// the y `y` in blocks. Points which would be outside of the coordinates
+    /// of an i32 are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::generator::BlockPosition3D;
+    /// use cubiomes::search::SamplePattern;
+    ///
+    /// let SamplePattern::Points(points) = SamplePattern::grid(3, 1000, 64) else {
+    ///     unreachable!("grids are points");
+    /// };
+    ///
+    /// assert_eq!(points.len(), 9);
+    /// assert_eq!(points[0], BlockPosition3D::new(-1000, 64, -1000));
+    /// assert_eq!(points[4], BlockPosition3D::new(0, 64, 0));
+    /// ```
+    #[must_use]
+    pub fn grid(count: u32, spacing: u32, y: i32) -> Self {
+        let offset = |i: u32| {
+            let offset =
+                (i64::from(i) - i64::from(count.saturating_sub(1)) / 2) * i64::from(spacing);
+            i32::try_from(offset).ok()
+        };
+        let offsets: Vec<i32> = (0..count).filter_map(offset).collect();
+
+        Self::Points(
+            offsets
+                .iter()
+                .flat_map(|&z| offsets.iter().map(move |&x| BlockPosition3D::new(x, y, z)))
+                .collect(),
+        )
+    }
+}
+
+/// A survey of how often each biome generates in one or more versions
+///
+/// The biomes of every seed of a range are sampled with a
+/// [`SamplePattern`], in parallel with one generator per thread, and the
+/// samples of all seeds are added up into [`BiomeStats`] for each version.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, MCVersion};
+/// use cubiomes::search::{BiomeSurvey, SamplePattern, SeedRange};
+///
+/// let survey = BiomeSurvey::new(
+///     [MCVersion::MC_1_17_1, MCVersion::MC_1_20_6],
+///     SeedRange::new(0, 50),
+///     SamplePattern::grid(8, 2048, 64),
+/// );
+///
+/// let stats = survey.run().expect("failed to generate biomes");
+///
+/// let mushrooms: Vec<_> = stats
+///     .iter()
+///     .map(|(version, stats)| (*version, stats.count(BiomeID::mushroom_fields)))
+///     .collect();
+/// assert_eq!(mushrooms, [(MCVersion::MC_1_17_1, 4), (MCVersion::MC_1_20_6, 11)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BiomeSurvey {
+    versions: Vec<MCVersion>,
+    seeds: SeedRange,
+    pattern: SamplePattern,
+    dimension: Dimension,
+    flags: GeneratorFlags,
+    threads: Option<NonZeroUsize>,
+}
+
+impl BiomeSurvey {
+    /// Creates a survey of the overworld without flags
+    ///
+    /// Every version is surveyed with the same seeds and pattern.
+    #[must_use]
+    pub fn new(
+        versions: impl IntoIterator<Item = MCVersion>,
+        seeds: SeedRange,
+        pattern: SamplePattern,
+    ) -> Self {
+        Self {
+            versions: versions.into_iter().collect(),
+            seeds,
+            pattern,
+            dimension: Dimension::DIM_OVERWORLD,
+            flags: GeneratorFlags::empty(),
+            threads: None,
+        }
+    }
+
+    /// Sets the dimension the biomes are sampled in
+    #[must_use]
+    pub fn dimension(self, dimension: Dimension) -> Self {
+        Self { dimension, ..self }
+    }
+
+    /// Sets the flags of the generators
+    #[must_use]
+    pub fn flags(self, flags: GeneratorFlags) -> Self {
+        Self { flags, ..self }
+    }
+
+    /// Sets the amount of threads to use
+    ///
+    /// If not set, the available parallelism of the system is used
+    #[must_use]
+    pub fn threads(self, threads: NonZeroUsize) -> Self {
+        Self {
+            threads: Some(threads),
+            ..self
+        }
+    }
+
+    /// Samples every seed in every version
+    ///
+    /// The versions are surveyed one after another, each on all threads.
+    /// Samples which aren't a valid biome are skipped, like in
+    /// [`Cache::histogram()`].
+    ///
+    /// # Errors
+    /// Returns the first error of generating the biomes of a seed, see
+    /// [`Cache::fill_cache()`] and [`Generator::biomes_at()`]
+    pub fn run(&self) -> Result<BTreeMap<MCVersion, BiomeStats>, GeneratorError> {
+        self.versions
+            .iter()
+            .map(|&version| Ok((version, self.survey_version(version)?)))
+            .collect()
+    }
+
+    fn survey_version(&self, version: MCVersion) -> Result<BiomeStats, GeneratorError> {
+        let mut seeds = self
+            .seeds
+            .par_seeds(version)
+            .dimension(self.dimension)
+            .flags(self.flags);
+        if let Some(threads) = self.threads {
+            seeds = seeds.threads(threads);
+        }
+
+        let states = seeds.for_each_seed(
+            || Ok([0_u64; BIOME_ID_LIMIT]),
+            |counts: &mut Result<[u64; BIOME_ID_LIMIT], GeneratorError>, generator| {
+                // Once a seed failed, the rest of the thread is skipped
+                if let Ok(raw_counts) = counts {
+                    if let Err(err) = self.sample(generator, raw_counts) {
+                        *counts = Err(err);
+                    }
+                }
+            },
+        );
+
+        let mut raw_counts = [0_u64; BIOME_ID_LIMIT];
+        for counts in states {
+            for (total, count) in raw_counts.iter_mut().zip(counts?) {
+                *total += count;
+            }
+        }
+
+        Ok(BiomeStats::from_counts(
+            (0_i32..)
+                .zip(raw_counts)
+                .filter(|&(_, count)| count > 0)
+                .filter_map(|(raw_biomeid, count)| {
+                    BiomeID::from_id(raw_biomeid).map(|biome| (biome, count))
+                })
+                .collect(),
+        ))
+    }
+
+    /// Adds the samples of the seed of a generator to the counts
+    fn sample(
+        &self,
+        generator: &Generator,
+        raw_counts: &mut [u64; BIOME_ID_LIMIT],
+    ) -> Result<(), GeneratorError> {
+        match &self.pattern {
+            SamplePattern::Area(range) => {
+                let mut cache = Cache::new(generator, *range);
+                cache.fill_cache()?;
+
+                for &raw_biomeid in cache.as_vec() {
+                    if let Some(count) = usize::try_from(raw_biomeid)
+                        .ok()
+                        .and_then(|index| raw_counts.get_mut(index))
+                    {
+                        *count += 1;
+                    }
+                }
+            }
+            SamplePattern::Points(points) => {
+                for biome in generator.biomes_at(points)? {
+                    if let Some(count) = raw_counts.get_mut(biome as usize) {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,967 @@
+use super::{
+    error::SearchError, Checkpoint, Criterion, CriterionMatch, Discrepancy, SearchArchive,
+    SearchDefinition, SearchPreset, SeedRange, BATCH_SIZE,
+};
+use crate::enums::*;
+use crate::generator::{BlockPosition, Cache, Generator, GeneratorFlags, Range, Scale, YLevel};
+use crate::progress::CancellationToken;
+use crate::structures::strongholds::first_stronghold;
+use std::{num::NonZeroUsize, time::Duration};
+
+fn village_search() -> SearchDefinition {
+    let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(-40, 40));
+    search.threads = NonZeroUsize::new(3);
+    search.criteria.push(Criterion::Structure {
+        structure: StructureType::Village,
+        x: 0,
+        z: 0,
+        radius: 400,
+    });
+
+    search
+}
+
+#[test]
+fn search_hits_are_verified() {
+    let search = village_search();
+    let results = search.run().expect("search failed");
+
+    dbg!(&results);
+
+    assert_eq!(results.seeds_checked, 80);
+    assert!(!results.hits.is_empty());
+    assert!(results.hits.windows(2).all(|w| w[0].seed < w[1].seed));
+
+    for hit in results.hits {
+        let mut generator = Generator::new(
+            MCVersion::MC_1_20_6,
+            hit.seed,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+
+        let nearest = generator
+            .nearest_structure(StructureType::Village, BlockPosition::new(0, 0), 400)
+            .expect("matching seeds have a village");
+
+        assert_eq!(hit.matches[0].pos, nearest.pos);
+    }
+}
+
+#[test]
+fn search_without_criteria_fails() {
+    let search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 10));
+
+    assert!(matches!(search.run(), Err(SearchError::NoCriteria)));
+}
+
+#[test]
+fn search_for_unavailable_structure_fails() {
+    let mut search = SearchDefinition::new(MCVersion::MC_1_15, SeedRange::new(0, 10));
+    search.criteria = vec![
+        Criterion::Structure {
+            structure: StructureType::Village,
+            x: 0,
+            z: 0,
+            radius: 256,
+        },
+        Criterion::NetherStructure {
+            structure: StructureType::Bastion,
+            x: 0,
+            z: 0,
+            radius: 256,
+        },
+    ];
+
+    assert!(matches!(
+        search.run(),
+        Err(SearchError::UnavailableStructure {
+            criterion: 1,
+            structure: StructureType::Bastion,
+            version: MCVersion::MC_1_15,
+            dimension: Dimension::DIM_NETHER,
+        })
+    ));
+}
+
+#[test]
+fn biome_criteria_match() {
+    let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 8));
+    search.criteria.push(Criterion::Biome {
+        biome: BiomeID::ocean,
+        x: 0,
+        z: 0,
+        radius: 1024,
+        y: YLevel::Absolute(64),
+        min_cells: 1,
+    });
+
+    let results = search.run().expect("search failed");
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        0,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    for hit in results.hits {
+        generator.apply_seed(Dimension::DIM_OVERWORLD, hit.seed);
+        let pos = hit.matches[0].pos;
+
+        let mut cache = Cache::new(
+            &generator,
+            Range {
+                scale: Scale::Quad,
+                x: pos.x / 4,
+                z: pos.z / 4,
+                size_x: 1,
+                size_z: 1,
+                y: 16,
+                size_y: 0,
+            },
+        );
+        cache.fill_cache().expect("failed to fill cache");
+
+        assert_eq!(cache.biome_at(0, 0, 0), Ok(BiomeID::ocean));
+    }
+}
+
+#[test]
+fn biome_criteria_reject_huge_radii() {
+    use crate::generator::error::{GeneratorError, TryFromRangeError};
+
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        0,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let criterion = |x, radius| Criterion::Biome {
+        biome: BiomeID::ocean,
+        x,
+        z: 0,
+        radius,
+        y: YLevel::Absolute(64),
+        min_cells: 1,
+    };
+
+    assert_eq!(
+        criterion(0, u32::MAX).check(&mut generator),
+        Err(GeneratorError::TryFromRangeError(
+            TryFromRangeError::XOutOfBounds
+        ))
+    );
+    assert_eq!(
+        criterion(1000, i32::MAX as u32).check(&mut generator),
+        Err(GeneratorError::TryFromRangeError(
+            TryFromRangeError::XOutOfBounds
+        ))
+    );
+    // The area fits, but is far too large to generate
+    assert!(criterion(0, i32::MAX as u32).check(&mut generator).is_err());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn search_roundtrips_toml() {
+    use super::ConfigFormat;
+
+    let config = r#"
+        version = "MC_1_20_6"
+        threads = 2
+
+        [seeds]
+        start = -40
+        end = 40
+
+        [[criteria]]
+        type = "structure"
+        structure = "Village"
+        x = 0
+        z = 0
+        radius = 400
+    "#;
+
+    let search: SearchDefinition = ConfigFormat::Toml.parse(config).expect("valid config");
+
+    assert_eq!(search.dimension, Dimension::DIM_OVERWORLD);
+    assert_eq!(search.criteria, village_search().criteria);
+
+    let results = search.run().expect("search failed");
+    let written = ConfigFormat::Toml
+        .write(&results)
+        .expect("results are valid toml");
+
+    assert_eq!(
+        ConfigFormat::Toml
+            .parse::<super::SearchResults>(&written)
+            .ok(),
+        Some(results)
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn criteria_take_named_y_levels() {
+    use super::ConfigFormat;
+
+    let config = r#"
+        version = "MC_1_20_6"
+
+        [seeds]
+        start = 0
+        end = 4
+
+        [[criteria]]
+        type = "biome_at"
+        biome = "plains"
+        x = 0
+        z = 0
+        y = "surface"
+
+        [[criteria]]
+        type = "biome_at"
+        biome = "plains"
+        x = 0
+        z = 0
+        y = -20
+    "#;
+
+    let search: SearchDefinition = ConfigFormat::Toml.parse(config).expect("valid config");
+    let levels: Vec<_> = search
+        .criteria
+        .iter()
+        .filter_map(|criterion| match criterion {
+            Criterion::BiomeAt { y, .. } => Some(*y),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(levels, [YLevel::Surface, YLevel::Absolute(-20)]);
+    assert!(ConfigFormat::Toml
+        .write(&search)
+        .expect("search is valid toml")
+        .contains("y = \"surface\""));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn search_roundtrips_json() {
+    use super::ConfigFormat;
+
+    let search = village_search();
+    let written = ConfigFormat::Json
+        .write(&search)
+        .expect("definition is valid json");
+
+    dbg!(&written);
+
+    assert_eq!(
+        ConfigFormat::Json.parse::<SearchDefinition>(&written).ok(),
+        Some(search)
+    );
+    assert!(matches!(
+        ConfigFormat::from_path("search.yaml"),
+        Err(SearchError::UnknownFormat(_))
+    ));
+}
+
+#[test]
+fn checkpoints_resume_search() {
+    let search = village_search();
+    let full = search.run().expect("search failed");
+
+    let mut checkpoints = Vec::new();
+    let results = search
+        .run_checkpointed(None, Duration::from_millis(1), |checkpoint| {
+            checkpoints.push(checkpoint.clone());
+            Ok(())
+        })
+        .expect("search failed");
+
+    assert_eq!(results, full);
+
+    let last = checkpoints.last().expect("a final checkpoint is given");
+    assert!(last.is_finished());
+    assert_eq!(last.next_seed(), None);
+    assert_eq!(last.hits, full.hits);
+
+    // Only hits of checked seeds are saved, as the later seeds are checked
+    // again when resuming
+    assert!(checkpoints.iter().all(|checkpoint| checkpoint
+        .hits
+        .iter()
+        .all(|hit| search.seeds.offset_of(hit.seed) < checkpoint.processed)));
+
+    // Pretend the search was interrupted in the middle
+    let checked: Vec<_> = full
+        .hits
+        .iter()
+        .filter(|hit| hit.seed < 0)
+        .cloned()
+        .collect();
+    let partial = Checkpoint::from_hits(search.seeds, 40, &checked);
+    assert_eq!(partial.next_seed(), Some(0));
+
+    let resumed = search
+        .run_checkpointed(Some(partial), Duration::from_secs(60), |_| Ok(()))
+        .expect("search failed");
+
+    assert_eq!(resumed, full);
+
+    let other = Checkpoint::new(SeedRange::new(0, 10));
+    assert!(matches!(
+        search.run_checkpointed(Some(other), Duration::from_secs(60), |_| Ok(())),
+        Err(SearchError::CheckpointMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn resumable_search_saves_checkpoint() {
+    let search = village_search();
+    let path =
+        std::env::temp_dir().join(format!("cubiomes-checkpoint-{}.json", std::process::id()));
+
+    let results = search
+        .run_resumable(&path, Duration::from_secs(60))
+        .expect("search failed");
+    let checkpoint = Checkpoint::load(&path).expect("checkpoint was saved");
+
+    assert!(checkpoint.is_finished());
+    assert_eq!(checkpoint.hits, results.hits);
+
+    // Resuming a finished search doesn't check any seeds again
+    let resumed = search
+        .run_resumable(&path, Duration::from_secs(60))
+        .expect("search failed");
+    assert_eq!(resumed, results);
+
+    std::fs::remove_file(path).expect("failed to remove checkpoint");
+}
+
+#[test]
+fn monitored_search_reports_progress() {
+    let search = village_search();
+    let full = search.run().expect("search failed");
+
+    let mut reports = Vec::new();
+    let results = search
+        .run_monitored(
+            &CancellationToken::new(),
+            Duration::from_millis(1),
+            |progress| {
+                reports.push(*progress);
+            },
+        )
+        .expect("search failed");
+
+    assert_eq!(results, full);
+
+    let last = reports.last().expect("a final report is given");
+    assert_eq!((last.done, last.total), (80, 80));
+
+    // Cancelling before starting checks no seeds at all
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+
+    let cancelled = search
+        .run_monitored(&cancel, Duration::from_secs(60), |_| ())
+        .expect("search failed");
+
+    assert_eq!(cancelled.seeds_checked, 0);
+    assert!(cancelled.hits.is_empty());
+}
+
+#[test]
+fn cancelled_search_only_gives_hits_of_checked_seeds() {
+    let mut search = village_search();
+    search.seeds = SeedRange::new(0, 8 * BATCH_SIZE.cast_signed());
+    search.threads = NonZeroUsize::new(4);
+
+    // Batches finish out of order, so cancelling leaves some finished
+    // batches after the first unfinished one
+    let cancel = CancellationToken::new();
+    let results = search
+        .run_monitored(&cancel, Duration::from_millis(1), |progress| {
+            if progress.done > 0 {
+                cancel.cancel();
+            }
+        })
+        .expect("search failed");
+
+    assert_eq!(results.seeds_checked % BATCH_SIZE, 0);
+    assert!(results
+        .hits
+        .iter()
+        .all(|hit| search.seeds.offset_of(hit.seed) < results.seeds_checked));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn stream_gives_every_hit() {
+    use futures::StreamExt;
+
+    let search = village_search();
+    let full = search.clone().run_async().await.expect("search failed");
+
+    let mut hits: Vec<_> = search
+        .stream()
+        .map(|hit| hit.expect("search failed"))
+        .collect()
+        .await;
+    hits.sort_by_key(|hit| hit.seed);
+
+    assert_eq!(hits, full.hits);
+
+    let empty = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 10));
+    let errors: Vec<_> = empty.stream().collect().await;
+    assert!(matches!(errors[..], [Err(SearchError::NoCriteria)]));
+}
+
+#[test]
+fn speedrun_criteria_match() {
+    let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(-40, 40));
+    search.threads = NonZeroUsize::new(3);
+    // The overworld criteria after the nether one are still checked in the
+    // overworld
+    search.criteria = vec![
+        Criterion::NetherStructure {
+            structure: StructureType::Fortress,
+            x: 0,
+            z: 0,
+            radius: 400,
+        },
+        Criterion::StructureNearSpawn {
+            structure: StructureType::Village,
+            radius: 400,
+        },
+        Criterion::FirstStronghold {
+            x: 0,
+            z: 0,
+            radius: 2000,
+        },
+    ];
+
+    let results = search.run().expect("search failed");
+
+    dbg!(&results);
+
+    assert!(!results.hits.is_empty());
+
+    for hit in results.hits {
+        let mut nether = Generator::new(
+            MCVersion::MC_1_20_6,
+            hit.seed,
+            Dimension::DIM_NETHER,
+            GeneratorFlags::empty(),
+        );
+        let fortress = nether
+            .nearest_structure(StructureType::Fortress, BlockPosition::new(0, 0), 400)
+            .expect("matching seeds have a fortress");
+        assert_eq!(hit.matches[0].pos, fortress.pos);
+
+        let mut generator = Generator::new(
+            MCVersion::MC_1_20_6,
+            hit.seed,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        let spawn = generator.estimate_spawn().expect("spawn is estimated");
+        let village = generator
+            .nearest_structure(StructureType::Village, spawn, 400)
+            .expect("matching seeds have a village near spawn");
+        assert_eq!(hit.matches[1].pos, village.pos);
+
+        let stronghold = first_stronghold(MCVersion::MC_1_20_6, hit.seed)
+            .refine(&generator)
+            .expect("stronghold is refined");
+        assert_eq!(hit.matches[2].pos, stronghold);
+        assert!(stronghold.x.pow(2) + stronghold.z.pow(2) <= 2000 * 2000);
+    }
+}
+
+#[test]
+fn presets_give_their_criteria() {
+    let search = SearchDefinition::from_preset(
+        SearchPreset::Speedrun,
+        MCVersion::MC_1_20_6,
+        SeedRange::new(0, 10),
+    );
+
+    assert_eq!(search.criteria, SearchPreset::Speedrun.criteria());
+    assert_eq!(search.dimension, Dimension::DIM_OVERWORLD);
+
+    // The speedrun preset includes the spawn start
+    for criterion in SearchPreset::SpawnStart.criteria() {
+        assert!(search.criteria.contains(&criterion));
+    }
+    assert!(SearchPreset::NetherEntry
+        .criteria()
+        .iter()
+        .all(|criterion| matches!(criterion, Criterion::NetherStructure { .. })));
+
+    search.run().expect("search failed");
+}
+
+#[test]
+fn criterion_errors_name_the_seed() {
+    let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(5, 10));
+    search.dimension = Dimension::DIM_NETHER;
+    search.criteria.push(Criterion::StructureNearSpawn {
+        structure: StructureType::Village,
+        radius: 100,
+    });
+
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        0,
+        Dimension::DIM_NETHER,
+        GeneratorFlags::empty(),
+    );
+
+    assert!(matches!(
+        search.check_seed(&mut generator, 7),
+        Err(SearchError::Criterion {
+            seed: 7,
+            criterion: 0,
+            error: crate::generator::error::GeneratorError::UnsupportedDimension(
+                Dimension::DIM_NETHER
+            ),
+        })
+    ));
+}
+
+#[test]
+fn parallel_seeds_match_a_sequential_check() {
+    let seeds = SeedRange::new(-200, 200);
+    let spawn_matches = |generator: &mut Generator| {
+        generator.get_biome_at((0, 64, 0)) == Ok(BiomeID::mushroom_fields)
+            || generator.get_biome_at((128, 64, 0)) == Ok(BiomeID::ocean)
+    };
+
+    let found = seeds
+        .par_seeds(MCVersion::MC_1_20_6)
+        .threads(NonZeroUsize::new(3).expect("3 isn't 0"))
+        .filter(spawn_matches);
+
+    dbg!(&found);
+
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        0,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let expected: Vec<i64> = (seeds.start..seeds.end)
+        .filter(|&seed| {
+            generator.apply_seed(Dimension::DIM_OVERWORLD, seed);
+            spawn_matches(&mut generator)
+        })
+        .collect();
+
+    assert!(!expected.is_empty());
+    assert_eq!(found, expected);
+
+    let seeds_seen = seeds
+        .par_seeds(MCVersion::MC_1_20_6)
+        .dimension(Dimension::DIM_NETHER)
+        .filter_map(|generator| Some(generator.seed()));
+
+    assert!(seeds_seen.iter().all(|(seed, seen)| seed == seen));
+    assert_eq!(seeds_seen.len() as u64, seeds.len());
+}
+
+#[test]
+fn criteria_expressions_parse() {
+    let criteria = Criterion::parse_all(
+        "biome(mushroom_fields) at (-16, 32) and STRUCTURE(Village) within 400 of (0, 0) \
+         AND nether_structure(fortress) within 200 of (10,-10) AND stronghold within 1800 of (0, 0)",
+    )
+    .expect("the expression is valid");
+
+    assert_eq!(
+        criteria,
+        [
+            Criterion::BiomeAt {
+                biome: BiomeID::mushroom_fields,
+                x: -16,
+                z: 32,
+                y: YLevel::Absolute(64),
+            },
+            village_search().criteria[0],
+            Criterion::NetherStructure {
+                structure: StructureType::Fortress,
+                x: 10,
+                z: -10,
+                radius: 200,
+            },
+            Criterion::FirstStronghold {
+                x: 0,
+                z: 0,
+                radius: 1800,
+            },
+        ]
+    );
+
+    let single: Criterion = "structure(monument) within 512 of spawn"
+        .parse()
+        .expect("the criterion is valid");
+    assert_eq!(
+        single,
+        Criterion::StructureNearSpawn {
+            structure: StructureType::Monument,
+            radius: 512,
+        }
+    );
+
+    let invalid = [
+        ("biome(not_a_biome) at (0, 0)", 6, "a biome"),
+        ("biome(plains) within -5 of (0, 0)", 21, "a radius"),
+        (
+            "biome(plains) within 5 of spawn",
+            14,
+            "a radius around a position instead of spawn",
+        ),
+        ("structure(village) within 5 of (0, 0) OR", 38, "AND"),
+        (
+            "treasure within 5 of (0, 0)",
+            0,
+            "biome, structure, nether_structure or stronghold",
+        ),
+        ("", 0, "biome, structure, nether_structure or stronghold"),
+    ];
+
+    for (expression, offset, expected) in invalid {
+        let error = Criterion::parse_all(expression);
+        dbg!(&error);
+
+        assert!(matches!(
+            error,
+            Err(SearchError::Expression { offset: o, expected: e }) if o == offset && e == expected
+        ));
+    }
+
+    assert!(
+        "structure(village) within 5 of spawn AND stronghold within 5 of (0, 0)"
+            .parse::<Criterion>()
+            .is_err()
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn search_criteria_load_from_an_expression() {
+    use super::ConfigFormat;
+
+    let config = r#"
+        version = "MC_1_20_6"
+        criteria = "structure(Village) within 400 of (0, 0)"
+
+        [seeds]
+        start = -40
+        end = 40
+    "#;
+
+    let search: SearchDefinition = ConfigFormat::Toml.parse(config).expect("valid config");
+    assert_eq!(search.criteria, village_search().criteria);
+
+    let invalid = config.replace("within 400", "within four hundred");
+    assert!(ConfigFormat::Toml
+        .parse::<SearchDefinition>(&invalid)
+        .is_err());
+}
+
+#[test]
+fn archived_results_are_verified() {
+    let search = village_search();
+    let results = search.run().expect("search failed");
+    let mut archive = SearchArchive::new(search, results);
+
+    assert_eq!(archive.verify().expect("verifying failed"), []);
+
+    let hits = &mut archive.results.hits;
+    let (seed, pos) = (hits[0].seed, hits[0].matches[0].pos);
+
+    hits[0].matches[0].pos.x += 16;
+    hits[1].matches.clear();
+    hits[1].matches.push(CriterionMatch {
+        criterion: 3,
+        pos: BlockPosition::new(0, 0),
+    });
+    let other = hits[1].clone();
+
+    let discrepancies = archive.verify().expect("verifying failed");
+    dbg!(&discrepancies);
+
+    assert_eq!(
+        discrepancies,
+        [
+            Discrepancy {
+                seed,
+                criterion: 0,
+                saved: Some(BlockPosition::new(pos.x + 16, pos.z)),
+                found: Some(pos),
+            },
+            Discrepancy {
+                seed: other.seed,
+                criterion: 0,
+                saved: None,
+                found: discrepancies[1].found,
+            },
+            Discrepancy {
+                seed: other.seed,
+                criterion: 3,
+                saved: Some(BlockPosition::new(0, 0)),
+                found: None,
+            },
+        ]
+    );
+    assert!(discrepancies[1].found.is_some());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn search_archives_roundtrip_json() {
+    use super::ConfigFormat;
+
+    let search = village_search();
+    let results = search.run().expect("search failed");
+    let archive = SearchArchive::new(search, results);
+
+    let written = ConfigFormat::Json
+        .write(&archive)
+        .expect("archives are valid json");
+    let loaded: SearchArchive = ConfigFormat::Json.parse(&written).expect("valid archive");
+
+    assert_eq!(loaded, archive);
+    assert_eq!(loaded.verify().expect("verifying failed"), []);
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn searches_are_traced() {
+    use std::sync::{atomic::AtomicU64, atomic::Ordering, Arc, Mutex};
+    use tracing::{field::Field, span, Event, Metadata, Subscriber};
+
+    /// Records the names of the spans and the messages of the events
+    #[derive(Default)]
+    struct Recorder {
+        names: Arc<Mutex<Vec<String>>>,
+        next_id: AtomicU64,
+    }
+
+    struct Message<'a>(&'a mut Vec<String>);
+
+    impl tracing::field::Visit for Message<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "message" {
+                self.0.push(format!("{value:?}"));
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+            self.names
+                .lock()
+                .expect("a test thread panicked")
+                .push(span.metadata().name().to_string());
+            span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut Message(
+                &mut self.names.lock().expect("a test thread panicked"),
+            ));
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    let recorder = Recorder::default();
+    let names = Arc::clone(&recorder.names);
+
+    let mut search = SearchDefinition::new(MCVersion::MC_1_20_6, SeedRange::new(0, 8));
+    search.threads = NonZeroUsize::new(2);
+    search.criteria.push(Criterion::Biome {
+        biome: BiomeID::ocean,
+        x: 0,
+        z: 0,
+        radius: 64,
+        y: YLevel::Absolute(64),
+        min_cells: 1,
+    });
+
+    tracing::subscriber::with_default(recorder, || search.run().expect("search failed"));
+
+    let names = names.lock().expect("a test thread panicked");
+    dbg!(&names);
+
+    assert!(names.iter().any(|name| name == "search"));
+    assert!(names.iter().any(|name| name == "search finished"));
+    // The caches are filled on the search threads
+    assert!(names.iter().any(|name| name == "fill_cache"));
+}
+
+#[test]
+fn large_biomes_searches_use_the_flag() {
+    let seeds = SeedRange::new(0, 40);
+    let criterion = Criterion::Biome {
+        biome: BiomeID::plains,
+        x: 0,
+        z: 0,
+        radius: 64,
+        y: YLevel::Absolute(64),
+        min_cells: 64,
+    };
+
+    let mut search = SearchDefinition::large_biomes(MCVersion::MC_1_20_6, seeds);
+    search.threads = NonZeroUsize::new(3);
+    search.criteria.push(criterion);
+    assert_eq!(search.flags, GeneratorFlags::LargeBiomes);
+
+    let results = search.run().expect("search failed");
+    let found: Vec<i64> = results.hits.iter().map(|hit| hit.seed).collect();
+    dbg!(&found);
+
+    let expected = seeds
+        .par_seeds(MCVersion::MC_1_20_6)
+        .large_biomes()
+        .filter(|generator| {
+            generator.flags() == GeneratorFlags::LargeBiomes
+                && matches!(criterion.check(generator), Ok(Some(_)))
+        });
+
+    assert_eq!(found, expected);
+}
+
+#[test]
+fn surveys_add_up_the_seeds() {
+    use super::{BiomeSurvey, SamplePattern};
+    use crate::generator::{BiomeStats, BlockPosition3D};
+    use std::collections::BTreeMap;
+
+    let seeds = SeedRange::new(-3, 5);
+    let range = Range::plane(Scale::Chunk, -20, -20, 40, 40, 64);
+    let points = vec![
+        BlockPosition3D::new(0, 64, 0),
+        BlockPosition3D::new(-900, 64, 300),
+        BlockPosition3D::new(5000, 64, -5000),
+    ];
+
+    let area = BiomeSurvey::new(
+        [MCVersion::MC_1_16_5, MCVersion::MC_1_20_6],
+        seeds,
+        SamplePattern::Area(range),
+    )
+    .threads(NonZeroUsize::new(3).expect("3 isn't 0"))
+    .run()
+    .expect("failed to generate biomes");
+    let scattered = BiomeSurvey::new(
+        [MCVersion::MC_1_20_6],
+        seeds,
+        SamplePattern::Points(points.clone()),
+    )
+    .run()
+    .expect("failed to generate biomes");
+
+    assert_eq!(area.len(), 2);
+    for (&version, stats) in &area {
+        assert_eq!(stats.total(), 40 * 40 * seeds.len());
+
+        let mut expected = BTreeMap::new();
+        for seed in seeds.start..seeds.end {
+            let generator = Generator::new(
+                version,
+                seed,
+                Dimension::DIM_OVERWORLD,
+                GeneratorFlags::empty(),
+            );
+            let stats = generator
+                .biome_stats(range)
+                .expect("failed to generate biomes");
+            for (biome, count, _) in stats.iter() {
+                *expected.entry(biome).or_insert(0) += count;
+            }
+        }
+        assert_eq!(*stats, BiomeStats::from_counts(expected));
+    }
+
+    let mut expected = BTreeMap::new();
+    for seed in seeds.start..seeds.end {
+        let generator = Generator::new(
+            MCVersion::MC_1_20_6,
+            seed,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        for biome in generator
+            .biomes_at(&points)
+            .expect("failed to generate biomes")
+        {
+            *expected.entry(biome).or_insert(0) += 1;
+        }
+    }
+    assert_eq!(
+        scattered[&MCVersion::MC_1_20_6],
+        BiomeStats::from_counts(expected)
+    );
+
+    let SamplePattern::Points(grid) = SamplePattern::grid(4, 1 << 30, 64) else {
+        unreachable!("grids are points");
+    };
+    // The offsets are -1, 0, 1 and 2 times the spacing, so 2 is left out
+    assert_eq!(grid.len(), 9);
+}
+
+#[test]
+fn evaluators_match_fresh_caches() {
+    let ranges = [
+        Range::plane(Scale::Chunk, -8, -8, 16, 16, 64),
+        Range::plane(Scale::Quad, 100, -40, 7, 9, 64),
+    ];
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        0,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let mut evaluator = super::Evaluator::new(generator, ranges, |generator, caches| {
+        assert_eq!(caches.len(), 2);
+        (
+            generator.seed(),
+            caches
+                .iter()
+                .map(|cache| cache.as_vec().clone())
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    for seed in [3, -5134222539607995087, 3] {
+        let (applied, biomes) = evaluator.evaluate(seed).expect("failed to fill caches");
+        assert_eq!(applied, seed);
+
+        let generator = Generator::new(
+            MCVersion::MC_1_20_6,
+            seed,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        for (range, biomes) in ranges.iter().zip(biomes) {
+            let mut cache = Cache::new(&generator, *range);
+            cache.fill_cache().expect("failed to fill cache");
+            assert_eq!(&biomes, cache.as_vec());
+        }
+    }
+}
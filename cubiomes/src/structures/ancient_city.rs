@@ -0,0 +1,153 @@
+//! Module containing searches for ancient cities
+//!
+//! Ancient cities generate deep underground in the deep dark, which is checked
+//! at the center of the city instead of its chunk. The city center is placed
+//! at a fixed depth, so [`AncientCity`] gives its bounds with the y included.
+
+use super::finder::StructureHit;
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::coords::{block_to_chunk, chunk_to_block};
+use crate::generator::{Aabb, BlockPosition, Generator};
+use std::mem::MaybeUninit;
+
+/// A verified ancient city found by a search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AncientCity {
+    /// The position of the generation attempt of the city, at the start of
+    /// its chunk
+    pub pos: BlockPosition,
+    /// The distance in blocks from the origin of the search to [`Self::pos`]
+    pub distance: f64,
+    /// The bounds of the center piece of the city, see [`ancient_city_center()`]
+    pub center_piece: Aabb,
+}
+
+impl AncientCity {
+    /// Gets the block in the middle of the center piece of the city
+    ///
+    /// The deep dark is checked below this block, at the bottom of the center
+    /// piece.
+    #[must_use]
+    pub fn center(&self) -> (i32, i32, i32) {
+        let middle = |min: i32, max: i32| min + (max - min) / 2;
+
+        (
+            middle(self.center_piece.min_x, self.center_piece.max_x),
+            middle(self.center_piece.min_y, self.center_piece.max_y),
+            middle(self.center_piece.min_z, self.center_piece.max_z),
+        )
+    }
+
+    fn from_hit(hit: StructureHit, version: MCVersion, seed: i64) -> Self {
+        Self {
+            pos: hit.pos,
+            distance: hit.distance,
+            center_piece: ancient_city_center(version, seed, hit.pos),
+        }
+    }
+}
+
+/// Gets the bounds of the center piece of an ancient city
+///
+/// `pos` is the generation attempt of the city, like from
+/// [`super::StructureRegion::get_structure_generation_attempt()`]. The center
+/// piece is rotated around the city anchor, so it is offset from `pos`
+/// depending on the rotation of the city.
+///
+/// This doesn't check if the city generates, see
+/// [`Generator::verify_structure_generation_attempt()`] for that.
+#[must_use]
+pub fn ancient_city_center(version: MCVersion, seed: i64, pos: BlockPosition) -> Aabb {
+    let mut variant: MaybeUninit<cubiomes_sys::StructureVariant> = MaybeUninit::uninit();
+
+    // SAFETY: ffi function is called correctly, and getVariant clears the
+    // variant before filling it in
+    let variant = unsafe {
+        cubiomes_sys::getVariant(
+            variant.as_mut_ptr(),
+            StructureType::Ancient_City as i32,
+            version as i32,
+            seed.cast_unsigned(),
+            pos.x,
+            pos.z,
+            -1,
+        );
+        variant.assume_init()
+    };
+
+    // The offsets of the piece are from the start of the chunk
+    let chunk_x = chunk_to_block(block_to_chunk(pos.x));
+    let chunk_z = chunk_to_block(block_to_chunk(pos.z));
+    let min = (
+        chunk_x + i32::from(variant.x),
+        i32::from(variant.y),
+        chunk_z + i32::from(variant.z),
+    );
+
+    Aabb::new(
+        min,
+        (
+            min.0 + i32::from(variant.sx) - 1,
+            min.1 + i32::from(variant.sy) - 1,
+            min.2 + i32::from(variant.sz) - 1,
+        ),
+    )
+}
+
+impl Generator {
+    /// Finds the closest verified ancient city around a position
+    ///
+    /// This is [`Self::nearest_structure()`] for ancient cities, with the
+    /// bounds of their center piece. Returns [`None`] if no city was found
+    /// within `max_radius` blocks, or before 1.19, which has no ancient
+    /// cities.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let city = generator
+    ///     .nearest_ancient_city(BlockPosition::new(0, 0), 3000)
+    ///     .expect("there is an ancient city nearby");
+    ///
+    /// assert_eq!(city.center(), (211, -12, 415));
+    /// ```
+    pub fn nearest_ancient_city(
+        &mut self,
+        origin: BlockPosition,
+        max_radius: u32,
+    ) -> Option<AncientCity> {
+        let hit = self.nearest_structure(StructureType::Ancient_City, origin, max_radius)?;
+
+        Some(AncientCity::from_hit(
+            hit,
+            self.minecraft_version(),
+            self.seed(),
+        ))
+    }
+
+    /// Finds every verified ancient city within a radius around a position
+    ///
+    /// This is [`Self::structures_within()`] for ancient cities, sorted with
+    /// the closest city first.
+    pub fn ancient_cities_within(
+        &mut self,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Vec<AncientCity> {
+        let (version, seed) = (self.minecraft_version(), self.seed());
+
+        self.structures_within(StructureType::Ancient_City, origin, radius)
+            .into_iter()
+            .map(|hit| AncientCity::from_hit(hit, version, seed))
+            .collect()
+    }
+}
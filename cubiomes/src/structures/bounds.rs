@@ -0,0 +1,224 @@
+//! Module containing the approximate bounds of structures
+//!
+//! Cubiomes knows the size and rotation of the start piece of some
+//! structures, and the pieces of end cities. [`Generator::structure_bounds()`]
+//! turns these into an [`Aabb`], so structures can be checked for overlap with
+//! each other or with the volumes of [`super::farms`] using the AABB
+//! utilities.
+
+use super::ancient_city::ancient_city_center;
+use super::farms::{monument_spawning_volume, WITCH_HUT_SPAWNING_Y};
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::coords::{block_to_chunk, chunk_to_block};
+use crate::generator::{error::GeneratorError, Aabb, BlockPosition, Generator};
+use std::mem::MaybeUninit;
+
+/// The y the start pieces of bastions are placed at
+const BASTION_Y: i32 = 33;
+
+/// The most pieces an end city has, `END_CITY_PIECES_MAX` in cubiomes
+const END_CITY_PIECES_MAX: usize = 421;
+
+impl Generator {
+    /// Estimates the bounding box of a structure generated at a position
+    ///
+    /// `pos` is the position of the structure as returned by the structure
+    /// generation functions, like [`Self::nearest_structure()`]. This doesn't
+    /// check if the structure generates, see
+    /// [`Self::verify_structure_generation_attempt()`] for that.
+    ///
+    /// The bounds come from what cubiomes knows about each structure, so
+    /// they are approximate:
+    ///
+    /// - Villages and bastions are the start piece of the village or the
+    ///   bastion. The rest of the jigsaw pieces aren't generated by cubiomes.
+    /// - Ancient cities are the center piece, see [`ancient_city_center()`].
+    /// - End cities are the box around all of their pieces, placed on the
+    ///   terrain of the end. This needs an end generator.
+    /// - Monuments are their spawning volume, see
+    ///   [`super::farms::monument_spawning_volume()`].
+    /// - Desert pyramids, jungle temples, swamp huts and igloos are the whole
+    ///   structure, without the basement of igloos.
+    ///
+    /// Structures on the surface are placed at [`Self::surface_y()`] in the
+    /// middle of their footprint, which is only an estimate before 1.18.
+    /// Swamp huts and monuments have fixed heights.
+    ///
+    /// Returns [`None`] for every other structure, for villages which can't
+    /// generate in the biome at the position or before 1.14, which has no
+    /// start pieces for villages, and for end cities on too low terrain.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let village = generator
+    ///     .nearest_structure(StructureType::Village, BlockPosition::new(0, 0), 2000)
+    ///     .expect("a village generates close to spawn");
+    /// let bounds = generator
+    ///     .structure_bounds(StructureType::Village, village.pos)
+    ///     .expect("failed to estimate the surface")
+    ///     .expect("villages have a start piece");
+    ///
+    /// assert!(bounds.expand(16).contains(village.pos.x, bounds.min_y, village.pos.z));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if estimating the surface fails, and for end cities
+    /// the errors of [`Self::end_city_terrain_height()`]
+    pub fn structure_bounds(
+        &mut self,
+        structure: StructureType,
+        pos: BlockPosition,
+    ) -> Result<Option<Aabb>, GeneratorError> {
+        let (version, seed) = (self.minecraft_version(), self.seed());
+
+        match structure {
+            StructureType::Village => {
+                let Some(biome) = self.village_biome(pos) else {
+                    return Ok(None);
+                };
+
+                footprint(structure, version, seed, pos, biome)
+                    .map(|footprint| self.on_surface(footprint))
+                    .transpose()
+            }
+            StructureType::Desert_Pyramid | StructureType::Jungle_Temple | StructureType::Igloo => {
+                footprint(structure, version, seed, pos, -1)
+                    .map(|footprint| self.on_surface(footprint))
+                    .transpose()
+            }
+            StructureType::Swamp_Hut => Ok(footprint(structure, version, seed, pos, -1)
+                .map(|footprint| raised(footprint, WITCH_HUT_SPAWNING_Y.0))),
+            StructureType::Monument => Ok(Some(monument_spawning_volume(pos))),
+            StructureType::Bastion => Ok(footprint(structure, version, seed, pos, -1)
+                .map(|footprint| raised(footprint, BASTION_Y))),
+            StructureType::Ancient_City => Ok(Some(ancient_city_center(version, seed, pos))),
+            StructureType::End_City => self.end_city_bounds(pos),
+            _ => Ok(None),
+        }
+    }
+
+    /// Gets the biome deciding the type of the village generated at a
+    /// position, or [`None`] if no village can generate there
+    pub(super) fn village_biome(&mut self, pos: BlockPosition) -> Option<i32> {
+        // SAFETY: The generator pointer is valid for the lifetime of self.
+        // For villages cubiomes returns the biome which made the position
+        // viable, and 0 for positions which aren't
+        let biome = unsafe {
+            cubiomes_sys::isViableStructurePos(
+                StructureType::Village as i32,
+                self.as_mut_ptr(),
+                pos.x,
+                pos.z,
+                0,
+            )
+        };
+
+        (biome > 0).then_some(biome)
+    }
+
+    /// Places a footprint on the estimated surface in the middle of it
+    fn on_surface(&self, footprint: Aabb) -> Result<Aabb, GeneratorError> {
+        let middle = BlockPosition::new(
+            footprint.min_x + (footprint.max_x - footprint.min_x) / 2,
+            footprint.min_z + (footprint.max_z - footprint.min_z) / 2,
+        );
+
+        Ok(raised(footprint, self.surface_y(middle)?))
+    }
+
+    fn end_city_bounds(&self, pos: BlockPosition) -> Result<Option<Aabb>, GeneratorError> {
+        let Some(ground) = self.end_city_terrain_height(pos)? else {
+            return Ok(None);
+        };
+
+        let mut pieces: Vec<cubiomes_sys::Piece> = Vec::with_capacity(END_CITY_PIECES_MAX);
+
+        // SAFETY: The buffer holds END_CITY_PIECES_MAX pieces, which is the
+        // most cubiomes writes, and it returns how many pieces it wrote
+        unsafe {
+            let count = cubiomes_sys::getEndCityPieces(
+                pieces.as_mut_ptr(),
+                self.seed().cast_unsigned(),
+                block_to_chunk(pos.x),
+                block_to_chunk(pos.z),
+            );
+            pieces.set_len(usize::try_from(count).unwrap_or(0).min(END_CITY_PIECES_MAX));
+        }
+
+        // The pieces are placed from y 0, on top of the ground
+        Ok(pieces
+            .iter()
+            .map(|piece| {
+                Aabb::new(
+                    (piece.bb0.x, ground + piece.bb0.y, piece.bb0.z),
+                    (piece.bb1.x, ground + piece.bb1.y, piece.bb1.z),
+                )
+            })
+            .reduce(|bounds, piece| bounds.union(&piece)))
+    }
+}
+
+/// Gets the box of the variant of a structure, with its bottom at y 0
+fn footprint(
+    structure: StructureType,
+    version: MCVersion,
+    seed: i64,
+    pos: BlockPosition,
+    biome: i32,
+) -> Option<Aabb> {
+    let mut variant: MaybeUninit<cubiomes_sys::StructureVariant> = MaybeUninit::uninit();
+
+    // SAFETY: ffi function is called correctly, and getVariant clears the
+    // variant before filling it in
+    let variant = unsafe {
+        cubiomes_sys::getVariant(
+            variant.as_mut_ptr(),
+            structure as i32,
+            version as i32,
+            seed.cast_unsigned(),
+            pos.x,
+            pos.z,
+            biome,
+        );
+        variant.assume_init()
+    };
+
+    if variant.sx <= 0 || variant.sz <= 0 {
+        return None;
+    }
+
+    // The offsets are from the start of the chunk
+    let min = (
+        chunk_to_block(block_to_chunk(pos.x)) + i32::from(variant.x),
+        0,
+        chunk_to_block(block_to_chunk(pos.z)) + i32::from(variant.z),
+    );
+
+    Some(Aabb::new(
+        min,
+        (
+            min.0 + i32::from(variant.sx) - 1,
+            (i32::from(variant.sy) - 1).max(0),
+            min.2 + i32::from(variant.sz) - 1,
+        ),
+    ))
+}
+
+/// Moves a box starting at y 0 up to start at a y
+fn raised(footprint: Aabb, y: i32) -> Aabb {
+    Aabb {
+        min_y: footprint.min_y + y,
+        max_y: footprint.max_y + y,
+        ..footprint
+    }
+}
@@ -0,0 +1,181 @@
+//! Module containing custom structure configurations
+//!
+//! Servers can change where structures generate, for example paper allows
+//! changing the salt, spacing and separation of every structure. Searches with
+//! a [`StructureConfig`] override reproduce the structures of such servers,
+//! see [`super::StructureRegion::with_config()`] and
+//! [`crate::generator::Generator::nearest_structure_with_config()`].
+
+use super::outpost::passes_outpost_chance;
+use super::StructureGenerationError;
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::BlockPosition;
+use crate::rng::{region_seed, JavaRandom};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::mem::MaybeUninit;
+
+/// The placement of a structure spread over regions
+///
+/// Every region is `spacing` chunks wide and has one generation attempt, which
+/// is placed at least `separation` chunks away from the next region.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{MCVersion, StructureType};
+/// use cubiomes::structures::config::StructureConfig;
+/// use cubiomes::structures::StructureRegion;
+///
+/// let vanilla = StructureConfig::of(StructureType::Village, MCVersion::MC_1_20_6)
+///     .expect("villages are generated in 1.20");
+///
+/// // Twice as many villages as in vanilla
+/// let config = StructureConfig {
+///     spacing: 24,
+///     separation: 6,
+///     ..vanilla
+/// };
+///
+/// let region = StructureRegion::new(0, 0, MCVersion::MC_1_20_6, StructureType::Village)
+///     .expect("villages are generated in 1.20")
+///     .with_config(config)
+///     .expect("the config is valid");
+///
+/// assert_eq!(region.region_size_chunks(), 24);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StructureConfig {
+    /// The salt added to the seed of every region
+    pub salt: i32,
+    /// The width of a region in chunks
+    pub spacing: i32,
+    /// The minimum number of chunks between the attempts of two regions
+    pub separation: i32,
+}
+
+impl StructureConfig {
+    /// Gets the vanilla configuration of a structure in a version
+    ///
+    /// # Errors
+    /// Returns [`StructureGenerationError::CubiomesError`] if the structure
+    /// isn't generated in the version
+    pub fn of(
+        structure_type: StructureType,
+        version: MCVersion,
+    ) -> Result<Self, StructureGenerationError> {
+        let mut config: MaybeUninit<cubiomes_sys::StructureConfig> = MaybeUninit::uninit();
+
+        // SAFETY: config is initialized if getStructureConfig did not return 0
+        let config = unsafe {
+            match cubiomes_sys::getStructureConfig(
+                structure_type as i32,
+                version as i32,
+                config.as_mut_ptr(),
+            ) {
+                0 => return Err(StructureGenerationError::CubiomesError),
+                _ => config.assume_init(),
+            }
+        };
+
+        Ok(Self {
+            salt: config.salt,
+            spacing: i32::from(config.regionSize),
+            separation: i32::from(config.regionSize) - i32::from(config.chunkRange),
+        })
+    }
+
+    /// Gets the number of chunks an attempt can be placed at in a region
+    #[must_use]
+    pub fn chunk_range(&self) -> i32 {
+        self.spacing - self.separation
+    }
+
+    /// Checks that attempts of the structure can be placed with this config
+    pub(super) fn validate(
+        &self,
+        structure_type: StructureType,
+    ) -> Result<(), StructureGenerationError> {
+        if placement(structure_type).is_none() {
+            return Err(StructureGenerationError::UnsupportedConfig(structure_type));
+        }
+
+        if self.separation < 0 || self.chunk_range() <= 0 {
+            return Err(StructureGenerationError::InvalidConfig);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the generation attempt of a region like `getStructurePos` of
+    /// cubiomes does with the vanilla config
+    ///
+    /// The config must be validated for the structure.
+    pub(super) fn attempt(
+        &self,
+        structure_type: StructureType,
+        seed: i64,
+        region_x: i32,
+        region_z: i32,
+    ) -> Option<BlockPosition> {
+        let placement = placement(structure_type)?;
+        let range = self.chunk_range();
+
+        let mut rng = JavaRandom::new(region_seed(seed, region_x, region_z, i64::from(self.salt)));
+        let mut offset = || match placement {
+            Placement::Feature => rng.next_int(range),
+            // Large structures are more likely to be at the center of the region
+            Placement::Large => (rng.next_int(range) + rng.next_int(range)) / 2,
+        };
+        let (offset_x, offset_z) = (offset(), offset());
+
+        let chunk_x = region_x.wrapping_mul(self.spacing).wrapping_add(offset_x);
+        let chunk_z = region_z.wrapping_mul(self.spacing).wrapping_add(offset_z);
+        let pos = BlockPosition::new(chunk_x.wrapping_mul(16), chunk_z.wrapping_mul(16));
+
+        let valid = match structure_type {
+            StructureType::Outpost => passes_outpost_chance(seed, chunk_x, chunk_z),
+            // End cities don't generate on the main island
+            StructureType::End_City => {
+                let (x, z) = (i64::from(pos.x), i64::from(pos.z));
+                x * x + z * z >= 1008 * 1008
+            }
+            _ => true,
+        };
+
+        valid.then_some(pos)
+    }
+}
+
+/// How the attempt of a structure is placed in its region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    /// Uniformly in the region
+    Feature,
+    /// Towards the center of the region
+    Large,
+}
+
+/// Gets the placement of a structure, if custom configs are supported for it
+fn placement(structure_type: StructureType) -> Option<Placement> {
+    match structure_type {
+        StructureType::Feature
+        | StructureType::Desert_Pyramid
+        | StructureType::Jungle_Pyramid
+        | StructureType::Swamp_Hut
+        | StructureType::Igloo
+        | StructureType::Village
+        | StructureType::Ocean_Ruin
+        | StructureType::Shipwreck
+        | StructureType::Ruined_Portal
+        | StructureType::Ruined_Portal_N
+        | StructureType::Ancient_City
+        | StructureType::Trail_Ruins
+        | StructureType::Trial_Chambers
+        | StructureType::Outpost => Some(Placement::Feature),
+        StructureType::Monument | StructureType::Mansion | StructureType::End_City => {
+            Some(Placement::Large)
+        }
+        _ => None,
+    }
+}
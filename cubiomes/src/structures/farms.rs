@@ -0,0 +1,606 @@
+//! Module containing helpers for planning mob farms around structures
+//!
+//! Mobs only spawn within a sphere around the player, which in java edition
+//! has a radius of [`DESPAWN_RADIUS`] blocks. A farm built around multiple
+//! structures only works at full rates if every spawning space of every
+//! structure is inside that sphere when the player stands at a single AFK
+//! position.
+//!
+//! The AFK position is found by calculating the smallest sphere enclosing all
+//! spawning volumes, see [`AfkPosition::enclosing()`].
+
+use super::finder::StructureHit;
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::{Aabb, BlockPosition, Generator};
+use crate::rng::Xoroshiro;
+use std::mem::MaybeUninit;
+use thiserror::Error;
+
+/// The radius of the sphere in which mobs spawn around the player
+pub const DESPAWN_RADIUS: f64 = 128.0;
+
+/// The vertical range in which guardians spawn inside an ocean monument
+pub const MONUMENT_SPAWNING_Y: (i32, i32) = (39, 61);
+
+/// The maximum amount of swamp huts a single farm can be built around
+pub const MAX_WITCH_HUTS: usize = 4;
+
+/// The vertical range of the bounding box swamp huts are started with, in
+/// which witches spawn
+pub const WITCH_HUT_SPAWNING_Y: (i32, i32) = (64, 70);
+
+/// Represents an error when planning a farm
+#[derive(Error, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum FarmError {
+    /// The amount of structures given is not supported by the farm
+    #[error("A farm can not be built around {0} structures")]
+    InvalidStructureCount(usize),
+}
+
+/// A position for the player to stand at, while operating a farm
+///
+/// The position is the center of the smallest sphere containing every block
+/// of the spawning volumes it was calculated for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AfkPosition {
+    /// The x coordinate of the position
+    pub x: f64,
+    /// The y coordinate of the position
+    pub y: f64,
+    /// The z coordinate of the position
+    pub z: f64,
+    /// The distance to the furthest spawning block from this position
+    pub radius: f64,
+}
+
+impl AfkPosition {
+    /// Calculates the optimal AFK position for the given spawning volumes
+    ///
+    /// The position is the center of the smallest sphere, which contains the
+    /// center of every block inside the given volumes.
+    ///
+    /// Returns [`None`] if no volumes are given.
+    #[must_use]
+    pub fn enclosing(volumes: &[Aabb]) -> Option<Self> {
+        let points: Vec<[f64; 3]> = volumes.iter().flat_map(Aabb::corner_centers).collect();
+
+        if points.is_empty() {
+            return None;
+        }
+
+        let ball = minimal_ball(points);
+
+        Some(Self {
+            x: ball.center[0],
+            y: ball.center[1],
+            z: ball.center[2],
+            radius: ball.radius,
+        })
+    }
+
+    /// Checks if every spawning block is within `despawn_radius` from this position
+    ///
+    /// Usually `despawn_radius` should be [`DESPAWN_RADIUS`]
+    #[must_use]
+    pub fn covers_all(&self, despawn_radius: f64) -> bool {
+        self.radius <= despawn_radius
+    }
+
+    /// Gets the block this position is inside of
+    #[must_use]
+    pub fn block(&self) -> (i32, i32, i32) {
+        (
+            self.x.floor() as i32,
+            self.y.floor() as i32,
+            self.z.floor() as i32,
+        )
+    }
+}
+
+/// Gets the volume in which guardians spawn for a monument
+///
+/// The position should be the position of the monument as returned by the
+/// structure generation functions.
+#[must_use]
+pub fn monument_spawning_volume(pos: BlockPosition) -> Aabb {
+    // Monuments are 58 blocks wide and centered on their starting chunk
+    let (center_x, center_z) = (pos.x + 8, pos.z + 8);
+
+    Aabb::new(
+        (center_x - 29, MONUMENT_SPAWNING_Y.0, center_z - 29),
+        (center_x + 28, MONUMENT_SPAWNING_Y.1, center_z + 28),
+    )
+}
+
+/// Gets the volume in which witches spawn for a swamp hut
+///
+/// The position should be the position of the hut as returned by the
+/// structure generation functions. Huts are 7 by 9 blocks, and since 1.20
+/// they are rotated, which can swap their width and depth. Unlike
+/// [`WitchHutFarm::platform_volume()`] this is exactly the footprint of the
+/// hut.
+#[must_use]
+pub fn witch_hut_spawning_volume(version: MCVersion, seed: i64, pos: BlockPosition) -> Aabb {
+    let mut variant: MaybeUninit<cubiomes_sys::StructureVariant> = MaybeUninit::uninit();
+
+    // SAFETY: ffi function is called correctly, and getVariant clears the
+    // variant before filling it in
+    let variant = unsafe {
+        cubiomes_sys::getVariant(
+            variant.as_mut_ptr(),
+            StructureType::Swamp_Hut as i32,
+            version as i32,
+            seed.cast_unsigned(),
+            pos.x,
+            pos.z,
+            -1,
+        );
+        variant.assume_init()
+    };
+
+    Aabb::new(
+        (pos.x, WITCH_HUT_SPAWNING_Y.0, pos.z),
+        (
+            pos.x + i32::from(variant.sx) - 1,
+            WITCH_HUT_SPAWNING_Y.1,
+            pos.z + i32::from(variant.sz) - 1,
+        ),
+    )
+}
+
+/// A verified swamp hut found by a search, with the volume witches spawn in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WitchHut {
+    /// The position of the hut, at the start of its chunk
+    pub pos: BlockPosition,
+    /// The distance in blocks from the origin of the search to [`Self::pos`]
+    pub distance: f64,
+    /// The volume witches spawn in, see [`witch_hut_spawning_volume()`]
+    pub spawning_volume: Aabb,
+}
+
+/// The layout of a witch farm built around swamp huts
+///
+/// The platform is given relative to the position of each hut, as returned by
+/// the structure generation functions. The y coordinates of the platform are
+/// absolute, since huts don't have a fixed height.
+///
+/// # Examples
+/// ```
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::structures::farms::WitchHutFarm;
+///
+/// let farm = WitchHutFarm::default();
+///
+/// let huts = [
+///     BlockPosition::new(0, 0),
+///     BlockPosition::new(144, 0),
+///     BlockPosition::new(0, 144),
+///     BlockPosition::new(144, 144),
+/// ];
+///
+/// let afk = farm.afk_position(&huts).expect("four huts are supported");
+///
+/// assert!(afk.covers_all(farm.despawn_radius));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WitchHutFarm {
+    /// The radius of the sphere around the AFK position in which witches spawn
+    pub despawn_radius: f64,
+    /// The spawning platform of a single hut, relative to the hut position
+    pub platform: Aabb,
+}
+
+impl Default for WitchHutFarm {
+    /// Uses [`DESPAWN_RADIUS`] and a platform covering the whole hut
+    ///
+    /// Huts are 7 by 9 blocks, depending on their rotation. The default
+    /// platform is 9 by 9 blocks, so it covers every rotation.
+    fn default() -> Self {
+        Self {
+            despawn_radius: DESPAWN_RADIUS,
+            platform: Aabb::new((0, 64, 0), (8, 70, 8)),
+        }
+    }
+}
+
+impl WitchHutFarm {
+    /// Gets the spawning platform of the hut at the given position
+    #[must_use]
+    pub fn platform_volume(&self, hut: BlockPosition) -> Aabb {
+        Aabb::new(
+            (
+                hut.x + self.platform.min_x,
+                self.platform.min_y,
+                hut.z + self.platform.min_z,
+            ),
+            (
+                hut.x + self.platform.max_x,
+                self.platform.max_y,
+                hut.z + self.platform.max_z,
+            ),
+        )
+    }
+
+    /// Calculates the optimal AFK position for a farm around the given huts
+    ///
+    /// The AFK position is returned even if not all platforms are within the
+    /// despawn sphere, use [`AfkPosition::covers_all()`] or
+    /// [`WitchHutFarm::covers_all()`] to check it.
+    ///
+    /// # Errors
+    /// Returns [`FarmError::InvalidStructureCount`] if not between 1 and
+    /// [`MAX_WITCH_HUTS`] huts are given
+    pub fn afk_position(&self, huts: &[BlockPosition]) -> Result<AfkPosition, FarmError> {
+        if !(1..=MAX_WITCH_HUTS).contains(&huts.len()) {
+            return Err(FarmError::InvalidStructureCount(huts.len()));
+        }
+
+        let volumes: Vec<Aabb> = huts.iter().map(|&hut| self.platform_volume(hut)).collect();
+
+        AfkPosition::enclosing(&volumes).ok_or(FarmError::InvalidStructureCount(huts.len()))
+    }
+
+    /// Checks if a single AFK position covers every platform of the given huts
+    ///
+    /// # Errors
+    /// Returns [`FarmError::InvalidStructureCount`] if not between 1 and
+    /// [`MAX_WITCH_HUTS`] huts are given
+    pub fn covers_all(&self, huts: &[BlockPosition]) -> Result<bool, FarmError> {
+        Ok(self.afk_position(huts)?.covers_all(self.despawn_radius))
+    }
+}
+
+/// A group of ocean monuments which can be farmed from a single AFK position
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonumentCluster {
+    /// The positions of the monuments in the cluster
+    pub monuments: Vec<BlockPosition>,
+    /// The AFK position from which all monuments are in range
+    pub afk: AfkPosition,
+}
+
+impl Generator {
+    /// Finds every verified swamp hut within a radius around a position
+    ///
+    /// This is [`Self::structures_within()`] for swamp huts, with the volume
+    /// witches spawn in for each hut. The volumes can be given directly to
+    /// [`AfkPosition::enclosing()`]. The huts are sorted with the closest one
+    /// first.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Aabb, BlockPosition, Generator, GeneratorFlags};
+    /// use cubiomes::structures::farms::{AfkPosition, DESPAWN_RADIUS};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let huts = generator.witch_huts_within(BlockPosition::new(0, 0), 4000);
+    /// let volumes: Vec<Aabb> = huts.iter().take(2).map(|hut| hut.spawning_volume).collect();
+    ///
+    /// let afk = AfkPosition::enclosing(&volumes).expect("there are swamp huts nearby");
+    ///
+    /// assert_eq!(huts[0].pos, BlockPosition::new(-3568, 528));
+    /// assert_eq!(huts[1].pos, BlockPosition::new(-3888, 592));
+    ///
+    /// // The huts are too far apart for a single farm
+    /// assert!(!afk.covers_all(DESPAWN_RADIUS));
+    /// ```
+    pub fn witch_huts_within(&mut self, origin: BlockPosition, radius: u32) -> Vec<WitchHut> {
+        let (version, seed) = (self.minecraft_version(), self.seed());
+
+        self.structures_within(StructureType::Swamp_Hut, origin, radius)
+            .into_iter()
+            .map(|hit| WitchHut {
+                pos: hit.pos,
+                distance: hit.distance,
+                spawning_volume: witch_hut_spawning_volume(version, seed, hit.pos),
+            })
+            .collect()
+    }
+
+    /// Finds groups of ocean monuments which can be farmed from a single AFK position
+    ///
+    /// Searches for monuments within `radius` blocks of `origin` and returns
+    /// every group of at least `min_count` monuments, for which every spawning
+    /// space is within [`DESPAWN_RADIUS`] of a single position. Groups which
+    /// are part of a bigger group are not returned separately.
+    ///
+    /// `min_count` is always at least 2. The clusters are sorted with the
+    /// largest clusters first.
+    pub fn monument_clusters(
+        &mut self,
+        origin: BlockPosition,
+        radius: u32,
+        min_count: usize,
+    ) -> Vec<MonumentCluster> {
+        let min_count = min_count.max(2);
+
+        let monuments: Vec<BlockPosition> = self
+            .structures_within(StructureType::Monument, origin, radius)
+            .into_iter()
+            .map(|hit: StructureHit| hit.pos)
+            .collect();
+
+        let fits = |group: &[BlockPosition]| {
+            let volumes: Vec<Aabb> = group
+                .iter()
+                .copied()
+                .map(monument_spawning_volume)
+                .collect();
+            AfkPosition::enclosing(&volumes).filter(|afk| afk.covers_all(DESPAWN_RADIUS))
+        };
+
+        // Only monuments which fit together pairwise can be in a group. The
+        // sphere is at least as wide as the distance between two monuments,
+        // so monuments further apart are skipped before calculating it.
+        let neighbours: Vec<Vec<usize>> = monuments
+            .iter()
+            .enumerate()
+            .map(|(i, &first)| {
+                (i + 1..monuments.len())
+                    .filter(|&j| first.distance(monuments[j]) <= 2.0 * DESPAWN_RADIUS)
+                    .filter(|&j| fits(&[first, monuments[j]]).is_some())
+                    .collect()
+            })
+            .collect();
+
+        let mut clusters: Vec<MonumentCluster> = Vec::new();
+
+        // Groups are grown one monument at a time, and only from groups that
+        // fit, as a group never fits if a part of it doesn't
+        let mut groups: Vec<(Vec<usize>, Vec<usize>)> = (0..monuments.len())
+            .map(|i| (vec![i], neighbours[i].clone()))
+            .collect();
+
+        while let Some((members, candidates)) = groups.pop() {
+            for (n, &next) in candidates.iter().enumerate() {
+                let mut grown = members.clone();
+                grown.push(next);
+
+                let group: Vec<BlockPosition> = grown.iter().map(|&j| monuments[j]).collect();
+                let Some(afk) = fits(&group) else {
+                    continue;
+                };
+
+                let remaining: Vec<usize> = candidates[n + 1..]
+                    .iter()
+                    .copied()
+                    .filter(|j| neighbours[next].contains(j))
+                    .collect();
+                groups.push((grown, remaining));
+
+                if group.len() >= min_count {
+                    clusters.push(MonumentCluster {
+                        monuments: group,
+                        afk,
+                    });
+                }
+            }
+        }
+
+        let maximal: Vec<bool> = clusters
+            .iter()
+            .map(|cluster| {
+                !clusters.iter().any(|other| {
+                    other.monuments.len() > cluster.monuments.len()
+                        && cluster
+                            .monuments
+                            .iter()
+                            .all(|pos| other.monuments.contains(pos))
+                })
+            })
+            .collect();
+
+        let mut clusters: Vec<MonumentCluster> = clusters
+            .into_iter()
+            .zip(maximal)
+            .filter_map(|(cluster, maximal)| maximal.then_some(cluster))
+            .collect();
+
+        clusters.sort_by(|a, b| {
+            b.monuments
+                .len()
+                .cmp(&a.monuments.len())
+                .then(a.afk.radius.total_cmp(&b.afk.radius))
+        });
+
+        clusters
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Ball {
+    center: [f64; 3],
+    radius: f64,
+}
+
+// Tolerance used for detecting degenerate sets of points
+const EPSILON: f64 = 1e-7;
+
+// Tolerance used for checking if a point is inside a ball
+const CONTAINS_EPSILON: f64 = 1e-6;
+
+impl Ball {
+    const EMPTY: Ball = Ball {
+        center: [0.0; 3],
+        radius: -1.0,
+    };
+
+    fn contains(&self, point: &[f64; 3]) -> bool {
+        self.radius >= 0.0 && distance(&self.center, point) <= self.radius + CONTAINS_EPSILON
+    }
+
+    fn from_two(a: &[f64; 3], b: &[f64; 3]) -> Ball {
+        let center = [
+            (a[0] + b[0]) / 2.0,
+            (a[1] + b[1]) / 2.0,
+            (a[2] + b[2]) / 2.0,
+        ];
+        Ball {
+            center,
+            radius: distance(&center, a),
+        }
+    }
+
+    fn from_three(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3]) -> Ball {
+        let ab = sub(b, a);
+        let ac = sub(c, a);
+        let normal = cross(&ab, &ac);
+        let normal_len = dot(&normal, &normal);
+
+        if normal_len < EPSILON {
+            // The points are on a line, so the ball is defined by the furthest pair
+            return [
+                Ball::from_two(a, b),
+                Ball::from_two(a, c),
+                Ball::from_two(b, c),
+            ]
+            .into_iter()
+            .max_by(|x, y| x.radius.total_cmp(&y.radius))
+            .unwrap_or(Ball::EMPTY);
+        }
+
+        let offset = cross(
+            &sub(&scale(&ac, dot(&ab, &ab)), &scale(&ab, dot(&ac, &ac))),
+            &normal,
+        );
+        let offset = scale(&offset, 1.0 / (2.0 * normal_len));
+        let center = add(a, &offset);
+
+        Ball {
+            center,
+            radius: distance(&center, a),
+        }
+    }
+
+    fn from_four(a: &[f64; 3], b: &[f64; 3], c: &[f64; 3], d: &[f64; 3]) -> Ball {
+        let ab = sub(b, a);
+        let ac = sub(c, a);
+        let ad = sub(d, a);
+        let det = dot(&ab, &cross(&ac, &ad));
+
+        if det.abs() < EPSILON {
+            // The points are on a plane, pick the smallest ball of three points
+            // which contains the fourth one
+            let points = [a, b, c, d];
+            return (0..4)
+                .map(|skip| {
+                    let rest: Vec<&[f64; 3]> =
+                        (0..4).filter(|&i| i != skip).map(|i| points[i]).collect();
+                    (Ball::from_three(rest[0], rest[1], rest[2]), points[skip])
+                })
+                .filter(|(ball, skipped)| ball.contains(skipped))
+                .map(|(ball, _)| ball)
+                .min_by(|x, y| x.radius.total_cmp(&y.radius))
+                .unwrap_or(Ball::EMPTY);
+        }
+
+        let offset = add(
+            &add(
+                &scale(&cross(&ac, &ad), dot(&ab, &ab)),
+                &scale(&cross(&ad, &ab), dot(&ac, &ac)),
+            ),
+            &scale(&cross(&ab, &ac), dot(&ad, &ad)),
+        );
+        let center = add(a, &scale(&offset, 1.0 / (2.0 * det)));
+
+        Ball {
+            center,
+            radius: distance(&center, a),
+        }
+    }
+
+    fn from_boundary(boundary: &[[f64; 3]]) -> Ball {
+        match boundary {
+            [] => Ball::EMPTY,
+            [a] => Ball {
+                center: *a,
+                radius: 0.0,
+            },
+            [a, b] => Ball::from_two(a, b),
+            [a, b, c] => Ball::from_three(a, b, c),
+            [a, b, c, d, ..] => Ball::from_four(a, b, c, d),
+        }
+    }
+}
+
+// Welzl's algorithm for the smallest enclosing ball of a set of points
+//
+// The recursion is unrolled into a loop for each of the up to four points on
+// the boundary, so the stack doesn't grow with the amount of points. The
+// points are shuffled first, as the expected linear time only holds for a
+// random order, while the corners of the volumes are sorted.
+fn minimal_ball(mut points: Vec<[f64; 3]>) -> Ball {
+    let mut rng = Xoroshiro::new(0);
+    for i in (1..points.len()).rev() {
+        let j = rng.next_long().cast_unsigned() % (i as u64 + 1);
+        points.swap(i, j as usize);
+    }
+
+    let mut ball = Ball::EMPTY;
+
+    for i in 0..points.len() {
+        if ball.contains(&points[i]) {
+            continue;
+        }
+        ball = Ball::from_boundary(&[points[i]]);
+
+        for j in 0..i {
+            if ball.contains(&points[j]) {
+                continue;
+            }
+            ball = Ball::from_boundary(&[points[i], points[j]]);
+
+            for k in 0..j {
+                if ball.contains(&points[k]) {
+                    continue;
+                }
+                ball = Ball::from_boundary(&[points[i], points[j], points[k]]);
+
+                for l in 0..k {
+                    if !ball.contains(&points[l]) {
+                        ball = Ball::from_boundary(&[points[i], points[j], points[k], points[l]]);
+                    }
+                }
+            }
+        }
+    }
+
+    ball
+}
+
+fn sub(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn add(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn scale(a: &[f64; 3], s: f64) -> [f64; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    let d = sub(a, b);
+    dot(&d, &d).sqrt()
+}
@@ -0,0 +1,356 @@
+//! Module containing convenience searches for structures around a position
+//!
+//! For most use cases [`Generator::nearest_structure()`] is all that is
+//! needed. It combines iterating over the [`StructureRegion`]s around a
+//! position, getting the generation attempt of each region and verifying it.
+//!
+//! Servers with customized structure spacing can be searched with
+//! [`Generator::nearest_structure_with_config()`] and
+//! [`Generator::structures_within_with_config()`].
+//!
+//! Every search returns its hits sorted by their distance from the origin,
+//! and [`StructureHits`] narrows them down further.
+
+use super::config::StructureConfig;
+use super::variant::StructureVariant;
+use super::StructureRegion;
+use crate::enums::StructureType;
+use crate::generator::{spiral, BlockPosition, Generator};
+
+/// A verified instance of a structure found by a search
+///
+/// The hit contains the position of the structure, its type, the distance
+/// in blocks from the origin the search was performed around and its
+/// variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StructureHit {
+    /// The block position of the structure
+    pub pos: BlockPosition,
+    /// The type of the structure
+    pub kind: StructureType,
+    /// The distance in blocks from the origin of the search
+    pub distance: f64,
+    /// The variant of the structure, if cubiomes knows it for the type, see
+    /// [`Generator::structure_variant()`]
+    pub variant: Option<StructureVariant>,
+}
+
+impl StructureHit {
+    /// Creates a hit without a variant, with its distance from `origin`
+    #[must_use]
+    pub fn new(pos: BlockPosition, kind: StructureType, origin: BlockPosition) -> Self {
+        Self {
+            pos,
+            kind,
+            distance: origin.distance(pos),
+            variant: None,
+        }
+    }
+}
+
+/// Adapters for iterators over [`StructureHit`]s
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+/// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+/// use cubiomes::structures::finder::StructureHits;
+///
+/// let mut generator = Generator::new(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     Dimension::DIM_OVERWORLD,
+///     GeneratorFlags::empty(),
+/// );
+///
+/// let origin = BlockPosition::new(0, 0);
+/// let mut hits = generator.structures_within(StructureType::Village, origin, 3000);
+/// hits.extend(generator.structures_within(StructureType::Outpost, origin, 3000));
+///
+/// // The three closest villages or outposts within 2000 blocks of 500, 500
+/// let closest = hits
+///     .into_iter()
+///     .sorted_from(BlockPosition::new(500, 500))
+///     .into_iter()
+///     .within(2000.0)
+///     .closest(3);
+///
+/// assert!(closest.len() <= 3);
+/// assert!(closest.windows(2).all(|pair| pair[0].distance <= pair[1].distance));
+/// ```
+pub trait StructureHits: Iterator<Item = StructureHit> + Sized {
+    /// Keeps the hits at most `radius` blocks from their origin
+    fn within(self, radius: f64) -> impl Iterator<Item = StructureHit> {
+        self.filter(move |hit| hit.distance <= radius)
+    }
+
+    /// Gets the `n` hits closest to their origin, with the closest first
+    #[must_use]
+    fn closest(self, n: usize) -> Vec<StructureHit> {
+        let mut hits: Vec<StructureHit> = self.collect();
+        sort_hits(&mut hits);
+        hits.truncate(n);
+        hits
+    }
+
+    /// Measures the distances of the hits from another origin and sorts them
+    /// by it, with the closest first
+    ///
+    /// This combines the hits of searches around different origins, or of
+    /// several structure types.
+    #[must_use]
+    fn sorted_from(self, origin: BlockPosition) -> Vec<StructureHit> {
+        let mut hits: Vec<StructureHit> = self
+            .map(|hit| StructureHit {
+                distance: origin.distance(hit.pos),
+                ..hit
+            })
+            .collect();
+        sort_hits(&mut hits);
+        hits
+    }
+}
+
+impl<I: Iterator<Item = StructureHit>> StructureHits for I {}
+
+/// Sorts hits by their distance, with the closest first
+pub(super) fn sort_hits(hits: &mut [StructureHit]) {
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+}
+
+impl Generator {
+    /// Finds the closest verified instance of a structure around a position
+    ///
+    /// The regions around `origin` are checked in rings, starting from the
+    /// region containing `origin`. The search stops once no unchecked region
+    /// could contain a structure closer than the closest one found, or once
+    /// the regions are further than `max_radius` blocks away.
+    ///
+    /// Returns [`None`] if no structure was found within `max_radius` blocks,
+    /// or if the structure doesn't generate in the version of the generator.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let hit = generator
+    ///     .nearest_structure(StructureType::Village, BlockPosition::new(0, 0), 2000)
+    ///     .expect("there should be a village within 2000 blocks");
+    ///
+    /// assert!(hit.distance <= 2000.0);
+    /// ```
+    pub fn nearest_structure(
+        &mut self,
+        structure_type: StructureType,
+        origin: BlockPosition,
+        max_radius: u32,
+    ) -> Option<StructureHit> {
+        let region = self.origin_region(structure_type, None, origin)?;
+        self.nearest_structure_from(region, origin, max_radius)
+    }
+
+    /// Finds the closest verified instance of a structure around a position,
+    /// with a custom [`StructureConfig`]
+    ///
+    /// This is [`Self::nearest_structure()`] for servers with customized
+    /// structure spacing, see [`StructureRegion::with_config()`].
+    ///
+    /// Returns [`None`] if no structure was found within `max_radius` blocks,
+    /// if the structure doesn't generate in the version of the generator, or
+    /// if the config isn't valid for the structure.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    /// use cubiomes::structures::config::StructureConfig;
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let config = StructureConfig {
+    ///     salt: 10387312,
+    ///     spacing: 16,
+    ///     separation: 4,
+    /// };
+    ///
+    /// let hit = generator
+    ///     .nearest_structure_with_config(StructureType::Village, config, BlockPosition::new(0, 0), 2000)
+    ///     .expect("there should be a village within 2000 blocks");
+    ///
+    /// assert!(hit.distance <= 2000.0);
+    /// ```
+    pub fn nearest_structure_with_config(
+        &mut self,
+        structure_type: StructureType,
+        config: StructureConfig,
+        origin: BlockPosition,
+        max_radius: u32,
+    ) -> Option<StructureHit> {
+        let region = self.origin_region(structure_type, Some(config), origin)?;
+        self.nearest_structure_from(region, origin, max_radius)
+    }
+
+    fn nearest_structure_from(
+        &mut self,
+        mut region: StructureRegion,
+        origin: BlockPosition,
+        max_radius: u32,
+    ) -> Option<StructureHit> {
+        let structure_type = region.structure_type();
+        let region_size = region.region_size_blocks() as f64;
+        let origin_region = BlockPosition::new(region.x, region.z);
+        let max_radius = max_radius as f64;
+
+        let mut closest: Option<StructureHit> = None;
+
+        for cell in spiral(origin_region, 1, ring_limit(max_radius, region_size)) {
+            // Any region in this ring is at least (ring - 1) regions away from
+            // the origin, so there is no point in checking it if it can't
+            // contain anything closer than what we already have
+            let min_distance = f64::from(cell.ring.saturating_sub(1)) * region_size;
+
+            if closest.is_some_and(|hit| hit.distance <= min_distance) {
+                break;
+            }
+
+            (region.x, region.z) = (cell.pos.x, cell.pos.z);
+
+            let Some(pos) = region.get_structure_generation_attempt(self.seed()) else {
+                continue;
+            };
+
+            let distance = origin.distance(pos);
+
+            if distance > max_radius || closest.is_some_and(|hit| hit.distance <= distance) {
+                continue;
+            }
+
+            if let Ok(true) = self.verify_structure_generation_attempt(pos, structure_type) {
+                closest = Some(StructureHit {
+                    variant: self.structure_variant(structure_type, pos),
+                    ..StructureHit::new(pos, structure_type, origin)
+                });
+            }
+        }
+
+        closest
+    }
+
+    /// Finds every verified instance of a structure within a radius around a position
+    ///
+    /// The hits are sorted by their distance from `origin`, with the closest
+    /// structure first.
+    ///
+    /// Returns an empty [`Vec`] if the structure doesn't generate in the
+    /// version of the generator.
+    pub fn structures_within(
+        &mut self,
+        structure_type: StructureType,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Vec<StructureHit> {
+        match self.origin_region(structure_type, None, origin) {
+            Some(region) => self.structures_within_from(region, origin, radius),
+            None => Vec::new(),
+        }
+    }
+
+    /// Finds every verified instance of a structure within a radius around a
+    /// position, with a custom [`StructureConfig`]
+    ///
+    /// This is [`Self::structures_within()`] for servers with customized
+    /// structure spacing, see [`StructureRegion::with_config()`].
+    ///
+    /// Returns an empty [`Vec`] if the structure doesn't generate in the
+    /// version of the generator, or if the config isn't valid for the
+    /// structure.
+    pub fn structures_within_with_config(
+        &mut self,
+        structure_type: StructureType,
+        config: StructureConfig,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Vec<StructureHit> {
+        match self.origin_region(structure_type, Some(config), origin) {
+            Some(region) => self.structures_within_from(region, origin, radius),
+            None => Vec::new(),
+        }
+    }
+
+    fn structures_within_from(
+        &mut self,
+        mut region: StructureRegion,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Vec<StructureHit> {
+        let structure_type = region.structure_type();
+        let region_size = region.region_size_blocks() as f64;
+        let origin_region = BlockPosition::new(region.x, region.z);
+        let radius = radius as f64;
+
+        let mut hits = Vec::new();
+
+        for cell in spiral(origin_region, 1, ring_limit(radius, region_size)) {
+            (region.x, region.z) = (cell.pos.x, cell.pos.z);
+
+            let Some(pos) = region.get_structure_generation_attempt(self.seed()) else {
+                continue;
+            };
+
+            let distance = origin.distance(pos);
+
+            if distance > radius {
+                continue;
+            }
+
+            if let Ok(true) = self.verify_structure_generation_attempt(pos, structure_type) {
+                hits.push(StructureHit {
+                    variant: self.structure_variant(structure_type, pos),
+                    ..StructureHit::new(pos, structure_type, origin)
+                });
+            }
+        }
+
+        sort_hits(&mut hits);
+        hits
+    }
+
+    /// Gets the region containing `origin`, with an optional config override
+    fn origin_region(
+        &self,
+        structure_type: StructureType,
+        config: Option<StructureConfig>,
+        origin: BlockPosition,
+    ) -> Option<StructureRegion> {
+        let mut region =
+            StructureRegion::new(0, 0, self.minecraft_version(), structure_type).ok()?;
+
+        if let Some(config) = config {
+            region = region.with_config(config).ok()?;
+        }
+
+        region.set_new_minecraft_pos(origin);
+        Some(region)
+    }
+}
+
+/// Gets the last ring of regions which can contain an attempt within a radius
+///
+/// Regions of ring `n` are at least `n - 1` regions away from the origin, as
+/// the origin can be anywhere in its region.
+fn ring_limit(radius: f64, region_size: f64) -> u32 {
+    ((radius / region_size) as u32).saturating_add(1)
+}
@@ -0,0 +1,97 @@
+//! Module containing the positions of amethyst geodes
+//!
+//! Geodes are decorator features, so every chunk has its own attempt, which
+//! only depends on the seed. The attempts are the structure positions of
+//! cubiomes, with a region of a single chunk, see [`geode_in_chunk()`].
+
+use super::finder::{sort_hits, StructureHit};
+use super::variant::structure_variant;
+use super::StructureRegion;
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::{spiral, BlockPosition, Generator, Scale};
+
+/// Gets the geode placed in a chunk, if the chunk has one
+///
+/// The position is the corner of the geode with the smallest x and z, the
+/// geode itself is up to 16 blocks larger. Geodes generate in every biome,
+/// so no generator is needed. The chunk is given in chunk coordinates.
+///
+/// Returns [`None`] if the chunk has no geode, or before 1.17, which has no
+/// geodes.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::structures::geodes::geode_in_chunk;
+///
+/// let geodes = (0..16)
+///     .flat_map(|x| (0..16).map(move |z| (x, z)))
+///     .filter_map(|(x, z)| geode_in_chunk(MCVersion::MC_1_20_6, -5134222539607995087, x, z))
+///     .count();
+///
+/// // About one in 24 chunks has a geode
+/// assert_eq!(geodes, 8);
+/// ```
+#[must_use]
+pub fn geode_in_chunk(
+    version: MCVersion,
+    world_seed: i64,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Option<BlockPosition> {
+    if version < MCVersion::MC_1_17_1 {
+        return None;
+    }
+
+    StructureRegion::new(chunk_x, chunk_z, version, StructureType::Geode)
+        .ok()?
+        .get_structure_generation_attempt(world_seed)
+}
+
+impl Generator {
+    /// Finds every geode within a radius around a position
+    ///
+    /// The hits are sorted by their distance from `origin`, with the closest
+    /// geode first. Returns an empty [`Vec`] before 1.17.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let geodes = generator.geodes_within(BlockPosition::new(0, 0), 128);
+    ///
+    /// assert_eq!(geodes.len(), 2);
+    /// assert_eq!(geodes[0].pos, BlockPosition::new(-122, 2));
+    /// assert!(geodes.iter().all(|geode| geode.distance <= 128.0));
+    /// ```
+    #[must_use]
+    pub fn geodes_within(&self, origin: BlockPosition, radius: u32) -> Vec<StructureHit> {
+        let version = self.minecraft_version();
+        let chunk = Scale::Chunk as i32;
+        let (chunk_x, chunk_z) = origin.scale_by_num(chunk);
+
+        let mut hits: Vec<StructureHit> = spiral(
+            BlockPosition::new(chunk_x, chunk_z),
+            1,
+            radius / chunk.unsigned_abs() + 1,
+        )
+        .filter_map(|cell| geode_in_chunk(version, self.seed(), cell.pos.x, cell.pos.z))
+        .map(|pos| StructureHit {
+            variant: structure_variant(StructureType::Geode, version, self.seed(), pos, -1),
+            ..StructureHit::new(pos, StructureType::Geode, origin)
+        })
+        .filter(|hit| hit.distance <= f64::from(radius))
+        .collect();
+
+        sort_hits(&mut hits);
+        hits
+    }
+}
@@ -0,0 +1,133 @@
+//! Module containing the variants of igloos
+//!
+//! Half of all igloos have a hidden basement below them, reached by a ladder
+//! of 4 to 11 pieces. Whether an igloo has one only depends on the seed and
+//! the chunk of the igloo, see [`igloo_variant()`].
+
+use super::Rotation;
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::{BlockPosition, Generator};
+use std::mem::MaybeUninit;
+
+/// The basement and rotation of an igloo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IglooVariant {
+    /// The rotation of the igloo
+    pub rotation: Rotation,
+    /// The number of ladder pieces leading down to the basement, or [`None`]
+    /// if the igloo has no basement
+    pub ladder_pieces: Option<u8>,
+}
+
+impl IglooVariant {
+    /// Checks if the igloo has a basement
+    #[must_use]
+    pub fn has_basement(&self) -> bool {
+        self.ladder_pieces.is_some()
+    }
+}
+
+/// A verified igloo found by a search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Igloo {
+    /// The position of the generation attempt of the igloo, at the start of
+    /// its chunk
+    pub pos: BlockPosition,
+    /// The distance in blocks from the origin of the search to [`Self::pos`]
+    pub distance: f64,
+    /// The basement and rotation of the igloo
+    pub variant: IglooVariant,
+}
+
+/// Gets the variant of an igloo
+///
+/// `pos` is the generation attempt of the igloo, like from
+/// [`super::StructureRegion::get_structure_generation_attempt()`].
+///
+/// This doesn't check if the igloo generates, see
+/// [`Generator::verify_structure_generation_attempt()`] for that.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::structures::igloo::igloo_variant;
+///
+/// let variant = igloo_variant(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     BlockPosition::new(-320, 96),
+/// );
+///
+/// if let Some(pieces) = variant.ladder_pieces {
+///     assert!((4..12).contains(&pieces));
+/// }
+/// ```
+#[must_use]
+pub fn igloo_variant(version: MCVersion, seed: i64, pos: BlockPosition) -> IglooVariant {
+    let mut variant: MaybeUninit<cubiomes_sys::StructureVariant> = MaybeUninit::uninit();
+
+    // SAFETY: ffi function is called correctly, and getVariant clears the
+    // variant before filling it in
+    let variant = unsafe {
+        cubiomes_sys::getVariant(
+            variant.as_mut_ptr(),
+            StructureType::Igloo as i32,
+            version as i32,
+            seed.cast_unsigned(),
+            pos.x,
+            pos.z,
+            -1,
+        );
+        variant.assume_init()
+    };
+
+    // Cubiomes splits the rotation into a quarter turn and a half turn, the
+    // latter stored as the mirror
+    let rotation = i32::from(variant.rotation) + 2 * i32::from(variant.mirror);
+
+    IglooVariant {
+        rotation: Rotation::from_index(rotation),
+        ladder_pieces: (variant.basement() != 0).then_some(variant.size),
+    }
+}
+
+impl Generator {
+    /// Finds every verified igloo within a radius around a position
+    ///
+    /// This is [`Self::structures_within()`] for igloos, with the variant of
+    /// each igloo. The igloos are sorted with the closest one first.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let basement = generator
+    ///     .igloos_within(BlockPosition::new(0, 0), 5000)
+    ///     .into_iter()
+    ///     .find(|igloo| igloo.variant.has_basement());
+    ///
+    /// let igloo = basement.expect("there is an igloo with a basement nearby");
+    /// assert_eq!(igloo.pos, BlockPosition::new(816, -944));
+    /// ```
+    pub fn igloos_within(&mut self, origin: BlockPosition, radius: u32) -> Vec<Igloo> {
+        let (version, seed) = (self.minecraft_version(), self.seed());
+
+        self.structures_within(StructureType::Igloo, origin, radius)
+            .into_iter()
+            .map(|hit| Igloo {
+                pos: hit.pos,
+                distance: hit.distance,
+                variant: igloo_variant(version, seed, hit.pos),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,58 @@
+//! Module containing searches for woodland mansions
+//!
+//! Mansions generate in dark forests, which cubiomes checks in a radius
+//! around the mansion up to 1.17 and at its center since 1.18. Since 1.18 the
+//! terrain at the corners of the mansion has to be high enough as well, which
+//! the searches of this module rule out with
+//! [`Generator::verify_structure_terrain()`].
+
+use super::finder::StructureHit;
+use crate::enums::StructureType;
+use crate::generator::{BlockPosition, Generator};
+
+impl Generator {
+    /// Finds the closest verified woodland mansion around a position
+    ///
+    /// Every mansion within `max_radius` blocks is checked, see
+    /// [`Self::mansions_within()`]. Returns [`None`] if no mansion was found,
+    /// or before 1.11, which has no mansions.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mansion = generator
+    ///     .nearest_mansion(BlockPosition::new(0, 0), 5000)
+    ///     .expect("there is a mansion nearby");
+    ///
+    /// assert_eq!(mansion.pos, BlockPosition::new(1712, -1840));
+    /// ```
+    pub fn nearest_mansion(
+        &mut self,
+        origin: BlockPosition,
+        max_radius: u32,
+    ) -> Option<StructureHit> {
+        self.mansions_within(origin, max_radius).into_iter().next()
+    }
+
+    /// Finds every verified woodland mansion within a radius around a
+    /// position
+    ///
+    /// This is [`Self::structures_within()`] for mansions, without the
+    /// mansions on too low terrain since 1.18. The mansions are sorted with
+    /// the closest one first.
+    pub fn mansions_within(&mut self, origin: BlockPosition, radius: u32) -> Vec<StructureHit> {
+        let mut mansions = self.structures_within(StructureType::Mansion, origin, radius);
+        mansions.retain(|hit| self.verify_structure_terrain(hit.pos, StructureType::Mansion));
+
+        mansions
+    }
+}
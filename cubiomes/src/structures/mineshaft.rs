@@ -0,0 +1,136 @@
+//! Module containing the chunks mineshafts start in
+//!
+//! Unlike most structures, mineshafts aren't spread over regions. Every chunk
+//! has its own chance of starting a mineshaft, which only depends on the
+//! seed, see [`mineshaft_chunks()`]. Cubiomes treats every overworld biome as
+//! viable for mineshafts, so the chunks are candidates which aren't checked
+//! against the biomes.
+
+use super::finder::{sort_hits, StructureHit};
+use super::is_structure_enabled;
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::coords::block_to_chunk;
+use crate::generator::{BlockPosition, Generator};
+
+/// Gets the chunks starting a mineshaft in an area
+///
+/// The area is given in chunk coordinates, from `min` to `max` with both
+/// corners included. The positions are the start of the mineshaft chunks in
+/// block coordinates, ordered by x and then by z.
+///
+/// Returns an empty [`Vec`] if the version has no mineshafts, or if `max` is
+/// smaller than `min`.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::structures::mineshaft::mineshaft_chunks;
+///
+/// let mineshafts = mineshaft_chunks(
+///     MCVersion::MC_1_20_6,
+///     -5134222539607995087,
+///     BlockPosition::new(-32, -32),
+///     BlockPosition::new(31, 31),
+/// );
+///
+/// // About one in 250 chunks starts a mineshaft
+/// assert_eq!(mineshafts.len(), 16);
+/// ```
+#[must_use]
+pub fn mineshaft_chunks(
+    version: MCVersion,
+    world_seed: i64,
+    min: BlockPosition,
+    max: BlockPosition,
+) -> Vec<BlockPosition> {
+    if !is_structure_enabled(StructureType::Mineshaft, version) || max.x < min.x || max.z < min.z {
+        return Vec::new();
+    }
+
+    // Despite their names in the header, the size arguments of getMineshafts
+    // are the inclusive end of the area
+    let count_mineshafts = |out: *mut cubiomes_sys::Pos, capacity: i32| {
+        // SAFETY: ffi function is called correctly, and writes at most
+        // `capacity` positions to `out`, which may be null
+        unsafe {
+            cubiomes_sys::getMineshafts(
+                version as i32,
+                world_seed.cast_unsigned(),
+                min.x,
+                min.z,
+                max.x,
+                max.z,
+                out,
+                capacity,
+            )
+        }
+    };
+
+    let count = count_mineshafts(std::ptr::null_mut(), 0);
+    let mut positions = vec![cubiomes_sys::Pos { x: 0, z: 0 }; count.unsigned_abs() as usize];
+    count_mineshafts(positions.as_mut_ptr(), count);
+
+    positions.into_iter().map(BlockPosition::from).collect()
+}
+
+/// Checks if a chunk starts a mineshaft
+///
+/// The chunk is given in chunk coordinates. Returns `false` if the version
+/// has no mineshafts.
+#[must_use]
+pub fn is_mineshaft_chunk(version: MCVersion, world_seed: i64, chunk_x: i32, chunk_z: i32) -> bool {
+    let chunk = BlockPosition::new(chunk_x, chunk_z);
+
+    !mineshaft_chunks(version, world_seed, chunk, chunk).is_empty()
+}
+
+impl Generator {
+    /// Finds every chunk starting a mineshaft within a radius around a
+    /// position
+    ///
+    /// The hits are the start of the chunks, sorted by their distance from
+    /// `origin` with the closest mineshaft first. Mineshafts spread far from
+    /// their chunk, so an overlay should draw them larger than the chunk.
+    /// Returns an empty [`Vec`] if the version has no mineshafts.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let mineshafts = generator.mineshafts_within(BlockPosition::new(0, 0), 500);
+    ///
+    /// assert_eq!(mineshafts.len(), 8);
+    /// assert_eq!(mineshafts[0].pos, BlockPosition::new(-176, -192));
+    /// ```
+    #[must_use]
+    pub fn mineshafts_within(&self, origin: BlockPosition, radius: u32) -> Vec<StructureHit> {
+        let radius_blocks = i32::try_from(radius).unwrap_or(i32::MAX);
+        let min = BlockPosition::new(
+            block_to_chunk(origin.x.saturating_sub(radius_blocks)),
+            block_to_chunk(origin.z.saturating_sub(radius_blocks)),
+        );
+        let max = BlockPosition::new(
+            block_to_chunk(origin.x.saturating_add(radius_blocks)),
+            block_to_chunk(origin.z.saturating_add(radius_blocks)),
+        );
+
+        let mut hits: Vec<StructureHit> =
+            mineshaft_chunks(self.minecraft_version(), self.seed(), min, max)
+                .into_iter()
+                .map(|pos| StructureHit::new(pos, StructureType::Mineshaft, origin))
+                .filter(|hit| hit.distance <= f64::from(radius))
+                .collect();
+
+        sort_hits(&mut hits);
+        hits
+    }
+}
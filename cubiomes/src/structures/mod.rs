@@ -6,26 +6,70 @@
 //! Notably stronghold generation follows an iterative method instead. For
 //! generating positions of strongholds, see [`strongholds::StrongholdIter`] and
 //! [`crate::generator::Generator::strongholds()`].
+//!
+//! For simply finding the closest structure of a type around a position, see
+//! [`crate::generator::Generator::nearest_structure()`].
+//!
+//! Servers with customized structure spacing are supported with a
+//! [`config::StructureConfig`] override, see [`StructureRegion::with_config()`].
 
-
-use crate::generator::{BlockPosition, Generator};
-use std::mem::{transmute, MaybeUninit};
+use crate::generator::coords::{block_to_chunk, block_to_region};
+use crate::generator::{error::GeneratorError, BlockPosition, Cache, Generator, Range, Scale};
+use crate::rng::JavaRandom;
 use bitflags::bitflags;
+use config::StructureConfig;
 use cubiomes_sys::enums::{self};
-use thiserror::Error;
+use cubiomes_sys::num_traits::FromPrimitive;
 use enums::StructureType;
+use std::mem::MaybeUninit;
+use thiserror::Error;
+
+/// The y at which cubiomes samples the biome of features since 1.16
+const FEATURE_BIOME_Y: i32 = 319 >> 2;
 
+pub mod ancient_city;
+pub mod bounds;
+pub mod config;
+pub mod farms;
+pub mod finder;
+pub mod geodes;
+pub mod igloo;
+pub mod mansion;
+pub mod mineshaft;
+pub mod nether;
+pub mod ocean_ruin;
+pub mod outpost;
+pub mod shipwreck;
+pub mod slime;
+pub mod spawn;
 pub mod strongholds;
 #[cfg(test)]
 mod test;
+pub mod treasure;
+pub mod variant;
 
 /// Reperesents an error in cubiomes
 #[derive(Error, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 pub enum StructureGenerationError {
-    /// Cubiomes did not return 0 or 1. Encountering this error is most likely a
-    /// bug. Please report it on github
-    #[error("Underlying library cubiomes returned a bool that is not 0 or 1.")]
+    /// Cubiomes returned an unexpected result, like a negative value for a
+    /// check. Encountering this error is most likely a bug, unless it's
+    /// documented for the function. Please report it on github
+    #[error("Underlying library cubiomes returned an unexpected result.")]
     CubiomesError,
+    /// The separation of a custom [`StructureConfig`] is negative or not
+    /// smaller than its spacing
+    #[error(
+        "The separation of a structure config must be at least 0 and smaller than the spacing."
+    )]
+    InvalidConfig,
+    /// Custom [`StructureConfig`]s aren't supported for the structure type
+    #[error("Custom structure configs are not supported for {0:?}.")]
+    UnsupportedConfig(StructureType),
+    /// The generator is for a dimension the operation doesn't support
+    ///
+    /// The dimension of the generator is given as a parameter.
+    #[error("The operation isn't supported in {0:?}.")]
+    UnsupportedDimension(enums::Dimension),
 }
 
 // This is empty, since I dont know what flags cubiomes supports
@@ -36,14 +80,15 @@ impl Generator {
     /// Tries to get the [`BlockPosition`] of a structure inside of a
     /// [`StructureRegion`] with this generator.
     ///
-    /// # Panics
-    /// The function panics if the version of the structure region does not
-    /// match the generator
+    /// Returns [`None`] if the version of the structure region doesn't match
+    /// the generator.
     pub fn try_generate_structure_in_region(
         &mut self,
         region_pos: StructureRegion,
     ) -> Option<BlockPosition> {
-        assert_eq!(self.minecraft_version(), region_pos.minecraft_version);
+        if self.minecraft_version() != region_pos.minecraft_version {
+            return None;
+        }
 
         let pos = self.get_structure_generation_attempt(region_pos)?;
 
@@ -66,6 +111,12 @@ impl Generator {
         pos: BlockPosition,
         structure_type: StructureType,
     ) -> Result<bool, StructureGenerationError> {
+        // In the nether cubiomes checks the biome of every structure, and
+        // exits the process for the structures it has no biome check for
+        if self.dimension() == enums::Dimension::DIM_NETHER && !has_biome_check(structure_type) {
+            return Ok(false);
+        }
+
         // SAFETY: The foreign function is being called properly
         match unsafe {
             cubiomes_sys::isViableStructurePos(
@@ -77,11 +128,39 @@ impl Generator {
             )
         } {
             0 => Ok(false),
-            1 => Ok(true),
+            // Villages return the biome which made the position viable
+            1.. => Ok(true),
             _ => Err(StructureGenerationError::CubiomesError),
         }
     }
 
+    /// Rules out structure generation attempts on too low terrain since 1.18
+    ///
+    /// Since 1.18 desert pyramids, jungle temples and woodland mansions need
+    /// the surface at the corners of the structure to be high enough.
+    /// Cubiomes approximates the surface with the depth of the climate, so this
+    /// only rules out attempts which are unlikely to generate. Returns `true`
+    /// for every other structure and before 1.18.
+    ///
+    /// This is meant for the overworld and is a separate check from
+    /// [`Self::verify_structure_generation_attempt()`].
+    pub fn verify_structure_terrain(
+        &mut self,
+        pos: BlockPosition,
+        structure_type: StructureType,
+    ) -> bool {
+        // SAFETY: The foreign function is being called properly, and restores
+        // the climate parameter of the generator it changes
+        unsafe {
+            cubiomes_sys::isViableStructureTerrain(
+                structure_type as i32,
+                self.as_mut_ptr(),
+                pos.x,
+                pos.z,
+            ) != 0
+        }
+    }
+
     fn get_structure_generation_attempt(
         &self,
         region_pos: StructureRegion,
@@ -89,6 +168,66 @@ impl Generator {
         let seed = self.seed();
         region_pos.get_structure_generation_attempt(seed)
     }
+
+    /// Gets the biome of a feature like a shipwreck, where cubiomes checks it
+    ///
+    /// Up to 1.15 the biome is at block 9, 9 of the chunk, and since then in
+    /// the middle of the chunk at 1:4 scale. Unlike the viability check of
+    /// cubiomes the biome is fully generated, so ocean temperatures are
+    /// included.
+    fn feature_biome(&self, pos: BlockPosition) -> Result<enums::BiomeID, GeneratorError> {
+        let (chunk_x, chunk_z) = (block_to_chunk(pos.x), block_to_chunk(pos.z));
+
+        if self.minecraft_version() <= enums::MCVersion::MC_1_15_2 {
+            return self.get_biome_at((chunk_x * 16 + 9, 0, chunk_z * 16 + 9));
+        }
+
+        let mut cache = Cache::new(
+            self,
+            Range {
+                scale: Scale::Quad,
+                x: chunk_x * 4 + 2,
+                z: chunk_z * 4 + 2,
+                size_x: 1,
+                size_z: 1,
+                y: FEATURE_BIOME_Y,
+                size_y: 0,
+            },
+        );
+        cache.fill_cache()?;
+
+        cache.biome_at(0, 0, 0)
+    }
+}
+
+/// The rotation of a structure template, clockwise when viewed from above
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rotation {
+    /// The template is not rotated
+    None,
+    /// The template is rotated by 90 degrees
+    Clockwise90,
+    /// The template is rotated by 180 degrees
+    Clockwise180,
+    /// The template is rotated by 270 degrees
+    CounterClockwise90,
+}
+
+impl Rotation {
+    /// Picks a rotation like `Rotation.getRandom` of minecraft
+    fn random(rng: &mut JavaRandom) -> Self {
+        Self::from_index(rng.next_int(4))
+    }
+
+    /// Gets the rotation at an index of `Rotation.values()` of minecraft
+    fn from_index(index: i32) -> Self {
+        match index {
+            0 => Self::None,
+            1 => Self::Clockwise90,
+            2 => Self::Clockwise180,
+            _ => Self::CounterClockwise90,
+        }
+    }
 }
 
 /// Represents a region for generating a specific structure in a specific
@@ -151,7 +290,8 @@ pub struct StructureRegion {
     /// The scale can be acquired with [`Self::region_size_blocks()`] or
     /// [`Self::region_size_chunks()`]
     pub z: i32,
-    region_size: i8,
+    region_size: i32,
+    config: Option<StructureConfig>,
     pub(crate) minecraft_version: enums::MCVersion,
     pub(crate) structure_type: enums::StructureType,
 }
@@ -173,6 +313,7 @@ impl StructureRegion {
             x: region_x,
             z: region_z,
             region_size: region_scale,
+            config: None,
             minecraft_version,
             structure_type,
         })
@@ -190,22 +331,58 @@ impl StructureRegion {
         let region_scale = get_structure_scale(structure_type, minecraft_version)?;
 
         // Multiply the scale by 16 since structure positions are in chunk size for some reason
-        let (x, z) = pos.scale_by_num((region_scale as i32) * 16);
+        let (x, z) = pos.scale_by_num(region_scale * 16);
 
         Ok(Self {
             x,
             z,
             region_size: region_scale,
+            config: None,
             minecraft_version,
             structure_type,
         })
     }
 
+    /// Overrides the vanilla [`StructureConfig`] of [self]
+    ///
+    /// The region keeps its coordinates, which are now in the scale of the
+    /// spacing of the config. Generation attempts are then placed like
+    /// minecraft does with the config, for example on servers with
+    /// customized structure spacing.
+    ///
+    /// # Errors
+    /// Returns [`StructureGenerationError::InvalidConfig`] if the separation of
+    /// the config is negative or not smaller than the spacing, and
+    /// [`StructureGenerationError::UnsupportedConfig`] if custom configs aren't
+    /// supported for the structure type, like for mineshafts or bastions
+    pub fn with_config(
+        mut self,
+        config: StructureConfig,
+    ) -> Result<Self, StructureGenerationError> {
+        config.validate(self.structure_type)?;
+
+        self.region_size = config.spacing;
+        self.config = Some(config);
+
+        Ok(self)
+    }
+
+    /// Gets the custom [`StructureConfig`] of [self], if it has one
+    #[inline]
+    #[must_use]
+    pub const fn config(&self) -> Option<StructureConfig> {
+        self.config
+    }
+
     /// Tries to get the [`BlockPosition`] of a generation attempt for self
     ///
     /// Check [self] for what a generation attempt means
     #[must_use]
     pub fn get_structure_generation_attempt(&self, seed: i64) -> Option<BlockPosition> {
+        if let Some(config) = self.config {
+            return config.attempt(self.structure_type, seed, self.x, self.z);
+        }
+
         let minecraft_version = self.minecraft_version;
 
         let mut pos: MaybeUninit<cubiomes_sys::Pos> = MaybeUninit::uninit();
@@ -213,13 +390,13 @@ impl StructureRegion {
         // SAFETY:
         // The ffi function receives correct input data
         //
-        // The seed is transmuted as cubiomes wants it as u64
+        // The seed is cast to unsigned as cubiomes wants it as u64
         // even though minecraft uses signed integers
         if unsafe {
             cubiomes_sys::getStructurePos(
                 self.structure_type as i32,
                 minecraft_version as i32,
-                transmute::<i64, u64>(seed),
+                seed.cast_unsigned(),
                 self.x,
                 self.z,
                 pos.as_mut_ptr(),
@@ -237,21 +414,24 @@ impl StructureRegion {
 
     /// Moves [self] to the region of the given [`BlockPosition`]
     pub fn set_new_minecraft_pos(&mut self, pos: BlockPosition) {
-        (self.x, self.z) = pos.scale_by_num(self.region_size as i32);
+        (self.x, self.z) = (
+            block_to_region(pos.x, self.region_size),
+            block_to_region(pos.z, self.region_size),
+        );
     }
 
     /// Gets the region sife of [self] in chunks
     #[inline]
     #[must_use]
     pub const fn region_size_chunks(&self) -> i32 {
-        self.region_size as i32
+        self.region_size
     }
 
     /// Gets the region size of [self] in blocks
     #[inline]
     #[must_use]
     pub const fn region_size_blocks(&self) -> i32 {
-        (self.region_size as i32) * 16
+        self.region_size * 16
     }
 
     /// Gets the minecraft version of [self]
@@ -269,21 +449,190 @@ impl StructureRegion {
     }
 }
 
-fn get_structure_scale(
-    structure_type: enums::StructureType,
+/// Moves the structures of a 48-bit base seed by a number of regions
+///
+/// Structure attempts only depend on the lower 48 bits of the seed and the
+/// region, so a constellation of structures found for one base seed, like a
+/// quad witch hut, is found at every other region for the base seed this
+/// returns. The structure attempt of region `(x, z)` with the original base is
+/// the attempt of region `(x + region_x, z + region_z)` with the moved base,
+/// at the same position inside the region. Structures with another chance
+/// check at their chunk, like outposts, can still fail the check after
+/// moving.
+///
+/// The returned base only has the lower 48 bits set. This is a port of
+/// `moveStructure` from cubiomes, which is an inline function and thus not
+/// available in the bindings.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{MCVersion, StructureType};
+/// use cubiomes::structures::{move_structure, StructureRegion};
+///
+/// let base = -5134222539607995087 & 0xffff_ffff_ffff;
+/// let moved = move_structure(base, 2, -3);
+///
+/// let region = StructureRegion::new(0, 0, MCVersion::MC_1_20_6, StructureType::Swamp_Hut)
+///     .expect("swamp huts are generated in 1.20");
+/// let moved_region = StructureRegion::new(2, -3, MCVersion::MC_1_20_6, StructureType::Swamp_Hut)
+///     .expect("swamp huts are generated in 1.20");
+///
+/// let attempt = region
+///     .get_structure_generation_attempt(base)
+///     .expect("swamp huts have an attempt in every region");
+/// let moved_attempt = moved_region
+///     .get_structure_generation_attempt(moved)
+///     .expect("swamp huts have an attempt in every region");
+///
+/// assert_eq!(moved_attempt.x - attempt.x, 2 * region.region_size_blocks());
+/// assert_eq!(moved_attempt.z - attempt.z, -3 * region.region_size_blocks());
+/// ```
+#[must_use]
+pub fn move_structure(base_seed: i64, region_x: i32, region_z: i32) -> i64 {
+    base_seed
+        .wrapping_sub(i64::from(region_x).wrapping_mul(341873128712))
+        .wrapping_sub(i64::from(region_z).wrapping_mul(132897987541))
+        & 0xffff_ffff_ffff
+}
+
+/// Checks if a structure generates in a version
+///
+/// Structures added in later versions, like trail ruins in 1.20, aren't
+/// enabled in earlier versions, so searching for them finds nothing.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{MCVersion, StructureType};
+/// use cubiomes::structures::is_structure_enabled;
+///
+/// assert!(is_structure_enabled(StructureType::Trail_Ruins, MCVersion::MC_1_20_6));
+/// assert!(!is_structure_enabled(StructureType::Trail_Ruins, MCVersion::MC_1_19_4));
+/// ```
+#[must_use]
+pub fn is_structure_enabled(
+    structure_type: StructureType,
+    minecraft_version: enums::MCVersion,
+) -> bool {
+    structure_config(structure_type, minecraft_version).is_some()
+}
+
+/// Gets every structure which generates in a version and dimension
+///
+/// The structures are ordered like [`StructureType`]. Every structure in the
+/// list can be searched for with the structure searches, like
+/// [`Generator::nearest_structure()`].
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+/// use cubiomes::structures::enabled_structures;
+///
+/// let structures = enabled_structures(MCVersion::MC_1_20_6, Dimension::DIM_OVERWORLD);
+/// assert!(structures.contains(&StructureType::Trail_Ruins));
+/// assert!(!structures.contains(&StructureType::Fortress));
+/// ```
+#[must_use]
+pub fn enabled_structures(
     minecraft_version: enums::MCVersion,
-) -> Result<i8, StructureGenerationError> {
+    dimension: enums::Dimension,
+) -> Vec<StructureType> {
+    (0..StructureType::FEATURE_NUM as i32)
+        .filter_map(StructureType::from_i32)
+        .filter(|&structure_type| {
+            structure_config(structure_type, minecraft_version)
+                .is_some_and(|sconf| i32::from(sconf.dim) == dimension as i32)
+        })
+        .collect()
+}
+
+/// Checks if a structure can generate in a biome in a version
+///
+/// This is the biome check done by
+/// [`Generator::verify_structure_generation_attempt()`], so a generation
+/// attempt in a biome failing this check doesn't generate the structure.
+/// Some structures also check the terrain or other biomes around them.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, MCVersion, StructureType};
+/// use cubiomes::structures::is_viable_structure_biome;
+///
+/// let version = MCVersion::MC_1_20_6;
+/// assert!(is_viable_structure_biome(StructureType::Trail_Ruins, version, BiomeID::jungle));
+/// assert!(!is_viable_structure_biome(StructureType::Trail_Ruins, version, BiomeID::plains));
+/// ```
+#[must_use]
+pub fn is_viable_structure_biome(
+    structure_type: StructureType,
+    minecraft_version: enums::MCVersion,
+    biome: enums::BiomeID,
+) -> bool {
+    if !has_biome_check(structure_type) {
+        return false;
+    }
+
+    // SAFETY: ffi function is called correctly, it only compares the ids
+    unsafe {
+        cubiomes_sys::isViableFeatureBiome(
+            minecraft_version as i32,
+            structure_type as i32,
+            biome as i32,
+        ) != 0
+    }
+}
+
+/// Gets every biome a structure can generate in for a version
+///
+/// The biomes are ordered by their id. See [`is_viable_structure_biome()`].
+#[must_use]
+pub fn viable_structure_biomes(
+    structure_type: StructureType,
+    minecraft_version: enums::MCVersion,
+) -> Vec<enums::BiomeID> {
+    (0..256)
+        .filter_map(enums::BiomeID::from_id)
+        .filter(|&biome| is_viable_structure_biome(structure_type, minecraft_version, biome))
+        .collect()
+}
+
+/// Checks if cubiomes knows the biomes a structure generates in
+///
+/// `isViableFeatureBiome` exits the process for the other structures.
+fn has_biome_check(structure_type: StructureType) -> bool {
+    !matches!(
+        structure_type,
+        StructureType::Feature
+            | StructureType::Geode
+            | StructureType::End_Island
+            | StructureType::FEATURE_NUM
+    )
+}
+
+/// Gets the cubiomes config of a structure, if it generates in the version
+fn structure_config(
+    structure_type: StructureType,
+    minecraft_version: enums::MCVersion,
+) -> Option<cubiomes_sys::StructureConfig> {
     let mut sconf: MaybeUninit<cubiomes_sys::StructureConfig> = MaybeUninit::uninit();
 
-    // SAFETY: sconf is initialized if GetStructureConfig did not return 0
+    // SAFETY: sconf is initialized if getStructureConfig did not return 0
     unsafe {
         match cubiomes_sys::getStructureConfig(
             structure_type as i32,
             minecraft_version as i32,
             sconf.as_mut_ptr(),
         ) {
-            0 => Err(StructureGenerationError::CubiomesError),
-            _ => Ok(sconf.assume_init().regionSize),
+            0 => None,
+            _ => Some(sconf.assume_init()),
         }
     }
 }
+
+fn get_structure_scale(
+    structure_type: enums::StructureType,
+    minecraft_version: enums::MCVersion,
+) -> Result<i32, StructureGenerationError> {
+    structure_config(structure_type, minecraft_version)
+        .map(|sconf| i32::from(sconf.regionSize))
+        .ok_or(StructureGenerationError::CubiomesError)
+}
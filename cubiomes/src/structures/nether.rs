@@ -0,0 +1,154 @@
+//! Module for telling apart the fortresses and bastions of the nether
+//!
+//! Since 1.16 fortresses and bastions share the same regions, and each region
+//! has at most one of them. See [`Generator::nether_structure()`].
+
+use super::{StructureGenerationError, StructureRegion};
+use crate::enums::{Dimension, MCVersion, StructureType};
+use crate::generator::{BlockPosition, Generator};
+use std::mem::MaybeUninit;
+
+/// The type of a bastion remnant, given by its starting piece
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BastionType {
+    /// The housing units bastion
+    HousingUnits,
+    /// The hoglin stables bastion
+    HoglinStables,
+    /// The treasure room bastion
+    Treasure,
+    /// The bridge bastion
+    Bridge,
+}
+
+/// The structure generated in a nether region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NetherStructureKind {
+    /// A nether fortress
+    Fortress,
+    /// A bastion remnant of the given type
+    Bastion(BastionType),
+}
+
+/// A structure found by [`Generator::nether_structure()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NetherStructure {
+    /// The position of the structure
+    pub pos: BlockPosition,
+    /// Whether the structure is a fortress or a bastion
+    pub kind: NetherStructureKind,
+}
+
+impl Generator {
+    /// Gets the fortress or bastion generated in a nether region
+    ///
+    /// The region is in the scale of fortress regions, see
+    /// [`StructureRegion::from_block_position()`] for converting a position to
+    /// one. Since 1.16 bastions share these regions, and a region generates
+    /// a bastion if the attempt succeeds and its biome allows it. Otherwise
+    /// the region may generate a fortress. Before 1.16 only fortresses exist.
+    ///
+    /// Returns [`None`] if the region generates neither.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    /// use cubiomes::structures::nether::NetherStructureKind;
+    /// use cubiomes::structures::StructureRegion;
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_NETHER,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let region = StructureRegion::from_block_position(
+    ///     BlockPosition::new(0, 0),
+    ///     MCVersion::MC_1_20_6,
+    ///     StructureType::Fortress,
+    /// )
+    /// .expect("fortresses exist in 1.20");
+    ///
+    /// let structure = generator
+    ///     .nether_structure(region.x, region.z)
+    ///     .expect("failed to check the region")
+    ///     .expect("a structure generates in this region");
+    ///
+    /// assert_eq!(structure.kind, NetherStructureKind::Fortress);
+    /// assert_eq!(structure.pos, BlockPosition::new(288, 272));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`StructureGenerationError::UnsupportedDimension`] if the
+    /// generator isn't for the nether, and an error if the version has no
+    /// fortresses or cubiomes fails to check the biome of the structure
+    pub fn nether_structure(
+        &mut self,
+        region_x: i32,
+        region_z: i32,
+    ) -> Result<Option<NetherStructure>, StructureGenerationError> {
+        if self.dimension() != Dimension::DIM_NETHER {
+            return Err(StructureGenerationError::UnsupportedDimension(
+                self.dimension(),
+            ));
+        }
+
+        let version = self.minecraft_version();
+        let fortress = StructureRegion::new(region_x, region_z, version, StructureType::Fortress)?;
+
+        if version >= MCVersion::MC_1_16_1 {
+            let bastion =
+                StructureRegion::new(region_x, region_z, version, StructureType::Bastion)?;
+
+            if let Some(pos) = bastion.get_structure_generation_attempt(self.seed()) {
+                if self.verify_structure_generation_attempt(pos, StructureType::Bastion)? {
+                    return Ok(Some(NetherStructure {
+                        pos,
+                        kind: NetherStructureKind::Bastion(self.bastion_type(pos)),
+                    }));
+                }
+            }
+        }
+
+        // Since 1.18 fortresses generate wherever bastions don't, which the
+        // biome check of cubiomes takes care of
+        if let Some(pos) = fortress.get_structure_generation_attempt(self.seed()) {
+            if self.verify_structure_generation_attempt(pos, StructureType::Fortress)? {
+                return Ok(Some(NetherStructure {
+                    pos,
+                    kind: NetherStructureKind::Fortress,
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn bastion_type(&self, pos: BlockPosition) -> BastionType {
+        let mut variant: MaybeUninit<cubiomes_sys::StructureVariant> = MaybeUninit::uninit();
+
+        // SAFETY: ffi function is called correctly, and getVariant clears the
+        // variant before filling it in
+        let variant = unsafe {
+            cubiomes_sys::getVariant(
+                variant.as_mut_ptr(),
+                StructureType::Bastion as i32,
+                self.minecraft_version() as i32,
+                self.seed().cast_unsigned(),
+                pos.x,
+                pos.z,
+                -1,
+            );
+            variant.assume_init()
+        };
+
+        match variant.start {
+            0 => BastionType::HousingUnits,
+            1 => BastionType::HoglinStables,
+            2 => BastionType::Treasure,
+            _ => BastionType::Bridge,
+        }
+    }
+}
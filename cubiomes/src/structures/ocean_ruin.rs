@@ -0,0 +1,193 @@
+//! Module containing the variants of ocean ruins
+//!
+//! Ocean ruins are made of stone bricks in cold oceans and of sandstone in
+//! warm oceans. Each ruin is either small or large, and large ruins are
+//! usually surrounded by a cluster of small ruins, see [`ocean_ruin_variant()`].
+
+use super::Rotation;
+use crate::enums::{BiomeID, StructureType};
+use crate::generator::coords::block_to_chunk;
+use crate::generator::{error::GeneratorError, BlockPosition, Generator};
+use crate::rng::chunk_generate_random;
+
+/// The chance of a ruin to be large
+const LARGE_PROBABILITY: f32 = 0.3;
+
+/// The chance of a large ruin to be surrounded by small ruins
+const CLUSTER_PROBABILITY: f32 = 0.9;
+
+/// The kind of ocean an ocean ruin is in, which decides its blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OceanRuinTemperature {
+    /// A stone brick ruin of frozen, cold and normal oceans
+    Cold,
+    /// A sandstone ruin of lukewarm and warm oceans
+    Warm,
+}
+
+impl OceanRuinTemperature {
+    /// Gets the kind of ruin generated in a biome
+    ///
+    /// Returns [`None`] if the biome isn't an ocean, as ocean ruins only
+    /// generate in oceans.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::BiomeID;
+    /// use cubiomes::structures::ocean_ruin::OceanRuinTemperature;
+    ///
+    /// assert_eq!(
+    ///     OceanRuinTemperature::of(BiomeID::deep_lukewarm_ocean),
+    ///     Some(OceanRuinTemperature::Warm)
+    /// );
+    /// assert_eq!(OceanRuinTemperature::of(BiomeID::plains), None);
+    /// ```
+    #[must_use]
+    pub fn of(biome: BiomeID) -> Option<Self> {
+        match biome {
+            BiomeID::warm_ocean
+            | BiomeID::lukewarm_ocean
+            | BiomeID::deep_warm_ocean
+            | BiomeID::deep_lukewarm_ocean => Some(Self::Warm),
+            BiomeID::ocean
+            | BiomeID::frozen_ocean
+            | BiomeID::cold_ocean
+            | BiomeID::deep_ocean
+            | BiomeID::deep_cold_ocean
+            | BiomeID::deep_frozen_ocean => Some(Self::Cold),
+            _ => None,
+        }
+    }
+}
+
+/// The size and rotation of an ocean ruin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct OceanRuinVariant {
+    /// The kind of ocean the ruin is in
+    pub temperature: OceanRuinTemperature,
+    /// The rotation of the main ruin
+    pub rotation: Rotation,
+    /// Whether the main ruin is large instead of small
+    pub large: bool,
+    /// Whether small ruins are placed around the main ruin, which is only
+    /// done for large ruins
+    pub cluster: bool,
+}
+
+/// A verified ocean ruin found by a search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OceanRuin {
+    /// The position of the generation attempt of the ruin, at the start of
+    /// its chunk
+    pub pos: BlockPosition,
+    /// The distance in blocks from the origin of the search to [`Self::pos`]
+    pub distance: f64,
+    /// The size and rotation of the ruin
+    pub variant: OceanRuinVariant,
+}
+
+/// Gets the variant of an ocean ruin
+///
+/// `pos` is the generation attempt of the ruin, like from
+/// [`super::StructureRegion::get_structure_generation_attempt()`]. The
+/// temperature depends on the biome of the ruin, which
+/// [`Generator::ocean_ruin_variant_at()`] looks up. The size and rotation are
+/// picked the same way in every version with ocean ruins.
+///
+/// This doesn't check if the ruin generates, see
+/// [`Generator::verify_structure_generation_attempt()`] for that.
+///
+/// # Examples
+/// ```
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::structures::ocean_ruin::{ocean_ruin_variant, OceanRuinTemperature};
+///
+/// let variant = ocean_ruin_variant(
+///     -5134222539607995087,
+///     BlockPosition::new(64, 128),
+///     OceanRuinTemperature::Cold,
+/// );
+/// assert!(variant.large || !variant.cluster);
+/// ```
+#[must_use]
+pub fn ocean_ruin_variant(
+    seed: i64,
+    pos: BlockPosition,
+    temperature: OceanRuinTemperature,
+) -> OceanRuinVariant {
+    let mut rng = chunk_generate_random(seed, block_to_chunk(pos.x), block_to_chunk(pos.z));
+
+    let rotation = Rotation::random(&mut rng);
+    let large = rng.next_float() <= LARGE_PROBABILITY;
+    let cluster = large && rng.next_float() <= CLUSTER_PROBABILITY;
+
+    OceanRuinVariant {
+        temperature,
+        rotation,
+        large,
+        cluster,
+    }
+}
+
+impl Generator {
+    /// Gets the variant of an ocean ruin, looking up the temperature of its
+    /// ocean
+    ///
+    /// The biome is sampled where cubiomes checks the biome of ocean ruins.
+    /// See [`ocean_ruin_variant()`].
+    ///
+    /// Returns [`None`] if the biome of the ruin isn't an ocean, in which case
+    /// the ruin doesn't generate.
+    ///
+    /// # Errors
+    /// Returns an error if generating the biome of the ruin fails
+    pub fn ocean_ruin_variant_at(
+        &self,
+        pos: BlockPosition,
+    ) -> Result<Option<OceanRuinVariant>, GeneratorError> {
+        let temperature = OceanRuinTemperature::of(self.feature_biome(pos)?);
+
+        Ok(temperature.map(|temperature| ocean_ruin_variant(self.seed(), pos, temperature)))
+    }
+
+    /// Finds every verified ocean ruin within a radius around a position
+    ///
+    /// This is [`Self::structures_within()`] for ocean ruins, with the variant
+    /// of each ruin. The ruins are sorted with the closest one first. Returns
+    /// an empty [`Vec`] before 1.13, which has no ocean ruins.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    /// use cubiomes::structures::ocean_ruin::OceanRuinTemperature;
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let large_warm_ruins = generator
+    ///     .ocean_ruins_within(BlockPosition::new(0, 0), 2000)
+    ///     .into_iter()
+    ///     .filter(|ruin| ruin.variant.large)
+    ///     .filter(|ruin| ruin.variant.temperature == OceanRuinTemperature::Warm)
+    ///     .count();
+    ///
+    /// assert_eq!(large_warm_ruins, 1);
+    /// ```
+    pub fn ocean_ruins_within(&mut self, origin: BlockPosition, radius: u32) -> Vec<OceanRuin> {
+        self.structures_within(StructureType::Ocean_Ruin, origin, radius)
+            .into_iter()
+            .filter_map(|hit| {
+                Some(OceanRuin {
+                    pos: hit.pos,
+                    distance: hit.distance,
+                    variant: self.ocean_ruin_variant_at(hit.pos).ok()??,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,81 @@
+//! Module containing searches for pillager outposts
+//!
+//! Outposts are spread over regions like villages, but only one in five
+//! region attempts passes the extra chance check, see [`passes_outpost_chance()`].
+//! The attempts passing it still fail if a village generates within 10
+//! chunks, which cubiomes checks with the biome when verifying the attempt.
+
+use super::finder::StructureHit;
+use crate::enums::StructureType;
+use crate::generator::{BlockPosition, Generator};
+use crate::rng::JavaRandom;
+
+/// Checks if the chunk of an outpost attempt passes the extra chance check
+///
+/// Cubiomes already does this check when getting the attempt of an outpost
+/// region, so it is only needed for attempts placed without cubiomes. The
+/// chunk is given in chunk coordinates.
+///
+/// The check is seeded with the chunk divided by 16, so every outpost attempt
+/// in an area of 16 by 16 chunks passes or fails together.
+///
+/// # Examples
+/// ```
+/// use cubiomes::structures::outpost::passes_outpost_chance;
+///
+/// let seed = -5134222539607995087;
+///
+/// assert_eq!(passes_outpost_chance(seed, 3, 5), passes_outpost_chance(seed, 12, 0));
+/// ```
+#[must_use]
+pub fn passes_outpost_chance(world_seed: i64, chunk_x: i32, chunk_z: i32) -> bool {
+    let mut rng =
+        JavaRandom::new(world_seed ^ i64::from(chunk_x >> 4) ^ (i64::from(chunk_z >> 4) << 4));
+    rng.next(31);
+
+    rng.next_int(5) == 0
+}
+
+impl Generator {
+    /// Finds the closest verified pillager outpost around a position
+    ///
+    /// This is [`Self::nearest_structure()`] for outposts, so the attempts
+    /// pass the chance check, their biome and the distance to villages.
+    /// Returns [`None`] if no outpost was found within `max_radius` blocks, or
+    /// before 1.14, which has no outposts.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let outpost = generator
+    ///     .nearest_outpost(BlockPosition::new(0, 0), 3000)
+    ///     .expect("there is an outpost nearby");
+    ///
+    /// assert_eq!(outpost.pos, BlockPosition::new(816, -784));
+    /// ```
+    pub fn nearest_outpost(
+        &mut self,
+        origin: BlockPosition,
+        max_radius: u32,
+    ) -> Option<StructureHit> {
+        self.nearest_structure(StructureType::Outpost, origin, max_radius)
+    }
+
+    /// Finds every verified pillager outpost within a radius around a
+    /// position
+    ///
+    /// This is [`Self::structures_within()`] for outposts, sorted with the
+    /// closest outpost first.
+    pub fn outposts_within(&mut self, origin: BlockPosition, radius: u32) -> Vec<StructureHit> {
+        self.structures_within(StructureType::Outpost, origin, radius)
+    }
+}
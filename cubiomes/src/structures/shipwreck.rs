@@ -0,0 +1,253 @@
+//! Module containing the variants of shipwrecks
+//!
+//! Every shipwreck is one of a few templates, which decide if the ship is
+//! whole or only its front or back half, and how it is lying. Beached ships
+//! pick from fewer templates than ships in oceans, as beached ships are never
+//! upside down. Cubiomes doesn't provide the variants of shipwrecks, so they
+//! are ported from minecraft, see [`shipwreck_variant()`].
+
+use super::Rotation;
+use crate::enums::{BiomeID, StructureType};
+use crate::generator::coords::block_to_chunk;
+use crate::generator::{error::GeneratorError, BlockPosition, Generator};
+use crate::rng::chunk_generate_random;
+
+/// How a shipwreck is lying, given by its template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ShipwreckShape {
+    /// An upright ship with its mast, which is always whole
+    WithMast,
+    /// A ship lying upside down, only generated in oceans
+    UpsideDown,
+    /// A ship lying on its side
+    Sideways,
+    /// An upright ship without its mast
+    RightsideUp,
+}
+
+/// The half of a ship generated by a shipwreck
+///
+/// The supply chest is in the front of a ship, and the map and treasure
+/// chests are in its back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ShipwreckPart {
+    /// The whole ship
+    Full,
+    /// Only the front half of the ship
+    FrontHalf,
+    /// Only the back half of the ship
+    BackHalf,
+}
+
+/// The templates of beached shipwrecks, in the order minecraft picks from
+const BEACHED_TEMPLATES: [(ShipwreckShape, ShipwreckPart, bool); 11] = [
+    (ShipwreckShape::WithMast, ShipwreckPart::Full, false),
+    (ShipwreckShape::Sideways, ShipwreckPart::Full, false),
+    (ShipwreckShape::Sideways, ShipwreckPart::FrontHalf, false),
+    (ShipwreckShape::Sideways, ShipwreckPart::BackHalf, false),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::Full, false),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::FrontHalf, false),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::BackHalf, false),
+    (ShipwreckShape::WithMast, ShipwreckPart::Full, true),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::Full, true),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::FrontHalf, true),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::BackHalf, true),
+];
+
+/// The templates of ocean shipwrecks, in the order minecraft picks from
+const OCEAN_TEMPLATES: [(ShipwreckShape, ShipwreckPart, bool); 20] = [
+    (ShipwreckShape::WithMast, ShipwreckPart::Full, false),
+    (ShipwreckShape::UpsideDown, ShipwreckPart::Full, false),
+    (ShipwreckShape::UpsideDown, ShipwreckPart::FrontHalf, false),
+    (ShipwreckShape::UpsideDown, ShipwreckPart::BackHalf, false),
+    (ShipwreckShape::Sideways, ShipwreckPart::Full, false),
+    (ShipwreckShape::Sideways, ShipwreckPart::FrontHalf, false),
+    (ShipwreckShape::Sideways, ShipwreckPart::BackHalf, false),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::Full, false),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::FrontHalf, false),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::BackHalf, false),
+    (ShipwreckShape::WithMast, ShipwreckPart::Full, true),
+    (ShipwreckShape::UpsideDown, ShipwreckPart::Full, true),
+    (ShipwreckShape::UpsideDown, ShipwreckPart::FrontHalf, true),
+    (ShipwreckShape::UpsideDown, ShipwreckPart::BackHalf, true),
+    (ShipwreckShape::Sideways, ShipwreckPart::Full, true),
+    (ShipwreckShape::Sideways, ShipwreckPart::FrontHalf, true),
+    (ShipwreckShape::Sideways, ShipwreckPart::BackHalf, true),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::Full, true),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::FrontHalf, true),
+    (ShipwreckShape::RightsideUp, ShipwreckPart::BackHalf, true),
+];
+
+/// The template and rotation of a shipwreck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShipwreckVariant {
+    /// Whether the ship is on a beach instead of in an ocean
+    pub beached: bool,
+    /// The rotation of the template
+    pub rotation: Rotation,
+    /// How the ship is lying
+    pub shape: ShipwreckShape,
+    /// The half of the ship which generates
+    pub part: ShipwreckPart,
+    /// Whether the ship is overgrown and missing some of its blocks
+    pub degraded: bool,
+}
+
+impl ShipwreckVariant {
+    /// Gets the name of the template of the shipwreck
+    ///
+    /// This is the name minecraft uses for the template, like
+    /// `shipwreck/sideways_backhalf_degraded`.
+    #[must_use]
+    pub fn template_name(&self) -> String {
+        let shape = match self.shape {
+            ShipwreckShape::WithMast => "with_mast",
+            ShipwreckShape::UpsideDown => "upsidedown",
+            ShipwreckShape::Sideways => "sideways",
+            ShipwreckShape::RightsideUp => "rightsideup",
+        };
+        let part = match (self.shape, self.part) {
+            (ShipwreckShape::WithMast, _) => "",
+            (_, ShipwreckPart::Full) => "_full",
+            (_, ShipwreckPart::FrontHalf) => "_fronthalf",
+            (_, ShipwreckPart::BackHalf) => "_backhalf",
+        };
+        let degraded = if self.degraded { "_degraded" } else { "" };
+
+        format!("shipwreck/{shape}{part}{degraded}")
+    }
+}
+
+/// A verified shipwreck found by a search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shipwreck {
+    /// The position of the generation attempt of the shipwreck, at the start
+    /// of its chunk
+    pub pos: BlockPosition,
+    /// The distance in blocks from the origin of the search to [`Self::pos`]
+    pub distance: f64,
+    /// The template and rotation of the shipwreck
+    pub variant: ShipwreckVariant,
+}
+
+/// Gets the variant of a shipwreck
+///
+/// `pos` is the generation attempt of the shipwreck, like from
+/// [`super::StructureRegion::get_structure_generation_attempt()`]. Whether
+/// the ship is beached depends on its biome, which
+/// [`Generator::shipwreck_variant_at()`] looks up. The rotation and template
+/// are picked the same way in every version with shipwrecks.
+///
+/// This doesn't check if the shipwreck generates, see
+/// [`Generator::verify_structure_generation_attempt()`] for that.
+///
+/// # Examples
+/// ```
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::structures::shipwreck::{shipwreck_variant, ShipwreckShape};
+///
+/// let variant = shipwreck_variant(-5134222539607995087, BlockPosition::new(160, -320), true);
+/// assert_ne!(variant.shape, ShipwreckShape::UpsideDown);
+/// ```
+#[must_use]
+pub fn shipwreck_variant(seed: i64, pos: BlockPosition, beached: bool) -> ShipwreckVariant {
+    let mut rng = chunk_generate_random(seed, block_to_chunk(pos.x), block_to_chunk(pos.z));
+
+    let rotation = Rotation::random(&mut rng);
+
+    let templates: &[_] = if beached {
+        &BEACHED_TEMPLATES
+    } else {
+        &OCEAN_TEMPLATES
+    };
+    let index = rng.next_int(templates.len() as i32);
+    let (shape, part, degraded) = templates[index as usize];
+
+    ShipwreckVariant {
+        beached,
+        rotation,
+        shape,
+        part,
+        degraded,
+    }
+}
+
+impl Generator {
+    /// Gets the variant of a shipwreck, looking up whether it is beached
+    ///
+    /// The biome is sampled where cubiomes checks the biome of shipwrecks,
+    /// and the ship is beached in beaches and snowy beaches. See
+    /// [`shipwreck_variant()`].
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let hit = generator
+    ///     .nearest_structure(StructureType::Shipwreck, BlockPosition::new(0, 0), 2000)
+    ///     .expect("there is a shipwreck nearby");
+    /// let variant = generator
+    ///     .shipwreck_variant_at(hit.pos)
+    ///     .expect("the shipwreck was verified in its biome");
+    ///
+    /// assert_eq!(hit.pos, BlockPosition::new(80, -96));
+    /// assert_eq!(variant.template_name(), "shipwreck/sideways_fronthalf_degraded");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if generating the biome of the shipwreck fails
+    pub fn shipwreck_variant_at(
+        &self,
+        pos: BlockPosition,
+    ) -> Result<ShipwreckVariant, GeneratorError> {
+        let biome = self.feature_biome(pos)?;
+        let beached = matches!(biome, BiomeID::beach | BiomeID::snowy_beach);
+
+        Ok(shipwreck_variant(self.seed(), pos, beached))
+    }
+
+    /// Finds every verified shipwreck within a radius around a position
+    ///
+    /// This is [`Self::structures_within()`] for shipwrecks, with the variant
+    /// of each ship. The shipwrecks are sorted with the closest one first.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    /// use cubiomes::structures::shipwreck::ShipwreckPart;
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// // The treasure chest is in the back half of a ship
+    /// let treasure = generator
+    ///     .shipwrecks_within(BlockPosition::new(0, 0), 1000)
+    ///     .into_iter()
+    ///     .filter(|ship| ship.variant.part != ShipwreckPart::FrontHalf);
+    /// ```
+    pub fn shipwrecks_within(&mut self, origin: BlockPosition, radius: u32) -> Vec<Shipwreck> {
+        self.structures_within(StructureType::Shipwreck, origin, radius)
+            .into_iter()
+            .filter_map(|hit| {
+                Some(Shipwreck {
+                    pos: hit.pos,
+                    distance: hit.distance,
+                    variant: self.shipwreck_variant_at(hit.pos).ok()?,
+                })
+            })
+            .collect()
+    }
+}
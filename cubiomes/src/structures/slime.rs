@@ -0,0 +1,193 @@
+//! Module containing slime chunk checks and searches for slime farms
+//!
+//! Slime chunks only depend on the seed, so unlike structures they don't need
+//! to be verified against the biomes of the world. See
+//! [`Generator::is_slime_chunk()`] for checking a single chunk and
+//! [`Generator::slime_clusters()`] for finding areas with many slime chunks.
+
+use crate::generator::{BlockPosition, Generator, Scale};
+use crate::rng::{slime_seed, JavaRandom};
+
+/// Checks if a chunk is a slime chunk in a world with the given seed
+///
+/// The chunk is given in chunk coordinates. This is a port of `isSlimeChunk`
+/// from cubiomes, which is an inline function and thus not available in the
+/// bindings. See [`slime_seed()`] for the seed of the check.
+#[must_use]
+pub fn is_slime_chunk(seed: i64, chunk_x: i32, chunk_z: i32) -> bool {
+    JavaRandom::new(slime_seed(seed, chunk_x, chunk_z)).next_int(10) == 0
+}
+
+/// The shape of an area of chunks checked for slime chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SlimeClusterShape {
+    /// A square, `size` chunks wide, starting from the chunk of the cluster
+    Square {
+        /// The width of the square in chunks
+        size: u32,
+    },
+    /// A circle of chunks around the chunk of the cluster
+    ///
+    /// A chunk is part of the circle if its center is within `radius` chunks
+    /// of the center of the cluster chunk.
+    Circle {
+        /// The radius of the circle in chunks
+        radius: u32,
+    },
+}
+
+impl SlimeClusterShape {
+    /// Gives the chunk offsets contained in this shape
+    fn offsets(self) -> Vec<(i32, i32)> {
+        match self {
+            Self::Square { size } => {
+                let size = size as i32;
+                (0..size)
+                    .flat_map(|x| (0..size).map(move |z| (x, z)))
+                    .collect()
+            }
+            Self::Circle { radius } => {
+                let r = radius as i32;
+                (-r..=r)
+                    .flat_map(|x| (-r..=r).map(move |z| (x, z)))
+                    .filter(|(x, z)| x * x + z * z <= r * r)
+                    .collect()
+            }
+        }
+    }
+
+    /// Gives the smallest and largest offset contained in this shape
+    fn extent(self) -> (i32, i32) {
+        match self {
+            Self::Square { size } => (0, (size as i32 - 1).max(0)),
+            Self::Circle { radius } => (-(radius as i32), radius as i32),
+        }
+    }
+}
+
+/// An area of chunks containing slime chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlimeCluster {
+    /// The x coordinate of the chunk the cluster is anchored at
+    ///
+    /// This is the corner of a [`SlimeClusterShape::Square`] with the
+    /// smallest coordinates, or the center of a [`SlimeClusterShape::Circle`]
+    pub chunk_x: i32,
+    /// The z coordinate of the chunk the cluster is anchored at
+    pub chunk_z: i32,
+    /// The shape of the cluster
+    pub shape: SlimeClusterShape,
+    /// The amount of slime chunks inside the cluster
+    pub count: u32,
+}
+
+impl SlimeCluster {
+    /// Gives the chunk coordinates of every chunk in the perimeter of the cluster
+    ///
+    /// This includes chunks that are not slime chunks.
+    pub fn chunks(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.shape
+            .offsets()
+            .into_iter()
+            .map(|(x, z)| (self.chunk_x + x, self.chunk_z + z))
+    }
+}
+
+impl Generator {
+    /// Checks if the chunk containing the given block position is a slime chunk
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let slime_chunks = (0..16)
+    ///     .filter(|x| generator.is_slime_chunk(BlockPosition::new(x * 16, 0)))
+    ///     .count();
+    ///
+    /// // About one in ten chunks is a slime chunk
+    /// assert_eq!(slime_chunks, 2);
+    /// ```
+    #[must_use]
+    pub fn is_slime_chunk(&self, pos: BlockPosition) -> bool {
+        let (chunk_x, chunk_z) = pos.scale_by_num(Scale::Chunk as i32);
+        is_slime_chunk(self.seed(), chunk_x, chunk_z)
+    }
+
+    /// Finds areas containing many slime chunks around a position
+    ///
+    /// Every chunk within `radius` blocks of `origin` on both axes is used as
+    /// the anchor of an area of the given shape. Areas containing at least
+    /// `min_count` slime chunks are returned, sorted with the highest count of
+    /// slime chunks first.
+    ///
+    /// The areas overlap, so a single group of slime chunks is usually
+    /// returned as multiple clusters.
+    #[must_use]
+    pub fn slime_clusters(
+        &self,
+        origin: BlockPosition,
+        radius: u32,
+        shape: SlimeClusterShape,
+        min_count: u32,
+    ) -> Vec<SlimeCluster> {
+        let seed = self.seed();
+        let radius = radius as i32;
+
+        let (min_x, min_z) = BlockPosition::new(origin.x - radius, origin.z - radius)
+            .scale_by_num(Scale::Chunk as i32);
+        let (max_x, max_z) = BlockPosition::new(origin.x + radius, origin.z + radius)
+            .scale_by_num(Scale::Chunk as i32);
+
+        // Every chunk that any of the areas could contain is checked once
+        let (low, high) = shape.extent();
+        let (grid_x, grid_z) = (min_x + low, min_z + low);
+        let width = (max_x - min_x + high - low + 1) as usize;
+        let depth = (max_z - min_z + high - low + 1) as usize;
+
+        let grid: Vec<bool> = (0..width * depth)
+            .map(|i| {
+                is_slime_chunk(
+                    seed,
+                    grid_x + (i / depth) as i32,
+                    grid_z + (i % depth) as i32,
+                )
+            })
+            .collect();
+
+        let offsets = shape.offsets();
+        let mut clusters = Vec::new();
+
+        for chunk_x in min_x..=max_x {
+            for chunk_z in min_z..=max_z {
+                let count = offsets
+                    .iter()
+                    .filter(|(x, z)| {
+                        let i = (chunk_x + x - grid_x) as usize;
+                        let j = (chunk_z + z - grid_z) as usize;
+                        grid[i * depth + j]
+                    })
+                    .count() as u32;
+
+                if count >= min_count {
+                    clusters.push(SlimeCluster {
+                        chunk_x,
+                        chunk_z,
+                        shape,
+                        count,
+                    });
+                }
+            }
+        }
+
+        clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.count));
+        clusters
+    }
+}
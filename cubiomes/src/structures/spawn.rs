@@ -0,0 +1,145 @@
+//! Module containing the world spawn and the biomes it can be placed in
+//!
+//! Up to 1.17 the spawn is placed in one of a few biomes close to the origin,
+//! see [`valid_spawn_biomes()`]. Since 1.18 the spawn is chosen by the climate
+//! instead, so it isn't restricted to any biomes. The spawn itself can be
+//! estimated with [`Generator::estimate_spawn()`].
+
+use crate::enums::{BiomeID, Dimension, MCVersion};
+use crate::generator::{error::GeneratorError, BlockPosition, Generator};
+
+/// The y at which cubiomes samples the biomes for the spawn
+const SPAWN_BIOME_Y: i32 = 63;
+
+/// The spawn biomes of versions up to 1.0
+const SPAWN_BIOMES_1_0: [BiomeID; 3] = [BiomeID::forest, BiomeID::swamp, BiomeID::taiga];
+
+/// The spawn biomes of versions up to 1.17
+const SPAWN_BIOMES_1_17: [BiomeID; 7] = [
+    BiomeID::forest,
+    BiomeID::plains,
+    BiomeID::taiga,
+    BiomeID::taiga_hills,
+    BiomeID::wooded_hills,
+    BiomeID::jungle,
+    BiomeID::jungle_hills,
+];
+
+/// Gets the biomes the world spawn can be placed in for a version
+///
+/// Returns [`None`] if the spawn isn't chosen by its biome. This is the
+/// case for beta 1.7, where the spawn is on a random sand block, and since
+/// 1.18, where the spawn is chosen by the climate instead.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::{BiomeID, MCVersion};
+/// use cubiomes::structures::spawn::valid_spawn_biomes;
+///
+/// let biomes = valid_spawn_biomes(MCVersion::MC_1_16_5).expect("1.16 spawns by biome");
+/// assert!(biomes.contains(&BiomeID::plains));
+/// assert!(!biomes.contains(&BiomeID::desert));
+///
+/// assert_eq!(valid_spawn_biomes(MCVersion::MC_1_20_6), None);
+/// ```
+#[must_use]
+pub fn valid_spawn_biomes(version: MCVersion) -> Option<&'static [BiomeID]> {
+    if version <= MCVersion::MC_B1_7 || version >= MCVersion::MC_1_18_2 {
+        None
+    } else if version <= MCVersion::MC_1_0_0 {
+        Some(&SPAWN_BIOMES_1_0)
+    } else {
+        Some(&SPAWN_BIOMES_1_17)
+    }
+}
+
+/// Checks if the world spawn can be placed in a biome for a version
+///
+/// Returns `false` for versions where the spawn isn't chosen by its biome,
+/// see [`valid_spawn_biomes()`].
+#[must_use]
+pub fn is_valid_spawn_biome(version: MCVersion, biome: BiomeID) -> bool {
+    valid_spawn_biomes(version).is_some_and(|biomes| biomes.contains(&biome))
+}
+
+impl Generator {
+    /// Checks if the biome at a position is a valid spawn biome
+    ///
+    /// The biome is sampled at y 63, like the spawn estimation of cubiomes
+    /// does. This is useful for searches like spawning in a jungle, as the
+    /// spawn can only be near positions passing this check.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    /// use cubiomes::structures::spawn::is_valid_spawn_biome;
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_16_5,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let valid = generator
+    ///     .is_valid_spawn_biome_at(BlockPosition::new(0, 0))
+    ///     .expect("1.16 spawns by biome");
+    ///
+    /// let biome = generator.get_biome_at((0, 63, 0)).expect("failed to get biome");
+    /// assert_eq!(valid, is_valid_spawn_biome(MCVersion::MC_1_16_5, biome));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedVersion`] if the spawn of the
+    /// version isn't chosen by its biome, [`GeneratorError::UnsupportedDimension`]
+    /// outside of the overworld, or an error if generating the biome fails
+    pub fn is_valid_spawn_biome_at(&self, pos: BlockPosition) -> Result<bool, GeneratorError> {
+        let biomes = valid_spawn_biomes(self.version())
+            .ok_or(GeneratorError::UnsupportedVersion(self.version()))?;
+        if self.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        let biome = self.get_biome_at(pos.at_y(SPAWN_BIOME_Y))?;
+        Ok(biomes.contains(&biome))
+    }
+
+    /// Estimates the world spawn from the biomes
+    ///
+    /// The spawn is moved afterwards in game to a nearby grass block, which
+    /// depends on the terrain and isn't done here. The actual spawn is close
+    /// to the estimate, which is enough for searches like structures near
+    /// spawn.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let spawn = generator.estimate_spawn().expect("the generator is for the overworld");
+    /// let ruins = generator.structures_within(StructureType::Trail_Ruins, spawn, 500);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::UnsupportedDimension`] outside of the
+    /// overworld
+    pub fn estimate_spawn(&self) -> Result<BlockPosition, GeneratorError> {
+        if self.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(self.dimension()));
+        }
+
+        // SAFETY: ffi function is called correctly with an overworld
+        // generator, and the random state can be null
+        let spawn = unsafe { cubiomes_sys::estimateSpawn(self.as_ptr(), std::ptr::null_mut()) };
+
+        Ok(spawn.into())
+    }
+}
@@ -1,8 +1,163 @@
 //! Module containing [StrongholdIter], an iterator which generates all the
 //! strongholds in a [Generator]
+//!
+//! The first stronghold can also be estimated from the seed alone with
+//! [`first_stronghold()`], without generating any biomes.
+//!
+//! Since 1.9 the 128 strongholds are placed in 8 rings around the origin, see
+//! [`ring_distances()`]. The iterator can be narrowed down to a ring with
+//! [`StrongholdIter::ring()`] or to a distance with
+//! [`StrongholdIter::within_distance()`].
 
-use crate::generator::{BlockPosition, Generator};
-use std::mem::{transmute, MaybeUninit};
+use crate::enums::{Dimension, MCVersion};
+use crate::generator::{error::GeneratorError, BlockPosition, Generator};
+use std::{mem::MaybeUninit, ops::RangeInclusive};
+
+/// How far a located stronghold can be from the estimate of its ring
+///
+/// The estimate is in the middle of a chunk, the biome is searched within 112
+/// blocks on each axis, and the stronghold is then moved into its chunk.
+const MAX_RING_OFFSET: f64 = 112.0 * std::f64::consts::SQRT_2 + 24.0;
+
+/// Gets the distances from the origin the strongholds of a ring are estimated
+/// at, in blocks
+///
+/// Since 1.9 strongholds are placed in 8 rings 3072 blocks apart, the first
+/// ring at 1408 to 2688 blocks. Before 1.9 the 3 strongholds are in a single
+/// ring at 640 to 1152 blocks, which every ring gives for these versions. The
+/// located strongholds can be up to about 180 blocks closer or further than
+/// their estimate.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::structures::strongholds::ring_distances;
+///
+/// assert_eq!(ring_distances(MCVersion::MC_1_20_6, 0), 1408.0..=2688.0);
+/// assert_eq!(ring_distances(MCVersion::MC_1_20_6, 1), 4480.0..=5760.0);
+/// ```
+#[must_use]
+pub fn ring_distances(version: MCVersion, ring: u32) -> RangeInclusive<f64> {
+    if version < MCVersion::MC_1_9 {
+        return 1.25 * 32.0 * 16.0..=2.25 * 32.0 * 16.0;
+    }
+
+    // The distance in chunks is 128 + 192 * ring, give or take 40 chunks
+    let center = (4.0 * 32.0 + 6.0 * f64::from(ring) * 32.0) * 16.0;
+    let spread = 0.5 * 32.0 * 2.5 * 16.0;
+
+    center - spread..=center + spread
+}
+
+/// The estimate of the first stronghold of a seed, before checking the biomes
+///
+/// The angle and distance of the first stronghold only depend on the seed and
+/// version, so they are cheap to get. The stronghold is then moved to a
+/// nearby biome it can generate in, which needs a [`Generator`] and is done
+/// by [`Self::refine()`].
+///
+/// Created with [`first_stronghold()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FirstStronghold {
+    /// The angle from the origin towards the stronghold in radians
+    ///
+    /// The angle starts at the positive x axis and turns towards positive z.
+    pub angle: f64,
+    /// The distance from the origin to the estimate in blocks
+    pub distance: f64,
+    /// The approximate position of the stronghold
+    ///
+    /// This is the center of the chunk the angle and distance point to.
+    pub approx: BlockPosition,
+    version: MCVersion,
+    seed: i64,
+}
+
+/// Estimates the first stronghold of a seed without generating any biomes
+///
+/// The stronghold is moved by up to 112 blocks on each axis from the estimate
+/// by [`FirstStronghold::refine()`], so the estimate is only good for finding
+/// the direction of the stronghold.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::generator::BlockPosition;
+/// use cubiomes::structures::strongholds::first_stronghold;
+///
+/// let estimate = first_stronghold(MCVersion::MC_1_20_6, -5134222539607995087);
+///
+/// assert_eq!(estimate.angle.to_degrees().round(), 320.0);
+/// assert_eq!(estimate.approx, BlockPosition::new(1736, -1448));
+/// ```
+#[must_use]
+pub fn first_stronghold(version: MCVersion, seed: i64) -> FirstStronghold {
+    let mut sh_iter: MaybeUninit<cubiomes_sys::StrongholdIter> = MaybeUninit::uninit();
+
+    // SAFETY: ffi function is called correctly
+    let approx = unsafe {
+        cubiomes_sys::initFirstStronghold(
+            sh_iter.as_mut_ptr(),
+            version as i32,
+            seed.cast_unsigned(),
+        )
+    };
+    // SAFETY: sh_iter was initialized by ffi
+    let sh_iter = unsafe { sh_iter.assume_init() };
+
+    FirstStronghold {
+        angle: sh_iter.angle,
+        distance: sh_iter.dist * 16.0,
+        approx: approx.into(),
+        version,
+        seed,
+    }
+}
+
+impl FirstStronghold {
+    /// Finds the exact position of the first stronghold
+    ///
+    /// This checks the biomes around the estimate, which is the expensive step
+    /// of locating strongholds. The position is the same as the first one of
+    /// [`Generator::strongholds()`], which is the starting staircase of the
+    /// stronghold. The portal room is generated somewhere around it.
+    ///
+    /// # Errors
+    /// Returns [`GeneratorError::MismatchedGenerator`] if the version or seed
+    /// of the generator don't match the estimate,
+    /// [`GeneratorError::UnsupportedVersion`] before beta 1.8, which has no
+    /// strongholds, and [`GeneratorError::UnsupportedDimension`] if the
+    /// generator isn't for the overworld
+    pub fn refine(&self, generator: &Generator) -> Result<BlockPosition, GeneratorError> {
+        if (generator.minecraft_version(), generator.seed()) != (self.version, self.seed) {
+            return Err(GeneratorError::MismatchedGenerator(generator.context()));
+        }
+
+        if self.version < MCVersion::MC_B1_8 {
+            return Err(GeneratorError::UnsupportedVersion(self.version));
+        }
+        if generator.dimension() != Dimension::DIM_OVERWORLD {
+            return Err(GeneratorError::UnsupportedDimension(generator.dimension()));
+        }
+
+        let mut sh_iter: MaybeUninit<cubiomes_sys::StrongholdIter> = MaybeUninit::uninit();
+
+        // SAFETY: ffi functions are called correctly, the iterator is
+        // initialized before the first stronghold is generated, and the
+        // generator is for the overworld
+        let sh_iter = unsafe {
+            cubiomes_sys::initFirstStronghold(
+                sh_iter.as_mut_ptr(),
+                self.version as i32,
+                self.seed.cast_unsigned(),
+            );
+            cubiomes_sys::nextStronghold(sh_iter.as_mut_ptr(), generator.as_ptr());
+            sh_iter.assume_init()
+        };
+
+        Ok(sh_iter.pos.into())
+    }
+}
 
 /// An iterator over the strongholds in a [Generator]
 ///
@@ -22,6 +177,7 @@ pub struct StrongholdIter<'generator> {
     generator: &'generator Generator,
     inner: cubiomes_sys::StrongholdIter,
     strongholds_left: usize,
+    ring: u32,
 }
 
 impl<'generator> Generator {
@@ -38,21 +194,152 @@ impl<'generator> Generator {
             cubiomes_sys::initFirstStronghold(
                 sh_iter.as_mut_ptr(),
                 self.minecraft_version() as i32,
-                transmute::<i64, u64>(self.seed()),
+                self.seed().cast_unsigned(),
             );
         }
 
-        // We subtract one since cubiomes strongholds left is weird like that
+        // cubiomes counts the stronghold it just generated as well, so this
+        // includes the first stronghold, which is now in the iterator
         let strongholds_left =
             // SAFETY: ffi function is called correctly
-            unsafe { cubiomes_sys::nextStronghold(sh_iter.as_mut_ptr(), self.as_ptr()) } as usize
-                - 1;
+            unsafe { cubiomes_sys::nextStronghold(sh_iter.as_mut_ptr(), self.as_ptr()) };
 
         StrongholdIter {
             generator: self,
             // SAFETY: sh_iter was initialized by ffi
             inner: unsafe { sh_iter.assume_init() },
-            strongholds_left,
+            strongholds_left: usize::try_from(strongholds_left).unwrap_or(0),
+            ring: 0,
+        }
+    }
+}
+
+impl<'generator> StrongholdIter<'generator> {
+    /// Gets the ring of the stronghold the iterator produces next
+    ///
+    /// Returns [`None`] if all strongholds are generated.
+    #[must_use]
+    pub fn next_ring(&self) -> Option<u32> {
+        (self.strongholds_left > 0).then_some(self.ring)
+    }
+
+    /// Narrows the iterator down to the strongholds of a single ring
+    ///
+    /// The rings are counted from 0, the innermost ring of 3 strongholds. The
+    /// iterator ends after the last stronghold of the ring, so the strongholds
+    /// of the outer rings are never generated. Since 1.19.4 the strongholds of
+    /// the inner rings are skipped without generating their biomes as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// assert_eq!(generator.strongholds().ring(0).count(), 3);
+    /// assert_eq!(generator.strongholds().ring(1).count(), 6);
+    /// ```
+    pub fn ring(mut self, ring: u32) -> impl Iterator<Item = BlockPosition> + 'generator {
+        std::iter::from_fn(move || {
+            while self.next_ring()? < ring {
+                self.advance(ring);
+            }
+
+            if self.next_ring()? > ring {
+                return None;
+            }
+
+            self.next()
+        })
+    }
+
+    /// Narrows the iterator down to the strongholds within a distance of the
+    /// origin, in blocks
+    ///
+    /// The iterator ends once the rings left are all further away than the
+    /// distance, see [`ring_distances()`], so the strongholds of the outer
+    /// rings are never generated.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let first_ring: Vec<_> = generator.strongholds().within_distance(1500.0).collect();
+    /// assert_eq!(first_ring, [BlockPosition::new(-1404, -508)]);
+    /// ```
+    pub fn within_distance(
+        mut self,
+        distance: f64,
+    ) -> impl Iterator<Item = BlockPosition> + 'generator {
+        let version = self.generator.minecraft_version();
+
+        std::iter::from_fn(move || loop {
+            let ring = self.next_ring()?;
+            if *ring_distances(version, ring).start() - MAX_RING_OFFSET > distance {
+                return None;
+            }
+
+            let pos = self.next()?;
+            if f64::from(pos.x).hypot(f64::from(pos.z)) <= distance {
+                return Some(pos);
+            }
+        })
+    }
+
+    /// Moves on to the next stronghold without producing the current one
+    ///
+    /// Since 1.19.4 the position of a stronghold doesn't change the ones
+    /// after it, so the biomes of the strongholds before the target ring
+    /// aren't generated.
+    fn advance(&mut self, target_ring: u32) {
+        if self.strongholds_left == 0 {
+            return;
+        }
+        self.strongholds_left -= 1;
+
+        if self.strongholds_left > 0 {
+            let independent = self.generator.minecraft_version() > MCVersion::MC_1_19_2;
+            let skipped = u32::try_from(self.inner.ringnum).unwrap_or(0) < target_ring;
+
+            self.generate_next(!(independent && skipped));
+        }
+    }
+
+    /// Generates the stronghold after the current one, locating its biome if
+    /// `locate` is set
+    ///
+    /// Not locating the biome is only allowed since 1.19.4, before that
+    /// cubiomes always reads the generator.
+    fn generate_next(&mut self, locate: bool) {
+        // The ring number of cubiomes is already the ring of the stronghold
+        // it generates next
+        self.ring = u32::try_from(self.inner.ringnum).unwrap_or(0);
+
+        // SAFETY: ffi function is called correctly, the caller checked
+        // strongholds_left so we aren't iterating beyond its borders, and
+        // the generator is only null since 1.19.4, where cubiomes checks for
+        // it and skips locating the biome
+        unsafe {
+            let generator = if locate {
+                self.generator.as_ptr()
+            } else {
+                std::ptr::null()
+            };
+            cubiomes_sys::nextStronghold(&mut self.inner, generator);
         }
     }
 }
@@ -64,15 +351,14 @@ impl Iterator for StrongholdIter<'_> {
         if 0 == self.strongholds_left {
             return None;
         }
-        // We subtract one since cubiomes strongholds left is weird like that
-        self.strongholds_left =
-            // SAFETY: ffi function is called correctly, and as we checked 
-            // strongholds_left we aren't iterating beyond its borders
-            unsafe { cubiomes_sys::nextStronghold(&mut self.inner, self.generator.as_ptr()) }
-                as usize
-                - 1;
-
-        Some(self.inner.pos.into())
+        let pos = self.inner.pos.into();
+        self.strongholds_left -= 1;
+
+        if self.strongholds_left > 0 {
+            self.generate_next(true);
+        }
+
+        Some(pos)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
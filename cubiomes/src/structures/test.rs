@@ -1,11 +1,32 @@
 use crate::enums::*;
-use crate::generator::{Generator, GeneratorFlags};
-use crate::structures::strongholds::StrongholdIter;
+use crate::generator::{
+    error::GeneratorError, Aabb, BlockPosition, Cache, Generator, GeneratorFlags, Range, Scale,
+};
+use crate::rng::{chunk_generate_random, region_seed, JavaRandom};
+use crate::structures::ancient_city::ancient_city_center;
+use crate::structures::config::StructureConfig;
+use crate::structures::farms::{AfkPosition, FarmError, WitchHutFarm, DESPAWN_RADIUS};
+use crate::structures::geodes::geode_in_chunk;
+use crate::structures::mineshaft::{is_mineshaft_chunk, mineshaft_chunks};
+use crate::structures::nether::{NetherStructure, NetherStructureKind};
+use crate::structures::ocean_ruin::{ocean_ruin_variant, OceanRuinTemperature};
+use crate::structures::outpost::passes_outpost_chance;
+use crate::structures::shipwreck::{
+    shipwreck_variant, ShipwreckPart, ShipwreckShape, ShipwreckVariant,
+};
+use crate::structures::slime::{is_slime_chunk, SlimeClusterShape};
+use crate::structures::spawn::{is_valid_spawn_biome, valid_spawn_biomes};
+use crate::structures::strongholds::{first_stronghold, ring_distances, StrongholdIter};
+use crate::structures::treasure::treasure_attempt;
+use crate::structures::{
+    is_viable_structure_biome, move_structure, viable_structure_biomes, Rotation,
+    StructureGenerationError, StructureRegion,
+};
 
 #[test]
 fn iterate_over_limit() {
     let generator = Generator::new(
-        MCVersion::MC_1_21_WD,
+        MCVersion::MC_1_20_6,
         2103012030,
         Dimension::DIM_OVERWORLD,
         GeneratorFlags::empty(),
@@ -17,3 +38,1292 @@ fn iterate_over_limit() {
 
     dbg!(strongholds);
 }
+
+#[test]
+fn nearest_structure_is_verified() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let origin = BlockPosition::new(3888, 2656);
+
+    let hit = generator
+        .nearest_structure(StructureType::Outpost, origin, 2000)
+        .expect("There is an outpost in the region of the origin");
+
+    dbg!(hit);
+
+    assert!(hit.distance <= 2000.0);
+    assert!(generator
+        .verify_structure_generation_attempt(hit.pos, StructureType::Outpost)
+        .expect("Outposts are valid for this version"));
+}
+
+#[test]
+fn nearest_structure_respects_radius() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    assert_eq!(
+        generator.nearest_structure(StructureType::Mansion, BlockPosition::new(0, 0), 0),
+        None
+    );
+}
+
+#[test]
+fn structure_hits_have_variants_and_adapters() {
+    use crate::structures::finder::{StructureHit, StructureHits};
+    use crate::structures::igloo::igloo_variant;
+
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(0, 0);
+
+    let villages = generator.structures_within(StructureType::Village, origin, 3000);
+    assert!(!villages.is_empty());
+    for hit in &villages {
+        assert_eq!(hit.kind, StructureType::Village);
+        let variant = hit.variant.expect("villages have variants");
+        assert!(variant.biome.is_some() && variant.start.is_some());
+    }
+
+    for hit in generator.structures_within(StructureType::Igloo, origin, 5000) {
+        let variant = hit.variant.expect("igloos have variants");
+        let igloo = igloo_variant(MCVersion::MC_1_20_6, generator.seed(), hit.pos);
+        assert_eq!(variant.rotation, igloo.rotation);
+        assert_eq!(variant.basement, igloo.ladder_pieces.is_some());
+    }
+
+    let closest = villages.iter().copied().closest(2);
+    assert_eq!(closest, villages[..2.min(villages.len())]);
+    assert!(villages
+        .iter()
+        .copied()
+        .within(1000.0)
+        .all(|hit| hit.distance <= 1000.0));
+
+    let moved = BlockPosition::new(-800, 1200);
+    let sorted = villages.iter().copied().sorted_from(moved);
+    assert_eq!(sorted.len(), villages.len());
+    assert!(sorted
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+    for hit in sorted {
+        let expected = StructureHit::new(hit.pos, hit.kind, moved).distance;
+        assert!((hit.distance - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn afk_position_of_single_box() {
+    let volume = Aabb::new((0, 0, 0), (9, 19, 29));
+    let afk = AfkPosition::enclosing(&[volume]).expect("a volume was given");
+
+    // The corner centers span 9 x 19 x 29 blocks
+    let expected = (9.0f64 * 9.0 + 19.0 * 19.0 + 29.0 * 29.0).sqrt() / 2.0;
+
+    assert!((afk.radius - expected).abs() < 1e-6);
+    assert_eq!(afk.block(), (5, 10, 15));
+}
+
+#[test]
+fn afk_position_of_many_boxes() {
+    // Enough corners to overflow the stack, if every corner took a call
+    let volumes: Vec<Aabb> = (0..50_000)
+        .map(|i| Aabb::new((i, 0, -i), (i, 0, -i)))
+        .collect();
+    let afk = AfkPosition::enclosing(&volumes).expect("volumes were given");
+
+    let expected = 49_999.0 * std::f64::consts::SQRT_2 / 2.0;
+
+    assert!((afk.radius - expected).abs() < 1e-6);
+    assert_eq!(afk.block(), (25_000, 0, -24_999));
+}
+
+#[test]
+fn afk_position_is_minimal() {
+    let mut state: u64 = 0x2545F4914F6CDD1D;
+    let mut next = |range: i32| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % range as u64) as i32
+    };
+
+    for _ in 0..50 {
+        let volumes: Vec<Aabb> = (0..next(4) + 1)
+            .map(|_| {
+                let (x, y, z) = (next(200), next(60), next(200));
+                Aabb::new((x, y, z), (x + next(60), y + next(20), z + next(60)))
+            })
+            .collect();
+
+        let afk = AfkPosition::enclosing(&volumes).expect("volumes were given");
+        let corners: Vec<[f64; 3]> = volumes.iter().flat_map(Aabb::corner_centers).collect();
+
+        let furthest = |c: [f64; 3]| {
+            corners
+                .iter()
+                .map(|p| {
+                    ((p[0] - c[0]).powi(2) + (p[1] - c[1]).powi(2) + (p[2] - c[2]).powi(2)).sqrt()
+                })
+                .fold(0.0, f64::max)
+        };
+
+        assert!(furthest([afk.x, afk.y, afk.z]) <= afk.radius + 1e-6);
+
+        // An approximation of the enclosing sphere can never be smaller
+        let mut center = corners[0];
+        for i in 1..10000 {
+            let p = corners
+                .iter()
+                .max_by(|a, b| {
+                    let da = (a[0] - center[0]).powi(2)
+                        + (a[1] - center[1]).powi(2)
+                        + (a[2] - center[2]).powi(2);
+                    let db = (b[0] - center[0]).powi(2)
+                        + (b[1] - center[1]).powi(2)
+                        + (b[2] - center[2]).powi(2);
+                    da.total_cmp(&db)
+                })
+                .expect("there are corners");
+            for axis in 0..3 {
+                center[axis] += (p[axis] - center[axis]) / (i + 1) as f64;
+            }
+        }
+
+        assert!(afk.radius <= furthest(center) + 1e-6);
+    }
+}
+
+#[test]
+fn monument_clusters_fit() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        162,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let clusters = generator.monument_clusters(BlockPosition::new(0, 0), 1000, 2);
+
+    assert!(clusters.iter().any(|cluster| cluster
+        .monuments
+        .contains(&BlockPosition::new(-512, -192))
+        && cluster.monuments.contains(&BlockPosition::new(-688, -192))));
+
+    for cluster in clusters {
+        assert!(cluster.monuments.len() >= 2);
+        assert!(cluster.afk.covers_all(DESPAWN_RADIUS));
+    }
+}
+
+#[test]
+fn witch_hut_farm_coverage() {
+    let farm = WitchHutFarm::default();
+
+    let close = [BlockPosition::new(0, 0), BlockPosition::new(160, 0)];
+    let far = [BlockPosition::new(0, 0), BlockPosition::new(320, 0)];
+
+    let afk = farm.afk_position(&close).expect("two huts are supported");
+    dbg!(afk);
+
+    assert_eq!(afk.block(), (84, 67, 4));
+    assert_eq!(farm.covers_all(&close), Ok(true));
+    assert_eq!(farm.covers_all(&far), Ok(false));
+
+    assert_eq!(
+        farm.afk_position(&[]),
+        Err(FarmError::InvalidStructureCount(0))
+    );
+    assert_eq!(
+        farm.covers_all(&[BlockPosition::new(0, 0); 5]),
+        Err(FarmError::InvalidStructureCount(5))
+    );
+}
+
+#[test]
+fn witch_huts_have_spawning_volumes() {
+    let farm = WitchHutFarm::default();
+
+    for version in [MCVersion::MC_1_19_4, MCVersion::MC_1_20_6] {
+        let mut generator = Generator::new(
+            version,
+            -5134222539607995087,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+
+        let huts = generator.witch_huts_within(BlockPosition::new(0, 0), 8000);
+        assert!(!huts.is_empty());
+
+        for hut in &huts {
+            let volume = dbg!(hut.spawning_volume);
+            let size = (
+                volume.max_x - volume.min_x + 1,
+                volume.max_y - volume.min_y + 1,
+                volume.max_z - volume.min_z + 1,
+            );
+
+            // Huts are only rotated since 1.20
+            if version <= MCVersion::MC_1_19_4 {
+                assert_eq!(size, (7, 7, 9));
+            } else {
+                assert!(size == (7, 7, 9) || size == (9, 7, 7));
+            }
+
+            let platform = farm.platform_volume(hut.pos);
+            assert_eq!(volume.intersection(&platform), Some(volume));
+        }
+    }
+}
+
+#[test]
+fn slime_chunks_match_cubiomes() {
+    // Counts of slime chunks in -300..300 on both axes, generated with cubiomes
+    for (seed, expected) in [
+        (-5134222539607995087, 36095),
+        (162, 35976),
+        (123456789, 36116),
+    ] {
+        let count = (-300..300)
+            .flat_map(|x| (-300..300).map(move |z| (x, z)))
+            .filter(|&(x, z)| is_slime_chunk(seed, x, z))
+            .count();
+
+        assert_eq!(count, expected);
+    }
+}
+
+#[test]
+fn slime_clusters_are_sorted() {
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let shape = SlimeClusterShape::Square { size: 4 };
+    let clusters = generator.slime_clusters(BlockPosition::new(0, 0), 1000, shape, 4);
+
+    dbg!(clusters.first());
+
+    assert!(!clusters.is_empty());
+    assert!(clusters.windows(2).all(|w| w[0].count >= w[1].count));
+
+    for cluster in clusters.iter().take(10) {
+        let count = cluster
+            .chunks()
+            .filter(|&(x, z)| is_slime_chunk(generator.seed(), x, z))
+            .count() as u32;
+
+        assert_eq!(count, cluster.count);
+    }
+
+    let circle = SlimeClusterShape::Circle { radius: 8 };
+    let clusters = generator.slime_clusters(BlockPosition::new(0, 0), 256, circle, 0);
+
+    assert!(clusters
+        .iter()
+        .all(|cluster| cluster.chunks().count() == 197));
+}
+
+#[test]
+fn moved_structures_keep_their_offsets() {
+    let base = 0x1234_5678_9abc;
+
+    for structure in [
+        StructureType::Swamp_Hut,
+        StructureType::Village,
+        StructureType::Monument,
+    ] {
+        for (dx, dz) in [(1, 0), (-5, 12), (300, -700)] {
+            let moved = move_structure(base, dx, dz);
+            assert_eq!(moved, moved & 0xffff_ffff_ffff);
+
+            for (x, z) in [(0, 0), (-1, -1), (7, -3)] {
+                let region = |x, z| {
+                    StructureRegion::new(x, z, MCVersion::MC_1_20_6, structure)
+                        .expect("the structure is generated in 1.20")
+                };
+                let size = region(x, z).region_size_blocks();
+
+                assert_eq!(
+                    region(x + dx, z + dz).get_structure_generation_attempt(moved),
+                    region(x, z)
+                        .get_structure_generation_attempt(base)
+                        .map(|pos| BlockPosition::new(pos.x + dx * size, pos.z + dz * size))
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn vanilla_config_matches_cubiomes() {
+    let seed = -5134222539607995087;
+
+    for structure_type in [
+        StructureType::Swamp_Hut,
+        StructureType::Monument,
+        StructureType::Outpost,
+        StructureType::Mansion,
+    ] {
+        let config = StructureConfig::of(structure_type, MCVersion::MC_1_20_6)
+            .expect("The structure is generated in 1.20");
+
+        for (x, z) in [(0, 0), (3, -2), (-7, 5), (-1, -1)] {
+            let region = StructureRegion::new(x, z, MCVersion::MC_1_20_6, structure_type)
+                .expect("The structure is generated in 1.20");
+            let custom = region
+                .with_config(config)
+                .expect("The vanilla config is valid");
+
+            assert_eq!(
+                region.get_structure_generation_attempt(seed),
+                custom.get_structure_generation_attempt(seed),
+                "{structure_type:?} in region {x}, {z}"
+            );
+        }
+    }
+}
+
+#[test]
+fn regions_hold_negative_blocks() {
+    let mut region = StructureRegion::new(0, 0, MCVersion::MC_1_20_6, StructureType::Village)
+        .expect("Villages are generated in 1.20");
+    let size = region.region_size_blocks();
+
+    for (block, expected) in [
+        (-1, -1),
+        (-size, -1),
+        (-size - 1, -2),
+        (size - 1, 0),
+        (size, 1),
+    ] {
+        region.set_new_minecraft_pos(BlockPosition::new(block, -block));
+        assert_eq!((region.x, region.z), (expected, (-block).div_euclid(size)));
+    }
+}
+
+#[test]
+fn custom_config_changes_spacing() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let vanilla = StructureConfig::of(StructureType::Village, MCVersion::MC_1_20_6)
+        .expect("Villages are generated in 1.20");
+    let config = StructureConfig {
+        spacing: 64,
+        separation: 16,
+        ..vanilla
+    };
+
+    let region = StructureRegion::new(2, -1, MCVersion::MC_1_20_6, StructureType::Village)
+        .expect("Villages are generated in 1.20")
+        .with_config(config)
+        .expect("The config is valid");
+
+    let attempt = region
+        .get_structure_generation_attempt(generator.seed())
+        .expect("Villages have an attempt in every region");
+
+    dbg!(attempt);
+
+    assert_eq!(attempt.x.div_euclid(64 * 16), 2);
+    assert_eq!(attempt.z.div_euclid(64 * 16), -1);
+    assert!(attempt.x.rem_euclid(64 * 16) < 48 * 16);
+    assert!(attempt.z.rem_euclid(64 * 16) < 48 * 16);
+
+    let hits = generator.structures_within_with_config(
+        StructureType::Village,
+        config,
+        BlockPosition::new(0, 0),
+        3000,
+    );
+
+    dbg!(&hits);
+
+    for hit in hits {
+        let (x, z) = hit.pos.scale_by_num(64 * 16);
+        let region = StructureRegion::new(x, z, MCVersion::MC_1_20_6, StructureType::Village)
+            .expect("Villages are generated in 1.20")
+            .with_config(config)
+            .expect("The config is valid");
+
+        assert_eq!(
+            region.get_structure_generation_attempt(generator.seed()),
+            Some(hit.pos)
+        );
+    }
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let region = StructureRegion::new(0, 0, MCVersion::MC_1_20_6, StructureType::Village)
+        .expect("Villages are generated in 1.20");
+    let config = StructureConfig {
+        salt: 10387312,
+        spacing: 8,
+        separation: 8,
+    };
+
+    assert_eq!(
+        region.with_config(config),
+        Err(StructureGenerationError::InvalidConfig)
+    );
+
+    let region = StructureRegion::new(0, 0, MCVersion::MC_1_20_6, StructureType::Mineshaft)
+        .expect("Mineshafts are generated in 1.20");
+
+    assert_eq!(
+        region.with_config(StructureConfig {
+            spacing: 16,
+            ..config
+        }),
+        Err(StructureGenerationError::UnsupportedConfig(
+            StructureType::Mineshaft
+        ))
+    );
+}
+
+#[test]
+fn spawn_biomes_follow_the_version() {
+    assert_eq!(valid_spawn_biomes(MCVersion::MC_B1_7), None);
+    assert_eq!(valid_spawn_biomes(MCVersion::MC_1_18_2), None);
+    assert_eq!(valid_spawn_biomes(MCVersion::MC_1_20_6), None);
+    assert!(is_valid_spawn_biome(MCVersion::MC_1_0_0, BiomeID::swamp));
+    assert!(!is_valid_spawn_biome(MCVersion::MC_1_16_5, BiomeID::swamp));
+    assert!(is_valid_spawn_biome(
+        MCVersion::MC_1_16_5,
+        BiomeID::jungle_hills
+    ));
+    assert!(!is_valid_spawn_biome(
+        MCVersion::MC_1_20_6,
+        BiomeID::cherry_grove
+    ));
+
+    let generator = Generator::new(
+        MCVersion::MC_1_16_5,
+        2151901553968352745,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let mut valid = 0;
+    for x in (-512..512).step_by(64) {
+        for z in (-512..512).step_by(64) {
+            let pos = BlockPosition::new(x, z);
+            let biome = generator
+                .get_biome_at((x, 63, z))
+                .expect("The biome should generate");
+            let is_valid = generator
+                .is_valid_spawn_biome_at(pos)
+                .expect("1.16 spawns by biome");
+
+            assert_eq!(is_valid, is_valid_spawn_biome(MCVersion::MC_1_16_5, biome));
+            valid += usize::from(is_valid);
+        }
+    }
+    assert!(dbg!(valid) > 0);
+
+    let modern = Generator::new(
+        MCVersion::MC_1_20_6,
+        2151901553968352745,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        modern.is_valid_spawn_biome_at(BlockPosition::new(0, 0)),
+        Err(GeneratorError::UnsupportedVersion(MCVersion::MC_1_20_6))
+    );
+}
+
+#[test]
+fn first_stronghold_estimate_refines_to_the_first_stronghold() {
+    for (version, seed) in [
+        (MCVersion::MC_1_20_6, -5134222539607995087),
+        (MCVersion::MC_1_16_5, 2151901553968352745),
+        (MCVersion::MC_1_8_9, 2103012030),
+    ] {
+        let generator = Generator::new(
+            version,
+            seed,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        let estimate = dbg!(first_stronghold(version, seed));
+
+        // The approximate position is the center of the chunk the angle and
+        // distance point to
+        let x = estimate.angle.cos() * estimate.distance;
+        let z = estimate.angle.sin() * estimate.distance;
+        assert!((f64::from(estimate.approx.x - 8) - x).abs() <= 8.0);
+        assert!((f64::from(estimate.approx.z - 8) - z).abs() <= 8.0);
+
+        let refined = estimate
+            .refine(&generator)
+            .expect("The generator is for the overworld");
+        assert_eq!(Some(refined), generator.strongholds().next());
+        assert_eq!(
+            generator.strongholds().count(),
+            if version >= MCVersion::MC_1_9_4 {
+                128
+            } else {
+                3
+            }
+        );
+        assert!(refined.x.abs_diff(estimate.approx.x) <= 128);
+        assert!(refined.z.abs_diff(estimate.approx.z) <= 128);
+    }
+
+    let nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        first_stronghold(MCVersion::MC_1_20_6, -5134222539607995087).refine(&nether),
+        Err(GeneratorError::UnsupportedDimension(Dimension::DIM_NETHER))
+    );
+}
+
+#[test]
+fn nether_regions_generate_one_structure() {
+    for (version, seed) in [
+        (MCVersion::MC_1_20_6, -5134222539607995087),
+        (MCVersion::MC_1_16_5, 2151901553968352745),
+        (MCVersion::MC_1_12_2, 2103012030),
+    ] {
+        let mut generator = Generator::new(
+            version,
+            seed,
+            Dimension::DIM_NETHER,
+            GeneratorFlags::empty(),
+        );
+
+        let mut kinds = Vec::new();
+        for x in -4..4 {
+            for z in -4..4 {
+                let structure = generator
+                    .nether_structure(x, z)
+                    .expect("The region should be checked");
+
+                let fortress = StructureRegion::new(x, z, version, StructureType::Fortress)
+                    .expect("1.0+ has fortresses");
+                let fortress = generator.try_generate_structure_in_region(fortress);
+                let bastion = StructureRegion::new(x, z, version, StructureType::Bastion)
+                    .ok()
+                    .and_then(|region| generator.try_generate_structure_in_region(region));
+
+                match structure {
+                    Some(NetherStructure {
+                        pos,
+                        kind: NetherStructureKind::Bastion(_),
+                    }) => assert_eq!(bastion, Some(pos)),
+                    Some(NetherStructure {
+                        pos,
+                        kind: NetherStructureKind::Fortress,
+                    }) => {
+                        assert_eq!(fortress, Some(pos));
+                        assert_eq!(bastion, None);
+                    }
+                    None => {
+                        assert_eq!(fortress, None);
+                        assert_eq!(bastion, None);
+                    }
+                }
+
+                kinds.extend(structure.map(|structure| structure.kind));
+            }
+        }
+
+        dbg!(&kinds);
+        assert!(kinds.contains(&NetherStructureKind::Fortress));
+        assert_eq!(
+            kinds
+                .iter()
+                .any(|kind| matches!(kind, NetherStructureKind::Bastion(_))),
+            version >= MCVersion::MC_1_16_1
+        );
+    }
+}
+
+#[test]
+fn ancient_cities_are_in_the_deep_dark() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let cities = generator.ancient_cities_within(BlockPosition::new(0, 0), 3000);
+    assert!(!dbg!(&cities).is_empty());
+    assert!(cities
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+    assert_eq!(
+        generator
+            .nearest_ancient_city(BlockPosition::new(0, 0), 3000)
+            .map(|city| city.pos),
+        Some(cities[0].pos)
+    );
+
+    for city in &cities {
+        assert_eq!(
+            city.center_piece,
+            ancient_city_center(MCVersion::MC_1_20_6, generator.seed(), city.pos)
+        );
+        assert!(city.center_piece.min_y < 0);
+        assert!(city.pos.x.abs_diff(city.center().0) <= 64);
+        assert!(city.pos.z.abs_diff(city.center().2) <= 64);
+
+        // cubiomes checks the biome at the bottom of the center piece
+        let (x, _, z) = city.center();
+        let mut cache = Cache::new(
+            &generator,
+            Range {
+                scale: Scale::Quad,
+                x: x >> 2,
+                z: z >> 2,
+                size_x: 1,
+                size_z: 1,
+                y: city.center_piece.min_y >> 2,
+                size_y: 0,
+            },
+        );
+        cache.fill_cache().expect("The cache should fill");
+        assert_eq!(cache.as_vec()[0], BiomeID::deep_dark as i32);
+    }
+
+    let mut old = Generator::new(
+        MCVersion::MC_1_18_2,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        old.nearest_ancient_city(BlockPosition::new(0, 0), 3000),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "unstable-versions")]
+fn trial_chambers_are_gated_by_version() {
+    use crate::structures::{enabled_structures, is_structure_enabled};
+
+    assert!(is_structure_enabled(
+        StructureType::Trial_Chambers,
+        MCVersion::MC_1_21_1
+    ));
+    assert!(!is_structure_enabled(
+        StructureType::Trial_Chambers,
+        MCVersion::MC_1_20_6
+    ));
+    assert!(
+        StructureRegion::new(0, 0, MCVersion::MC_1_20_6, StructureType::Trial_Chambers).is_err()
+    );
+
+    let overworld = enabled_structures(MCVersion::MC_1_21_WD, Dimension::DIM_OVERWORLD);
+    let nether = enabled_structures(MCVersion::MC_1_21_WD, Dimension::DIM_NETHER);
+    let end = enabled_structures(MCVersion::MC_1_21_WD, Dimension::DIM_END);
+    dbg!(&overworld, &nether, &end);
+
+    assert!(overworld.contains(&StructureType::Trial_Chambers));
+    assert!(overworld.contains(&StructureType::Ancient_City));
+    assert!(nether.contains(&StructureType::Fortress));
+    assert!(nether.contains(&StructureType::Bastion));
+    assert!(end.contains(&StructureType::End_City));
+    assert!(
+        !enabled_structures(MCVersion::MC_1_20_6, Dimension::DIM_OVERWORLD)
+            .contains(&StructureType::Trial_Chambers)
+    );
+    assert!(
+        !enabled_structures(MCVersion::MC_1_12_2, Dimension::DIM_NETHER)
+            .contains(&StructureType::Bastion)
+    );
+
+    let mut generator = Generator::new(
+        MCVersion::MC_1_21_WD,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let chambers = generator.structures_within(
+        StructureType::Trial_Chambers,
+        BlockPosition::new(0, 0),
+        2000,
+    );
+    assert!(!dbg!(&chambers).is_empty());
+    assert!(chambers
+        .iter()
+        .all(|hit| hit.kind == StructureType::Trial_Chambers));
+
+    let mut old = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    assert!(old
+        .structures_within(
+            StructureType::Trial_Chambers,
+            BlockPosition::new(0, 0),
+            2000
+        )
+        .is_empty());
+}
+
+#[test]
+fn trail_ruins_near_spawn() {
+    let version = MCVersion::MC_1_20_6;
+    let biomes = viable_structure_biomes(StructureType::Trail_Ruins, version);
+    assert_eq!(
+        biomes,
+        [
+            BiomeID::taiga,
+            BiomeID::jungle,
+            BiomeID::snowy_taiga,
+            BiomeID::old_growth_pine_taiga,
+            BiomeID::old_growth_birch_forest,
+            BiomeID::old_growth_spruce_taiga,
+        ]
+    );
+    assert!(viable_structure_biomes(StructureType::Trail_Ruins, MCVersion::MC_1_19_4).is_empty());
+    assert!(is_viable_structure_biome(
+        StructureType::Ancient_City,
+        MCVersion::MC_1_20_6,
+        BiomeID::deep_dark
+    ));
+
+    let mut generator = Generator::new(
+        version,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let spawn = dbg!(generator.estimate_spawn()).expect("The generator is for the overworld");
+
+    let ruins = generator.structures_within(StructureType::Trail_Ruins, spawn, 3000);
+    assert!(!dbg!(&ruins).is_empty());
+
+    let mut nether = Generator::new(
+        version,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        nether.estimate_spawn(),
+        Err(GeneratorError::UnsupportedDimension(Dimension::DIM_NETHER))
+    );
+    assert!(nether
+        .structures_within(StructureType::Trail_Ruins, spawn, 3000)
+        .is_empty());
+}
+
+#[test]
+fn geodes_are_placed_in_their_chunk() {
+    let seed = -5134222539607995087;
+
+    for version in [
+        MCVersion::MC_1_17_1,
+        MCVersion::MC_1_18_2,
+        MCVersion::MC_1_20_6,
+    ] {
+        let mut geodes = 0;
+
+        for x in -32..32 {
+            for z in -32..32 {
+                if let Some(pos) = geode_in_chunk(version, seed, x, z) {
+                    assert_eq!(pos.scale_by_num(16), (x, z));
+                    geodes += 1;
+                }
+            }
+        }
+
+        // About one in 24 chunks has a geode
+        assert!((100..250).contains(&geodes));
+    }
+
+    assert_eq!(geode_in_chunk(MCVersion::MC_1_16_5, seed, 0, 0), None);
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(100, -50);
+    let hits = generator.geodes_within(origin, 300);
+
+    let mut expected: Vec<BlockPosition> = (-40..40)
+        .flat_map(|x| (-40..40).map(move |z| (x, z)))
+        .filter_map(|(x, z)| geode_in_chunk(MCVersion::MC_1_20_6, seed, x, z))
+        .filter(|pos| {
+            let (dx, dz) = (f64::from(pos.x - origin.x), f64::from(pos.z - origin.z));
+            (dx * dx + dz * dz).sqrt() <= 300.0
+        })
+        .collect();
+    let mut found: Vec<BlockPosition> = hits.iter().map(|hit| hit.pos).collect();
+    expected.sort();
+    found.sort();
+
+    assert_eq!(found, expected);
+    assert!(hits
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+}
+
+#[test]
+fn buried_treasures_match_cubiomes() {
+    let seed = -5134222539607995087;
+
+    for version in [MCVersion::MC_1_13_2, MCVersion::MC_1_20_6] {
+        for x in -32..32 {
+            for z in -32..32 {
+                let region = StructureRegion::new(x, z, version, StructureType::Treasure)
+                    .expect("Buried treasure is generated since 1.13");
+
+                assert_eq!(
+                    treasure_attempt(version, seed, x, z),
+                    region.get_structure_generation_attempt(seed)
+                );
+            }
+        }
+    }
+
+    assert_eq!(treasure_attempt(MCVersion::MC_1_12_2, seed, 0, 0), None);
+
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(-200, 300);
+    let hits = generator.buried_treasures_within(origin, 1000);
+    let expected = generator.structures_within(StructureType::Treasure, origin, 1000);
+
+    let mut found: Vec<BlockPosition> = hits.iter().map(|hit| hit.pos).collect();
+    let mut expected: Vec<BlockPosition> = expected.iter().map(|hit| hit.pos).collect();
+    found.sort();
+    expected.sort();
+
+    assert!(!dbg!(&found).is_empty());
+    assert_eq!(found, expected);
+    assert!(hits
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+}
+
+#[test]
+fn shipwrecks_have_variants() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let ships = generator.shipwrecks_within(BlockPosition::new(0, 0), 3000);
+    assert!(!ships.is_empty());
+
+    for ship in &ships {
+        let variant = ship.variant;
+        dbg!(ship.pos, variant.template_name(), variant.rotation);
+
+        assert!(!(variant.beached && variant.shape == ShipwreckShape::UpsideDown));
+        assert!(variant.shape != ShipwreckShape::WithMast || variant.part == ShipwreckPart::Full);
+        assert_eq!(
+            variant,
+            shipwreck_variant(generator.seed(), ship.pos, variant.beached)
+        );
+    }
+
+    assert!(ships.iter().any(|ship| !ship.variant.beached));
+    assert!(ships
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+
+    let variant = ShipwreckVariant {
+        beached: false,
+        rotation: Rotation::None,
+        shape: ShipwreckShape::Sideways,
+        part: ShipwreckPart::BackHalf,
+        degraded: true,
+    };
+    assert_eq!(
+        variant.template_name(),
+        "shipwreck/sideways_backhalf_degraded"
+    );
+}
+
+#[test]
+fn ocean_ruins_have_variants() {
+    for version in [MCVersion::MC_1_14_4, MCVersion::MC_1_20_6] {
+        let mut generator = Generator::new(
+            version,
+            -5134222539607995087,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+
+        let ruins = generator.ocean_ruins_within(BlockPosition::new(0, 0), 3000);
+        let hits =
+            generator.structures_within(StructureType::Ocean_Ruin, BlockPosition::new(0, 0), 3000);
+        assert_eq!(dbg!(ruins.len()), hits.len());
+
+        for ruin in &ruins {
+            assert!(ruin.variant.large || !ruin.variant.cluster);
+            assert_eq!(
+                ruin.variant,
+                ocean_ruin_variant(generator.seed(), ruin.pos, ruin.variant.temperature)
+            );
+        }
+
+        assert!(ruins.iter().any(|ruin| ruin.variant.large));
+        assert!(ruins.iter().any(|ruin| !ruin.variant.large));
+    }
+
+    assert_eq!(
+        OceanRuinTemperature::of(BiomeID::frozen_ocean),
+        Some(OceanRuinTemperature::Cold)
+    );
+    assert_eq!(
+        OceanRuinTemperature::of(BiomeID::warm_ocean),
+        Some(OceanRuinTemperature::Warm)
+    );
+    assert_eq!(OceanRuinTemperature::of(BiomeID::beach), None);
+}
+
+#[test]
+fn mineshafts_match_cubiomes() {
+    let seed = -5134222539607995087;
+
+    for version in [MCVersion::MC_1_12_2, MCVersion::MC_1_20_6] {
+        let mineshafts = mineshaft_chunks(
+            version,
+            seed,
+            BlockPosition::new(-32, -32),
+            BlockPosition::new(31, 31),
+        );
+        let mut expected = Vec::new();
+
+        for x in -32..32 {
+            for z in -32..32 {
+                let region = StructureRegion::new(x, z, version, StructureType::Mineshaft)
+                    .expect("Mineshafts are generated since beta 1.8");
+
+                if let Some(pos) = region.get_structure_generation_attempt(seed) {
+                    expected.push(pos);
+                }
+                assert_eq!(
+                    is_mineshaft_chunk(version, seed, x, z),
+                    region.get_structure_generation_attempt(seed).is_some()
+                );
+            }
+        }
+
+        assert!(!dbg!(&mineshafts).is_empty());
+        assert_eq!(mineshafts, expected);
+    }
+
+    assert!(mineshaft_chunks(
+        MCVersion::MC_1_20_6,
+        seed,
+        BlockPosition::new(1, 0),
+        BlockPosition::new(0, 0)
+    )
+    .is_empty());
+
+    let generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(100, -50);
+    let hits = generator.mineshafts_within(origin, 400);
+
+    assert!(hits.iter().all(|hit| hit.distance <= 400.0));
+    assert!(hits
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+}
+
+#[test]
+fn igloos_have_basements() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+
+    let igloos = generator.igloos_within(BlockPosition::new(0, 0), 10000);
+    assert!(!dbg!(&igloos).is_empty());
+
+    for igloo in &igloos {
+        // Since 1.13 igloos are picked with the generator of their chunk
+        let mut rng = chunk_generate_random(
+            generator.seed(),
+            igloo.pos.x.div_euclid(16),
+            igloo.pos.z.div_euclid(16),
+        );
+        let rotation = Rotation::from_index(rng.next_int(4));
+        let basement = rng.next_double() < 0.5;
+        let pieces = rng.next_int(8) + 4;
+
+        assert_eq!(igloo.variant.rotation, rotation);
+        assert_eq!(igloo.variant.has_basement(), basement);
+        if let Some(ladder_pieces) = igloo.variant.ladder_pieces {
+            assert_eq!(i32::from(ladder_pieces), pieces);
+        }
+    }
+
+    assert!(igloos.iter().any(|igloo| igloo.variant.has_basement()));
+    assert!(igloos.iter().any(|igloo| !igloo.variant.has_basement()));
+}
+
+#[test]
+fn mansions_are_in_dark_forests() {
+    // Up to 1.17 the whole area around a mansion has to be dark forest, which
+    // makes them rare, so 1.16 is checked with a seed known to have one
+    for (version, seed, nearest) in [
+        (MCVersion::MC_1_16_5, 26, BlockPosition::new(-832, -656)),
+        (
+            MCVersion::MC_1_20_6,
+            -5134222539607995087,
+            BlockPosition::new(1712, -1840),
+        ),
+    ] {
+        let mut generator = Generator::new(
+            version,
+            seed,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        let origin = BlockPosition::new(0, 0);
+
+        let mansions = generator.mansions_within(origin, 5000);
+        let candidates = generator.structures_within(StructureType::Mansion, origin, 5000);
+
+        assert!(mansions.iter().all(|mansion| candidates.contains(mansion)));
+        assert_eq!(
+            generator
+                .nearest_mansion(origin, 5000)
+                .map(|mansion| mansion.pos),
+            Some(nearest)
+        );
+
+        for mansion in &mansions {
+            let biome = generator
+                .get_biome_at((mansion.pos.x + 7, 320, mansion.pos.z + 7))
+                .expect("Failed to get the biome of the mansion");
+            assert!(biome == BiomeID::dark_forest || biome == BiomeID::pale_garden);
+        }
+    }
+}
+
+#[test]
+fn outposts_pass_the_chance_check() {
+    let seed = -5134222539607995087;
+    let version = MCVersion::MC_1_20_6;
+    let config = StructureConfig::of(StructureType::Outpost, version)
+        .expect("Outposts are generated since 1.14");
+    let mut candidates = 0;
+
+    for x in -16..16 {
+        for z in -16..16 {
+            // The attempt of the region without the chance check
+            let mut rng = JavaRandom::new(region_seed(seed, x, z, i64::from(config.salt)));
+            let chunk_x = x * config.spacing + rng.next_int(config.chunk_range());
+            let chunk_z = z * config.spacing + rng.next_int(config.chunk_range());
+            let candidate = BlockPosition::new(chunk_x * 16, chunk_z * 16);
+
+            let region = StructureRegion::new(x, z, version, StructureType::Outpost)
+                .expect("Outposts are generated since 1.14");
+            let expected = passes_outpost_chance(seed, chunk_x, chunk_z).then_some(candidate);
+
+            assert_eq!(region.get_structure_generation_attempt(seed), expected);
+            candidates += usize::from(expected.is_some());
+        }
+    }
+
+    // About one in five attempts passes
+    assert!((100..320).contains(&dbg!(candidates)));
+
+    let mut generator = Generator::new(
+        version,
+        seed,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(0, 0);
+    let outposts = generator.outposts_within(origin, 5000);
+
+    assert!(!dbg!(&outposts).is_empty());
+    assert_eq!(
+        generator.nearest_outpost(origin, 5000),
+        outposts.first().copied()
+    );
+    assert!(outposts.iter().all(|outpost| passes_outpost_chance(
+        seed,
+        outpost.pos.x >> 4,
+        outpost.pos.z >> 4
+    )));
+}
+
+#[test]
+fn structures_have_bounds() {
+    let mut generator = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    let origin = BlockPosition::new(0, 0);
+
+    // Villages of every type are found and bounded by their start piece
+    let villages = generator.structures_within(StructureType::Village, origin, 3000);
+    assert!(!villages.is_empty());
+    for village in &villages {
+        let bounds = generator
+            .structure_bounds(StructureType::Village, village.pos)
+            .expect("failed to estimate the surface")
+            .expect("villages have a start piece");
+
+        dbg!(village.pos, bounds);
+        assert!(bounds
+            .expand(24)
+            .contains(village.pos.x, bounds.min_y, village.pos.z));
+        assert!(bounds.max_x - bounds.min_x < 24 && bounds.max_z - bounds.min_z < 24);
+    }
+
+    let hut = generator
+        .nearest_structure(StructureType::Swamp_Hut, origin, 5000)
+        .expect("there is a swamp hut near spawn");
+    assert_eq!(
+        generator.structure_bounds(StructureType::Swamp_Hut, hut.pos),
+        Ok(Some(crate::structures::farms::witch_hut_spawning_volume(
+            MCVersion::MC_1_20_6,
+            -5134222539607995087,
+            hut.pos
+        )))
+    );
+    assert_eq!(
+        generator.structure_bounds(StructureType::Mineshaft, origin),
+        Ok(None)
+    );
+
+    let mut nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_NETHER,
+        GeneratorFlags::empty(),
+    );
+    let bastion = nether
+        .nearest_structure(StructureType::Bastion, origin, 2000)
+        .expect("there is a bastion near spawn");
+    let bounds = nether
+        .structure_bounds(StructureType::Bastion, bastion.pos)
+        .expect("bastions don't need the surface")
+        .expect("bastions have a start piece");
+    assert_eq!(bounds.min_y, 33);
+    assert!(bounds.max_x - bounds.min_x >= 15 && bounds.max_y - bounds.min_y >= 23);
+
+    let mut end = Generator::new(
+        MCVersion::MC_1_20_6,
+        -5134222539607995087,
+        Dimension::DIM_END,
+        GeneratorFlags::empty(),
+    );
+    // Not every city attempt is on high enough terrain
+    let (city, bounds) = end
+        .structures_within(StructureType::End_City, BlockPosition::new(1500, 0), 2000)
+        .into_iter()
+        .find_map(|city| {
+            end.structure_bounds(StructureType::End_City, city.pos)
+                .expect("the generator is in the end")
+                .map(|bounds| (city, bounds))
+        })
+        .expect("there is an end city in the outer end");
+    dbg!(city.pos, bounds);
+    assert!(bounds.contains(city.pos.x + 8, bounds.min_y, city.pos.z + 8));
+    assert!(bounds.min_y >= 60 && bounds.max_y > bounds.min_y + 8);
+}
+
+#[test]
+fn strongholds_are_filtered_by_ring() {
+    for version in [MCVersion::MC_1_18_2, MCVersion::MC_1_20_6] {
+        let generator = Generator::new(
+            version,
+            -5134222539607995087,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        let all: Vec<BlockPosition> = generator.strongholds().take(19).collect();
+
+        // The inner rings hold 3, 6 and 10 strongholds
+        let mut start = 0;
+        for (ring, size) in [3, 6, 10].into_iter().enumerate() {
+            let ring = ring as u32;
+            let strongholds: Vec<BlockPosition> = generator.strongholds().ring(ring).collect();
+            assert_eq!(
+                strongholds,
+                all[start..start + size],
+                "{version:?} ring {ring}"
+            );
+
+            let distances = ring_distances(version, ring);
+            for pos in &strongholds {
+                let distance = f64::from(pos.x).hypot(f64::from(pos.z));
+                assert!(distance > distances.start() - 200.0 && distance < distances.end() + 200.0);
+            }
+
+            start += size;
+        }
+        let mut iter = generator.strongholds();
+        assert_eq!(iter.next_ring(), Some(0));
+        iter.nth(2);
+        assert_eq!(iter.next_ring(), Some(1));
+
+        let near: Vec<BlockPosition> = generator.strongholds().within_distance(6000.0).collect();
+        let expected: Vec<BlockPosition> = all
+            .iter()
+            .copied()
+            .filter(|pos| f64::from(pos.x).hypot(f64::from(pos.z)) <= 6000.0)
+            .collect();
+        assert_eq!(near, expected);
+    }
+
+    let legacy = Generator::new(
+        MCVersion::MC_1_8_9,
+        -5134222539607995087,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(legacy.strongholds().ring(0).count(), 3);
+    assert_eq!(legacy.strongholds().ring(1).count(), 0);
+    assert_eq!(legacy.strongholds().within_distance(200.0).count(), 0);
+}
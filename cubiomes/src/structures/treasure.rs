@@ -0,0 +1,120 @@
+//! Module containing the positions of buried treasure
+//!
+//! Every chunk has a treasure attempt at a fixed offset, which succeeds for
+//! one in a hundred chunks. The attempt then only generates in beaches, see
+//! [`Generator::buried_treasures_within()`].
+
+use super::finder::{sort_hits, StructureHit};
+use crate::enums::{MCVersion, StructureType};
+use crate::generator::{spiral, BlockPosition, Generator, Scale};
+use crate::rng::{region_seed, JavaRandom};
+
+/// The salt of the treasure attempts
+const TREASURE_SALT: i64 = 10387320;
+
+/// The chance of a chunk to have a treasure attempt
+const TREASURE_CHANCE: f32 = 0.01;
+
+/// The offset of the chest from the start of its chunk
+const CHEST_OFFSET: i32 = 9;
+
+/// Gets the buried treasure attempt of a chunk, if the chunk has one
+///
+/// The position is the block of the chest, which is always at 9, 9 in its
+/// chunk. The attempt still has to be in a beach to generate, which
+/// [`Generator::verify_structure_generation_attempt()`] checks. The chunk is
+/// given in chunk coordinates.
+///
+/// Returns [`None`] if the chunk has no attempt, or before 1.13, which has no
+/// buried treasure.
+///
+/// # Examples
+/// ```
+/// use cubiomes::enums::MCVersion;
+/// use cubiomes::structures::treasure::treasure_attempt;
+///
+/// if let Some(pos) = treasure_attempt(MCVersion::MC_1_20_6, -5134222539607995087, 40, -3) {
+///     assert_eq!(pos.x, 40 * 16 + 9);
+/// }
+/// ```
+#[must_use]
+pub fn treasure_attempt(
+    version: MCVersion,
+    world_seed: i64,
+    chunk_x: i32,
+    chunk_z: i32,
+) -> Option<BlockPosition> {
+    if version < MCVersion::MC_1_13_2 {
+        return None;
+    }
+
+    let mut rng = JavaRandom::new(region_seed(world_seed, chunk_x, chunk_z, TREASURE_SALT));
+    if rng.next_float() >= TREASURE_CHANCE {
+        return None;
+    }
+
+    Some(BlockPosition::new(
+        chunk_x.wrapping_mul(16) + CHEST_OFFSET,
+        chunk_z.wrapping_mul(16) + CHEST_OFFSET,
+    ))
+}
+
+impl Generator {
+    /// Finds every buried treasure within a radius around a position
+    ///
+    /// The attempts of the chunks are calculated without cubiomes, and only
+    /// the successful ones are checked for a beach. The hits are the blocks
+    /// of the chests, sorted by their distance from `origin` with the closest
+    /// treasure first. Returns an empty [`Vec`] before 1.13.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// let treasures: Vec<_> = generator
+    ///     .buried_treasures_within(BlockPosition::new(0, 0), 1000)
+    ///     .into_iter()
+    ///     .map(|treasure| treasure.pos)
+    ///     .collect();
+    ///
+    /// assert_eq!(treasures, [BlockPosition::new(-487, 9), BlockPosition::new(169, 937)]);
+    /// ```
+    pub fn buried_treasures_within(
+        &mut self,
+        origin: BlockPosition,
+        radius: u32,
+    ) -> Vec<StructureHit> {
+        let (version, seed) = (self.minecraft_version(), self.seed());
+        let chunk = Scale::Chunk as i32;
+        let (chunk_x, chunk_z) = origin.scale_by_num(chunk);
+
+        let attempts: Vec<BlockPosition> = spiral(
+            BlockPosition::new(chunk_x, chunk_z),
+            1,
+            radius / chunk.unsigned_abs() + 1,
+        )
+        .filter_map(|cell| treasure_attempt(version, seed, cell.pos.x, cell.pos.z))
+        .filter(|&pos| origin.distance(pos) <= f64::from(radius))
+        .collect();
+
+        let mut hits: Vec<StructureHit> = attempts
+            .into_iter()
+            .filter(|&pos| {
+                self.verify_structure_generation_attempt(pos, StructureType::Treasure)
+                    .unwrap_or(false)
+            })
+            .map(|pos| StructureHit::new(pos, StructureType::Treasure, origin))
+            .collect();
+
+        sort_hits(&mut hits);
+        hits
+    }
+}
@@ -0,0 +1,154 @@
+//! Module containing the variants of structures
+//!
+//! Cubiomes knows the start piece, rotation and a few special kinds of some
+//! structures, like abandoned villages, giant ruined portals or igloos with a
+//! basement. [`Generator::structure_variant()`] gets these for a structure,
+//! and the searches of [`super::finder`] include them in every
+//! [`super::finder::StructureHit`].
+
+use super::Rotation;
+use crate::enums::{BiomeID, MCVersion, StructureType};
+use crate::generator::{BlockPosition, Generator};
+use std::mem::MaybeUninit;
+
+/// The variant of a structure, see [`Generator::structure_variant()`]
+///
+/// Only the fields cubiomes knows for the type of the structure are set, the
+/// others are `false`, 0 or [`None`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StructureVariant {
+    /// The rotation of the start piece
+    pub rotation: Rotation,
+    /// Whether the start piece is mirrored, only set for ruined portals
+    pub mirrored: bool,
+    /// The biome the style of the structure is picked from, like the
+    /// biome of the houses of a village
+    pub biome: Option<BiomeID>,
+    /// The index of the start piece in the pool of the structure
+    pub start: Option<u8>,
+    /// The size of a geode, or the amount of ladder pieces of an igloo
+    pub size: u8,
+    /// Whether a village is a zombie village
+    pub abandoned: bool,
+    /// Whether a ruined portal is a giant portal
+    pub giant: bool,
+    /// Whether a ruined portal is underground
+    pub underground: bool,
+    /// Whether a ruined portal has an air pocket
+    pub airpocket: bool,
+    /// Whether an igloo has a basement
+    pub basement: bool,
+    /// Whether a geode has a crack
+    pub cracked: bool,
+}
+
+impl Generator {
+    /// Gets the variant of a structure at its generation attempt
+    ///
+    /// Villages and ruined portals depend on the biome at the structure,
+    /// which is generated for them. The position isn't verified, see
+    /// [`Self::verify_structure_generation_attempt()`].
+    ///
+    /// Returns [`None`] for structures without variants in cubiomes, like
+    /// strongholds or monuments, and for villages in biomes which can't have
+    /// villages.
+    ///
+    /// # Examples
+    /// ```
+    /// use cubiomes::enums::{Dimension, MCVersion, StructureType};
+    /// use cubiomes::generator::{BlockPosition, Generator, GeneratorFlags};
+    ///
+    /// let mut generator = Generator::new(
+    ///     MCVersion::MC_1_20_6,
+    ///     -5134222539607995087,
+    ///     Dimension::DIM_OVERWORLD,
+    ///     GeneratorFlags::empty(),
+    /// );
+    ///
+    /// for hit in generator.structures_within(StructureType::Village, BlockPosition::new(0, 0), 2000) {
+    ///     let variant = generator.structure_variant(StructureType::Village, hit.pos);
+    ///     assert_eq!(variant, hit.variant);
+    ///
+    ///     if variant.is_some_and(|variant| variant.abandoned) {
+    ///         println!("Zombie village at {:?}", hit.pos);
+    ///     }
+    /// }
+    /// ```
+    pub fn structure_variant(
+        &mut self,
+        structure: StructureType,
+        pos: BlockPosition,
+    ) -> Option<StructureVariant> {
+        let biome = match structure {
+            StructureType::Village => self.village_biome(pos)?,
+            StructureType::Ruined_Portal | StructureType::Ruined_Portal_N => {
+                self.feature_biome(pos).ok()? as i32
+            }
+            _ => -1,
+        };
+
+        structure_variant(structure, self.minecraft_version(), self.seed(), pos, biome)
+    }
+}
+
+/// Gets the variant of a structure with the biome cubiomes picks it with
+///
+/// The biome is -1 for structures which don't depend on it.
+pub(super) fn structure_variant(
+    structure: StructureType,
+    version: MCVersion,
+    seed: i64,
+    pos: BlockPosition,
+    biome: i32,
+) -> Option<StructureVariant> {
+    let mut variant: MaybeUninit<cubiomes_sys::StructureVariant> = MaybeUninit::uninit();
+
+    // SAFETY: ffi function is called correctly, and getVariant clears the
+    // variant before filling it in
+    let (found, variant) = unsafe {
+        let found = cubiomes_sys::getVariant(
+            variant.as_mut_ptr(),
+            structure as i32,
+            version as i32,
+            seed.cast_unsigned(),
+            pos.x,
+            pos.z,
+            biome,
+        );
+        (found, variant.assume_init())
+    };
+
+    if found == 0 {
+        return None;
+    }
+
+    // Except for ruined portals, cubiomes splits the rotation into a quarter
+    // turn and a half turn, the latter stored as the mirror
+    let portal = matches!(
+        structure,
+        StructureType::Ruined_Portal | StructureType::Ruined_Portal_N
+    );
+    let (rotation, mirrored) = if portal {
+        (i32::from(variant.rotation), variant.mirror != 0)
+    } else {
+        (
+            i32::from(variant.rotation) + 2 * i32::from(variant.mirror),
+            false,
+        )
+    };
+
+    Some(StructureVariant {
+        rotation: Rotation::from_index(rotation),
+        mirrored,
+        biome: BiomeID::from_id(i32::from(variant.biome)).filter(|_| variant.biome >= 0),
+        // The start is -1 when there is none
+        start: (variant.start != u8::MAX).then_some(variant.start),
+        size: variant.size,
+        abandoned: variant.abandoned() != 0,
+        giant: variant.giant() != 0,
+        underground: variant.underground() != 0,
+        airpocket: variant.airpocket() != 0,
+        basement: variant.basement() != 0,
+        cracked: variant.cracked() != 0,
+    })
+}
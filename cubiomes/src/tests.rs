@@ -1,13 +1,15 @@
 use crate::enums::MCVersion;
-use crate::generator::{Cache, error::GeneratorError, Generator, GeneratorFlags, Range, Scale};
+use crate::generator::{
+    error::GeneratorError, BlockPosition, Cache, Generator, GeneratorFlags, Range, Scale,
+};
+use crate::render::{Image, Map, Overlay, RenderOptions};
 
-use std::ffi::CStr;
 use cubiomes_sys::enums::{self, Dimension};
-
+use std::ffi::CStr;
 
 fn init_generator() -> Generator {
     let seed: i64 = -4804349703814383506;
-    let mc_version = MCVersion::MC_1_21_WD;
+    let mc_version = MCVersion::MC_1_20_6;
 
     // SAFETY: seed is immediatly applied
     unsafe {
@@ -21,7 +23,7 @@ fn init_generator() -> Generator {
 #[test]
 fn biome_to_str_sanity() {
     let biome = enums::BiomeID::badlands;
-    let version = MCVersion::MC_1_21_WD;
+    let version = MCVersion::MC_1_20_6;
 
     let _str;
 
@@ -37,7 +39,7 @@ fn simple_biome_test() -> Result<(), GeneratorError> {
     let generator = init_generator();
 
     assert_eq!(
-        generator.get_biome_at(700, 256, -2300)?,
+        generator.get_biome_at((700, 256, -2300))?,
         enums::BiomeID::mushroomIsland
     );
     Ok(())
@@ -99,3 +101,599 @@ fn test_range_outside_bounds() {
     let range = Range { ..SOME_RANGE };
     assert!(range.is_inside(32, 32));
 }
+
+#[test]
+fn range_rounds_negative_coordinates_down() {
+    let range = Range::plane(Scale::Quad, -4, -4, 8, 8, 64);
+
+    assert_eq!(range.global_to_local_coord(-1, 0), Some((3, 4)));
+    assert_eq!(range.global_to_local_coord(-16, -16), Some((0, 0)));
+    assert_eq!(range.global_to_local_coord(15, 15), Some((7, 7)));
+    assert_eq!(range.global_to_local_coord(-17, 0), None);
+    assert_eq!(range.global_to_local_coord(16, 0), None);
+}
+
+#[test]
+fn image_writes_ppm() {
+    let mut image = Image::new(2, 3);
+    image.set_pixel(1, 2, [1, 2, 3]);
+    image.set_pixel(2, 0, [4, 5, 6]);
+
+    let mut ppm = Vec::new();
+    image
+        .write_ppm(&mut ppm)
+        .expect("writing to a vec can't fail");
+
+    assert_eq!(&ppm[..11], b"P6\n2 3\n255\n");
+    assert_eq!(ppm.len(), 11 + 2 * 3 * 3);
+    assert_eq!(&ppm[ppm.len() - 3..], &[1, 2, 3]);
+}
+
+#[test]
+fn ascii_map_has_two_rows_per_line_and_a_legend() {
+    let generator = init_generator();
+    let mut cache = Cache::new(&generator, Range::plane(Scale::Chunk, -8, -8, 12, 7, 16));
+    cache.fill_cache().expect("failed to fill cache");
+
+    let mut map = Vec::new();
+    crate::render::ascii(&cache, &mut map).expect("writing to a vec can't fail");
+    let map = String::from_utf8(map).expect("the map is text");
+
+    let (blocks, legend) = map.split_once("\n\n").expect("the legend follows the map");
+    let lines: Vec<&str> = blocks.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines
+        .iter()
+        .all(|line| line.matches('\u{2580}').count() == 12));
+    // The last row has no row below it, so no background
+    assert!(!lines[3].contains("\x1b[48;2;"));
+
+    let stats = cache.histogram();
+    assert_eq!(legend.lines().count(), stats.len());
+    for (biome, _) in stats {
+        assert!(legend.contains(&format!(" {biome:?} ")));
+    }
+}
+
+#[test]
+fn overlays_are_drawn_on_the_right_pixels() {
+    let generator = init_generator();
+    let mut cache = Cache::new(&generator, Range::plane(Scale::Quad, -16, -16, 32, 32, 64));
+    cache.fill_cache().expect("failed to fill cache");
+    let biomes = Map::from_cache(&cache);
+
+    let pos = BlockPosition::new(-3, 21);
+    assert_eq!(biomes.pixel_of(pos), Some((15, 21)));
+    assert_eq!(biomes.block_at(15, 21), BlockPosition::new(-4, 20));
+    assert_eq!(biomes.pixel_of(BlockPosition::new(64, 0)), None);
+
+    let map = biomes.clone().with_overlays(&[
+        Overlay::chunk_grid([1, 1, 1]),
+        Overlay::Structures {
+            positions: vec![pos],
+            color: [2, 2, 2],
+        },
+    ]);
+    let image = map.image();
+    for i in 0..32 {
+        // Chunks start every 4 pixels, the first one at the left edge
+        assert_eq!(image.pixel(i, 1) == Some([1, 1, 1]), i % 4 == 0);
+    }
+    assert_eq!(image.pixel(15, 21), Some([2, 2, 2]));
+    assert_eq!(image.pixel(18, 21), Some([0, 0, 0]));
+
+    let slime = biomes.clone().with_overlays(&[Overlay::SlimeChunks {
+        seed: generator.seed(),
+        color: [255, 0, 255],
+        opacity: 1.0,
+    }]);
+    for (x, y) in [(0, 0), (13, 7), (31, 31)] {
+        let shaded = slime.image().pixel(x, y) == Some([255, 0, 255]);
+        assert_eq!(shaded, generator.is_slime_chunk(slime.block_at(x, y)));
+    }
+}
+
+#[test]
+fn exported_maps_have_a_legend_and_a_scale_bar() {
+    let generator = init_generator();
+    let mut cache = Cache::new(
+        &generator,
+        Range::plane(Scale::Quad, -64, -64, 128, 128, 64),
+    );
+    cache.fill_cache().expect("failed to fill cache");
+    let map = Map::from_cache(&cache);
+
+    let plain = map.export(&RenderOptions::default());
+    assert_eq!(&plain, map.image());
+
+    let biomes = cache.histogram().len() as u32;
+    assert_eq!(map.legend_biomes().len() as u32, biomes);
+
+    let image = map.export(&RenderOptions {
+        legend: true,
+        scale_bar: true,
+        text_scale: 1,
+    });
+    assert!(image.width() > 128);
+    // A line for every biome after the padding of the legend
+    assert_eq!(image.height(), 128.max(4 + 7 * biomes));
+    for y in 0..128 {
+        for x in 0..128 {
+            // The scale bar covers the lower left corner only
+            if x >= 64 || y < 100 {
+                assert_eq!(image.pixel(x, y), map.image().pixel(x, y));
+            }
+        }
+    }
+
+    // A quarter of the map is 128 blocks, so the bar is 100 blocks long
+    let bar_y = 128 - 4 - 2 - 2;
+    let bar: Vec<u32> = (0..64)
+        .filter(|&x| image.pixel(x, bar_y) == Some([0, 0, 0]))
+        .collect();
+    assert_eq!(bar.len(), 25);
+    assert!(bar.windows(2).all(|pair| pair[1] == pair[0] + 1));
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn image_writes_png() {
+    let mut image = Image::new(4, 4);
+    image.set_pixel(1, 2, [1, 2, 3]);
+
+    let mut png = Vec::new();
+    image
+        .write_png(&mut png)
+        .expect("writing to a vec can't fail");
+
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+}
+
+#[cfg(feature = "viewer")]
+#[test]
+fn viewer_pans_and_zooms_without_a_window() {
+    use crate::generator::BlockPosition;
+    use crate::render::Viewer;
+
+    let generator = init_generator();
+    let packed = |image: Image| -> Vec<u32> {
+        (0..image.height())
+            .flat_map(|y| (0..image.width()).map(move |x| (x, y)))
+            .filter_map(|(x, y)| image.pixel(x, y))
+            .map(|[r, g, b]| u32::from_be_bytes([0, r, g, b]))
+            .collect()
+    };
+    let fresh = |range: Range| {
+        let mut cache = Cache::new(&generator, range);
+        cache.fill_cache().expect("failed to fill cache");
+        packed(cache.to_image())
+    };
+
+    let mut viewer = Viewer::new(&generator, 64, 48).with_center(BlockPosition::new(1000, -300));
+    assert_eq!(
+        viewer.range(),
+        &Range::plane(Scale::Quad, 218, -99, 64, 48, 64)
+    );
+    assert!(
+        viewer.biome_at_pixel(0, 0).is_err(),
+        "nothing is rendered yet"
+    );
+
+    viewer.render().expect("failed to render");
+    assert_eq!(viewer.frame(), fresh(*viewer.range()));
+
+    // Panning only generates the new cells, but shows the same biomes
+    viewer.pan(5, -3).expect("failed to pan");
+    assert_eq!(viewer.frame(), fresh(*viewer.range()));
+    assert_eq!(viewer.center(), BlockPosition::new(1020, -312));
+
+    assert_eq!(viewer.zoom_in(), Ok(true));
+    assert_eq!(viewer.scale(), Scale::Block);
+    assert_eq!(viewer.center(), BlockPosition::new(1020, -312));
+    assert_eq!(viewer.frame(), fresh(*viewer.range()));
+    assert_eq!(viewer.zoom_in(), Ok(false));
+
+    viewer.zoom(Scale::HalfRegion).expect("failed to zoom");
+    assert_eq!(viewer.zoom_out(), Ok(false));
+    assert_eq!(viewer.range().y, 16);
+}
+
+#[test]
+fn map_tiles_match_caches() {
+    use crate::generator::BlockPosition;
+    use crate::render::{TileId, TileRenderer, TILE_PIXELS};
+
+    let generator = init_generator();
+    let renderer = TileRenderer::new(&generator, 64);
+
+    // Zoom 6 is one pixel per quad, so the tile is the same as a quad cache
+    let tile = TileId {
+        zoom: 6,
+        x: -1,
+        y: 2,
+    };
+    assert_eq!(tile.origin(), BlockPosition::new(-1024, 2048));
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: -256,
+            z: 512,
+            size_x: TILE_PIXELS,
+            size_z: TILE_PIXELS,
+            y: 16,
+            size_y: 0,
+        },
+    );
+    cache.fill_cache().expect("failed to fill cache");
+
+    assert_eq!(renderer.render(tile), Ok(cache.to_image()));
+
+    // Zoom 5 is between scales, so every other quad is used
+    let half = renderer
+        .render(TileId {
+            zoom: 5,
+            x: -1,
+            y: 1,
+        })
+        .expect("failed to render tile");
+
+    for (x, z) in [(128, 0), (200, 100), (255, 127)] {
+        assert_eq!(
+            half.pixel(x, z),
+            cache.to_image().pixel((x - 128) * 2, z * 2)
+        );
+    }
+
+    let tiles: Vec<_> = TileId::covering(
+        0,
+        BlockPosition::new(70000, 10),
+        BlockPosition::new(-10, -10),
+    )
+    .collect();
+    assert_eq!(tiles.len(), 6);
+    assert_eq!(
+        tiles[0],
+        TileId {
+            zoom: 0,
+            x: -1,
+            y: -1
+        }
+    );
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn map_tiles_are_saved() {
+    use crate::generator::BlockPosition;
+    use crate::progress::CancellationToken;
+    use crate::render::TileRenderer;
+
+    let generator = init_generator();
+    let renderer = TileRenderer::new(&generator, 64);
+    let directory = std::env::temp_dir().join(format!("cubiomes-tiles-{}", std::process::id()));
+
+    let saved = renderer
+        .save_tiles(
+            0..=1,
+            BlockPosition::new(-10, -10),
+            BlockPosition::new(10, 10),
+            &directory,
+            &CancellationToken::new(),
+        )
+        .expect("failed to save tiles");
+
+    assert_eq!(saved, 8);
+    assert!(directory.join("0").join("-1").join("-1.png").exists());
+    assert!(directory.join("1").join("0").join("0.png").exists());
+
+    std::fs::remove_dir_all(directory).expect("failed to remove tiles");
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_count_generation() {
+    use crate::metrics;
+    use crate::progress::CancellationToken;
+
+    let generator = init_generator();
+    let before = metrics::snapshot();
+
+    let mut cache = Cache::new(
+        &generator,
+        Range {
+            scale: Scale::Quad,
+            x: 0,
+            z: 0,
+            size_x: 300,
+            size_z: 20,
+            y: 16,
+            size_y: 0,
+        },
+    );
+    cache
+        .fill_cache_with_progress(&CancellationToken::new(), |_| ())
+        .expect("failed to fill cache");
+
+    let mut search = crate::search::SearchDefinition::new(
+        MCVersion::MC_1_20_6,
+        crate::search::SeedRange::new(0, 10),
+    );
+    search.criteria.push(crate::search::Criterion::BiomeAt {
+        biome: enums::BiomeID::ocean,
+        x: 0,
+        z: 0,
+        y: crate::generator::YLevel::Absolute(64),
+    });
+    search.run().expect("search failed");
+
+    // Other tests generate at the same time, so only lower bounds hold
+    let used = metrics::snapshot().since(&before);
+    dbg!(&used);
+
+    assert!(used.caches_filled >= 2);
+    assert!(used.cells_generated >= 300 * 20);
+    assert!(used.tiles_completed >= 2);
+    assert!(used.seeds_evaluated >= 10);
+    assert!(used.average_latency().is_some());
+    assert_eq!(
+        metrics::GenerationMetrics::default().average_latency(),
+        None
+    );
+}
+
+#[test]
+fn versions_are_iterated_in_order() {
+    let versions: Vec<MCVersion> = MCVersion::iter().collect();
+
+    dbg!(&versions);
+
+    assert_eq!(MCVersion::oldest(), MCVersion::MC_B1_7);
+    assert_eq!(versions.first(), Some(&MCVersion::oldest()));
+    assert_eq!(versions.last(), Some(&MCVersion::latest()));
+    assert!(versions.windows(2).all(|w| w[0] < w[1]));
+    assert!(!versions.contains(&MCVersion::MC_UNDEF));
+    assert!(versions.contains(&MCVersion::MC_1_16_1));
+
+    #[cfg(feature = "unstable-versions")]
+    assert_eq!(MCVersion::latest(), MCVersion::MC_NEWEST);
+    #[cfg(not(feature = "unstable-versions"))]
+    assert_eq!(MCVersion::latest(), MCVersion::MC_1_20_6);
+
+    // Every version can set up a generator
+    for version in versions {
+        let generator = Generator::new(
+            version,
+            1,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        assert_eq!(generator.minecraft_version(), version);
+    }
+}
+
+#[test]
+fn biome_table_matches_from_primitive() {
+    use cubiomes_sys::num_traits::FromPrimitive;
+
+    for id in (-300..300).chain([i32::MIN, i32::MAX]) {
+        assert_eq!(
+            enums::BiomeID::from_id(id),
+            enums::BiomeID::from_i32(id),
+            "{id}"
+        );
+    }
+}
+
+#[test]
+fn biome_predicates_follow_cubiomes() {
+    use enums::BiomeID;
+
+    assert!(BiomeID::warm_ocean.is_shallow_ocean());
+    assert!(BiomeID::deep_frozen_ocean.is_deep_ocean());
+    assert!(BiomeID::deep_frozen_ocean.is_oceanic());
+    assert!(!BiomeID::river.is_oceanic());
+    assert!(!BiomeID::none.is_oceanic());
+
+    // Before 1.16 the badlands plateaus are in the category of the other
+    // badlands, but are only similar to each other
+    assert!(BiomeID::badlands.is_similar(BiomeID::badlands_plateau, MCVersion::MC_1_15));
+    assert!(!BiomeID::badlands_plateau.is_similar(BiomeID::badlands, MCVersion::MC_1_15));
+    assert!(!BiomeID::badlands.is_similar(BiomeID::badlands_plateau, MCVersion::MC_1_16_5));
+
+    assert_eq!(
+        BiomeID::birch_forest.mutated(MCVersion::MC_1_9_4),
+        Some(BiomeID::tall_birch_hills)
+    );
+    assert_eq!(
+        BiomeID::birch_forest.mutated(MCVersion::MC_1_12_2),
+        Some(BiomeID::tall_birch_forest)
+    );
+    assert_eq!(
+        BiomeID::birch_forest_hills.mutated(MCVersion::MC_1_10),
+        None
+    );
+}
+
+#[test]
+fn available_structures_have_a_config() {
+    use crate::structures::config::StructureConfig;
+    use enums::StructureType;
+
+    for version in MCVersion::iter() {
+        for dimension in [
+            Dimension::DIM_OVERWORLD,
+            Dimension::DIM_NETHER,
+            Dimension::DIM_END,
+        ] {
+            for structure in version.structures(dimension) {
+                assert!(
+                    StructureConfig::of(structure, version).is_ok(),
+                    "{structure:?} in {version:?}"
+                );
+            }
+        }
+    }
+
+    let nether = MCVersion::MC_1_20_6.structures(Dimension::DIM_NETHER);
+    assert_eq!(
+        nether,
+        [
+            StructureType::Ruined_Portal_N,
+            StructureType::Fortress,
+            StructureType::Bastion
+        ]
+    );
+    assert!(!StructureType::Feature.available_in(MCVersion::MC_1_12_2, Dimension::DIM_OVERWORLD));
+}
+
+#[test]
+fn generated_biomes_have_a_rarity() {
+    use enums::{BiomeID, BiomeRarity};
+
+    assert_eq!(
+        BiomeID::plains.rarity(MCVersion::MC_1_20_6),
+        Some(BiomeRarity::Common)
+    );
+    assert_eq!(
+        BiomeID::mushroom_fields.rarity(MCVersion::MC_1_20_6),
+        Some(BiomeRarity::VeryRare)
+    );
+    assert_eq!(BiomeID::cherry_grove.rarity(MCVersion::MC_1_19_2), None);
+    assert_eq!(BiomeID::shrubland.rarity(MCVersion::MC_1_16_5), None);
+    assert_eq!(
+        BiomeID::shrubland.rarity(MCVersion::MC_B1_7),
+        Some(BiomeRarity::Common)
+    );
+
+    for version in [
+        MCVersion::MC_1_6_4,
+        MCVersion::MC_1_16_5,
+        MCVersion::MC_1_20_6,
+    ] {
+        let generator = Generator::new(
+            version,
+            1,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty(),
+        );
+        let mut cache = Cache::new(
+            &generator,
+            Range::plane(Scale::Quad, -256, -256, 128, 128, 64),
+        );
+        cache.fill_cache().expect("failed to generate the biomes");
+
+        for &id in cache.as_vec() {
+            let biome = BiomeID::from_id(id).expect("cubiomes generated an unknown biome");
+            assert!(biome.rarity(version).is_some(), "{biome:?} in {version:?}");
+        }
+    }
+}
+
+#[test]
+fn bad_input_returns_errors_instead_of_panicking() {
+    use crate::generator::{spiral, BlockPosition};
+    use crate::render::{TileId, MAX_ZOOM};
+    use crate::rng::JavaRandom;
+    use crate::structures::{
+        is_viable_structure_biome, strongholds::first_stronghold, StructureGenerationError,
+    };
+    use enums::{BiomeID, StructureType};
+
+    assert_eq!(
+        Generator::try_new(
+            MCVersion::MC_UNDEF,
+            1,
+            Dimension::DIM_OVERWORLD,
+            GeneratorFlags::empty()
+        ),
+        Err(GeneratorError::UnsupportedVersion(MCVersion::MC_UNDEF))
+    );
+    assert_eq!(
+        Generator::try_new(
+            MCVersion::MC_1_20_6,
+            1,
+            Dimension::DIM_UNDEF,
+            GeneratorFlags::empty()
+        ),
+        Err(GeneratorError::UnsupportedDimension(Dimension::DIM_UNDEF))
+    );
+
+    let undefined = Generator::new(
+        MCVersion::MC_UNDEF,
+        1,
+        Dimension::DIM_OVERWORLD,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        undefined.get_biome_at((0, 64, 0)),
+        Err(GeneratorError::UnsupportedVersion(MCVersion::MC_UNDEF))
+    );
+
+    let mut generator = init_generator();
+    assert_eq!(
+        generator
+            .biome_column(0, 0, generator.world_limits().y_range(), 0)
+            .map(|_| ()),
+        Err(GeneratorError::ZeroStep)
+    );
+    assert_eq!(
+        generator.nether_structure(0, 0).map(|_| ()),
+        Err(StructureGenerationError::UnsupportedDimension(
+            Dimension::DIM_OVERWORLD
+        ))
+    );
+    assert!(matches!(
+        first_stronghold(MCVersion::MC_1_20_6, 1).refine(&generator),
+        Err(GeneratorError::MismatchedGenerator(_))
+    ));
+    assert_eq!(spiral(BlockPosition::new(0, 0), 0, 100).count(), 1);
+
+    let empty = Range {
+        size_x: 0,
+        ..Range::plane(Scale::Quad, 0, 0, 4, 4, 64)
+    };
+    let mut empty_cache = Cache::new(&generator, empty);
+    assert!(empty_cache.fill_cache().is_err());
+    assert!(!format!("{empty_cache:?}").is_empty());
+    let mut map = Vec::new();
+    assert!(crate::render::ascii(&empty_cache, &mut map).is_ok());
+
+    // Cubiomes exits the process for structures it has no biome check for
+    assert!(!is_viable_structure_biome(
+        StructureType::Geode,
+        MCVersion::MC_1_20_6,
+        BiomeID::plains
+    ));
+    let mut nether = Generator::new(
+        MCVersion::MC_1_20_6,
+        1,
+        Dimension::DIM_NETHER,
+        GeneratorFlags::empty(),
+    );
+    assert_eq!(
+        nether.verify_structure_generation_attempt(BlockPosition::new(0, 0), StructureType::Geode),
+        Ok(false)
+    );
+
+    let tile = TileId {
+        zoom: MAX_ZOOM + 4,
+        x: 0,
+        y: 0,
+    };
+    assert_eq!(tile.blocks_per_pixel(), 1);
+    assert_eq!(JavaRandom::new(1).next_int(0), 0);
+}
+
+#[test]
+fn cubiomes_version_is_reported() {
+    let version = crate::cubiomes_version();
+
+    assert!(!version.revision.is_empty());
+    // The tests run against the vendored cubiomes unless they are pointed to
+    // another one
+    if std::env::var_os("CUBIOMES_SRC_DIR").is_none() {
+        assert!(version.vendored);
+    }
+}
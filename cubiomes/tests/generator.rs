@@ -27,7 +27,7 @@ fn test_random_points() {
 
     for _ in 0..POINT_AMOUNT {
         let generator = Generator::new(
-            MCVersion::MC_1_21_WD,
+            MCVersion::MC_1_20_6,
             rng.random(),
             Dimension::DIM_OVERWORLD,
             GeneratorFlags::empty(),
@@ -35,7 +35,7 @@ fn test_random_points() {
 
         let (x, z) = get_random_point(&mut rng);
 
-        let biome_get_biome_at = generator.get_biome_at(x, 320, z).unwrap_or_else(|_| {
+        let biome_get_biome_at = generator.get_biome_at((x, 320, z)).unwrap_or_else(|_| {
             panic!(
                 "Failed to generate biome at x: {}, y: {} z: {}",
                 x, TEST_Y, z